@@ -32,7 +32,7 @@ use vulkano::instance::{Instance, PhysicalDevice};
 use vulkano::swapchain;
 use vulkano::swapchain::{
     AcquireError, ColorSpace, FullscreenExclusive, PresentMode, SurfaceTransform, Swapchain,
-    SwapchainCreationError,
+    SwapchainCreationError, SwapchainOptions,
 };
 use vulkano::sync;
 use vulkano::sync::{FlushError, GpuFuture};
@@ -101,8 +101,10 @@ fn main() {
             alpha,
             PresentMode::Fifo,
             FullscreenExclusive::Default,
+            None,
             true,
             ColorSpace::SrgbNonLinear,
+            SwapchainOptions::default(),
         )
         .unwrap();
         let images = images
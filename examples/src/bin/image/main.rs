@@ -23,7 +23,7 @@ use vulkano::sampler::{Filter, MipmapMode, Sampler, SamplerAddressMode};
 use vulkano::swapchain;
 use vulkano::swapchain::{
     AcquireError, ColorSpace, FullscreenExclusive, PresentMode, SurfaceTransform, Swapchain,
-    SwapchainCreationError,
+    SwapchainCreationError, SwapchainOptions,
 };
 use vulkano::sync;
 use vulkano::sync::{FlushError, GpuFuture};
@@ -93,8 +93,10 @@ fn main() {
             alpha,
             PresentMode::Fifo,
             FullscreenExclusive::Default,
+            None,
             true,
             ColorSpace::SrgbNonLinear,
+            SwapchainOptions::default(),
         )
         .unwrap()
     };
@@ -212,12 +214,8 @@ fn main() {
     );
 
     let mut dynamic_state = DynamicState {
-        line_width: None,
         viewports: None,
-        scissors: None,
-        compare_mask: None,
-        write_mask: None,
-        reference: None,
+        ..DynamicState::none()
     };
     let mut framebuffers =
         window_size_dependent_setup(&images, render_pass.clone(), &mut dynamic_state);
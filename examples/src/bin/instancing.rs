@@ -31,7 +31,7 @@ use vulkano::pipeline::GraphicsPipeline;
 use vulkano::swapchain;
 use vulkano::swapchain::{
     AcquireError, ColorSpace, FullscreenExclusive, PresentMode, SurfaceTransform, Swapchain,
-    SwapchainCreationError,
+    SwapchainCreationError, SwapchainOptions,
 };
 use vulkano::sync;
 use vulkano::sync::{FlushError, GpuFuture};
@@ -117,8 +117,10 @@ fn main() {
             alpha,
             PresentMode::Fifo,
             FullscreenExclusive::Default,
+            None,
             true,
             ColorSpace::SrgbNonLinear,
+            SwapchainOptions::default(),
         )
         .unwrap()
     };
@@ -250,12 +252,8 @@ fn main() {
     );
 
     let mut dynamic_state = DynamicState {
-        line_width: None,
         viewports: None,
-        scissors: None,
-        compare_mask: None,
-        write_mask: None,
-        reference: None,
+        ..DynamicState::none()
     };
     let mut framebuffers =
         window_size_dependent_setup(&images, render_pass.clone(), &mut dynamic_state);
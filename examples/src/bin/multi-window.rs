@@ -29,7 +29,7 @@ use vulkano::swapchain;
 use vulkano::swapchain::Surface;
 use vulkano::swapchain::{
     AcquireError, ColorSpace, FullscreenExclusive, PresentMode, SurfaceTransform, Swapchain,
-    SwapchainCreationError,
+    SwapchainCreationError, SwapchainOptions,
 };
 use vulkano::sync;
 use vulkano::sync::{FlushError, GpuFuture};
@@ -119,8 +119,10 @@ fn main() {
             alpha,
             PresentMode::Fifo,
             FullscreenExclusive::Default,
+            None,
             true,
             ColorSpace::SrgbNonLinear,
+            SwapchainOptions::default(),
         )
         .unwrap()
     };
@@ -218,12 +220,8 @@ fn main() {
     );
 
     let mut dynamic_state = DynamicState {
-        line_width: None,
         viewports: None,
-        scissors: None,
-        compare_mask: None,
-        write_mask: None,
-        reference: None,
+        ..DynamicState::none()
     };
 
     window_surfaces.insert(
@@ -296,8 +294,10 @@ fn main() {
                     alpha,
                     PresentMode::Fifo,
                     FullscreenExclusive::Default,
+                    None,
                     true,
                     ColorSpace::SrgbNonLinear,
+                    SwapchainOptions::default(),
                 )
                 .unwrap()
             };
@@ -91,7 +91,7 @@ fn main() {
 
     // The `vulkano_shaders::shaders!` macro generates a struct with the correct representation of the push constants struct specified in the shader.
     // Here we create an instance of the generated struct.
-    let push_constants = cs::ty::PushConstantData {
+    let push_constants = cs::MainPushConstants {
         multiple: 1,
         addend: 1.0,
         enable: 1,
@@ -13,6 +13,7 @@
 #![allow(non_camel_case_types)]
 
 use std::ffi::CStr;
+use std::ffi::CString;
 use std::fmt;
 use std::mem;
 use std::os::raw::c_char;
@@ -56,6 +57,7 @@ pub type SwapchainKHR = u64;
 pub type DisplayKHR = u64;
 pub type DisplayModeKHR = u64;
 pub type DescriptorUpdateTemplateKHR = u64;
+pub type ShaderEXT = u64;
 pub type DeviceAddress = u64;
 
 pub const LOD_CLAMP_NONE: f32 = 1000.0;
@@ -74,6 +76,7 @@ pub const MAX_MEMORY_TYPES: u32 = 32;
 pub const MAX_MEMORY_HEAPS: u32 = 16;
 pub const MAX_EXTENSION_NAME_SIZE: u32 = 256;
 pub const MAX_DESCRIPTION_SIZE: u32 = 256;
+pub const MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT: u32 = 32;
 pub const NULL_HANDLE: u64 = 0;
 
 pub type PipelineCacheHeaderVersion = u32;
@@ -100,12 +103,14 @@ pub const ERROR_FORMAT_NOT_SUPPORTED: u32 = -11i32 as u32;
 pub const ERROR_SURFACE_LOST_KHR: u32 = -1000000000i32 as u32;
 pub const ERROR_NATIVE_WINDOW_IN_USE_KHR: u32 = -1000000001i32 as u32;
 pub const SUBOPTIMAL_KHR: u32 = 1000001003;
+pub const PIPELINE_COMPILE_REQUIRED_EXT: u32 = 1000297000;
 pub const ERROR_OUT_OF_DATE_KHR: u32 = -1000001004i32 as u32;
 pub const ERROR_INCOMPATIBLE_DISPLAY_KHR: u32 = -1000003001i32 as u32;
 pub const ERROR_VALIDATION_FAILED_EXT: u32 = -1000011001i32 as u32;
 pub const ERROR_INVALID_SHADER_NV: u32 = -1000012000i32 as u32;
 pub const ERROR_OUT_OF_POOL_MEMORY_KHR: u32 = -1000069000i32 as u32;
 pub const ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT: u32 = -1000255000i32 as u32;
+pub const ERROR_INVALID_EXTERNAL_HANDLE: u32 = -1000072003i32 as u32;
 
 pub type StructureType = u32;
 pub const STRUCTURE_TYPE_APPLICATION_INFO: u32 = 0;
@@ -527,12 +532,25 @@ pub const STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_INFO_EXT: u32 = 100025500
 pub const STRUCTURE_TYPE_SURFACE_CAPABILITIES_FULL_SCREEN_EXCLUSIVE_EXT: u32 = 1000255002;
 pub const STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_WIN32_INFO_EXT: u32 = 1000255001;
 pub const STRUCTURE_TYPE_HEADLESS_SURFACE_CREATE_INFO_EXT: u32 = 1000256000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PROVOKING_VERTEX_FEATURES_EXT: u32 = 1000254000;
+pub const STRUCTURE_TYPE_PIPELINE_RASTERIZATION_PROVOKING_VERTEX_STATE_CREATE_INFO_EXT: u32 =
+    1000254001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PROVOKING_VERTEX_PROPERTIES_EXT: u32 = 1000254002;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_LINE_RASTERIZATION_FEATURES_EXT: u32 = 1000259000;
 pub const STRUCTURE_TYPE_PIPELINE_RASTERIZATION_LINE_STATE_CREATE_INFO_EXT: u32 = 1000259001;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_LINE_RASTERIZATION_PROPERTIES_EXT: u32 = 1000259002;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_ATOMIC_FLOAT_FEATURES_EXT: u32 = 1000260000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_INDEX_TYPE_UINT8_FEATURES_EXT: u32 = 1000265000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_FEATURES_EXT: u32 = 1000267000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PRIMITIVE_TOPOLOGY_LIST_RESTART_FEATURES_EXT: u32 =
+    1000356000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_2_FEATURES_EXT: u32 = 1000377000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_COLOR_WRITE_ENABLE_FEATURES_EXT: u32 = 1000381000;
+pub const STRUCTURE_TYPE_PIPELINE_COLOR_WRITE_CREATE_INFO_EXT: u32 = 1000381001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_3_FEATURES_EXT: u32 = 1000455000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_OBJECT_FEATURES_EXT: u32 = 1000482000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_OBJECT_PROPERTIES_EXT: u32 = 1000482001;
+pub const STRUCTURE_TYPE_SHADER_CREATE_INFO_EXT: u32 = 1000482002;
 pub const STRUCTURE_TYPE_DEFERRED_OPERATION_INFO_KHR: u32 = 1000268000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PIPELINE_EXECUTABLE_PROPERTIES_FEATURES_KHR: u32 =
     1000269000;
@@ -575,6 +593,13 @@ pub const STRUCTURE_TYPE_DEVICE_DIAGNOSTICS_CONFIG_CREATE_INFO_NV: u32 = 1000300
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_FRAGMENT_DENSITY_MAP_2_FEATURES_EXT: u32 = 1000332000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_FRAGMENT_DENSITY_MAP_2_PROPERTIES_EXT: u32 = 1000332001;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_IMAGE_ROBUSTNESS_FEATURES_EXT: u32 = 1000335000;
+pub const STRUCTURE_TYPE_PIPELINE_ROBUSTNESS_CREATE_INFO_EXT: u32 = 1000068000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PIPELINE_ROBUSTNESS_FEATURES_EXT: u32 = 1000068001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PIPELINE_ROBUSTNESS_PROPERTIES_EXT: u32 = 1000068002;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_MODULE_IDENTIFIER_FEATURES_EXT: u32 = 1000462000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_MODULE_IDENTIFIER_PROPERTIES_EXT: u32 = 1000462001;
+pub const STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_MODULE_IDENTIFIER_CREATE_INFO_EXT: u32 = 1000462002;
+pub const STRUCTURE_TYPE_SHADER_MODULE_IDENTIFIER_EXT: u32 = 1000462003;
 pub const STRUCTURE_TYPE_COPY_BUFFER_INFO_2_KHR: u32 = 1000337000;
 pub const STRUCTURE_TYPE_COPY_IMAGE_INFO_2_KHR: u32 = 1000337001;
 pub const STRUCTURE_TYPE_COPY_BUFFER_TO_IMAGE_INFO_2_KHR: u32 = 1000337002;
@@ -1152,6 +1177,8 @@ pub const IMAGE_LAYOUT_TRANSFER_SRC_OPTIMAL: u32 = 6;
 pub const IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL: u32 = 7;
 pub const IMAGE_LAYOUT_PREINITIALIZED: u32 = 8;
 pub const IMAGE_LAYOUT_PRESENT_SRC_KHR: u32 = 1000001002;
+pub const IMAGE_LAYOUT_FRAGMENT_SHADING_RATE_ATTACHMENT_OPTIMAL_KHR: u32 = 1000164003;
+pub const IMAGE_LAYOUT_FRAGMENT_DENSITY_MAP_OPTIMAL_EXT: u32 = 1000218000;
 
 pub type ImageViewType = u32;
 pub const IMAGE_VIEW_TYPE_1D: u32 = 0;
@@ -1262,6 +1289,52 @@ pub const BLEND_OP_SUBTRACT: u32 = 1;
 pub const BLEND_OP_REVERSE_SUBTRACT: u32 = 2;
 pub const BLEND_OP_MIN: u32 = 3;
 pub const BLEND_OP_MAX: u32 = 4;
+pub const BLEND_OP_ZERO_EXT: u32 = 1000148000;
+pub const BLEND_OP_SRC_EXT: u32 = 1000148001;
+pub const BLEND_OP_DST_EXT: u32 = 1000148002;
+pub const BLEND_OP_SRC_OVER_EXT: u32 = 1000148003;
+pub const BLEND_OP_DST_OVER_EXT: u32 = 1000148004;
+pub const BLEND_OP_SRC_IN_EXT: u32 = 1000148005;
+pub const BLEND_OP_DST_IN_EXT: u32 = 1000148006;
+pub const BLEND_OP_SRC_OUT_EXT: u32 = 1000148007;
+pub const BLEND_OP_DST_OUT_EXT: u32 = 1000148008;
+pub const BLEND_OP_SRC_ATOP_EXT: u32 = 1000148009;
+pub const BLEND_OP_DST_ATOP_EXT: u32 = 1000148010;
+pub const BLEND_OP_XOR_EXT: u32 = 1000148011;
+pub const BLEND_OP_MULTIPLY_EXT: u32 = 1000148012;
+pub const BLEND_OP_SCREEN_EXT: u32 = 1000148013;
+pub const BLEND_OP_OVERLAY_EXT: u32 = 1000148014;
+pub const BLEND_OP_DARKEN_EXT: u32 = 1000148015;
+pub const BLEND_OP_LIGHTEN_EXT: u32 = 1000148016;
+pub const BLEND_OP_COLORDODGE_EXT: u32 = 1000148017;
+pub const BLEND_OP_COLORBURN_EXT: u32 = 1000148018;
+pub const BLEND_OP_HARDLIGHT_EXT: u32 = 1000148019;
+pub const BLEND_OP_SOFTLIGHT_EXT: u32 = 1000148020;
+pub const BLEND_OP_DIFFERENCE_EXT: u32 = 1000148021;
+pub const BLEND_OP_EXCLUSION_EXT: u32 = 1000148022;
+pub const BLEND_OP_INVERT_EXT: u32 = 1000148023;
+pub const BLEND_OP_INVERT_RGB_EXT: u32 = 1000148024;
+pub const BLEND_OP_LINEARDODGE_EXT: u32 = 1000148025;
+pub const BLEND_OP_LINEARBURN_EXT: u32 = 1000148026;
+pub const BLEND_OP_VIVIDLIGHT_EXT: u32 = 1000148027;
+pub const BLEND_OP_LINEARLIGHT_EXT: u32 = 1000148028;
+pub const BLEND_OP_PINLIGHT_EXT: u32 = 1000148029;
+pub const BLEND_OP_HARDMIX_EXT: u32 = 1000148030;
+pub const BLEND_OP_HSL_HUE_EXT: u32 = 1000148031;
+pub const BLEND_OP_HSL_SATURATION_EXT: u32 = 1000148032;
+pub const BLEND_OP_HSL_COLOR_EXT: u32 = 1000148033;
+pub const BLEND_OP_HSL_LUMINOSITY_EXT: u32 = 1000148034;
+pub const BLEND_OP_PLUS_EXT: u32 = 1000148035;
+pub const BLEND_OP_PLUS_CLAMPED_EXT: u32 = 1000148036;
+pub const BLEND_OP_PLUS_CLAMPED_ALPHA_EXT: u32 = 1000148037;
+pub const BLEND_OP_PLUS_DARKER_EXT: u32 = 1000148038;
+pub const BLEND_OP_MINUS_EXT: u32 = 1000148039;
+pub const BLEND_OP_MINUS_CLAMPED_EXT: u32 = 1000148040;
+pub const BLEND_OP_CONTRAST_EXT: u32 = 1000148041;
+pub const BLEND_OP_INVERT_OVG_EXT: u32 = 1000148042;
+pub const BLEND_OP_RED_EXT: u32 = 1000148043;
+pub const BLEND_OP_GREEN_EXT: u32 = 1000148044;
+pub const BLEND_OP_BLUE_EXT: u32 = 1000148045;
 
 pub type DynamicState = u32;
 pub const DYNAMIC_STATE_VIEWPORT: u32 = 0;
@@ -1273,6 +1346,23 @@ pub const DYNAMIC_STATE_DEPTH_BOUNDS: u32 = 5;
 pub const DYNAMIC_STATE_STENCIL_COMPARE_MASK: u32 = 6;
 pub const DYNAMIC_STATE_STENCIL_WRITE_MASK: u32 = 7;
 pub const DYNAMIC_STATE_STENCIL_REFERENCE: u32 = 8;
+pub const DYNAMIC_STATE_FRAGMENT_SHADING_RATE_KHR: u32 = 1000226000;
+pub const DYNAMIC_STATE_CULL_MODE_EXT: u32 = 1000267000;
+pub const DYNAMIC_STATE_FRONT_FACE_EXT: u32 = 1000267001;
+pub const DYNAMIC_STATE_PRIMITIVE_TOPOLOGY_EXT: u32 = 1000267002;
+pub const DYNAMIC_STATE_DEPTH_TEST_ENABLE_EXT: u32 = 1000267006;
+pub const DYNAMIC_STATE_DEPTH_WRITE_ENABLE_EXT: u32 = 1000267007;
+pub const DYNAMIC_STATE_DEPTH_COMPARE_OP_EXT: u32 = 1000267008;
+pub const DYNAMIC_STATE_RASTERIZER_DISCARD_ENABLE_EXT: u32 = 1000377001;
+pub const DYNAMIC_STATE_DEPTH_BIAS_ENABLE_EXT: u32 = 1000377002;
+pub const DYNAMIC_STATE_LOGIC_OP_EXT: u32 = 1000377003;
+pub const DYNAMIC_STATE_PRIMITIVE_RESTART_ENABLE_EXT: u32 = 1000377004;
+pub const DYNAMIC_STATE_COLOR_BLEND_ENABLE_EXT: u32 = 1000455010;
+pub const DYNAMIC_STATE_COLOR_BLEND_EQUATION_EXT: u32 = 1000455011;
+pub const DYNAMIC_STATE_COLOR_WRITE_MASK_EXT: u32 = 1000455012;
+pub const DYNAMIC_STATE_LINE_STIPPLE_EXT: u32 = 1000259000;
+pub const DYNAMIC_STATE_SAMPLE_LOCATIONS_EXT: u32 = 1000143000;
+pub const DYNAMIC_STATE_COLOR_WRITE_ENABLE_EXT: u32 = 1000381000;
 
 pub type Filter = u32;
 pub const FILTER_NEAREST: u32 = 0;
@@ -1396,6 +1486,8 @@ pub const IMAGE_USAGE_COLOR_ATTACHMENT_BIT: u32 = 0x00000010;
 pub const IMAGE_USAGE_DEPTH_STENCIL_ATTACHMENT_BIT: u32 = 0x00000020;
 pub const IMAGE_USAGE_TRANSIENT_ATTACHMENT_BIT: u32 = 0x00000040;
 pub const IMAGE_USAGE_INPUT_ATTACHMENT_BIT: u32 = 0x00000080;
+pub const IMAGE_USAGE_FRAGMENT_SHADING_RATE_ATTACHMENT_BIT_KHR: u32 = 0x00000100;
+pub const IMAGE_USAGE_FRAGMENT_DENSITY_MAP_BIT_EXT: u32 = 0x00000200;
 pub type ImageUsageFlags = Flags;
 
 pub type ImageCreateFlagBits = u32;
@@ -1405,6 +1497,8 @@ pub const IMAGE_CREATE_SPARSE_ALIASED_BIT: u32 = 0x00000004;
 pub const IMAGE_CREATE_MUTABLE_FORMAT_BIT: u32 = 0x00000008;
 pub const IMAGE_CREATE_CUBE_COMPATIBLE_BIT: u32 = 0x00000010;
 pub const IMAGE_CREATE_2D_ARRAY_COMPATIBLE_BIT_KHR: u32 = 0x00000020;
+pub const IMAGE_CREATE_PROTECTED_BIT: u32 = 0x00000800;
+pub const IMAGE_CREATE_MULTISAMPLED_RENDER_TO_SINGLE_SAMPLED_BIT_EXT: u32 = 0x00040000;
 pub type ImageCreateFlags = Flags;
 
 pub type SampleCountFlagBits = u32;
@@ -1422,6 +1516,7 @@ pub const QUEUE_GRAPHICS_BIT: u32 = 0x00000001;
 pub const QUEUE_COMPUTE_BIT: u32 = 0x00000002;
 pub const QUEUE_TRANSFER_BIT: u32 = 0x00000004;
 pub const QUEUE_SPARSE_BINDING_BIT: u32 = 0x00000008;
+pub const QUEUE_PROTECTED_BIT: u32 = 0x00000010;
 pub type QueueFlags = Flags;
 
 pub type MemoryPropertyFlagBits = u32;
@@ -1430,12 +1525,15 @@ pub const MEMORY_PROPERTY_HOST_VISIBLE_BIT: u32 = 0x00000002;
 pub const MEMORY_PROPERTY_HOST_COHERENT_BIT: u32 = 0x00000004;
 pub const MEMORY_PROPERTY_HOST_CACHED_BIT: u32 = 0x00000008;
 pub const MEMORY_PROPERTY_LAZILY_ALLOCATED_BIT: u32 = 0x00000010;
+pub const MEMORY_PROPERTY_PROTECTED_BIT: u32 = 0x00000020;
 pub type MemoryPropertyFlags = Flags;
 
 pub type MemoryHeapFlagBits = u32;
 pub const MEMORY_HEAP_DEVICE_LOCAL_BIT: u32 = 0x00000001;
 pub type MemoryHeapFlags = Flags;
 pub type DeviceCreateFlags = Flags;
+pub type DeviceQueueCreateFlagBits = u32;
+pub const DEVICE_QUEUE_CREATE_PROTECTED_BIT: u32 = 0x00000001;
 pub type DeviceQueueCreateFlags = Flags;
 
 pub type PipelineStageFlagBits = u32;
@@ -1519,6 +1617,7 @@ pub type BufferCreateFlagBits = u32;
 pub const BUFFER_CREATE_SPARSE_BINDING_BIT: u32 = 0x00000001;
 pub const BUFFER_CREATE_SPARSE_RESIDENCY_BIT: u32 = 0x00000002;
 pub const BUFFER_CREATE_SPARSE_ALIASED_BIT: u32 = 0x00000004;
+pub const BUFFER_CREATE_PROTECTED_BIT: u32 = 0x00000008;
 pub type BufferCreateFlags = Flags;
 
 pub type BufferUsageFlagBits = u32;
@@ -1580,6 +1679,15 @@ pub type PipelineDynamicStateCreateFlags = Flags;
 pub type PipelineLayoutCreateFlags = Flags;
 pub type ShaderStageFlags = Flags;
 pub type SubgroupFeatureFlags = Flags;
+pub const SUBGROUP_FEATURE_BASIC_BIT: u32 = 0x00000001;
+pub const SUBGROUP_FEATURE_VOTE_BIT: u32 = 0x00000002;
+pub const SUBGROUP_FEATURE_ARITHMETIC_BIT: u32 = 0x00000004;
+pub const SUBGROUP_FEATURE_BALLOT_BIT: u32 = 0x00000008;
+pub const SUBGROUP_FEATURE_SHUFFLE_BIT: u32 = 0x00000010;
+pub const SUBGROUP_FEATURE_SHUFFLE_RELATIVE_BIT: u32 = 0x00000020;
+pub const SUBGROUP_FEATURE_CLUSTERED_BIT: u32 = 0x00000040;
+pub const SUBGROUP_FEATURE_QUAD_BIT: u32 = 0x00000080;
+pub const SUBGROUP_FEATURE_PARTITIONED_BIT_NV: u32 = 0x00000100;
 pub type SamplerCreateFlags = Flags;
 pub type DescriptorSetLayoutCreateFlags = Flags;
 
@@ -1617,11 +1725,13 @@ pub type AccessFlags = Flags;
 
 pub type DependencyFlagBits = u32;
 pub const DEPENDENCY_BY_REGION_BIT: u32 = 0x00000001;
+pub const DEPENDENCY_VIEW_LOCAL_BIT: u32 = 0x00000002;
 pub type DependencyFlags = Flags;
 
 pub type CommandPoolCreateFlagBits = u32;
 pub const COMMAND_POOL_CREATE_TRANSIENT_BIT: u32 = 0x00000001;
 pub const COMMAND_POOL_CREATE_RESET_COMMAND_BUFFER_BIT: u32 = 0x00000002;
+pub const COMMAND_POOL_CREATE_PROTECTED_BIT: u32 = 0x00000004;
 pub type CommandPoolCreateFlags = Flags;
 
 pub type CommandPoolResetFlagBits = u32;
@@ -1730,6 +1840,29 @@ pub const EXTERNAL_MEMORY_HANDLE_TYPE_D3D12_RESOURCE_BIT_KHR: u32 =
     EXTERNAL_MEMORY_HANDLE_TYPE_D3D12_RESOURCE_BIT;
 pub type ExternalMemoryHandleTypeFlags = Flags;
 
+pub type ExternalSemaphoreHandleTypeFlagBits = u32;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT: u32 = 0x00000001;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT: u32 = 0x00000002;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT: u32 = 0x00000004;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT: u32 = 0x00000008;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT: u32 = 0x00000010;
+pub type ExternalSemaphoreHandleTypeFlags = Flags;
+
+pub type SemaphoreImportFlagBits = u32;
+pub const SEMAPHORE_IMPORT_TEMPORARY_BIT: u32 = 0x00000001;
+pub type SemaphoreImportFlags = Flags;
+
+pub type ExternalFenceHandleTypeFlagBits = u32;
+pub const EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_FD_BIT: u32 = 0x00000001;
+pub const EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_BIT: u32 = 0x00000002;
+pub const EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT: u32 = 0x00000004;
+pub const EXTERNAL_FENCE_HANDLE_TYPE_SYNC_FD_BIT: u32 = 0x00000008;
+pub type ExternalFenceHandleTypeFlags = Flags;
+
+pub type FenceImportFlagBits = u32;
+pub const FENCE_IMPORT_TEMPORARY_BIT: u32 = 0x00000001;
+pub type FenceImportFlags = Flags;
+
 pub type ObjectType = u32;
 pub const OBJECT_TYPE_UNKNOWN: u32 = 0;
 pub const OBJECT_TYPE_INSTANCE: u32 = 1;
@@ -1769,6 +1902,7 @@ pub const OBJECT_TYPE_DEBUG_UTILS_MESSENGER_EXT: u32 = 1000128000;
 pub const OBJECT_TYPE_VALIDATION_CACHE_EXT: u32 = 1000160000;
 pub const OBJECT_TYPE_ACCELERATION_STRUCTURE_NV: u32 = 1000165000;
 pub const OBJECT_TYPE_PERFORMANCE_CONFIGURATION_INTEL: u32 = 1000210000;
+pub const OBJECT_TYPE_SHADER_EXT: u32 = 1000482000;
 pub const OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_KHR: u32 = OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE;
 pub const OBJECT_TYPE_SAMPLER_YCBCR_CONVERSION_KHR: u32 = OBJECT_TYPE_SAMPLER_YCBCR_CONVERSION;
 
@@ -1821,18 +1955,10 @@ pub type PFN_vkReallocationFunction = extern "system" fn(
     SystemAllocationScope,
 ) -> *mut c_void;
 pub type PFN_vkFreeFunction = extern "system" fn(*mut c_void, *mut c_void);
-pub type PFN_vkInternalAllocationNotification = extern "system" fn(
-    *mut c_void,
-    usize,
-    InternalAllocationType,
-    SystemAllocationScope,
-) -> *mut c_void;
-pub type PFN_vkInternalFreeNotification = extern "system" fn(
-    *mut c_void,
-    usize,
-    InternalAllocationType,
-    SystemAllocationScope,
-) -> *mut c_void;
+pub type PFN_vkInternalAllocationNotification =
+    extern "system" fn(*mut c_void, usize, InternalAllocationType, SystemAllocationScope);
+pub type PFN_vkInternalFreeNotification =
+    extern "system" fn(*mut c_void, usize, InternalAllocationType, SystemAllocationScope);
 pub type PFN_vkDebugUtilsMessengerCallbackEXT = extern "system" fn(
     DebugUtilsMessageSeverityFlagBitsEXT,
     DebugUtilsMessageTypeFlagsEXT,
@@ -1849,6 +1975,239 @@ pub const FULL_SCREEN_EXCLUSIVE_DISALLOWED_EXT: u32 = 2;
 pub const FULL_SCREEN_EXCLUSIVE_APPLICATION_CONTROLLED_EXT: u32 = 3;
 pub const FULL_SCREEN_EXCLUSIVE_MAX_ENUM_EXT: u32 = 0x7FFFFFFF;
 
+pub type ConservativeRasterizationModeEXT = u32;
+pub const CONSERVATIVE_RASTERIZATION_MODE_DISABLED_EXT: u32 = 0;
+pub const CONSERVATIVE_RASTERIZATION_MODE_OVERESTIMATE_EXT: u32 = 1;
+pub const CONSERVATIVE_RASTERIZATION_MODE_UNDERESTIMATE_EXT: u32 = 2;
+pub type PipelineRasterizationConservativeStateCreateFlagsEXT = Flags;
+
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_GRAPHICS_PIPELINE_LIBRARY_FEATURES_EXT: u32 = 1000320000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_GRAPHICS_PIPELINE_LIBRARY_PROPERTIES_EXT: u32 = 1000320001;
+pub const STRUCTURE_TYPE_GRAPHICS_PIPELINE_LIBRARY_CREATE_INFO_EXT: u32 = 1000320002;
+pub const STRUCTURE_TYPE_PRESENT_ID_KHR: u32 = 1000294000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PRESENT_ID_FEATURES_KHR: u32 = 1000294001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PRESENT_WAIT_FEATURES_KHR: u32 = 1000248000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SWAPCHAIN_MAINTENANCE_1_FEATURES_EXT: u32 = 1000275000;
+pub const STRUCTURE_TYPE_SWAPCHAIN_PRESENT_FENCE_INFO_EXT: u32 = 1000275001;
+pub const STRUCTURE_TYPE_SWAPCHAIN_PRESENT_MODES_CREATE_INFO_EXT: u32 = 1000275002;
+pub const STRUCTURE_TYPE_SWAPCHAIN_PRESENT_MODE_INFO_EXT: u32 = 1000275003;
+pub const STRUCTURE_TYPE_SWAPCHAIN_PRESENT_SCALING_CREATE_INFO_EXT: u32 = 1000275004;
+pub const STRUCTURE_TYPE_RELEASE_SWAPCHAIN_IMAGES_INFO_EXT: u32 = 1000275005;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTISAMPLED_RENDER_TO_SINGLE_SAMPLED_FEATURES_EXT: u32 =
+    1000376000;
+pub const STRUCTURE_TYPE_SUBPASS_RESOLVE_PERFORMANCE_QUERY_EXT: u32 = 1000376001;
+pub const STRUCTURE_TYPE_MULTISAMPLED_RENDER_TO_SINGLE_SAMPLED_INFO_EXT: u32 = 1000376002;
+
+pub const SWAPCHAIN_CREATE_DEFERRED_MEMORY_ALLOCATION_BIT_EXT: u32 = 0x00000008;
+pub const SWAPCHAIN_CREATE_PROTECTED_BIT_KHR: u32 = 0x00000002;
+
+pub type PresentScalingFlagsEXT = Flags;
+pub const PRESENT_SCALING_ONE_TO_ONE_BIT_EXT: u32 = 0x00000001;
+pub const PRESENT_SCALING_ASPECT_RATIO_STRETCH_BIT_EXT: u32 = 0x00000002;
+pub const PRESENT_SCALING_STRETCH_BIT_EXT: u32 = 0x00000004;
+
+pub type PresentGravityFlagsEXT = Flags;
+pub const PRESENT_GRAVITY_MIN_BIT_EXT: u32 = 0x00000001;
+pub const PRESENT_GRAVITY_MAX_BIT_EXT: u32 = 0x00000002;
+pub const PRESENT_GRAVITY_CENTERED_BIT_EXT: u32 = 0x00000004;
+
+pub type SemaphoreType = u32;
+pub const SEMAPHORE_TYPE_BINARY: u32 = 0;
+pub const SEMAPHORE_TYPE_TIMELINE: u32 = 1;
+
+pub type SemaphoreWaitFlags = Flags;
+pub const SEMAPHORE_WAIT_ANY_BIT: u32 = 0x00000001;
+
+pub const PIPELINE_CREATE_LIBRARY_BIT_KHR: u32 = 0x00000800;
+pub const PIPELINE_CREATE_LINK_TIME_OPTIMIZATION_BIT_EXT: u32 = 0x00000400;
+pub const PIPELINE_CREATE_RETAIN_LINK_TIME_OPTIMIZATION_INFO_BIT_EXT: u32 = 0x00800000;
+pub const PIPELINE_CREATE_FAIL_ON_PIPELINE_COMPILE_REQUIRED_BIT_EXT: u32 = 0x00000100;
+
+pub type GraphicsPipelineLibraryFlagBitsEXT = u32;
+pub const GRAPHICS_PIPELINE_LIBRARY_VERTEX_INPUT_INTERFACE_BIT_EXT: u32 = 0x00000001;
+pub const GRAPHICS_PIPELINE_LIBRARY_PRE_RASTERIZATION_SHADERS_BIT_EXT: u32 = 0x00000002;
+pub const GRAPHICS_PIPELINE_LIBRARY_FRAGMENT_SHADER_BIT_EXT: u32 = 0x00000004;
+pub const GRAPHICS_PIPELINE_LIBRARY_FRAGMENT_OUTPUT_INTERFACE_BIT_EXT: u32 = 0x00000008;
+pub type GraphicsPipelineLibraryFlagsEXT = Flags;
+
+pub type FragmentShadingRateCombinerOpKHR = u32;
+pub const FRAGMENT_SHADING_RATE_COMBINER_OP_KEEP_KHR: u32 = 0;
+pub const FRAGMENT_SHADING_RATE_COMBINER_OP_REPLACE_KHR: u32 = 1;
+pub const FRAGMENT_SHADING_RATE_COMBINER_OP_MIN_KHR: u32 = 2;
+pub const FRAGMENT_SHADING_RATE_COMBINER_OP_MAX_KHR: u32 = 3;
+pub const FRAGMENT_SHADING_RATE_COMBINER_OP_MUL_KHR: u32 = 4;
+
+pub type ShaderCreateFlagsEXT = Flags;
+pub type ShaderCreateFlagBitsEXT = u32;
+pub const SHADER_CREATE_LINK_STAGE_BIT_EXT: u32 = 0x00000001;
+
+pub type ShaderCodeTypeEXT = u32;
+pub const SHADER_CODE_TYPE_BINARY_EXT: u32 = 0;
+pub const SHADER_CODE_TYPE_SPIRV_EXT: u32 = 1;
+
+pub const PIPELINE_CREATE_CAPTURE_STATISTICS_BIT_KHR: u32 = 0x00000040;
+pub const PIPELINE_CREATE_CAPTURE_INTERNAL_REPRESENTATIONS_BIT_KHR: u32 = 0x00000080;
+
+pub type PipelineExecutableStatisticFormatKHR = u32;
+pub const PIPELINE_EXECUTABLE_STATISTIC_FORMAT_BOOL32_KHR: u32 = 0;
+pub const PIPELINE_EXECUTABLE_STATISTIC_FORMAT_INT64_KHR: u32 = 1;
+pub const PIPELINE_EXECUTABLE_STATISTIC_FORMAT_UINT64_KHR: u32 = 2;
+pub const PIPELINE_EXECUTABLE_STATISTIC_FORMAT_FLOAT64_KHR: u32 = 3;
+
+pub type LineRasterizationModeEXT = u32;
+pub const LINE_RASTERIZATION_MODE_DEFAULT_EXT: u32 = 0;
+pub const LINE_RASTERIZATION_MODE_RECTANGULAR_EXT: u32 = 1;
+pub const LINE_RASTERIZATION_MODE_BRESENHAM_EXT: u32 = 2;
+pub const LINE_RASTERIZATION_MODE_RECTANGULAR_SMOOTH_EXT: u32 = 3;
+
+pub type ProvokingVertexModeEXT = u32;
+pub const PROVOKING_VERTEX_MODE_FIRST_VERTEX_EXT: u32 = 0;
+pub const PROVOKING_VERTEX_MODE_LAST_VERTEX_EXT: u32 = 1;
+
+pub type BlendOverlapEXT = u32;
+pub const BLEND_OVERLAP_UNCORRELATED_EXT: u32 = 0;
+pub const BLEND_OVERLAP_DISJOINT_EXT: u32 = 1;
+pub const BLEND_OVERLAP_CONJOINT_EXT: u32 = 2;
+
+pub type PipelineRasterizationDepthClipStateCreateFlagsEXT = Flags;
+
+pub type PipelineRobustnessBufferBehaviorEXT = u32;
+pub const PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_DEVICE_DEFAULT_EXT: u32 = 0;
+pub const PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_DISABLED_EXT: u32 = 1;
+pub const PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_ROBUST_BUFFER_ACCESS_EXT: u32 = 2;
+pub const PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_ROBUST_BUFFER_ACCESS_2_EXT: u32 = 3;
+
+pub type PipelineRobustnessImageBehaviorEXT = u32;
+pub const PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_DEVICE_DEFAULT_EXT: u32 = 0;
+pub const PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_DISABLED_EXT: u32 = 1;
+pub const PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_ROBUST_IMAGE_ACCESS_EXT: u32 = 2;
+pub const PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_ROBUST_IMAGE_ACCESS_2_EXT: u32 = 3;
+
+pub type ValidationFeatureEnableEXT = u32;
+pub const VALIDATION_FEATURE_ENABLE_GPU_ASSISTED_EXT: u32 = 0;
+pub const VALIDATION_FEATURE_ENABLE_GPU_ASSISTED_RESERVE_BINDING_SLOT_EXT: u32 = 1;
+pub const VALIDATION_FEATURE_ENABLE_BEST_PRACTICES_EXT: u32 = 2;
+pub const VALIDATION_FEATURE_ENABLE_DEBUG_PRINTF_EXT: u32 = 3;
+pub const VALIDATION_FEATURE_ENABLE_SYNCHRONIZATION_VALIDATION_EXT: u32 = 4;
+
+pub type ValidationFeatureDisableEXT = u32;
+pub const VALIDATION_FEATURE_DISABLE_ALL_EXT: u32 = 0;
+pub const VALIDATION_FEATURE_DISABLE_SHADERS_EXT: u32 = 1;
+pub const VALIDATION_FEATURE_DISABLE_THREAD_SAFETY_EXT: u32 = 2;
+pub const VALIDATION_FEATURE_DISABLE_API_PARAMETERS_EXT: u32 = 3;
+pub const VALIDATION_FEATURE_DISABLE_OBJECT_LIFETIMES_EXT: u32 = 4;
+pub const VALIDATION_FEATURE_DISABLE_CORE_CHECKS_EXT: u32 = 5;
+pub const VALIDATION_FEATURE_DISABLE_UNIQUE_HANDLES_EXT: u32 = 6;
+
+pub type ResolveModeFlagBits = u32;
+pub const RESOLVE_MODE_NONE: u32 = 0;
+pub const RESOLVE_MODE_SAMPLE_ZERO_BIT: u32 = 0x00000001;
+pub const RESOLVE_MODE_AVERAGE_BIT: u32 = 0x00000002;
+pub const RESOLVE_MODE_MIN_BIT: u32 = 0x00000004;
+pub const RESOLVE_MODE_MAX_BIT: u32 = 0x00000008;
+pub type ResolveModeFlags = Flags;
+
+pub type FramebufferCreateFlagBits = u32;
+pub const FRAMEBUFFER_CREATE_IMAGELESS_BIT: u32 = 0x00000001;
+
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PAGEABLE_DEVICE_LOCAL_MEMORY_FEATURES_EXT: u32 =
+    1000412000;
+
+pub type DeviceMemoryReportFlagsEXT = Flags;
+pub type DeviceMemoryReportEventTypeEXT = u32;
+pub const DEVICE_MEMORY_REPORT_EVENT_TYPE_ALLOCATE_EXT: u32 = 0;
+pub const DEVICE_MEMORY_REPORT_EVENT_TYPE_FREE_EXT: u32 = 1;
+pub const DEVICE_MEMORY_REPORT_EVENT_TYPE_IMPORT_EXT: u32 = 2;
+pub const DEVICE_MEMORY_REPORT_EVENT_TYPE_UNIMPORT_EXT: u32 = 3;
+pub const DEVICE_MEMORY_REPORT_EVENT_TYPE_ALLOCATION_FAILED_EXT: u32 = 4;
+
+pub type PFN_vkDeviceMemoryReportCallbackEXT =
+    extern "system" fn(*const DeviceMemoryReportCallbackDataEXT, *mut c_void);
+
+pub const MAX_DEVICE_GROUP_SIZE: u32 = 32;
+
+pub type MemoryAllocateFlagBits = u32;
+pub const MEMORY_ALLOCATE_DEVICE_MASK_BIT: u32 = 0x00000001;
+pub type MemoryAllocateFlags = Flags;
+
+pub type PeerMemoryFeatureFlagBits = u32;
+pub const PEER_MEMORY_FEATURE_COPY_SRC_BIT: u32 = 0x00000001;
+pub const PEER_MEMORY_FEATURE_COPY_DST_BIT: u32 = 0x00000002;
+pub const PEER_MEMORY_FEATURE_GENERIC_SRC_BIT: u32 = 0x00000004;
+pub const PEER_MEMORY_FEATURE_GENERIC_DST_BIT: u32 = 0x00000008;
+pub type PeerMemoryFeatureFlags = Flags;
+
+pub const INSTANCE_CREATE_ENUMERATE_PORTABILITY_BIT_KHR: u32 = 0x00000001;
+
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_FAULT_FEATURES_EXT: u32 = 1000341000;
+pub const STRUCTURE_TYPE_DEVICE_FAULT_COUNTS_EXT: u32 = 1000341001;
+pub const STRUCTURE_TYPE_DEVICE_FAULT_INFO_EXT: u32 = 1000341002;
+
+pub type DeviceFaultAddressTypeEXT = u32;
+pub const DEVICE_FAULT_ADDRESS_TYPE_NONE_EXT: u32 = 0;
+pub const DEVICE_FAULT_ADDRESS_TYPE_READ_INVALID_EXT: u32 = 1;
+pub const DEVICE_FAULT_ADDRESS_TYPE_WRITE_INVALID_EXT: u32 = 2;
+pub const DEVICE_FAULT_ADDRESS_TYPE_EXECUTE_INVALID_EXT: u32 = 3;
+pub const DEVICE_FAULT_ADDRESS_TYPE_INSTRUCTION_POINTER_UNKNOWN_EXT: u32 = 4;
+pub const DEVICE_FAULT_ADDRESS_TYPE_INSTRUCTION_POINTER_INVALID_EXT: u32 = 5;
+pub const DEVICE_FAULT_ADDRESS_TYPE_INSTRUCTION_POINTER_FAULT_EXT: u32 = 6;
+
+pub const MAX_DRIVER_NAME_SIZE: u32 = 256;
+pub const MAX_DRIVER_INFO_SIZE: u32 = 256;
+
+pub type DriverId = u32;
+pub const DRIVER_ID_AMD_PROPRIETARY: u32 = 1;
+pub const DRIVER_ID_AMD_OPEN_SOURCE: u32 = 2;
+pub const DRIVER_ID_MESA_RADV: u32 = 3;
+pub const DRIVER_ID_NVIDIA_PROPRIETARY: u32 = 4;
+pub const DRIVER_ID_INTEL_PROPRIETARY_WINDOWS: u32 = 5;
+pub const DRIVER_ID_INTEL_OPEN_SOURCE_MESA: u32 = 6;
+pub const DRIVER_ID_IMAGINATION_PROPRIETARY: u32 = 7;
+pub const DRIVER_ID_QUALCOMM_PROPRIETARY: u32 = 8;
+pub const DRIVER_ID_ARM_PROPRIETARY: u32 = 9;
+pub const DRIVER_ID_GOOGLE_SWIFTSHADER: u32 = 10;
+pub const DRIVER_ID_GGP_PROPRIETARY: u32 = 11;
+pub const DRIVER_ID_BROADCOM_PROPRIETARY: u32 = 12;
+pub const DRIVER_ID_MESA_LLVMPIPE: u32 = 13;
+pub const DRIVER_ID_MOLTENVK: u32 = 14;
+pub const DRIVER_ID_COREAVI_PROPRIETARY: u32 = 15;
+pub const DRIVER_ID_JUICE_PROPRIETARY: u32 = 16;
+pub const DRIVER_ID_VERISILICON_PROPRIETARY: u32 = 17;
+pub const DRIVER_ID_MESA_TURNIP: u32 = 18;
+pub const DRIVER_ID_MESA_V3DV: u32 = 19;
+pub const DRIVER_ID_MESA_PANVK: u32 = 20;
+pub const DRIVER_ID_SAMSUNG_PROPRIETARY: u32 = 21;
+pub const DRIVER_ID_MESA_VENUS: u32 = 22;
+pub const DRIVER_ID_MESA_DOZEN: u32 = 23;
+pub const DRIVER_ID_MESA_NVK: u32 = 24;
+pub const DRIVER_ID_IMAGINATION_OPEN_SOURCE_MESA: u32 = 25;
+
+pub type ToolPurposeFlagBitsEXT = u32;
+pub const TOOL_PURPOSE_VALIDATION_BIT_EXT: u32 = 0x00000001;
+pub const TOOL_PURPOSE_PROFILING_BIT_EXT: u32 = 0x00000002;
+pub const TOOL_PURPOSE_TRACING_BIT_EXT: u32 = 0x00000004;
+pub const TOOL_PURPOSE_ADDITIONAL_FEATURES_BIT_EXT: u32 = 0x00000008;
+pub const TOOL_PURPOSE_MODIFYING_FEATURES_BIT_EXT: u32 = 0x00000010;
+pub const TOOL_PURPOSE_DEBUG_REPORTING_BIT_EXT: u32 = 0x00000020;
+pub const TOOL_PURPOSE_DEBUG_MARKERS_BIT_EXT: u32 = 0x00000040;
+pub type ToolPurposeFlagsEXT = Flags;
+
+pub type PrivateDataSlotEXT = u64;
+pub const OBJECT_TYPE_PRIVATE_DATA_SLOT_EXT: u32 = 1000295000;
+pub type PrivateDataSlotCreateFlagsEXT = Flags;
+
+pub const STRUCTURE_TYPE_LAYER_SETTINGS_CREATE_INFO_EXT: u32 = 1000496000;
+
+pub type LayerSettingTypeEXT = u32;
+pub const LAYER_SETTING_TYPE_BOOL32_EXT: u32 = 0;
+pub const LAYER_SETTING_TYPE_INT32_EXT: u32 = 1;
+pub const LAYER_SETTING_TYPE_INT64_EXT: u32 = 2;
+pub const LAYER_SETTING_TYPE_UINT32_EXT: u32 = 3;
+pub const LAYER_SETTING_TYPE_UINT64_EXT: u32 = 4;
+pub const LAYER_SETTING_TYPE_FLOAT32_EXT: u32 = 5;
+pub const LAYER_SETTING_TYPE_FLOAT64_EXT: u32 = 6;
+pub const LAYER_SETTING_TYPE_STRING_EXT: u32 = 7;
+
 #[repr(C)]
 pub struct ApplicationInfo {
     pub sType: StructureType,
@@ -3054,6 +3413,27 @@ pub struct SurfaceCapabilitiesKHR {
     pub supportedUsageFlags: ImageUsageFlags,
 }
 
+#[repr(C)]
+pub struct PhysicalDeviceSurfaceInfo2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub surface: SurfaceKHR,
+}
+
+#[repr(C)]
+pub struct SurfaceCapabilities2KHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub surfaceCapabilities: SurfaceCapabilitiesKHR,
+}
+
+#[repr(C)]
+pub struct SurfaceProtectedCapabilitiesKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub supportsProtected: Bool32,
+}
+
 #[repr(C)]
 pub struct SurfaceFormatKHR {
     pub format: Format,
@@ -3411,6 +3791,15 @@ pub struct ViSurfaceCreateInfoNN {
     pub window: *const c_void,
 }
 
+pub type HeadlessSurfaceCreateFlagsEXT = Flags;
+
+#[repr(C)]
+pub struct HeadlessSurfaceCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: HeadlessSurfaceCreateFlagsEXT,
+}
+
 #[repr(C)]
 pub struct PhysicalDevicePushDescriptorPropertiesKHR {
     pub sType: StructureType,
@@ -3561,6 +3950,13 @@ pub struct SurfaceFullScreenExclusiveInfoEXT {
     pub fullScreenExclusive: FullScreenExclusiveEXT,
 }
 
+#[repr(C)]
+pub struct SurfaceFullScreenExclusiveWin32InfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub hmonitor: *mut c_void,
+}
+
 #[repr(C)]
 pub struct ExportMemoryAllocateInfo {
     pub sType: StructureType,
@@ -3606,6 +4002,1364 @@ pub struct ImportMemoryFdInfoKHR {
     pub fd: i32,
 }
 
+#[repr(C)]
+pub struct PhysicalDeviceConservativeRasterizationPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub primitiveOverestimationSize: f32,
+    pub maxExtraPrimitiveOverestimationSize: f32,
+    pub extraPrimitiveOverestimationSizeGranularity: f32,
+    pub primitiveUnderestimation: Bool32,
+    pub conservativePointAndLineRasterization: Bool32,
+    pub degenerateTrianglesRasterized: Bool32,
+    pub degenerateLinesRasterized: Bool32,
+    pub fullyCoveredFragmentShaderInputVariable: Bool32,
+    pub conservativeRasterizationPostDepthCoverage: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineRasterizationConservativeStateCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: PipelineRasterizationConservativeStateCreateFlagsEXT,
+    pub conservativeRasterizationMode: ConservativeRasterizationModeEXT,
+    pub extraPrimitiveOverestimationSize: f32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceGraphicsPipelineLibraryFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub graphicsPipelineLibrary: Bool32,
+}
+
+#[repr(C)]
+pub struct GraphicsPipelineLibraryCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: GraphicsPipelineLibraryFlagsEXT,
+}
+
+#[repr(C)]
+pub struct PipelineLibraryCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub libraryCount: u32,
+    pub pLibraries: *const Pipeline,
+}
+
+#[repr(C)]
+pub struct VertexInputBindingDivisorDescriptionEXT {
+    pub binding: u32,
+    pub divisor: u32,
+}
+
+#[repr(C)]
+pub struct PipelineVertexInputDivisorStateCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub vertexBindingDivisorCount: u32,
+    pub pVertexBindingDivisors: *const VertexInputBindingDivisorDescriptionEXT,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceVertexAttributeDivisorPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxVertexAttribDivisor: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceVertexAttributeDivisorFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub vertexAttributeInstanceRateDivisor: Bool32,
+    pub vertexAttributeInstanceRateZeroDivisor: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineFragmentShadingRateStateCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fragmentSize: Extent2D,
+    pub combinerOps: [FragmentShadingRateCombinerOpKHR; 2],
+}
+
+#[repr(C)]
+pub struct FragmentShadingRateAttachmentInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pFragmentShadingRateAttachment: *const AttachmentReference2,
+    pub shadingRateAttachmentTexelSize: Extent2D,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceFragmentShadingRateFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pipelineFragmentShadingRate: Bool32,
+    pub primitiveFragmentShadingRate: Bool32,
+    pub attachmentFragmentShadingRate: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceFragmentShadingRatePropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub minFragmentShadingRateAttachmentTexelSize: Extent2D,
+    pub maxFragmentShadingRateAttachmentTexelSize: Extent2D,
+    pub maxFragmentShadingRateAttachmentTexelSizeAspectRatio: u32,
+    pub primitiveFragmentShadingRateWithMultipleViewports: Bool32,
+    pub layeredShadingRateAttachments: Bool32,
+    pub fragmentShadingRateNonTrivialCombinerOps: Bool32,
+    pub maxFragmentSize: Extent2D,
+    pub maxFragmentSizeAspectRatio: u32,
+    pub maxFragmentShadingRateCoverageSamples: u32,
+    pub maxFragmentShadingRateRasterizationSamples: SampleCountFlagBits,
+    pub fragmentShadingRateWithShaderDepthStencilWrites: Bool32,
+    pub fragmentShadingRateWithSampleMask: Bool32,
+    pub fragmentShadingRateWithShaderSampleMask: Bool32,
+    pub fragmentShadingRateWithConservativeRasterization: Bool32,
+    pub fragmentShadingRateWithFragmentShaderInterlock: Bool32,
+    pub fragmentShadingRateWithCustomSampleLocations: Bool32,
+    pub fragmentShadingRateStrictMultiplyCombiner: Bool32,
+}
+
+#[repr(C)]
+pub struct AttachmentDescription2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: AttachmentDescriptionFlags,
+    pub format: Format,
+    pub samples: SampleCountFlagBits,
+    pub loadOp: AttachmentLoadOp,
+    pub storeOp: AttachmentStoreOp,
+    pub stencilLoadOp: AttachmentLoadOp,
+    pub stencilStoreOp: AttachmentStoreOp,
+    pub initialLayout: ImageLayout,
+    pub finalLayout: ImageLayout,
+}
+
+#[repr(C)]
+pub struct AttachmentReference2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub attachment: u32,
+    pub layout: ImageLayout,
+    pub aspectMask: ImageAspectFlags,
+}
+
+#[repr(C)]
+pub struct SubpassDescription2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: SubpassDescriptionFlags,
+    pub pipelineBindPoint: PipelineBindPoint,
+    pub viewMask: u32,
+    pub inputAttachmentCount: u32,
+    pub pInputAttachments: *const AttachmentReference2,
+    pub colorAttachmentCount: u32,
+    pub pColorAttachments: *const AttachmentReference2,
+    pub pResolveAttachments: *const AttachmentReference2,
+    pub pDepthStencilAttachment: *const AttachmentReference2,
+    pub preserveAttachmentCount: u32,
+    pub pPreserveAttachments: *const u32,
+}
+
+#[repr(C)]
+pub struct SubpassDependency2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub srcSubpass: u32,
+    pub dstSubpass: u32,
+    pub srcStageMask: PipelineStageFlags,
+    pub dstStageMask: PipelineStageFlags,
+    pub srcAccessMask: AccessFlags,
+    pub dstAccessMask: AccessFlags,
+    pub dependencyFlags: DependencyFlags,
+    pub viewOffset: i32,
+}
+
+#[repr(C)]
+pub struct RenderPassCreateInfo2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: RenderPassCreateFlags,
+    pub attachmentCount: u32,
+    pub pAttachments: *const AttachmentDescription2,
+    pub subpassCount: u32,
+    pub pSubpasses: *const SubpassDescription2,
+    pub dependencyCount: u32,
+    pub pDependencies: *const SubpassDependency2,
+    pub correlatedViewMaskCount: u32,
+    pub pCorrelatedViewMasks: *const u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceExtendedDynamicStateFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub extendedDynamicState: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceExtendedDynamicState2FeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub extendedDynamicState2: Bool32,
+    pub extendedDynamicState2LogicOp: Bool32,
+    pub extendedDynamicState2PatchControlPoints: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceExtendedDynamicState3FeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub extendedDynamicState3TessellationDomainOrigin: Bool32,
+    pub extendedDynamicState3DepthClampEnable: Bool32,
+    pub extendedDynamicState3PolygonMode: Bool32,
+    pub extendedDynamicState3RasterizationSamples: Bool32,
+    pub extendedDynamicState3SampleMask: Bool32,
+    pub extendedDynamicState3AlphaToCoverageEnable: Bool32,
+    pub extendedDynamicState3AlphaToOneEnable: Bool32,
+    pub extendedDynamicState3LogicOpEnable: Bool32,
+    pub extendedDynamicState3ColorBlendEnable: Bool32,
+    pub extendedDynamicState3ColorBlendEquation: Bool32,
+    pub extendedDynamicState3ColorWriteMask: Bool32,
+    pub extendedDynamicState3RasterizationStream: Bool32,
+    pub extendedDynamicState3ConservativeRasterizationMode: Bool32,
+    pub extendedDynamicState3ExtraPrimitiveOverestimationSize: Bool32,
+    pub extendedDynamicState3DepthClipEnable: Bool32,
+    pub extendedDynamicState3SampleLocationsEnable: Bool32,
+    pub extendedDynamicState3ColorBlendAdvanced: Bool32,
+    pub extendedDynamicState3ProvokingVertexMode: Bool32,
+    pub extendedDynamicState3LineRasterizationMode: Bool32,
+    pub extendedDynamicState3LineStippleEnable: Bool32,
+    pub extendedDynamicState3DepthClipNegativeOneToOne: Bool32,
+    pub extendedDynamicState3ViewportWScalingEnable: Bool32,
+    pub extendedDynamicState3ViewportSwizzle: Bool32,
+    pub extendedDynamicState3CoverageToColorEnable: Bool32,
+    pub extendedDynamicState3CoverageToColorLocation: Bool32,
+    pub extendedDynamicState3CoverageModulationMode: Bool32,
+    pub extendedDynamicState3CoverageModulationTableEnable: Bool32,
+    pub extendedDynamicState3CoverageModulationTable: Bool32,
+    pub extendedDynamicState3CoverageReductionMode: Bool32,
+    pub extendedDynamicState3RepresentativeFragmentTestEnable: Bool32,
+    pub extendedDynamicState3ShadingRateImageEnable: Bool32,
+}
+
+#[repr(C)]
+pub struct ColorBlendEquationEXT {
+    pub srcColorBlendFactor: BlendFactor,
+    pub dstColorBlendFactor: BlendFactor,
+    pub colorBlendOp: BlendOp,
+    pub srcAlphaBlendFactor: BlendFactor,
+    pub dstAlphaBlendFactor: BlendFactor,
+    pub alphaBlendOp: BlendOp,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceShaderObjectFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub shaderObject: Bool32,
+}
+
+#[repr(C)]
+pub struct ShaderCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: ShaderCreateFlagsEXT,
+    pub stage: ShaderStageFlagBits,
+    pub nextStage: ShaderStageFlags,
+    pub codeType: ShaderCodeTypeEXT,
+    pub codeSize: usize,
+    pub pCode: *const c_void,
+    pub pName: *const c_char,
+    pub setLayoutCount: u32,
+    pub pSetLayouts: *const DescriptorSetLayout,
+    pub pushConstantRangeCount: u32,
+    pub pPushConstantRanges: *const PushConstantRange,
+    pub pSpecializationInfo: *const SpecializationInfo,
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePipelineExecutablePropertiesFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pipelineExecutableInfo: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pipeline: Pipeline,
+}
+
+#[repr(C)]
+pub struct PipelineExecutablePropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub stages: ShaderStageFlags,
+    pub name: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub subgroupSize: u32,
+}
+
+#[repr(C)]
+pub struct PipelineExecutableInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pipeline: Pipeline,
+    pub executableIndex: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union PipelineExecutableStatisticValueKHR {
+    pub b32: Bool32,
+    pub i64: i64,
+    pub u64: u64,
+    pub f64: f64,
+}
+
+#[repr(C)]
+pub struct PipelineExecutableStatisticKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub name: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub format: PipelineExecutableStatisticFormatKHR,
+    pub value: PipelineExecutableStatisticValueKHR,
+}
+
+#[repr(C)]
+pub struct PipelineExecutableInternalRepresentationKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub name: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub isText: Bool32,
+    pub dataSize: usize,
+    pub pData: *mut c_void,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceLineRasterizationFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub rectangularLines: Bool32,
+    pub bresenhamLines: Bool32,
+    pub smoothLines: Bool32,
+    pub stippledRectangularLines: Bool32,
+    pub stippledBresenhamLines: Bool32,
+    pub stippledSmoothLines: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceLineRasterizationPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub lineSubPixelPrecisionBits: u32,
+}
+
+#[repr(C)]
+pub struct PipelineRasterizationLineStateCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub lineRasterizationMode: LineRasterizationModeEXT,
+    pub stippledLineEnable: Bool32,
+    pub lineStippleFactor: u32,
+    pub lineStipplePattern: u16,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceProvokingVertexFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub provokingVertexLast: Bool32,
+    pub transformFeedbackPreservesProvokingVertex: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceProvokingVertexPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub provokingVertexModePerPipeline: Bool32,
+    pub transformFeedbackPreservesTriangleFanProvokingVertex: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineRasterizationProvokingVertexStateCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub provokingVertexMode: ProvokingVertexModeEXT,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceBlendOperationAdvancedFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub advancedBlendCoherentOperations: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceBlendOperationAdvancedPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub advancedBlendMaxColorAttachments: u32,
+    pub advancedBlendIndependentBlend: Bool32,
+    pub advancedBlendNonPremultipliedSrcColor: Bool32,
+    pub advancedBlendNonPremultipliedDstColor: Bool32,
+    pub advancedBlendCorrelatedOverlap: Bool32,
+    pub advancedBlendAllOperations: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineColorBlendAdvancedStateCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub srcPremultiplied: Bool32,
+    pub dstPremultiplied: Bool32,
+    pub blendOverlap: BlendOverlapEXT,
+}
+
+#[repr(C)]
+pub struct SampleLocationEXT {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[repr(C)]
+pub struct SampleLocationsInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub sampleLocationsPerPixel: SampleCountFlagBits,
+    pub sampleLocationGridSize: Extent2D,
+    pub sampleLocationsCount: u32,
+    pub pSampleLocations: *const SampleLocationEXT,
+}
+
+#[repr(C)]
+pub struct PipelineSampleLocationsStateCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub sampleLocationsEnable: Bool32,
+    pub sampleLocationsInfo: SampleLocationsInfoEXT,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceSampleLocationsPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub sampleLocationSampleCounts: SampleCountFlags,
+    pub maxSampleLocationGridSize: Extent2D,
+    pub sampleLocationCoordinateRange: [f32; 2],
+    pub sampleLocationSubPixelBits: u32,
+    pub variableSampleLocations: Bool32,
+}
+
+#[repr(C)]
+pub struct MultisamplePropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxSampleLocationGridSize: Extent2D,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceColorWriteEnableFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub colorWriteEnable: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineColorWriteCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub attachmentCount: u32,
+    pub pColorWriteEnables: *const Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDepthClipEnableFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub depthClipEnable: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineRasterizationDepthClipStateCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: PipelineRasterizationDepthClipStateCreateFlagsEXT,
+    pub depthClipEnable: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePrimitiveTopologyListRestartFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub primitiveTopologyListRestart: Bool32,
+    pub primitiveTopologyPatchListRestart: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceRobustness2FeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub robustBufferAccess2: Bool32,
+    pub robustImageAccess2: Bool32,
+    pub nullDescriptor: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceImageRobustnessFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub robustImageAccess: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePipelineRobustnessFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pipelineRobustness: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineRobustnessCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub storageBuffers: PipelineRobustnessBufferBehaviorEXT,
+    pub uniformBuffers: PipelineRobustnessBufferBehaviorEXT,
+    pub vertexInputs: PipelineRobustnessBufferBehaviorEXT,
+    pub images: PipelineRobustnessImageBehaviorEXT,
+}
+
+#[repr(C)]
+pub struct ValidationFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub enabledValidationFeatureCount: u32,
+    pub pEnabledValidationFeatures: *const ValidationFeatureEnableEXT,
+    pub disabledValidationFeatureCount: u32,
+    pub pDisabledValidationFeatures: *const ValidationFeatureDisableEXT,
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePipelineCreationCacheControlFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub pipelineCreationCacheControl: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceShaderModuleIdentifierFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub shaderModuleIdentifier: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceShaderModuleIdentifierPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub shaderModuleIdentifierAlgorithmUUID: [u8; UUID_SIZE as usize],
+}
+
+#[repr(C)]
+pub struct PipelineShaderStageModuleIdentifierCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub identifierSize: u32,
+    pub pIdentifier: *const u8,
+}
+
+#[repr(C)]
+pub struct ShaderModuleIdentifierEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub identifierSize: u32,
+    pub identifier: [u8; MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT as usize],
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMultiviewFeatures {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub multiview: Bool32,
+    pub multiviewGeometryShader: Bool32,
+    pub multiviewTessellationShader: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMultiviewProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxMultiviewViewCount: u32,
+    pub maxMultiviewInstanceIndex: u32,
+}
+
+#[repr(C)]
+pub struct RenderPassMultiviewCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub subpassCount: u32,
+    pub pViewMasks: *const u32,
+    pub dependencyCount: u32,
+    pub pViewOffsets: *const i32,
+    pub correlationMaskCount: u32,
+    pub pCorrelationMasks: *const u32,
+}
+
+#[repr(C)]
+pub struct SubpassDescriptionDepthStencilResolve {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub depthResolveMode: ResolveModeFlagBits,
+    pub stencilResolveMode: ResolveModeFlagBits,
+    pub pDepthStencilResolveAttachment: *const AttachmentReference2,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDepthStencilResolveProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub supportedDepthResolveModes: ResolveModeFlags,
+    pub supportedStencilResolveModes: ResolveModeFlags,
+    pub independentResolveNone: Bool32,
+    pub independentResolve: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceImagelessFramebufferFeatures {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub imagelessFramebuffer: Bool32,
+}
+
+#[repr(C)]
+pub struct FramebufferAttachmentImageInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: ImageCreateFlags,
+    pub usage: ImageUsageFlags,
+    pub width: u32,
+    pub height: u32,
+    pub layerCount: u32,
+    pub viewFormatCount: u32,
+    pub pViewFormats: *const Format,
+}
+
+#[repr(C)]
+pub struct FramebufferAttachmentsCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub attachmentImageInfoCount: u32,
+    pub pAttachmentImageInfos: *const FramebufferAttachmentImageInfo,
+}
+
+#[repr(C)]
+pub struct RenderPassAttachmentBeginInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub attachmentCount: u32,
+    pub pAttachments: *const ImageView,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceFragmentDensityMapFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub fragmentDensityMap: Bool32,
+    pub fragmentDensityMapDynamic: Bool32,
+    pub fragmentDensityMapNonSubsampledImages: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceFragmentDensityMapPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub minFragmentDensityTexelSize: Extent2D,
+    pub maxFragmentDensityTexelSize: Extent2D,
+    pub fragmentDensityInvocations: Bool32,
+}
+
+#[repr(C)]
+pub struct RenderPassFragmentDensityMapCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fragmentDensityMapAttachment: AttachmentReference,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMultisampledRenderToSingleSampledFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub multisampledRenderToSingleSampled: Bool32,
+}
+
+#[repr(C)]
+pub struct SubpassResolvePerformanceQueryEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub optimal: Bool32,
+}
+
+#[repr(C)]
+pub struct MultisampledRenderToSingleSampledInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub multisampledRenderToSingleSampledEnable: Bool32,
+    pub rasterizationSamples: SampleCountFlagBits,
+}
+
+#[repr(C)]
+pub struct XYColorEXT {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[repr(C)]
+pub struct HdrMetadataEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub displayPrimaryRed: XYColorEXT,
+    pub displayPrimaryGreen: XYColorEXT,
+    pub displayPrimaryBlue: XYColorEXT,
+    pub whitePoint: XYColorEXT,
+    pub maxLuminance: f32,
+    pub minLuminance: f32,
+    pub maxContentLightLevel: f32,
+    pub maxFrameAverageLightLevel: f32,
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePresentIdFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub presentId: Bool32,
+}
+
+#[repr(C)]
+pub struct PresentIdKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub swapchainCount: u32,
+    pub pPresentIds: *const u64,
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePresentWaitFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub presentWait: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceSwapchainMaintenance1FeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub swapchainMaintenance1: Bool32,
+}
+
+#[repr(C)]
+pub struct SwapchainPresentFenceInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub swapchainCount: u32,
+    pub pFences: *const Fence,
+}
+
+#[repr(C)]
+pub struct SwapchainPresentModesCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub presentModeCount: u32,
+    pub pPresentModes: *const PresentModeKHR,
+}
+
+#[repr(C)]
+pub struct SwapchainPresentModeInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub swapchainCount: u32,
+    pub pPresentModes: *const PresentModeKHR,
+}
+
+#[repr(C)]
+pub struct SwapchainPresentScalingCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub scalingBehavior: PresentScalingFlagsEXT,
+    pub presentGravityX: PresentGravityFlagsEXT,
+    pub presentGravityY: PresentGravityFlagsEXT,
+}
+
+#[repr(C)]
+pub struct ReleaseSwapchainImagesInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub swapchain: SwapchainKHR,
+    pub imageIndexCount: u32,
+    pub pImageIndices: *const u32,
+}
+
+#[repr(C)]
+pub struct RefreshCycleDurationGOOGLE {
+    pub refreshDuration: u64,
+}
+
+#[repr(C)]
+pub struct PastPresentationTimingGOOGLE {
+    pub presentID: u32,
+    pub desiredPresentTime: u64,
+    pub actualPresentTime: u64,
+    pub earliestPresentTime: u64,
+    pub presentMargin: u64,
+}
+
+#[repr(C)]
+pub struct PresentTimeGOOGLE {
+    pub presentID: u32,
+    pub desiredPresentTime: u64,
+}
+
+#[repr(C)]
+pub struct PresentTimesInfoGOOGLE {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub swapchainCount: u32,
+    pub pTimes: *const PresentTimeGOOGLE,
+}
+
+pub type DisplayPowerStateEXT = u32;
+pub const DISPLAY_POWER_STATE_OFF_EXT: u32 = 0;
+pub const DISPLAY_POWER_STATE_SUSPEND_EXT: u32 = 1;
+pub const DISPLAY_POWER_STATE_ON_EXT: u32 = 2;
+
+pub type DeviceEventTypeEXT = u32;
+pub const DEVICE_EVENT_TYPE_DISPLAY_HOTPLUG_EXT: u32 = 0;
+
+pub type DisplayEventTypeEXT = u32;
+pub const DISPLAY_EVENT_TYPE_FIRST_PIXEL_OUT_EXT: u32 = 0;
+
+pub type SurfaceCounterFlagBitsEXT = u32;
+pub const SURFACE_COUNTER_VBLANK_BIT_EXT: u32 = 0x00000001;
+pub type SurfaceCounterFlagsEXT = Flags;
+
+#[repr(C)]
+pub struct DisplayPowerInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub powerState: DisplayPowerStateEXT,
+}
+
+#[repr(C)]
+pub struct DeviceEventInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub deviceEvent: DeviceEventTypeEXT,
+}
+
+#[repr(C)]
+pub struct DisplayEventInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub displayEvent: DisplayEventTypeEXT,
+}
+
+#[repr(C)]
+pub struct SwapchainCounterCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub surfaceCounters: SurfaceCounterFlagsEXT,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTimelineSemaphoreFeatures {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub timelineSemaphore: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTimelineSemaphoreProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxTimelineSemaphoreValueDifference: u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreTypeCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphoreType: SemaphoreType,
+    pub initialValue: u64,
+}
+
+#[repr(C)]
+pub struct TimelineSemaphoreSubmitInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub waitSemaphoreValueCount: u32,
+    pub pWaitSemaphoreValues: *const u64,
+    pub signalSemaphoreValueCount: u32,
+    pub pSignalSemaphoreValues: *const u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreWaitInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: SemaphoreWaitFlags,
+    pub semaphoreCount: u32,
+    pub pSemaphores: *const Semaphore,
+    pub pValues: *const u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreSignalInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub value: u64,
+}
+
+#[repr(C)]
+pub struct ExportSemaphoreCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub handleTypes: ExternalSemaphoreHandleTypeFlags,
+}
+
+#[repr(C)]
+pub struct ImportSemaphoreFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub flags: SemaphoreImportFlags,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBits,
+    pub fd: i32,
+}
+
+#[repr(C)]
+pub struct SemaphoreGetFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBits,
+}
+
+#[repr(C)]
+pub struct ImportSemaphoreWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub flags: SemaphoreImportFlags,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBits,
+    pub handle: *mut c_void,
+    pub name: *const u16,
+}
+
+#[repr(C)]
+pub struct SemaphoreGetWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBits,
+}
+
+#[repr(C)]
+pub struct ExportFenceCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub handleTypes: ExternalFenceHandleTypeFlags,
+}
+
+#[repr(C)]
+pub struct ImportFenceFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fence: Fence,
+    pub flags: FenceImportFlags,
+    pub handleType: ExternalFenceHandleTypeFlagBits,
+    pub fd: i32,
+}
+
+#[repr(C)]
+pub struct FenceGetFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fence: Fence,
+    pub handleType: ExternalFenceHandleTypeFlagBits,
+}
+
+#[repr(C)]
+pub struct ImportFenceWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fence: Fence,
+    pub flags: FenceImportFlags,
+    pub handleType: ExternalFenceHandleTypeFlagBits,
+    pub handle: *mut c_void,
+    pub name: *const u16,
+}
+
+#[repr(C)]
+pub struct FenceGetWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fence: Fence,
+    pub handleType: ExternalFenceHandleTypeFlagBits,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMemoryBudgetPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub heapBudget: [DeviceSize; MAX_MEMORY_HEAPS as usize],
+    pub heapUsage: [DeviceSize; MAX_MEMORY_HEAPS as usize],
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMemoryPriorityFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub memoryPriority: Bool32,
+}
+
+#[repr(C)]
+pub struct MemoryPriorityAllocateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub priority: f32,
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePageableDeviceLocalMemoryFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub pageableDeviceLocalMemory: Bool32,
+}
+
+#[repr(C)]
+pub struct MemoryGetWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub memory: DeviceMemory,
+    pub handleType: ExternalMemoryHandleTypeFlagBits,
+}
+
+#[repr(C)]
+pub struct ImportMemoryHostPointerInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub handleType: ExternalMemoryHandleTypeFlagBits,
+    pub pHostPointer: *mut c_void,
+}
+
+#[repr(C)]
+pub struct MemoryHostPointerPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub memoryTypeBits: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceExternalMemoryHostPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub minImportedHostPointerAlignment: DeviceSize,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDeviceMemoryReportFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub deviceMemoryReport: Bool32,
+}
+
+#[repr(C)]
+pub struct DeviceDeviceMemoryReportCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: DeviceMemoryReportFlagsEXT,
+    pub pfnUserCallback: PFN_vkDeviceMemoryReportCallbackEXT,
+    pub pUserData: *mut c_void,
+}
+
+#[repr(C)]
+pub struct DeviceMemoryReportCallbackDataEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub flags: DeviceMemoryReportFlagsEXT,
+    pub ty: DeviceMemoryReportEventTypeEXT,
+    pub memoryObjectId: u64,
+    pub size: DeviceSize,
+    pub objectType: ObjectType,
+    pub objectHandle: u64,
+    pub heapIndex: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceGroupProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub physicalDeviceCount: u32,
+    pub physicalDevices: [PhysicalDevice; MAX_DEVICE_GROUP_SIZE as usize],
+    pub subsetAllocation: Bool32,
+}
+
+#[repr(C)]
+pub struct DeviceGroupDeviceCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub physicalDeviceCount: u32,
+    pub pPhysicalDevices: *const PhysicalDevice,
+}
+
+#[repr(C)]
+pub struct MemoryAllocateFlagsInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: MemoryAllocateFlags,
+    pub deviceMask: u32,
+}
+
+#[repr(C)]
+pub struct DeviceGroupCommandBufferBeginInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub deviceMask: u32,
+}
+
+#[repr(C)]
+pub struct DeviceQueueInfo2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: DeviceQueueCreateFlags,
+    pub queueFamilyIndex: u32,
+    pub queueIndex: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceProtectedMemoryFeatures {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub protectedMemory: Bool32,
+}
+
+// Extension functions that have been promoted to a core version of Vulkan with the same
+// behavior, and the name of the core function.
+//
+// The core name is only tried if the extension function isn't available, which allows using the
+// functionality of a promoted extension with a Vulkan version that includes it. Extension
+// functions whose core version behaves differently, such as `vkGetBufferDeviceAddressEXT`, must
+// not be in this list.
+const PROMOTED_FUNCTIONS: &[(&[u8], &[u8])] = &[
+    // Vulkan 1.1
+    (b"vkCmdSetDeviceMaskKHR", b"vkCmdSetDeviceMask"),
+    (b"vkEnumeratePhysicalDeviceGroupsKHR", b"vkEnumeratePhysicalDeviceGroups"),
+    (b"vkGetDeviceGroupPeerMemoryFeaturesKHR", b"vkGetDeviceGroupPeerMemoryFeatures"),
+    (b"vkGetBufferMemoryRequirements2KHR", b"vkGetBufferMemoryRequirements2"),
+    (b"vkGetImageMemoryRequirements2KHR", b"vkGetImageMemoryRequirements2"),
+    (b"vkGetPhysicalDeviceFeatures2KHR", b"vkGetPhysicalDeviceFeatures2"),
+    (b"vkGetPhysicalDeviceProperties2KHR", b"vkGetPhysicalDeviceProperties2"),
+    (b"vkGetPhysicalDeviceFormatProperties2KHR", b"vkGetPhysicalDeviceFormatProperties2"),
+    (b"vkGetPhysicalDeviceImageFormatProperties2KHR", b"vkGetPhysicalDeviceImageFormatProperties2"),
+    (b"vkGetPhysicalDeviceQueueFamilyProperties2KHR", b"vkGetPhysicalDeviceQueueFamilyProperties2"),
+    (b"vkGetPhysicalDeviceMemoryProperties2KHR", b"vkGetPhysicalDeviceMemoryProperties2"),
+    (b"vkGetPhysicalDeviceSparseImageFormatProperties2KHR", b"vkGetPhysicalDeviceSparseImageFormatProperties2"),
+    (b"vkTrimCommandPoolKHR", b"vkTrimCommandPool"),
+    (b"vkCreateDescriptorUpdateTemplateKHR", b"vkCreateDescriptorUpdateTemplate"),
+    (b"vkDestroyDescriptorUpdateTemplateKHR", b"vkDestroyDescriptorUpdateTemplate"),
+    (b"vkUpdateDescriptorSetWithTemplateKHR", b"vkUpdateDescriptorSetWithTemplate"),
+    // Vulkan 1.2
+    (b"vkCreateRenderPass2KHR", b"vkCreateRenderPass2"),
+    (b"vkGetSemaphoreCounterValueKHR", b"vkGetSemaphoreCounterValue"),
+    (b"vkWaitSemaphoresKHR", b"vkWaitSemaphores"),
+    (b"vkSignalSemaphoreKHR", b"vkSignalSemaphore"),
+    // Vulkan 1.3
+    (b"vkCmdSetCullModeEXT", b"vkCmdSetCullMode"),
+    (b"vkCmdSetFrontFaceEXT", b"vkCmdSetFrontFace"),
+    (b"vkCmdSetPrimitiveTopologyEXT", b"vkCmdSetPrimitiveTopology"),
+    (b"vkCmdSetDepthTestEnableEXT", b"vkCmdSetDepthTestEnable"),
+    (b"vkCmdSetDepthWriteEnableEXT", b"vkCmdSetDepthWriteEnable"),
+    (b"vkCmdSetDepthCompareOpEXT", b"vkCmdSetDepthCompareOp"),
+    (b"vkCmdSetRasterizerDiscardEnableEXT", b"vkCmdSetRasterizerDiscardEnable"),
+    (b"vkCmdSetDepthBiasEnableEXT", b"vkCmdSetDepthBiasEnable"),
+    (b"vkCmdSetPrimitiveRestartEnableEXT", b"vkCmdSetPrimitiveRestartEnable"),
+    (b"vkGetPhysicalDeviceToolPropertiesEXT", b"vkGetPhysicalDeviceToolProperties"),
+    (b"vkCreatePrivateDataSlotEXT", b"vkCreatePrivateDataSlot"),
+    (b"vkDestroyPrivateDataSlotEXT", b"vkDestroyPrivateDataSlot"),
+    (b"vkSetPrivateDataEXT", b"vkSetPrivateData"),
+    (b"vkGetPrivateDataEXT", b"vkGetPrivateData"),
+];
+
+// Returns the name of the core function that an extension function has been promoted to.
+fn core_name(name: &CStr) -> Option<CString> {
+    PROMOTED_FUNCTIONS
+        .iter()
+        .find(|&&(ext, _)| ext == name.to_bytes())
+        .map(|&(_, core)| CString::new(core).unwrap())
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePortabilitySubsetFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub constantAlphaColorBlendFactors: Bool32,
+    pub events: Bool32,
+    pub imageViewFormatReinterpretation: Bool32,
+    pub imageViewFormatSwizzle: Bool32,
+    pub imageView2DOn3DImage: Bool32,
+    pub multisampleArrayImage: Bool32,
+    pub mutableComparisonSamplers: Bool32,
+    pub pointPolygons: Bool32,
+    pub samplerMipLodBias: Bool32,
+    pub separateStencilMaskRef: Bool32,
+    pub shaderSampleRateInterpolationFunctions: Bool32,
+    pub tessellationIsolines: Bool32,
+    pub tessellationPointMode: Bool32,
+    pub triangleFans: Bool32,
+    pub vertexAttributeAccessBeyondStride: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePortabilitySubsetPropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub minVertexInputBindingStrideAlignment: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceFaultFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub deviceFault: Bool32,
+    pub deviceFaultVendorBinary: Bool32,
+}
+
+#[repr(C)]
+pub struct DeviceFaultCountsEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub addressInfoCount: u32,
+    pub vendorInfoCount: u32,
+    pub vendorBinarySize: DeviceSize,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct DeviceFaultAddressInfoEXT {
+    pub addressType: DeviceFaultAddressTypeEXT,
+    pub reportedAddress: DeviceAddress,
+    pub addressPrecision: DeviceSize,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct DeviceFaultVendorInfoEXT {
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub vendorFaultCode: u64,
+    pub vendorFaultData: u64,
+}
+
+#[repr(C)]
+pub struct DeviceFaultInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub pAddressInfos: *mut DeviceFaultAddressInfoEXT,
+    pub pVendorInfos: *mut DeviceFaultVendorInfoEXT,
+    pub pVendorBinaryData: *mut c_void,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ConformanceVersion {
+    pub major: u8,
+    pub minor: u8,
+    pub subminor: u8,
+    pub patch: u8,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDriverProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub driverID: DriverId,
+    pub driverName: [c_char; MAX_DRIVER_NAME_SIZE as usize],
+    pub driverInfo: [c_char; MAX_DRIVER_INFO_SIZE as usize],
+    pub conformanceVersion: ConformanceVersion,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceToolPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub name: [c_char; MAX_EXTENSION_NAME_SIZE as usize],
+    pub version: [c_char; MAX_EXTENSION_NAME_SIZE as usize],
+    pub purposes: ToolPurposeFlagsEXT,
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub layer: [c_char; MAX_EXTENSION_NAME_SIZE as usize],
+}
+
+#[repr(C)]
+pub struct PhysicalDevicePrivateDataFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub privateData: Bool32,
+}
+
+#[repr(C)]
+pub struct DevicePrivateDataCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub privateDataSlotRequestCount: u32,
+}
+
+#[repr(C)]
+pub struct PrivateDataSlotCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: PrivateDataSlotCreateFlagsEXT,
+}
+
+#[repr(C)]
+pub struct LayerSettingEXT {
+    pub pLayerName: *const c_char,
+    pub pSettingName: *const c_char,
+    pub ty: LayerSettingTypeEXT,
+    pub valueCount: u32,
+    pub pValues: *const c_void,
+}
+
+#[repr(C)]
+pub struct LayerSettingsCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub settingCount: u32,
+    pub pSettings: *const LayerSettingEXT,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceProtectedMemoryProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub protectedNoFault: Bool32,
+}
+
+#[repr(C)]
+pub struct ProtectedSubmitInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub protectedSubmit: Bool32,
+}
+
 #[macro_export]
 macro_rules! ptrs {
     ($struct_name:ident, { $($name:ident => ($($param_n:ident: $param_ty:ty),*) -> $ret:ty,)+ }) => (
@@ -3634,14 +5388,24 @@ macro_rules! ptrs {
                         $name: unsafe {
                             extern "system" fn $name($(_: $param_ty),*) { panic!("function pointer `{}` not loaded", stringify!($name)) }
                             let name = CStr::from_bytes_with_nul_unchecked(concat!("vk", stringify!($name), "\0").as_bytes());
-                            let val = f(name);
-                            if val.is_null() { mem::transmute($name as *const ()) } else { mem::transmute(val) }
+                            let mut val = f(name);
+                            if val.is_null() {
+                                if let Some(core) = core_name(name) { val = f(&core); }
+                            }
+                            if val.is_null() {
+                                mem::transmute::<*const (), extern "system" fn($($param_ty),*) -> $ret>($name as *const ())
+                            } else {
+                                mem::transmute::<*const c_void, extern "system" fn($($param_ty),*) -> $ret>(val)
+                            }
                         },
                     )+
                 }
             }
 
             $(
+                /// # Safety
+                ///
+                /// The parameters must satisfy the valid usage rules of the Vulkan command.
                 #[inline]
                 pub unsafe fn $name(&self $(, $param_n: $param_ty)*) -> $ret {
                     let ptr = self.$name;
@@ -3695,6 +5459,7 @@ ptrs!(InstancePointers, {
     CreateDisplayPlaneSurfaceKHR => (instance: Instance, pCreateInfo: *const DisplaySurfaceCreateInfoKHR, pAllocator: *const AllocationCallbacks, pSurface: *mut SurfaceKHR) -> Result,
     GetPhysicalDeviceSurfaceSupportKHR => (physicalDevice: PhysicalDevice, queueFamilyIndex: u32, surface: SurfaceKHR, pSupported: *mut Bool32) -> Result,
     GetPhysicalDeviceSurfaceCapabilitiesKHR => (physicalDevice: PhysicalDevice, surface: SurfaceKHR, pSurfaceCapabilities: *mut SurfaceCapabilitiesKHR) -> Result,
+    GetPhysicalDeviceSurfaceCapabilities2KHR => (physicalDevice: PhysicalDevice, pSurfaceInfo: *const PhysicalDeviceSurfaceInfo2KHR, pSurfaceCapabilities: *mut SurfaceCapabilities2KHR) -> Result,
     GetPhysicalDeviceSurfaceFormatsKHR => (physicalDevice: PhysicalDevice, surface: SurfaceKHR, pSurfaceFormatCount: *mut u32, pSurfaceFormats: *mut SurfaceFormatKHR) -> Result,
     GetPhysicalDeviceSurfacePresentModesKHR => (physicalDevice: PhysicalDevice, surface: SurfaceKHR, pPresentModeCount: *mut u32, pPresentModes: *mut PresentModeKHR) -> Result,
     CreateDebugUtilsMessengerEXT => (instance: Instance, pCreateInfo: *const DebugUtilsMessengerCreateInfoEXT, pAllocator: *const AllocationCallbacks, pMessenger: *const DebugUtilsMessengerEXT) -> Result,
@@ -3708,6 +5473,7 @@ ptrs!(InstancePointers, {
     GetPhysicalDeviceMetalFeaturesMVK => (physicalDevice: PhysicalDevice, pMetalFeatures: *mut MVKPhysicalDeviceMetalFeatures) -> Result,
     GetSwapchainPerformanceMVK => (device: Device, swapchain: SwapchainKHR, pSwapchainPerf: *mut MVKSwapchainPerformance) -> Result,
     CreateViSurfaceNN => (instance: Instance, pCreateInfo: *const ViSurfaceCreateInfoNN, pAllocator: *const AllocationCallbacks, pSurface: *mut SurfaceKHR) -> Result,
+    CreateHeadlessSurfaceEXT => (instance: Instance, pCreateInfo: *const HeadlessSurfaceCreateInfoEXT, pAllocator: *const AllocationCallbacks, pSurface: *mut SurfaceKHR) -> Result,
     GetPhysicalDeviceFeatures2KHR => (physicalDevice: PhysicalDevice, pFeatures: *mut PhysicalDeviceFeatures2KHR) -> (),
     GetPhysicalDeviceProperties2KHR => (physicalDevice: PhysicalDevice, pProperties: *mut PhysicalDeviceProperties2KHR) -> (),
     GetPhysicalDeviceFormatProperties2KHR => (physicalDevice: PhysicalDevice, pFormatProperties: *mut FormatProperties2KHR) -> (),
@@ -3715,6 +5481,9 @@ ptrs!(InstancePointers, {
     GetPhysicalDeviceQueueFamilyProperties2KHR => (physicalDevice: PhysicalDevice, pQueueFamilyPropertiesCount: *mut u32, pQueueFamilyProperties: *mut QueueFamilyProperties2KHR) -> (),
     GetPhysicalDeviceMemoryProperties2KHR => (physicalDevice: PhysicalDevice, pMemoryProperties: *mut PhysicalDeviceMemoryProperties2KHR) -> (),
     GetPhysicalDeviceSparseImageFormatProperties2KHR => (physicalDevice: PhysicalDevice, pFormatInfo: *const PhysicalDeviceSparseImageFormatInfo2KHR, pPropertyCount: *mut u32, pProperties: *mut SparseImageFormatProperties2KHR) -> (),
+    GetPhysicalDeviceMultisamplePropertiesEXT => (physicalDevice: PhysicalDevice, samples: SampleCountFlagBits, pMultisampleProperties: *mut MultisamplePropertiesEXT) -> (),
+    EnumeratePhysicalDeviceGroupsKHR => (instance: Instance, pPhysicalDeviceGroupCount: *mut u32, pPhysicalDeviceGroupProperties: *mut PhysicalDeviceGroupProperties) -> Result,
+    GetPhysicalDeviceToolPropertiesEXT => (physicalDevice: PhysicalDevice, pToolCount: *mut u32, pToolProperties: *mut PhysicalDeviceToolPropertiesEXT) -> Result,
 });
 
 ptrs!(DevicePointers, {
@@ -3861,4 +5630,61 @@ ptrs!(DevicePointers, {
     GetBufferDeviceAddressEXT => (device: Device, pInfo: *const BufferDeviceAddressInfo) -> DeviceAddress,
     GetMemoryFdKHR => (device: Device, pGetFdInfo: *const MemoryGetFdInfoKHR, pFd: *mut i32) -> Result,
     GetMemoryFdPropertiesKHR => (device: Device, handleType: ExternalMemoryHandleTypeFlagBits, fd: i32, pMemoryFdProperties: *mut MemoryFdPropertiesKHR) -> Result,
+    CreateRenderPass2KHR => (device: Device, pCreateInfo: *const RenderPassCreateInfo2, pAllocator: *const AllocationCallbacks, pRenderPass: *mut RenderPass) -> Result,
+    CmdSetFragmentShadingRateKHR => (commandBuffer: CommandBuffer, pFragmentSize: *const Extent2D, combinerOps: *const FragmentShadingRateCombinerOpKHR) -> (),
+    CmdSetCullModeEXT => (commandBuffer: CommandBuffer, cullMode: CullModeFlags) -> (),
+    CmdSetFrontFaceEXT => (commandBuffer: CommandBuffer, frontFace: FrontFace) -> (),
+    CmdSetPrimitiveTopologyEXT => (commandBuffer: CommandBuffer, primitiveTopology: PrimitiveTopology) -> (),
+    CmdSetDepthTestEnableEXT => (commandBuffer: CommandBuffer, depthTestEnable: Bool32) -> (),
+    CmdSetDepthWriteEnableEXT => (commandBuffer: CommandBuffer, depthWriteEnable: Bool32) -> (),
+    CmdSetDepthCompareOpEXT => (commandBuffer: CommandBuffer, depthCompareOp: CompareOp) -> (),
+    CmdSetRasterizerDiscardEnableEXT => (commandBuffer: CommandBuffer, rasterizerDiscardEnable: Bool32) -> (),
+    CmdSetDepthBiasEnableEXT => (commandBuffer: CommandBuffer, depthBiasEnable: Bool32) -> (),
+    CmdSetLogicOpEXT => (commandBuffer: CommandBuffer, logicOp: LogicOp) -> (),
+    CmdSetPrimitiveRestartEnableEXT => (commandBuffer: CommandBuffer, primitiveRestartEnable: Bool32) -> (),
+    CmdSetColorBlendEnableEXT => (commandBuffer: CommandBuffer, firstAttachment: u32, attachmentCount: u32, pColorBlendEnables: *const Bool32) -> (),
+    CmdSetColorBlendEquationEXT => (commandBuffer: CommandBuffer, firstAttachment: u32, attachmentCount: u32, pColorBlendEquations: *const ColorBlendEquationEXT) -> (),
+    CmdSetColorWriteMaskEXT => (commandBuffer: CommandBuffer, firstAttachment: u32, attachmentCount: u32, pColorWriteMasks: *const ColorComponentFlags) -> (),
+    CreateShadersEXT => (device: Device, createInfoCount: u32, pCreateInfos: *const ShaderCreateInfoEXT, pAllocator: *const AllocationCallbacks, pShaders: *mut ShaderEXT) -> Result,
+    DestroyShaderEXT => (device: Device, shader: ShaderEXT, pAllocator: *const AllocationCallbacks) -> (),
+    CmdBindShadersEXT => (commandBuffer: CommandBuffer, stageCount: u32, pStages: *const ShaderStageFlagBits, pShaders: *const ShaderEXT) -> (),
+    GetPipelineExecutablePropertiesKHR => (device: Device, pPipelineInfo: *const PipelineInfoKHR, pExecutableCount: *mut u32, pProperties: *mut PipelineExecutablePropertiesKHR) -> Result,
+    GetPipelineExecutableStatisticsKHR => (device: Device, pExecutableInfo: *const PipelineExecutableInfoKHR, pStatisticCount: *mut u32, pStatistics: *mut PipelineExecutableStatisticKHR) -> Result,
+    GetPipelineExecutableInternalRepresentationsKHR => (device: Device, pExecutableInfo: *const PipelineExecutableInfoKHR, pInternalRepresentationCount: *mut u32, pInternalRepresentations: *mut PipelineExecutableInternalRepresentationKHR) -> Result,
+    CmdSetLineStippleEXT => (commandBuffer: CommandBuffer, lineStippleFactor: u32, lineStipplePattern: u16) -> (),
+    CmdSetSampleLocationsEXT => (commandBuffer: CommandBuffer, pSampleLocationsInfo: *const SampleLocationsInfoEXT) -> (),
+    CmdSetColorWriteEnableEXT => (commandBuffer: CommandBuffer, attachmentCount: u32, pColorWriteEnables: *const Bool32) -> (),
+    GetShaderModuleIdentifierEXT => (device: Device, shaderModule: ShaderModule, pIdentifier: *mut ShaderModuleIdentifierEXT) -> (),
+    GetShaderModuleCreateInfoIdentifierEXT => (device: Device, pCreateInfo: *const ShaderModuleCreateInfo, pIdentifier: *mut ShaderModuleIdentifierEXT) -> (),
+    SetHdrMetadataEXT => (device: Device, swapchainCount: u32, pSwapchains: *const SwapchainKHR, pMetadata: *const HdrMetadataEXT) -> (),
+    WaitForPresentKHR => (device: Device, swapchain: SwapchainKHR, presentId: u64, timeout: u64) -> Result,
+    ReleaseSwapchainImagesEXT => (device: Device, pReleaseInfo: *const ReleaseSwapchainImagesInfoEXT) -> Result,
+    GetRefreshCycleDurationGOOGLE => (device: Device, swapchain: SwapchainKHR, pDisplayTimingProperties: *mut RefreshCycleDurationGOOGLE) -> Result,
+    GetPastPresentationTimingGOOGLE => (device: Device, swapchain: SwapchainKHR, pPresentationTimingCount: *mut u32, pPresentationTimings: *mut PastPresentationTimingGOOGLE) -> Result,
+    DisplayPowerControlEXT => (device: Device, display: DisplayKHR, pDisplayPowerInfo: *const DisplayPowerInfoEXT) -> Result,
+    RegisterDeviceEventEXT => (device: Device, pDeviceEventInfo: *const DeviceEventInfoEXT, pAllocator: *const AllocationCallbacks, pFence: *mut Fence) -> Result,
+    RegisterDisplayEventEXT => (device: Device, display: DisplayKHR, pDisplayEventInfo: *const DisplayEventInfoEXT, pAllocator: *const AllocationCallbacks, pFence: *mut Fence) -> Result,
+    GetSwapchainCounterEXT => (device: Device, swapchain: SwapchainKHR, counter: SurfaceCounterFlagBitsEXT, pCounterValue: *mut u64) -> Result,
+    GetSemaphoreCounterValueKHR => (device: Device, semaphore: Semaphore, pValue: *mut u64) -> Result,
+    WaitSemaphoresKHR => (device: Device, pWaitInfo: *const SemaphoreWaitInfo, timeout: u64) -> Result,
+    SignalSemaphoreKHR => (device: Device, pSignalInfo: *const SemaphoreSignalInfo) -> Result,
+    ImportSemaphoreFdKHR => (device: Device, pImportSemaphoreFdInfo: *const ImportSemaphoreFdInfoKHR) -> Result,
+    GetSemaphoreFdKHR => (device: Device, pGetFdInfo: *const SemaphoreGetFdInfoKHR, pFd: *mut i32) -> Result,
+    ImportSemaphoreWin32HandleKHR => (device: Device, pImportSemaphoreWin32HandleInfo: *const ImportSemaphoreWin32HandleInfoKHR) -> Result,
+    GetSemaphoreWin32HandleKHR => (device: Device, pGetWin32HandleInfo: *const SemaphoreGetWin32HandleInfoKHR, pHandle: *mut *mut c_void) -> Result,
+    ImportFenceFdKHR => (device: Device, pImportFenceFdInfo: *const ImportFenceFdInfoKHR) -> Result,
+    GetFenceFdKHR => (device: Device, pGetFdInfo: *const FenceGetFdInfoKHR, pFd: *mut i32) -> Result,
+    ImportFenceWin32HandleKHR => (device: Device, pImportFenceWin32HandleInfo: *const ImportFenceWin32HandleInfoKHR) -> Result,
+    GetFenceWin32HandleKHR => (device: Device, pGetWin32HandleInfo: *const FenceGetWin32HandleInfoKHR, pHandle: *mut *mut c_void) -> Result,
+    SetDeviceMemoryPriorityEXT => (device: Device, memory: DeviceMemory, priority: f32) -> (),
+    GetMemoryWin32HandleKHR => (device: Device, pGetWin32HandleInfo: *const MemoryGetWin32HandleInfoKHR, pHandle: *mut *mut c_void) -> Result,
+    GetMemoryHostPointerPropertiesEXT => (device: Device, handleType: ExternalMemoryHandleTypeFlagBits, pHostPointer: *const c_void, pMemoryHostPointerProperties: *mut MemoryHostPointerPropertiesEXT) -> Result,
+    GetDeviceGroupPeerMemoryFeaturesKHR => (device: Device, heapIndex: u32, localDeviceIndex: u32, remoteDeviceIndex: u32, pPeerMemoryFeatures: *mut PeerMemoryFeatureFlags) -> (),
+    CmdSetDeviceMaskKHR => (commandBuffer: CommandBuffer, deviceMask: u32) -> (),
+    GetDeviceQueue2 => (device: Device, pQueueInfo: *const DeviceQueueInfo2, pQueue: *mut Queue) -> (),
+    GetDeviceFaultInfoEXT => (device: Device, pFaultCounts: *mut DeviceFaultCountsEXT, pFaultInfo: *mut DeviceFaultInfoEXT) -> Result,
+    CreatePrivateDataSlotEXT => (device: Device, pCreateInfo: *const PrivateDataSlotCreateInfoEXT, pAllocator: *const AllocationCallbacks, pPrivateDataSlot: *mut PrivateDataSlotEXT) -> Result,
+    DestroyPrivateDataSlotEXT => (device: Device, privateDataSlot: PrivateDataSlotEXT, pAllocator: *const AllocationCallbacks) -> (),
+    SetPrivateDataEXT => (device: Device, objectType: ObjectType, objectHandle: u64, privateDataSlot: PrivateDataSlotEXT, data: u64) -> Result,
+    GetPrivateDataEXT => (device: Device, objectType: ObjectType, objectHandle: u64, privateDataSlot: PrivateDataSlotEXT, pData: *mut u64) -> (),
 });
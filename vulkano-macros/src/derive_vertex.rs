@@ -0,0 +1,97 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Fields;
+use syn::Ident;
+use syn::LitStr;
+use syn::Result;
+use syn::Token;
+
+pub fn derive_vertex(ast: DeriveInput) -> Result<TokenStream> {
+    let struct_name = &ast.ident;
+
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    data.fields.span(),
+                    "`#[derive(Vertex)]` requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                ast.span(),
+                "`#[derive(Vertex)]` can only be used on structs",
+            ))
+        }
+    };
+
+    let mut members = Vec::with_capacity(fields.len());
+    for field in fields.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_ty = &field.ty;
+
+        // By default, the member is matched by its field name.
+        let mut names = vec![LitStr::new(&field_name.to_string(), field_name.span())];
+        let mut format = quote! { None };
+
+        for attr in field.attrs.iter() {
+            if attr.path.is_ident("name") {
+                let custom_names =
+                    attr.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?;
+                names = custom_names.into_iter().collect();
+            } else if attr.path.is_ident("format") {
+                let format_ident: Ident = attr.parse_args()?;
+                format = quote! { Some(::vulkano::format::Format::#format_ident) };
+            }
+        }
+
+        members.push(quote! {
+            if #(name == #names)||* {
+                let dummy = ::std::mem::MaybeUninit::<Self>::uninit();
+                let dummy_ptr = dummy.as_ptr();
+                let member_ptr = unsafe { ::std::ptr::addr_of!((*dummy_ptr).#field_name) };
+                let (ty, array_size) =
+                    <#field_ty as ::vulkano::pipeline::vertex::VertexMember>::format();
+
+                return Some(::vulkano::pipeline::vertex::VertexMemberInfo {
+                    offset: member_ptr as usize - dummy_ptr as usize,
+                    ty,
+                    array_size,
+                    format: #format,
+                });
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    Ok(quote! {
+        #[allow(unsafe_code)]
+        unsafe impl #impl_generics ::vulkano::pipeline::vertex::Vertex
+            for #struct_name #ty_generics #where_clause
+        {
+            #[inline(always)]
+            fn member(name: &str) -> Option<::vulkano::pipeline::vertex::VertexMemberInfo> {
+                #(#members)*
+
+                None
+            }
+        }
+    })
+}
@@ -0,0 +1,33 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Derive macros for vulkano.
+//!
+//! This crate is re-exported by vulkano, and shouldn't be used directly. See the documentation of
+//! `vulkano::pipeline::vertex::Vertex` for the usage of `#[derive(Vertex)]`.
+
+#![doc(html_logo_url = "https://raw.githubusercontent.com/vulkano-rs/vulkano/master/logo.png")]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
+use syn::DeriveInput;
+
+mod derive_vertex;
+
+/// Implements the `Vertex` trait on a struct.
+#[proc_macro_derive(Vertex, attributes(name, format))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    derive_vertex::derive_vertex(ast)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
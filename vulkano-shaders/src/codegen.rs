@@ -271,7 +271,7 @@ where
     for instruction in doc.instructions.iter() {
         if let &Instruction::EntryPoint { .. } = instruction {
             let (outside, entry_point, descriptor_sets) =
-                entry_point::write_entry_point(&doc, instruction, &types_meta);
+                entry_point::write_entry_point(&doc, instruction, generate_structs, &types_meta);
             entry_points_inside_impl.push(entry_point);
             entry_points_outside_impl.push(outside);
             entry_points_outside_impl.push(descriptor_sets);
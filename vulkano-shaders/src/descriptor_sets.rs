@@ -28,8 +28,10 @@ struct Descriptor {
 pub(super) fn write_descriptor_sets(
     doc: &Spirv,
     entry_point_layout_name: &Ident,
+    push_constants_name: &Ident,
     entrypoint_id: u32,
     interface: &[u32],
+    generate_structs: bool,
     types_meta: &TypesMeta,
 ) -> TokenStream {
     // TODO: somewhat implemented correctly
@@ -39,6 +41,7 @@ pub(super) fn write_descriptor_sets(
 
     // Looping to find all the push constant structs.
     let mut push_constants_size = 0;
+    let mut push_constants_ty = None;
     for instruction in doc.instructions.iter() {
         let type_id = match instruction {
             &Instruction::TypePointer {
@@ -49,9 +52,12 @@ pub(super) fn write_descriptor_sets(
             _ => continue,
         };
 
-        let (_, size, _) = crate::structs::type_from_id(doc, type_id, types_meta);
+        let (ty, size, _) = crate::structs::type_from_id(doc, type_id, types_meta);
         let size = size.expect("Found runtime-sized push constants");
-        push_constants_size = cmp::max(push_constants_size, size);
+        if size >= push_constants_size {
+            push_constants_size = size;
+            push_constants_ty = Some(ty);
+        }
     }
 
     // Writing the body of the `descriptor` method.
@@ -103,7 +109,20 @@ pub(super) fn write_descriptor_sets(
         }
     );
 
+    // Writing the name of the type of the push constants expected by the entry point.
+    let push_constants = match push_constants_ty {
+        Some(ty) if generate_structs => quote! {
+            pub type #push_constants_name = ty::#ty;
+        },
+        Some(_) => quote! {},
+        None => quote! {
+            pub type #push_constants_name = ();
+        },
+    };
+
     quote! {
+        #push_constants
+
         #[derive(Debug, Clone)]
         pub struct #entry_point_layout_name(pub ShaderStages);
 
@@ -18,6 +18,7 @@ use crate::{spirv_search, TypesMeta};
 pub(super) fn write_entry_point(
     doc: &Spirv,
     instruction: &Instruction,
+    generate_structs: bool,
     types_meta: &TypesMeta,
 ) -> (TokenStream, TokenStream, TokenStream) {
     let (execution, id, ep_name, interface) = match instruction {
@@ -61,11 +62,17 @@ pub(super) fn write_entry_point(
         format!("{}Layout", capitalized_ep_name).as_str(),
         Span::call_site(),
     );
+    let push_constants_name = Ident::new(
+        format!("{}PushConstants", capitalized_ep_name).as_str(),
+        Span::call_site(),
+    );
     let descriptor_sets_layout_struct = write_descriptor_sets(
         &doc,
         &descriptor_sets_layout_name,
+        &push_constants_name,
         id,
         interface,
+        generate_structs,
         &types_meta,
     );
 
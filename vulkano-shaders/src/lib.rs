@@ -57,11 +57,56 @@
 //! * The `Layout` newtype. This contains a [`ShaderStages`][ShaderStages] struct.
 //! An implementation of [`PipelineLayoutDesc`][PipelineLayoutDesc] is also
 //! generated for the newtype.
+//! * A `PushConstants` type alias for each entry point, prefixed by the
+//! capitalized name of the entry point (for example `MainPushConstants`). It
+//! points to the generated struct of the push constants block, or is `()` if
+//! the entry point has none. The alias only names the type; it is not checked
+//! against the push constants that are passed when drawing.
 //! * The `SpecializationConstants` struct. This contains a field for every
 //! specialization constant found in the shader data. Implementations of
 //! `Default` and [`SpecializationConstants`][SpecializationConstants] are also
 //! generated for the struct.
 //!
+//! For example, the push constants of the `main` entry point below can be
+//! built through the alias:
+//!
+//! ```
+//! # fn main() {}
+//! mod vs {
+//!     vulkano_shaders::shader!{
+//!         ty: "vertex",
+//!         src: "
+//! #version 450
+//!
+//! layout(push_constant) uniform PushConstantData {
+//!     int multiple;
+//!     float addend;
+//! } pc;
+//!
+//! void main() {
+//!     gl_Position = vec4(pc.addend * pc.multiple);
+//! }"
+//!     }
+//! }
+//! # mod fs {
+//! #     vulkano_shaders::shader!{
+//! #         ty: "fragment",
+//! #         src: "
+//! # #version 450
+//! #
+//! # void main() {}"
+//! #     }
+//! # }
+//!
+//! fn push_constants() -> vs::MainPushConstants {
+//!     vs::ty::PushConstantData {
+//!         multiple: 2,
+//!         addend: 1.0,
+//!     }
+//! }
+//! # fn no_push_constants() -> fs::MainPushConstants {}
+//! ```
+//!
 //! All of these generated items will be accessed through the module specified
 //! by `mod_name: foo` If you wanted to store the `Shader` in a struct of your own,
 //! you could do something like this:
@@ -11,11 +11,84 @@ use std::sync::Arc;
 
 use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 use vulkano::instance::Instance;
+use vulkano::instance::InstanceExtensions;
 use vulkano::swapchain::Surface;
 use vulkano::swapchain::SurfaceCreationError;
 
+/// Returns the instance extensions that are needed to create a surface for the given window with
+/// `create_vk_surface_from_handle`.
+///
+/// This works with any windowing library that implements `HasRawWindowHandle`, such as winit or
+/// sdl2. Only the extensions that are supported by the Vulkan implementation are returned.
+pub fn required_extensions_for_handle<W>(window: &W) -> InstanceExtensions
+where
+    W: HasRawWindowHandle,
+{
+    let ideal = ideal_extensions(window.raw_window_handle());
+
+    match InstanceExtensions::supported_by_core() {
+        Ok(supported) => supported.intersection(&ideal),
+        Err(_) => InstanceExtensions::none(),
+    }
+}
+
+// Returns the extensions that are needed for the given kind of window, whether or not they are
+// supported.
+fn ideal_extensions(handle: RawWindowHandle) -> InstanceExtensions {
+    let mut ideal = InstanceExtensions {
+        khr_surface: true,
+        khr_get_physical_device_properties2: true,
+        khr_get_surface_capabilities2: true,
+        ..InstanceExtensions::none()
+    };
+
+    match handle {
+        #[cfg(target_os = "ios")]
+        RawWindowHandle::IOS(_) => ideal.mvk_ios_surface = true,
+        #[cfg(target_os = "macos")]
+        RawWindowHandle::MacOS(_) => ideal.mvk_macos_surface = true,
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonflybsd",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        RawWindowHandle::Xlib(_) => ideal.khr_xlib_surface = true,
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonflybsd",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        RawWindowHandle::Xcb(_) => ideal.khr_xcb_surface = true,
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonflybsd",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        RawWindowHandle::Wayland(_) => ideal.khr_wayland_surface = true,
+        #[cfg(target_os = "android")]
+        RawWindowHandle::Android(_) => ideal.khr_android_surface = true,
+        #[cfg(target_os = "windows")]
+        RawWindowHandle::Windows(_) => ideal.khr_win32_surface = true,
+        _ => (),
+    }
+
+    ideal
+}
+
 /// Creates a vulkan surface from a generic window
-/// which implements HasRawWindowHandle and thus can reveal the os-dependent handle
+/// which implements HasRawWindowHandle and thus can reveal the os-dependent handle.
+///
+/// The instance must have been created with the extensions returned by
+/// `required_extensions_for_handle`. If the kind of window isn't supported on this platform,
+/// `SurfaceCreationError::UnsupportedWindowHandle` is returned.
+///
+/// > **Note**: On macOS and iOS, the view must be backed by a `CAMetalLayer`.
 pub fn create_vk_surface_from_handle<W>(
     window: W,
     instance: Arc<Instance>,
@@ -63,9 +136,7 @@ where
             RawWindowHandle::Android(h) => handle_to_surface(h.a_native_window, instance, window),
             #[cfg(target_os = "windows")]
             RawWindowHandle::Windows(h) => handle_to_surface(h.hinstance, h.hwnd, instance, window),
-            #[cfg(target_os = "wasm")]
-            RawWindowHandle::Web(_) => unimplemented!(),
-            _ => unimplemented!(),
+            _ => Err(SurfaceCreationError::UnsupportedWindowHandle),
         }
     }
 }
@@ -154,3 +225,52 @@ unsafe fn handle_to_surface<W: Sized>(
 ) -> Result<Arc<Surface<W>>, SurfaceCreationError> {
     Surface::from_hwnd(instance, hinstance as *const _, hwnd as *const _, win)
 }
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod tests {
+    use super::*;
+    use raw_window_handle::unix::WaylandHandle;
+    use raw_window_handle::unix::XlibHandle;
+
+    struct Window(RawWindowHandle);
+
+    unsafe impl HasRawWindowHandle for Window {
+        fn raw_window_handle(&self) -> RawWindowHandle {
+            self.0
+        }
+    }
+
+    #[test]
+    fn extensions_of_handle() {
+        let xlib = ideal_extensions(RawWindowHandle::Xlib(XlibHandle::empty()));
+        assert!(xlib.khr_surface);
+        assert!(xlib.khr_xlib_surface);
+        assert!(!xlib.khr_xcb_surface && !xlib.khr_wayland_surface);
+
+        let wayland = ideal_extensions(RawWindowHandle::Wayland(WaylandHandle::empty()));
+        assert!(wayland.khr_wayland_surface);
+        assert!(!wayland.khr_xlib_surface);
+
+        // Only the supported extensions are required.
+        let window = Window(RawWindowHandle::Xlib(XlibHandle::empty()));
+        let required = required_extensions_for_handle(&window);
+        assert_eq!(required.intersection(&xlib), required);
+    }
+
+    #[test]
+    fn missing_surface_extension() {
+        let instance = match Instance::new(None, &InstanceExtensions::none(), None) {
+            Ok(instance) => instance,
+            Err(_) => return,
+        };
+
+        let window = Window(RawWindowHandle::Xlib(XlibHandle::empty()));
+        match create_vk_surface_from_handle(window, instance) {
+            Err(SurfaceCreationError::MissingExtension {
+                name: "VK_KHR_xlib_surface",
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-// Copyright (c) 2016 The vulkano developers
+// Copyright (c) 2021 The vulkano developers
 // Licensed under the Apache License, Version 2.0
 // <LICENSE-APACHE or
 // https://www.apache.org/licenses/LICENSE-2.0> or the MIT
@@ -7,17 +7,369 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use std::mem;
+//! Host memory allocation callbacks.
+//!
+//! By default, the Vulkan implementation allocates the host memory that it needs on its own. If
+//! you want to track or control these allocations, for example on platforms where host memory is
+//! scarce, you can implement the `Alloc` trait and pass it to `Instance::with_alloc` or to
+//! `Device::with_alloc`. The callbacks are then used for the creation and destruction of every
+//! object that belongs to the instance or to the device.
+
+use std::alloc::Layout;
+use std::collections::HashMap;
+use std::fmt;
 use std::os::raw::c_void;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use crate::vk;
 
+/// Allocator of host memory used by the Vulkan implementation.
+///
+/// # Safety
+///
+/// - `alloc` and `realloc` must return a non-null pointer to a block of at least `layout.size()`
+///   bytes aligned to `layout.align()`, or `None` if the allocation failed.
+/// - `realloc` must preserve the content of the original block, up to the smaller of the two
+///   sizes.
+/// - The functions can be called from any thread, at any time.
+///
 pub unsafe trait Alloc {
-    fn alloc(&self, size: usize, alignment: usize) -> Result<*mut c_void, ()>;
+    /// Allocates a block of memory.
+    ///
+    /// The size of `layout` is never 0.
+    fn alloc(&self, layout: Layout) -> Option<*mut c_void>;
+
+    /// Grows or shrinks a block of memory previously returned by `alloc` or `realloc`.
+    ///
+    /// `original` is never null, `original_layout` is the layout it was allocated with, and the
+    /// size of `layout` is never 0. The original block must not be freed if `None` is returned.
+    fn realloc(
+        &self,
+        original: *mut c_void,
+        original_layout: Layout,
+        layout: Layout,
+    ) -> Option<*mut c_void>;
+
+    /// Frees a block of memory previously returned by `alloc` or `realloc`.
+    ///
+    /// `memory` is never null, and `layout` is the layout it was allocated with.
+    fn free(&self, memory: *mut c_void, layout: Layout);
+
+    /// Called when the implementation has freed memory that it allocated on its own, for example
+    /// executable memory.
+    #[inline]
+    fn internal_free_notification(&self, size: usize) {}
+
+    /// Called when the implementation has allocated memory on its own, for example executable
+    /// memory.
+    #[inline]
+    fn internal_allocation_notification(&self, size: usize) {}
+}
+
+/// Host allocation callbacks that can be passed to an instance or a device.
+///
+/// Cloning this object is cheap, and all the clones share the same allocator.
+#[derive(Clone)]
+pub struct AllocationCallbacks {
+    inner: Arc<AllocationCallbacksInner>,
+}
+
+struct AllocationCallbacksInner {
+    callbacks: vk::AllocationCallbacks,
+    // Boxed so that `pUserData` is a pointer that stays valid.
+    user_data: Box<UserData>,
+}
+
+// Object that `pUserData` points to.
+struct UserData {
+    alloc: Box<dyn Alloc + Send + Sync>,
+    // Layout of each block that is currently allocated, indexed by its address. Vulkan doesn't
+    // give the size of the block to free or to reallocate, but `Alloc` needs it.
+    layouts: Mutex<HashMap<usize, Layout>>,
+}
+
+unsafe impl Send for AllocationCallbacksInner {}
+unsafe impl Sync for AllocationCallbacksInner {}
+
+impl AllocationCallbacks {
+    /// Builds allocation callbacks that forward to `alloc`.
+    pub fn new<A>(alloc: A) -> AllocationCallbacks
+    where
+        A: Alloc + Send + Sync + 'static,
+    {
+        let user_data = Box::new(UserData {
+            alloc: Box::new(alloc),
+            layouts: Mutex::new(HashMap::new()),
+        });
+
+        let callbacks = vk::AllocationCallbacks {
+            pUserData: &*user_data as *const UserData as *mut c_void,
+            pfnAllocation: allocation_function,
+            pfnReallocation: reallocation_function,
+            pfnFree: free_function,
+            pfnInternalAllocation: internal_allocation_notification,
+            pfnInternalFree: internal_free_notification,
+        };
+
+        AllocationCallbacks {
+            inner: Arc::new(AllocationCallbacksInner {
+                callbacks,
+                user_data,
+            }),
+        }
+    }
+
+    /// Returns the pointer to pass to Vulkan, or null if `callbacks` is `None`.
+    #[inline]
+    pub(crate) fn as_ptr(
+        callbacks: Option<&AllocationCallbacks>,
+    ) -> *const vk::AllocationCallbacks {
+        match callbacks {
+            Some(callbacks) => &callbacks.inner.callbacks,
+            None => ptr::null(),
+        }
+    }
+}
+
+impl fmt::Debug for AllocationCallbacks {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan allocation callbacks>")
+    }
+}
+
+#[inline]
+unsafe fn user_data<'a>(user_data: *mut c_void) -> &'a UserData {
+    &*(user_data as *const UserData)
+}
+
+// The functions below are called through FFI and must not panic, so they return null or do
+// nothing if the data they are given doesn't make sense.
+impl UserData {
+    // Returns the layouts of the blocks that are currently allocated. A poisoned lock is used
+    // anyway, as the map is never left in an inconsistent state.
+    fn layouts(&self) -> MutexGuard<'_, HashMap<usize, Layout>> {
+        self.layouts.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn alloc(&self, size: usize, alignment: usize) -> *mut c_void {
+        let layout = match Layout::from_size_align(size, alignment) {
+            Ok(layout) if size != 0 => layout,
+            _ => return ptr::null_mut(),
+        };
+
+        match self.alloc.alloc(layout) {
+            Some(memory) => {
+                self.layouts().insert(memory as usize, layout);
+                memory
+            }
+            None => ptr::null_mut(),
+        }
+    }
+
+    fn realloc(&self, original: *mut c_void, size: usize, alignment: usize) -> *mut c_void {
+        let mut layouts = self.layouts();
+        let original_layout = match layouts.get(&(original as usize)) {
+            Some(&layout) => layout,
+            None => return ptr::null_mut(),
+        };
+        let layout = match Layout::from_size_align(size, alignment) {
+            Ok(layout) => layout,
+            Err(_) => return ptr::null_mut(),
+        };
+
+        match self.alloc.realloc(original, original_layout, layout) {
+            Some(memory) => {
+                layouts.remove(&(original as usize));
+                layouts.insert(memory as usize, layout);
+                memory
+            }
+            None => ptr::null_mut(),
+        }
+    }
+
+    fn free(&self, memory: *mut c_void) {
+        let layout = match self.layouts().remove(&(memory as usize)) {
+            Some(layout) => layout,
+            None => return,
+        };
+        self.alloc.free(memory, layout);
+    }
+}
+
+extern "system" fn allocation_function(
+    user_data_ptr: *mut c_void,
+    size: usize,
+    alignment: usize,
+    _scope: vk::SystemAllocationScope,
+) -> *mut c_void {
+    unsafe { user_data(user_data_ptr) }.alloc(size, alignment)
+}
+
+extern "system" fn reallocation_function(
+    user_data_ptr: *mut c_void,
+    original: *mut c_void,
+    size: usize,
+    alignment: usize,
+    _scope: vk::SystemAllocationScope,
+) -> *mut c_void {
+    let user_data = unsafe { user_data(user_data_ptr) };
+
+    if original.is_null() {
+        return user_data.alloc(size, alignment);
+    }
+
+    if size == 0 {
+        user_data.free(original);
+        return ptr::null_mut();
+    }
+
+    user_data.realloc(original, size, alignment)
+}
+
+extern "system" fn free_function(user_data_ptr: *mut c_void, memory: *mut c_void) {
+    if !memory.is_null() {
+        unsafe { user_data(user_data_ptr) }.free(memory)
+    }
+}
+
+extern "system" fn internal_allocation_notification(
+    user_data_ptr: *mut c_void,
+    size: usize,
+    _ty: vk::InternalAllocationType,
+    _scope: vk::SystemAllocationScope,
+) {
+    unsafe {
+        user_data(user_data_ptr)
+            .alloc
+            .internal_allocation_notification(size)
+    }
+}
+
+extern "system" fn internal_free_notification(
+    user_data_ptr: *mut c_void,
+    size: usize,
+    _ty: vk::InternalAllocationType,
+    _scope: vk::SystemAllocationScope,
+) {
+    unsafe {
+        user_data(user_data_ptr)
+            .alloc
+            .internal_free_notification(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    // Allocator that counts the number of blocks currently allocated.
+    struct Counting(Arc<AtomicUsize>);
+
+    unsafe impl Alloc for Counting {
+        fn alloc(&self, layout: Layout) -> Option<*mut c_void> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Some(unsafe { std::alloc::alloc(layout) } as *mut c_void)
+        }
+
+        fn realloc(
+            &self,
+            original: *mut c_void,
+            original_layout: Layout,
+            layout: Layout,
+        ) -> Option<*mut c_void> {
+            assert_eq!(original_layout.align(), layout.align());
+            let memory =
+                unsafe { std::alloc::realloc(original as *mut u8, original_layout, layout.size()) };
+            Some(memory as *mut c_void)
+        }
+
+        fn free(&self, memory: *mut c_void, layout: Layout) {
+            self.0.fetch_sub(1, Ordering::SeqCst);
+            unsafe { std::alloc::dealloc(memory as *mut u8, layout) }
+        }
+    }
+
+    #[test]
+    fn forwards_to_alloc() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let callbacks = AllocationCallbacks::new(Counting(count.clone()));
+        let raw = unsafe { &*AllocationCallbacks::as_ptr(Some(&callbacks)) };
+
+        let memory = (raw.pfnAllocation)(raw.pUserData, 16, 8, vk::SYSTEM_ALLOCATION_SCOPE_OBJECT);
+        assert!(!memory.is_null());
+        assert_eq!(memory as usize % 8, 0);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        // The reallocated block is freed with its new size.
+        let memory = (raw.pfnReallocation)(
+            raw.pUserData,
+            memory,
+            64,
+            8,
+            vk::SYSTEM_ALLOCATION_SCOPE_OBJECT,
+        );
+        assert!(!memory.is_null());
+        assert_eq!(memory as usize % 8, 0);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        // Reallocating to a size of 0 frees the memory.
+        let result = (raw.pfnReallocation)(
+            raw.pUserData,
+            memory,
+            0,
+            8,
+            vk::SYSTEM_ALLOCATION_SCOPE_OBJECT,
+        );
+        assert!(result.is_null());
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        // Freeing a null pointer is ignored.
+        (raw.pfnFree)(raw.pUserData, ptr::null_mut());
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn free_with_layout() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let callbacks = AllocationCallbacks::new(Counting(count.clone()));
+        let raw = unsafe { &*AllocationCallbacks::as_ptr(Some(&callbacks)) };
 
-    fn realloc(&self, original: *mut c_void, size: usize, alignment: usize) -> Result<*mut c_void, ()>;
+        let memory = (raw.pfnAllocation)(raw.pUserData, 32, 16, vk::SYSTEM_ALLOCATION_SCOPE_OBJECT);
+        assert!(!memory.is_null());
+        (raw.pfnFree)(raw.pUserData, memory);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
 
-    fn free(&self, *mut c_void);
+    #[test]
+    fn unknown_pointer() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let callbacks = AllocationCallbacks::new(Counting(count.clone()));
+        let raw = unsafe { &*AllocationCallbacks::as_ptr(Some(&callbacks)) };
 
-    fn internal_free_notification(&self, size: usize);
+        // Pointers that weren't returned by the callbacks are not passed to the allocator.
+        let mut block = [0u64; 4];
+        let unknown = block.as_mut_ptr() as *mut c_void;
+        let result = (raw.pfnReallocation)(
+            raw.pUserData,
+            unknown,
+            64,
+            8,
+            vk::SYSTEM_ALLOCATION_SCOPE_OBJECT,
+        );
+        assert!(result.is_null());
+        (raw.pfnFree)(raw.pUserData, unknown);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
 
-    fn internal_allocation_notification(&self, size: usize);
+    #[test]
+    fn none_is_null() {
+        assert!(AllocationCallbacks::as_ptr(None).is_null());
+    }
 }
@@ -43,22 +43,21 @@ use crate::device::DeviceOwned;
 use crate::device::Queue;
 use crate::image::ImageAccess;
 use crate::instance::QueueFamily;
-use crate::memory::pool::AllocFromRequirementsFilter;
 use crate::memory::pool::AllocLayout;
-use crate::memory::pool::MappingRequirement;
-use crate::memory::pool::MemoryPool;
+use crate::memory::pool::MemoryAllocator;
 use crate::memory::pool::MemoryPoolAlloc;
+use crate::memory::pool::MemoryUsage;
 use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPoolAlloc;
 use crate::memory::Content;
 use crate::memory::CpuAccess as MemCpuAccess;
 use crate::memory::DedicatedAlloc;
 use crate::memory::DeviceMemoryAllocError;
+use crate::sync::AccessError;
+use crate::sync::Sharing;
 use parking_lot::RwLock;
 use parking_lot::RwLockReadGuard;
 use parking_lot::RwLockWriteGuard;
-use crate::sync::AccessError;
-use crate::sync::Sharing;
 
 /// Buffer whose content is accessible by the CPU.
 ///
@@ -101,6 +100,7 @@ enum CurrentGpuAccess {
 
 impl<T> CpuAccessibleBuffer<T> {
     /// Builds a new buffer with some data in it. Only allowed for sized data.
+    #[inline]
     pub fn from_data(
         device: Arc<Device>,
         usage: BufferUsage,
@@ -109,15 +109,59 @@ impl<T> CpuAccessibleBuffer<T> {
     ) -> Result<Arc<CpuAccessibleBuffer<T>>, DeviceMemoryAllocError>
     where
         T: Content + Copy + 'static,
+    {
+        let pool = Device::standard_pool(&device);
+        CpuAccessibleBuffer::from_data_with_allocator(device, usage, host_cached, data, &pool)
+    }
+
+    /// Builds a new uninitialized buffer. Only allowed for sized data.
+    ///
+    /// # Safety
+    ///
+    /// The content of the buffer is undefined, and must be written before it is read.
+    ///
+    #[inline]
+    pub unsafe fn uninitialized(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        host_cached: bool,
+    ) -> Result<Arc<CpuAccessibleBuffer<T>>, DeviceMemoryAllocError> {
+        CpuAccessibleBuffer::raw(
+            device,
+            mem::size_of::<T>(),
+            usage,
+            host_cached,
+            iter::empty(),
+        )
+    }
+}
+
+impl<T, A> CpuAccessibleBuffer<T, PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc,
+{
+    /// Same as `from_data`, but allocates the memory of the buffer from `allocator`.
+    pub fn from_data_with_allocator<P>(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        host_cached: bool,
+        data: T,
+        allocator: &P,
+    ) -> Result<Arc<Self>, DeviceMemoryAllocError>
+    where
+        T: Content + Copy + 'static,
+        P: MemoryAllocator<Alloc = A>,
     {
         unsafe {
-            let uninitialized = CpuAccessibleBuffer::raw(
-                device,
-                mem::size_of::<T>(),
-                usage,
-                host_cached,
-                iter::empty(),
-            )?;
+            let uninitialized: Arc<CpuAccessibleBuffer<T, _>> =
+                CpuAccessibleBuffer::raw_with_allocator(
+                    device,
+                    mem::size_of::<T>(),
+                    usage,
+                    host_cached,
+                    iter::empty(),
+                    allocator,
+                )?;
 
             // Note that we are in panic-unsafety land here. However a panic should never ever
             // happen here, so in theory we are safe.
@@ -131,17 +175,42 @@ impl<T> CpuAccessibleBuffer<T> {
             Ok(uninitialized)
         }
     }
+}
 
-    /// Builds a new uninitialized buffer. Only allowed for sized data.
+impl<T> CpuAccessibleBuffer<[T]> {
+    /// Builds a new buffer that contains an array `T`. The initial data comes from an iterator
+    /// that produces that list of Ts.
     #[inline]
-    pub unsafe fn uninitialized(
+    pub fn from_iter<I>(
         device: Arc<Device>,
         usage: BufferUsage,
         host_cached: bool,
-    ) -> Result<Arc<CpuAccessibleBuffer<T>>, DeviceMemoryAllocError> {
+        data: I,
+    ) -> Result<Arc<CpuAccessibleBuffer<[T]>>, DeviceMemoryAllocError>
+    where
+        I: ExactSizeIterator<Item = T>,
+        T: Content + 'static,
+    {
+        let pool = Device::standard_pool(&device);
+        CpuAccessibleBuffer::from_iter_with_allocator(device, usage, host_cached, data, &pool)
+    }
+
+    /// Builds a new buffer. Can be used for arrays.
+    ///
+    /// # Safety
+    ///
+    /// The content of the buffer is undefined, and must be written before it is read.
+    ///
+    #[inline]
+    pub unsafe fn uninitialized_array(
+        device: Arc<Device>,
+        len: usize,
+        usage: BufferUsage,
+        host_cached: bool,
+    ) -> Result<Arc<CpuAccessibleBuffer<[T]>>, DeviceMemoryAllocError> {
         CpuAccessibleBuffer::raw(
             device,
-            mem::size_of::<T>(),
+            len * mem::size_of::<T>(),
             usage,
             host_cached,
             iter::empty(),
@@ -149,22 +218,33 @@ impl<T> CpuAccessibleBuffer<T> {
     }
 }
 
-impl<T> CpuAccessibleBuffer<[T]> {
-    /// Builds a new buffer that contains an array `T`. The initial data comes from an iterator
-    /// that produces that list of Ts.
-    pub fn from_iter<I>(
+impl<T, A> CpuAccessibleBuffer<[T], PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc,
+{
+    /// Same as `from_iter`, but allocates the memory of the buffer from `allocator`.
+    pub fn from_iter_with_allocator<I, P>(
         device: Arc<Device>,
         usage: BufferUsage,
         host_cached: bool,
         data: I,
-    ) -> Result<Arc<CpuAccessibleBuffer<[T]>>, DeviceMemoryAllocError>
+        allocator: &P,
+    ) -> Result<Arc<Self>, DeviceMemoryAllocError>
     where
         I: ExactSizeIterator<Item = T>,
         T: Content + 'static,
+        P: MemoryAllocator<Alloc = A>,
     {
         unsafe {
-            let uninitialized =
-                CpuAccessibleBuffer::uninitialized_array(device, data.len(), usage, host_cached)?;
+            let uninitialized: Arc<CpuAccessibleBuffer<[T], _>> =
+                CpuAccessibleBuffer::raw_with_allocator(
+                    device,
+                    data.len() * mem::size_of::<T>(),
+                    usage,
+                    host_cached,
+                    iter::empty(),
+                    allocator,
+                )?;
 
             // Note that we are in panic-unsafety land here. However a panic should never ever
             // happen here, so in theory we are safe.
@@ -181,41 +261,59 @@ impl<T> CpuAccessibleBuffer<[T]> {
             Ok(uninitialized)
         }
     }
+}
 
-    /// Builds a new buffer. Can be used for arrays.
+impl<T: ?Sized> CpuAccessibleBuffer<T> {
+    /// Builds a new buffer without checking the size.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the size that you pass is correct for `T`.
+    ///
     #[inline]
-    pub unsafe fn uninitialized_array(
+    pub unsafe fn raw<'a, I>(
         device: Arc<Device>,
-        len: usize,
+        size: usize,
         usage: BufferUsage,
         host_cached: bool,
-    ) -> Result<Arc<CpuAccessibleBuffer<[T]>>, DeviceMemoryAllocError> {
-        CpuAccessibleBuffer::raw(
+        queue_families: I,
+    ) -> Result<Arc<CpuAccessibleBuffer<T>>, DeviceMemoryAllocError>
+    where
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        let pool = Device::standard_pool(&device);
+        CpuAccessibleBuffer::raw_with_allocator(
             device,
-            len * mem::size_of::<T>(),
+            size,
             usage,
             host_cached,
-            iter::empty(),
+            queue_families,
+            &pool,
         )
     }
 }
 
-impl<T: ?Sized> CpuAccessibleBuffer<T> {
-    /// Builds a new buffer without checking the size.
+impl<T: ?Sized, A> CpuAccessibleBuffer<T, PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc,
+{
+    /// Same as `raw`, but allocates the memory of the buffer from `allocator`.
     ///
     /// # Safety
     ///
     /// You must ensure that the size that you pass is correct for `T`.
     ///
-    pub unsafe fn raw<'a, I>(
+    pub unsafe fn raw_with_allocator<'a, I, P>(
         device: Arc<Device>,
         size: usize,
         usage: BufferUsage,
         host_cached: bool,
         queue_families: I,
-    ) -> Result<Arc<CpuAccessibleBuffer<T>>, DeviceMemoryAllocError>
+        allocator: &P,
+    ) -> Result<Arc<Self>, DeviceMemoryAllocError>
     where
         I: IntoIterator<Item = QueueFamily<'a>>,
+        P: MemoryAllocator<Alloc = A>,
     {
         let queue_families = queue_families
             .into_iter()
@@ -237,27 +335,18 @@ impl<T: ?Sized> CpuAccessibleBuffer<T> {
             }
         };
 
-        let mem = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
+        let usage = if host_cached {
+            MemoryUsage::Download
+        } else {
+            MemoryUsage::Upload
+        };
+
+        let mem = MemoryAllocator::alloc_for_usage(
+            allocator,
             &mem_reqs,
             AllocLayout::Linear,
-            MappingRequirement::Map,
+            usage,
             DedicatedAlloc::Buffer(&buffer),
-            |m| {
-                if m.is_host_cached() {
-                    if host_cached {
-                        AllocFromRequirementsFilter::Preferred
-                    } else {
-                        AllocFromRequirementsFilter::Allowed
-                    }
-                } else {
-                    if host_cached {
-                        AllocFromRequirementsFilter::Allowed
-                    } else {
-                        AllocFromRequirementsFilter::Preferred
-                    }
-                }
-            },
         )?;
         debug_assert!((mem.offset() % mem_reqs.alignment) == 0);
         debug_assert!(mem.mapped_memory().is_some());
@@ -653,7 +742,22 @@ impl fmt::Display for WriteLockError {
 
 #[cfg(test)]
 mod tests {
-    use crate::buffer::{BufferUsage, CpuAccessibleBuffer};
+    use crate::buffer::{BufferAccess, BufferUsage, CpuAccessibleBuffer};
+    use crate::memory::pool::StdMemoryPool;
+    use std::ffi::CString;
+
+    #[test]
+    fn debug_name_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device, BufferUsage::all(), false, 0u32).unwrap();
+        let name = CString::new("buffer").unwrap();
+
+        assert_should_panic!("the `ext_debug_utils` extension must be loaded", {
+            let _ = buffer.set_debug_name(&name);
+        });
+    }
 
     #[test]
     fn create_empty_buffer() {
@@ -664,4 +768,20 @@ mod tests {
         let _ = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, EMPTY);
         let _ = CpuAccessibleBuffer::from_iter(device, BufferUsage::all(), false, EMPTY.iter());
     }
+
+    #[test]
+    fn create_with_allocator() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let pool = StdMemoryPool::new(device.clone());
+        let buffer = CpuAccessibleBuffer::from_data_with_allocator(
+            device,
+            BufferUsage::all(),
+            false,
+            5u32,
+            &pool,
+        )
+        .unwrap();
+        assert_eq!(*buffer.read().unwrap(), 5);
+    }
 }
@@ -31,11 +31,11 @@ use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::device::Queue;
 use crate::image::ImageAccess;
-use crate::memory::pool::AllocFromRequirementsFilter;
 use crate::memory::pool::AllocLayout;
-use crate::memory::pool::MappingRequirement;
+use crate::memory::pool::MemoryAllocator;
 use crate::memory::pool::MemoryPool;
 use crate::memory::pool::MemoryPoolAlloc;
+use crate::memory::pool::MemoryUsage;
 use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPool;
 use crate::memory::DedicatedAlloc;
@@ -199,14 +199,7 @@ impl<T> CpuBufferPool<T> {
     #[inline]
     pub fn new(device: Arc<Device>, usage: BufferUsage) -> CpuBufferPool<T> {
         let pool = Device::standard_pool(&device);
-
-        CpuBufferPool {
-            device: device,
-            pool: pool,
-            current_buffer: Mutex::new(None),
-            usage: usage.clone(),
-            marker: PhantomData,
-        }
+        CpuBufferPool::with_allocator(device, usage, pool)
     }
 
     /// Builds a `CpuBufferPool` meant for simple uploads.
@@ -257,8 +250,24 @@ impl<T> CpuBufferPool<T> {
 
 impl<T, A> CpuBufferPool<T, A>
 where
-    A: MemoryPool,
+    A: MemoryAllocator,
 {
+    /// Builds a `CpuBufferPool` that allocates the memory of its buffers from `allocator`.
+    #[inline]
+    pub fn with_allocator(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        allocator: A,
+    ) -> CpuBufferPool<T, A> {
+        CpuBufferPool {
+            device,
+            pool: allocator,
+            current_buffer: Mutex::new(None),
+            usage,
+            marker: PhantomData,
+        }
+    }
+
     /// Returns the current capacity of the pool, in number of elements.
     pub fn capacity(&self) -> usize {
         match *self.current_buffer.lock().unwrap() {
@@ -385,13 +394,12 @@ where
                 }
             };
 
-            let mem = MemoryPool::alloc_from_requirements(
+            let mem = MemoryAllocator::alloc_for_usage(
                 &self.pool,
                 &mem_reqs,
                 AllocLayout::Linear,
-                MappingRequirement::Map,
+                MemoryUsage::Dynamic,
                 DedicatedAlloc::Buffer(&buffer),
-                |_| AllocFromRequirementsFilter::Allowed,
             )?;
             debug_assert!((mem.offset() % mem_reqs.alignment) == 0);
             debug_assert!(mem.mapped_memory().is_some());
@@ -35,11 +35,10 @@ use crate::device::DeviceOwned;
 use crate::device::Queue;
 use crate::image::ImageAccess;
 use crate::instance::QueueFamily;
-use crate::memory::pool::AllocFromRequirementsFilter;
 use crate::memory::pool::AllocLayout;
-use crate::memory::pool::MappingRequirement;
-use crate::memory::pool::MemoryPool;
+use crate::memory::pool::MemoryAllocator;
 use crate::memory::pool::MemoryPoolAlloc;
+use crate::memory::pool::MemoryUsage;
 use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPoolAlloc;
 use crate::memory::DedicatedAlloc;
@@ -90,6 +89,7 @@ impl<T: ?Sized> ImmutableBuffer<T> {
     /// the initial upload operation. In order to be allowed to use the `ImmutableBuffer`, you must
     /// either submit your operation after this future, or execute this future and wait for it to
     /// be finished before submitting your own operation.
+    #[inline]
     pub fn from_data(
         data: T,
         usage: BufferUsage,
@@ -98,13 +98,8 @@ impl<T: ?Sized> ImmutableBuffer<T> {
     where
         T: 'static + Copy + Send + Sync + Sized,
     {
-        let source = CpuAccessibleBuffer::from_data(
-            queue.device().clone(),
-            BufferUsage::transfer_source(),
-            false,
-            data,
-        )?;
-        ImmutableBuffer::from_buffer(source, usage, queue)
+        let pool = Device::standard_pool(queue.device());
+        ImmutableBuffer::from_data_with_allocator(data, usage, queue, &pool)
     }
 
     /// Builds an `ImmutableBuffer` that copies its data from another buffer.
@@ -113,6 +108,7 @@ impl<T: ?Sized> ImmutableBuffer<T> {
     /// the initial upload operation. In order to be allowed to use the `ImmutableBuffer`, you must
     /// either submit your operation after this future, or execute this future and wait for it to
     /// be finished before submitting your own operation.
+    #[inline]
     pub fn from_buffer<B>(
         source: B,
         usage: BufferUsage,
@@ -121,6 +117,49 @@ impl<T: ?Sized> ImmutableBuffer<T> {
     where
         B: BufferAccess + TypedBufferAccess<Content = T> + 'static + Clone + Send + Sync,
         T: 'static + Send + Sync,
+    {
+        let pool = Device::standard_pool(source.device());
+        ImmutableBuffer::from_buffer_with_allocator(source, usage, queue, &pool)
+    }
+}
+
+impl<T: ?Sized, A> ImmutableBuffer<T, PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc + Send + Sync + 'static,
+{
+    /// Same as `from_data`, but allocates the memory of the buffer, and of the intermediate
+    /// buffer used for the upload, from `allocator`.
+    pub fn from_data_with_allocator<P>(
+        data: T,
+        usage: BufferUsage,
+        queue: Arc<Queue>,
+        allocator: &P,
+    ) -> Result<(Arc<Self>, ImmutableBufferFromBufferFuture), DeviceMemoryAllocError>
+    where
+        T: 'static + Copy + Send + Sync + Sized,
+        P: MemoryAllocator<Alloc = A>,
+    {
+        let source = CpuAccessibleBuffer::from_data_with_allocator(
+            queue.device().clone(),
+            BufferUsage::transfer_source(),
+            false,
+            data,
+            allocator,
+        )?;
+        ImmutableBuffer::from_buffer_with_allocator(source, usage, queue, allocator)
+    }
+
+    /// Same as `from_buffer`, but allocates the memory of the buffer from `allocator`.
+    pub fn from_buffer_with_allocator<B, P>(
+        source: B,
+        usage: BufferUsage,
+        queue: Arc<Queue>,
+        allocator: &P,
+    ) -> Result<(Arc<Self>, ImmutableBufferFromBufferFuture), DeviceMemoryAllocError>
+    where
+        B: BufferAccess + TypedBufferAccess<Content = T> + 'static + Clone + Send + Sync,
+        T: 'static + Send + Sync,
+        P: MemoryAllocator<Alloc = A>,
     {
         unsafe {
             // We automatically set `transfer_destination` to true in order to avoid annoying errors.
@@ -129,11 +168,12 @@ impl<T: ?Sized> ImmutableBuffer<T> {
                 ..usage
             };
 
-            let (buffer, init) = ImmutableBuffer::raw(
+            let (buffer, init) = ImmutableBuffer::raw_with_allocator(
                 source.device().clone(),
                 source.size(),
                 actual_usage,
                 source.device().active_queue_families(),
+                allocator,
             )?;
 
             let mut cbb = AutoCommandBufferBuilder::new(source.device().clone(), queue.family())?;
@@ -183,6 +223,7 @@ impl<T> ImmutableBuffer<T> {
 }
 
 impl<T> ImmutableBuffer<[T]> {
+    #[inline]
     pub fn from_iter<D>(
         data: D,
         usage: BufferUsage,
@@ -192,13 +233,8 @@ impl<T> ImmutableBuffer<[T]> {
         D: ExactSizeIterator<Item = T>,
         T: 'static + Send + Sync + Sized,
     {
-        let source = CpuAccessibleBuffer::from_iter(
-            queue.device().clone(),
-            BufferUsage::transfer_source(),
-            false,
-            data,
-        )?;
-        ImmutableBuffer::from_buffer(source, usage, queue)
+        let pool = Device::standard_pool(queue.device());
+        ImmutableBuffer::from_iter_with_allocator(data, usage, queue, &pool)
     }
 
     /// Builds a new buffer with uninitialized data. Can be used for arrays.
@@ -238,6 +274,34 @@ impl<T> ImmutableBuffer<[T]> {
     }
 }
 
+impl<T, A> ImmutableBuffer<[T], PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc + Send + Sync + 'static,
+{
+    /// Same as `from_iter`, but allocates the memory of the buffer, and of the intermediate
+    /// buffer used for the upload, from `allocator`.
+    pub fn from_iter_with_allocator<D, P>(
+        data: D,
+        usage: BufferUsage,
+        queue: Arc<Queue>,
+        allocator: &P,
+    ) -> Result<(Arc<Self>, ImmutableBufferFromBufferFuture), DeviceMemoryAllocError>
+    where
+        D: ExactSizeIterator<Item = T>,
+        T: 'static + Send + Sync + Sized,
+        P: MemoryAllocator<Alloc = A>,
+    {
+        let source = CpuAccessibleBuffer::from_iter_with_allocator(
+            queue.device().clone(),
+            BufferUsage::transfer_source(),
+            false,
+            data,
+            allocator,
+        )?;
+        ImmutableBuffer::from_buffer_with_allocator(source, usage, queue, allocator)
+    }
+}
+
 impl<T: ?Sized> ImmutableBuffer<T> {
     /// Builds a new buffer without checking the size and granting free access for the initial
     /// upload.
@@ -263,19 +327,57 @@ impl<T: ?Sized> ImmutableBuffer<T> {
     ) -> Result<(Arc<ImmutableBuffer<T>>, ImmutableBufferInitialization<T>), DeviceMemoryAllocError>
     where
         I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        let pool = Device::standard_pool(&device);
+        ImmutableBuffer::raw_with_allocator(device, size, usage, queue_families, &pool)
+    }
+}
+
+// Buffer and initialization access returned by `raw_with_allocator`.
+type RawBuffer<T, A> = (
+    Arc<ImmutableBuffer<T, PotentialDedicatedAllocation<A>>>,
+    ImmutableBufferInitialization<T, PotentialDedicatedAllocation<A>>,
+);
+
+impl<T: ?Sized, A> ImmutableBuffer<T, PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc,
+{
+    /// Same as `raw`, but allocates the memory of the buffer from `allocator`.
+    ///
+    /// # Safety
+    ///
+    /// - You must ensure that the size that you pass is correct for `T`.
+    /// - The `ImmutableBufferInitialization` should be used to fill the buffer with some initial
+    ///   data.
+    ///
+    #[inline]
+    pub unsafe fn raw_with_allocator<'a, I, P>(
+        device: Arc<Device>,
+        size: usize,
+        usage: BufferUsage,
+        queue_families: I,
+        allocator: &P,
+    ) -> Result<RawBuffer<T, A>, DeviceMemoryAllocError>
+    where
+        I: IntoIterator<Item = QueueFamily<'a>>,
+        P: MemoryAllocator<Alloc = A>,
     {
         let queue_families = queue_families.into_iter().map(|f| f.id()).collect();
-        ImmutableBuffer::raw_impl(device, size, usage, queue_families)
+        ImmutableBuffer::raw_impl(device, size, usage, queue_families, allocator)
     }
 
-    // Internal implementation of `raw`. This is separated from `raw` so that it doesn't need to be
-    // inlined.
-    unsafe fn raw_impl(
+    // Internal implementation of `raw_with_allocator`. This is separated from
+    // `raw_with_allocator` so that it doesn't need to be inlined.
+    unsafe fn raw_impl<P>(
         device: Arc<Device>,
         size: usize,
         usage: BufferUsage,
         queue_families: SmallVec<[u32; 4]>,
-    ) -> Result<(Arc<ImmutableBuffer<T>>, ImmutableBufferInitialization<T>), DeviceMemoryAllocError>
+        allocator: &P,
+    ) -> Result<RawBuffer<T, A>, DeviceMemoryAllocError>
+    where
+        P: MemoryAllocator<Alloc = A>,
     {
         let (buffer, mem_reqs) = {
             let sharing = if queue_families.len() >= 2 {
@@ -292,19 +394,12 @@ impl<T: ?Sized> ImmutableBuffer<T> {
             }
         };
 
-        let mem = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
+        let mem = MemoryAllocator::alloc_for_usage(
+            allocator,
             &mem_reqs,
             AllocLayout::Linear,
-            MappingRequirement::DoNotMap,
+            MemoryUsage::DeviceOnly,
             DedicatedAlloc::Buffer(&buffer),
-            |t| {
-                if t.is_device_local() {
-                    AllocFromRequirementsFilter::Preferred
-                } else {
-                    AllocFromRequirementsFilter::Allowed
-                }
-            },
         )?;
         debug_assert!((mem.offset() % mem_reqs.alignment) == 0);
         buffer.bind_memory(mem.memory(), mem.offset())?;
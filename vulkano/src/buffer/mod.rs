@@ -79,6 +79,8 @@
 
 pub use self::cpu_access::CpuAccessibleBuffer;
 pub use self::cpu_pool::CpuBufferPool;
+pub use self::device_local::DefragmentationError;
+pub use self::device_local::DefragmentedBuffer;
 pub use self::device_local::DeviceLocalBuffer;
 pub use self::immutable::ImmutableBuffer;
 pub use self::slice::BufferSlice;
@@ -39,6 +39,7 @@ use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocError;
+use crate::memory::ExternalMemoryHandleType;
 use crate::memory::MemoryRequirements;
 use crate::sync::Sharing;
 
@@ -54,6 +55,7 @@ pub struct UnsafeBuffer {
     device: Arc<Device>,
     size: usize,
     usage: vk::BufferUsageFlags,
+    protected: bool,
 }
 
 impl UnsafeBuffer {
@@ -66,12 +68,102 @@ impl UnsafeBuffer {
     /// - Panics if `sparse.sparse` is false and `sparse.sparse_residency` or `sparse.sparse_aliased` is true.
     /// - Panics if `usage` is empty.
     ///
+    #[inline]
     pub unsafe fn new<'a, I>(
+        device: Arc<Device>,
+        size: usize,
+        usage: BufferUsage,
+        sharing: Sharing<I>,
+        sparse: SparseLevel,
+    ) -> Result<(UnsafeBuffer, MemoryRequirements), BufferCreationError>
+    where
+        I: Iterator<Item = u32>,
+    {
+        UnsafeBuffer::new_with_external_memory(
+            device,
+            size,
+            usage,
+            sharing,
+            sparse,
+            ExternalMemoryHandleType::none(),
+        )
+    }
+
+    /// Same as `new`, but the memory bound to the buffer can be exported with or imported from
+    /// the given handle types.
+    ///
+    /// The memory must then be allocated with the same handle types in its export or import
+    /// infos.
+    ///
+    /// # Panic
+    ///
+    /// # Safety
+    ///
+    /// - Same as `new`, see the module's documentation.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_types` isn't empty and the `khr_external_memory` extension isn't
+    ///   loaded.
+    /// - Same panics as `new`.
+    ///
+    #[inline]
+    pub unsafe fn new_with_external_memory<I>(
+        device: Arc<Device>,
+        size: usize,
+        usage: BufferUsage,
+        sharing: Sharing<I>,
+        sparse: SparseLevel,
+        handle_types: ExternalMemoryHandleType,
+    ) -> Result<(UnsafeBuffer, MemoryRequirements), BufferCreationError>
+    where
+        I: Iterator<Item = u32>,
+    {
+        UnsafeBuffer::new_impl(device, size, usage, sharing, sparse, handle_types, false)
+    }
+
+    /// Same as `new`, but creates a protected buffer.
+    ///
+    /// The memory bound to a protected buffer must be of a protected memory type, and can only be
+    /// accessed by protected queues. Protected buffers can't be sparse.
+    ///
+    /// # Safety
+    ///
+    /// - Same as `new`, see the module's documentation.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `usage` is empty.
+    ///
+    #[inline]
+    pub unsafe fn new_protected<I>(
+        device: Arc<Device>,
+        size: usize,
+        usage: BufferUsage,
+        sharing: Sharing<I>,
+    ) -> Result<(UnsafeBuffer, MemoryRequirements), BufferCreationError>
+    where
+        I: Iterator<Item = u32>,
+    {
+        UnsafeBuffer::new_impl(
+            device,
+            size,
+            usage,
+            sharing,
+            SparseLevel::none(),
+            ExternalMemoryHandleType::none(),
+            true,
+        )
+    }
+
+    unsafe fn new_impl<I>(
         device: Arc<Device>,
         size: usize,
         mut usage: BufferUsage,
         sharing: Sharing<I>,
         sparse: SparseLevel,
+        handle_types: ExternalMemoryHandleType,
+        protected: bool,
     ) -> Result<(UnsafeBuffer, MemoryRequirements), BufferCreationError>
     where
         I: Iterator<Item = u32>,
@@ -104,6 +196,9 @@ impl UnsafeBuffer {
         if sparse.sparse_aliased && !device.enabled_features().sparse_residency_aliased {
             return Err(BufferCreationError::SparseResidencyAliasedFeatureNotEnabled);
         }
+        if protected && !device.enabled_features().protected_memory {
+            return Err(BufferCreationError::ProtectedMemoryFeatureNotEnabled);
+        }
         if usage.device_address && !device.enabled_features().buffer_device_address {
             usage.device_address = false;
             if usage.to_vulkan_bits() == 0 {
@@ -126,10 +221,35 @@ impl UnsafeBuffer {
                 Sharing::Concurrent(ids) => (vk::SHARING_MODE_CONCURRENT, ids.collect()),
             };
 
+            let handle_bits = handle_types.to_bits();
+            let external_info = if handle_bits != 0 {
+                assert!(
+                    device.loaded_extensions_or_core().khr_external_memory,
+                    "the `khr_external_memory` extension must be loaded to create a buffer with \
+                     external memory"
+                );
+
+                Some(vk::ExternalMemoryBufferCreateInfo {
+                    sType: vk::STRUCTURE_TYPE_EXTERNAL_MEMORY_BUFFER_CREATE_INFO,
+                    pNext: ptr::null(),
+                    handleTypes: handle_bits,
+                })
+            } else {
+                None
+            };
+
             let infos = vk::BufferCreateInfo {
                 sType: vk::STRUCTURE_TYPE_BUFFER_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: sparse.to_flags(),
+                pNext: external_info
+                    .as_ref()
+                    .map(|info| info as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
+                flags: sparse.to_flags()
+                    | if protected {
+                        vk::BUFFER_CREATE_PROTECTED_BIT
+                    } else {
+                        0
+                    },
                 size: size as u64,
                 usage: usage_bits,
                 sharingMode: sh_mode,
@@ -141,7 +261,7 @@ impl UnsafeBuffer {
             check_errors(vk.CreateBuffer(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -153,14 +273,17 @@ impl UnsafeBuffer {
                 al * (1 + (val - 1) / al)
             }
 
-            let mut output = if device.loaded_extensions().khr_get_memory_requirements2 {
+            let mut output = if device
+                .loaded_extensions_or_core()
+                .khr_get_memory_requirements2
+            {
                 let infos = vk::BufferMemoryRequirementsInfo2KHR {
                     sType: vk::STRUCTURE_TYPE_BUFFER_MEMORY_REQUIREMENTS_INFO_2_KHR,
                     pNext: ptr::null_mut(),
                     buffer: buffer,
                 };
 
-                let mut output2 = if device.loaded_extensions().khr_dedicated_allocation {
+                let mut output2 = if device.loaded_extensions_or_core().khr_dedicated_allocation {
                     Some(vk::MemoryDedicatedRequirementsKHR {
                         sType: vk::STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR,
                         pNext: ptr::null(),
@@ -186,8 +309,8 @@ impl UnsafeBuffer {
 
                 let mut out = MemoryRequirements::from_vulkan_reqs(output.memoryRequirements);
                 if let Some(output2) = output2 {
-                    debug_assert_eq!(output2.requiresDedicatedAllocation, 0);
                     out.prefer_dedicated = output2.prefersDedicatedAllocation != 0;
+                    out.requires_dedicated = output2.requiresDedicatedAllocation != 0;
                 }
                 out
             } else {
@@ -234,6 +357,7 @@ impl UnsafeBuffer {
             device: device.clone(),
             size: size as usize,
             usage: usage_bits,
+            protected,
         };
 
         Ok((obj, mem_reqs))
@@ -286,6 +410,18 @@ impl UnsafeBuffer {
         self.size
     }
 
+    /// Returns true if the buffer is a protected buffer.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
+    /// Returns the usage the buffer was created with.
+    #[inline]
+    pub fn usage(&self) -> BufferUsage {
+        BufferUsage::from_vulkan_bits(self.usage)
+    }
+
     #[inline]
     pub fn usage_transfer_source(&self) -> bool {
         (self.usage & vk::BUFFER_USAGE_TRANSFER_SRC_BIT) != 0
@@ -373,7 +509,11 @@ impl Drop for UnsafeBuffer {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyBuffer(self.device.internal_object(), self.buffer, ptr::null());
+            vk.DestroyBuffer(
+                self.device.internal_object(),
+                self.buffer,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -452,6 +592,8 @@ pub enum BufferCreationError {
     SparseResidencyAliasedFeatureNotEnabled,
     /// Device address was requested but the corresponding feature wasn't enabled.
     DeviceAddressFeatureNotEnabled,
+    /// A protected buffer was requested but the `protected_memory` feature wasn't enabled.
+    ProtectedMemoryFeatureNotEnabled,
 }
 
 impl error::Error for BufferCreationError {
@@ -484,6 +626,10 @@ impl fmt::Display for BufferCreationError {
                 BufferCreationError::DeviceAddressFeatureNotEnabled => {
                     "device address was requested but the corresponding feature wasn't enabled"
                 }
+                BufferCreationError::ProtectedMemoryFeatureNotEnabled => {
+                    "a protected buffer was requested but the `protected_memory` feature wasn't \
+                     enabled"
+                }
             }
         )
     }
@@ -675,4 +821,21 @@ mod tests {
             );
         };
     }
+
+    #[test]
+    fn protected_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        unsafe {
+            match UnsafeBuffer::new_protected(
+                device,
+                128,
+                BufferUsage::all(),
+                Sharing::Exclusive::<Empty<_>>,
+            ) {
+                Err(BufferCreationError::ProtectedMemoryFeatureNotEnabled) => (),
+                _ => panic!(),
+            }
+        };
+    }
 }
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::ffi::CStr;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::num::NonZeroU64;
@@ -20,6 +21,7 @@ use crate::device::Queue;
 use crate::image::ImageAccess;
 use crate::memory::Content;
 use crate::sync::AccessError;
+use crate::OomError;
 
 use crate::{vk, SafeDeref, VulkanObject};
 
@@ -84,6 +86,20 @@ pub unsafe trait BufferAccess: DeviceOwned {
         self.slice(index..(index + 1))
     }
 
+    /// Assigns a human-readable name to the buffer for debugging purposes.
+    ///
+    /// If `self` is a slice, the name is given to the whole buffer.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_debug_utils` extension isn't loaded on the instance.
+    ///
+    #[inline]
+    fn set_debug_name(&self, name: &CStr) -> Result<(), OomError> {
+        let buffer = self.inner().buffer;
+        buffer.device().set_object_name(buffer, name)
+    }
+
     /// Returns true if an access to `self` potentially overlaps the same memory as an access to
     /// `other`.
     ///
@@ -69,6 +69,22 @@ impl BufferUsage {
         result
     }
 
+    /// Builds a `BufferUsage` from raw Vulkan bits.
+    pub(crate) fn from_vulkan_bits(bits: vk::BufferUsageFlagBits) -> BufferUsage {
+        BufferUsage {
+            transfer_source: bits & vk::BUFFER_USAGE_TRANSFER_SRC_BIT != 0,
+            transfer_destination: bits & vk::BUFFER_USAGE_TRANSFER_DST_BIT != 0,
+            uniform_texel_buffer: bits & vk::BUFFER_USAGE_UNIFORM_TEXEL_BUFFER_BIT != 0,
+            storage_texel_buffer: bits & vk::BUFFER_USAGE_STORAGE_TEXEL_BUFFER_BIT != 0,
+            uniform_buffer: bits & vk::BUFFER_USAGE_UNIFORM_BUFFER_BIT != 0,
+            storage_buffer: bits & vk::BUFFER_USAGE_STORAGE_BUFFER_BIT != 0,
+            index_buffer: bits & vk::BUFFER_USAGE_INDEX_BUFFER_BIT != 0,
+            vertex_buffer: bits & vk::BUFFER_USAGE_VERTEX_BUFFER_BIT != 0,
+            indirect_buffer: bits & vk::BUFFER_USAGE_INDIRECT_BUFFER_BIT != 0,
+            device_address: bits & vk::BUFFER_USAGE_SHADER_DEVICE_ADDRESS_BIT != 0,
+        }
+    }
+
     /// Builds a `BufferUsage` with all values set to false.
     #[inline]
     pub const fn none() -> BufferUsage {
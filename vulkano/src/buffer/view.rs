@@ -166,7 +166,7 @@ where
             check_errors(vk.CreateBufferView(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             (output.assume_init(), format_props)
@@ -253,7 +253,7 @@ where
             vk.DestroyBufferView(
                 self.buffer.inner().buffer.device().internal_object(),
                 self.view,
-                ptr::null(),
+                self.buffer.inner().buffer.device().alloc_ptr(),
             );
         }
     }
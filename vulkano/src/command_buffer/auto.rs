@@ -16,6 +16,7 @@ use crate::command_buffer::pool::CommandPoolBuilderAlloc;
 use crate::command_buffer::synced::SyncCommandBuffer;
 use crate::command_buffer::synced::SyncCommandBufferBuilder;
 use crate::command_buffer::synced::SyncCommandBufferBuilderError;
+use crate::command_buffer::synced::SyncEvent;
 use crate::command_buffer::sys::Flags;
 use crate::command_buffer::sys::UnsafeCommandBuffer;
 use crate::command_buffer::sys::UnsafeCommandBufferBuilderBufferImageCopy;
@@ -60,9 +61,12 @@ use crate::image::ImageAccess;
 use crate::image::ImageLayout;
 use crate::instance::QueueFamily;
 use crate::pipeline::input_assembly::Index;
+use crate::pipeline::shader_object::ShaderObject;
+use crate::pipeline::shader_object::ShaderObjectStage;
 use crate::pipeline::vertex::VertexSource;
 use crate::pipeline::ComputePipelineAbstract;
 use crate::pipeline::GraphicsPipelineAbstract;
+use crate::query::OcclusionQueriesPool;
 use crate::query::QueryControlFlags;
 use crate::query::QueryPipelineStatisticFlags;
 use crate::sampler::Filter;
@@ -100,6 +104,9 @@ pub struct AutoCommandBufferBuilder<L, P = StandardCommandPoolBuilder> {
     // True if the queue family supports compute operations.
     compute_allowed: bool,
 
+    // The id of the queue family that the command buffer is recorded for.
+    queue_family_id: u32,
+
     // The inheritance for secondary command buffers.
     inheritance: Option<
         CommandBufferInheritance<
@@ -114,6 +121,12 @@ pub struct AutoCommandBufferBuilder<L, P = StandardCommandPoolBuilder> {
     // If we're inside a render pass, contains the render pass state.
     render_pass_state: Option<RenderPassState>,
 
+    // If an occlusion query is active, contains the query state.
+    query_state: Option<QueryState>,
+
+    // True if a vertex shader object is bound, and no graphics pipeline was bound since.
+    vertex_shader_object_bound: bool,
+
     _data: PhantomData<L>,
 }
 
@@ -124,6 +137,15 @@ struct RenderPassState {
     framebuffer: vk::Framebuffer, // Always null for secondary command buffers
 }
 
+// The state of the active occlusion query.
+struct QueryState {
+    pool: vk::QueryPool,
+    index: u32,
+    flags: QueryControlFlags,
+    // The subpass that the query was begun in, if it was begun inside a render pass.
+    subpass: Option<u32>,
+}
+
 impl AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandPoolBuilder> {
     #[inline]
     pub fn new(
@@ -466,7 +488,7 @@ impl AutoCommandBufferBuilder<SecondaryAutoCommandBuffer, StandardCommandPoolBui
 }
 
 impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
-    // Actual constructor. Private.
+    // Allocates the command buffer from the standard command pool of the device.
     fn with_flags<R, F>(
         device: Arc<Device>,
         queue_family: QueueFamily,
@@ -477,6 +499,68 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
         R: RenderPassAbstract + Clone + Send + Sync + 'static,
         F: FramebufferAbstract + Clone + Send + Sync + 'static,
     {
+        let pool = Device::standard_command_pool(&device, queue_family);
+        let pool_builder_alloc = pool
+            .alloc(!matches!(level, CommandBufferLevel::Primary), 1)?
+            .next()
+            .expect("Requested one command buffer from the command pool, but got zero.");
+
+        AutoCommandBufferBuilder::with_pool_alloc(pool_builder_alloc, level, flags)
+    }
+}
+
+impl<B> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<B::Alloc>, B>
+where
+    B: CommandPoolBuilderAlloc,
+{
+    /// Starts building a primary command buffer allocated from `pool`, for the queue family of
+    /// the pool.
+    ///
+    /// Same as `primary_one_time_submit`, the final command buffer can only be submitted once
+    /// before being destroyed.
+    #[inline]
+    pub fn primary_one_time_submit_from_pool<P>(
+        pool: &P,
+    ) -> Result<AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<B::Alloc>, B>, OomError>
+    where
+        P: CommandPool<Builder = B>,
+    {
+        let pool_builder_alloc = pool
+            .alloc(false, 1)?
+            .next()
+            .expect("Requested one command buffer from the command pool, but got zero.");
+
+        AutoCommandBufferBuilder::with_pool_alloc(
+            pool_builder_alloc,
+            CommandBufferLevel::primary(),
+            Flags::OneTimeSubmit,
+        )
+    }
+}
+
+impl<L, P> AutoCommandBufferBuilder<L, P>
+where
+    P: CommandPoolBuilderAlloc,
+{
+    // Actual constructor. Private.
+    fn with_pool_alloc<R, F>(
+        pool_builder_alloc: P,
+        level: CommandBufferLevel<R, F>,
+        flags: Flags,
+    ) -> Result<AutoCommandBufferBuilder<L, P>, OomError>
+    where
+        R: RenderPassAbstract + Clone + Send + Sync + 'static,
+        F: FramebufferAbstract + Clone + Send + Sync + 'static,
+    {
+        let (graphics_allowed, compute_allowed, queue_family_id) = {
+            let queue_family = pool_builder_alloc.queue_family();
+            (
+                queue_family.supports_graphics(),
+                queue_family.supports_compute(),
+                queue_family.id(),
+            )
+        };
+
         let (inheritance, render_pass_state) = match &level {
             CommandBufferLevel::Primary => (None, None),
             CommandBufferLevel::Secondary(inheritance) => {
@@ -520,20 +604,18 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
         };
 
         unsafe {
-            let pool = Device::standard_command_pool(&device, queue_family);
-            let pool_builder_alloc = pool
-                .alloc(!matches!(level, CommandBufferLevel::Primary), 1)?
-                .next()
-                .expect("Requested one command buffer from the command pool, but got zero.");
             let inner = SyncCommandBufferBuilder::new(pool_builder_alloc.inner(), level, flags)?;
 
             Ok(AutoCommandBufferBuilder {
                 inner,
                 pool_builder_alloc,
                 state_cacher: StateCacher::new(),
-                graphics_allowed: queue_family.supports_graphics(),
-                compute_allowed: queue_family.supports_compute(),
+                graphics_allowed,
+                compute_allowed,
+                queue_family_id,
                 render_pass_state,
+                query_state: None,
+                vertex_shader_object_bound: false,
                 inheritance,
                 flags,
                 _data: PhantomData,
@@ -546,6 +628,41 @@ impl<P> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<P::Alloc>, P>
 where
     P: CommandPoolBuilderAlloc,
 {
+    /// Makes the images used by `previous` start in the layout that `previous` leaves them in,
+    /// instead of their initial layout requirement.
+    ///
+    /// Combined with `keep_final_layouts` on the builder of `previous`, this avoids transitioning
+    /// the images back to their final layout requirement at the end of `previous` and
+    /// immediately transitioning them again at the start of this command buffer. Only the images
+    /// that haven't been used yet by this builder are affected, so this should be called before
+    /// recording any command.
+    ///
+    /// The command buffer can then only be submitted right after `previous`, in the same chain of
+    /// futures. Submitting it otherwise returns an `UnexpectedImageLayout` error, unless the
+    /// layouts are the same anyway.
+    #[inline]
+    pub fn continue_from<Q>(&mut self, previous: &PrimaryAutoCommandBuffer<Q>) -> &mut Self {
+        unsafe {
+            self.inner.continue_from(&previous.inner);
+        }
+        self
+    }
+
+    /// Leaves the images in the layout of their last use at the end of the command buffer,
+    /// instead of transitioning them to their final layout requirement.
+    ///
+    /// # Safety
+    ///
+    /// - The command buffer must only be submitted right before a command buffer whose builder
+    ///   called `continue_from` with it, on the same queue and in the same chain of futures.
+    ///   Otherwise, the images are left in a layout that vulkano doesn't know about.
+    ///
+    #[inline]
+    pub unsafe fn keep_final_layouts(&mut self) -> &mut Self {
+        self.inner.set_keep_final_layouts(true);
+        self
+    }
+
     /// Builds the command buffer.
     #[inline]
     pub fn build(self) -> Result<PrimaryAutoCommandBuffer<P::Alloc>, BuildError> {
@@ -553,6 +670,10 @@ where
             return Err(AutoCommandBufferBuilderContextError::ForbiddenInsideRenderPass.into());
         }
 
+        if self.query_state.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
+        }
+
         let submit_state = match self.flags {
             Flags::None => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -578,6 +699,10 @@ where
     /// Builds the command buffer.
     #[inline]
     pub fn build(self) -> Result<SecondaryAutoCommandBuffer<P::Alloc>, BuildError> {
+        if self.query_state.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
+        }
+
         let submit_state = match self.flags {
             Flags::None => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -598,6 +723,29 @@ where
 }
 
 impl<L, P> AutoCommandBufferBuilder<L, P> {
+    /// Calls `logger` with every synchronization decision made while recording the commands
+    /// that come after this call: the pipeline barriers, the image layout transitions, the
+    /// accesses that are merged without a barrier, and the conflicts. Each `SyncEvent` contains
+    /// the index and name of the command and the name of the resource.
+    ///
+    /// This is meant for debugging incorrect rendering caused by wrong assumptions about the
+    /// automatic synchronization, and slows down the recording of commands.
+    ///
+    /// ```no_run
+    /// # use vulkano::command_buffer::AutoCommandBufferBuilder;
+    /// # use vulkano::command_buffer::PrimaryAutoCommandBuffer;
+    /// # let mut builder: AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> = return;
+    /// builder.log_synchronization(|event| println!("{}", event));
+    /// ```
+    #[inline]
+    pub fn log_synchronization<F>(&mut self, logger: F) -> &mut Self
+    where
+        F: FnMut(&SyncEvent) + Send + Sync + 'static,
+    {
+        self.inner.set_logger(Some(Box::new(logger)));
+        self
+    }
+
     #[inline]
     fn ensure_outside_render_pass(&self) -> Result<(), AutoCommandBufferBuilderContextError> {
         if self.render_pass_state.is_some() {
@@ -615,6 +763,11 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     where
         Gp: ?Sized + GraphicsPipelineAbstract,
     {
+        // Pipeline libraries can only be linked into other pipelines.
+        if !pipeline.library_parts().is_empty() {
+            return Err(AutoCommandBufferBuilderContextError::PipelineLibrary);
+        }
+
         let render_pass_state = self
             .render_pass_state
             .as_ref()
@@ -1190,6 +1343,21 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         Ok(self)
     }
 
+    /// Sets the physical devices of the device group that execute the commands that follow. Bit
+    /// `i` of the mask corresponds to the physical device `i` of `Device::device_group`.
+    ///
+    /// By default, the commands are executed by all the physical devices of the group.
+    #[inline]
+    pub fn set_device_mask(&mut self, mask: u32) -> Result<&mut Self, SetDeviceMaskError> {
+        check_device_mask(self.device(), mask)?;
+
+        unsafe {
+            self.inner.set_device_mask(mask);
+        }
+
+        Ok(self)
+    }
+
     /// Perform a single compute operation using a compute pipeline.
     #[inline]
     pub fn dispatch<Cp, S, Pc, Do, Doi>(
@@ -1290,6 +1458,44 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         }
     }
 
+    /// Binds shader objects to their stages, or unbinds a stage if its shader is `None`.
+    ///
+    /// The graphics shaders that are bound are used by `draw_with_shaders`. Binding a graphics
+    /// pipeline afterwards replaces all the graphics shader objects.
+    #[inline]
+    pub fn bind_shaders(
+        &mut self,
+        shaders: Vec<(ShaderObjectStage, Option<Arc<ShaderObject>>)>,
+    ) -> Result<&mut Self, BindShadersError> {
+        for &(stage, _) in shaders.iter() {
+            let allowed = match stage {
+                ShaderObjectStage::Compute => self.compute_allowed,
+                _ => self.graphics_allowed,
+            };
+
+            if !allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+        }
+
+        check_bind_shaders(self.device(), &shaders)?;
+
+        if let Some((_, shader)) = shaders
+            .iter()
+            .find(|(stage, _)| *stage == ShaderObjectStage::Vertex)
+        {
+            self.vertex_shader_object_bound = shader.is_some();
+        }
+
+        unsafe {
+            // The pipelines that are bound are replaced by the shaders.
+            self.state_cacher.invalidate();
+            self.inner.bind_shaders(shaders);
+        }
+
+        Ok(self)
+    }
+
     /// Perform a single draw operation using a graphics pipeline.
     ///
     /// `vertex_buffer` is a set of vertex and/or instance buffers used to provide input.
@@ -1325,6 +1531,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 self.state_cacher.bind_graphics_pipeline(&pipeline)
             {
                 self.inner.bind_pipeline_graphics(pipeline.clone());
+                self.vertex_shader_object_bound = false;
             }
 
             let dynamic = self.state_cacher.dynamic_state(dynamic);
@@ -1403,6 +1610,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 self.state_cacher.bind_graphics_pipeline(&pipeline)
             {
                 self.inner.bind_pipeline_graphics(pipeline.clone());
+                self.vertex_shader_object_bound = false;
             }
 
             let dynamic = self.state_cacher.dynamic_state(dynamic);
@@ -1475,6 +1683,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 self.state_cacher.bind_graphics_pipeline(&pipeline)
             {
                 self.inner.bind_pipeline_graphics(pipeline.clone());
+                self.vertex_shader_object_bound = false;
             }
 
             if let StateCacherOutcome::NeedChange =
@@ -1567,6 +1776,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 self.state_cacher.bind_graphics_pipeline(&pipeline)
             {
                 self.inner.bind_pipeline_graphics(pipeline.clone());
+                self.vertex_shader_object_bound = false;
             }
 
             if let StateCacherOutcome::NeedChange =
@@ -1604,6 +1814,52 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         }
     }
 
+    /// Perform a single draw operation using the shader objects bound with `bind_shaders`
+    /// instead of a graphics pipeline.
+    ///
+    /// The states of `dynamic` are set before drawing.
+    ///
+    /// # Safety
+    ///
+    /// - Drawing with shader objects requires all the graphics state to be set dynamically. The
+    ///   states that are not part of `DynamicState` must have been set with the `inner` builder.
+    /// - Vertex buffers can't be bound by this function, so the vertex shader must not have any
+    ///   input.
+    /// - The descriptor sets and push constants used by the shaders must have been bound with the
+    ///   `inner` builder, using a layout compatible with the layout of the shaders.
+    ///
+    #[inline]
+    pub unsafe fn draw_with_shaders(
+        &mut self,
+        dynamic: &DynamicState,
+        vertex_count: u32,
+        instance_count: u32,
+        first_vertex: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, DrawWithShadersError> {
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass)?;
+
+        if render_pass_state.contents != SubpassContents::Inline {
+            return Err(AutoCommandBufferBuilderContextError::WrongSubpassType.into());
+        }
+
+        if !self.vertex_shader_object_bound {
+            return Err(AutoCommandBufferBuilderContextError::VertexShaderObjectNotBound.into());
+        }
+
+        let dynamic = self.state_cacher.dynamic_state(dynamic);
+        set_state(&mut self.inner, &dynamic);
+
+        debug_assert!(self.graphics_allowed);
+
+        self.inner
+            .draw(vertex_count, instance_count, first_vertex, first_instance);
+        Ok(self)
+    }
+
     /// Adds a command that writes the content of a buffer.
     ///
     /// This function is similar to the `memset` function in C. The `data` parameter is a number
@@ -1627,6 +1883,235 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         }
     }
 
+    /// Adds a command that releases the ownership of a buffer to another queue family.
+    ///
+    /// Buffers created with `Sharing::Exclusive` can only be accessed by one queue family at a
+    /// time. To use such a buffer from a queue of another family without losing its content, its
+    /// ownership must be released by a command buffer of the current family, then acquired with
+    /// `acquire_buffer_ownership` by a command buffer of the other family. The acquiring command
+    /// buffer must be submitted after the releasing one, and wait on a semaphore signaled by it.
+    ///
+    /// `stages` and `access` describe how the buffer has been accessed by the previous commands
+    /// of the current queue family. The buffer must not be used by the commands that come after
+    /// this one in the command buffer.
+    ///
+    /// Buffers created with `Sharing::Concurrent` don't need to be transferred, and must not be
+    /// passed to this function.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use vulkano::buffer::BufferAccess;
+    /// # use vulkano::command_buffer::AutoCommandBufferBuilder;
+    /// # use vulkano::command_buffer::PrimaryCommandBuffer;
+    /// # use vulkano::device::Queue;
+    /// # use vulkano::sync::AccessFlagBits;
+    /// # use vulkano::sync::PipelineStages;
+    /// # use vulkano::sync::GpuFuture;
+    /// # let transfer_queue: Arc<Queue> = return;
+    /// # let graphics_queue: Arc<Queue> = return;
+    /// # let buffer: Arc<dyn BufferAccess + Send + Sync> = return;
+    /// let transfer_stages = PipelineStages { transfer: true, ..PipelineStages::none() };
+    /// let transfer_access = AccessFlagBits { transfer_write: true, ..AccessFlagBits::none() };
+    /// let vertex_stages = PipelineStages { vertex_input: true, ..PipelineStages::none() };
+    /// let vertex_access = AccessFlagBits { vertex_attribute_read: true, ..AccessFlagBits::none() };
+    ///
+    /// // Upload the content of the buffer on the transfer queue, then release it.
+    /// let mut builder = AutoCommandBufferBuilder::primary_one_time_submit(
+    ///     transfer_queue.device().clone(),
+    ///     transfer_queue.family(),
+    /// ).unwrap();
+    /// // ... copy to `buffer` here ...
+    /// builder
+    ///     .release_buffer_ownership(
+    ///         buffer.clone(),
+    ///         graphics_queue.family(),
+    ///         transfer_stages,
+    ///         transfer_access,
+    ///     )
+    ///     .unwrap();
+    /// let release = builder.build().unwrap();
+    ///
+    /// // Acquire it on the graphics queue before using it for drawing.
+    /// let mut builder = AutoCommandBufferBuilder::primary_one_time_submit(
+    ///     graphics_queue.device().clone(),
+    ///     graphics_queue.family(),
+    /// ).unwrap();
+    /// builder
+    ///     .acquire_buffer_ownership(
+    ///         buffer.clone(),
+    ///         transfer_queue.family(),
+    ///         vertex_stages,
+    ///         vertex_access,
+    ///     )
+    ///     .unwrap();
+    /// // ... draw with `buffer` here ...
+    /// let acquire = builder.build().unwrap();
+    ///
+    /// // The semaphore makes the graphics queue wait for the release.
+    /// let future = release
+    ///     .execute(transfer_queue.clone())
+    ///     .unwrap()
+    ///     .then_signal_semaphore()
+    ///     .then_execute(graphics_queue.clone(), acquire)
+    ///     .unwrap()
+    ///     .then_signal_fence_and_flush()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the buffer or `destination` don't belong to the same device as the command
+    ///   buffer.
+    ///
+    #[inline]
+    pub fn release_buffer_ownership<B>(
+        &mut self,
+        buffer: B,
+        destination: QueueFamily,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+    ) -> Result<&mut Self, QueueFamilyTransferError>
+    where
+        B: BufferAccess + Send + Sync + 'static,
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            assert_eq!(
+                buffer.device().internal_object(),
+                self.device().internal_object()
+            );
+            check_queue_family_transfer(self.device(), self.queue_family_id, destination)?;
+            self.inner.queue_family_transfer_buffer(
+                buffer,
+                stages,
+                access,
+                (self.queue_family_id, destination.id()),
+                true,
+            )?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that acquires the ownership of a buffer that has been released by another
+    /// queue family with `release_buffer_ownership`.
+    ///
+    /// `stages` and `access` describe how the buffer is going to be accessed by the commands that
+    /// come after this one.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the buffer or `source` don't belong to the same device as the command buffer.
+    ///
+    #[inline]
+    pub fn acquire_buffer_ownership<B>(
+        &mut self,
+        buffer: B,
+        source: QueueFamily,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+    ) -> Result<&mut Self, QueueFamilyTransferError>
+    where
+        B: BufferAccess + Send + Sync + 'static,
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            assert_eq!(
+                buffer.device().internal_object(),
+                self.device().internal_object()
+            );
+            check_queue_family_transfer(self.device(), self.queue_family_id, source)?;
+            self.inner.queue_family_transfer_buffer(
+                buffer,
+                stages,
+                access,
+                (source.id(), self.queue_family_id),
+                false,
+            )?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that releases the ownership of an image to another queue family.
+    ///
+    /// Same as `release_buffer_ownership`, but for images. The layout of the image isn't changed
+    /// by the transfer: the image is released in its final layout requirement, and must be
+    /// acquired in the same layout.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the image or `destination` don't belong to the same device as the command
+    ///   buffer.
+    ///
+    #[inline]
+    pub fn release_image_ownership<I>(
+        &mut self,
+        image: I,
+        destination: QueueFamily,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+    ) -> Result<&mut Self, QueueFamilyTransferError>
+    where
+        I: ImageAccess + Send + Sync + 'static,
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            assert_eq!(
+                image.inner().image.device().internal_object(),
+                self.device().internal_object()
+            );
+            check_queue_family_transfer(self.device(), self.queue_family_id, destination)?;
+            self.inner.queue_family_transfer_image(
+                image,
+                stages,
+                access,
+                (self.queue_family_id, destination.id()),
+                true,
+            )?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that acquires the ownership of an image that has been released by another
+    /// queue family with `release_image_ownership`.
+    ///
+    /// Same as `acquire_buffer_ownership`, but for images. The image is acquired in its initial
+    /// layout requirement.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the image or `source` don't belong to the same device as the command buffer.
+    ///
+    #[inline]
+    pub fn acquire_image_ownership<I>(
+        &mut self,
+        image: I,
+        source: QueueFamily,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+    ) -> Result<&mut Self, QueueFamilyTransferError>
+    where
+        I: ImageAccess + Send + Sync + 'static,
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            assert_eq!(
+                image.inner().image.device().internal_object(),
+                self.device().internal_object()
+            );
+            check_queue_family_transfer(self.device(), self.queue_family_id, source)?;
+            self.inner.queue_family_transfer_image(
+                image,
+                stages,
+                access,
+                (source.id(), self.queue_family_id),
+                false,
+            )?;
+            Ok(self)
+        }
+    }
+
     /// Adds a command that writes data to a buffer.
     ///
     /// If `data` is larger than the buffer, only the part of `data` that fits is written. If the
@@ -1657,6 +2142,102 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             Ok(self)
         }
     }
+
+    /// Adds a command that begins an occlusion query. The query counts the samples that pass the
+    /// depth and stencil tests until the `end_query` command.
+    ///
+    /// Only one occlusion query can be active at a time. If the query is begun inside a render
+    /// pass, it must be ended in the same subpass.
+    ///
+    /// # Safety
+    ///
+    /// - The query must have been reset with `reset_query_pool` since its last use when the
+    ///   command buffer executes.
+    ///
+    #[inline]
+    pub unsafe fn begin_query(
+        &mut self,
+        pool: Arc<OcclusionQueriesPool>,
+        index: u32,
+        flags: QueryControlFlags,
+    ) -> Result<&mut Self, BeginQueryError> {
+        if !self.graphics_allowed {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
+
+        check_begin_query(self.device(), &pool, index, flags)?;
+
+        if self.query_state.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
+        }
+
+        self.query_state = Some(QueryState {
+            pool: pool.inner().internal_object(),
+            index,
+            flags,
+            subpass: self.render_pass_state.as_ref().map(|state| state.subpass.1),
+        });
+        self.inner.begin_query(pool, index, flags);
+
+        Ok(self)
+    }
+
+    /// Adds a command that ends the active occlusion query, which must be the query `index` of
+    /// `pool`.
+    #[inline]
+    pub fn end_query(
+        &mut self,
+        pool: Arc<OcclusionQueriesPool>,
+        index: u32,
+    ) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
+        let subpass = self.render_pass_state.as_ref().map(|state| state.subpass.1);
+
+        match self.query_state {
+            Some(ref state)
+                if state.pool == pool.inner().internal_object()
+                    && state.index == index
+                    && state.subpass == subpass => {}
+            _ => return Err(AutoCommandBufferBuilderContextError::QueryNotActive),
+        }
+
+        self.query_state = None;
+        unsafe {
+            self.inner.end_query(pool, index);
+        }
+
+        Ok(self)
+    }
+
+    /// Adds a command that resets the `count` queries starting at `first_index`, which makes
+    /// them available for a new use.
+    ///
+    /// # Safety
+    ///
+    /// - The queries must not be in use by the device when the command buffer executes, for
+    ///   example by another command buffer that is executing at the same time.
+    ///
+    #[inline]
+    pub unsafe fn reset_query_pool(
+        &mut self,
+        pool: Arc<OcclusionQueriesPool>,
+        first_index: u32,
+        count: u32,
+    ) -> Result<&mut Self, ResetQueryPoolError> {
+        self.ensure_outside_render_pass()?;
+        check_reset_query_pool(self.device(), &pool, first_index, count)?;
+
+        if let Some(ref state) = self.query_state {
+            if state.pool == pool.inner().internal_object()
+                && (first_index..first_index + count).contains(&state.index)
+            {
+                return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
+            }
+        }
+
+        self.inner.reset_query_pool(pool, first_index, count);
+
+        Ok(self)
+    }
 }
 
 /// Commands that can only be executed on primary command buffers
@@ -1857,13 +2438,22 @@ where
         C: SecondaryCommandBuffer + Send + Sync + 'static,
     {
         if let Some(render_pass) = command_buffer.inheritance().render_pass {
-            // TODO: If support for queries is added, their compatibility should be checked
-            // here too per vkCmdExecuteCommands specs
             self.ensure_inside_render_pass_secondary(&render_pass)?;
         } else {
             self.ensure_outside_render_pass()?;
         }
 
+        if let Some(ref state) = self.query_state {
+            if !self.device().enabled_features().inherited_queries {
+                return Err(AutoCommandBufferBuilderContextError::QueryNotInherited);
+            }
+
+            match command_buffer.inheritance().occlusion_query {
+                Some(flags) if flags.precise || !state.flags.precise => (),
+                _ => return Err(AutoCommandBufferBuilderContextError::QueryNotInherited),
+            }
+        }
+
         Ok(())
     }
 
@@ -2012,6 +2602,78 @@ unsafe fn set_state(destination: &mut SyncCommandBufferBuilder, dynamic: &Dynami
     if let Some(reference) = dynamic.reference {
         destination.set_stencil_reference(reference);
     }
+
+    if let Some(ref depth_bounds) = dynamic.depth_bounds {
+        destination.set_depth_bounds(depth_bounds.start, depth_bounds.end);
+    }
+
+    if let Some(ref fragment_shading_rate) = dynamic.fragment_shading_rate {
+        destination.set_fragment_shading_rate(*fragment_shading_rate);
+    }
+
+    if let Some(cull_mode) = dynamic.cull_mode {
+        destination.set_cull_mode(cull_mode);
+    }
+
+    if let Some(front_face) = dynamic.front_face {
+        destination.set_front_face(front_face);
+    }
+
+    if let Some(primitive_topology) = dynamic.primitive_topology {
+        destination.set_primitive_topology(primitive_topology);
+    }
+
+    if let Some(depth_test_enable) = dynamic.depth_test_enable {
+        destination.set_depth_test_enable(depth_test_enable);
+    }
+
+    if let Some(depth_write_enable) = dynamic.depth_write_enable {
+        destination.set_depth_write_enable(depth_write_enable);
+    }
+
+    if let Some(depth_compare_op) = dynamic.depth_compare_op {
+        destination.set_depth_compare_op(depth_compare_op);
+    }
+
+    if let Some(primitive_restart_enable) = dynamic.primitive_restart_enable {
+        destination.set_primitive_restart_enable(primitive_restart_enable);
+    }
+
+    if let Some(rasterizer_discard_enable) = dynamic.rasterizer_discard_enable {
+        destination.set_rasterizer_discard_enable(rasterizer_discard_enable);
+    }
+
+    if let Some(depth_bias_enable) = dynamic.depth_bias_enable {
+        destination.set_depth_bias_enable(depth_bias_enable);
+    }
+
+    if let Some(logic_op) = dynamic.logic_op {
+        destination.set_logic_op(logic_op);
+    }
+
+    if let Some(ref color_blend_enable) = dynamic.color_blend_enable {
+        destination.set_color_blend_enable(0, color_blend_enable.clone());
+    }
+
+    if let Some(ref color_blend_equation) = dynamic.color_blend_equation {
+        destination.set_color_blend_equation(0, color_blend_equation.clone());
+    }
+
+    if let Some(ref color_write_mask) = dynamic.color_write_mask {
+        destination.set_color_write_mask(0, color_write_mask.clone());
+    }
+
+    if let Some(ref color_write_enable) = dynamic.color_write_enable {
+        destination.set_color_write_enable(color_write_enable.clone());
+    }
+
+    if let Some(line_stipple) = dynamic.line_stipple {
+        destination.set_line_stipple(line_stipple.factor, line_stipple.pattern);
+    }
+
+    if let Some(ref sample_locations) = dynamic.sample_locations {
+        destination.set_sample_locations(sample_locations.clone());
+    }
 }
 
 // Shortcut function to bind vertex buffers.
@@ -2474,11 +3136,21 @@ err_gen!(FillBufferError {
     CheckFillBufferError,
 });
 
+err_gen!(QueueFamilyTransferError {
+    AutoCommandBufferBuilderContextError,
+    CheckQueueFamilyTransferError,
+    SyncCommandBufferBuilderError,
+});
+
 err_gen!(DebugMarkerError {
     AutoCommandBufferBuilderContextError,
     CheckColorError,
 });
 
+err_gen!(SetDeviceMaskError {
+    CheckDeviceMaskError,
+});
+
 err_gen!(DispatchError {
     AutoCommandBufferBuilderContextError,
     CheckPushConstantsValidityError,
@@ -2495,6 +3167,15 @@ err_gen!(DispatchIndirectError {
     SyncCommandBufferBuilderError,
 });
 
+err_gen!(BindShadersError {
+    AutoCommandBufferBuilderContextError,
+    CheckBindShadersError,
+});
+
+err_gen!(DrawWithShadersError {
+    AutoCommandBufferBuilderContextError,
+});
+
 err_gen!(DrawError {
     AutoCommandBufferBuilderContextError,
     CheckDynamicStateValidityError,
@@ -2545,6 +3226,16 @@ err_gen!(UpdateBufferError {
     CheckUpdateBufferError,
 });
 
+err_gen!(BeginQueryError {
+    AutoCommandBufferBuilderContextError,
+    CheckBeginQueryError,
+});
+
+err_gen!(ResetQueryPoolError {
+    AutoCommandBufferBuilderContextError,
+    CheckResetQueryPoolError,
+});
+
 #[derive(Debug, Copy, Clone)]
 pub enum AutoCommandBufferBuilderContextError {
     /// Operation forbidden inside of a render pass.
@@ -2573,6 +3264,21 @@ pub enum AutoCommandBufferBuilderContextError {
     /// Tried to use a graphics pipeline or secondary command buffer whose render pass
     /// is incompatible with the current render pass.
     IncompatibleRenderPass,
+    /// Tried to draw with a graphics pipeline library, which can only be linked into other
+    /// pipelines.
+    PipelineLibrary,
+    /// Tried to draw with shader objects while no vertex shader object is bound, or after a
+    /// graphics pipeline was bound.
+    VertexShaderObjectNotBound,
+    /// Tried to begin a query while another occlusion query is active, to reset the active
+    /// query, or to build a command buffer with an active query.
+    QueryIsActive,
+    /// Tried to end a query that isn't active, or in a different subpass than the one it was
+    /// begun in.
+    QueryNotActive,
+    /// Tried to execute a secondary command buffer that doesn't allow the active occlusion
+    /// query, or without the `inherited_queries` feature.
+    QueryNotInherited,
 }
 
 impl error::Error for AutoCommandBufferBuilderContextError {}
@@ -2614,6 +3320,24 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
                     "tried to use a graphics pipeline or secondary command buffer whose render pass \
                   is incompatible with the current render pass"
                 }
+                AutoCommandBufferBuilderContextError::PipelineLibrary => {
+                    "tried to draw with a graphics pipeline library"
+                }
+                AutoCommandBufferBuilderContextError::VertexShaderObjectNotBound => {
+                    "tried to draw with shader objects while no vertex shader object is bound"
+                }
+                AutoCommandBufferBuilderContextError::QueryIsActive => {
+                    "tried to begin or reset a query, or to build a command buffer, while an \
+                     occlusion query is active"
+                }
+                AutoCommandBufferBuilderContextError::QueryNotActive => {
+                    "tried to end a query that isn't active, or in a different subpass than the \
+                     one it was begun in"
+                }
+                AutoCommandBufferBuilderContextError::QueryNotInherited => {
+                    "tried to execute a secondary command buffer that doesn't allow the active \
+                     occlusion query"
+                }
             }
         )
     }
@@ -2623,17 +3347,46 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
 mod tests {
     use crate::buffer::BufferUsage;
     use crate::buffer::CpuAccessibleBuffer;
+    use crate::command_buffer::pool::TransientCommandPool;
     use crate::command_buffer::synced::SyncCommandBufferBuilderError;
+    use crate::command_buffer::synced::SyncEvent;
+    use crate::command_buffer::synced::SyncEventKind;
+    use crate::command_buffer::validity::CheckBindShadersError;
     use crate::command_buffer::AutoCommandBufferBuilder;
+    use crate::command_buffer::AutoCommandBufferBuilderContextError;
+    use crate::command_buffer::BeginQueryError;
+    use crate::command_buffer::BindShadersError;
+    use crate::command_buffer::BuildError;
     use crate::command_buffer::CommandBufferExecError;
+    use crate::command_buffer::DrawError;
+    use crate::command_buffer::DrawWithShadersError;
+    use crate::command_buffer::DynamicState;
     use crate::command_buffer::ExecuteCommandsError;
     use crate::command_buffer::PrimaryCommandBuffer;
+    use crate::command_buffer::ResetQueryPoolError;
+    use crate::command_buffer::SubpassContents;
     use crate::device::Device;
     use crate::device::DeviceExtensions;
     use crate::device::Features;
+    use crate::format::ClearValue;
+    use crate::format::Format;
+    use crate::framebuffer::Framebuffer;
+    use crate::image::view::ImageView;
+    use crate::image::AttachmentImage;
+    use crate::image::ImageAccess;
+    use crate::image::ImageLayout;
+    use crate::image::ImageUsage;
     use crate::instance;
+    use crate::pipeline::graphics_pipeline_tests;
+    use crate::pipeline::shader_object::ShaderObjectStage;
+    use crate::pipeline::viewport::Viewport;
+    use crate::pipeline::GraphicsPipelineLibraryParts;
+    use crate::query::OcclusionQueriesPool;
+    use crate::query::QueryControlFlags;
+    use crate::query::QueryResultFlags;
     use crate::sync::GpuFuture;
     use std::sync::Arc;
+    use std::sync::Mutex;
 
     #[test]
     fn copy_buffer_dimensions() {
@@ -2753,4 +3506,333 @@ mod tests {
             builder.execute_commands(secondary.clone()).unwrap();
         }
     }
+
+    #[test]
+    fn protected_requires_protected_queue() {
+        let (device, queue) = gfx_dev_and_queue!(protected_memory);
+        assert!(!queue.is_protected());
+
+        let pool = Arc::new(TransientCommandPool::new_protected(device, queue.family()).unwrap());
+        let cb = AutoCommandBufferBuilder::primary_one_time_submit_from_pool(&pool)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(cb.inner().is_protected());
+
+        match cb.execute(queue) {
+            Err(CommandBufferExecError::ProtectedQueueRequired) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn log_synchronization() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0u32)
+                .unwrap();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut builder =
+            AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+        builder.log_synchronization({
+            let events = events.clone();
+            move |event: &SyncEvent| events.lock().unwrap().push(event.clone())
+        });
+        builder.fill_buffer(buffer.clone(), 0).unwrap();
+        builder.fill_buffer(buffer.clone(), 1).unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0].kind,
+            SyncEventKind::FirstUse { transition: None }
+        ));
+        assert_eq!(events[1].command_index, 1);
+        assert_eq!(events[1].command_name, "vkCmdFillBuffer");
+        assert!(matches!(
+            events[1].kind,
+            SyncEventKind::Barrier {
+                previous_command_index: 0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn continue_from_kept_layouts() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = AttachmentImage::with_usage(
+            device.clone(),
+            [4, 4],
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+        )
+        .unwrap();
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+        unsafe {
+            builder.keep_final_layouts();
+        }
+        builder
+            .clear_color_image(image.clone(), ClearValue::Float([0.0; 4]))
+            .unwrap();
+        let cb1 = builder.build().unwrap();
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+        builder.continue_from(&cb1);
+        builder
+            .clear_color_image(image.clone(), ClearValue::Float([1.0; 4]))
+            .unwrap();
+        let cb2 = builder.build().unwrap();
+
+        // The second command buffer expects the image to be in the layout left by the first one.
+        assert!(cb1
+            .inner
+            .final_image_layouts()
+            .contains(&(image.conflict_key(), ImageLayout::TransferDstOptimal)));
+
+        cb1.execute(queue.clone())
+            .unwrap()
+            .then_execute_same_queue(cb2)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn occlusion_query() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let pool = OcclusionQueriesPool::new(device.clone(), 2);
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+        unsafe {
+            builder.reset_query_pool(pool.clone(), 0, 2).unwrap();
+            builder
+                .begin_query(pool.clone(), 1, QueryControlFlags::default())
+                .unwrap();
+        }
+        builder.end_query(pool.clone(), 1).unwrap();
+        let cb = builder.build().unwrap();
+
+        cb.execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let mut results = [0u64; 2];
+        let flags = QueryResultFlags {
+            wait: true,
+            with_availability: true,
+            ..QueryResultFlags::default()
+        };
+        assert_eq!(pool.get_results(1, 1, &mut results, flags), Ok(true));
+        assert_eq!(results[0], 0);
+        assert_ne!(results[1], 0);
+    }
+
+    #[test]
+    fn query_state_errors() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let pool = OcclusionQueriesPool::new(device.clone(), 2);
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+
+        assert!(matches!(
+            builder.end_query(pool.clone(), 0),
+            Err(AutoCommandBufferBuilderContextError::QueryNotActive)
+        ));
+
+        unsafe {
+            builder
+                .begin_query(pool.clone(), 0, QueryControlFlags::default())
+                .unwrap();
+
+            assert!(matches!(
+                builder.begin_query(pool.clone(), 1, QueryControlFlags::default()),
+                Err(BeginQueryError::AutoCommandBufferBuilderContextError(
+                    AutoCommandBufferBuilderContextError::QueryIsActive
+                ))
+            ));
+            assert!(matches!(
+                builder.reset_query_pool(pool.clone(), 0, 1),
+                Err(ResetQueryPoolError::AutoCommandBufferBuilderContextError(
+                    AutoCommandBufferBuilderContextError::QueryIsActive
+                ))
+            ));
+        }
+
+        assert!(matches!(
+            builder.end_query(pool.clone(), 1),
+            Err(AutoCommandBufferBuilderContextError::QueryNotActive)
+        ));
+        assert!(matches!(
+            builder.build(),
+            Err(BuildError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::QueryIsActive
+            ))
+        ));
+    }
+
+    #[test]
+    fn draw_with_pipeline_library() {
+        let instance = instance!();
+
+        let phys = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let queue_family = match phys.queue_families().find(|q| q.supports_graphics()) {
+            Some(q) => q,
+            None => return,
+        };
+
+        let features = Features {
+            graphics_pipeline_library: true,
+            ..Features::none()
+        };
+        if !phys.supported_features().superset_of(&features) {
+            return;
+        }
+
+        let extensions = DeviceExtensions {
+            khr_pipeline_library: true,
+            ext_graphics_pipeline_library: true,
+            ..DeviceExtensions::none()
+        };
+        let (device, mut queues) = match Device::new(
+            phys,
+            &features,
+            &extensions,
+            std::iter::once((queue_family, 0.5)),
+        ) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        let queue = queues.next().unwrap();
+
+        let (vs, fs) = graphics_pipeline_tests::basic_shaders(&device);
+        let subpass = graphics_pipeline_tests::basic_subpass(&device, None);
+        let render_pass = subpass.render_pass().clone();
+        let library = Arc::new(
+            graphics_pipeline_tests::basic_pipeline(&vs, &fs, subpass)
+                .library(GraphicsPipelineLibraryParts::all())
+                .build(device.clone())
+                .unwrap(),
+        );
+
+        let image = AttachmentImage::new(device.clone(), [4, 4], Format::R8G8B8A8Unorm).unwrap();
+        let framebuffer = Arc::new(
+            Framebuffer::start(render_pass)
+                .add(ImageView::new(image).unwrap())
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+        let vertices = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            false,
+            [graphics_pipeline_tests::BasicVertex::default(); 3]
+                .iter()
+                .copied(),
+        )
+        .unwrap();
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+        builder
+            .begin_render_pass(
+                framebuffer,
+                SubpassContents::Inline,
+                vec![ClearValue::Float([0.0; 4])],
+            )
+            .unwrap();
+
+        let dynamic = DynamicState {
+            viewports: Some(vec![Viewport {
+                origin: [0.0, 0.0],
+                dimensions: [4.0, 4.0],
+                depth_range: 0.0..1.0,
+            }]),
+            ..DynamicState::none()
+        };
+        assert!(matches!(
+            builder.draw(library, &dynamic, vertices, (), (), vec![]),
+            Err(DrawError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::PipelineLibrary
+            ))
+        ));
+    }
+
+    #[test]
+    fn draw_with_shaders_errors() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let subpass = graphics_pipeline_tests::basic_subpass(&device, None);
+        let render_pass = subpass.render_pass().clone();
+        let image = AttachmentImage::new(device.clone(), [4, 4], Format::R8G8B8A8Unorm).unwrap();
+        let framebuffer = Arc::new(
+            Framebuffer::start(render_pass)
+                .add(ImageView::new(image).unwrap())
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+
+        assert!(matches!(
+            builder.bind_shaders(vec![
+                (ShaderObjectStage::Vertex, None),
+                (ShaderObjectStage::Vertex, None),
+            ]),
+            Err(BindShadersError::CheckBindShadersError(
+                CheckBindShadersError::DuplicateStage {
+                    stage: ShaderObjectStage::Vertex,
+                }
+            ))
+        ));
+
+        unsafe {
+            assert!(matches!(
+                builder.draw_with_shaders(&DynamicState::none(), 3, 1, 0, 0),
+                Err(DrawWithShadersError::AutoCommandBufferBuilderContextError(
+                    AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass
+                ))
+            ));
+        }
+
+        builder
+            .begin_render_pass(
+                framebuffer,
+                SubpassContents::Inline,
+                vec![ClearValue::Float([0.0; 4])],
+            )
+            .unwrap();
+
+        unsafe {
+            assert!(matches!(
+                builder.draw_with_shaders(&DynamicState::none(), 3, 1, 0, 0),
+                Err(DrawWithShadersError::AutoCommandBufferBuilderContextError(
+                    AutoCommandBufferBuilderContextError::VertexShaderObjectNotBound
+                ))
+            ));
+        }
+    }
 }
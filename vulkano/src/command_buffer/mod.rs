@@ -75,7 +75,9 @@
 
 pub use self::auto::AutoCommandBufferBuilder;
 pub use self::auto::AutoCommandBufferBuilderContextError;
+pub use self::auto::BeginQueryError;
 pub use self::auto::BeginRenderPassError;
+pub use self::auto::BindShadersError;
 pub use self::auto::BlitImageError;
 pub use self::auto::BuildError;
 pub use self::auto::ClearColorImageError;
@@ -89,10 +91,14 @@ pub use self::auto::DrawError;
 pub use self::auto::DrawIndexedError;
 pub use self::auto::DrawIndexedIndirectError;
 pub use self::auto::DrawIndirectError;
+pub use self::auto::DrawWithShadersError;
 pub use self::auto::ExecuteCommandsError;
 pub use self::auto::FillBufferError;
 pub use self::auto::PrimaryAutoCommandBuffer;
+pub use self::auto::QueueFamilyTransferError;
+pub use self::auto::ResetQueryPoolError;
 pub use self::auto::SecondaryAutoCommandBuffer;
+pub use self::auto::SetDeviceMaskError;
 pub use self::auto::UpdateBufferError;
 pub use self::state_cacher::StateCacher;
 pub use self::state_cacher::StateCacherOutcome;
@@ -101,14 +107,21 @@ pub use self::traits::CommandBufferExecFuture;
 pub use self::traits::PrimaryCommandBuffer;
 pub use self::traits::SecondaryCommandBuffer;
 use crate::framebuffer::{EmptySinglePassRenderPassDesc, Framebuffer, RenderPass, Subpass};
-use crate::pipeline::depth_stencil::DynamicStencilValue;
+use crate::pipeline::blend::{AttachmentBlend, LogicOp};
+use crate::pipeline::depth_stencil::{Compare, DynamicStencilValue};
+use crate::pipeline::input_assembly::PrimitiveTopology;
+use crate::pipeline::multisample::SampleLocations;
+use crate::pipeline::raster::{CullMode, FrontFace, LineStipple};
+use crate::pipeline::shading_rate::FragmentShadingRate;
 use crate::pipeline::viewport::{Scissor, Viewport};
 use crate::query::QueryControlFlags;
 use crate::query::QueryPipelineStatisticFlags;
+use std::ops::Range;
 use std::sync::Arc;
 
 mod auto;
 pub mod pool;
+pub mod render_graph;
 mod state_cacher;
 pub mod submit;
 pub mod synced;
@@ -153,6 +166,30 @@ pub struct DynamicState {
     pub compare_mask: Option<DynamicStencilValue>,
     pub write_mask: Option<DynamicStencilValue>,
     pub reference: Option<DynamicStencilValue>,
+    pub depth_bounds: Option<Range<f32>>,
+    pub fragment_shading_rate: Option<FragmentShadingRate>,
+    pub cull_mode: Option<CullMode>,
+    pub front_face: Option<FrontFace>,
+    pub primitive_topology: Option<PrimitiveTopology>,
+    pub depth_test_enable: Option<bool>,
+    pub depth_write_enable: Option<bool>,
+    pub depth_compare_op: Option<Compare>,
+    pub primitive_restart_enable: Option<bool>,
+    pub rasterizer_discard_enable: Option<bool>,
+    pub depth_bias_enable: Option<bool>,
+    pub logic_op: Option<LogicOp>,
+    /// Whether blending is enabled, for each color attachment starting from the first.
+    pub color_blend_enable: Option<Vec<bool>>,
+    /// The blend equation of each color attachment starting from the first. Only the blend
+    /// factors and operations of each `AttachmentBlend` are used.
+    pub color_blend_equation: Option<Vec<AttachmentBlend>>,
+    /// The red, green, blue and alpha write mask of each color attachment starting from the first.
+    pub color_write_mask: Option<Vec<[bool; 4]>>,
+    /// Whether writes are enabled for each color attachment of the subpass. Must contain one
+    /// value per color attachment.
+    pub color_write_enable: Option<Vec<bool>>,
+    pub line_stipple: Option<LineStipple>,
+    pub sample_locations: Option<SampleLocations>,
 }
 
 impl DynamicState {
@@ -165,6 +202,24 @@ impl DynamicState {
             compare_mask: None,
             write_mask: None,
             reference: None,
+            depth_bounds: None,
+            fragment_shading_rate: None,
+            cull_mode: None,
+            front_face: None,
+            primitive_topology: None,
+            depth_test_enable: None,
+            depth_write_enable: None,
+            depth_compare_op: None,
+            primitive_restart_enable: None,
+            rasterizer_discard_enable: None,
+            depth_bias_enable: None,
+            logic_op: None,
+            color_blend_enable: None,
+            color_blend_equation: None,
+            color_write_mask: None,
+            color_write_enable: None,
+            line_stipple: None,
+            sample_locations: None,
         }
     }
 }
@@ -26,9 +26,11 @@ pub use self::sys::CommandPoolTrimError;
 pub use self::sys::UnsafeCommandPool;
 pub use self::sys::UnsafeCommandPoolAlloc;
 pub use self::sys::UnsafeCommandPoolAllocIter;
+pub use self::transient::TransientCommandPool;
 
 pub mod standard;
 mod sys;
+pub mod transient;
 
 /// Types that manage the memory of command buffers.
 ///
@@ -41,6 +41,9 @@ pub struct UnsafeCommandPool {
     // Index of the associated queue family in the physical device.
     queue_family_index: u32,
 
+    // True if the pool was created with `new_protected`.
+    protected: bool,
+
     // We don't want `UnsafeCommandPool` to implement Sync.
     // This marker unimplements both Send and Sync, but we reimplement Send manually right under.
     dummy_avoid_sync: PhantomData<*const u8>,
@@ -62,11 +65,46 @@ impl UnsafeCommandPool {
     ///
     /// - Panics if the queue family doesn't belong to the same physical device as `device`.
     ///
+    #[inline]
     pub fn new(
         device: Arc<Device>,
         queue_family: QueueFamily,
         transient: bool,
         reset_cb: bool,
+    ) -> Result<UnsafeCommandPool, OomError> {
+        UnsafeCommandPool::new_impl(device, queue_family, transient, reset_cb, false)
+    }
+
+    /// Same as `new`, but creates a protected pool.
+    ///
+    /// The command buffers allocated from a protected pool are protected command buffers. They
+    /// can access protected resources, and can only be submitted to protected queues.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue family doesn't belong to the same physical device as `device`.
+    /// - Panics if the `protected_memory` feature is not enabled on the device.
+    ///
+    #[inline]
+    pub fn new_protected(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        transient: bool,
+        reset_cb: bool,
+    ) -> Result<UnsafeCommandPool, OomError> {
+        assert!(
+            device.enabled_features().protected_memory,
+            "the `protected_memory` feature must be enabled to create a protected command pool"
+        );
+        UnsafeCommandPool::new_impl(device, queue_family, transient, reset_cb, true)
+    }
+
+    fn new_impl(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        transient: bool,
+        reset_cb: bool,
+        protected: bool,
     ) -> Result<UnsafeCommandPool, OomError> {
         assert_eq!(
             device.physical_device().internal_object(),
@@ -87,7 +125,12 @@ impl UnsafeCommandPool {
             } else {
                 0
             };
-            flag1 | flag2
+            let flag3 = if protected {
+                vk::COMMAND_POOL_CREATE_PROTECTED_BIT
+            } else {
+                0
+            };
+            flag1 | flag2 | flag3
         };
 
         let pool = unsafe {
@@ -102,7 +145,7 @@ impl UnsafeCommandPool {
             check_errors(vk.CreateCommandPool(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -112,6 +155,7 @@ impl UnsafeCommandPool {
             pool: pool,
             device: device.clone(),
             queue_family_index: queue_family.id(),
+            protected,
             dummy_avoid_sync: PhantomData,
         })
     }
@@ -148,7 +192,7 @@ impl UnsafeCommandPool {
     /// simply ignore any possible error.
     pub fn trim(&self) -> Result<(), CommandPoolTrimError> {
         unsafe {
-            if !self.device.loaded_extensions().khr_maintenance1 {
+            if !self.device.loaded_extensions_or_core().khr_maintenance1 {
                 return Err(CommandPoolTrimError::Maintenance1ExtensionNotEnabled);
             }
 
@@ -175,6 +219,7 @@ impl UnsafeCommandPool {
             return Ok(UnsafeCommandPoolAllocIter {
                 device: self.device.clone(),
                 list: vec![].into_iter(),
+                protected: self.protected,
             });
         }
 
@@ -204,6 +249,7 @@ impl UnsafeCommandPool {
             Ok(UnsafeCommandPoolAllocIter {
                 device: self.device.clone(),
                 list: out.into_iter(),
+                protected: self.protected,
             })
         }
     }
@@ -237,6 +283,12 @@ impl UnsafeCommandPool {
             .queue_family_by_id(self.queue_family_index)
             .unwrap()
     }
+
+    /// Returns true if the pool was created with `new_protected`.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandPool {
@@ -262,7 +314,11 @@ impl Drop for UnsafeCommandPool {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyCommandPool(self.device.internal_object(), self.pool, ptr::null());
+            vk.DestroyCommandPool(
+                self.device.internal_object(),
+                self.pool,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -271,6 +327,16 @@ impl Drop for UnsafeCommandPool {
 pub struct UnsafeCommandPoolAlloc {
     command_buffer: vk::CommandBuffer,
     device: Arc<Device>,
+    // True if the command buffer was allocated from a protected pool.
+    protected: bool,
+}
+
+impl UnsafeCommandPoolAlloc {
+    /// Returns true if the command buffer was allocated from a protected pool.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandPoolAlloc {
@@ -296,6 +362,7 @@ unsafe impl VulkanObject for UnsafeCommandPoolAlloc {
 pub struct UnsafeCommandPoolAllocIter {
     device: Arc<Device>,
     list: VecIntoIter<vk::CommandBuffer>,
+    protected: bool,
 }
 
 impl Iterator for UnsafeCommandPoolAllocIter {
@@ -308,6 +375,7 @@ impl Iterator for UnsafeCommandPoolAllocIter {
             .map(|command_buffer| UnsafeCommandPoolAlloc {
                 command_buffer,
                 device: self.device.clone(),
+                protected: self.protected,
             })
     }
 
@@ -381,6 +449,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn protected_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        assert_should_panic!(
+            "the `protected_memory` feature must be enabled to create a protected command pool",
+            {
+                let _ = UnsafeCommandPool::new_protected(device, queue.family(), false, false);
+            }
+        );
+    }
+
     #[test]
     fn check_maintenance_when_trim() {
         let (device, queue) = gfx_dev_and_queue!();
@@ -0,0 +1,367 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::vec::IntoIter as VecIntoIter;
+
+use crate::command_buffer::pool::CommandPool;
+use crate::command_buffer::pool::CommandPoolAlloc;
+use crate::command_buffer::pool::CommandPoolBuilderAlloc;
+use crate::command_buffer::pool::UnsafeCommandPool;
+use crate::command_buffer::pool::UnsafeCommandPoolAlloc;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::instance::QueueFamily;
+use crate::OomError;
+use crate::VulkanObject;
+
+/// Command pool for short-lived command buffers, for example command buffers that are recorded
+/// again at each frame.
+///
+/// The Vulkan pool is created with the transient flag, and the command buffers that are dropped
+/// are kept for reuse. Once all of them have been dropped, `reset` recycles the memory of the
+/// whole pool at once.
+///
+/// It is guaranteed that the allocated command buffers keep the `Arc<TransientCommandPool>`
+/// alive. Contrary to `StandardCommandPool`, there is only one Vulkan pool, which is locked while
+/// allocating and while a command buffer is returned to the pool.
+pub struct TransientCommandPool {
+    // The device.
+    device: Arc<Device>,
+
+    // Identifier of the queue family.
+    queue_family: u32,
+
+    // The Vulkan pool and the command buffers that are available for reuse.
+    inner: Mutex<TransientCommandPoolInner>,
+}
+
+unsafe impl Send for TransientCommandPool {}
+unsafe impl Sync for TransientCommandPool {}
+
+struct TransientCommandPoolInner {
+    // The Vulkan pool.
+    pool: UnsafeCommandPool,
+    // List of existing primary command buffers that are available for reuse.
+    available_primary_command_buffers: Vec<UnsafeCommandPoolAlloc>,
+    // List of existing secondary command buffers that are available for reuse.
+    available_secondary_command_buffers: Vec<UnsafeCommandPoolAlloc>,
+    // Number of command buffers that have been allocated and haven't been dropped yet.
+    num_in_use: usize,
+}
+
+impl TransientCommandPool {
+    /// Builds a new pool.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    ///
+    #[inline]
+    pub fn new(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+    ) -> Result<TransientCommandPool, OomError> {
+        TransientCommandPool::new_impl(device, queue_family, false)
+    }
+
+    /// Same as `new`, but the command buffers are allocated from a protected pool.
+    ///
+    /// The command buffers can access protected resources, and can only be executed on a
+    /// protected queue.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    /// - Panics if the `protected_memory` feature is not enabled on the device.
+    ///
+    #[inline]
+    pub fn new_protected(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+    ) -> Result<TransientCommandPool, OomError> {
+        TransientCommandPool::new_impl(device, queue_family, true)
+    }
+
+    fn new_impl(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        protected: bool,
+    ) -> Result<TransientCommandPool, OomError> {
+        assert_eq!(
+            device.physical_device().internal_object(),
+            queue_family.physical_device().internal_object()
+        );
+
+        let pool = if protected {
+            UnsafeCommandPool::new_protected(device.clone(), queue_family, true, true)?
+        } else {
+            UnsafeCommandPool::new(device.clone(), queue_family, true, true)?
+        };
+
+        Ok(TransientCommandPool {
+            device,
+            queue_family: queue_family.id(),
+            inner: Mutex::new(TransientCommandPoolInner {
+                pool,
+                available_primary_command_buffers: Vec::new(),
+                available_secondary_command_buffers: Vec::new(),
+                num_in_use: 0,
+            }),
+        })
+    }
+
+    /// Returns the number of command buffers allocated from this pool that haven't been dropped
+    /// yet.
+    #[inline]
+    pub fn num_in_use(&self) -> usize {
+        self.inner.lock().unwrap().num_in_use
+    }
+
+    /// Resets all the command buffers of the pool at once, if none of them is in use.
+    ///
+    /// Returns `false` without doing anything if a command buffer allocated from the pool hasn't
+    /// been dropped yet.
+    pub fn reset(&self) -> Result<bool, OomError> {
+        let inner = self.inner.lock().unwrap();
+
+        if inner.num_in_use != 0 {
+            return Ok(false);
+        }
+
+        // Safe because the command buffers of the pool have all been dropped, which means that
+        // none of them is pending execution.
+        unsafe {
+            inner.pool.reset(false)?;
+        }
+
+        Ok(true)
+    }
+}
+
+unsafe impl CommandPool for Arc<TransientCommandPool> {
+    type Iter = VecIntoIter<TransientCommandPoolBuilder>;
+    type Builder = TransientCommandPoolBuilder;
+    type Alloc = TransientCommandPoolAlloc;
+
+    fn alloc(&self, secondary: bool, count: u32) -> Result<Self::Iter, OomError> {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+
+        let existing = if secondary {
+            &mut inner.available_secondary_command_buffers
+        } else {
+            &mut inner.available_primary_command_buffers
+        };
+
+        // First, pick from already-existing command buffers, then allocate the rest.
+        let num_existing = existing.len().min(count as usize);
+        let mut cmds: Vec<_> = existing.drain(existing.len() - num_existing..).collect();
+        cmds.extend(
+            inner
+                .pool
+                .alloc_command_buffers(secondary, count as usize - num_existing)?,
+        );
+        inner.num_in_use += cmds.len();
+
+        let output = cmds
+            .into_iter()
+            .map(|cmd| TransientCommandPoolBuilder {
+                inner: TransientCommandPoolAlloc {
+                    cmd: ManuallyDrop::new(cmd),
+                    pool: self.clone(),
+                    secondary,
+                },
+                dummy_avoid_send_sync: PhantomData,
+            })
+            .collect::<Vec<_>>();
+
+        Ok(output.into_iter())
+    }
+
+    #[inline]
+    fn queue_family(&self) -> QueueFamily<'_> {
+        self.device
+            .physical_device()
+            .queue_family_by_id(self.queue_family)
+            .unwrap()
+    }
+}
+
+unsafe impl DeviceOwned for TransientCommandPool {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+/// Command buffer allocated from a `TransientCommandPool` and that is currently being built.
+pub struct TransientCommandPoolBuilder {
+    // Same as `StandardCommandPoolBuilder`, the builder must not implement `Send` and `Sync`.
+    inner: TransientCommandPoolAlloc,
+    // Unimplemented `Send` and `Sync` from the builder.
+    dummy_avoid_send_sync: PhantomData<*const u8>,
+}
+
+unsafe impl CommandPoolBuilderAlloc for TransientCommandPoolBuilder {
+    type Alloc = TransientCommandPoolAlloc;
+
+    #[inline]
+    fn inner(&self) -> &UnsafeCommandPoolAlloc {
+        self.inner.inner()
+    }
+
+    #[inline]
+    fn into_alloc(self) -> Self::Alloc {
+        self.inner
+    }
+
+    #[inline]
+    fn queue_family(&self) -> QueueFamily<'_> {
+        self.inner.queue_family()
+    }
+}
+
+unsafe impl DeviceOwned for TransientCommandPoolBuilder {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+/// Command buffer allocated from a `TransientCommandPool`.
+pub struct TransientCommandPoolAlloc {
+    // The actual command buffer. Extracted in the `Drop` implementation.
+    cmd: ManuallyDrop<UnsafeCommandPoolAlloc>,
+    // We hold a reference to the command pool for our destructor.
+    pool: Arc<TransientCommandPool>,
+    // True if secondary command buffer.
+    secondary: bool,
+}
+
+unsafe impl Send for TransientCommandPoolAlloc {}
+unsafe impl Sync for TransientCommandPoolAlloc {}
+
+unsafe impl CommandPoolAlloc for TransientCommandPoolAlloc {
+    #[inline]
+    fn inner(&self) -> &UnsafeCommandPoolAlloc {
+        &self.cmd
+    }
+
+    #[inline]
+    fn queue_family(&self) -> QueueFamily<'_> {
+        self.pool.queue_family()
+    }
+}
+
+unsafe impl DeviceOwned for TransientCommandPoolAlloc {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.pool.device
+    }
+}
+
+impl Drop for TransientCommandPoolAlloc {
+    fn drop(&mut self) {
+        // Safe because `self.cmd` is wrapped in a `ManuallyDrop`.
+        let cmd: UnsafeCommandPoolAlloc = unsafe { ptr::read(&*self.cmd) };
+
+        let mut inner = self.pool.inner.lock().unwrap();
+        inner.num_in_use -= 1;
+
+        if self.secondary {
+            inner.available_secondary_command_buffers.push(cmd);
+        } else {
+            inner.available_primary_command_buffers.push(cmd);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command_buffer::pool::CommandPool;
+    use crate::command_buffer::pool::CommandPoolBuilderAlloc;
+    use crate::command_buffer::pool::TransientCommandPool;
+    use crate::VulkanObject;
+    use std::sync::Arc;
+
+    #[test]
+    fn reuse_command_buffers() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(TransientCommandPool::new(device, queue.family()).unwrap());
+
+        let cb = pool.alloc(false, 1).unwrap().next().unwrap();
+        let raw = cb.inner().internal_object();
+        drop(cb);
+
+        // A secondary command buffer doesn't reuse a primary one.
+        let secondary = pool.alloc(true, 1).unwrap().next().unwrap();
+        assert_ne!(raw, secondary.inner().internal_object());
+
+        let cb2 = pool.alloc(false, 1).unwrap().next().unwrap();
+        assert_eq!(raw, cb2.inner().internal_object());
+    }
+
+    #[test]
+    fn reset_only_when_unused() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(TransientCommandPool::new(device, queue.family()).unwrap());
+        assert!(pool.reset().unwrap());
+
+        let cbs = pool.alloc(false, 2).unwrap().collect::<Vec<_>>();
+        assert_eq!(pool.num_in_use(), 2);
+        assert!(!pool.reset().unwrap());
+
+        drop(cbs);
+        assert_eq!(pool.num_in_use(), 0);
+        assert!(pool.reset().unwrap());
+    }
+
+    #[test]
+    fn pool_kept_alive_by_allocs() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(TransientCommandPool::new(device, queue.family()).unwrap());
+        let pool_weak = Arc::downgrade(&pool);
+
+        let cb = pool.alloc(false, 1).unwrap().next().unwrap();
+        drop(pool);
+        assert!(pool_weak.upgrade().is_some());
+
+        drop(cb);
+        assert!(pool_weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn protected_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        assert_should_panic!(
+            "the `protected_memory` feature must be enabled to create a protected command pool",
+            {
+                let _ = TransientCommandPool::new_protected(device, queue.family());
+            }
+        );
+    }
+
+    #[test]
+    fn unprotected_command_buffers() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(TransientCommandPool::new(device, queue.family()).unwrap());
+        let cb = pool.alloc(false, 1).unwrap().next().unwrap();
+        assert!(!cb.inner().is_protected());
+    }
+}
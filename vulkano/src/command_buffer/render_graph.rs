@@ -0,0 +1,518 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Dependencies and culling of the passes of a frame from the resources that they use.
+//!
+//! A `RenderGraph` is a list of passes. Each pass declares the images and buffers that it reads
+//! and writes, and provides a closure that records its commands. From these declarations, the
+//! graph knows which passes depend on each other: a pass depends on an earlier pass if they both
+//! access the same resource and at least one of them writes to it.
+//!
+//! When recording, the passes are executed in declaration order, which is always valid since
+//! dependencies only point to earlier passes. If outputs are declared with `add_output_image` or
+//! `add_output_buffer`, the passes that don't contribute to any of the outputs are culled.
+//!
+//! # Scope
+//!
+//! The graph only decides which passes are recorded. In particular:
+//!
+//! - It doesn't reorder the passes. They are always recorded in declaration order.
+//! - It doesn't compute pipeline barriers or image layout transitions. These are handled by the
+//!   automatic synchronization of `AutoCommandBufferBuilder` while the passes record their
+//!   commands, like for any other command.
+//! - It doesn't allocate or alias resources. All the images and buffers are created by the
+//!   application.
+//! - It records all the passes into the same command buffer, and therefore for the same queue. If
+//!   you want to split passes across queues, `execution_order` and `dependencies` tell you which
+//!   passes need to be synchronized.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::command_buffer::AutoCommandBufferBuilder;
+//! # use vulkano::command_buffer::PrimaryAutoCommandBuffer;
+//! # use vulkano::image::ImageAccess;
+//! use vulkano::command_buffer::render_graph::RenderGraph;
+//!
+//! # let mut builder: AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> = return;
+//! # let gbuffer: Arc<dyn ImageAccess + Send + Sync> = return;
+//! # let lighting: Arc<dyn ImageAccess + Send + Sync> = return;
+//! # let swapchain_image: Arc<dyn ImageAccess + Send + Sync> = return;
+//! let mut graph = RenderGraph::new();
+//!
+//! graph
+//!     .add_pass("geometry")
+//!     .writes_image(gbuffer.clone())
+//!     .build(|builder| {
+//!         // ... record the geometry pass here ...
+//!         Ok(())
+//!     });
+//!
+//! graph
+//!     .add_pass("lighting")
+//!     .reads_image(gbuffer.clone())
+//!     .writes_image(lighting.clone())
+//!     .build(|builder| {
+//!         // ... record the lighting pass here ...
+//!         Ok(())
+//!     });
+//!
+//! graph
+//!     .add_pass("tonemap")
+//!     .reads_image(lighting.clone())
+//!     .writes_image(swapchain_image.clone())
+//!     .build(|builder| {
+//!         // ... record the tonemapping pass here ...
+//!         Ok(())
+//!     });
+//!
+//! graph.add_output_image(swapchain_image.clone());
+//! graph.record(&mut builder).unwrap();
+//! ```
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::buffer::BufferAccess;
+use crate::command_buffer::pool::standard::StandardCommandPoolBuilder;
+use crate::command_buffer::AutoCommandBufferBuilder;
+use crate::image::ImageAccess;
+
+/// Error returned by the closure that records the commands of a pass.
+pub type PassError = Box<dyn error::Error + Send + Sync>;
+
+type RecordFn<'a, L, P> =
+    Box<dyn FnOnce(&mut AutoCommandBufferBuilder<L, P>) -> Result<(), PassError> + 'a>;
+
+/// A list of passes whose dependencies are derived from the resources that they access.
+///
+/// See the documentation of the `render_graph` module for more information.
+pub struct RenderGraph<'a, L, P = StandardCommandPoolBuilder> {
+    passes: Vec<Pass<'a, L, P>>,
+    output_images: Vec<Arc<dyn ImageAccess + Send + Sync>>,
+    output_buffers: Vec<Arc<dyn BufferAccess + Send + Sync>>,
+}
+
+struct Pass<'a, L, P> {
+    resources: PassResources,
+    record: RecordFn<'a, L, P>,
+}
+
+// The resources accessed by a pass. The boolean is true if the resource is written.
+struct PassResources {
+    name: String,
+    images: Vec<(Arc<dyn ImageAccess + Send + Sync>, bool)>,
+    buffers: Vec<(Arc<dyn BufferAccess + Send + Sync>, bool)>,
+}
+
+impl PassResources {
+    // Returns true if `self` must be executed after `other`, assuming that `other` comes first.
+    fn depends_on(&self, other: &PassResources) -> bool {
+        for &(ref image, write) in &self.images {
+            for &(ref other_image, other_write) in &other.images {
+                if (write || other_write) && image.conflicts_image(&**other_image) {
+                    return true;
+                }
+            }
+
+            for &(ref other_buffer, other_write) in &other.buffers {
+                if (write || other_write) && image.conflicts_buffer(&**other_buffer) {
+                    return true;
+                }
+            }
+        }
+
+        for &(ref buffer, write) in &self.buffers {
+            for &(ref other_image, other_write) in &other.images {
+                if (write || other_write) && buffer.conflicts_image(&**other_image) {
+                    return true;
+                }
+            }
+
+            for &(ref other_buffer, other_write) in &other.buffers {
+                if (write || other_write) && buffer.conflicts_buffer(&**other_buffer) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    // Returns true if the pass writes to one of the given resources.
+    fn writes_to(
+        &self,
+        images: &[Arc<dyn ImageAccess + Send + Sync>],
+        buffers: &[Arc<dyn BufferAccess + Send + Sync>],
+    ) -> bool {
+        let writes_image = |image: &dyn ImageAccess| {
+            self.images
+                .iter()
+                .any(|&(ref own, write)| write && own.conflicts_image(image))
+                || self
+                    .buffers
+                    .iter()
+                    .any(|&(ref own, write)| write && own.conflicts_image(image))
+        };
+
+        let writes_buffer = |buffer: &dyn BufferAccess| {
+            self.images
+                .iter()
+                .any(|&(ref own, write)| write && own.conflicts_buffer(buffer))
+                || self
+                    .buffers
+                    .iter()
+                    .any(|&(ref own, write)| write && own.conflicts_buffer(buffer))
+        };
+
+        images.iter().any(|image| writes_image(&**image))
+            || buffers.iter().any(|buffer| writes_buffer(&**buffer))
+    }
+}
+
+impl<'a, L, P> RenderGraph<'a, L, P> {
+    /// Builds a new empty `RenderGraph`.
+    #[inline]
+    pub fn new() -> RenderGraph<'a, L, P> {
+        RenderGraph {
+            passes: Vec::new(),
+            output_images: Vec::new(),
+            output_buffers: Vec::new(),
+        }
+    }
+
+    /// Starts declaring a new pass. The pass is added to the graph when calling `build` on the
+    /// returned builder.
+    #[inline]
+    pub fn add_pass<'g>(&'g mut self, name: &str) -> PassBuilder<'g, 'a, L, P> {
+        PassBuilder {
+            graph: self,
+            resources: PassResources {
+                name: name.to_owned(),
+                images: Vec::new(),
+                buffers: Vec::new(),
+            },
+        }
+    }
+
+    /// Declares an image as an output of the graph. The passes that write to it, and the passes
+    /// they depend on, are never culled.
+    #[inline]
+    pub fn add_output_image(&mut self, image: Arc<dyn ImageAccess + Send + Sync>) {
+        self.output_images.push(image);
+    }
+
+    /// Declares a buffer as an output of the graph. The passes that write to it, and the passes
+    /// they depend on, are never culled.
+    #[inline]
+    pub fn add_output_buffer(&mut self, buffer: Arc<dyn BufferAccess + Send + Sync>) {
+        self.output_buffers.push(buffer);
+    }
+
+    /// Returns the number of passes that have been added to the graph.
+    #[inline]
+    pub fn num_passes(&self) -> usize {
+        self.passes.len()
+    }
+
+    /// Returns the name of a pass.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `pass` is out of range.
+    ///
+    #[inline]
+    pub fn pass_name(&self, pass: usize) -> &str {
+        &self.passes[pass].resources.name
+    }
+
+    /// Returns the passes that must be executed before `pass`, because they access a resource
+    /// that `pass` also accesses and one of the two passes writes to it.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `pass` is out of range.
+    ///
+    pub fn dependencies(&self, pass: usize) -> Vec<usize> {
+        let resources = &self.passes[pass].resources;
+        (0..pass)
+            .filter(|&other| resources.depends_on(&self.passes[other].resources))
+            .collect()
+    }
+
+    /// Returns the passes that are going to be recorded, in order.
+    ///
+    /// If no output has been declared, this returns all the passes in declaration order.
+    /// Otherwise, only the passes that contribute to one of the outputs are returned.
+    pub fn execution_order(&self) -> Vec<usize> {
+        if self.output_images.is_empty() && self.output_buffers.is_empty() {
+            return (0..self.passes.len()).collect();
+        }
+
+        let mut needed: Vec<bool> = self
+            .passes
+            .iter()
+            .map(|pass| {
+                pass.resources
+                    .writes_to(&self.output_images, &self.output_buffers)
+            })
+            .collect();
+
+        // Dependencies always point to earlier passes, so a single backwards walk is enough.
+        for pass in (0..self.passes.len()).rev() {
+            if !needed[pass] {
+                continue;
+            }
+
+            for dependency in self.dependencies(pass) {
+                needed[dependency] = true;
+            }
+        }
+
+        (0..self.passes.len())
+            .filter(|&pass| needed[pass])
+            .collect()
+    }
+
+    /// Records the passes returned by `execution_order` into `builder`.
+    ///
+    /// If a pass returns an error, the passes that come after it are not recorded.
+    pub fn record(
+        self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), RenderGraphError> {
+        let order = self.execution_order();
+        let mut passes: Vec<Option<Pass<'a, L, P>>> = self.passes.into_iter().map(Some).collect();
+
+        for index in order {
+            let Pass { resources, record } = passes[index].take().unwrap();
+            record(builder).map_err(|error| RenderGraphError::PassError {
+                name: resources.name,
+                error,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, L, P> Default for RenderGraph<'a, L, P> {
+    #[inline]
+    fn default() -> RenderGraph<'a, L, P> {
+        RenderGraph::new()
+    }
+}
+
+impl<'a, L, P> fmt::Debug for RenderGraph<'a, L, P> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("RenderGraph")
+            .field(
+                "passes",
+                &self
+                    .passes
+                    .iter()
+                    .map(|pass| &pass.resources.name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Declares the resources accessed by a pass of a `RenderGraph`.
+///
+/// Returned by `RenderGraph::add_pass`.
+pub struct PassBuilder<'g, 'a, L, P> {
+    graph: &'g mut RenderGraph<'a, L, P>,
+    resources: PassResources,
+}
+
+impl<'g, 'a, L, P> PassBuilder<'g, 'a, L, P> {
+    /// Declares that the pass reads from an image.
+    #[inline]
+    pub fn reads_image(mut self, image: Arc<dyn ImageAccess + Send + Sync>) -> Self {
+        self.resources.images.push((image, false));
+        self
+    }
+
+    /// Declares that the pass writes to an image.
+    #[inline]
+    pub fn writes_image(mut self, image: Arc<dyn ImageAccess + Send + Sync>) -> Self {
+        self.resources.images.push((image, true));
+        self
+    }
+
+    /// Declares that the pass reads from a buffer.
+    #[inline]
+    pub fn reads_buffer(mut self, buffer: Arc<dyn BufferAccess + Send + Sync>) -> Self {
+        self.resources.buffers.push((buffer, false));
+        self
+    }
+
+    /// Declares that the pass writes to a buffer.
+    #[inline]
+    pub fn writes_buffer(mut self, buffer: Arc<dyn BufferAccess + Send + Sync>) -> Self {
+        self.resources.buffers.push((buffer, true));
+        self
+    }
+
+    /// Adds the pass to the graph, with the closure that records its commands, and returns the
+    /// index of the pass.
+    ///
+    /// The closure is only called by `RenderGraph::record`, and only if the pass isn't culled.
+    pub fn build<F>(self, record: F) -> usize
+    where
+        F: FnOnce(&mut AutoCommandBufferBuilder<L, P>) -> Result<(), PassError> + 'a,
+    {
+        self.graph.passes.push(Pass {
+            resources: self.resources,
+            record: Box::new(record),
+        });
+        self.graph.passes.len() - 1
+    }
+}
+
+/// Error that can happen when recording a `RenderGraph`.
+#[derive(Debug)]
+pub enum RenderGraphError {
+    /// The closure of a pass returned an error.
+    PassError {
+        /// The name of the pass.
+        name: String,
+        /// The error returned by the closure.
+        error: PassError,
+    },
+}
+
+impl error::Error for RenderGraphError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            RenderGraphError::PassError { ref error, .. } => Some(&**error),
+        }
+    }
+}
+
+impl fmt::Display for RenderGraphError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            RenderGraphError::PassError { ref name, .. } => {
+                write!(fmt, "error while recording the pass `{}`", name)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::sync::Arc;
+
+    use crate::buffer::BufferAccess;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use crate::command_buffer::render_graph::RenderGraph;
+    use crate::command_buffer::render_graph::RenderGraphError;
+    use crate::command_buffer::AutoCommandBufferBuilder;
+    use crate::command_buffer::PrimaryAutoCommandBuffer;
+
+    fn buffer(device: &Arc<crate::device::Device>) -> Arc<dyn BufferAccess + Send + Sync> {
+        CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0u32).unwrap()
+    }
+
+    #[test]
+    fn dependencies() {
+        let (device, _) = gfx_dev_and_queue!();
+        let a = buffer(&device);
+        let b = buffer(&device);
+
+        let mut graph = RenderGraph::<PrimaryAutoCommandBuffer>::new();
+        let write_a = graph
+            .add_pass("write_a")
+            .writes_buffer(a.clone())
+            .build(|_| Ok(()));
+        let read_a_1 = graph
+            .add_pass("read_a_1")
+            .reads_buffer(a.clone())
+            .build(|_| Ok(()));
+        let read_a_2 = graph
+            .add_pass("read_a_2")
+            .reads_buffer(a.clone())
+            .build(|_| Ok(()));
+        let write_b = graph
+            .add_pass("write_b")
+            .writes_buffer(b.clone())
+            .build(|_| Ok(()));
+
+        assert!(graph.dependencies(write_a).is_empty());
+        assert_eq!(graph.dependencies(read_a_1), vec![write_a]);
+        // Two reads of the same buffer don't depend on each other.
+        assert_eq!(graph.dependencies(read_a_2), vec![write_a]);
+        assert!(graph.dependencies(write_b).is_empty());
+        assert_eq!(graph.execution_order(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn culls_unused_passes() {
+        let (device, _) = gfx_dev_and_queue!();
+        let a = buffer(&device);
+        let b = buffer(&device);
+        let output = buffer(&device);
+
+        let mut graph = RenderGraph::<PrimaryAutoCommandBuffer>::new();
+        graph
+            .add_pass("write_a")
+            .writes_buffer(a.clone())
+            .build(|_| Ok(()));
+        graph
+            .add_pass("write_b")
+            .writes_buffer(b.clone())
+            .build(|_| Ok(()));
+        graph
+            .add_pass("resolve")
+            .reads_buffer(a.clone())
+            .writes_buffer(output.clone())
+            .build(|_| Ok(()));
+        graph.add_output_buffer(output);
+
+        assert_eq!(graph.execution_order(), vec![0, 2]);
+    }
+
+    #[test]
+    fn record_stops_at_pass_error() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let a = buffer(&device);
+        let recorded = Cell::new(Vec::new());
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+                .unwrap();
+        let mut graph = RenderGraph::default();
+        graph.add_pass("first").writes_buffer(a.clone()).build(|_| {
+            recorded.set(vec!["first"]);
+            Ok(())
+        });
+        graph
+            .add_pass("failing")
+            .reads_buffer(a.clone())
+            .build(|_| Err("failed".into()));
+        graph.add_pass("last").reads_buffer(a).build(|_| {
+            recorded.set(vec!["first", "last"]);
+            Ok(())
+        });
+
+        match graph.record(&mut builder) {
+            Err(RenderGraphError::PassError { ref name, .. }) if name == "failing" => (),
+            _ => panic!(),
+        }
+        assert_eq!(recorded.into_inner(), vec!["first"]);
+    }
+}
@@ -111,6 +111,24 @@ impl StateCacher {
         cmp!(compare_mask);
         cmp!(reference);
         cmp!(write_mask);
+        cmp!(depth_bounds);
+        cmp!(fragment_shading_rate);
+        cmp!(cull_mode);
+        cmp!(front_face);
+        cmp!(primitive_topology);
+        cmp!(depth_test_enable);
+        cmp!(depth_write_enable);
+        cmp!(depth_compare_op);
+        cmp!(primitive_restart_enable);
+        cmp!(rasterizer_discard_enable);
+        cmp!(depth_bias_enable);
+        cmp!(logic_op);
+        cmp!(color_blend_enable);
+        cmp!(color_blend_equation);
+        cmp!(color_write_mask);
+        cmp!(color_write_enable);
+        cmp!(line_stipple);
+        cmp!(sample_locations);
 
         changed
     }
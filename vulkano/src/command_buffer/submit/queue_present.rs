@@ -15,8 +15,10 @@ use std::ptr;
 
 use crate::device::DeviceOwned;
 use crate::device::Queue;
+use crate::swapchain::PresentMode;
 use crate::swapchain::PresentRegion;
 use crate::swapchain::Swapchain;
+use crate::sync::Fence;
 use crate::sync::Semaphore;
 
 use crate::check_errors;
@@ -34,6 +36,14 @@ pub struct SubmitPresentBuilder<'a> {
     image_indices: SmallVec<[u32; 4]>,
     present_regions: SmallVec<[vk::PresentRegionKHR; 4]>,
     rect_layers: SmallVec<[vk::RectLayerKHR; 4]>,
+    present_ids: SmallVec<[u64; 4]>,
+    present_fences: SmallVec<[vk::Fence; 4]>,
+    present_modes: SmallVec<[vk::PresentModeKHR; 4]>,
+    // True if `set_present_mode` has been called.
+    present_modes_set: bool,
+    present_times: SmallVec<[vk::PresentTimeGOOGLE; 4]>,
+    // True if `set_present_time` has been called.
+    present_times_set: bool,
     marker: PhantomData<&'a ()>,
 }
 
@@ -47,6 +57,12 @@ impl<'a> SubmitPresentBuilder<'a> {
             image_indices: SmallVec::new(),
             present_regions: SmallVec::new(),
             rect_layers: SmallVec::new(),
+            present_ids: SmallVec::new(),
+            present_fences: SmallVec::new(),
+            present_modes: SmallVec::new(),
+            present_modes_set: false,
+            present_times: SmallVec::new(),
+            present_times_set: false,
             marker: PhantomData,
         }
     }
@@ -81,6 +97,9 @@ impl<'a> SubmitPresentBuilder<'a> {
     ///
     /// If `VK_KHR_incremental_present` is not enabled, the `present_region` parameter is ignored.
     ///
+    /// The `present_id`, if any, tags the present so that it can later be waited upon with
+    /// `Swapchain::wait_for_present`.
+    ///
     /// # Safety
     ///
     /// - If you submit this builder, the swapchain must be kept alive until you are
@@ -88,12 +107,16 @@ impl<'a> SubmitPresentBuilder<'a> {
     ///
     /// - The swapchains and semaphores must all belong to the same device.
     ///
+    /// - If `present_id` is `Some`, the `present_id` feature must be enabled on the device and
+    ///   the id must be greater than any id previously used with this swapchain.
+    ///
     #[inline]
     pub unsafe fn add_swapchain<W>(
         &mut self,
         swapchain: &'a Swapchain<W>,
         image_num: u32,
         present_region: Option<&'a PresentRegion>,
+        present_id: Option<u64>,
     ) {
         debug_assert!(image_num < swapchain.num_images());
 
@@ -124,6 +147,86 @@ impl<'a> SubmitPresentBuilder<'a> {
 
         self.swapchains.push(swapchain.internal_object());
         self.image_indices.push(image_num);
+        // An id of zero means that the present isn't tagged.
+        self.present_ids.push(present_id.unwrap_or(0));
+        // A null fence means that no fence is signaled for this present.
+        self.present_fences.push(0);
+        // Presenting with the current mode of the swapchain doesn't change anything.
+        self.present_modes
+            .push(swapchain.current_present_mode() as vk::PresentModeKHR);
+        // A desired present time of zero means that the image is displayed as soon as possible.
+        self.present_times.push(vk::PresentTimeGOOGLE {
+            presentID: 0,
+            desiredPresentTime: 0,
+        });
+    }
+
+    /// Sets the identifier of the present of the swapchain that was added last, and the earliest
+    /// time at which its image must be displayed.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if no swapchain has been added with `add_swapchain`.
+    ///
+    /// # Safety
+    ///
+    /// - The `google_display_timing` extension must be enabled on the device.
+    ///
+    #[inline]
+    pub unsafe fn set_present_time(&mut self, present_id: u32, desired_present_time: u64) {
+        *self
+            .present_times
+            .last_mut()
+            .expect("no swapchain has been added") = vk::PresentTimeGOOGLE {
+            presentID: present_id,
+            desiredPresentTime: desired_present_time,
+        };
+        self.present_times_set = true;
+    }
+
+    /// Sets a fence to signal once the present of the swapchain that was added last no longer uses
+    /// its resources, such as the semaphores that it waits upon.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if no swapchain has been added with `add_swapchain`.
+    ///
+    /// # Safety
+    ///
+    /// - The `swapchain_maintenance1` feature must be enabled on the device.
+    ///
+    /// - If you submit this builder, the fence must be unsignaled, must not be used by any other
+    ///   submission and must be kept alive until it is signaled.
+    ///
+    #[inline]
+    pub unsafe fn set_present_fence(&mut self, fence: &'a Fence) {
+        *self
+            .present_fences
+            .last_mut()
+            .expect("no swapchain has been added") = fence.internal_object();
+    }
+
+    /// Switches the swapchain that was added last to another present mode, starting with this
+    /// present.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if no swapchain has been added with `add_swapchain`.
+    ///
+    /// # Safety
+    ///
+    /// - The `swapchain_maintenance1` feature must be enabled on the device.
+    ///
+    /// - `mode` must be the present mode of the swapchain or one of the
+    ///   `SwapchainOptions::present_modes` that it was created with.
+    ///
+    #[inline]
+    pub unsafe fn set_present_mode(&mut self, mode: PresentMode) {
+        *self
+            .present_modes
+            .last_mut()
+            .expect("no swapchain has been added") = mode as vk::PresentModeKHR;
+        self.present_modes_set = true;
     }
 
     /// Submits the command. Calls `vkQueuePresentKHR`.
@@ -140,6 +243,74 @@ impl<'a> SubmitPresentBuilder<'a> {
                 "Tried to submit a present command without any swapchain"
             );
 
+            let present_times = if self.present_times_set {
+                debug_assert!(queue.device().loaded_extensions().google_display_timing);
+                debug_assert_eq!(self.swapchains.len(), self.present_times.len());
+                Some(vk::PresentTimesInfoGOOGLE {
+                    sType: vk::STRUCTURE_TYPE_PRESENT_TIMES_INFO_GOOGLE,
+                    pNext: ptr::null(),
+                    swapchainCount: self.present_times.len() as u32,
+                    pTimes: self.present_times.as_ptr(),
+                })
+            } else {
+                None
+            };
+            let present_times_ptr = present_times
+                .as_ref()
+                .map(|times| times as *const vk::PresentTimesInfoGOOGLE as *const _)
+                .unwrap_or(ptr::null());
+
+            let present_modes = if self.present_modes_set {
+                debug_assert!(queue.device().enabled_features().swapchain_maintenance1);
+                debug_assert_eq!(self.swapchains.len(), self.present_modes.len());
+                Some(vk::SwapchainPresentModeInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_SWAPCHAIN_PRESENT_MODE_INFO_EXT,
+                    pNext: present_times_ptr,
+                    swapchainCount: self.present_modes.len() as u32,
+                    pPresentModes: self.present_modes.as_ptr(),
+                })
+            } else {
+                None
+            };
+            let present_modes_ptr = present_modes
+                .as_ref()
+                .map(|modes| modes as *const vk::SwapchainPresentModeInfoEXT as *const _)
+                .unwrap_or(present_times_ptr);
+
+            let present_fences = if self.present_fences.iter().any(|&fence| fence != 0) {
+                debug_assert!(queue.device().enabled_features().swapchain_maintenance1);
+                debug_assert_eq!(self.swapchains.len(), self.present_fences.len());
+                Some(vk::SwapchainPresentFenceInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_SWAPCHAIN_PRESENT_FENCE_INFO_EXT,
+                    pNext: present_modes_ptr,
+                    swapchainCount: self.present_fences.len() as u32,
+                    pFences: self.present_fences.as_ptr(),
+                })
+            } else {
+                None
+            };
+            let present_fences_ptr = present_fences
+                .as_ref()
+                .map(|fences| fences as *const vk::SwapchainPresentFenceInfoEXT as *const _)
+                .unwrap_or(present_modes_ptr);
+
+            let present_ids = if self.present_ids.iter().any(|&id| id != 0) {
+                debug_assert!(queue.device().enabled_features().present_id);
+                debug_assert_eq!(self.swapchains.len(), self.present_ids.len());
+                Some(vk::PresentIdKHR {
+                    sType: vk::STRUCTURE_TYPE_PRESENT_ID_KHR,
+                    pNext: present_fences_ptr,
+                    swapchainCount: self.present_ids.len() as u32,
+                    pPresentIds: self.present_ids.as_ptr(),
+                })
+            } else {
+                None
+            };
+            let present_ids_ptr = present_ids
+                .as_ref()
+                .map(|ids| ids as *const vk::PresentIdKHR as *const _)
+                .unwrap_or(present_fences_ptr);
+
             let present_regions = {
                 if !self.present_regions.is_empty() {
                     debug_assert!(queue.device().loaded_extensions().khr_incremental_present);
@@ -151,7 +322,7 @@ impl<'a> SubmitPresentBuilder<'a> {
                     }
                     Some(vk::PresentRegionsKHR {
                         sType: vk::STRUCTURE_TYPE_PRESENT_REGIONS_KHR,
-                        pNext: ptr::null(),
+                        pNext: present_ids_ptr,
                         swapchainCount: self.present_regions.len() as u32,
                         pRegions: self.present_regions.as_ptr(),
                     })
@@ -170,7 +341,7 @@ impl<'a> SubmitPresentBuilder<'a> {
                 pNext: present_regions
                     .as_ref()
                     .map(|pr| pr as *const vk::PresentRegionsKHR as *const _)
-                    .unwrap_or(ptr::null()),
+                    .unwrap_or(present_ids_ptr),
                 waitSemaphoreCount: self.wait_semaphores.len() as u32,
                 pWaitSemaphores: self.wait_semaphores.as_ptr(),
                 swapchainCount: self.swapchains.len() as u32,
@@ -196,6 +367,9 @@ impl<'a> fmt::Debug for SubmitPresentBuilder<'a> {
             .field("wait_semaphores", &self.wait_semaphores)
             .field("swapchains", &self.swapchains)
             .field("image_indices", &self.image_indices)
+            .field("present_ids", &self.present_ids)
+            .field("present_fences", &self.present_fences)
+            .field("present_modes", &self.present_modes)
             .finish()
     }
 }
@@ -12,12 +12,14 @@ use std::error;
 use std::fmt;
 use std::marker::PhantomData;
 use std::ptr;
+use std::slice;
 
 use crate::command_buffer::sys::UnsafeCommandBuffer;
 use crate::device::Queue;
 use crate::sync::Fence;
 use crate::sync::PipelineStages;
 use crate::sync::Semaphore;
+use crate::sync::TimelineSemaphore;
 
 use crate::check_errors;
 use crate::vk;
@@ -31,8 +33,17 @@ use crate::VulkanObject;
 #[derive(Debug)]
 pub struct SubmitCommandBufferBuilder<'a> {
     wait_semaphores: SmallVec<[vk::Semaphore; 16]>,
+    // The value to wait for of each semaphore in `wait_semaphores`. Ignored for binary semaphores.
+    wait_values: SmallVec<[u64; 16]>,
     destination_stages: SmallVec<[vk::PipelineStageFlags; 8]>,
     signal_semaphores: SmallVec<[vk::Semaphore; 16]>,
+    // The value to signal of each semaphore in `signal_semaphores`. Ignored for binary semaphores.
+    signal_values: SmallVec<[u64; 16]>,
+    // If true, at least one timeline semaphore has been added and the values must be passed to
+    // the implementation.
+    has_timeline_semaphores: bool,
+    // If true, the command buffers are protected and must be submitted as such.
+    protected: bool,
     command_buffers: SmallVec<[vk::CommandBuffer; 4]>,
     fence: vk::Fence,
     marker: PhantomData<&'a ()>,
@@ -44,8 +55,12 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     pub fn new() -> SubmitCommandBufferBuilder<'a> {
         SubmitCommandBufferBuilder {
             wait_semaphores: SmallVec::new(),
+            wait_values: SmallVec::new(),
             destination_stages: SmallVec::new(),
             signal_semaphores: SmallVec::new(),
+            signal_values: SmallVec::new(),
+            has_timeline_semaphores: false,
+            protected: false,
             command_buffers: SmallVec::new(),
             fence: 0,
             marker: PhantomData,
@@ -75,6 +90,12 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         self.fence != 0
     }
 
+    /// Returns true if the submission is a protected submission.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
     /// Adds an operation that signals a fence after this submission ends.
     ///
     /// # Example
@@ -144,9 +165,44 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         debug_assert!(stages.into_vulkan_bits() != 0);
         // TODO: debug assert that the device supports the stages
         self.wait_semaphores.push(semaphore.internal_object());
+        self.wait_values.push(0);
         self.destination_stages.push(stages.into_vulkan_bits());
     }
 
+    /// Adds a timeline semaphore to be waited upon before the command buffers are executed.
+    ///
+    /// Only the given `stages` of the command buffers added afterwards will wait until the
+    /// counter of the semaphore is greater than or equal to `value`. Other stages not included in
+    /// `stages` can execute before waiting.
+    ///
+    /// Contrary to binary semaphores, any number of submissions can wait for the same value.
+    ///
+    /// # Safety
+    ///
+    /// - The stages must be supported by the device.
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has at least started executing the command buffers.
+    ///
+    /// - If you submit this builder, the counter of the semaphore must reach `value` eventually,
+    ///   either from the CPU or from a submission that doesn't depend on this one.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_wait_timeline_semaphore(
+        &mut self,
+        semaphore: &'a TimelineSemaphore,
+        value: u64,
+        stages: PipelineStages,
+    ) {
+        debug_assert!(stages.into_vulkan_bits() != 0);
+        self.wait_semaphores.push(semaphore.internal_object());
+        self.wait_values.push(value);
+        self.destination_stages.push(stages.into_vulkan_bits());
+        self.has_timeline_semaphores = true;
+    }
+
     /// Adds a command buffer that is executed as part of this command.
     ///
     /// The command buffers are submitted in the order in which they are added.
@@ -172,7 +228,8 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
 
     /// Returns the number of semaphores to signal.
     ///
-    /// In other words, this is the number of times `add_signal_semaphore` has been called.
+    /// In other words, this is the number of times `add_signal_semaphore` and
+    /// `add_signal_timeline_semaphore` have been called.
     #[inline]
     pub fn num_signal_semaphores(&self) -> usize {
         self.signal_semaphores.len()
@@ -193,6 +250,46 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     #[inline]
     pub unsafe fn add_signal_semaphore(&mut self, semaphore: &'a Semaphore) {
         self.signal_semaphores.push(semaphore.internal_object());
+        self.signal_values.push(0);
+    }
+
+    /// Adds a timeline semaphore whose counter is going to be set to `value` at the end of the
+    /// submission.
+    ///
+    /// # Safety
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has finished executing this submission.
+    ///
+    /// - `value` must be greater than the current value of the counter, and than the values of
+    ///   all the signal operations that are submitted before this one.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_signal_timeline_semaphore(
+        &mut self,
+        semaphore: &'a TimelineSemaphore,
+        value: u64,
+    ) {
+        self.signal_semaphores.push(semaphore.internal_object());
+        self.signal_values.push(value);
+        self.has_timeline_semaphores = true;
+    }
+
+    /// Sets whether the submission is a protected submission.
+    ///
+    /// # Safety
+    ///
+    /// - If `protected` is true, all the command buffers must have been allocated from a
+    ///   protected command pool, and the builder must be submitted to a protected queue.
+    ///
+    /// - If `protected` is false, none of the command buffers must have been allocated from a
+    ///   protected command pool.
+    ///
+    #[inline]
+    pub unsafe fn set_protected(&mut self, protected: bool) {
+        self.protected = protected;
     }
 
     /// Submits the command buffer to the given queue.
@@ -201,25 +298,89 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     /// > possible together and avoid submitting them one by one.
     ///
     pub fn submit(self, queue: &Queue) -> Result<(), SubmitCommandBufferError> {
+        SubmitCommandBufferBuilder::submit_batches(queue, slice::from_ref(&self))
+    }
+
+    /// Submits multiple builders to the given queue with a single call, with one batch per
+    /// builder.
+    ///
+    /// The fence of the builder that has one, if any, is signaled once all the batches have
+    /// finished executing. Returns `MultipleFences` without submitting anything if more than one
+    /// builder has a fence.
+    pub(crate) fn submit_batches(
+        queue: &Queue,
+        batches: &[SubmitCommandBufferBuilder<'a>],
+    ) -> Result<(), SubmitCommandBufferError> {
         unsafe {
             let vk = queue.device().pointers();
             let queue = queue.internal_object_guard();
 
-            debug_assert_eq!(self.wait_semaphores.len(), self.destination_stages.len());
-
-            let batch = vk::SubmitInfo {
-                sType: vk::STRUCTURE_TYPE_SUBMIT_INFO,
-                pNext: ptr::null(),
-                waitSemaphoreCount: self.wait_semaphores.len() as u32,
-                pWaitSemaphores: self.wait_semaphores.as_ptr(),
-                pWaitDstStageMask: self.destination_stages.as_ptr(),
-                commandBufferCount: self.command_buffers.len() as u32,
-                pCommandBuffers: self.command_buffers.as_ptr(),
-                signalSemaphoreCount: self.signal_semaphores.len() as u32,
-                pSignalSemaphores: self.signal_semaphores.as_ptr(),
-            };
-
-            check_errors(vk.QueueSubmit(*queue, 1, &batch, self.fence))?;
+            let mut fence = 0;
+            for batch in batches {
+                debug_assert_eq!(batch.wait_semaphores.len(), batch.destination_stages.len());
+                debug_assert_eq!(batch.wait_semaphores.len(), batch.wait_values.len());
+                debug_assert_eq!(batch.signal_semaphores.len(), batch.signal_values.len());
+
+                if batch.fence != 0 {
+                    if fence != 0 {
+                        return Err(SubmitCommandBufferError::MultipleFences);
+                    }
+                    fence = batch.fence;
+                }
+            }
+
+            let timeline_infos: SmallVec<[_; 4]> = batches
+                .iter()
+                .map(|batch| vk::TimelineSemaphoreSubmitInfo {
+                    sType: vk::STRUCTURE_TYPE_TIMELINE_SEMAPHORE_SUBMIT_INFO,
+                    pNext: ptr::null(),
+                    waitSemaphoreValueCount: batch.wait_values.len() as u32,
+                    pWaitSemaphoreValues: batch.wait_values.as_ptr(),
+                    signalSemaphoreValueCount: batch.signal_values.len() as u32,
+                    pSignalSemaphoreValues: batch.signal_values.as_ptr(),
+                })
+                .collect();
+
+            let protected_infos: SmallVec<[_; 4]> = batches
+                .iter()
+                .zip(timeline_infos.iter())
+                .map(|(batch, timeline_infos)| vk::ProtectedSubmitInfo {
+                    sType: vk::STRUCTURE_TYPE_PROTECTED_SUBMIT_INFO,
+                    pNext: if batch.has_timeline_semaphores {
+                        timeline_infos as *const _ as *const _
+                    } else {
+                        ptr::null()
+                    },
+                    protectedSubmit: vk::TRUE,
+                })
+                .collect();
+
+            let infos: SmallVec<[_; 4]> = batches
+                .iter()
+                .zip(timeline_infos.iter())
+                .zip(protected_infos.iter())
+                .map(
+                    |((batch, timeline_infos), protected_infos)| vk::SubmitInfo {
+                        sType: vk::STRUCTURE_TYPE_SUBMIT_INFO,
+                        pNext: if batch.protected {
+                            protected_infos as *const _ as *const _
+                        } else if batch.has_timeline_semaphores {
+                            timeline_infos as *const _ as *const _
+                        } else {
+                            ptr::null()
+                        },
+                        waitSemaphoreCount: batch.wait_semaphores.len() as u32,
+                        pWaitSemaphores: batch.wait_semaphores.as_ptr(),
+                        pWaitDstStageMask: batch.destination_stages.as_ptr(),
+                        commandBufferCount: batch.command_buffers.len() as u32,
+                        pCommandBuffers: batch.command_buffers.as_ptr(),
+                        signalSemaphoreCount: batch.signal_semaphores.len() as u32,
+                        pSignalSemaphores: batch.signal_semaphores.as_ptr(),
+                    },
+                )
+                .collect();
+
+            check_errors(vk.QueueSubmit(*queue, infos.len() as u32, infos.as_ptr(), fence))?;
             Ok(())
         }
     }
@@ -228,17 +389,25 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     ///
     /// # Panic
     ///
-    /// Panics if both builders have a fence already set.
+    /// - Panics if both builders have a fence already set.
+    /// - Panics if only one of the builders is a protected submission.
     // TODO: create multiple batches instead
     pub fn merge(mut self, other: Self) -> Self {
         assert!(
             self.fence == 0 || other.fence == 0,
             "Can't merge two queue submits that both have a fence"
         );
+        assert_eq!(
+            self.protected, other.protected,
+            "Can't merge a protected queue submit with an unprotected one"
+        );
 
         self.wait_semaphores.extend(other.wait_semaphores);
+        self.wait_values.extend(other.wait_values);
         self.destination_stages.extend(other.destination_stages); // TODO: meh? will be solved if we submit multiple batches
         self.signal_semaphores.extend(other.signal_semaphores);
+        self.signal_values.extend(other.signal_values);
+        self.has_timeline_semaphores |= other.has_timeline_semaphores;
         self.command_buffers.extend(other.command_buffers);
 
         if self.fence == 0 {
@@ -258,6 +427,9 @@ pub enum SubmitCommandBufferError {
 
     /// The connection to the device has been lost.
     DeviceLost,
+
+    /// More than one of the builders submitted with a single call has a fence.
+    MultipleFences,
 }
 
 impl error::Error for SubmitCommandBufferError {
@@ -280,6 +452,9 @@ impl fmt::Display for SubmitCommandBufferError {
                 SubmitCommandBufferError::OomError(_) => "not enough memory",
                 SubmitCommandBufferError::DeviceLost =>
                     "the connection to the device has been lost",
+                SubmitCommandBufferError::MultipleFences => {
+                    "more than one of the builders submitted with a single call has a fence"
+                }
             }
         )
     }
@@ -302,8 +477,8 @@ impl From<Error> for SubmitCommandBufferError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
     use crate::sync::Fence;
+    use std::time::Duration;
 
     #[test]
     fn empty_submit() {
@@ -361,4 +536,20 @@ mod tests {
             });
         }
     }
+
+    #[test]
+    fn merge_protected_and_unprotected() {
+        unsafe {
+            let mut builder1 = SubmitCommandBufferBuilder::new();
+            builder1.set_protected(true);
+            let builder2 = SubmitCommandBufferBuilder::new();
+
+            assert_should_panic!(
+                "Can't merge a protected queue submit with an unprotected one",
+                {
+                    let _ = builder1.merge(builder2);
+                }
+            );
+        }
+    }
 }
@@ -0,0 +1,210 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+
+use crate::command_buffer::submit::SubmitAnyBuilder;
+use crate::command_buffer::submit::SubmitBindSparseBuilder;
+use crate::command_buffer::submit::SubmitCommandBufferBuilder;
+use crate::command_buffer::submit::SubmitPresentBuilder;
+use crate::device::Queue;
+use crate::sync::FlushError;
+
+/// Accumulates the submissions of a frame and sends them to a queue with as few calls as
+/// possible.
+///
+/// Consecutive command buffer submissions are sent with a single `vkQueueSubmit` call, with one
+/// batch each so that every submission keeps its own semaphores. A call is ended after a
+/// submission that signals a fence, since a call can only signal one fence. Present and sparse
+/// binding operations are sent between the calls, in the order in which they were added.
+///
+/// This is what the futures of the `sync` module use to send their submissions when they are
+/// flushed.
+///
+/// ```
+/// use vulkano::command_buffer::submit::SubmitCommandBufferBuilder;
+/// use vulkano::command_buffer::submit::SubmitPresentBuilder;
+/// use vulkano::command_buffer::submit::SubmitScheduler;
+///
+/// let mut scheduler = SubmitScheduler::new();
+/// scheduler.add_command_buffers(SubmitCommandBufferBuilder::new());
+/// scheduler.add_command_buffers(SubmitCommandBufferBuilder::new());
+/// scheduler.add_present(SubmitPresentBuilder::new());
+///
+/// // One call to `vkQueueSubmit` and one call to `vkQueuePresentKHR`.
+/// assert_eq!(scheduler.num_queue_calls(), 2);
+/// ```
+#[derive(Debug)]
+pub struct SubmitScheduler<'a> {
+    operations: Vec<Operation<'a>>,
+}
+
+#[derive(Debug)]
+enum Operation<'a> {
+    // Command buffer submissions that are sent with a single call. Only the last one can have a
+    // fence.
+    CommandBuffers(Box<SmallVec<[SubmitCommandBufferBuilder<'a>; 4]>>),
+    Present(SubmitPresentBuilder<'a>),
+    BindSparse(SubmitBindSparseBuilder<'a>),
+}
+
+impl<'a> SubmitScheduler<'a> {
+    /// Builds a new empty `SubmitScheduler`.
+    #[inline]
+    pub fn new() -> SubmitScheduler<'a> {
+        SubmitScheduler {
+            operations: Vec::new(),
+        }
+    }
+
+    /// Returns true if no operation has been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Returns the number of calls that `flush` is going to make to the queue.
+    #[inline]
+    pub fn num_queue_calls(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Adds any kind of submission.
+    ///
+    /// Waiting on semaphores without submitting anything is turned into a command buffer
+    /// submission without any command buffer.
+    pub fn add(&mut self, builder: SubmitAnyBuilder<'a>) {
+        match builder {
+            SubmitAnyBuilder::Empty => (),
+            SubmitAnyBuilder::SemaphoresWait(builder) => self.add_command_buffers(builder.into()),
+            SubmitAnyBuilder::CommandBuffer(builder) => self.add_command_buffers(builder),
+            SubmitAnyBuilder::QueuePresent(builder) => self.add_present(builder),
+            SubmitAnyBuilder::BindSparse(builder) => self.add_bind_sparse(builder),
+        }
+    }
+
+    /// Adds a command buffer submission, which is sent after all the operations added before.
+    pub fn add_command_buffers(&mut self, builder: SubmitCommandBufferBuilder<'a>) {
+        if let Some(Operation::CommandBuffers(ref mut batches)) = self.operations.last_mut() {
+            if !batches.last().unwrap().has_fence() {
+                batches.push(builder);
+                return;
+            }
+        }
+
+        let mut batches = Box::new(SmallVec::new());
+        batches.push(builder);
+        self.operations.push(Operation::CommandBuffers(batches));
+    }
+
+    /// Adds a present operation, which is sent after all the operations added before.
+    #[inline]
+    pub fn add_present(&mut self, builder: SubmitPresentBuilder<'a>) {
+        self.operations.push(Operation::Present(builder));
+    }
+
+    /// Adds a sparse binding operation, which is sent after all the operations added before.
+    #[inline]
+    pub fn add_bind_sparse(&mut self, builder: SubmitBindSparseBuilder<'a>) {
+        self.operations.push(Operation::BindSparse(builder));
+    }
+
+    /// Sends all the operations to the given queue, in order, and removes them from the
+    /// scheduler.
+    ///
+    /// If an error happens, the operation that failed is removed, and the operations that come
+    /// after it are kept in the scheduler without being sent.
+    ///
+    /// > **Note**: The safety requirements of each builder still apply. In particular, the
+    /// > objects that they reference must be kept alive until the GPU has finished using them.
+    pub fn flush(&mut self, queue: &Queue) -> Result<(), FlushError> {
+        while !self.operations.is_empty() {
+            match self.operations.remove(0) {
+                Operation::CommandBuffers(batches) => {
+                    SubmitCommandBufferBuilder::submit_batches(queue, &batches)?;
+                }
+                Operation::Present(builder) => builder.submit(queue)?,
+                Operation::BindSparse(builder) => builder.submit(queue)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Default for SubmitScheduler<'a> {
+    #[inline]
+    fn default() -> SubmitScheduler<'a> {
+        SubmitScheduler::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_buffer::submit::SubmitCommandBufferError;
+    use crate::sync::Fence;
+
+    #[test]
+    fn groups_command_buffers() {
+        let mut scheduler = SubmitScheduler::new();
+        assert!(scheduler.is_empty());
+
+        scheduler.add(SubmitAnyBuilder::Empty);
+        assert!(scheduler.is_empty());
+
+        scheduler.add_command_buffers(SubmitCommandBufferBuilder::new());
+        scheduler.add_command_buffers(SubmitCommandBufferBuilder::new());
+        assert_eq!(scheduler.num_queue_calls(), 1);
+
+        scheduler.add_present(SubmitPresentBuilder::new());
+        scheduler.add_command_buffers(SubmitCommandBufferBuilder::new());
+        assert_eq!(scheduler.num_queue_calls(), 3);
+    }
+
+    #[test]
+    fn fence_ends_call() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let fence = Fence::alloc(device.clone()).unwrap();
+
+            let mut scheduler = SubmitScheduler::new();
+            let mut builder = SubmitCommandBufferBuilder::new();
+            builder.set_fence_signal(&fence);
+            scheduler.add_command_buffers(builder);
+            scheduler.add_command_buffers(SubmitCommandBufferBuilder::new());
+            assert_eq!(scheduler.num_queue_calls(), 2);
+
+            scheduler.flush(&queue).unwrap();
+            assert!(scheduler.is_empty());
+            fence.wait(None).unwrap();
+        }
+    }
+
+    #[test]
+    fn multiple_fences_in_one_call() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let fence1 = Fence::alloc(device.clone()).unwrap();
+            let fence2 = Fence::alloc(device.clone()).unwrap();
+
+            let mut builder1 = SubmitCommandBufferBuilder::new();
+            builder1.set_fence_signal(&fence1);
+            let mut builder2 = SubmitCommandBufferBuilder::new();
+            builder2.set_fence_signal(&fence2);
+
+            match SubmitCommandBufferBuilder::submit_batches(&queue, &[builder1, builder2]) {
+                Err(SubmitCommandBufferError::MultipleFences) => (),
+                _ => panic!(),
+            }
+        }
+    }
+}
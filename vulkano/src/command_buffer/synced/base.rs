@@ -9,6 +9,9 @@
 
 use crate::buffer::BufferAccess;
 use crate::command_buffer::pool::UnsafeCommandPoolAlloc;
+use crate::command_buffer::synced::log::SyncEvent;
+use crate::command_buffer::synced::log::SyncEventKind;
+use crate::command_buffer::synced::log::SyncLogger;
 use crate::command_buffer::sys::Flags;
 use crate::command_buffer::sys::UnsafeCommandBuffer;
 use crate::command_buffer::sys::UnsafeCommandBufferBuilder;
@@ -92,6 +95,17 @@ pub struct SyncCommandBufferBuilder {
 
     // True if we're a secondary command buffer.
     is_secondary: bool,
+
+    // Layouts that images are in at the start of the command buffer, indexed by their conflict
+    // key, when they differ from their initial layout requirement.
+    initial_layouts: FnvHashMap<u64, ImageLayout>,
+
+    // If true, images aren't transitioned to their final layout requirement at the end of the
+    // command buffer.
+    keep_final_layouts: bool,
+
+    // Receives the synchronization decisions, if set.
+    logger: Option<SyncLogger>,
 }
 
 // # How pipeline stages work in Vulkan
@@ -192,6 +206,38 @@ impl From<CommandBufferExecError> for SyncCommandBufferBuilderError {
     }
 }
 
+// Sends an event about a resource of a command to the logger, if there is one. The kind of
+// the event is only built if needed.
+fn log_event<F>(
+    logger: &mut Option<SyncLogger>,
+    commands: &Commands,
+    command_index: usize,
+    resource_ty: KeyTy,
+    resource_index: usize,
+    kind: F,
+) where
+    F: FnOnce() -> SyncEventKind,
+{
+    let logger = match logger {
+        Some(logger) => logger,
+        None => return,
+    };
+
+    let command = &commands.commands[command_index];
+    let resource_name = match resource_ty {
+        KeyTy::Buffer => command.buffer_name(resource_index),
+        KeyTy::Image => command.image_name(resource_index),
+    };
+
+    logger(&SyncEvent {
+        command_index,
+        command_name: command.name(),
+        resource_name,
+        is_image: resource_ty == KeyTy::Image,
+        kind: kind(),
+    });
+}
+
 // List of commands stored inside a `SyncCommandBufferBuilder`.
 struct Commands {
     // Only the commands before `first_unflushed` have already been sent to the inner
@@ -479,9 +525,49 @@ impl SyncCommandBufferBuilder {
             barriers: Vec::new(),
             is_poisoned: false,
             is_secondary,
+            initial_layouts: FnvHashMap::default(),
+            keep_final_layouts: false,
+            logger: None,
         }
     }
 
+    /// Sets a function that is called with every synchronization decision that the builder
+    /// makes: the first use of each resource, the accesses that are merged or that require a
+    /// pipeline barrier, the image layout transitions, and the conflicts.
+    ///
+    /// This is meant for debugging incorrect rendering caused by wrong assumptions about
+    /// synchronization, and slows down the recording of commands.
+    #[inline]
+    pub fn set_logger(&mut self, logger: Option<SyncLogger>) {
+        self.logger = logger;
+    }
+
+    /// Makes the images start in the layouts that they have at the end of `previous`, instead of
+    /// their initial layout requirement. Only affects the images that haven't been used yet by
+    /// this builder.
+    ///
+    /// # Safety
+    ///
+    /// - The command buffer must only be submitted right after `previous` on the same queue.
+    ///
+    pub unsafe fn continue_from(&mut self, previous: &SyncCommandBuffer) {
+        debug_assert!(!self.is_secondary);
+        self.initial_layouts.extend(previous.final_image_layouts());
+    }
+
+    /// If `keep` is true, the images are left in the layout of their last use at the end of the
+    /// command buffer, instead of being transitioned to their final layout requirement.
+    ///
+    /// # Safety
+    ///
+    /// - The command buffer must only be submitted right before a command buffer whose builder
+    ///   called `continue_from` with it, on the same queue and in the same chain of futures.
+    ///
+    #[inline]
+    pub unsafe fn set_keep_final_layouts(&mut self, keep: bool) {
+        self.keep_final_layouts = keep;
+    }
+
     // Adds a command to be processed by the builder.
     //
     // The `resources` argument should contain each buffer or image used by the command.
@@ -604,6 +690,17 @@ impl SyncCommandBufferBuilder {
                                             let cmd1 = &commands_lock.commands[*collision_cmd_id];
                                             let cmd2 = &commands_lock.commands[latest_command_id];
 
+                                            log_event(
+                                                &mut self.logger,
+                                                &commands_lock,
+                                                latest_command_id,
+                                                resource_ty,
+                                                resource_index,
+                                                || SyncEventKind::Conflict {
+                                                    previous_command_index: *collision_cmd_id,
+                                                },
+                                            );
+
                                             return Err(SyncCommandBufferBuilderError::Conflict {
                                                 command1_name: cmd1.name(),
                                                 command1_param: match entry_key_resource_ty {
@@ -679,6 +776,25 @@ impl SyncCommandBufferBuilder {
                                         );
                                     }
                                 };
+
+                                let previous_command_index =
+                                    collision_cmd_ids.last().cloned().unwrap_or(0);
+                                log_event(
+                                    &mut self.logger,
+                                    &commands_lock,
+                                    latest_command_id,
+                                    resource_ty,
+                                    resource_index,
+                                    || SyncEventKind::Barrier {
+                                        previous_command_index,
+                                        source_stages: entry.memory.stages,
+                                        source_access: entry.memory.access,
+                                        destination_stages: memory.stages,
+                                        destination_access: memory.access,
+                                        old_layout: entry.current_layout,
+                                        new_layout: start_layout,
+                                    },
+                                );
                             }
 
                             // Update state.
@@ -697,6 +813,18 @@ impl SyncCommandBufferBuilder {
                             let entry = entry.into_mut();
                             entry.memory.stages |= memory.stages;
                             entry.memory.access |= memory.access;
+
+                            if self.logger.is_some() {
+                                let commands_lock = self.commands.lock().unwrap();
+                                log_event(
+                                    &mut self.logger,
+                                    &commands_lock,
+                                    latest_command_id,
+                                    resource_ty,
+                                    resource_index,
+                                    || SyncEventKind::Merged,
+                                );
+                            }
                         }
                     }
 
@@ -706,6 +834,7 @@ impl SyncCommandBufferBuilder {
                         // is different from the first layout usage.
                         let mut actually_exclusive = memory.exclusive;
                         let mut actual_start_layout = start_layout;
+                        let mut transition = None;
 
                         if !self.is_secondary
                             && resource_ty == KeyTy::Image
@@ -715,11 +844,16 @@ impl SyncCommandBufferBuilder {
                             let commands_lock = self.commands.lock().unwrap();
                             let img =
                                 commands_lock.commands[latest_command_id].image(resource_index);
-                            let initial_layout_requirement = img.initial_layout_requirement();
+                            let seeded_layout = self.initial_layouts.get(&img.conflict_key());
+                            let initial_layout_requirement = seeded_layout
+                                .cloned()
+                                .unwrap_or_else(|| img.initial_layout_requirement());
 
                             // Checks if the image is initialized and transitions it
-                            // if it isn't
-                            let is_layout_initialized = img.is_layout_initialized();
+                            // if it isn't. An image that is seeded with the layout of a previous
+                            // command buffer is always initialized by it.
+                            let is_layout_initialized =
+                                seeded_layout.is_some() || img.is_layout_initialized();
 
                             if initial_layout_requirement != start_layout || !is_layout_initialized
                             {
@@ -765,10 +899,23 @@ impl SyncCommandBufferBuilder {
                                         start_layout,
                                     );
                                     img.layout_initialized();
+                                    transition = Some((from_layout, start_layout));
                                 }
                             }
                         }
 
+                        if self.logger.is_some() {
+                            let commands_lock = self.commands.lock().unwrap();
+                            log_event(
+                                &mut self.logger,
+                                &commands_lock,
+                                latest_command_id,
+                                resource_ty,
+                                resource_index,
+                                || SyncEventKind::FirstUse { transition },
+                            );
+                        }
+
                         entry.insert(ResourceState {
                             memory: PipelineMemoryAccess {
                                 stages: memory.stages,
@@ -869,7 +1016,7 @@ impl SyncCommandBufferBuilder {
         }
 
         // Transition images to their desired final layout.
-        if !self.is_secondary {
+        if !self.is_secondary && !self.keep_final_layouts {
             unsafe {
                 // TODO: this could be optimized by merging the barrier with the barrier above?
                 let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
@@ -886,6 +1033,19 @@ impl SyncCommandBufferBuilder {
                         continue;
                     }
 
+                    let last_command_id = *key.command_ids.borrow().last().unwrap();
+                    log_event(
+                        &mut self.logger,
+                        &commands_lock,
+                        last_command_id,
+                        KeyTy::Image,
+                        key.resource_index,
+                        || SyncEventKind::FinalTransition {
+                            old_layout: state.current_layout,
+                            new_layout: requested_layout,
+                        },
+                    );
+
                     barrier.add_image_memory_barrier(
                         img,
                         img.current_miplevels_access(),
@@ -940,6 +1100,7 @@ impl SyncCommandBufferBuilder {
             resources: final_resources_states,
             commands: final_commands,
             barriers: self.barriers,
+            keep_final_layouts: self.keep_final_layouts,
         })
     }
 }
@@ -977,6 +1138,9 @@ pub struct SyncCommandBuffer {
     // Locations within commands that pipeline barriers were inserted. For debugging purposes.
     // TODO: present only in cfg(debug_assertions)?
     barriers: Vec<usize>,
+
+    // True if the images weren't transitioned to their final layout requirement at the end.
+    keep_final_layouts: bool,
 }
 
 impl SyncCommandBuffer {
@@ -1107,11 +1271,7 @@ impl SyncCommandBuffer {
                     KeyTy::Image => {
                         let command = &self.commands[command_ids[0]];
                         let img = command.image(resource_index);
-                        let trans = if val.final_layout != val.initial_layout {
-                            Some(val.final_layout)
-                        } else {
-                            None
-                        };
+                        let trans = self.unlock_transition(img, val);
                         unsafe {
                             img.unlock(trans);
                         }
@@ -1155,17 +1315,52 @@ impl SyncCommandBuffer {
 
                 KeyTy::Image => {
                     let img = command.image(resource_index);
-                    let trans = if val.final_layout != val.initial_layout {
-                        Some(val.final_layout)
-                    } else {
-                        None
-                    };
+                    let trans = self.unlock_transition(img, val);
                     img.unlock(trans);
                 }
             }
         }
     }
 
+    // Returns the layout to pass to `ImageAccess::unlock`. When the final layouts are kept, an
+    // image left in another layout than its final layout requirement is transitioned back by
+    // the command buffer that continues from this one, which reports the transition instead.
+    fn unlock_transition(
+        &self,
+        image: &dyn ImageAccess,
+        state: &ResourceFinalState,
+    ) -> Option<ImageLayout> {
+        if state.final_layout == state.initial_layout {
+            return None;
+        }
+
+        if self.keep_final_layouts && state.final_layout != image.final_layout_requirement() {
+            return None;
+        }
+
+        Some(state.final_layout)
+    }
+
+    /// Returns the layouts that the images used by this command buffer are in at its end,
+    /// indexed by their conflict key.
+    pub fn final_image_layouts(&self) -> Vec<(u64, ImageLayout)> {
+        self.resources
+            .iter()
+            .filter_map(|(key, state)| match *key {
+                CbKey::Command {
+                    ref command_ids,
+                    resource_ty: KeyTy::Image,
+                    resource_index,
+                    ..
+                } => {
+                    let img = self.commands[command_ids[0]].image(resource_index);
+                    Some((img.conflict_key(), state.final_layout))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Checks whether this command buffer has access to a buffer.
     ///
     /// > **Note**: Suitable when implementing the `CommandBuffer` trait.
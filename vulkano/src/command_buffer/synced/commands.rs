@@ -20,6 +20,7 @@ use crate::command_buffer::sys::UnsafeCommandBufferBuilderColorImageClear;
 use crate::command_buffer::sys::UnsafeCommandBufferBuilderExecuteCommands;
 use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
 use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
+use crate::command_buffer::sys::UnsafeCommandBufferBuilderPipelineBarrier;
 use crate::command_buffer::CommandBufferExecError;
 use crate::command_buffer::SecondaryCommandBuffer;
 use crate::command_buffer::SubpassContents;
@@ -31,13 +32,25 @@ use crate::format::ClearValue;
 use crate::framebuffer::FramebufferAbstract;
 use crate::image::ImageAccess;
 use crate::image::ImageLayout;
+use crate::pipeline::blend::AttachmentBlend;
+use crate::pipeline::blend::LogicOp;
+use crate::pipeline::depth_stencil::Compare;
 use crate::pipeline::depth_stencil::DynamicStencilValue;
 use crate::pipeline::depth_stencil::StencilFaceFlags;
 use crate::pipeline::input_assembly::IndexType;
+use crate::pipeline::input_assembly::PrimitiveTopology;
+use crate::pipeline::multisample::SampleLocations;
+use crate::pipeline::raster::CullMode;
+use crate::pipeline::raster::FrontFace;
+use crate::pipeline::shader_object::ShaderObject;
+use crate::pipeline::shader_object::ShaderObjectStage;
+use crate::pipeline::shading_rate::FragmentShadingRate;
 use crate::pipeline::viewport::Scissor;
 use crate::pipeline::viewport::Viewport;
 use crate::pipeline::ComputePipelineAbstract;
 use crate::pipeline::GraphicsPipelineAbstract;
+use crate::query::OcclusionQueriesPool;
+use crate::query::QueryControlFlags;
 use crate::sampler::Filter;
 use crate::sync::AccessFlagBits;
 use crate::sync::Event;
@@ -53,6 +66,51 @@ use std::ptr;
 use std::sync::Arc;
 
 impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBeginQuery` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// - `index` must be in the range of `pool`, and the query must have been reset since its
+    ///   last use when the command buffer executes.
+    /// - No other query of the same type must be active.
+    ///
+    #[inline]
+    pub unsafe fn begin_query(
+        &mut self,
+        pool: Arc<OcclusionQueriesPool>,
+        index: u32,
+        flags: QueryControlFlags,
+    ) {
+        struct Cmd {
+            pool: Arc<OcclusionQueriesPool>,
+            index: u32,
+            flags: QueryControlFlags,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBeginQuery"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_query(self.pool.inner().query(self.index).unwrap(), self.flags);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                struct Fin(Arc<OcclusionQueriesPool>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdBeginQuery"
+                    }
+                }
+                Box::new(Fin(self.pool))
+            }
+        }
+
+        self.append_command(Cmd { pool, index, flags }, &[])
+            .unwrap();
+    }
+
     /// Calls `vkBeginRenderPass` on the builder.
     // TODO: it shouldn't be possible to get an error if the framebuffer checked conflicts already
     // TODO: after begin_render_pass has been called, flushing should be forbidden and an error
@@ -332,6 +390,53 @@ impl SyncCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdBindShadersEXT` on the builder.
+    ///
+    /// Each stage is bound to its shader, or unbound if the shader is `None`.
+    ///
+    /// # Safety
+    ///
+    /// - The shaders must belong to the same device as the command buffer, and each one must be
+    ///   bound to its own stage, with the features required by that stage enabled.
+    /// - A stage must not appear twice in `shaders`.
+    /// - The resources used by the shaders are not tracked, and must be kept alive and
+    ///   synchronized by the caller.
+    #[inline]
+    pub unsafe fn bind_shaders(
+        &mut self,
+        shaders: Vec<(ShaderObjectStage, Option<Arc<ShaderObject>>)>,
+    ) {
+        struct Cmd {
+            shaders: Vec<(ShaderObjectStage, Option<Arc<ShaderObject>>)>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBindShadersEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_shaders(
+                    self.shaders
+                        .iter()
+                        .map(|&(stage, ref shader)| (stage, shader.as_ref().map(|s| &**s))),
+                );
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                struct Fin(Vec<(ShaderObjectStage, Option<Arc<ShaderObject>>)>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdBindShadersEXT"
+                    }
+                }
+                Box::new(Fin(self.shaders))
+            }
+        }
+
+        self.append_command(Cmd { shaders }, &[]).unwrap();
+    }
+
     /// Starts the process of binding vertex buffers. Returns an intermediate struct which can be
     /// used to add the buffers.
     #[inline]
@@ -1672,6 +1777,42 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
+    /// Calls `vkCmdEndQuery` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// - The query `index` of `pool` must be active, and have been begun in the same subpass.
+    ///
+    #[inline]
+    pub unsafe fn end_query(&mut self, pool: Arc<OcclusionQueriesPool>, index: u32) {
+        struct Cmd {
+            pool: Arc<OcclusionQueriesPool>,
+            index: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdEndQuery"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_query(self.pool.inner().query(self.index).unwrap());
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                struct Fin(Arc<OcclusionQueriesPool>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdEndQuery"
+                    }
+                }
+                Box::new(Fin(self.pool))
+            }
+        }
+
+        self.append_command(Cmd { pool, index }, &[]).unwrap();
+    }
+
     /// Calls `vkCmdEndRenderPass` on the builder.
     #[inline]
     pub unsafe fn end_render_pass(&mut self) {
@@ -1882,93 +2023,415 @@ impl SyncCommandBufferBuilder {
         .unwrap();
     }
 
-    /// Calls `vkCmdResetEvent` on the builder.
+    /// Calls `vkCmdPipelineBarrier` on the builder to release or acquire the ownership of a
+    /// buffer, as one half of a queue family ownership transfer.
+    ///
+    /// If `release` is true, `stages` and `access` are the accesses of the source queue family
+    /// that must be finished before the transfer. Otherwise, they are the accesses of the
+    /// destination queue family that must wait for the transfer.
+    ///
+    /// # Safety
+    ///
+    /// - `queue_transfer` must contain the source and destination queue family indices of the
+    ///   transfer, and one of them must be the queue family of the command buffer.
+    /// - The other half of the transfer must be recorded with the same queue families into a
+    ///   command buffer of the other queue family, and the acquire must be synchronized with the
+    ///   release with a semaphore.
+    ///
     #[inline]
-    pub unsafe fn reset_event(&mut self, event: Arc<Event>, stages: PipelineStages) {
-        struct Cmd {
-            event: Arc<Event>,
+    pub unsafe fn queue_family_transfer_buffer<B>(
+        &mut self,
+        buffer: B,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+        queue_transfer: (u32, u32),
+        release: bool,
+    ) -> Result<(), SyncCommandBufferBuilderError>
+    where
+        B: BufferAccess + Send + Sync + 'static,
+    {
+        struct Cmd<B> {
+            buffer: B,
             stages: PipelineStages,
+            access: AccessFlagBits,
+            queue_transfer: (u32, u32),
+            release: bool,
         }
 
-        impl Command for Cmd {
+        impl<B> Command for Cmd<B>
+        where
+            B: BufferAccess + Send + Sync + 'static,
+        {
             fn name(&self) -> &'static str {
-                "vkCmdResetEvent"
+                "vkCmdPipelineBarrier"
             }
 
             unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
-                out.reset_event(&self.event, self.stages);
+                // The destination scope of a release and the source scope of an acquire are
+                // ignored, the other queue is synchronized with a semaphore instead.
+                let (source_stage, source_access, destination_stage, destination_access) =
+                    if self.release {
+                        let bottom = PipelineStages {
+                            bottom_of_pipe: true,
+                            ..PipelineStages::none()
+                        };
+                        (self.stages, self.access, bottom, AccessFlagBits::none())
+                    } else {
+                        let top = PipelineStages {
+                            top_of_pipe: true,
+                            ..PipelineStages::none()
+                        };
+                        (top, AccessFlagBits::none(), self.stages, self.access)
+                    };
+
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_buffer_memory_barrier(
+                    &self.buffer,
+                    source_stage,
+                    source_access,
+                    destination_stage,
+                    destination_access,
+                    false,
+                    Some(self.queue_transfer),
+                    0,
+                    self.buffer.size(),
+                );
+                out.pipeline_barrier(&barrier);
             }
 
             fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
-                struct Fin(Arc<Event>);
-                impl FinalCommand for Fin {
+                struct Fin<B>(B);
+                impl<B> FinalCommand for Fin<B>
+                where
+                    B: BufferAccess + Send + Sync + 'static,
+                {
                     fn name(&self) -> &'static str {
-                        "vkCmdResetEvent"
+                        "vkCmdPipelineBarrier"
+                    }
+                    fn buffer(&self, num: usize) -> &dyn BufferAccess {
+                        assert_eq!(num, 0);
+                        &self.0
+                    }
+                    fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                        "buffer".into()
                     }
                 }
-                Box::new(Fin(self.event))
+                Box::new(Fin(self.buffer))
+            }
+
+            fn buffer(&self, num: usize) -> &dyn BufferAccess {
+                assert_eq!(num, 0);
+                &self.buffer
+            }
+
+            fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                "buffer".into()
             }
         }
 
-        self.append_command(Cmd { event, stages }, &[]).unwrap();
+        self.append_command(
+            Cmd {
+                buffer,
+                stages,
+                access,
+                queue_transfer,
+                release,
+            },
+            &[(
+                KeyTy::Buffer,
+                Some((
+                    PipelineMemoryAccess {
+                        stages,
+                        access,
+                        exclusive: true,
+                    },
+                    ImageLayout::Undefined,
+                    ImageLayout::Undefined,
+                )),
+            )],
+        )?;
+
+        Ok(())
     }
 
-    /// Calls `vkCmdSetBlendConstants` on the builder.
+    /// Calls `vkCmdPipelineBarrier` on the builder to release or acquire the ownership of an
+    /// image, as one half of a queue family ownership transfer.
+    ///
+    /// Same as `queue_family_transfer_buffer`. The layout of the image isn't changed by the
+    /// transfer: it is the final layout requirement of the image when releasing, and its initial
+    /// layout requirement when acquiring.
+    ///
+    /// # Safety
+    ///
+    /// Same as `queue_family_transfer_buffer`.
+    ///
     #[inline]
-    pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
-        struct Cmd {
-            constants: [f32; 4],
+    pub unsafe fn queue_family_transfer_image<I>(
+        &mut self,
+        image: I,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+        queue_transfer: (u32, u32),
+        release: bool,
+    ) -> Result<(), SyncCommandBufferBuilderError>
+    where
+        I: ImageAccess + Send + Sync + 'static,
+    {
+        struct Cmd<I> {
+            image: I,
+            layout: ImageLayout,
+            stages: PipelineStages,
+            access: AccessFlagBits,
+            queue_transfer: (u32, u32),
+            release: bool,
         }
 
-        impl Command for Cmd {
+        impl<I> Command for Cmd<I>
+        where
+            I: ImageAccess + Send + Sync + 'static,
+        {
             fn name(&self) -> &'static str {
-                "vkCmdSetBlendConstants"
+                "vkCmdPipelineBarrier"
             }
 
             unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
-                out.set_blend_constants(self.constants);
+                let (source_stage, source_access, destination_stage, destination_access) =
+                    if self.release {
+                        let bottom = PipelineStages {
+                            bottom_of_pipe: true,
+                            ..PipelineStages::none()
+                        };
+                        (self.stages, self.access, bottom, AccessFlagBits::none())
+                    } else {
+                        let top = PipelineStages {
+                            top_of_pipe: true,
+                            ..PipelineStages::none()
+                        };
+                        (top, AccessFlagBits::none(), self.stages, self.access)
+                    };
+
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_image_memory_barrier(
+                    &self.image,
+                    self.image.current_miplevels_access(),
+                    self.image.current_layer_levels_access(),
+                    source_stage,
+                    source_access,
+                    destination_stage,
+                    destination_access,
+                    false,
+                    Some(self.queue_transfer),
+                    self.layout,
+                    self.layout,
+                );
+                out.pipeline_barrier(&barrier);
             }
 
             fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
-                Box::new("vkCmdSetBlendConstants")
+                struct Fin<I>(I);
+                impl<I> FinalCommand for Fin<I>
+                where
+                    I: ImageAccess + Send + Sync + 'static,
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdPipelineBarrier"
+                    }
+                    fn image(&self, num: usize) -> &dyn ImageAccess {
+                        assert_eq!(num, 0);
+                        &self.0
+                    }
+                    fn image_name(&self, _: usize) -> Cow<'static, str> {
+                        "image".into()
+                    }
+                }
+                Box::new(Fin(self.image))
+            }
+
+            fn image(&self, num: usize) -> &dyn ImageAccess {
+                assert_eq!(num, 0);
+                &self.image
+            }
+
+            fn image_name(&self, _: usize) -> Cow<'static, str> {
+                "image".into()
             }
         }
 
-        self.append_command(Cmd { constants }, &[]).unwrap();
+        let layout = if release {
+            image.final_layout_requirement()
+        } else {
+            image.initial_layout_requirement()
+        };
+
+        self.append_command(
+            Cmd {
+                image,
+                layout,
+                stages,
+                access,
+                queue_transfer,
+                release,
+            },
+            &[(
+                KeyTy::Image,
+                Some((
+                    PipelineMemoryAccess {
+                        stages,
+                        access,
+                        exclusive: true,
+                    },
+                    layout,
+                    layout,
+                )),
+            )],
+        )?;
+
+        Ok(())
     }
 
-    /// Calls `vkCmdSetDepthBias` on the builder.
+    /// Calls `vkCmdResetEvent` on the builder.
     #[inline]
-    pub unsafe fn set_depth_bias(&mut self, constant_factor: f32, clamp: f32, slope_factor: f32) {
+    pub unsafe fn reset_event(&mut self, event: Arc<Event>, stages: PipelineStages) {
         struct Cmd {
-            constant_factor: f32,
-            clamp: f32,
-            slope_factor: f32,
+            event: Arc<Event>,
+            stages: PipelineStages,
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "vkCmdSetDepthBias"
+                "vkCmdResetEvent"
             }
 
             unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
-                out.set_depth_bias(self.constant_factor, self.clamp, self.slope_factor);
+                out.reset_event(&self.event, self.stages);
             }
 
             fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
-                Box::new("vkCmdSetDepthBias")
+                struct Fin(Arc<Event>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdResetEvent"
+                    }
+                }
+                Box::new(Fin(self.event))
             }
         }
 
-        self.append_command(
-            Cmd {
-                constant_factor,
-                clamp,
-                slope_factor,
-            },
-            &[],
-        )
-        .unwrap();
+        self.append_command(Cmd { event, stages }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdResetQueryPool` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// - The range of queries must be in the range of `pool`.
+    /// - The queries must not be in use by the device when the command buffer executes.
+    ///
+    #[inline]
+    pub unsafe fn reset_query_pool(
+        &mut self,
+        pool: Arc<OcclusionQueriesPool>,
+        first_index: u32,
+        count: u32,
+    ) {
+        struct Cmd {
+            pool: Arc<OcclusionQueriesPool>,
+            first_index: u32,
+            count: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdResetQueryPool"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.reset_query_pool(
+                    self.pool
+                        .inner()
+                        .queries_range(self.first_index, self.count)
+                        .unwrap(),
+                );
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                struct Fin(Arc<OcclusionQueriesPool>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdResetQueryPool"
+                    }
+                }
+                Box::new(Fin(self.pool))
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                pool,
+                first_index,
+                count,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    /// Calls `vkCmdSetBlendConstants` on the builder.
+    #[inline]
+    pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
+        struct Cmd {
+            constants: [f32; 4],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetBlendConstants"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_blend_constants(self.constants);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetBlendConstants")
+            }
+        }
+
+        self.append_command(Cmd { constants }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetDepthBias` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_bias(&mut self, constant_factor: f32, clamp: f32, slope_factor: f32) {
+        struct Cmd {
+            constant_factor: f32,
+            clamp: f32,
+            slope_factor: f32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDepthBias"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_depth_bias(self.constant_factor, self.clamp, self.slope_factor);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDepthBias")
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                constant_factor,
+                clamp,
+                slope_factor,
+            },
+            &[],
+        )
+        .unwrap();
     }
 
     /// Calls `vkCmdSetDepthBounds` on the builder.
@@ -1996,6 +2459,520 @@ impl SyncCommandBufferBuilder {
         self.append_command(Cmd { min, max }, &[]).unwrap();
     }
 
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `pipeline_fragment_shading_rate` feature must be enabled, and the fragment size of
+    /// `shading_rate` must be valid.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(&mut self, shading_rate: FragmentShadingRate) {
+        struct Cmd {
+            shading_rate: FragmentShadingRate,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetFragmentShadingRateKHR"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_fragment_shading_rate(&self.shading_rate);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetFragmentShadingRateKHR")
+            }
+        }
+
+        self.append_command(Cmd { shading_rate }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetCullModeEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        struct Cmd {
+            cull_mode: CullMode,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetCullModeEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_cull_mode(self.cull_mode);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetCullModeEXT")
+            }
+        }
+
+        self.append_command(Cmd { cull_mode }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetFrontFaceEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_front_face(&mut self, front_face: FrontFace) {
+        struct Cmd {
+            front_face: FrontFace,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetFrontFaceEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_front_face(self.front_face);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetFrontFaceEXT")
+            }
+        }
+
+        self.append_command(Cmd { front_face }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetPrimitiveTopologyEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled, and `topology` must be of the same
+    /// class as the topology of the pipeline that is used to draw.
+    #[inline]
+    pub unsafe fn set_primitive_topology(&mut self, topology: PrimitiveTopology) {
+        struct Cmd {
+            topology: PrimitiveTopology,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetPrimitiveTopologyEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_primitive_topology(self.topology);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetPrimitiveTopologyEXT")
+            }
+        }
+
+        self.append_command(Cmd { topology }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetDepthTestEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_depth_test_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDepthTestEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_depth_test_enable(self.enable);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDepthTestEnableEXT")
+            }
+        }
+
+        self.append_command(Cmd { enable }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetDepthWriteEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_depth_write_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDepthWriteEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_depth_write_enable(self.enable);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDepthWriteEnableEXT")
+            }
+        }
+
+        self.append_command(Cmd { enable }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetDepthCompareOpEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_depth_compare_op(&mut self, compare_op: Compare) {
+        struct Cmd {
+            compare_op: Compare,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDepthCompareOpEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_depth_compare_op(self.compare_op);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDepthCompareOpEXT")
+            }
+        }
+
+        self.append_command(Cmd { compare_op }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetPrimitiveRestartEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state2` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_primitive_restart_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetPrimitiveRestartEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_primitive_restart_enable(self.enable);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetPrimitiveRestartEnableEXT")
+            }
+        }
+
+        self.append_command(Cmd { enable }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetRasterizerDiscardEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state2` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_rasterizer_discard_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetRasterizerDiscardEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_rasterizer_discard_enable(self.enable);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetRasterizerDiscardEnableEXT")
+            }
+        }
+
+        self.append_command(Cmd { enable }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetDepthBiasEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state2` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_depth_bias_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDepthBiasEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_depth_bias_enable(self.enable);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDepthBiasEnableEXT")
+            }
+        }
+
+        self.append_command(Cmd { enable }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetLogicOpEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state2_logic_op` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_logic_op(&mut self, logic_op: LogicOp) {
+        struct Cmd {
+            logic_op: LogicOp,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetLogicOpEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_logic_op(self.logic_op);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetLogicOpEXT")
+            }
+        }
+
+        self.append_command(Cmd { logic_op }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetColorBlendEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state3_color_blend_enable` feature must be enabled, and the enables
+    /// must not go past the color attachments of the subpass that is used to draw.
+    #[inline]
+    pub unsafe fn set_color_blend_enable(&mut self, first_attachment: u32, enables: Vec<bool>) {
+        struct Cmd {
+            first_attachment: u32,
+            enables: Vec<bool>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetColorBlendEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_color_blend_enable(self.first_attachment, &self.enables);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetColorBlendEnableEXT")
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                first_attachment,
+                enables,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    /// Calls `vkCmdSetColorBlendEquationEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state3_color_blend_equation` feature must be enabled, the equations
+    /// must not use advanced blend operations, and they must not go past the color attachments of
+    /// the subpass that is used to draw.
+    #[inline]
+    pub unsafe fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: Vec<AttachmentBlend>,
+    ) {
+        struct Cmd {
+            first_attachment: u32,
+            equations: Vec<AttachmentBlend>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetColorBlendEquationEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_color_blend_equation(self.first_attachment, &self.equations);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetColorBlendEquationEXT")
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                first_attachment,
+                equations,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    /// Calls `vkCmdSetColorWriteMaskEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state3_color_write_mask` feature must be enabled, and the masks must
+    /// not go past the color attachments of the subpass that is used to draw.
+    #[inline]
+    pub unsafe fn set_color_write_mask(&mut self, first_attachment: u32, masks: Vec<[bool; 4]>) {
+        struct Cmd {
+            first_attachment: u32,
+            masks: Vec<[bool; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetColorWriteMaskEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_color_write_mask(self.first_attachment, &self.masks);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetColorWriteMaskEXT")
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                first_attachment,
+                masks,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    /// Calls `vkCmdSetLineStippleEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `ext_line_rasterization` extension must be enabled, and `factor` must be between 1
+    /// and 256.
+    #[inline]
+    pub unsafe fn set_line_stipple(&mut self, factor: u32, pattern: u16) {
+        struct Cmd {
+            factor: u32,
+            pattern: u16,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetLineStippleEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_line_stipple(self.factor, self.pattern);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetLineStippleEXT")
+            }
+        }
+
+        self.append_command(Cmd { factor, pattern }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetColorWriteEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `color_write_enable` feature must be enabled, and `enables` must contain one value for
+    /// each color attachment of the subpass.
+    #[inline]
+    pub unsafe fn set_color_write_enable(&mut self, enables: Vec<bool>) {
+        struct Cmd {
+            enables: Vec<bool>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetColorWriteEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_color_write_enable(&self.enables);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetColorWriteEnableEXT")
+            }
+        }
+
+        self.append_command(Cmd { enables }, &[]).unwrap();
+    }
+
+    /// Calls `vkCmdSetSampleLocationsEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `ext_sample_locations` extension must be enabled, and `sample_locations` must contain
+    /// one location for each sample of each pixel of its grid.
+    #[inline]
+    pub unsafe fn set_sample_locations(&mut self, sample_locations: SampleLocations) {
+        struct Cmd {
+            sample_locations: SampleLocations,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetSampleLocationsEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_sample_locations(&self.sample_locations);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetSampleLocationsEXT")
+            }
+        }
+
+        self.append_command(Cmd { sample_locations }, &[]).unwrap();
+    }
+
     /// Calls `vkCmdSetEvent` on the builder.
     #[inline]
     pub unsafe fn set_event(&mut self, event: Arc<Event>, stages: PipelineStages) {
@@ -2027,6 +3004,36 @@ impl SyncCommandBufferBuilder {
         self.append_command(Cmd { event, stages }, &[]).unwrap();
     }
 
+    /// Calls `vkCmdSetDeviceMask` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// - The `khr_device_group` extension must be enabled on the device.
+    /// - `mask` must be a valid device mask for the device, see `Device::is_valid_device_mask`.
+    ///
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, mask: u32) {
+        struct Cmd {
+            mask: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDeviceMask"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_device_mask(self.mask);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDeviceMask")
+            }
+        }
+
+        self.append_command(Cmd { mask }, &[]).unwrap();
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
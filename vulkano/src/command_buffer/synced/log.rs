@@ -0,0 +1,142 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::image::ImageLayout;
+use crate::sync::AccessFlagBits;
+use crate::sync::PipelineStages;
+
+/// Function that receives the synchronization decisions of a `SyncCommandBufferBuilder`.
+pub type SyncLogger = Box<dyn FnMut(&SyncEvent) + Send + Sync>;
+
+/// A synchronization decision made by a `SyncCommandBufferBuilder` for a resource accessed by a
+/// command.
+///
+/// Reported to the logger passed to `SyncCommandBufferBuilder::set_logger`. The `Display`
+/// implementation prints the event on a single line.
+#[derive(Debug, Clone)]
+pub struct SyncEvent {
+    /// Index of the command within the command buffer.
+    pub command_index: usize,
+    /// Name of the command, for example `vkCmdCopyBuffer`.
+    pub command_name: &'static str,
+    /// Name of the resource within the parameters of the command, for example `destination`.
+    pub resource_name: Cow<'static, str>,
+    /// True if the resource is an image, false if it is a buffer.
+    pub is_image: bool,
+    /// What has been decided for this resource.
+    pub kind: SyncEventKind,
+}
+
+/// What a `SyncCommandBufferBuilder` has decided for a resource. See `SyncEvent`.
+#[derive(Debug, Clone)]
+pub enum SyncEventKind {
+    /// First use of the resource in the command buffer. If the image isn't in the layout
+    /// expected by the command yet, it is transitioned from `old_layout` to `new_layout`.
+    FirstUse {
+        transition: Option<(ImageLayout, ImageLayout)>,
+    },
+
+    /// The access doesn't conflict with the previous accesses to the resource, and has been
+    /// merged with them without any barrier.
+    Merged,
+
+    /// A pipeline barrier has been added between the previous accesses to the resource and this
+    /// command, and the image layout changes from `old_layout` to `new_layout`. For buffers, both
+    /// layouts are `Undefined`.
+    Barrier {
+        /// Index of the last command that accessed the resource before.
+        previous_command_index: usize,
+        source_stages: PipelineStages,
+        source_access: AccessFlagBits,
+        destination_stages: PipelineStages,
+        destination_access: AccessFlagBits,
+        old_layout: ImageLayout,
+        new_layout: ImageLayout,
+    },
+
+    /// The access conflicts with a previous access inside the same render pass, and no barrier
+    /// can be added between them. The builder returns an error.
+    Conflict {
+        /// Index of the conflicting command.
+        previous_command_index: usize,
+    },
+
+    /// The image is transitioned to its final layout requirement at the end of the command
+    /// buffer. `command_index` is the last command that used the image.
+    FinalTransition {
+        old_layout: ImageLayout,
+        new_layout: ImageLayout,
+    },
+}
+
+impl fmt::Display for SyncEvent {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "#{} {} ({} {}): ",
+            self.command_index,
+            self.command_name,
+            if self.is_image { "image" } else { "buffer" },
+            self.resource_name
+        )?;
+
+        match self.kind {
+            SyncEventKind::FirstUse { transition: None } => write!(fmt, "first use"),
+            SyncEventKind::FirstUse {
+                transition: Some((old_layout, new_layout)),
+            } => write!(
+                fmt,
+                "first use, layout {:?} -> {:?}",
+                old_layout, new_layout
+            ),
+            SyncEventKind::Merged => write!(fmt, "merged with the previous accesses"),
+            SyncEventKind::Barrier {
+                previous_command_index,
+                source_stages,
+                source_access,
+                destination_stages,
+                destination_access,
+                old_layout,
+                new_layout,
+            } => {
+                write!(
+                    fmt,
+                    "barrier after #{}, {} ({}) -> {} ({})",
+                    previous_command_index,
+                    join(source_stages.names()),
+                    join(source_access.names()),
+                    join(destination_stages.names()),
+                    join(destination_access.names())
+                )?;
+                if self.is_image {
+                    write!(fmt, ", layout {:?} -> {:?}", old_layout, new_layout)?;
+                }
+                Ok(())
+            }
+            SyncEventKind::Conflict {
+                previous_command_index,
+            } => write!(fmt, "unsolvable conflict with #{}", previous_command_index),
+            SyncEventKind::FinalTransition {
+                old_layout,
+                new_layout,
+            } => write!(fmt, "final layout {:?} -> {:?}", old_layout, new_layout),
+        }
+    }
+}
+
+fn join(names: Vec<&'static str>) -> String {
+    if names.is_empty() {
+        "none".to_owned()
+    } else {
+        names.join(" | ")
+    }
+}
@@ -17,6 +17,10 @@ pub use self::base::SyncCommandBufferBuilderError;
 pub use self::commands::SyncCommandBufferBuilderBindDescriptorSets;
 pub use self::commands::SyncCommandBufferBuilderBindVertexBuffer;
 pub use self::commands::SyncCommandBufferBuilderExecuteCommands;
+pub use self::log::SyncEvent;
+pub use self::log::SyncEventKind;
+pub use self::log::SyncLogger;
 
 mod base;
 mod commands;
+mod log;
@@ -27,13 +27,24 @@ use crate::framebuffer::FramebufferAbstract;
 use crate::framebuffer::RenderPassAbstract;
 use crate::image::ImageAccess;
 use crate::image::ImageLayout;
+use crate::pipeline::blend::AttachmentBlend;
+use crate::pipeline::blend::LogicOp;
+use crate::pipeline::depth_stencil::Compare;
 use crate::pipeline::depth_stencil::StencilFaceFlags;
 use crate::pipeline::input_assembly::IndexType;
+use crate::pipeline::input_assembly::PrimitiveTopology;
+use crate::pipeline::multisample::SampleLocations;
+use crate::pipeline::raster::CullMode;
+use crate::pipeline::raster::FrontFace;
+use crate::pipeline::shader_object::ShaderObject;
+use crate::pipeline::shader_object::ShaderObjectStage;
+use crate::pipeline::shading_rate::FragmentShadingRate;
 use crate::pipeline::viewport::Scissor;
 use crate::pipeline::viewport::Viewport;
 use crate::pipeline::ComputePipelineAbstract;
 use crate::pipeline::GraphicsPipelineAbstract;
 use crate::query::QueryControlFlags;
+use crate::query::QueryResultFlags;
 use crate::query::UnsafeQueriesRange;
 use crate::query::UnsafeQuery;
 use crate::sampler::Filter;
@@ -80,6 +91,7 @@ pub struct UnsafeCommandBufferBuilder {
     command_buffer: vk::CommandBuffer,
     device: Arc<Device>,
     flags: Flags,
+    protected: bool,
 }
 
 impl fmt::Debug for UnsafeCommandBufferBuilder {
@@ -212,6 +224,7 @@ impl UnsafeCommandBufferBuilder {
             command_buffer: pool_alloc.internal_object(),
             device: device.clone(),
             flags,
+            protected: pool_alloc.is_protected(),
         })
     }
 
@@ -226,6 +239,7 @@ impl UnsafeCommandBufferBuilder {
                 command_buffer: self.command_buffer,
                 device: self.device.clone(),
                 flags: self.flags,
+                protected: self.protected,
             })
         }
     }
@@ -299,9 +313,35 @@ impl UnsafeCommandBufferBuilder {
             0..framebuffer.dimensions()[1],
         ];
 
+        // Imageless framebuffers receive their image views when the render pass begins.
+        let raw_attachments: SmallVec<[_; 8]> = if framebuffer.is_imageless() {
+            (0..framebuffer.num_attachments())
+                .map(|num| {
+                    framebuffer
+                        .attached_image_view(num)
+                        .unwrap()
+                        .inner()
+                        .internal_object()
+                })
+                .collect()
+        } else {
+            SmallVec::new()
+        };
+
+        let attachment_begin_info = vk::RenderPassAttachmentBeginInfo {
+            sType: vk::STRUCTURE_TYPE_RENDER_PASS_ATTACHMENT_BEGIN_INFO,
+            pNext: ptr::null(),
+            attachmentCount: raw_attachments.len() as u32,
+            pAttachments: raw_attachments.as_ptr(),
+        };
+
         let begin = vk::RenderPassBeginInfo {
             sType: vk::STRUCTURE_TYPE_RENDER_PASS_BEGIN_INFO,
-            pNext: ptr::null(),
+            pNext: if framebuffer.is_imageless() {
+                &attachment_begin_info as *const _ as *const _
+            } else {
+                ptr::null()
+            },
             renderPass: raw_render_pass,
             framebuffer: raw_framebuffer,
             renderArea: vk::Rect2D {
@@ -416,6 +456,42 @@ impl UnsafeCommandBufferBuilder {
         vk.CmdBindPipeline(cmd, vk::PIPELINE_BIND_POINT_GRAPHICS, inner);
     }
 
+    /// Calls `vkCmdBindShadersEXT` on the builder.
+    ///
+    /// Each stage is bound to its shader, or unbound if the shader is `None`.
+    ///
+    /// # Safety
+    ///
+    /// - The shaders must belong to the same device as the command buffer, and each one must be
+    ///   bound to its own stage.
+    /// - A stage must not appear twice in `shaders`.
+    #[inline]
+    pub unsafe fn bind_shaders<'a, I>(&mut self, shaders: I)
+    where
+        I: IntoIterator<Item = (ShaderObjectStage, Option<&'a ShaderObject>)>,
+    {
+        let (stages, shaders): (SmallVec<[_; 6]>, SmallVec<[_; 6]>) = shaders
+            .into_iter()
+            .map(|(stage, shader)| {
+                let shader = match shader {
+                    Some(shader) => {
+                        debug_assert_eq!(shader.stage(), stage);
+                        shader.internal_object()
+                    }
+                    None => 0,
+                };
+                (stage.into_vulkan_bits(), shader)
+            })
+            .unzip();
+        if stages.is_empty() {
+            return;
+        }
+
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdBindShadersEXT(cmd, stages.len() as u32, stages.as_ptr(), shaders.as_ptr());
+    }
+
     /// Calls `vkCmdBindVertexBuffers` on the builder.
     ///
     /// Does nothing if the list of buffers is empty, as it would be a no-op and isn't a valid
@@ -987,12 +1063,13 @@ impl UnsafeCommandBufferBuilder {
         queries: UnsafeQueriesRange,
         destination: &dyn BufferAccess,
         stride: usize,
+        flags: QueryResultFlags,
     ) {
         let destination = destination.inner();
         debug_assert!(destination.offset < destination.buffer.size());
         debug_assert!(destination.buffer.usage_transfer_destination());
 
-        let flags = 0; // FIXME:
+        let flags: vk::QueryResultFlags = flags.into();
 
         let vk = self.device().pointers();
         let cmd = self.internal_object();
@@ -1324,6 +1401,298 @@ impl UnsafeCommandBufferBuilder {
         vk.CmdSetDepthBounds(cmd, min, max);
     }
 
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `pipeline_fragment_shading_rate` feature must be enabled, and the fragment size of
+    /// `shading_rate` must be valid.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(&mut self, shading_rate: &FragmentShadingRate) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(shading_rate.is_valid_fragment_size());
+        let fragment_size = vk::Extent2D {
+            width: shading_rate.fragment_size[0],
+            height: shading_rate.fragment_size[1],
+        };
+        let combiner_ops = [
+            shading_rate.combiner_ops[0].into(),
+            shading_rate.combiner_ops[1].into(),
+        ];
+        vk.CmdSetFragmentShadingRateKHR(cmd, &fragment_size, combiner_ops.as_ptr());
+    }
+
+    /// Calls `vkCmdSetCullModeEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetCullModeEXT(cmd, cull_mode as u32);
+    }
+
+    /// Calls `vkCmdSetFrontFaceEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_front_face(&mut self, front_face: FrontFace) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetFrontFaceEXT(cmd, front_face as u32);
+    }
+
+    /// Calls `vkCmdSetPrimitiveTopologyEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled, and `topology` must be of the same
+    /// class as the topology of the pipeline that is used to draw.
+    #[inline]
+    pub unsafe fn set_primitive_topology(&mut self, topology: PrimitiveTopology) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetPrimitiveTopologyEXT(cmd, topology.into());
+    }
+
+    /// Calls `vkCmdSetDepthTestEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_depth_test_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetDepthTestEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
+    /// Calls `vkCmdSetDepthWriteEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_depth_write_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetDepthWriteEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
+    /// Calls `vkCmdSetDepthCompareOpEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_depth_compare_op(&mut self, compare_op: Compare) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetDepthCompareOpEXT(cmd, compare_op as u32);
+    }
+
+    /// Calls `vkCmdSetPrimitiveRestartEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state2` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_primitive_restart_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetPrimitiveRestartEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
+    /// Calls `vkCmdSetRasterizerDiscardEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state2` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_rasterizer_discard_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetRasterizerDiscardEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
+    /// Calls `vkCmdSetDepthBiasEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state2` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_depth_bias_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetDepthBiasEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
+    /// Calls `vkCmdSetLogicOpEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state2_logic_op` feature must be enabled.
+    #[inline]
+    pub unsafe fn set_logic_op(&mut self, logic_op: LogicOp) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdSetLogicOpEXT(cmd, logic_op as u32);
+    }
+
+    /// Calls `vkCmdSetColorBlendEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state3_color_blend_enable` feature must be enabled, and the enables
+    /// must not go past the color attachments of the subpass that is used to draw.
+    #[inline]
+    pub unsafe fn set_color_blend_enable(&mut self, first_attachment: u32, enables: &[bool]) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        let enables = enables
+            .iter()
+            .map(|&enable| if enable { vk::TRUE } else { vk::FALSE })
+            .collect::<SmallVec<[_; 4]>>();
+        if enables.is_empty() {
+            return;
+        }
+
+        vk.CmdSetColorBlendEnableEXT(
+            cmd,
+            first_attachment,
+            enables.len() as u32,
+            enables.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdSetColorBlendEquationEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state3_color_blend_equation` feature must be enabled, the equations
+    /// must not use advanced blend operations, and they must not go past the color attachments of
+    /// the subpass that is used to draw.
+    #[inline]
+    pub unsafe fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        let equations = equations
+            .iter()
+            .map(|blend| vk::ColorBlendEquationEXT {
+                srcColorBlendFactor: blend.color_source as u32,
+                dstColorBlendFactor: blend.color_destination as u32,
+                colorBlendOp: blend.color_op as u32,
+                srcAlphaBlendFactor: blend.alpha_source as u32,
+                dstAlphaBlendFactor: blend.alpha_destination as u32,
+                alphaBlendOp: blend.alpha_op as u32,
+            })
+            .collect::<SmallVec<[_; 4]>>();
+        if equations.is_empty() {
+            return;
+        }
+
+        vk.CmdSetColorBlendEquationEXT(
+            cmd,
+            first_attachment,
+            equations.len() as u32,
+            equations.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdSetColorWriteMaskEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `extended_dynamic_state3_color_write_mask` feature must be enabled, and the masks must
+    /// not go past the color attachments of the subpass that is used to draw.
+    #[inline]
+    pub unsafe fn set_color_write_mask(&mut self, first_attachment: u32, masks: &[[bool; 4]]) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        let masks = masks
+            .iter()
+            .map(|mask| {
+                let bits = [
+                    vk::COLOR_COMPONENT_R_BIT,
+                    vk::COLOR_COMPONENT_G_BIT,
+                    vk::COLOR_COMPONENT_B_BIT,
+                    vk::COLOR_COMPONENT_A_BIT,
+                ];
+                mask.iter()
+                    .zip(bits.iter())
+                    .filter(|&(&enabled, _)| enabled)
+                    .fold(0, |flags, (_, &bit)| flags | bit)
+            })
+            .collect::<SmallVec<[_; 4]>>();
+        if masks.is_empty() {
+            return;
+        }
+
+        vk.CmdSetColorWriteMaskEXT(cmd, first_attachment, masks.len() as u32, masks.as_ptr());
+    }
+
+    /// Calls `vkCmdSetLineStippleEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `ext_line_rasterization` extension must be enabled, and `factor` must be between 1
+    /// and 256.
+    #[inline]
+    pub unsafe fn set_line_stipple(&mut self, factor: u32, pattern: u16) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!((1..=256).contains(&factor));
+        vk.CmdSetLineStippleEXT(cmd, factor, pattern);
+    }
+
+    /// Calls `vkCmdSetColorWriteEnableEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `color_write_enable` feature must be enabled, and `enables` must contain one value for
+    /// each color attachment of the subpass.
+    #[inline]
+    pub unsafe fn set_color_write_enable(&mut self, enables: &[bool]) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        let enables = enables
+            .iter()
+            .map(|&enable| if enable { vk::TRUE } else { vk::FALSE })
+            .collect::<SmallVec<[_; 4]>>();
+        if enables.is_empty() {
+            return;
+        }
+
+        vk.CmdSetColorWriteEnableEXT(cmd, enables.len() as u32, enables.as_ptr());
+    }
+
+    /// Calls `vkCmdSetSampleLocationsEXT` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// The `ext_sample_locations` extension must be enabled, and `sample_locations` must contain
+    /// one location for each sample of each pixel of its grid.
+    #[inline]
+    pub unsafe fn set_sample_locations(&mut self, sample_locations: &SampleLocations) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert_eq!(
+            sample_locations.locations.len(),
+            sample_locations.expected_len()
+        );
+        let info = sample_locations.to_vulkan();
+        vk.CmdSetSampleLocationsEXT(cmd, &info);
+    }
+
     /// Calls `vkCmdSetEvent` on the builder.
     #[inline]
     pub unsafe fn set_event(&mut self, event: &Event, stages: PipelineStages) {
@@ -1336,6 +1705,21 @@ impl UnsafeCommandBufferBuilder {
         vk.CmdSetEvent(cmd, event.internal_object(), stages.into_vulkan_bits());
     }
 
+    /// Calls `vkCmdSetDeviceMask` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// - The `khr_device_group` extension must be enabled on the device.
+    /// - `mask` must be a valid device mask for the device, see `Device::is_valid_device_mask`.
+    ///
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, mask: u32) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(self.device().is_valid_device_mask(mask));
+        vk.CmdSetDeviceMaskKHR(cmd, mask);
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
@@ -1967,6 +2351,7 @@ pub struct UnsafeCommandBuffer {
     command_buffer: vk::CommandBuffer,
     device: Arc<Device>,
     flags: Flags,
+    protected: bool,
 }
 
 impl UnsafeCommandBuffer {
@@ -1974,6 +2359,13 @@ impl UnsafeCommandBuffer {
     pub fn flags(&self) -> Flags {
         self.flags
     }
+
+    /// Returns true if the command buffer was allocated from a protected pool. It must then be
+    /// submitted to a protected queue.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandBuffer {
@@ -10,6 +10,7 @@
 use crate::buffer::BufferAccess;
 use crate::command_buffer::submit::SubmitAnyBuilder;
 use crate::command_buffer::submit::SubmitCommandBufferBuilder;
+use crate::command_buffer::submit::SubmitScheduler;
 use crate::command_buffer::sys::UnsafeCommandBuffer;
 use crate::command_buffer::CommandBufferInheritance;
 use crate::device::Device;
@@ -129,6 +130,10 @@ pub unsafe trait PrimaryCommandBuffer: DeviceOwned {
             assert!(future.queue().unwrap().is_same(&queue));
         }
 
+        if self.inner().is_protected() && !queue.is_protected() {
+            return Err(CommandBufferExecError::ProtectedQueueRequired);
+        }
+
         self.lock_submit(&future, &queue)?;
 
         Ok(CommandBufferExecFuture {
@@ -335,18 +340,30 @@ where
     }
 
     unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        let protected = self.command_buffer.inner().is_protected();
+
         Ok(match self.previous.build_submission()? {
             SubmitAnyBuilder::Empty => {
                 let mut builder = SubmitCommandBufferBuilder::new();
+                builder.set_protected(protected);
                 builder.add_command_buffer(self.command_buffer.inner());
                 SubmitAnyBuilder::CommandBuffer(builder)
             }
             SubmitAnyBuilder::SemaphoresWait(sem) => {
                 let mut builder: SubmitCommandBufferBuilder = sem.into();
+                builder.set_protected(protected);
                 builder.add_command_buffer(self.command_buffer.inner());
                 SubmitAnyBuilder::CommandBuffer(builder)
             }
             SubmitAnyBuilder::CommandBuffer(mut builder) => {
+                // A submission is either protected or not, so the previous command buffers are
+                // submitted on their own if they don't match.
+                if builder.is_protected() != protected {
+                    builder.submit(&self.queue)?;
+                    builder = SubmitCommandBufferBuilder::new();
+                    builder.set_protected(protected);
+                }
+
                 // FIXME: add pipeline barrier
                 builder.add_command_buffer(self.command_buffer.inner());
                 SubmitAnyBuilder::CommandBuffer(builder)
@@ -371,13 +388,9 @@ where
 
             let queue = self.queue.clone();
 
-            match self.build_submission()? {
-                SubmitAnyBuilder::Empty => {}
-                SubmitAnyBuilder::CommandBuffer(builder) => {
-                    builder.submit(&queue)?;
-                }
-                _ => unreachable!(),
-            };
+            let mut scheduler = SubmitScheduler::new();
+            scheduler.add(self.build_submission()?);
+            scheduler.flush(&queue)?;
 
             // Only write `true` here in order to try again next time if we failed to submit.
             *submitted = true;
@@ -492,6 +505,10 @@ pub enum CommandBufferExecError {
     /// The command buffer or one of the secondary command buffers it executes is already in use by
     /// the GPU and was not created with the "concurrent" flag.
     ExclusiveAlreadyInUse,
+
+    /// The command buffer was allocated from a protected pool, but the queue isn't a protected
+    /// queue.
+    ProtectedQueueRequired,
     // TODO: missing entries (eg. wrong queue family, secondary command buffer)
 }
 
@@ -523,6 +540,10 @@ impl fmt::Display for CommandBufferExecError {
                     "the command buffer or one of the secondary command buffers it executes is \
                  already in use was not created with the \"concurrent\" flag"
                 }
+                CommandBufferExecError::ProtectedQueueRequired => {
+                    "the command buffer was allocated from a protected pool, but the queue isn't \
+                     a protected queue"
+                }
             }
         )
     }
@@ -0,0 +1,133 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::pipeline::shader_object::ShaderObject;
+use crate::pipeline::shader_object::ShaderObjectStage;
+use crate::VulkanObject;
+
+/// Checks whether shader objects can be bound to their stages.
+///
+/// # Panic
+///
+/// - Panics if one of the shaders was not created with `device`.
+///
+pub fn check_bind_shaders(
+    device: &Device,
+    shaders: &[(ShaderObjectStage, Option<Arc<ShaderObject>>)],
+) -> Result<(), CheckBindShadersError> {
+    for (num, &(stage, ref shader)) in shaders.iter().enumerate() {
+        if shaders[..num].iter().any(|&(s, _)| s == stage) {
+            return Err(CheckBindShadersError::DuplicateStage { stage });
+        }
+
+        let shader = match *shader {
+            Some(ref shader) => shader,
+            // Unbinding a stage is always allowed.
+            None => continue,
+        };
+
+        assert_eq!(shader.device().internal_object(), device.internal_object());
+
+        if shader.stage() != stage {
+            return Err(CheckBindShadersError::StageMismatch { stage });
+        }
+
+        match stage {
+            ShaderObjectStage::TessellationControl | ShaderObjectStage::TessellationEvaluation
+                if !device.enabled_features().tessellation_shader =>
+            {
+                return Err(CheckBindShadersError::TessellationShaderFeatureNotEnabled);
+            }
+            ShaderObjectStage::Geometry if !device.enabled_features().geometry_shader => {
+                return Err(CheckBindShadersError::GeometryShaderFeatureNotEnabled);
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to bind shader objects.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckBindShadersError {
+    /// The same stage was given more than once.
+    DuplicateStage {
+        /// The stage.
+        stage: ShaderObjectStage,
+    },
+    /// A shader was bound to a stage that is not its own.
+    StageMismatch {
+        /// The stage the shader was bound to.
+        stage: ShaderObjectStage,
+    },
+    /// The `tessellation_shader` feature must be enabled in order to bind tessellation shaders.
+    TessellationShaderFeatureNotEnabled,
+    /// The `geometry_shader` feature must be enabled in order to bind a geometry shader.
+    GeometryShaderFeatureNotEnabled,
+}
+
+impl error::Error for CheckBindShadersError {}
+
+impl fmt::Display for CheckBindShadersError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckBindShadersError::DuplicateStage { .. } => {
+                    "the same stage was given more than once"
+                }
+                CheckBindShadersError::StageMismatch { .. } => {
+                    "a shader was bound to a stage that is not its own"
+                }
+                CheckBindShadersError::TessellationShaderFeatureNotEnabled => {
+                    "the `tessellation_shader` feature must be enabled in order to bind \
+                     tessellation shaders"
+                }
+                CheckBindShadersError::GeometryShaderFeatureNotEnabled => {
+                    "the `geometry_shader` feature must be enabled in order to bind a geometry \
+                     shader"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_unbound_stage() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(check_bind_shaders(&device, &[(ShaderObjectStage::Vertex, None)]).is_ok());
+        match check_bind_shaders(
+            &device,
+            &[
+                (ShaderObjectStage::Vertex, None),
+                (ShaderObjectStage::Fragment, None),
+                (ShaderObjectStage::Vertex, None),
+            ],
+        ) {
+            Err(CheckBindShadersError::DuplicateStage {
+                stage: ShaderObjectStage::Vertex,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use crate::device::Device;
+
+/// Checks whether a set device mask command is valid.
+pub fn check_device_mask(device: &Device, mask: u32) -> Result<(), CheckDeviceMaskError> {
+    if !device.loaded_extensions_or_core().khr_device_group {
+        return Err(CheckDeviceMaskError::ExtensionNotEnabled);
+    }
+
+    if !device.is_valid_device_mask(mask) {
+        return Err(CheckDeviceMaskError::InvalidMask);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `set_device_mask` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckDeviceMaskError {
+    /// The `khr_device_group` extension must be enabled on the device.
+    ExtensionNotEnabled,
+    /// The mask is 0, or contains physical devices that aren't part of the device group.
+    InvalidMask,
+}
+
+impl error::Error for CheckDeviceMaskError {}
+
+impl fmt::Display for CheckDeviceMaskError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckDeviceMaskError::ExtensionNotEnabled => {
+                    "the `khr_device_group` extension must be enabled on the device"
+                }
+                CheckDeviceMaskError::InvalidMask => {
+                    "the device mask is 0 or contains physical devices that aren't part of the \
+                     device group"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match check_device_mask(&device, 1) {
+            Err(CheckDeviceMaskError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_mask() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(device.is_valid_device_mask(1));
+        assert!(!device.is_valid_device_mask(0));
+        assert!(!device.is_valid_device_mask(0b10));
+    }
+}
@@ -11,6 +11,7 @@ use std::error;
 use std::fmt;
 
 use crate::command_buffer::DynamicState;
+use crate::pipeline::shading_rate::FragmentShadingRateCombinerOp;
 use crate::pipeline::GraphicsPipelineAbstract;
 
 /// Checks whether states that are about to be set are correct.
@@ -28,6 +29,10 @@ where
             if value != 1.0 && !pipeline.device().enabled_features().wide_lines {
                 return Err(CheckDynamicStateValidityError::LineWidthMissingExtension);
             }
+            let range = device.physical_device().limits().line_width_range();
+            if !(value >= range[0] && value <= range[1]) {
+                return Err(CheckDynamicStateValidityError::LineWidthOutOfRange);
+            }
         } else {
             return Err(CheckDynamicStateValidityError::LineWidthMissing);
         }
@@ -101,6 +106,245 @@ where
         }
     }
 
+    if pipeline.has_dynamic_depth_bounds() {
+        if let Some(ref depth_bounds) = state.depth_bounds {
+            if !(0.0..=1.0).contains(&depth_bounds.start)
+                || !(0.0..=1.0).contains(&depth_bounds.end)
+            {
+                return Err(CheckDynamicStateValidityError::DepthBoundsOutOfRange);
+            }
+        } else {
+            return Err(CheckDynamicStateValidityError::DepthBoundsMissing);
+        }
+    } else {
+        if state.depth_bounds.is_some() {
+            return Err(CheckDynamicStateValidityError::DepthBoundsNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_fragment_shading_rate() {
+        if let Some(ref fragment_shading_rate) = state.fragment_shading_rate {
+            if !fragment_shading_rate.is_valid_fragment_size() {
+                return Err(CheckDynamicStateValidityError::FragmentShadingRateInvalidSize);
+            }
+            if fragment_shading_rate.combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+                && !device.enabled_features().primitive_fragment_shading_rate
+            {
+                return Err(
+                    CheckDynamicStateValidityError::FragmentShadingRatePrimitiveFeatureNotEnabled,
+                );
+            }
+            if fragment_shading_rate.combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+                && !device.enabled_features().attachment_fragment_shading_rate
+            {
+                return Err(
+                    CheckDynamicStateValidityError::FragmentShadingRateAttachmentFeatureNotEnabled,
+                );
+            }
+            if !fragment_shading_rate
+                .combiner_ops
+                .iter()
+                .all(|op| op.is_trivial())
+                && !device
+                    .physical_device()
+                    .extended_properties()
+                    .fragment_shading_rate_non_trivial_combiner_ops()
+                    .unwrap_or(false)
+            {
+                return Err(
+                    CheckDynamicStateValidityError::FragmentShadingRateNonTrivialCombinerOpsNotSupported,
+                );
+            }
+        } else {
+            return Err(CheckDynamicStateValidityError::FragmentShadingRateMissing);
+        }
+    } else {
+        if state.fragment_shading_rate.is_some() {
+            return Err(CheckDynamicStateValidityError::FragmentShadingRateNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_line_stipple() {
+        if let Some(ref line_stipple) = state.line_stipple {
+            if !(line_stipple.factor >= 1 && line_stipple.factor <= 256) {
+                return Err(CheckDynamicStateValidityError::LineStippleInvalidFactor);
+            }
+        } else {
+            return Err(CheckDynamicStateValidityError::LineStippleMissing);
+        }
+    } else {
+        if state.line_stipple.is_some() {
+            return Err(CheckDynamicStateValidityError::LineStippleNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_sample_locations() {
+        if let Some(ref sample_locations) = state.sample_locations {
+            if sample_locations.locations.len() != sample_locations.expected_len() {
+                return Err(
+                    CheckDynamicStateValidityError::SampleLocationsCountMismatch {
+                        expected: sample_locations.expected_len(),
+                        obtained: sample_locations.locations.len(),
+                    },
+                );
+            }
+        } else {
+            return Err(CheckDynamicStateValidityError::SampleLocationsMissing);
+        }
+    } else {
+        if state.sample_locations.is_some() {
+            return Err(CheckDynamicStateValidityError::SampleLocationsNotDynamic);
+        }
+    }
+
+    let extended = pipeline.extended_dynamic_state();
+    let extended_states = [
+        (extended.cull_mode, state.cull_mode.is_some(), "cull_mode"),
+        (
+            extended.front_face,
+            state.front_face.is_some(),
+            "front_face",
+        ),
+        (
+            extended.primitive_topology,
+            state.primitive_topology.is_some(),
+            "primitive_topology",
+        ),
+        (
+            extended.depth_test_enable,
+            state.depth_test_enable.is_some(),
+            "depth_test_enable",
+        ),
+        (
+            extended.depth_write_enable,
+            state.depth_write_enable.is_some(),
+            "depth_write_enable",
+        ),
+        (
+            extended.depth_compare_op,
+            state.depth_compare_op.is_some(),
+            "depth_compare_op",
+        ),
+        (
+            extended.primitive_restart_enable,
+            state.primitive_restart_enable.is_some(),
+            "primitive_restart_enable",
+        ),
+        (
+            extended.rasterizer_discard_enable,
+            state.rasterizer_discard_enable.is_some(),
+            "rasterizer_discard_enable",
+        ),
+        (
+            extended.depth_bias_enable,
+            state.depth_bias_enable.is_some(),
+            "depth_bias_enable",
+        ),
+        (extended.logic_op, state.logic_op.is_some(), "logic_op"),
+        (
+            extended.color_blend_enable,
+            state.color_blend_enable.is_some(),
+            "color_blend_enable",
+        ),
+        (
+            extended.color_blend_equation,
+            state.color_blend_equation.is_some(),
+            "color_blend_equation",
+        ),
+        (
+            extended.color_write_mask,
+            state.color_write_mask.is_some(),
+            "color_write_mask",
+        ),
+        (
+            extended.color_write_enable,
+            state.color_write_enable.is_some(),
+            "color_write_enable",
+        ),
+    ];
+
+    for &(dynamic, passed, name) in extended_states.iter() {
+        if dynamic && !passed {
+            return Err(CheckDynamicStateValidityError::ExtendedStateMissing { name });
+        } else if !dynamic && passed {
+            return Err(CheckDynamicStateValidityError::ExtendedStateNotDynamic { name });
+        }
+    }
+
+    if let Some(ref topology) = state.primitive_topology {
+        if !topology.is_same_class(&pipeline.primitive_topology()) {
+            return Err(CheckDynamicStateValidityError::PrimitiveTopologyClassMismatch);
+        }
+    }
+
+    if state.primitive_topology.is_some() || state.primitive_restart_enable.is_some() {
+        let topology = state
+            .primitive_topology
+            .unwrap_or(pipeline.primitive_topology());
+        let restart = state
+            .primitive_restart_enable
+            .unwrap_or(pipeline.primitive_restart_enable());
+        if restart && !topology.supports_primitive_restart_with_features(device.enabled_features())
+        {
+            return Err(CheckDynamicStateValidityError::PrimitiveRestartNotSupported);
+        }
+    }
+
+    let num_color_attachments = pipeline
+        .num_color_attachments(pipeline.subpass_index())
+        .unwrap_or(0) as usize;
+
+    if let Some(ref color_write_enable) = state.color_write_enable {
+        if color_write_enable.len() != num_color_attachments {
+            return Err(
+                CheckDynamicStateValidityError::ColorWriteEnableCountMismatch {
+                    expected: num_color_attachments,
+                    obtained: color_write_enable.len(),
+                },
+            );
+        }
+    }
+
+    // These states are set for each color attachment of the subpass.
+    let color_attachment_states = [
+        (
+            state.color_blend_enable.as_ref().map(|v| v.len()),
+            "color_blend_enable",
+        ),
+        (
+            state.color_blend_equation.as_ref().map(|v| v.len()),
+            "color_blend_equation",
+        ),
+        (
+            state.color_write_mask.as_ref().map(|v| v.len()),
+            "color_write_mask",
+        ),
+    ];
+
+    for &(len, name) in color_attachment_states.iter() {
+        match len {
+            Some(len) if len != num_color_attachments => {
+                return Err(
+                    CheckDynamicStateValidityError::ColorAttachmentStateCountMismatch {
+                        name,
+                        expected: num_color_attachments,
+                        obtained: len,
+                    },
+                );
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(ref color_blend_equation) = state.color_blend_equation {
+        if color_blend_equation
+            .iter()
+            .any(|b| b.color_op.is_advanced() || b.alpha_op.is_advanced())
+        {
+            return Err(CheckDynamicStateValidityError::ColorBlendEquationAdvancedBlendOp);
+        }
+    }
+
     Ok(())
 }
 
@@ -114,6 +358,8 @@ pub enum CheckDynamicStateValidityError {
     /// The `wide_lines` extension must be enabled in order to use line width values different
     /// from 1.0.
     LineWidthMissingExtension,
+    /// The dynamic line width is outside of the range supported by the device.
+    LineWidthOutOfRange,
     /// Passed dynamic viewports, while the pipeline doesn't have viewports set as dynamic.
     ViewportsNotDynamic,
     /// The pipeline has dynamic viewports, but no viewports were passed.
@@ -148,6 +394,86 @@ pub enum CheckDynamicStateValidityError {
     ReferenceNotDynamic,
     /// The pipeline has dynamic reference, but no reference was passed.
     ReferenceMissing,
+    /// Passed dynamic depth bounds, while the pipeline doesn't have depth bounds set as dynamic.
+    DepthBoundsNotDynamic,
+    /// The pipeline has dynamic depth bounds, but no depth bounds were passed.
+    DepthBoundsMissing,
+    /// The dynamic depth bounds are not between 0.0 and 1.0.
+    DepthBoundsOutOfRange,
+    /// Passed a dynamic fragment shading rate, while the pipeline doesn't have the fragment
+    /// shading rate set as dynamic.
+    FragmentShadingRateNotDynamic,
+    /// The pipeline has a dynamic fragment shading rate, but no fragment shading rate was passed.
+    FragmentShadingRateMissing,
+    /// The width and height of the dynamic fragment shading rate are not 1, 2 or 4.
+    FragmentShadingRateInvalidSize,
+    /// The dynamic fragment shading rate combines the primitive shading rate, but the
+    /// `primitive_fragment_shading_rate` feature is not enabled.
+    FragmentShadingRatePrimitiveFeatureNotEnabled,
+    /// The dynamic fragment shading rate combines the attachment shading rate, but the
+    /// `attachment_fragment_shading_rate` feature is not enabled.
+    FragmentShadingRateAttachmentFeatureNotEnabled,
+    /// The dynamic fragment shading rate uses a combiner operation other than `Keep` or
+    /// `Replace`, which the device doesn't support.
+    FragmentShadingRateNonTrivialCombinerOpsNotSupported,
+    /// Passed a dynamic line stipple, while the pipeline doesn't have the line stipple set as
+    /// dynamic.
+    LineStippleNotDynamic,
+    /// The pipeline has a dynamic line stipple, but no line stipple was passed.
+    LineStippleMissing,
+    /// The factor of the dynamic line stipple is not between 1 and 256.
+    LineStippleInvalidFactor,
+    /// Passed dynamic sample locations, while the pipeline doesn't have the sample locations set
+    /// as dynamic.
+    SampleLocationsNotDynamic,
+    /// The pipeline has dynamic sample locations, but no sample locations were passed.
+    SampleLocationsMissing,
+    /// The number of dynamic sample locations doesn't match the number of samples per pixel and
+    /// the grid size.
+    SampleLocationsCountMismatch {
+        /// Expected number of locations.
+        expected: usize,
+        /// Number of locations that were passed.
+        obtained: usize,
+    },
+    /// Passed a value for a dynamic state of the `ExtendedDynamicState` of the pipeline, while the
+    /// pipeline doesn't have this state set as dynamic.
+    ExtendedStateNotDynamic {
+        /// Name of the state.
+        name: &'static str,
+    },
+    /// The pipeline has a state of its `ExtendedDynamicState` set as dynamic, but no value was
+    /// passed for it.
+    ExtendedStateMissing {
+        /// Name of the state.
+        name: &'static str,
+    },
+    /// The dynamic color blend equations contain an advanced blend operation, which can't be
+    /// set dynamically.
+    ColorBlendEquationAdvancedBlendOp,
+    /// The dynamic primitive topology is not of the same class as the topology of the pipeline.
+    PrimitiveTopologyClassMismatch,
+    /// Primitive restart is enabled, but the primitive topology doesn't support it with the
+    /// enabled features.
+    PrimitiveRestartNotSupported,
+    /// The number of dynamic color write enables doesn't match the number of color attachments
+    /// of the subpass.
+    ColorWriteEnableCountMismatch {
+        /// Expected number of values.
+        expected: usize,
+        /// Number of values that were passed.
+        obtained: usize,
+    },
+    /// The number of values of a dynamic state that is set for each color attachment doesn't
+    /// match the number of color attachments of the subpass.
+    ColorAttachmentStateCountMismatch {
+        /// Name of the state.
+        name: &'static str,
+        /// Expected number of values.
+        expected: usize,
+        /// Number of values that were passed.
+        obtained: usize,
+    },
 }
 
 impl error::Error for CheckDynamicStateValidityError {}
@@ -170,6 +496,9 @@ impl fmt::Display for CheckDynamicStateValidityError {
                     "the `wide_lines` extension must be enabled in order to use line width values \
                  different from 1.0"
                 }
+                CheckDynamicStateValidityError::LineWidthOutOfRange => {
+                    "the dynamic line width is outside of the range supported by the device"
+                }
                 CheckDynamicStateValidityError::ViewportsNotDynamic => {
                     "passed dynamic viewports, while the pipeline doesn't have viewports set as \
                  dynamic"
@@ -207,9 +536,280 @@ impl fmt::Display for CheckDynamicStateValidityError {
                 CheckDynamicStateValidityError::ReferenceMissing => {
                     "the pipeline has dynamic reference, but no reference was passed"
                 }
+                CheckDynamicStateValidityError::DepthBoundsNotDynamic => {
+                    "passed dynamic depth bounds, while the pipeline doesn't have depth bounds set as dynamic"
+                }
+                CheckDynamicStateValidityError::DepthBoundsMissing => {
+                    "the pipeline has dynamic depth bounds, but no depth bounds were passed"
+                }
+                CheckDynamicStateValidityError::DepthBoundsOutOfRange => {
+                    "the dynamic depth bounds are not between 0.0 and 1.0"
+                }
+                CheckDynamicStateValidityError::FragmentShadingRateNotDynamic => {
+                    "passed a dynamic fragment shading rate, while the pipeline doesn't have the fragment shading rate set as dynamic"
+                }
+                CheckDynamicStateValidityError::FragmentShadingRateMissing => {
+                    "the pipeline has a dynamic fragment shading rate, but no fragment shading rate was passed"
+                }
+                CheckDynamicStateValidityError::FragmentShadingRateInvalidSize => {
+                    "the width and height of the dynamic fragment shading rate are not 1, 2 or 4"
+                }
+                CheckDynamicStateValidityError::FragmentShadingRatePrimitiveFeatureNotEnabled => {
+                    "the dynamic fragment shading rate combines the primitive shading rate, but the `primitive_fragment_shading_rate` feature is not enabled"
+                }
+                CheckDynamicStateValidityError::FragmentShadingRateAttachmentFeatureNotEnabled => {
+                    "the dynamic fragment shading rate combines the attachment shading rate, but the `attachment_fragment_shading_rate` feature is not enabled"
+                }
+                CheckDynamicStateValidityError::FragmentShadingRateNonTrivialCombinerOpsNotSupported => {
+                    "the dynamic fragment shading rate uses a combiner operation that the device doesn't support"
+                }
+                CheckDynamicStateValidityError::LineStippleNotDynamic => {
+                    "passed a dynamic line stipple, while the pipeline doesn't have the line stipple set as dynamic"
+                }
+                CheckDynamicStateValidityError::LineStippleMissing => {
+                    "the pipeline has a dynamic line stipple, but no line stipple was passed"
+                }
+                CheckDynamicStateValidityError::LineStippleInvalidFactor => {
+                    "the factor of the dynamic line stipple is not between 1 and 256"
+                }
+                CheckDynamicStateValidityError::SampleLocationsNotDynamic => {
+                    "passed dynamic sample locations, while the pipeline doesn't have the sample locations set as dynamic"
+                }
+                CheckDynamicStateValidityError::SampleLocationsMissing => {
+                    "the pipeline has dynamic sample locations, but no sample locations were passed"
+                }
+                CheckDynamicStateValidityError::SampleLocationsCountMismatch { .. } => {
+                    "the number of dynamic sample locations doesn't match the number of samples per pixel and the grid size"
+                }
+                CheckDynamicStateValidityError::ExtendedStateNotDynamic { .. } => {
+                    "passed a value for an extended dynamic state, while the pipeline doesn't have this state set as dynamic"
+                }
+                CheckDynamicStateValidityError::ExtendedStateMissing { .. } => {
+                    "the pipeline has an extended dynamic state, but no value was passed for it"
+                }
+                CheckDynamicStateValidityError::ColorBlendEquationAdvancedBlendOp => {
+                    "the dynamic color blend equations contain an advanced blend operation"
+                }
+                CheckDynamicStateValidityError::PrimitiveTopologyClassMismatch => {
+                    "the dynamic primitive topology is not of the same class as the topology of the pipeline"
+                }
+                CheckDynamicStateValidityError::PrimitiveRestartNotSupported => {
+                    "primitive restart is enabled, but the primitive topology doesn't support it"
+                }
+                CheckDynamicStateValidityError::ColorWriteEnableCountMismatch { .. } => {
+                    "the number of dynamic color write enables doesn't match the number of color attachments of the subpass"
+                }
+                CheckDynamicStateValidityError::ColorAttachmentStateCountMismatch { .. } => {
+                    "the number of values of a dynamic color attachment state doesn't match the number of color attachments of the subpass"
+                }
             }
         )
     }
 }
 
-// TODO: tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::Device;
+    use crate::device::DeviceExtensions;
+    use crate::features::Features;
+    use crate::instance;
+    use crate::pipeline::graphics_pipeline_tests;
+    use crate::pipeline::raster::LineStipple;
+    use crate::pipeline::shading_rate::FragmentShadingRate;
+    use crate::pipeline::viewport::Viewport;
+    use crate::pipeline::ExtendedDynamicState;
+
+    #[test]
+    fn line_stipple_not_dynamic() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let (vs, fs) = graphics_pipeline_tests::basic_shaders(&device);
+        let pipeline = graphics_pipeline_tests::basic_pipeline(
+            &vs,
+            &fs,
+            graphics_pipeline_tests::basic_subpass(&device, None),
+        )
+        .build(device.clone())
+        .unwrap();
+
+        let state = DynamicState {
+            viewports: Some(vec![Viewport {
+                origin: [0.0, 0.0],
+                dimensions: [4.0, 4.0],
+                depth_range: 0.0..1.0,
+            }]),
+            line_stipple: Some(LineStipple {
+                factor: 1,
+                pattern: 0xffff,
+            }),
+            ..DynamicState::none()
+        };
+        match check_dynamic_state_validity(&pipeline, &state) {
+            Err(CheckDynamicStateValidityError::LineStippleNotDynamic) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn fragment_shading_rate_primitive_feature() {
+        let (device, _) = gfx_dev_and_queue!(pipeline_fragment_shading_rate;
+                                             khr_fragment_shading_rate, khr_create_renderpass2);
+
+        let (vs, fs) = graphics_pipeline_tests::basic_shaders(&device);
+        let pipeline = graphics_pipeline_tests::basic_pipeline(
+            &vs,
+            &fs,
+            graphics_pipeline_tests::basic_subpass(&device, None),
+        )
+        .fragment_shading_rate_dynamic()
+        .build(device.clone())
+        .unwrap();
+
+        let state = DynamicState {
+            viewports: Some(vec![Viewport {
+                origin: [0.0, 0.0],
+                dimensions: [4.0, 4.0],
+                depth_range: 0.0..1.0,
+            }]),
+            fragment_shading_rate: Some(FragmentShadingRate {
+                fragment_size: [2, 2],
+                combiner_ops: [
+                    FragmentShadingRateCombinerOp::Replace,
+                    FragmentShadingRateCombinerOp::Keep,
+                ],
+            }),
+            ..DynamicState::none()
+        };
+        assert!(matches!(
+            check_dynamic_state_validity(&pipeline, &state),
+            Err(CheckDynamicStateValidityError::FragmentShadingRatePrimitiveFeatureNotEnabled)
+        ));
+    }
+
+    #[test]
+    fn color_blend_enable_count_mismatch() {
+        let instance = instance!();
+
+        let phys = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let queue_family = match phys.queue_families().find(|q| q.supports_graphics()) {
+            Some(q) => q,
+            None => return,
+        };
+
+        let features = Features {
+            extended_dynamic_state3_color_blend_enable: true,
+            ..Features::none()
+        };
+        if !phys.supported_features().superset_of(&features) {
+            return;
+        }
+
+        let extensions = DeviceExtensions {
+            ext_extended_dynamic_state3: true,
+            ..DeviceExtensions::none()
+        };
+        let (device, _) = match Device::new(
+            phys,
+            &features,
+            &extensions,
+            std::iter::once((queue_family, 0.5)),
+        ) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+
+        let (vs, fs) = graphics_pipeline_tests::basic_shaders(&device);
+        let pipeline = graphics_pipeline_tests::basic_pipeline(
+            &vs,
+            &fs,
+            graphics_pipeline_tests::basic_subpass(&device, None),
+        )
+        .extended_dynamic_state(ExtendedDynamicState {
+            color_blend_enable: true,
+            ..ExtendedDynamicState::none()
+        })
+        .build(device.clone())
+        .unwrap();
+
+        let viewport = Viewport {
+            origin: [0.0, 0.0],
+            dimensions: [4.0, 4.0],
+            depth_range: 0.0..1.0,
+        };
+
+        // The subpass has a single color attachment.
+        let state = DynamicState {
+            viewports: Some(vec![viewport.clone()]),
+            color_blend_enable: Some(vec![true]),
+            ..DynamicState::none()
+        };
+        assert!(check_dynamic_state_validity(&pipeline, &state).is_ok());
+
+        let state = DynamicState {
+            viewports: Some(vec![viewport]),
+            color_blend_enable: Some(vec![true, false]),
+            ..DynamicState::none()
+        };
+        match check_dynamic_state_validity(&pipeline, &state) {
+            Err(CheckDynamicStateValidityError::ColorAttachmentStateCountMismatch {
+                expected: 1,
+                obtained: 2,
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn color_write_enable_count_mismatch() {
+        let (device, _) = gfx_dev_and_queue!(color_write_enable; ext_color_write_enable);
+
+        let (vs, fs) = graphics_pipeline_tests::basic_shaders(&device);
+        let pipeline = graphics_pipeline_tests::basic_pipeline(
+            &vs,
+            &fs,
+            graphics_pipeline_tests::basic_subpass(&device, None),
+        )
+        .extended_dynamic_state(ExtendedDynamicState {
+            color_write_enable: true,
+            ..ExtendedDynamicState::none()
+        })
+        .build(device.clone())
+        .unwrap();
+
+        let viewport = Viewport {
+            origin: [0.0, 0.0],
+            dimensions: [4.0, 4.0],
+            depth_range: 0.0..1.0,
+        };
+
+        let state = DynamicState {
+            viewports: Some(vec![viewport.clone()]),
+            ..DynamicState::none()
+        };
+        match check_dynamic_state_validity(&pipeline, &state) {
+            Err(CheckDynamicStateValidityError::ExtendedStateMissing {
+                name: "color_write_enable",
+            }) => (),
+            _ => panic!(),
+        }
+
+        let state = DynamicState {
+            viewports: Some(vec![viewport]),
+            color_write_enable: Some(vec![true, false]),
+            ..DynamicState::none()
+        };
+        match check_dynamic_state_validity(&pipeline, &state) {
+            Err(CheckDynamicStateValidityError::ColorWriteEnableCountMismatch {
+                expected: 1,
+                obtained: 2,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
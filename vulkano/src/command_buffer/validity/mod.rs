@@ -9,6 +9,7 @@
 
 //! Functions that check the validity of commands.
 
+pub use self::bind_shaders::{check_bind_shaders, CheckBindShadersError};
 pub use self::blit_image::{check_blit_image, CheckBlitImageError};
 pub use self::clear_color_image::{check_clear_color_image, CheckClearColorImageError};
 pub use self::copy_buffer::{check_copy_buffer, CheckCopyBuffer, CheckCopyBufferError};
@@ -18,15 +19,21 @@ pub use self::copy_image_buffer::{
 };
 pub use self::debug_marker::{check_debug_marker_color, CheckColorError};
 pub use self::descriptor_sets::{check_descriptor_sets_validity, CheckDescriptorSetsValidityError};
+pub use self::device_mask::{check_device_mask, CheckDeviceMaskError};
 pub use self::dispatch::{check_dispatch, CheckDispatchError};
 pub use self::dynamic_state::{check_dynamic_state_validity, CheckDynamicStateValidityError};
 pub use self::fill_buffer::{check_fill_buffer, CheckFillBufferError};
 pub use self::index_buffer::{check_index_buffer, CheckIndexBuffer, CheckIndexBufferError};
 pub use self::indirect_buffer::{check_indirect_buffer, CheckIndirectBufferError};
 pub use self::push_constants::{check_push_constants_validity, CheckPushConstantsValidityError};
+pub use self::query::{
+    check_begin_query, check_reset_query_pool, CheckBeginQueryError, CheckResetQueryPoolError,
+};
+pub use self::queue_family_transfer::{check_queue_family_transfer, CheckQueueFamilyTransferError};
 pub use self::update_buffer::{check_update_buffer, CheckUpdateBufferError};
 pub use self::vertex_buffers::{check_vertex_buffers, CheckVertexBuffer, CheckVertexBufferError};
 
+mod bind_shaders;
 mod blit_image;
 mod clear_color_image;
 mod copy_buffer;
@@ -34,11 +41,14 @@ mod copy_image;
 mod copy_image_buffer;
 mod debug_marker;
 mod descriptor_sets;
+mod device_mask;
 mod dispatch;
 mod dynamic_state;
 mod fill_buffer;
 mod index_buffer;
 mod indirect_buffer;
 mod push_constants;
+mod query;
+mod queue_family_transfer;
 mod update_buffer;
 mod vertex_buffers;
@@ -0,0 +1,157 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::query::OcclusionQueriesPool;
+use crate::query::QueryControlFlags;
+use crate::VulkanObject;
+
+/// Checks whether a begin query command is valid.
+///
+/// # Panic
+///
+/// - Panics if the query pool was not created with `device`.
+///
+pub fn check_begin_query(
+    device: &Device,
+    pool: &OcclusionQueriesPool,
+    index: u32,
+    flags: QueryControlFlags,
+) -> Result<(), CheckBeginQueryError> {
+    assert_eq!(pool.device().internal_object(), device.internal_object());
+
+    if index >= pool.num_slots() {
+        return Err(CheckBeginQueryError::OutOfRange);
+    }
+
+    if flags.precise && !device.enabled_features().occlusion_query_precise {
+        return Err(CheckBeginQueryError::OcclusionQueryPreciseFeatureNotEnabled);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `begin_query` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckBeginQueryError {
+    /// The query index is out of the range of the pool.
+    OutOfRange,
+    /// The `precise` flag was set but the `occlusion_query_precise` feature wasn't enabled.
+    OcclusionQueryPreciseFeatureNotEnabled,
+}
+
+impl error::Error for CheckBeginQueryError {}
+
+impl fmt::Display for CheckBeginQueryError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckBeginQueryError::OutOfRange => {
+                    "the query index is out of the range of the pool"
+                }
+                CheckBeginQueryError::OcclusionQueryPreciseFeatureNotEnabled => {
+                    "the `precise` flag was set but the `occlusion_query_precise` feature wasn't \
+                     enabled"
+                }
+            }
+        )
+    }
+}
+
+/// Checks whether a reset query pool command is valid.
+///
+/// # Panic
+///
+/// - Panics if the query pool was not created with `device`.
+///
+pub fn check_reset_query_pool(
+    device: &Device,
+    pool: &OcclusionQueriesPool,
+    first_index: u32,
+    count: u32,
+) -> Result<(), CheckResetQueryPoolError> {
+    assert_eq!(pool.device().internal_object(), device.internal_object());
+
+    if count == 0 || !matches!(first_index.checked_add(count), Some(end) if end <= pool.num_slots())
+    {
+        return Err(CheckResetQueryPoolError::OutOfRange);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `reset_query_pool` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckResetQueryPoolError {
+    /// The range of queries is empty or out of the range of the pool.
+    OutOfRange,
+}
+
+impl error::Error for CheckResetQueryPoolError {}
+
+impl fmt::Display for CheckResetQueryPoolError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckResetQueryPoolError::OutOfRange => {
+                    "the range of queries is empty or out of the range of the pool"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn begin_out_of_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = OcclusionQueriesPool::new(device.clone(), 4);
+
+        match check_begin_query(&device, &pool, 4, QueryControlFlags::default()) {
+            Err(CheckBeginQueryError::OutOfRange) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn begin_precise_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = OcclusionQueriesPool::new(device.clone(), 4);
+
+        match check_begin_query(&device, &pool, 0, QueryControlFlags { precise: true }) {
+            Err(CheckBeginQueryError::OcclusionQueryPreciseFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn reset_out_of_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = OcclusionQueriesPool::new(device.clone(), 4);
+
+        assert!(check_reset_query_pool(&device, &pool, 0, 4).is_ok());
+        match check_reset_query_pool(&device, &pool, 2, 3) {
+            Err(CheckResetQueryPoolError::OutOfRange) => (),
+            _ => panic!(),
+        }
+    }
+}
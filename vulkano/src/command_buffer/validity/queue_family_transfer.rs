@@ -0,0 +1,78 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use crate::device::Device;
+use crate::instance::QueueFamily;
+use crate::VulkanObject;
+
+/// Checks whether a queue family ownership transfer between the queue family of a command buffer
+/// and another queue family is valid.
+///
+/// # Panic
+///
+/// - Panics if `other_family` doesn't belong to the physical device of `device`.
+///
+pub fn check_queue_family_transfer(
+    device: &Device,
+    command_buffer_family: u32,
+    other_family: QueueFamily,
+) -> Result<(), CheckQueueFamilyTransferError> {
+    assert_eq!(
+        other_family.physical_device().internal_object(),
+        device.physical_device().internal_object()
+    );
+
+    if other_family.id() == command_buffer_family {
+        return Err(CheckQueueFamilyTransferError::SameQueueFamily);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a queue family ownership transfer.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckQueueFamilyTransferError {
+    /// The source and destination queue families are the same.
+    SameQueueFamily,
+}
+
+impl error::Error for CheckQueueFamilyTransferError {}
+
+impl fmt::Display for CheckQueueFamilyTransferError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckQueueFamilyTransferError::SameQueueFamily => {
+                    "the source and destination queue families are the same"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        match check_queue_family_transfer(&device, queue.family().id(), queue.family()) {
+            Err(CheckQueueFamilyTransferError::SameQueueFamily) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -767,6 +767,18 @@ impl ShaderStages {
         }
         result
     }
+
+    #[inline]
+    pub(crate) fn from_vulkan_bits(value: vk::ShaderStageFlags) -> ShaderStages {
+        ShaderStages {
+            vertex: (value & vk::SHADER_STAGE_VERTEX_BIT) != 0,
+            tessellation_control: (value & vk::SHADER_STAGE_TESSELLATION_CONTROL_BIT) != 0,
+            tessellation_evaluation: (value & vk::SHADER_STAGE_TESSELLATION_EVALUATION_BIT) != 0,
+            geometry: (value & vk::SHADER_STAGE_GEOMETRY_BIT) != 0,
+            fragment: (value & vk::SHADER_STAGE_FRAGMENT_BIT) != 0,
+            compute: (value & vk::SHADER_STAGE_COMPUTE_BIT) != 0,
+        }
+    }
 }
 
 impl BitOr for ShaderStages {
@@ -25,6 +25,7 @@ use crate::descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::image::view::ImageViewAbstract;
+use crate::image::ImageLayout;
 use crate::sampler::Sampler;
 
 use crate::check_errors;
@@ -323,7 +324,7 @@ impl UnsafeDescriptorPool {
             check_errors(vk.CreateDescriptorPool(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -506,7 +507,11 @@ impl Drop for UnsafeDescriptorPool {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyDescriptorPool(self.device.internal_object(), self.pool, ptr::null());
+            vk.DestroyDescriptorPool(
+                self.device.internal_object(),
+                self.pool,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -1106,6 +1111,30 @@ impl DescriptorWrite {
         }
     }
 
+    /// Same as `input_attachment`, but the descriptor uses `layout` instead of the layout
+    /// returned by the `descriptor_layouts` of the image.
+    ///
+    /// The layout must be the one that the subpass that reads the input attachment uses for it.
+    #[inline]
+    pub fn input_attachment_with_layout<I>(
+        binding: u32,
+        array_element: u32,
+        image_view: &I,
+        layout: ImageLayout,
+    ) -> DescriptorWrite
+    where
+        I: ?Sized + ImageViewAbstract,
+    {
+        DescriptorWrite {
+            binding,
+            first_array_element: array_element,
+            inner: smallvec!(DescriptorWriteInner::InputAttachment(
+                image_view.inner().internal_object(),
+                layout as u32
+            )),
+        }
+    }
+
     /// Returns the type corresponding to this write.
     #[inline]
     pub fn ty(&self) -> DescriptorType {
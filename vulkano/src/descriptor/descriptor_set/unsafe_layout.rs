@@ -97,7 +97,7 @@ impl UnsafeDescriptorSetLayout {
             check_errors(vk.CreateDescriptorSetLayout(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -161,7 +161,11 @@ impl Drop for UnsafeDescriptorSetLayout {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyDescriptorSetLayout(self.device.internal_object(), self.layout, ptr::null());
+            vk.DestroyDescriptorSetLayout(
+                self.device.internal_object(),
+                self.layout,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -65,6 +65,7 @@ pub use self::traits::PipelineLayoutSetsCompatible;
 pub use self::traits::PipelineLayoutSuperset;
 pub use self::tweaks::PipelineLayoutDescTweaks;
 pub use self::union::PipelineLayoutDescUnion;
+pub use self::union::PipelineLayoutDescUnionError;
 
 mod empty;
 mod limits_check;
@@ -148,7 +148,7 @@ where
             check_errors(vk.CreatePipelineLayout(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -244,7 +244,11 @@ impl<L> Drop for PipelineLayout<L> {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyPipelineLayout(self.device.internal_object(), self.layout, ptr::null());
+            vk.DestroyPipelineLayout(
+                self.device.internal_object(),
+                self.layout,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -21,6 +21,7 @@ use crate::descriptor::pipeline_layout::limits_check;
 use crate::descriptor::pipeline_layout::PipelineLayout;
 use crate::descriptor::pipeline_layout::PipelineLayoutCreationError;
 use crate::descriptor::pipeline_layout::PipelineLayoutDescUnion;
+use crate::descriptor::pipeline_layout::PipelineLayoutDescUnionError;
 use crate::descriptor::pipeline_layout::PipelineLayoutSys;
 use crate::device::Device;
 use crate::device::DeviceOwned;
@@ -103,6 +104,19 @@ pub unsafe trait PipelineLayoutDesc {
         PipelineLayoutDescUnion::new(self, other)
     }
 
+    /// Builds the union of this layout and another, and checks that they are compatible.
+    #[inline]
+    fn try_union<T>(
+        self,
+        other: T,
+    ) -> Result<PipelineLayoutDescUnion<Self, T>, PipelineLayoutDescUnionError>
+    where
+        Self: Sized,
+        T: PipelineLayoutDesc,
+    {
+        PipelineLayoutDescUnion::try_new(self, other)
+    }
+
     /// Checks whether this description fulfills the device limits requirements.
     #[inline]
     fn check_against_limits(
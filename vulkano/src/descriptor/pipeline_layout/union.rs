@@ -12,6 +12,8 @@ use crate::descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use crate::descriptor::pipeline_layout::PipelineLayoutDesc;
 use crate::descriptor::pipeline_layout::PipelineLayoutDescPcRange;
 use std::cmp;
+use std::error;
+use std::fmt;
 use std::sync::Arc;
 
 /// Contains the union of two pipeline layout description.
@@ -24,12 +26,74 @@ pub struct PipelineLayoutDescUnion<A, B> {
 }
 
 impl<A, B> PipelineLayoutDescUnion<A, B> {
-    // FIXME: check collisions
+    /// Builds the union of `a` and `b`, without checking that they are compatible. Querying a
+    /// descriptor that has incompatible definitions in `a` and `b` will panic.
+    ///
+    /// See also `try_new`.
     pub fn new(a: A, b: B) -> PipelineLayoutDescUnion<A, B> {
         PipelineLayoutDescUnion { a: a, b: b }
     }
 }
 
+impl<A, B> PipelineLayoutDescUnion<A, B>
+where
+    A: PipelineLayoutDesc,
+    B: PipelineLayoutDesc,
+{
+    /// Builds the union of `a` and `b`, and checks that the descriptors and the push constants
+    /// ranges of both descriptions can be merged together.
+    pub fn try_new(
+        a: A,
+        b: B,
+    ) -> Result<PipelineLayoutDescUnion<A, B>, PipelineLayoutDescUnionError> {
+        for set_num in 0..cmp::min(a.num_sets(), b.num_sets()) {
+            let num_bindings = match (
+                a.num_bindings_in_set(set_num),
+                b.num_bindings_in_set(set_num),
+            ) {
+                (Some(a), Some(b)) => cmp::min(a, b),
+                _ => continue,
+            };
+
+            for binding_num in 0..num_bindings {
+                let (a_desc, b_desc) = match (
+                    a.descriptor(set_num, binding_num),
+                    b.descriptor(set_num, binding_num),
+                ) {
+                    (Some(a_desc), Some(b_desc)) => (a_desc, b_desc),
+                    _ => continue,
+                };
+
+                if a_desc.union(&b_desc).is_none() {
+                    return Err(PipelineLayoutDescUnionError::IncompatibleDescriptors {
+                        set_num: set_num as u32,
+                        descriptor: binding_num as u32,
+                    });
+                }
+            }
+        }
+
+        // The union merges the ranges that share stages, which is only correct if they share
+        // all of them.
+        for a_num in 0..a.num_push_constants_ranges() {
+            let a_pc = a.push_constants_range(a_num).unwrap();
+
+            for b_num in 0..b.num_push_constants_ranges() {
+                let b_pc = b.push_constants_range(b_num).unwrap();
+
+                if a_pc.stages.intersects(&b_pc.stages) && a_pc.stages != b_pc.stages {
+                    return Err(PipelineLayoutDescUnionError::PushConstantsStagesConflict {
+                        first_offset: a_pc.offset,
+                        second_offset: b_pc.offset,
+                    });
+                }
+            }
+        }
+
+        Ok(PipelineLayoutDescUnion { a, b })
+    }
+}
+
 unsafe impl<A, B> PipelineLayoutDesc for PipelineLayoutDescUnion<A, B>
 where
     A: PipelineLayoutDesc,
@@ -133,3 +197,132 @@ where
         None
     }
 }
+
+/// Error that can happen when building the union of two pipeline layout descriptions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PipelineLayoutDescUnionError {
+    /// The two descriptions have descriptors of different types at the same location.
+    IncompatibleDescriptors { set_num: u32, descriptor: u32 },
+
+    /// A push constants range of the first description and one of the second description are
+    /// accessible by some, but not all, of the same shader stages.
+    PushConstantsStagesConflict {
+        first_offset: usize,
+        second_offset: usize,
+    },
+}
+
+impl error::Error for PipelineLayoutDescUnionError {}
+
+impl fmt::Display for PipelineLayoutDescUnionError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                PipelineLayoutDescUnionError::IncompatibleDescriptors { .. } => {
+                    "the two descriptions have descriptors of different types at the same location"
+                }
+                PipelineLayoutDescUnionError::PushConstantsStagesConflict { .. } => {
+                    "two push constants ranges are accessible by some, but not all, of the same \
+                     shader stages"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::descriptor::descriptor::DescriptorDesc;
+    use crate::descriptor::descriptor::DescriptorDescTy;
+    use crate::descriptor::descriptor::DescriptorImageDescArray;
+    use crate::descriptor::descriptor::ShaderStages;
+    use crate::descriptor::pipeline_layout::PipelineLayoutDesc;
+    use crate::descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+    use crate::descriptor::pipeline_layout::PipelineLayoutDescUnion;
+    use crate::descriptor::pipeline_layout::PipelineLayoutDescUnionError;
+    use crate::descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::iter;
+
+    fn desc(ty: DescriptorDescTy, stages: ShaderStages) -> RuntimePipelineDesc {
+        let descriptor = DescriptorDesc {
+            ty,
+            array_count: 1,
+            stages,
+            readonly: true,
+        };
+
+        RuntimePipelineDesc::new(iter::once(iter::once(Some(descriptor))), iter::empty()).unwrap()
+    }
+
+    #[test]
+    fn descriptors_merged() {
+        let vertex = ShaderStages {
+            vertex: true,
+            ..ShaderStages::none()
+        };
+        let fragment = ShaderStages {
+            fragment: true,
+            ..ShaderStages::none()
+        };
+
+        let union = PipelineLayoutDescUnion::try_new(
+            desc(DescriptorDescTy::Sampler, vertex),
+            desc(DescriptorDescTy::Sampler, fragment),
+        )
+        .unwrap();
+
+        assert_eq!(union.descriptor(0, 0).unwrap().stages, vertex | fragment);
+    }
+
+    #[test]
+    fn descriptors_conflict() {
+        let union = PipelineLayoutDescUnion::try_new(
+            desc(DescriptorDescTy::Sampler, ShaderStages::all_graphics()),
+            desc(
+                DescriptorDescTy::InputAttachment {
+                    multisampled: false,
+                    array_layers: DescriptorImageDescArray::NonArrayed,
+                },
+                ShaderStages::all_graphics(),
+            ),
+        );
+
+        match union {
+            Err(PipelineLayoutDescUnionError::IncompatibleDescriptors {
+                set_num: 0,
+                descriptor: 0,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn push_constants_conflict() {
+        let vertex = ShaderStages {
+            vertex: true,
+            ..ShaderStages::none()
+        };
+
+        let range = |stages| {
+            RuntimePipelineDesc::new::<_, _, iter::Empty<Option<DescriptorDesc>>>(
+                iter::empty(),
+                iter::once(PipelineLayoutDescPcRange {
+                    offset: 0,
+                    size: 16,
+                    stages,
+                }),
+            )
+            .unwrap()
+        };
+
+        assert!(PipelineLayoutDescUnion::try_new(range(vertex), range(vertex)).is_ok());
+
+        match PipelineLayoutDescUnion::try_new(range(vertex), range(ShaderStages::all_graphics())) {
+            Err(PipelineLayoutDescUnionError::PushConstantsStagesConflict { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Extension structures that vulkano doesn't know about.
+//!
+//! The `Features` struct and the properties of `PhysicalDevice` only cover the extensions that
+//! vulkano supports. In order to use another extension, you can define its structures yourself,
+//! implement `ExtensionFeatures` or `ExtensionProperties` for them, and pass them to
+//! `PhysicalDevice::extension_features`, `PhysicalDevice::extension_properties` and
+//! `Device::with_extension_features`.
+
+use std::fmt;
+use std::mem;
+
+use crate::features::Base;
+use crate::vk;
+
+/// Structure that can be added to the `pNext` chain of `VkPhysicalDeviceFeatures2` and of
+/// `VkDeviceCreateInfo`.
+///
+/// # Safety
+///
+/// - The type must be `#[repr(C)]` and start with the `sType` and `pNext` members, like every
+///   Vulkan structure.
+/// - `STRUCTURE_TYPE` must be the `sType` value of the structure.
+/// - A value whose bytes are all zero must be valid, which is the case of every structure that
+///   only contains integers, booleans and pointers.
+pub unsafe trait ExtensionFeatures: Copy + Send + Sync + 'static {
+    /// Value of the `sType` member of the structure.
+    const STRUCTURE_TYPE: vk::StructureType;
+}
+
+/// Structure that can be added to the `pNext` chain of `VkPhysicalDeviceProperties2`.
+///
+/// # Safety
+///
+/// Same as `ExtensionFeatures`.
+pub unsafe trait ExtensionProperties: Copy + Send + Sync + 'static {
+    /// Value of the `sType` member of the structure.
+    const STRUCTURE_TYPE: vk::StructureType;
+}
+
+/// Returns a zeroed structure with its `sType` member set.
+#[inline]
+pub(crate) fn zeroed_struct<T: Copy>(structure_type: vk::StructureType) -> T {
+    unsafe {
+        let mut output: T = mem::zeroed();
+        (*(&mut output as *mut T as *mut Base)).sType = structure_type;
+        output
+    }
+}
+
+/// List of extension feature structures to enable at device creation.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::device::ExtensionFeatures;
+/// use vulkano::device::ExtensionFeaturesChain;
+/// use std::os::raw::c_void;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone)]
+/// struct PhysicalDeviceFooFeatures {
+///     s_type: u32,
+///     p_next: *mut c_void,
+///     foo: u32,
+/// }
+///
+/// unsafe impl Send for PhysicalDeviceFooFeatures {}
+/// unsafe impl Sync for PhysicalDeviceFooFeatures {}
+/// unsafe impl ExtensionFeatures for PhysicalDeviceFooFeatures {
+///     const STRUCTURE_TYPE: u32 = 1000999000;
+/// }
+///
+/// let mut chain = ExtensionFeaturesChain::new();
+/// chain.push(PhysicalDeviceFooFeatures {
+///     s_type: 0,
+///     p_next: std::ptr::null_mut(),
+///     foo: 1,
+/// });
+/// assert_eq!(chain.len(), 1);
+/// ```
+#[derive(Default)]
+pub struct ExtensionFeaturesChain {
+    structs: Vec<(vk::StructureType, Box<dyn ChainedStruct>)>,
+}
+
+// Object-safe part of `ExtensionFeatures`, used to keep the structures in a list.
+trait ChainedStruct: Send + Sync {
+    fn base_mut(&mut self) -> *mut Base;
+}
+
+impl<T> ChainedStruct for T
+where
+    T: ExtensionFeatures,
+{
+    #[inline]
+    fn base_mut(&mut self) -> *mut Base {
+        self as *mut T as *mut Base
+    }
+}
+
+impl ExtensionFeaturesChain {
+    /// Builds an empty chain.
+    #[inline]
+    pub fn new() -> ExtensionFeaturesChain {
+        ExtensionFeaturesChain {
+            structs: Vec::new(),
+        }
+    }
+
+    /// Adds a structure to the chain. Its `sType` member is overwritten with
+    /// `T::STRUCTURE_TYPE`, and its `pNext` member is overwritten at device creation.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if a structure of the same type has already been added.
+    ///
+    pub fn push<T>(&mut self, structure: T)
+    where
+        T: ExtensionFeatures,
+    {
+        assert!(
+            !self.contains(T::STRUCTURE_TYPE),
+            "a structure of the same type is already in the chain"
+        );
+
+        let mut structure = Box::new(structure);
+        unsafe {
+            (*structure.base_mut()).sType = T::STRUCTURE_TYPE;
+        }
+        self.structs.push((T::STRUCTURE_TYPE, structure));
+    }
+
+    /// Returns the number of structures in the chain.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.structs.len()
+    }
+
+    /// Returns true if the chain is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.structs.is_empty()
+    }
+
+    /// Returns true if a structure with the given `sType` is in the chain.
+    #[inline]
+    pub fn contains(&self, structure_type: vk::StructureType) -> bool {
+        self.structs.iter().any(|&(ty, _)| ty == structure_type)
+    }
+
+    #[inline]
+    pub(crate) fn structure_types<'a>(&'a self) -> impl Iterator<Item = vk::StructureType> + 'a {
+        self.structs.iter().map(|&(ty, _)| ty)
+    }
+
+    /// Links the structures together, with the last one pointing to `next`, and returns the
+    /// first one. Returns `next` if the chain is empty.
+    ///
+    /// The returned pointer is valid as long as `self` is neither modified nor dropped.
+    pub(crate) fn link(&mut self, next: *mut Base) -> *mut Base {
+        let mut head = next;
+        for &mut (_, ref mut structure) in self.structs.iter_mut().rev() {
+            let base = structure.base_mut();
+            unsafe {
+                (*base).pNext = head;
+            }
+            head = base;
+        }
+        head
+    }
+}
+
+impl fmt::Debug for ExtensionFeaturesChain {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_list()
+            .entries(self.structs.iter().map(|&(ty, _)| ty))
+            .finish()
+    }
+}
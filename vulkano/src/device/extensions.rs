@@ -17,6 +17,7 @@ use std::str;
 use crate::check_errors;
 use crate::extensions::SupportedExtensionsError;
 use crate::instance::PhysicalDevice;
+use crate::instance::Version;
 use crate::vk;
 use crate::VulkanObject;
 
@@ -129,6 +130,7 @@ device_extensions! {
         khr_portability_subset,
     ],
     khr_swapchain => b"VK_KHR_swapchain",
+    khr_device_group => b"VK_KHR_device_group",
     khr_display_swapchain => b"VK_KHR_display_swapchain",
     khr_sampler_mirror_clamp_to_edge => b"VK_KHR_sampler_mirror_clamp_to_edge",
     khr_maintenance1 => b"VK_KHR_maintenance1",
@@ -137,16 +139,116 @@ device_extensions! {
     khr_incremental_present => b"VK_KHR_incremental_present",
     khr_16bit_storage => b"VK_KHR_16bit_storage",
     khr_8bit_storage => b"VK_KHR_8bit_storage",
+    khr_shader_float16_int8 => b"VK_KHR_shader_float16_int8",
+    khr_shader_atomic_int64 => b"VK_KHR_shader_atomic_int64",
+    khr_variable_pointers => b"VK_KHR_variable_pointers",
     khr_storage_buffer_storage_class => b"VK_KHR_storage_buffer_storage_class",
     ext_debug_utils => b"VK_EXT_debug_utils",
     khr_multiview => b"VK_KHR_multiview",
     ext_full_screen_exclusive => b"VK_EXT_full_screen_exclusive",
+    ext_conservative_rasterization => b"VK_EXT_conservative_rasterization",
+    khr_pipeline_library => b"VK_KHR_pipeline_library",
+    ext_graphics_pipeline_library => b"VK_EXT_graphics_pipeline_library",
+    ext_vertex_attribute_divisor => b"VK_EXT_vertex_attribute_divisor",
+    khr_create_renderpass2 => b"VK_KHR_create_renderpass2",
+    khr_depth_stencil_resolve => b"VK_KHR_depth_stencil_resolve",
+    khr_driver_properties => b"VK_KHR_driver_properties",
+    khr_imageless_framebuffer => b"VK_KHR_imageless_framebuffer",
+    khr_fragment_shading_rate => b"VK_KHR_fragment_shading_rate",
+    ext_fragment_density_map => b"VK_EXT_fragment_density_map",
+    ext_multisampled_render_to_single_sampled => b"VK_EXT_multisampled_render_to_single_sampled",
+    ext_hdr_metadata => b"VK_EXT_hdr_metadata",
+    khr_present_id => b"VK_KHR_present_id",
+    khr_present_wait => b"VK_KHR_present_wait",
+    ext_swapchain_maintenance1 => b"VK_EXT_swapchain_maintenance1",
+    google_display_timing => b"VK_GOOGLE_display_timing",
+    ext_display_control => b"VK_EXT_display_control",
+    khr_timeline_semaphore => b"VK_KHR_timeline_semaphore",
+    ext_extended_dynamic_state => b"VK_EXT_extended_dynamic_state",
+    ext_tooling_info => b"VK_EXT_tooling_info",
+    ext_extended_dynamic_state2 => b"VK_EXT_extended_dynamic_state2",
+    ext_extended_dynamic_state3 => b"VK_EXT_extended_dynamic_state3",
+    ext_shader_object => b"VK_EXT_shader_object",
+    khr_pipeline_executable_properties => b"VK_KHR_pipeline_executable_properties",
+    ext_line_rasterization => b"VK_EXT_line_rasterization",
+    ext_provoking_vertex => b"VK_EXT_provoking_vertex",
+    ext_blend_operation_advanced => b"VK_EXT_blend_operation_advanced",
+    ext_sample_locations => b"VK_EXT_sample_locations",
+    ext_color_write_enable => b"VK_EXT_color_write_enable",
+    ext_depth_clip_enable => b"VK_EXT_depth_clip_enable",
+    ext_primitive_topology_list_restart => b"VK_EXT_primitive_topology_list_restart",
+    ext_robustness2 => b"VK_EXT_robustness2",
+    ext_image_robustness => b"VK_EXT_image_robustness",
+    ext_pipeline_robustness => b"VK_EXT_pipeline_robustness",
+    ext_pipeline_creation_cache_control => b"VK_EXT_pipeline_creation_cache_control",
+    ext_shader_module_identifier => b"VK_EXT_shader_module_identifier",
+    ext_memory_budget => b"VK_EXT_memory_budget",
+    ext_memory_priority => b"VK_EXT_memory_priority",
+    ext_pageable_device_local_memory => b"VK_EXT_pageable_device_local_memory",
+    ext_device_memory_report => b"VK_EXT_device_memory_report",
+    ext_device_fault => b"VK_EXT_device_fault",
+    ext_private_data => b"VK_EXT_private_data",
+    khr_shader_non_semantic_info => b"VK_KHR_shader_non_semantic_info",
     khr_external_memory => b"VK_KHR_external_memory",
     khr_external_memory_fd => b"VK_KHR_external_memory_fd",
+    khr_external_memory_win32 => b"VK_KHR_external_memory_win32",
+    khr_external_semaphore => b"VK_KHR_external_semaphore",
+    khr_external_semaphore_fd => b"VK_KHR_external_semaphore_fd",
+    khr_external_semaphore_win32 => b"VK_KHR_external_semaphore_win32",
+    khr_external_fence => b"VK_KHR_external_fence",
+    khr_external_fence_fd => b"VK_KHR_external_fence_fd",
+    khr_external_fence_win32 => b"VK_KHR_external_fence_win32",
     ext_external_memory_dmabuf => b"VK_EXT_external_memory_dma_buf",
+    ext_external_memory_host => b"VK_EXT_external_memory_host",
     khr_portability_subset => b"VK_KHR_portability_subset",
 }
 
+impl DeviceExtensions {
+    /// Returns the extensions whose functionality is part of the given version of Vulkan.
+    ///
+    /// A device that uses this version doesn't need to enable them, and their functions are
+    /// loaded from the core API instead. The features they add must still be enabled.
+    pub fn core_in(version: Version) -> DeviceExtensions {
+        let mut extensions = DeviceExtensions::none();
+
+        if version >= Version::V1_1 {
+            extensions.khr_16bit_storage = true;
+            extensions.khr_dedicated_allocation = true;
+            extensions.khr_device_group = true;
+            extensions.khr_external_fence = true;
+            extensions.khr_external_memory = true;
+            extensions.khr_external_semaphore = true;
+            extensions.khr_get_memory_requirements2 = true;
+            extensions.khr_maintenance1 = true;
+            extensions.khr_multiview = true;
+            extensions.khr_storage_buffer_storage_class = true;
+            extensions.khr_variable_pointers = true;
+        }
+
+        if version >= Version::V1_2 {
+            extensions.khr_8bit_storage = true;
+            extensions.khr_create_renderpass2 = true;
+            extensions.khr_depth_stencil_resolve = true;
+            extensions.khr_driver_properties = true;
+            extensions.khr_imageless_framebuffer = true;
+            extensions.khr_shader_atomic_int64 = true;
+            extensions.khr_shader_float16_int8 = true;
+            extensions.khr_timeline_semaphore = true;
+        }
+
+        if version >= Version::V1_3 {
+            extensions.ext_extended_dynamic_state = true;
+            extensions.ext_tooling_info = true;
+            extensions.ext_private_data = true;
+            extensions.ext_image_robustness = true;
+            extensions.ext_pipeline_creation_cache_control = true;
+            extensions.khr_shader_non_semantic_info = true;
+        }
+
+        extensions
+    }
+}
+
 /// This helper type can only be instantiated inside this module.
 /// See `*Extensions::_unbuildable`.
 #[doc(hidden)]
@@ -156,6 +258,8 @@ pub struct Unbuildable(());
 #[cfg(test)]
 mod tests {
     use crate::device::{DeviceExtensions, RawDeviceExtensions};
+    use crate::device::{Features, Vulkan11Features, Vulkan12Features, Vulkan13Features};
+    use crate::instance::Version;
 
     #[test]
     fn empty_extensions() {
@@ -163,6 +267,65 @@ mod tests {
         assert!(d.iter().next().is_none());
     }
 
+    #[test]
+    fn core_in() {
+        assert_eq!(
+            DeviceExtensions::core_in(Version::V1_0),
+            DeviceExtensions::none()
+        );
+
+        let v1_1 = DeviceExtensions::core_in(Version::V1_1);
+        let v1_2 = DeviceExtensions::core_in(Version::V1_2);
+        assert!(v1_1.khr_maintenance1 && !v1_1.khr_timeline_semaphore);
+        assert!(v1_2.khr_maintenance1 && v1_2.khr_timeline_semaphore);
+        assert_eq!(v1_2.intersection(&v1_1), v1_1);
+    }
+
+    #[test]
+    fn promoted_features_extensions_are_core() {
+        let v1_1 = Vulkan11Features::all().required_extensions();
+        let v1_2 = Vulkan12Features::all().required_extensions();
+        let v1_3 = Vulkan13Features::all().required_extensions();
+
+        assert!(v1_1.khr_multiview && v1_2.khr_timeline_semaphore && v1_3.ext_private_data);
+        assert_eq!(
+            v1_1.intersection(&DeviceExtensions::core_in(Vulkan11Features::version())),
+            v1_1
+        );
+        assert_eq!(
+            v1_2.intersection(&DeviceExtensions::core_in(Vulkan12Features::version())),
+            v1_2
+        );
+        assert_eq!(
+            v1_3.intersection(&DeviceExtensions::core_in(Vulkan13Features::version())),
+            v1_3
+        );
+        assert_eq!(
+            DeviceExtensions::core_in(Version::V1_1).intersection(&v1_2),
+            DeviceExtensions::none()
+        );
+    }
+
+    #[test]
+    fn promoted_features_roundtrip() {
+        let features = Vulkan12Features {
+            timeline_semaphore: true,
+            ..Vulkan12Features::none()
+        };
+        let all: Features = features.into();
+
+        assert!(all.timeline_semaphore && !all.shader_int8);
+        assert_eq!(all.vulkan12_features(), features);
+        assert_eq!(all.vulkan11_features(), Vulkan11Features::none());
+        assert_eq!(
+            features.required_extensions(),
+            DeviceExtensions {
+                khr_timeline_semaphore: true,
+                ..DeviceExtensions::none()
+            }
+        );
+    }
+
     #[test]
     fn required_if_supported_extensions() {
         assert_eq!(
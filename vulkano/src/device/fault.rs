@@ -0,0 +1,133 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use crate::vk;
+
+/// Information about the fault that caused a device to be lost, returned by
+/// `Device::fault_info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceFault {
+    /// Human-readable description of the fault.
+    pub description: String,
+
+    /// GPU virtual addresses involved in the fault.
+    pub addresses: Vec<DeviceFaultAddress>,
+
+    /// Vendor-specific details about the fault.
+    pub vendor_infos: Vec<DeviceFaultVendorInfo>,
+
+    /// Vendor-specific binary crash dump. Only queried if the `device_fault_vendor_binary`
+    /// feature is enabled, and `None` otherwise.
+    ///
+    /// The data starts with a header that identifies the vendor and the driver, and is meant to
+    /// be decoded by vendor tools.
+    pub vendor_binary: Option<Vec<u8>>,
+}
+
+/// GPU virtual address involved in a device fault.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceFaultAddress {
+    /// How the address was involved in the fault.
+    pub ty: DeviceFaultAddressType,
+
+    /// The address, which may have been rounded down to a multiple of `precision`.
+    pub address: u64,
+
+    /// The faulting access happened somewhere in `address..address + precision`. Always a power
+    /// of two.
+    pub precision: u64,
+}
+
+/// How an address was involved in a device fault.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DeviceFaultAddressType {
+    /// Currently unused by the implementations.
+    None,
+    /// An invalid read access.
+    ReadInvalid,
+    /// An invalid write access.
+    WriteInvalid,
+    /// An attempt to execute non-executable memory.
+    ExecuteInvalid,
+    /// An instruction pointer that is not related to the fault.
+    InstructionPointerUnknown,
+    /// The instruction pointer of an instruction that was invalid.
+    InstructionPointerInvalid,
+    /// The instruction pointer of the instruction that caused the fault.
+    InstructionPointerFault,
+}
+
+impl DeviceFaultAddressType {
+    #[inline]
+    fn from_vulkan(val: vk::DeviceFaultAddressTypeEXT) -> DeviceFaultAddressType {
+        match val {
+            vk::DEVICE_FAULT_ADDRESS_TYPE_READ_INVALID_EXT => DeviceFaultAddressType::ReadInvalid,
+            vk::DEVICE_FAULT_ADDRESS_TYPE_WRITE_INVALID_EXT => DeviceFaultAddressType::WriteInvalid,
+            vk::DEVICE_FAULT_ADDRESS_TYPE_EXECUTE_INVALID_EXT => {
+                DeviceFaultAddressType::ExecuteInvalid
+            }
+            vk::DEVICE_FAULT_ADDRESS_TYPE_INSTRUCTION_POINTER_UNKNOWN_EXT => {
+                DeviceFaultAddressType::InstructionPointerUnknown
+            }
+            vk::DEVICE_FAULT_ADDRESS_TYPE_INSTRUCTION_POINTER_INVALID_EXT => {
+                DeviceFaultAddressType::InstructionPointerInvalid
+            }
+            vk::DEVICE_FAULT_ADDRESS_TYPE_INSTRUCTION_POINTER_FAULT_EXT => {
+                DeviceFaultAddressType::InstructionPointerFault
+            }
+            _ => DeviceFaultAddressType::None,
+        }
+    }
+}
+
+impl From<&vk::DeviceFaultAddressInfoEXT> for DeviceFaultAddress {
+    #[inline]
+    fn from(info: &vk::DeviceFaultAddressInfoEXT) -> DeviceFaultAddress {
+        DeviceFaultAddress {
+            ty: DeviceFaultAddressType::from_vulkan(info.addressType),
+            address: info.reportedAddress,
+            precision: info.addressPrecision,
+        }
+    }
+}
+
+/// Vendor-specific information about a device fault.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceFaultVendorInfo {
+    /// Human-readable description of the information.
+    pub description: String,
+    /// Vendor-specific fault code.
+    pub code: u64,
+    /// Vendor-specific data associated with the fault.
+    pub data: u64,
+}
+
+impl From<&vk::DeviceFaultVendorInfoEXT> for DeviceFaultVendorInfo {
+    #[inline]
+    fn from(info: &vk::DeviceFaultVendorInfoEXT) -> DeviceFaultVendorInfo {
+        DeviceFaultVendorInfo {
+            description: description_to_string(&info.description),
+            code: info.vendorFaultCode,
+            data: info.vendorFaultData,
+        }
+    }
+}
+
+// Converts a null-terminated description returned by the implementation.
+#[inline]
+pub(crate) fn description_to_string(description: &[c_char]) -> String {
+    unsafe {
+        CStr::from_ptr(description.as_ptr())
+            .to_string_lossy()
+            .into_owned()
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::os::raw::c_void;
+use std::panic;
+
+use crate::vk;
+
+/// Kind of event reported to the callback of `Device::with_memory_report`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DeviceMemoryReportEvent {
+    /// Memory has been allocated by the implementation.
+    Allocate,
+    /// Memory has been freed by the implementation.
+    Free,
+    /// External memory has been imported.
+    Import,
+    /// Imported external memory has been released.
+    Unimport,
+    /// The implementation failed to allocate memory.
+    AllocationFailed,
+}
+
+/// Device memory event reported to the callback of `Device::with_memory_report`.
+///
+/// This includes the allocations of `DeviceMemory` objects, but also the memory that the
+/// implementation allocates internally for the other objects of the device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DeviceMemoryReport {
+    /// Kind of event.
+    pub event: DeviceMemoryReportEvent,
+
+    /// Identifier of the memory, unique for the lifetime of the device. The same identifier is
+    /// used for the allocation and the free of a piece of memory.
+    pub memory_object_id: u64,
+
+    /// Size of the memory in bytes. Only meaningful for `Allocate`, `Import` and
+    /// `AllocationFailed` events.
+    pub size: u64,
+
+    /// Type of the Vulkan object that the memory is associated with.
+    pub object_type: vk::ObjectType,
+
+    /// Raw handle of the Vulkan object that the memory is associated with, or 0.
+    pub object_handle: u64,
+
+    /// Index of the memory heap of the memory. Only meaningful for `Allocate`, `Import` and
+    /// `AllocationFailed` events.
+    pub heap_index: u32,
+}
+
+pub(crate) type MemoryReportCallback = Box<dyn Fn(&DeviceMemoryReport) + Send + Sync>;
+
+pub(crate) extern "system" fn memory_report_callback(
+    callback_data: *const vk::DeviceMemoryReportCallbackDataEXT,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let user_callback = &*(user_data as *const MemoryReportCallback);
+        let data = &*callback_data;
+
+        let event = match data.ty {
+            vk::DEVICE_MEMORY_REPORT_EVENT_TYPE_ALLOCATE_EXT => DeviceMemoryReportEvent::Allocate,
+            vk::DEVICE_MEMORY_REPORT_EVENT_TYPE_FREE_EXT => DeviceMemoryReportEvent::Free,
+            vk::DEVICE_MEMORY_REPORT_EVENT_TYPE_IMPORT_EXT => DeviceMemoryReportEvent::Import,
+            vk::DEVICE_MEMORY_REPORT_EVENT_TYPE_UNIMPORT_EXT => DeviceMemoryReportEvent::Unimport,
+            vk::DEVICE_MEMORY_REPORT_EVENT_TYPE_ALLOCATION_FAILED_EXT => {
+                DeviceMemoryReportEvent::AllocationFailed
+            }
+            _ => return,
+        };
+
+        let report = DeviceMemoryReport {
+            event,
+            memory_object_id: data.memoryObjectId,
+            size: data.size,
+            object_type: data.objectType,
+            object_handle: data.objectHandle,
+            heap_index: data.heapIndex,
+        };
+
+        // Unwinding through the implementation is undefined behavior.
+        let _ = panic::catch_unwind(panic::AssertUnwindSafe(move || {
+            user_callback(&report);
+        }));
+    }
+}
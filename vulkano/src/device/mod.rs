@@ -85,47 +85,109 @@
 //!
 //! The `Device::new` function returns the newly-created device, but also the list of queues.
 //!
+//! # Thread safety
+//!
+//! `Device` and `Queue` can be shared between threads and used concurrently. Each queue has its
+//! own lock, which is only held while submitting to it or waiting for it to become idle, so that
+//! submissions to different queues never wait for each other. Recording command buffers doesn't
+//! lock the queues: the standard command pools are looked up with a lock per queue family, and
+//! each of them uses one Vulkan pool per thread. The pools of semaphores and events and the count
+//! of memory allocations are lock-free.
+//!
+//! The only exception is `Device::wait`, which waits for all the queues at once. Vulkan requires
+//! that nothing is submitted to any queue of the device while it runs, which is why it is
+//! `unsafe`.
+//!
+//! # Device loss
+//!
+//! The device can be lost at any time, for example because of a driver crash, a command that
+//! took too long to execute and was killed by the operating system, or a hardware failure. This
+//! is reported by a `DeviceLost` error in the functions that execute or wait for commands, such
+//! as submitting a command buffer or waiting for a fence.
+//!
+//! If the `ext_device_fault` extension and the `device_fault` feature are enabled, you can call
+//! `Device::fault_info` to know why the device was lost.
+//!
+//! A lost device can't be recovered. In order to continue, you must drop the `Device` and every
+//! object created from it, which includes the queues, buffers, images, image views, samplers,
+//! memory pools, shader modules, pipelines, descriptor sets, render passes, framebuffers, command
+//! buffers, swapchains and synchronization primitives. Then create a new device and recreate your
+//! resources. The `Instance`, the surfaces and the physical devices remain valid, although you
+//! should enumerate the physical devices again because the one that was lost may have
+//! disappeared.
+//!
 //! # Extended example
 //!
 //! TODO: write
 
-use fnv::FnvHasher;
+use crossbeam_queue::SegQueue;
 use smallvec::SmallVec;
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::cmp;
 use std::error;
 use std::ffi::CStr;
 use std::fmt;
-use std::hash::BuildHasherDefault;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
+use std::os::raw::c_void;
 use std::ptr;
+use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
 use std::sync::Weak;
 
+use crate::alloc::AllocationCallbacks;
 use crate::command_buffer::pool::StandardCommandPool;
 use crate::descriptor::descriptor_set::StdDescriptorPool;
 use crate::instance::Instance;
 use crate::instance::PhysicalDevice;
+use crate::instance::PhysicalDeviceGroup;
 use crate::instance::QueueFamily;
+use crate::instance::Version;
+use crate::memory::pool::MemoryTypePolicy;
 use crate::memory::pool::StdMemoryPool;
+use crate::memory::MemoryHeapBudget;
+use crate::memory::PeerMemoryFeatures;
+use crate::sync::Fence;
+use crate::sync::FencePool;
+use crate::sync::FenceWaiter;
 
 use crate::check_errors;
 use crate::vk;
 use crate::Error;
 use crate::OomError;
+use crate::Success;
 use crate::SynchronizedVulkanObject;
 use crate::VulkanHandle;
 use crate::VulkanObject;
 
+pub use self::extension_structs::ExtensionFeatures;
+pub use self::extension_structs::ExtensionFeaturesChain;
+pub use self::extension_structs::ExtensionProperties;
 pub use self::extensions::DeviceExtensions;
 pub use self::extensions::RawDeviceExtensions;
+pub use self::fault::DeviceFault;
+pub use self::fault::DeviceFaultAddress;
+pub use self::fault::DeviceFaultAddressType;
+pub use self::fault::DeviceFaultVendorInfo;
+use self::memory_report::memory_report_callback;
+pub use self::memory_report::DeviceMemoryReport;
+pub use self::memory_report::DeviceMemoryReportEvent;
+use self::memory_report::MemoryReportCallback;
+pub use self::private_data::PrivateDataSlot;
 pub use crate::features::Features;
+use crate::features::FeaturesFfi;
+pub use crate::features::Vulkan11Features;
+pub use crate::features::Vulkan12Features;
+pub use crate::features::Vulkan13Features;
+pub(crate) mod extension_structs;
 mod extensions;
+mod fault;
+mod memory_report;
+mod private_data;
 
 use crate::format::Format;
 use crate::image::ImageCreateFlags;
@@ -138,19 +200,27 @@ use crate::image::ImageUsage;
 pub struct Device {
     instance: Arc<Instance>,
     physical_device: usize,
+    // Indices of the physical devices of the device group, including `physical_device`.
+    device_group: SmallVec<[usize; 4]>,
     device: vk::Device,
     vk: vk::DevicePointers,
     standard_pool: Mutex<Weak<StdMemoryPool>>,
+    memory_type_policy: Mutex<MemoryTypePolicy>,
     standard_descriptor_pool: Mutex<Weak<StdDescriptorPool>>,
-    standard_command_pools:
-        Mutex<HashMap<u32, Weak<StandardCommandPool>, BuildHasherDefault<FnvHasher>>>,
+    // Indexed by queue family.
+    standard_command_pools: Vec<Mutex<Weak<StandardCommandPool>>>,
+    api_version: Version,
     features: Features,
     extensions: DeviceExtensions,
     active_queue_families: SmallVec<[u32; 8]>,
-    allocation_count: Mutex<u32>,
-    fence_pool: Mutex<Vec<vk::Fence>>,
-    semaphore_pool: Mutex<Vec<vk::Semaphore>>,
-    event_pool: Mutex<Vec<vk::Event>>,
+    allocation_count: AtomicU32,
+    fence_pool: Mutex<FencePool>,
+    fence_waiter: Mutex<FenceWaiter>,
+    semaphore_pool: SegQueue<vk::Semaphore>,
+    event_pool: SegQueue<vk::Event>,
+    alloc: Option<AllocationCallbacks>,
+    // Kept alive for the lifetime of the device, since the implementation holds a pointer to it.
+    memory_report: Option<Box<MemoryReportCallback>>,
 }
 
 // The `StandardCommandPool` type doesn't implement Send/Sync, so we have to manually reimplement
@@ -158,6 +228,26 @@ pub struct Device {
 unsafe impl Send for Device {}
 unsafe impl Sync for Device {}
 
+// Parameters of `Device::new_inner` that only some of the constructors set.
+struct DeviceCreateOptions<'a> {
+    alloc: Option<AllocationCallbacks>,
+    memory_report: Option<MemoryReportCallback>,
+    extension_features: ExtensionFeaturesChain,
+    device_group: &'a [PhysicalDevice<'a>],
+}
+
+impl<'a> DeviceCreateOptions<'a> {
+    #[inline]
+    fn new(alloc: Option<AllocationCallbacks>) -> DeviceCreateOptions<'a> {
+        DeviceCreateOptions {
+            alloc,
+            memory_report: None,
+            extension_features: ExtensionFeaturesChain::new(),
+            device_group: &[],
+        }
+    }
+}
+
 impl Device {
     /// Builds a new Vulkan device for the given physical device.
     ///
@@ -171,7 +261,9 @@ impl Device {
     ///   the family whose queue belongs to and a priority between 0.0 and 1.0 to assign to it.
     ///   A queue with a higher value indicates that the commands will execute faster than on a
     ///   queue with a lower value. Note however that no guarantee can be made on the way the
-    ///   priority value is handled by the implementation.
+    ///   priority value is handled by the implementation. The elements can be either
+    ///   `(QueueFamily, f32)` tuples or `QueueCreateInfo`s, which can also request protected
+    ///   queues.
     ///
     /// # Panic
     ///
@@ -179,6 +271,7 @@ impl Device {
     ///
     // TODO: return Arc<Queue> and handle synchronization in the Queue
     // TODO: should take the PhysicalDevice by value
+    #[inline]
     pub fn new<'a, I, Ext>(
         phys: PhysicalDevice,
         requested_features: &Features,
@@ -186,9 +279,191 @@ impl Device {
         queue_families: I,
     ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
     where
-        I: IntoIterator<Item = (QueueFamily<'a>, f32)>,
+        I: IntoIterator,
+        I::Item: Into<QueueCreateInfo<'a>>,
+        Ext: Into<RawDeviceExtensions>,
+    {
+        let alloc = phys.instance().alloc().cloned();
+        Device::new_inner(
+            phys,
+            requested_features,
+            extensions.into(),
+            queue_families,
+            DeviceCreateOptions::new(alloc),
+        )
+    }
+
+    /// Same as `new`, but provides an allocator that will be used by the Vulkan library whenever
+    /// it needs to allocate memory on the host for the device or for the objects that belong to
+    /// it, instead of the allocator of the instance.
+    #[inline]
+    pub fn with_alloc<'a, I, Ext>(
+        phys: PhysicalDevice,
+        requested_features: &Features,
+        extensions: Ext,
+        queue_families: I,
+        alloc: AllocationCallbacks,
+    ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+    where
+        I: IntoIterator,
+        I::Item: Into<QueueCreateInfo<'a>>,
+        Ext: Into<RawDeviceExtensions>,
+    {
+        Device::new_inner(
+            phys,
+            requested_features,
+            extensions.into(),
+            queue_families,
+            DeviceCreateOptions::new(Some(alloc)),
+        )
+    }
+
+    /// Same as `new`, but `callback` is called every time the implementation allocates, frees,
+    /// imports or releases device memory, including the memory that it allocates internally for
+    /// the objects of the device.
+    ///
+    /// The callback can be called from any thread, and must not call any Vulkan function.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_device_memory_report` extension or the `device_memory_report`
+    ///   feature isn't requested.
+    ///
+    pub fn with_memory_report<'a, I, Ext, F>(
+        phys: PhysicalDevice,
+        requested_features: &Features,
+        extensions: Ext,
+        queue_families: I,
+        callback: F,
+    ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+    where
+        I: IntoIterator,
+        I::Item: Into<QueueCreateInfo<'a>>,
         Ext: Into<RawDeviceExtensions>,
+        F: Fn(&DeviceMemoryReport) + Send + Sync + 'static,
+    {
+        let alloc = phys.instance().alloc().cloned();
+        Device::new_inner(
+            phys,
+            requested_features,
+            extensions.into(),
+            queue_families,
+            DeviceCreateOptions {
+                memory_report: Some(Box::new(callback)),
+                ..DeviceCreateOptions::new(alloc)
+            },
+        )
+    }
+
+    /// Same as `new`, but also enables the features of `extension_features`, which are
+    /// structures of extensions that `Features` doesn't cover.
+    ///
+    /// The extensions that the structures belong to must be enabled in `extensions`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the structures of `extension_features` is already handled by
+    ///   `Features`.
+    ///
+    pub fn with_extension_features<'a, I, Ext>(
+        phys: PhysicalDevice,
+        requested_features: &Features,
+        extensions: Ext,
+        queue_families: I,
+        extension_features: ExtensionFeaturesChain,
+    ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+    where
+        I: IntoIterator,
+        I::Item: Into<QueueCreateInfo<'a>>,
+        Ext: Into<RawDeviceExtensions>,
+    {
+        let alloc = phys.instance().alloc().cloned();
+        Device::new_inner(
+            phys,
+            requested_features,
+            extensions.into(),
+            queue_families,
+            DeviceCreateOptions {
+                extension_features,
+                ..DeviceCreateOptions::new(alloc)
+            },
+        )
+    }
+
+    /// Same as `new`, but the device spans all the physical devices of `group`.
+    ///
+    /// The queue families must belong to the first physical device of the group, which is the
+    /// one returned by `physical_device()`. Device masks, for example in
+    /// `DeviceMemoryBuilder::device_mask` or `AutoCommandBufferBuilder::set_device_mask`, refer
+    /// to the physical devices by their index in the group.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_device_group_creation` instance extension isn't loaded.
+    ///
+    pub fn with_device_group<'a, I, Ext>(
+        group: &PhysicalDeviceGroup,
+        requested_features: &Features,
+        extensions: Ext,
+        queue_families: I,
+    ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+    where
+        I: IntoIterator,
+        I::Item: Into<QueueCreateInfo<'a>>,
+        Ext: Into<RawDeviceExtensions>,
+    {
+        let phys = group.physical_devices()[0];
+        assert!(
+            phys.instance()
+                .loaded_extensions_or_core()
+                .khr_device_group_creation,
+            "the `khr_device_group_creation` extension must be loaded"
+        );
+
+        let alloc = phys.instance().alloc().cloned();
+        Device::new_inner(
+            phys,
+            requested_features,
+            extensions.into(),
+            queue_families,
+            DeviceCreateOptions {
+                device_group: group.physical_devices(),
+                ..DeviceCreateOptions::new(alloc)
+            },
+        )
+    }
+
+    fn new_inner<'a, I>(
+        phys: PhysicalDevice,
+        requested_features: &Features,
+        extensions: RawDeviceExtensions,
+        queue_families: I,
+        options: DeviceCreateOptions,
+    ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+    where
+        I: IntoIterator,
+        I::Item: Into<QueueCreateInfo<'a>>,
     {
+        let DeviceCreateOptions {
+            alloc,
+            memory_report,
+            mut extension_features,
+            device_group,
+        } = options;
+
+        if memory_report.is_some() {
+            assert!(
+                DeviceExtensions::from(&extensions).ext_device_memory_report
+                    && requested_features.device_memory_report,
+                "the `ext_device_memory_report` extension and the `device_memory_report` feature \
+                 must be enabled"
+            );
+        }
+
+        // Note that we need to double-box the callback, because a `*const Fn()` is a fat pointer
+        // that can't be cast to a `*const c_void`.
+        let memory_report = memory_report.map(Box::new);
+
         let queue_families = queue_families.into_iter();
 
         if !phys.supported_features().superset_of(&requested_features) {
@@ -197,8 +472,9 @@ impl Device {
 
         let vk_i = phys.instance().pointers();
 
-        // this variable will contain the queue family ID and queue ID of each requested queue
-        let mut output_queues: SmallVec<[(u32, u32); 8]> = SmallVec::new();
+        // this variable will contain the queue family ID, queue ID, priority and protected flag of
+        // each requested queue
+        let mut output_queues: SmallVec<[(u32, u32, f32, bool); 8]> = SmallVec::new();
 
         // Device layers were deprecated in Vulkan 1.0.13, and device layer requests should be
         // ignored by the driver. For backwards compatibility, the spec recommends passing the
@@ -215,7 +491,6 @@ impl Device {
             .map(|layer| layer.as_ptr())
             .collect::<SmallVec<[_; 16]>>();
 
-        let extensions = extensions.into();
         let extensions_list = extensions
             .iter()
             .map(|extension| extension.as_ptr())
@@ -228,11 +503,18 @@ impl Device {
 
         // device creation
         let device = unsafe {
-            // each element of `queues` is a `(queue_family, priorities)`
-            // each queue family must only have one entry in `queues`
-            let mut queues: Vec<(u32, Vec<f32>)> = Vec::with_capacity(phys.queue_families().len());
+            // each element of `queues` is a `(queue_family, protected, priorities)`
+            // each queue family must only have one entry in `queues` per value of `protected`
+            let mut queues: Vec<(u32, bool, Vec<f32>)> =
+                Vec::with_capacity(phys.queue_families().len());
+
+            for info in queue_families {
+                let QueueCreateInfo {
+                    family: queue_family,
+                    priority,
+                    protected,
+                } = info.into();
 
-            for (queue_family, priority) in queue_families {
                 // checking the parameters
                 assert_eq!(
                     queue_family.physical_device().internal_object(),
@@ -241,33 +523,57 @@ impl Device {
                 if priority < 0.0 || priority > 1.0 {
                     return Err(DeviceCreationError::PriorityOutOfRange);
                 }
+                if protected {
+                    if !requested_features.protected_memory {
+                        return Err(DeviceCreationError::ProtectedMemoryNotEnabled);
+                    }
+                    if !queue_family.supports_protected() {
+                        return Err(DeviceCreationError::ProtectedQueueNotSupported);
+                    }
+                }
 
                 // adding to `queues` and `output_queues`
-                if let Some(q) = queues.iter_mut().find(|q| q.0 == queue_family.id()) {
-                    output_queues.push((queue_family.id(), q.1.len() as u32));
-                    q.1.push(priority);
-                    if q.1.len() > queue_family.queues_count() {
-                        return Err(DeviceCreationError::TooManyQueuesForFamily);
+                let index = match queues
+                    .iter()
+                    .position(|q| q.0 == queue_family.id() && q.1 == protected)
+                {
+                    Some(index) => index,
+                    None => {
+                        queues.push((queue_family.id(), protected, Vec::new()));
+                        queues.len() - 1
                     }
-                    continue;
+                };
+                let id = queues[index].2.len() as u32;
+                queues[index].2.push(priority);
+                output_queues.push((queue_family.id(), id, priority, protected));
+
+                let family_count: usize = queues
+                    .iter()
+                    .filter(|q| q.0 == queue_family.id())
+                    .map(|q| q.2.len())
+                    .sum();
+                if family_count > queue_family.queues_count() {
+                    return Err(DeviceCreationError::TooManyQueuesForFamily);
                 }
-                queues.push((queue_family.id(), vec![priority]));
-                output_queues.push((queue_family.id(), 0));
             }
 
             // turning `queues` into an array of `vkDeviceQueueCreateInfo` suitable for Vulkan
             let queues = queues
                 .iter()
-                .map(|&(queue_id, ref priorities)| {
-                    vk::DeviceQueueCreateInfo {
+                .map(
+                    |&(queue_id, protected, ref priorities)| vk::DeviceQueueCreateInfo {
                         sType: vk::STRUCTURE_TYPE_DEVICE_QUEUE_CREATE_INFO,
                         pNext: ptr::null(),
-                        flags: 0, // reserved
+                        flags: if protected {
+                            vk::DEVICE_QUEUE_CREATE_PROTECTED_BIT
+                        } else {
+                            0
+                        },
                         queueFamilyIndex: queue_id,
                         queueCount: priorities.len() as u32,
                         pQueuePriorities: priorities.as_ptr(),
-                    }
-                })
+                    },
+                )
                 .collect::<SmallVec<[_; 16]>>();
 
             // TODO: The plan regarding `robustBufferAccess` is to check the shaders' code to see
@@ -287,11 +593,51 @@ impl Device {
             //       Note that if we ever remove this, don't forget to adjust the change in
             //       `Device`'s construction below.
 
-            let features = requested_features.into_vulkan_features_v2();
+            let mut features = requested_features.into_vulkan_features_v2();
+            for ty in extension_features.structure_types() {
+                assert!(
+                    !features.contains(ty),
+                    "an extension feature structure is already handled by `Features`"
+                );
+            }
+            let mut next =
+                extension_features.link(FeaturesFfi::mut_base_ptr(&mut features)) as *const c_void;
+
+            let device_group_handles = device_group
+                .iter()
+                .map(|p| p.internal_object())
+                .collect::<SmallVec<[_; 4]>>();
+            let device_group_info = if !device_group.is_empty() {
+                Some(vk::DeviceGroupDeviceCreateInfo {
+                    sType: vk::STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO,
+                    pNext: next,
+                    physicalDeviceCount: device_group_handles.len() as u32,
+                    pPhysicalDevices: device_group_handles.as_ptr(),
+                })
+            } else {
+                None
+            };
+            if let Some(ref info) = device_group_info {
+                next = info as *const _ as *const _;
+            }
+
+            let memory_report_info =
+                memory_report
+                    .as_ref()
+                    .map(|callback| vk::DeviceDeviceMemoryReportCreateInfoEXT {
+                        sType: vk::STRUCTURE_TYPE_DEVICE_DEVICE_MEMORY_REPORT_CREATE_INFO_EXT,
+                        pNext: next,
+                        flags: 0, // reserved
+                        pfnUserCallback: memory_report_callback,
+                        pUserData: &**callback as *const MemoryReportCallback as *mut c_void,
+                    });
+            if let Some(ref info) = memory_report_info {
+                next = info as *const _ as *const _;
+            }
 
             let infos = vk::DeviceCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DEVICE_CREATE_INFO,
-                pNext: features.base_ptr() as *const _,
+                pNext: next,
                 flags: 0, // reserved
                 queueCreateInfoCount: queues.len() as u32,
                 pQueueCreateInfos: queues.as_ptr(),
@@ -306,7 +652,7 @@ impl Device {
             check_errors(vk_i.CreateDevice(
                 phys.internal_object(),
                 &infos,
-                ptr::null(),
+                AllocationCallbacks::as_ptr(alloc.as_ref()),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -318,7 +664,7 @@ impl Device {
         });
 
         let mut active_queue_families: SmallVec<[u32; 8]> = SmallVec::new();
-        for (queue_family, _) in output_queues.iter() {
+        for (queue_family, _, _, _) in output_queues.iter() {
             if let None = active_queue_families
                 .iter()
                 .find(|&&qf| qf == *queue_family)
@@ -330,11 +676,21 @@ impl Device {
         let device = Arc::new(Device {
             instance: phys.instance().clone(),
             physical_device: phys.index(),
+            device_group: if device_group.is_empty() {
+                SmallVec::from_slice(&[phys.index()])
+            } else {
+                device_group.iter().map(|p| p.index()).collect()
+            },
             device: device,
             vk: vk,
             standard_pool: Mutex::new(Weak::new()),
+            memory_type_policy: Mutex::new(MemoryTypePolicy::default()),
             standard_descriptor_pool: Mutex::new(Weak::new()),
-            standard_command_pools: Mutex::new(Default::default()),
+            standard_command_pools: phys
+                .queue_families()
+                .map(|_| Mutex::new(Weak::new()))
+                .collect(),
+            api_version: cmp::min(phys.api_version(), phys.instance().api_version()),
             features: Features {
                 // Always enabled ; see above
                 robust_buffer_access: true,
@@ -342,10 +698,13 @@ impl Device {
             },
             extensions: (&extensions).into(),
             active_queue_families,
-            allocation_count: Mutex::new(0),
-            fence_pool: Mutex::new(Vec::new()),
-            semaphore_pool: Mutex::new(Vec::new()),
-            event_pool: Mutex::new(Vec::new()),
+            allocation_count: AtomicU32::new(0),
+            fence_pool: Mutex::new(FencePool::default()),
+            fence_waiter: Mutex::new(FenceWaiter::default()),
+            semaphore_pool: SegQueue::new(),
+            event_pool: SegQueue::new(),
+            alloc,
+            memory_report,
         });
 
         // Iterator for the produced queues.
@@ -392,6 +751,64 @@ impl Device {
         PhysicalDevice::from_index(&self.instance, self.physical_device).unwrap()
     }
 
+    /// Returns the physical devices that the device spans, in the order of their index in device
+    /// masks.
+    ///
+    /// Unless the device was created with `with_device_group`, this only contains the physical
+    /// device returned by `physical_device()`.
+    #[inline]
+    pub fn device_group(&self) -> SmallVec<[PhysicalDevice<'_>; 4]> {
+        self.device_group
+            .iter()
+            .map(|&index| PhysicalDevice::from_index(&self.instance, index).unwrap())
+            .collect()
+    }
+
+    /// Returns true if `mask` is a valid device mask for this device, in other words if it isn't
+    /// 0 and only contains physical devices of the device group.
+    #[inline]
+    pub fn is_valid_device_mask(&self, mask: u32) -> bool {
+        let num = self.device_group.len() as u32;
+        mask != 0 && (num >= 32 || mask >> num == 0)
+    }
+
+    /// Returns how the physical device `local_device_index` of the device group can access
+    /// memory of the heap `heap_index` that is allocated on the physical device
+    /// `remote_device_index`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_device_group` extension isn't enabled on the device.
+    /// - Panics if one of the indices is out of range, or if the two device indices are equal.
+    ///
+    pub fn peer_memory_features(
+        &self,
+        heap_index: u32,
+        local_device_index: u32,
+        remote_device_index: u32,
+    ) -> PeerMemoryFeatures {
+        assert!(
+            self.loaded_extensions_or_core().khr_device_group,
+            "the `khr_device_group` extension must be enabled"
+        );
+        assert!(heap_index < self.physical_device().memory_heaps().len() as u32);
+        assert!((local_device_index as usize) < self.device_group.len());
+        assert!((remote_device_index as usize) < self.device_group.len());
+        assert_ne!(local_device_index, remote_device_index);
+
+        unsafe {
+            let mut output = 0;
+            self.vk.GetDeviceGroupPeerMemoryFeaturesKHR(
+                self.device,
+                heap_index,
+                local_device_index,
+                remote_device_index,
+                &mut output,
+            );
+            PeerMemoryFeatures::from_bits(output)
+        }
+    }
+
     /// Returns an iterator to the list of queues families that this device uses.
     ///
     /// > **Note**: Will return `-> impl ExactSizeIterator<Item = QueueFamily>` in the future.
@@ -414,12 +831,54 @@ impl Device {
         &self.features
     }
 
+    /// Returns the host allocation callbacks used for the device and its objects, if any.
+    #[inline]
+    pub fn alloc(&self) -> Option<&AllocationCallbacks> {
+        self.alloc.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn alloc_ptr(&self) -> *const vk::AllocationCallbacks {
+        AllocationCallbacks::as_ptr(self.alloc.as_ref())
+    }
+
     /// Returns the list of extensions that have been loaded.
     #[inline]
     pub fn loaded_extensions(&self) -> &DeviceExtensions {
         &self.extensions
     }
 
+    /// Returns the version of Vulkan used by the device.
+    ///
+    /// This is the lower of the version supported by the physical device and of the version
+    /// used by the instance.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
+    /// Returns the extensions that have been loaded, plus the ones whose functionality is part
+    /// of the version of Vulkan used by the device.
+    ///
+    /// The functions of the latter are loaded from the core API. For example on a Vulkan 1.2
+    /// device, timeline semaphores can be used without enabling `khr_timeline_semaphore`, as
+    /// long as the `timeline_semaphore` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let device: std::sync::Arc<vulkano::device::Device> = return;
+    /// let extensions = device.loaded_extensions_or_core();
+    /// if extensions.khr_timeline_semaphore && !device.loaded_extensions().khr_timeline_semaphore {
+    ///     println!("Timeline semaphores are provided by core Vulkan");
+    /// }
+    /// ```
+    #[inline]
+    pub fn loaded_extensions_or_core(&self) -> DeviceExtensions {
+        self.extensions
+            .union(&DeviceExtensions::core_in(self.api_version))
+    }
+
     /// Returns the standard memory pool used by default if you don't provide any other pool.
     pub fn standard_pool(me: &Arc<Self>) -> Arc<StdMemoryPool> {
         let mut pool = me.standard_pool.lock().unwrap();
@@ -434,6 +893,20 @@ impl Device {
         new_pool
     }
 
+    /// Returns the policy that the buffers and images use to choose their memory type.
+    #[inline]
+    pub fn memory_type_policy(&self) -> MemoryTypePolicy {
+        *self.memory_type_policy.lock().unwrap()
+    }
+
+    /// Changes the policy that the buffers and images use to choose their memory type.
+    ///
+    /// Only the resources that are created afterwards are affected.
+    #[inline]
+    pub fn set_memory_type_policy(&self, policy: MemoryTypePolicy) {
+        *self.memory_type_policy.lock().unwrap() = policy;
+    }
+
     /// Returns the standard descriptor pool used by default if you don't provide any other pool.
     pub fn standard_descriptor_pool(me: &Arc<Self>) -> Arc<StdDescriptorPool> {
         let mut pool = me.standard_descriptor_pool.lock().unwrap();
@@ -456,22 +929,182 @@ impl Device {
     /// - Panics if the device and the queue family don't belong to the same physical device.
     ///
     pub fn standard_command_pool(me: &Arc<Self>, queue: QueueFamily) -> Arc<StandardCommandPool> {
-        let mut standard_command_pools = me.standard_command_pools.lock().unwrap();
+        assert_eq!(
+            queue.physical_device().internal_object(),
+            me.physical_device().internal_object()
+        );
 
-        match standard_command_pools.entry(queue.id()) {
-            Entry::Occupied(mut entry) => {
-                if let Some(pool) = entry.get().upgrade() {
-                    return pool;
+        let mut pool = me.standard_command_pools[queue.id() as usize]
+            .lock()
+            .unwrap();
+
+        if let Some(p) = pool.upgrade() {
+            return p;
+        }
+
+        // The weak pointer is empty, so we create the pool.
+        let new_pool = Arc::new(StandardCommandPool::new(me.clone(), queue));
+        *pool = Arc::downgrade(&new_pool);
+        new_pool
+    }
+
+    /// Returns a fence that is signaled the next time a display is plugged into or unplugged from
+    /// the physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_display_control` extension is not enabled on the device.
+    ///
+    pub fn register_display_hotplug_event(me: &Arc<Self>) -> Result<Fence, OomError> {
+        assert!(
+            me.loaded_extensions().ext_display_control,
+            "the ext_display_control extension must be enabled"
+        );
+
+        unsafe {
+            let infos = vk::DeviceEventInfoEXT {
+                sType: vk::STRUCTURE_TYPE_DEVICE_EVENT_INFO_EXT,
+                pNext: ptr::null(),
+                deviceEvent: vk::DEVICE_EVENT_TYPE_DISPLAY_HOTPLUG_EXT,
+            };
+
+            let mut output = MaybeUninit::uninit();
+            check_errors(me.vk.RegisterDeviceEventEXT(
+                me.device,
+                &infos,
+                me.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            Ok(Fence::from_raw(me.clone(), output.assume_init()))
+        }
+    }
+
+    /// Returns the current memory usage and budget of each heap of the physical device, indexed by
+    /// heap id.
+    ///
+    /// The budget is only an estimate, which changes with the memory usage of the other
+    /// applications. Compare it with `StdMemoryPool::statistics` to know how much of
+    /// the usage comes from vulkano allocations.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_memory_budget` extension is not enabled on the device.
+    ///
+    pub fn memory_budget(&self) -> Vec<MemoryHeapBudget> {
+        assert!(
+            self.loaded_extensions().ext_memory_budget,
+            "the ext_memory_budget extension must be enabled"
+        );
+
+        unsafe {
+            let mut budget = vk::PhysicalDeviceMemoryBudgetPropertiesEXT {
+                sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_BUDGET_PROPERTIES_EXT,
+                pNext: ptr::null_mut(),
+                heapBudget: [0; vk::MAX_MEMORY_HEAPS as usize],
+                heapUsage: [0; vk::MAX_MEMORY_HEAPS as usize],
+            };
+            let mut output = vk::PhysicalDeviceMemoryProperties2KHR {
+                sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_PROPERTIES_2_KHR,
+                pNext: &mut budget as *mut _ as *const _,
+                memoryProperties: mem::zeroed(),
+            };
+
+            let vk_i = self.instance().pointers();
+            vk_i.GetPhysicalDeviceMemoryProperties2KHR(
+                self.physical_device().internal_object(),
+                &mut output,
+            );
+
+            (0..output.memoryProperties.memoryHeapCount as usize)
+                .map(|heap| MemoryHeapBudget {
+                    usage: budget.heapUsage[heap] as usize,
+                    budget: budget.heapBudget[heap] as usize,
+                })
+                .collect()
+        }
+    }
+
+    /// Queries information about the fault that caused the device to be lost.
+    ///
+    /// This is meant to be called after an operation returned a `DeviceLost` error. See the
+    /// module documentation for how to recover from a device loss. If the device isn't lost, the
+    /// returned information is empty.
+    ///
+    /// The vendor binary is only queried if the `device_fault_vendor_binary` feature is enabled.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_device_fault` extension or the `device_fault` feature is not enabled
+    ///   on the device.
+    ///
+    pub fn fault_info(&self) -> Result<DeviceFault, OomError> {
+        assert!(
+            self.loaded_extensions().ext_device_fault && self.enabled_features().device_fault,
+            "the `ext_device_fault` extension and the `device_fault` feature must be enabled"
+        );
+
+        let vendor_binary = self.enabled_features().device_fault_vendor_binary;
+
+        unsafe {
+            loop {
+                let mut counts = vk::DeviceFaultCountsEXT {
+                    sType: vk::STRUCTURE_TYPE_DEVICE_FAULT_COUNTS_EXT,
+                    pNext: ptr::null_mut(),
+                    addressInfoCount: 0,
+                    vendorInfoCount: 0,
+                    vendorBinarySize: 0,
+                };
+                check_errors(self.vk.GetDeviceFaultInfoEXT(
+                    self.device,
+                    &mut counts,
+                    ptr::null_mut(),
+                ))?;
+                if !vendor_binary {
+                    counts.vendorBinarySize = 0;
                 }
 
-                let new_pool = Arc::new(StandardCommandPool::new(me.clone(), queue));
-                *entry.get_mut() = Arc::downgrade(&new_pool);
-                new_pool
-            }
-            Entry::Vacant(entry) => {
-                let new_pool = Arc::new(StandardCommandPool::new(me.clone(), queue));
-                entry.insert(Arc::downgrade(&new_pool));
-                new_pool
+                let mut addresses: Vec<vk::DeviceFaultAddressInfoEXT> =
+                    Vec::with_capacity(counts.addressInfoCount as usize);
+                let mut vendor_infos: Vec<vk::DeviceFaultVendorInfoEXT> =
+                    Vec::with_capacity(counts.vendorInfoCount as usize);
+                let mut binary: Vec<u8> = Vec::with_capacity(counts.vendorBinarySize as usize);
+
+                let mut info = vk::DeviceFaultInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_DEVICE_FAULT_INFO_EXT,
+                    pNext: ptr::null_mut(),
+                    description: [0; vk::MAX_DESCRIPTION_SIZE as usize],
+                    pAddressInfos: addresses.as_mut_ptr(),
+                    pVendorInfos: vendor_infos.as_mut_ptr(),
+                    pVendorBinaryData: if vendor_binary {
+                        binary.as_mut_ptr() as *mut c_void
+                    } else {
+                        ptr::null_mut()
+                    },
+                };
+                let result = check_errors(self.vk.GetDeviceFaultInfoEXT(
+                    self.device,
+                    &mut counts,
+                    &mut info,
+                ))?;
+
+                // The number of entries may have grown between the two calls.
+                if let Success::Incomplete = result {
+                    continue;
+                }
+
+                addresses.set_len(counts.addressInfoCount as usize);
+                vendor_infos.set_len(counts.vendorInfoCount as usize);
+                binary.set_len(counts.vendorBinarySize as usize);
+
+                return Ok(DeviceFault {
+                    description: fault::description_to_string(&info.description),
+                    addresses: addresses.iter().map(DeviceFaultAddress::from).collect(),
+                    vendor_infos: vendor_infos
+                        .iter()
+                        .map(DeviceFaultVendorInfo::from)
+                        .collect(),
+                    vendor_binary: if vendor_binary { Some(binary) } else { None },
+                });
             }
         }
     }
@@ -483,26 +1116,33 @@ impl Device {
     /// https://www.khronos.org/registry/vulkan/specs/1.0/html/vkspec.html#vkAllocateMemory
     ///
     /// Warning: You should never modify this value, except in `device_memory` module
-    pub(crate) fn allocation_count(&self) -> &Mutex<u32> {
+    pub(crate) fn allocation_count(&self) -> &AtomicU32 {
         &self.allocation_count
     }
 
-    pub(crate) fn fence_pool(&self) -> &Mutex<Vec<vk::Fence>> {
+    pub(crate) fn fence_pool(&self) -> &Mutex<FencePool> {
         &self.fence_pool
     }
 
-    pub(crate) fn semaphore_pool(&self) -> &Mutex<Vec<vk::Semaphore>> {
+    pub(crate) fn fence_waiter(&self) -> &Mutex<FenceWaiter> {
+        &self.fence_waiter
+    }
+
+    pub(crate) fn semaphore_pool(&self) -> &SegQueue<vk::Semaphore> {
         &self.semaphore_pool
     }
 
-    pub(crate) fn event_pool(&self) -> &Mutex<Vec<vk::Event>> {
+    pub(crate) fn event_pool(&self) -> &SegQueue<vk::Event> {
         &self.event_pool
     }
 
     /// Assigns a human-readable name to `object` for debugging purposes.
     ///
+    /// The name appears in the messages of the validation layers and in tools such as RenderDoc.
+    ///
     /// # Panics
     /// * If `object` is not owned by this device.
+    /// * If the `ext_debug_utils` extension isn't loaded on the instance.
     pub fn set_object_name<T: VulkanObject + DeviceOwned>(
         &self,
         object: &T,
@@ -516,12 +1156,20 @@ impl Device {
     ///
     /// # Safety
     /// `object` must be a Vulkan handle owned by this device, and its type must be accurately described by `ty`.
+    ///
+    /// # Panics
+    /// * If the `ext_debug_utils` extension isn't loaded on the instance.
     pub unsafe fn set_object_name_raw(
         &self,
         ty: vk::ObjectType,
         object: u64,
         name: &CStr,
     ) -> Result<(), OomError> {
+        assert!(
+            self.instance().loaded_extensions().ext_debug_utils,
+            "the `ext_debug_utils` extension must be loaded"
+        );
+
         let info = vk::DebugUtilsObjectNameInfoEXT {
             sType: vk::STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_NAME_INFO_EXT,
             pNext: ptr::null(),
@@ -594,15 +1242,18 @@ impl Drop for Device {
     fn drop(&mut self) {
         unsafe {
             for &raw_fence in self.fence_pool.lock().unwrap().iter() {
-                self.vk.DestroyFence(self.device, raw_fence, ptr::null());
+                self.vk
+                    .DestroyFence(self.device, raw_fence, self.alloc_ptr());
             }
-            for &raw_sem in self.semaphore_pool.lock().unwrap().iter() {
-                self.vk.DestroySemaphore(self.device, raw_sem, ptr::null());
+            while let Some(raw_sem) = self.semaphore_pool.pop() {
+                self.vk
+                    .DestroySemaphore(self.device, raw_sem, self.alloc_ptr());
             }
-            for &raw_event in self.event_pool.lock().unwrap().iter() {
-                self.vk.DestroyEvent(self.device, raw_event, ptr::null());
+            while let Some(raw_event) = self.event_pool.pop() {
+                self.vk
+                    .DestroyEvent(self.device, raw_event, self.alloc_ptr());
             }
-            self.vk.DestroyDevice(self.device, ptr::null());
+            self.vk.DestroyDevice(self.device, self.alloc_ptr());
         }
     }
 }
@@ -646,11 +1297,51 @@ where
     }
 }
 
+/// Parameters of a queue to create, passed to `Device::new`.
+///
+/// A `(QueueFamily, f32)` tuple can be used instead to create an unprotected queue with the given
+/// priority.
+#[derive(Debug, Copy, Clone)]
+pub struct QueueCreateInfo<'a> {
+    /// The family that the queue belongs to.
+    pub family: QueueFamily<'a>,
+
+    /// Priority of the queue, between 0.0 and 1.0.
+    pub priority: f32,
+
+    /// If true, the queue is a protected queue. This requires the `protected_memory` feature, and
+    /// a family that supports protected queues.
+    pub protected: bool,
+}
+
+impl<'a> QueueCreateInfo<'a> {
+    /// Builds a `QueueCreateInfo` for an unprotected queue with a priority of 0.5.
+    #[inline]
+    pub fn new(family: QueueFamily<'a>) -> QueueCreateInfo<'a> {
+        QueueCreateInfo {
+            family,
+            priority: 0.5,
+            protected: false,
+        }
+    }
+}
+
+impl<'a> From<(QueueFamily<'a>, f32)> for QueueCreateInfo<'a> {
+    #[inline]
+    fn from((family, priority): (QueueFamily<'a>, f32)) -> QueueCreateInfo<'a> {
+        QueueCreateInfo {
+            family,
+            priority,
+            protected: false,
+        }
+    }
+}
+
 /// Iterator that returns the queues produced when creating a device.
 pub struct QueuesIter {
     next_queue: usize,
     device: Arc<Device>,
-    families_and_ids: SmallVec<[(u32, u32); 8]>,
+    families_and_ids: SmallVec<[(u32, u32, f32, bool); 8]>,
 }
 
 unsafe impl DeviceOwned for QueuesIter {
@@ -664,23 +1355,36 @@ impl Iterator for QueuesIter {
 
     fn next(&mut self) -> Option<Arc<Queue>> {
         unsafe {
-            let &(family, id) = match self.families_and_ids.get(self.next_queue) {
-                Some(a) => a,
-                None => return None,
-            };
+            let &(family, id, priority, protected) = self.families_and_ids.get(self.next_queue)?;
 
             self.next_queue += 1;
 
             let mut output = MaybeUninit::uninit();
-            self.device
-                .vk
-                .GetDeviceQueue(self.device.device, family, id, output.as_mut_ptr());
+            if protected {
+                // Protected queues can only be retrieved with `vkGetDeviceQueue2`.
+                let info = vk::DeviceQueueInfo2 {
+                    sType: vk::STRUCTURE_TYPE_DEVICE_QUEUE_INFO_2,
+                    pNext: ptr::null(),
+                    flags: vk::DEVICE_QUEUE_CREATE_PROTECTED_BIT,
+                    queueFamilyIndex: family,
+                    queueIndex: id,
+                };
+                self.device
+                    .vk
+                    .GetDeviceQueue2(self.device.device, &info, output.as_mut_ptr());
+            } else {
+                self.device
+                    .vk
+                    .GetDeviceQueue(self.device.device, family, id, output.as_mut_ptr());
+            }
 
             Some(Arc::new(Queue {
                 queue: Mutex::new(output.assume_init()),
                 device: self.device.clone(),
                 family: family,
                 id: id,
+                priority,
+                protected,
             }))
         }
     }
@@ -710,6 +1414,10 @@ pub enum DeviceCreationError {
     ExtensionNotPresent,
     /// Tried to create too many queues for a given family.
     TooManyQueuesForFamily,
+    /// A protected queue was requested, but the `protected_memory` feature isn't enabled.
+    ProtectedMemoryNotEnabled,
+    /// A protected queue was requested in a family that doesn't support protected queues.
+    ProtectedQueueNotSupported,
     /// The priority of one of the queues is out of the [0.0; 1.0] range.
     PriorityOutOfRange,
     /// There is no memory available on the host (ie. the CPU, RAM, etc.).
@@ -737,6 +1445,14 @@ impl fmt::Display for DeviceCreationError {
                 DeviceCreationError::TooManyQueuesForFamily => {
                     "tried to create too many queues for a given family"
                 }
+                DeviceCreationError::ProtectedMemoryNotEnabled => {
+                    "a protected queue was requested, but the `protected_memory` feature isn't \
+                     enabled"
+                }
+                DeviceCreationError::ProtectedQueueNotSupported => {
+                    "a protected queue was requested in a family that doesn't support protected \
+                     queues"
+                }
                 DeviceCreationError::FeatureNotPresent => {
                     "some of the requested features are unsupported by the physical device"
                 }
@@ -779,6 +1495,8 @@ pub struct Queue {
     device: Arc<Device>,
     family: u32,
     id: u32, // id within family
+    priority: f32,
+    protected: bool,
 }
 
 impl Queue {
@@ -793,6 +1511,7 @@ impl Queue {
     pub fn is_same(&self, other: &Queue) -> bool {
         self.id == other.id
             && self.family == other.family
+            && self.protected == other.protected
             && self.device.internal_object() == other.device.internal_object()
     }
 
@@ -806,11 +1525,38 @@ impl Queue {
     }
 
     /// Returns the index of this queue within its family.
+    ///
+    /// Protected and unprotected queues are numbered separately.
     #[inline]
     pub fn id_within_family(&self) -> u32 {
         self.id
     }
 
+    /// Assigns a human-readable name to the queue for debugging purposes.
+    ///
+    /// # Panics
+    /// * If the `ext_debug_utils` extension isn't loaded on the instance.
+    #[inline]
+    pub fn set_debug_name(&self, name: &CStr) -> Result<(), OomError> {
+        let queue = *self.queue.lock().unwrap();
+        unsafe {
+            self.device
+                .set_object_name_raw(vk::OBJECT_TYPE_QUEUE, queue.value(), name)
+        }
+    }
+
+    /// Returns the priority that was given to this queue at device creation.
+    #[inline]
+    pub fn priority(&self) -> f32 {
+        self.priority
+    }
+
+    /// Returns true if this is a protected queue.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
     /// Waits until all work on this queue has finished.
     ///
     /// Just like `Device::wait()`, you shouldn't have to call this function in a typical program.
@@ -853,8 +1599,14 @@ mod tests {
     use crate::device::Device;
     use crate::device::DeviceCreationError;
     use crate::device::DeviceExtensions;
+    use crate::device::ExtensionFeatures;
+    use crate::device::ExtensionFeaturesChain;
+    use crate::device::QueueCreateInfo;
     use crate::features::Features;
     use crate::instance;
+    use crate::vk;
+    use std::os::raw::c_void;
+    use std::ptr;
     use std::sync::Arc;
 
     #[test]
@@ -885,6 +1637,103 @@ mod tests {
         };
     }
 
+    #[test]
+    fn memory_report_requires_extension() {
+        let instance = instance!();
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+
+        assert_should_panic!(
+            "the `ext_device_memory_report` extension and the `device_memory_report` feature \
+             must be enabled",
+            {
+                let _ = Device::with_memory_report(
+                    physical,
+                    &Features::none(),
+                    &DeviceExtensions::none(),
+                    Some((family, 1.0)),
+                    |_| {},
+                );
+            }
+        );
+    }
+
+    #[test]
+    fn fault_info_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!(
+            "the `ext_device_fault` extension and the `device_fault` feature must be enabled",
+            {
+                let _ = device.fault_info();
+            }
+        );
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    struct MemoryPriorityFeatures {
+        s_type: vk::StructureType,
+        p_next: *mut c_void,
+        memory_priority: vk::Bool32,
+    }
+
+    unsafe impl Send for MemoryPriorityFeatures {}
+    unsafe impl Sync for MemoryPriorityFeatures {}
+    unsafe impl ExtensionFeatures for MemoryPriorityFeatures {
+        const STRUCTURE_TYPE: vk::StructureType =
+            vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_PRIORITY_FEATURES_EXT;
+    }
+
+    #[test]
+    fn query_extension_features() {
+        let instance = instance!();
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let features: MemoryPriorityFeatures = physical.extension_features();
+        assert_eq!(
+            features.memory_priority != vk::FALSE,
+            physical.supported_features().memory_priority
+        );
+    }
+
+    #[test]
+    fn extension_features_already_handled() {
+        let instance = instance!();
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+        let mut chain = ExtensionFeaturesChain::new();
+        chain.push(MemoryPriorityFeatures {
+            s_type: 0,
+            p_next: ptr::null_mut(),
+            memory_priority: vk::TRUE,
+        });
+
+        assert_should_panic!(
+            "an extension feature structure is already handled by `Features`",
+            {
+                let _ = Device::with_extension_features(
+                    physical,
+                    &Features::none(),
+                    &DeviceExtensions::none(),
+                    Some((family, 1.0)),
+                    chain,
+                );
+            }
+        );
+    }
+
     #[test]
     fn unsupposed_features() {
         let instance = instance!();
@@ -942,4 +1791,93 @@ mod tests {
             _ => panic!(),
         };
     }
+    #[test]
+    fn queue_priority() {
+        let instance = instance!();
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+
+        let (_, mut queues) = Device::new(
+            physical,
+            &Features::none(),
+            &DeviceExtensions::none(),
+            Some(QueueCreateInfo {
+                priority: 0.25,
+                ..QueueCreateInfo::new(family)
+            }),
+        )
+        .unwrap();
+
+        let queue = queues.next().unwrap();
+        assert_eq!(queue.priority(), 0.25);
+        assert!(!queue.is_protected());
+    }
+
+    #[test]
+    fn protected_queue_requires_feature() {
+        let instance = instance!();
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+
+        match Device::new(
+            physical,
+            &Features::none(),
+            &DeviceExtensions::none(),
+            Some(QueueCreateInfo {
+                protected: true,
+                ..QueueCreateInfo::new(family)
+            }),
+        ) {
+            Err(DeviceCreationError::ProtectedMemoryNotEnabled) => (), // Success
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn display_hotplug_event_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!("ext_display_control", {
+            let _ = Device::register_display_hotplug_event(&device);
+        });
+    }
+
+    #[test]
+    fn memory_budget_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!("ext_memory_budget", {
+            device.memory_budget();
+        });
+    }
+
+    #[test]
+    fn standard_command_pool_per_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Device::standard_command_pool(&device, queue.family());
+        assert!(Arc::ptr_eq(
+            &pool,
+            &Device::standard_command_pool(&device, queue.family())
+        ));
+
+        let other_family = device
+            .physical_device()
+            .queue_families()
+            .find(|family| family.id() != queue.family().id());
+        if let Some(other_family) = other_family {
+            assert!(!Arc::ptr_eq(
+                &pool,
+                &Device::standard_command_pool(&device, other_family)
+            ));
+        }
+    }
 }
@@ -0,0 +1,247 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+
+use crate::check_errors;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::vk;
+use crate::OomError;
+use crate::VulkanHandle;
+use crate::VulkanObject;
+
+/// Slot that attaches a `u64` to each object of a device.
+///
+/// Each slot stores one value per object, which is 0 until it is set. This lets you associate
+/// your own data with the objects of vulkano, for example an identifier in an external tracking
+/// system, without keeping a map indexed by their handles.
+///
+/// The `ext_private_data` extension and the `private_data` feature must be enabled on the device.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use vulkano::device::PrivateDataSlot;
+/// use vulkano::sync::Event;
+/// # let device: Arc<vulkano::device::Device> = return;
+///
+/// let slot = PrivateDataSlot::new(device.clone()).unwrap();
+/// let event = Event::alloc(device.clone()).unwrap();
+///
+/// slot.set(&event, 42).unwrap();
+/// assert_eq!(slot.get(&event), 42);
+/// ```
+#[derive(Debug)]
+pub struct PrivateDataSlot {
+    slot: vk::PrivateDataSlotEXT,
+    device: Arc<Device>,
+}
+
+impl PrivateDataSlot {
+    /// Builds a new slot.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_private_data` extension or the `private_data` feature is not enabled
+    ///   on the device.
+    ///
+    pub fn new(device: Arc<Device>) -> Result<PrivateDataSlot, OomError> {
+        assert!(
+            device.loaded_extensions_or_core().ext_private_data
+                && device.enabled_features().private_data,
+            "the `ext_private_data` extension and the `private_data` feature must be enabled"
+        );
+
+        let slot = unsafe {
+            let infos = vk::PrivateDataSlotCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_PRIVATE_DATA_SLOT_CREATE_INFO_EXT,
+                pNext: ptr::null(),
+                flags: 0, // reserved
+            };
+
+            let mut output = MaybeUninit::uninit();
+            let vk = device.pointers();
+            check_errors(vk.CreatePrivateDataSlotEXT(
+                device.internal_object(),
+                &infos,
+                device.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(PrivateDataSlot { slot, device })
+    }
+
+    /// Stores `data` in the slot for `object`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `object` doesn't belong to the same device as the slot.
+    ///
+    pub fn set<T>(&self, object: &T, data: u64) -> Result<(), OomError>
+    where
+        T: VulkanObject + DeviceOwned,
+    {
+        assert_eq!(
+            object.device().internal_object(),
+            self.device.internal_object()
+        );
+        unsafe { self.set_raw(T::TYPE, object.internal_object().value(), data) }
+    }
+
+    /// Returns the data stored in the slot for `object`, or 0 if nothing was stored.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `object` doesn't belong to the same device as the slot.
+    ///
+    pub fn get<T>(&self, object: &T) -> u64
+    where
+        T: VulkanObject + DeviceOwned,
+    {
+        assert_eq!(
+            object.device().internal_object(),
+            self.device.internal_object()
+        );
+        unsafe { self.get_raw(T::TYPE, object.internal_object().value()) }
+    }
+
+    /// Stores `data` in the slot for a raw Vulkan object.
+    ///
+    /// # Safety
+    ///
+    /// `object` must be a Vulkan handle that belongs to the device of the slot, or the handle of
+    /// the device itself, and its type must be accurately described by `ty`.
+    pub unsafe fn set_raw(
+        &self,
+        ty: vk::ObjectType,
+        object: u64,
+        data: u64,
+    ) -> Result<(), OomError> {
+        let vk = self.device.pointers();
+        check_errors(vk.SetPrivateDataEXT(
+            self.device.internal_object(),
+            ty,
+            object,
+            self.slot,
+            data,
+        ))?;
+        Ok(())
+    }
+
+    /// Returns the data stored in the slot for a raw Vulkan object, or 0 if nothing was stored.
+    ///
+    /// # Safety
+    ///
+    /// Same as `set_raw`.
+    pub unsafe fn get_raw(&self, ty: vk::ObjectType, object: u64) -> u64 {
+        let vk = self.device.pointers();
+        let mut output = 0;
+        vk.GetPrivateDataEXT(
+            self.device.internal_object(),
+            ty,
+            object,
+            self.slot,
+            &mut output,
+        );
+        output
+    }
+}
+
+unsafe impl DeviceOwned for PrivateDataSlot {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for PrivateDataSlot {
+    type Object = vk::PrivateDataSlotEXT;
+
+    const TYPE: vk::ObjectType = vk::OBJECT_TYPE_PRIVATE_DATA_SLOT_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::PrivateDataSlotEXT {
+        self.slot
+    }
+}
+
+impl Drop for PrivateDataSlot {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroyPrivateDataSlotEXT(
+                self.device.internal_object(),
+                self.slot,
+                self.device.alloc_ptr(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::device::Device;
+    use crate::device::DeviceExtensions;
+    use crate::device::PrivateDataSlot;
+    use crate::features::Features;
+    use crate::instance::PhysicalDevice;
+
+    #[test]
+    fn requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!(
+            "the `ext_private_data` extension and the `private_data` feature must be enabled",
+            {
+                let _ = PrivateDataSlot::new(device);
+            }
+        );
+    }
+
+    #[test]
+    fn set_and_get() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let extensions = DeviceExtensions {
+            ext_private_data: true,
+            ..DeviceExtensions::none()
+        };
+        let features = Features {
+            private_data: true,
+            ..Features::none()
+        };
+        if !DeviceExtensions::supported_by_device(physical).ext_private_data
+            || !physical.supported_features().private_data
+        {
+            return;
+        }
+
+        let family = physical.queue_families().next().unwrap();
+        let (device, _) =
+            Device::new(physical, &features, &extensions, Some((family, 1.0))).unwrap();
+
+        let slot = PrivateDataSlot::new(device.clone()).unwrap();
+        let other = PrivateDataSlot::new(device.clone()).unwrap();
+
+        assert_eq!(slot.get(&other), 0);
+        slot.set(&other, 0xdead_beef).unwrap();
+        assert_eq!(slot.get(&other), 0xdead_beef);
+    }
+}
@@ -11,6 +11,8 @@ use std::marker::PhantomPinned;
 use std::pin::Pin;
 use std::{mem, ptr};
 
+use crate::device::DeviceExtensions;
+use crate::instance::Version;
 use crate::vk;
 
 macro_rules! features_init {
@@ -165,8 +167,8 @@ macro_rules! features_ffi_init_pinned {
 #[allow(non_snake_case)]
 #[repr(C)]
 pub(crate) struct Base {
-    sType: vk::StructureType,
-    pNext: *mut Base,
+    pub(crate) sType: vk::StructureType,
+    pub(crate) pNext: *mut Base,
 }
 
 // Can't define this structure with macros :(
@@ -280,6 +282,107 @@ pub struct Features {
 
     pub shader_float16: bool,
     pub shader_int8: bool,
+
+    pub graphics_pipeline_library: bool,
+
+    pub vertex_attribute_instance_rate_divisor: bool,
+    pub vertex_attribute_instance_rate_zero_divisor: bool,
+
+    pub pipeline_fragment_shading_rate: bool,
+    pub primitive_fragment_shading_rate: bool,
+    pub attachment_fragment_shading_rate: bool,
+
+    pub extended_dynamic_state: bool,
+    pub extended_dynamic_state2: bool,
+    pub extended_dynamic_state2_logic_op: bool,
+    pub extended_dynamic_state3_color_blend_enable: bool,
+    pub extended_dynamic_state3_color_blend_equation: bool,
+    pub extended_dynamic_state3_color_write_mask: bool,
+
+    pub shader_object: bool,
+
+    pub pipeline_executable_info: bool,
+
+    pub rectangular_lines: bool,
+    pub bresenham_lines: bool,
+    pub smooth_lines: bool,
+    pub stippled_rectangular_lines: bool,
+    pub stippled_bresenham_lines: bool,
+    pub stippled_smooth_lines: bool,
+
+    pub provoking_vertex_last: bool,
+    pub transform_feedback_preserves_provoking_vertex: bool,
+
+    pub advanced_blend_coherent_operations: bool,
+
+    pub color_write_enable: bool,
+
+    pub depth_clip_enable: bool,
+
+    pub primitive_topology_list_restart: bool,
+    pub primitive_topology_patch_list_restart: bool,
+
+    pub robust_buffer_access2: bool,
+    pub robust_image_access2: bool,
+    pub null_descriptor: bool,
+
+    pub robust_image_access: bool,
+
+    pub pipeline_robustness: bool,
+
+    pub pipeline_creation_cache_control: bool,
+
+    pub shader_module_identifier: bool,
+
+    pub multiview: bool,
+    pub multiview_geometry_shader: bool,
+    pub multiview_tessellation_shader: bool,
+
+    pub imageless_framebuffer: bool,
+
+    pub fragment_density_map: bool,
+    pub fragment_density_map_dynamic: bool,
+    pub fragment_density_map_non_subsampled_images: bool,
+
+    pub multisampled_render_to_single_sampled: bool,
+
+    pub present_id: bool,
+    pub present_wait: bool,
+
+    pub swapchain_maintenance1: bool,
+
+    pub timeline_semaphore: bool,
+
+    pub memory_priority: bool,
+
+    pub pageable_device_local_memory: bool,
+
+    pub device_memory_report: bool,
+
+    pub device_fault: bool,
+    pub device_fault_vendor_binary: bool,
+
+    pub private_data: bool,
+
+    pub protected_memory: bool,
+
+    // Features of `VK_KHR_portability_subset`. A device that doesn't support this extension
+    // supports all of this functionality, but reports these features as false.
+    pub constant_alpha_color_blend_factors: bool,
+    pub events: bool,
+    pub image_view_format_reinterpretation: bool,
+    pub image_view_format_swizzle: bool,
+    pub image_view_2d_on_3d_image: bool,
+    pub multisample_array_image: bool,
+    pub mutable_comparison_samplers: bool,
+    pub point_polygons: bool,
+    pub sampler_mip_lod_bias: bool,
+    pub separate_stencil_mask_ref: bool,
+    pub shader_sample_rate_interpolation_functions: bool,
+    pub tessellation_isolines: bool,
+    pub tessellation_point_mode: bool,
+    pub triangle_fans: bool,
+    pub vertex_attribute_access_beyond_stride: bool,
 }
 
 pub(crate) struct FeaturesFfi {
@@ -291,6 +394,41 @@ pub(crate) struct FeaturesFfi {
     i8_storage: vk::PhysicalDevice8BitStorageFeatures,
     i16_storage: vk::PhysicalDevice16BitStorageFeatures,
     f16_i8: vk::PhysicalDeviceShaderFloat16Int8Features,
+    graphics_pipeline_library: vk::PhysicalDeviceGraphicsPipelineLibraryFeaturesEXT,
+    vertex_attribute_divisor: vk::PhysicalDeviceVertexAttributeDivisorFeaturesEXT,
+    fragment_shading_rate: vk::PhysicalDeviceFragmentShadingRateFeaturesKHR,
+    extended_dynamic_state: vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT,
+    extended_dynamic_state2: vk::PhysicalDeviceExtendedDynamicState2FeaturesEXT,
+    extended_dynamic_state3: vk::PhysicalDeviceExtendedDynamicState3FeaturesEXT,
+    shader_object: vk::PhysicalDeviceShaderObjectFeaturesEXT,
+    pipeline_executable_properties: vk::PhysicalDevicePipelineExecutablePropertiesFeaturesKHR,
+    line_rasterization: vk::PhysicalDeviceLineRasterizationFeaturesEXT,
+    provoking_vertex: vk::PhysicalDeviceProvokingVertexFeaturesEXT,
+    blend_operation_advanced: vk::PhysicalDeviceBlendOperationAdvancedFeaturesEXT,
+    color_write_enable: vk::PhysicalDeviceColorWriteEnableFeaturesEXT,
+    depth_clip_enable: vk::PhysicalDeviceDepthClipEnableFeaturesEXT,
+    primitive_topology_list_restart: vk::PhysicalDevicePrimitiveTopologyListRestartFeaturesEXT,
+    robustness2: vk::PhysicalDeviceRobustness2FeaturesEXT,
+    image_robustness: vk::PhysicalDeviceImageRobustnessFeaturesEXT,
+    pipeline_robustness: vk::PhysicalDevicePipelineRobustnessFeaturesEXT,
+    pipeline_creation_cache_control: vk::PhysicalDevicePipelineCreationCacheControlFeaturesEXT,
+    shader_module_identifier: vk::PhysicalDeviceShaderModuleIdentifierFeaturesEXT,
+    multiview: vk::PhysicalDeviceMultiviewFeatures,
+    imageless_framebuffer: vk::PhysicalDeviceImagelessFramebufferFeatures,
+    fragment_density_map: vk::PhysicalDeviceFragmentDensityMapFeaturesEXT,
+    multisampled_render_to_single_sampled:
+        vk::PhysicalDeviceMultisampledRenderToSingleSampledFeaturesEXT,
+    present_id: vk::PhysicalDevicePresentIdFeaturesKHR,
+    present_wait: vk::PhysicalDevicePresentWaitFeaturesKHR,
+    swapchain_maintenance1: vk::PhysicalDeviceSwapchainMaintenance1FeaturesEXT,
+    timeline_semaphore: vk::PhysicalDeviceTimelineSemaphoreFeatures,
+    memory_priority: vk::PhysicalDeviceMemoryPriorityFeaturesEXT,
+    pageable_device_local_memory: vk::PhysicalDevicePageableDeviceLocalMemoryFeaturesEXT,
+    device_memory_report: vk::PhysicalDeviceDeviceMemoryReportFeaturesEXT,
+    device_fault: vk::PhysicalDeviceFaultFeaturesEXT,
+    private_data: vk::PhysicalDevicePrivateDataFeaturesEXT,
+    protected_memory: vk::PhysicalDeviceProtectedMemoryFeatures,
+    portability_subset: vk::PhysicalDevicePortabilitySubsetFeaturesKHR,
 }
 
 macro_rules! features {
@@ -423,6 +561,18 @@ macro_rules! features {
             }
             this
           }
+          pub(crate) fn contains(&self, structure_type: vk::StructureType) -> bool {
+            let mut next = self.base_ptr();
+            unsafe {
+              while let Some(next_ref) = next.as_ref() {
+                if next_ref.sType == structure_type {
+                  return true;
+                }
+                next = next_ref.pNext as *const Base;
+              }
+            }
+            false
+          }
           #[inline(always)]
           pub(crate) fn base_ptr(&self) -> *const Base {
             &self.main as *const _ as *const Base
@@ -554,4 +704,441 @@ features! {
         shader_int8 => shaderInt8,
       ],
     },
+    extension {
+      ty: vk::PhysicalDeviceGraphicsPipelineLibraryFeaturesEXT,
+      ffi_name: graphics_pipeline_library,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_GRAPHICS_PIPELINE_LIBRARY_FEATURES_EXT,
+      fields: [
+        graphics_pipeline_library => graphicsPipelineLibrary,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceVertexAttributeDivisorFeaturesEXT,
+      ffi_name: vertex_attribute_divisor,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_VERTEX_ATTRIBUTE_DIVISOR_FEATURES_EXT,
+      fields: [
+        vertex_attribute_instance_rate_divisor => vertexAttributeInstanceRateDivisor,
+        vertex_attribute_instance_rate_zero_divisor => vertexAttributeInstanceRateZeroDivisor,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceFragmentShadingRateFeaturesKHR,
+      ffi_name: fragment_shading_rate,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_FRAGMENT_SHADING_RATE_FEATURES_KHR,
+      fields: [
+        pipeline_fragment_shading_rate => pipelineFragmentShadingRate,
+        primitive_fragment_shading_rate => primitiveFragmentShadingRate,
+        attachment_fragment_shading_rate => attachmentFragmentShadingRate,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT,
+      ffi_name: extended_dynamic_state,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_FEATURES_EXT,
+      fields: [
+        extended_dynamic_state => extendedDynamicState,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceExtendedDynamicState2FeaturesEXT,
+      ffi_name: extended_dynamic_state2,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_2_FEATURES_EXT,
+      fields: [
+        extended_dynamic_state2 => extendedDynamicState2,
+        extended_dynamic_state2_logic_op => extendedDynamicState2LogicOp,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceExtendedDynamicState3FeaturesEXT,
+      ffi_name: extended_dynamic_state3,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_3_FEATURES_EXT,
+      fields: [
+        extended_dynamic_state3_color_blend_enable => extendedDynamicState3ColorBlendEnable,
+        extended_dynamic_state3_color_blend_equation => extendedDynamicState3ColorBlendEquation,
+        extended_dynamic_state3_color_write_mask => extendedDynamicState3ColorWriteMask,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceShaderObjectFeaturesEXT,
+      ffi_name: shader_object,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_OBJECT_FEATURES_EXT,
+      fields: [
+        shader_object => shaderObject,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePipelineExecutablePropertiesFeaturesKHR,
+      ffi_name: pipeline_executable_properties,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PIPELINE_EXECUTABLE_PROPERTIES_FEATURES_KHR,
+      fields: [
+        pipeline_executable_info => pipelineExecutableInfo,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceLineRasterizationFeaturesEXT,
+      ffi_name: line_rasterization,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_LINE_RASTERIZATION_FEATURES_EXT,
+      fields: [
+        rectangular_lines => rectangularLines,
+        bresenham_lines => bresenhamLines,
+        smooth_lines => smoothLines,
+        stippled_rectangular_lines => stippledRectangularLines,
+        stippled_bresenham_lines => stippledBresenhamLines,
+        stippled_smooth_lines => stippledSmoothLines,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceProvokingVertexFeaturesEXT,
+      ffi_name: provoking_vertex,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROVOKING_VERTEX_FEATURES_EXT,
+      fields: [
+        provoking_vertex_last => provokingVertexLast,
+        transform_feedback_preserves_provoking_vertex => transformFeedbackPreservesProvokingVertex,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceBlendOperationAdvancedFeaturesEXT,
+      ffi_name: blend_operation_advanced,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_BLEND_OPERATION_ADVANCED_FEATURES_EXT,
+      fields: [
+        advanced_blend_coherent_operations => advancedBlendCoherentOperations,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceColorWriteEnableFeaturesEXT,
+      ffi_name: color_write_enable,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_COLOR_WRITE_ENABLE_FEATURES_EXT,
+      fields: [
+        color_write_enable => colorWriteEnable,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceDepthClipEnableFeaturesEXT,
+      ffi_name: depth_clip_enable,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DEPTH_CLIP_ENABLE_FEATURES_EXT,
+      fields: [
+        depth_clip_enable => depthClipEnable,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePrimitiveTopologyListRestartFeaturesEXT,
+      ffi_name: primitive_topology_list_restart,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PRIMITIVE_TOPOLOGY_LIST_RESTART_FEATURES_EXT,
+      fields: [
+        primitive_topology_list_restart => primitiveTopologyListRestart,
+        primitive_topology_patch_list_restart => primitiveTopologyPatchListRestart,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceRobustness2FeaturesEXT,
+      ffi_name: robustness2,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_ROBUSTNESS_2_FEATURES_EXT,
+      fields: [
+        robust_buffer_access2 => robustBufferAccess2,
+        robust_image_access2 => robustImageAccess2,
+        null_descriptor => nullDescriptor,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceImageRobustnessFeaturesEXT,
+      ffi_name: image_robustness,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_IMAGE_ROBUSTNESS_FEATURES_EXT,
+      fields: [
+        robust_image_access => robustImageAccess,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePipelineRobustnessFeaturesEXT,
+      ffi_name: pipeline_robustness,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PIPELINE_ROBUSTNESS_FEATURES_EXT,
+      fields: [
+        pipeline_robustness => pipelineRobustness,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePipelineCreationCacheControlFeaturesEXT,
+      ffi_name: pipeline_creation_cache_control,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PIPELINE_CREATION_CACHE_CONTROL_FEATURES_EXT,
+      fields: [
+        pipeline_creation_cache_control => pipelineCreationCacheControl,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceShaderModuleIdentifierFeaturesEXT,
+      ffi_name: shader_module_identifier,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_MODULE_IDENTIFIER_FEATURES_EXT,
+      fields: [
+        shader_module_identifier => shaderModuleIdentifier,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceMultiviewFeatures,
+      ffi_name: multiview,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES,
+      fields: [
+        multiview => multiview,
+        multiview_geometry_shader => multiviewGeometryShader,
+        multiview_tessellation_shader => multiviewTessellationShader,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceImagelessFramebufferFeatures,
+      ffi_name: imageless_framebuffer,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_IMAGELESS_FRAMEBUFFER_FEATURES,
+      fields: [
+        imageless_framebuffer => imagelessFramebuffer,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceFragmentDensityMapFeaturesEXT,
+      ffi_name: fragment_density_map,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_FRAGMENT_DENSITY_MAP_FEATURES_EXT,
+      fields: [
+        fragment_density_map => fragmentDensityMap,
+        fragment_density_map_dynamic => fragmentDensityMapDynamic,
+        fragment_density_map_non_subsampled_images => fragmentDensityMapNonSubsampledImages,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceMultisampledRenderToSingleSampledFeaturesEXT,
+      ffi_name: multisampled_render_to_single_sampled,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTISAMPLED_RENDER_TO_SINGLE_SAMPLED_FEATURES_EXT,
+      fields: [
+        multisampled_render_to_single_sampled => multisampledRenderToSingleSampled,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePresentIdFeaturesKHR,
+      ffi_name: present_id,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PRESENT_ID_FEATURES_KHR,
+      fields: [
+        present_id => presentId,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePresentWaitFeaturesKHR,
+      ffi_name: present_wait,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PRESENT_WAIT_FEATURES_KHR,
+      fields: [
+        present_wait => presentWait,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceSwapchainMaintenance1FeaturesEXT,
+      ffi_name: swapchain_maintenance1,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SWAPCHAIN_MAINTENANCE_1_FEATURES_EXT,
+      fields: [
+        swapchain_maintenance1 => swapchainMaintenance1,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceTimelineSemaphoreFeatures,
+      ffi_name: timeline_semaphore,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_FEATURES,
+      fields: [
+        timeline_semaphore => timelineSemaphore,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceMemoryPriorityFeaturesEXT,
+      ffi_name: memory_priority,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_PRIORITY_FEATURES_EXT,
+      fields: [
+        memory_priority => memoryPriority,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePageableDeviceLocalMemoryFeaturesEXT,
+      ffi_name: pageable_device_local_memory,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PAGEABLE_DEVICE_LOCAL_MEMORY_FEATURES_EXT,
+      fields: [
+        pageable_device_local_memory => pageableDeviceLocalMemory,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceDeviceMemoryReportFeaturesEXT,
+      ffi_name: device_memory_report,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DEVICE_MEMORY_REPORT_FEATURES_EXT,
+      fields: [
+        device_memory_report => deviceMemoryReport,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceFaultFeaturesEXT,
+      ffi_name: device_fault,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_FAULT_FEATURES_EXT,
+      fields: [
+        device_fault => deviceFault,
+        device_fault_vendor_binary => deviceFaultVendorBinary,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePrivateDataFeaturesEXT,
+      ffi_name: private_data,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PRIVATE_DATA_FEATURES_EXT,
+      fields: [
+        private_data => privateData,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceProtectedMemoryFeatures,
+      ffi_name: protected_memory,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES,
+      fields: [
+        protected_memory => protectedMemory,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePortabilitySubsetFeaturesKHR,
+      ffi_name: portability_subset,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PORTABILITY_SUBSET_FEATURES_KHR,
+      fields: [
+        constant_alpha_color_blend_factors => constantAlphaColorBlendFactors,
+        events => events,
+        image_view_format_reinterpretation => imageViewFormatReinterpretation,
+        image_view_format_swizzle => imageViewFormatSwizzle,
+        image_view_2d_on_3d_image => imageView2DOn3DImage,
+        multisample_array_image => multisampleArrayImage,
+        mutable_comparison_samplers => mutableComparisonSamplers,
+        point_polygons => pointPolygons,
+        sampler_mip_lod_bias => samplerMipLodBias,
+        separate_stencil_mask_ref => separateStencilMaskRef,
+        shader_sample_rate_interpolation_functions => shaderSampleRateInterpolationFunctions,
+        tessellation_isolines => tessellationIsolines,
+        tessellation_point_mode => tessellationPointMode,
+        triangle_fans => triangleFans,
+        vertex_attribute_access_beyond_stride => vertexAttributeAccessBeyondStride,
+      ],
+    },
+}
+
+macro_rules! promoted_features {
+    ($(
+        $(#[$attr:meta])*
+        $ty:ident {
+            version: $version:ident,
+            $(#[$getter_attr:meta])*
+            getter: $getter:ident,
+            fields: [
+                $($name:ident $(=> $ext:ident)?,)+
+            ],
+        },
+    )+) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+            #[allow(missing_docs)]
+            pub struct $ty {
+                $(pub $name: bool,)+
+            }
+
+            impl $ty {
+                /// Builds a value with all the features disabled.
+                #[inline]
+                pub fn none() -> $ty {
+                    $ty::default()
+                }
+
+                /// Builds a value with all the features enabled.
+                #[inline]
+                pub fn all() -> $ty {
+                    $ty {
+                        $($name: true,)+
+                    }
+                }
+
+                /// Returns the version of Vulkan that the features are part of.
+                #[inline]
+                pub fn version() -> Version {
+                    Version::$version
+                }
+
+                /// Returns the extensions that must be enabled in order to use the enabled
+                /// features of `self` on a device that uses an older version of Vulkan than
+                /// `version()`.
+                ///
+                /// A feature without an extension, such as `protected_memory`, can't be used on
+                /// an older version.
+                #[inline]
+                pub fn required_extensions(&self) -> DeviceExtensions {
+                    let mut extensions = DeviceExtensions::none();
+                    $($(
+                        if self.$name {
+                            extensions.$ext = true;
+                        }
+                    )?)+
+                    extensions
+                }
+            }
+
+            impl From<$ty> for Features {
+                #[inline]
+                fn from(features: $ty) -> Features {
+                    let mut out = Features::none();
+                    $(out.$name = features.$name;)+
+                    out
+                }
+            }
+
+            impl Features {
+                $(#[$getter_attr])*
+                #[inline]
+                pub fn $getter(&self) -> $ty {
+                    $ty {
+                        $($name: self.$name,)+
+                    }
+                }
+            }
+        )+
+    };
+}
+
+promoted_features! {
+    /// The features of `Features` that are part of Vulkan 1.1, with the extension that they
+    /// were promoted from.
+    Vulkan11Features {
+        version: V1_1,
+        /// Returns the features of `self` that are part of Vulkan 1.1.
+        getter: vulkan11_features,
+        fields: [
+            storage_buffer_16bit => khr_16bit_storage,
+            storage_uniform_16bit => khr_16bit_storage,
+            storage_push_constant_16bit => khr_16bit_storage,
+            storage_input_output_16bit => khr_16bit_storage,
+            multiview => khr_multiview,
+            variable_pointers_storage_buffer => khr_variable_pointers,
+            variable_pointers => khr_variable_pointers,
+            protected_memory,
+        ],
+    },
+    /// The features of `Features` that are part of Vulkan 1.2, with the extension that they
+    /// were promoted from.
+    Vulkan12Features {
+        version: V1_2,
+        /// Returns the features of `self` that are part of Vulkan 1.2.
+        getter: vulkan12_features,
+        fields: [
+            storage_buffer_8bit => khr_8bit_storage,
+            storage_uniform_8bit => khr_8bit_storage,
+            storage_push_constant_8bit => khr_8bit_storage,
+            shader_buffer_int64_atomics => khr_shader_atomic_int64,
+            shader_shared_int64_atomics => khr_shader_atomic_int64,
+            shader_float16 => khr_shader_float16_int8,
+            shader_int8 => khr_shader_float16_int8,
+            imageless_framebuffer => khr_imageless_framebuffer,
+            timeline_semaphore => khr_timeline_semaphore,
+        ],
+    },
+    /// The features of `Features` that are part of Vulkan 1.3, with the extension that they
+    /// were promoted from.
+    Vulkan13Features {
+        version: V1_3,
+        /// Returns the features of `self` that are part of Vulkan 1.3.
+        getter: vulkan13_features,
+        fields: [
+            robust_image_access => ext_image_robustness,
+            pipeline_creation_cache_control => ext_pipeline_creation_cache_control,
+            private_data => ext_private_data,
+        ],
+    },
 }
@@ -108,6 +108,7 @@ use std::{error, fmt, mem};
 
 use half::f16;
 use crate::instance::PhysicalDevice;
+use crate::pipeline::vertex::VertexMemberTy;
 
 use crate::vk;
 use crate::VulkanObject;
@@ -245,6 +246,17 @@ macro_rules! formats {
                 }
             }
 
+            /// Returns the type and the number of components of an element of this format, as
+            /// they are laid out in a vertex buffer. Returns `None` if the format can't be
+            /// described by a `VertexMemberTy`.
+            pub(crate) fn vertex_member(&self) -> Option<(VertexMemberTy, usize)> {
+                match *self {
+                    $(
+                        Format::$name => formats!(__inner_vertex_member__ $($d_ty)*),
+                    )+
+                }
+            }
+
             /// Retrieves the properties of a format when used by a certain device.
             #[inline]
             pub fn properties(&self, device: PhysicalDevice) -> FormatProperties {
@@ -463,6 +475,25 @@ macro_rules! formats {
     (__inner_ty__ $name:ident ycbcr) => { FormatTy::Ycbcr };
 
 
+    (__inner_vertex_member__ [$ty:ident; $dim:expr]) => {
+        formats!(__inner_vertex_member_ty__ $ty).map(|ty| (ty, $dim))
+    };
+    (__inner_vertex_member__ $ty:ident) => {
+        formats!(__inner_vertex_member_ty__ $ty).map(|ty| (ty, 1))
+    };
+    (__inner_vertex_member__ ) => { None };
+
+    (__inner_vertex_member_ty__ i8) => { Some(VertexMemberTy::I8) };
+    (__inner_vertex_member_ty__ u8) => { Some(VertexMemberTy::U8) };
+    (__inner_vertex_member_ty__ i16) => { Some(VertexMemberTy::I16) };
+    (__inner_vertex_member_ty__ u16) => { Some(VertexMemberTy::U16) };
+    (__inner_vertex_member_ty__ i32) => { Some(VertexMemberTy::I32) };
+    (__inner_vertex_member_ty__ u32) => { Some(VertexMemberTy::U32) };
+    (__inner_vertex_member_ty__ f16) => { Some(VertexMemberTy::F16) };
+    (__inner_vertex_member_ty__ f32) => { Some(VertexMemberTy::F32) };
+    (__inner_vertex_member_ty__ f64) => { Some(VertexMemberTy::F64) };
+    (__inner_vertex_member_ty__ $ty:ident) => { None };
+
     (__inner_strongstorage__ $name:ident [$ty:ty; $dim:expr]) => {
         formats!(__inner_strongstorage_common__ $name [$ty; $dim]);
         unsafe impl AcceptsPixels<$ty> for $name {
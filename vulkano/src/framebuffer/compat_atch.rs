@@ -63,6 +63,7 @@ where
         if subpass
             .color_attachments
             .iter()
+            .chain(subpass.resolve_attachments.iter())
             .any(|&(n, _)| n == attachment_num)
         {
             debug_assert!(image_view.image().has_color()); // Was normally checked by the render pass.
@@ -71,21 +72,25 @@ where
             }
         }
 
-        if let Some((ds, _)) = subpass.depth_stencil {
-            if ds == attachment_num {
-                // Was normally checked by the render pass.
-                debug_assert!(image_view.image().has_depth() || image_view.image().has_stencil());
-                if !image_view
-                    .image()
-                    .inner()
-                    .image
-                    .usage()
-                    .depth_stencil_attachment
-                {
-                    return Err(
-                        IncompatibleRenderPassAttachmentError::MissingDepthStencilAttachmentUsage,
-                    );
-                }
+        if subpass
+            .depth_stencil
+            .iter()
+            .map(|&(n, _)| n)
+            .chain(subpass.depth_stencil_resolve.iter().map(|&(n, _, _, _)| n))
+            .any(|n| n == attachment_num)
+        {
+            // Was normally checked by the render pass.
+            debug_assert!(image_view.image().has_depth() || image_view.image().has_stencil());
+            if !image_view
+                .image()
+                .inner()
+                .image
+                .usage()
+                .depth_stencil_attachment
+            {
+                return Err(
+                    IncompatibleRenderPassAttachmentError::MissingDepthStencilAttachmentUsage,
+                );
             }
         }
 
@@ -98,6 +103,56 @@ where
                 return Err(IncompatibleRenderPassAttachmentError::MissingInputAttachmentUsage);
             }
         }
+
+        // The single-sampled attachments of a subpass that renders multisampled to
+        // single-sampled attachments need a special flag.
+        if subpass.multisampled_render_to_single_sampled.is_some()
+            && attachment_desc.samples == 1
+            && subpass
+                .color_attachments
+                .iter()
+                .chain(subpass.input_attachments.iter())
+                .chain(subpass.depth_stencil.iter())
+                .any(|&(n, _)| n == attachment_num)
+            && !image_view
+                .image()
+                .inner()
+                .image
+                .flags()
+                .multisampled_render_to_single_sampled
+        {
+            return Err(
+                IncompatibleRenderPassAttachmentError::MissingMultisampledRenderToSingleSampledFlag,
+            );
+        }
+
+        if let Some((fsr, _, _)) = subpass.fragment_shading_rate_attachment {
+            if fsr == attachment_num
+                && !image_view
+                    .image()
+                    .inner()
+                    .image
+                    .usage()
+                    .fragment_shading_rate_attachment
+            {
+                return Err(
+                    IncompatibleRenderPassAttachmentError::MissingFragmentShadingRateAttachmentUsage,
+                );
+            }
+        }
+    }
+
+    if let Some((density_map, _)) = render_pass.fragment_density_map_attachment() {
+        if density_map == attachment_num
+            && !image_view
+                .image()
+                .inner()
+                .image
+                .usage()
+                .fragment_density_map
+        {
+            return Err(IncompatibleRenderPassAttachmentError::MissingFragmentDensityMapUsage);
+        }
     }
 
     // TODO: consider forbidding LoadOp::Load if image is transient
@@ -140,6 +195,18 @@ pub enum IncompatibleRenderPassAttachmentError {
 
     /// The image is used as an input attachment but is missing the input attachment usage.
     MissingInputAttachmentUsage,
+
+    /// The image is used as a fragment shading rate attachment but is missing the fragment
+    /// shading rate attachment usage.
+    MissingFragmentShadingRateAttachmentUsage,
+
+    /// The image is used as a fragment density map but is missing the fragment density map
+    /// usage.
+    MissingFragmentDensityMapUsage,
+
+    /// The image is used by a subpass that renders multisampled to single-sampled attachments,
+    /// but wasn't created with the `multisampled_render_to_single_sampled` flag.
+    MissingMultisampledRenderToSingleSampledFlag,
 }
 
 impl error::Error for IncompatibleRenderPassAttachmentError {}
@@ -172,6 +239,19 @@ impl fmt::Display for IncompatibleRenderPassAttachmentError {
                     "the image is used as an input attachment but is missing the input \
                  attachment usage"
                 }
+                IncompatibleRenderPassAttachmentError::MissingFragmentShadingRateAttachmentUsage => {
+                    "the image is used as a fragment shading rate attachment but is missing the \
+                 fragment shading rate attachment usage"
+                }
+                IncompatibleRenderPassAttachmentError::MissingFragmentDensityMapUsage => {
+                    "the image is used as a fragment density map but is missing the fragment \
+                 density map usage"
+                }
+                IncompatibleRenderPassAttachmentError::MissingMultisampledRenderToSingleSampledFlag => {
+                    "the image is used by a subpass that renders multisampled to single-sampled \
+                 attachments, but wasn't created with the `multisampled_render_to_single_sampled` \
+                 flag"
+                }
             }
         )
     }
@@ -103,6 +103,32 @@ pub unsafe trait RenderPassDesc: RenderPassDescClearValues<Vec<ClearValue>> {
         }
     }
 
+    /// Returns the correlation masks of the render pass.
+    ///
+    /// Each mask is a set of views that are likely to be spatially correlated, for example the
+    /// views of both eyes in VR, which the implementation can use to render them more
+    /// efficiently. The masks must not have any bit in common, and are only allowed if the
+    /// render pass uses multiview.
+    ///
+    /// The default implementation returns an empty list.
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        Vec::new()
+    }
+
+    /// Returns the attachment that holds the fragment density map of the render pass, if any,
+    /// and the layout it is in during the render pass.
+    ///
+    /// The density map is read by the implementation during rasterization to shade some regions
+    /// of the render area at a reduced resolution. This requires the `ext_fragment_density_map`
+    /// extension and the `fragment_density_map` feature.
+    ///
+    /// The default implementation returns `None`.
+    #[inline]
+    fn fragment_density_map_attachment(&self) -> Option<(usize, ImageLayout)> {
+        None
+    }
+
     /// Returns true if this render pass is compatible with another render pass.
     ///
     /// Two render passes that contain one subpass are compatible if they are identical. Two render
@@ -145,6 +171,9 @@ pub unsafe trait RenderPassDesc: RenderPassDescClearValues<Vec<ClearValue>> {
 
     /// Returns the number of samples of the attachments of a subpass. Returns `None` if out of
     /// range or if the subpass has no attachment. TODO: return an enum instead?
+    ///
+    /// If the subpass renders multisampled to single-sampled attachments, returns the number of
+    /// samples it is rasterized with instead.
     #[inline]
     fn num_samples(&self, subpass: u32) -> Option<u32> {
         (&self)
@@ -152,6 +181,10 @@ pub unsafe trait RenderPassDesc: RenderPassDescClearValues<Vec<ClearValue>> {
             .skip(subpass as usize)
             .next()
             .and_then(|p| {
+                if let Some(samples) = p.multisampled_render_to_single_sampled {
+                    return Some(samples);
+                }
+
                 // TODO: chain input attachments as well?
                 p.color_attachments
                     .iter()
@@ -356,6 +389,16 @@ where
     fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
         (**self).dependency_desc(num)
     }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        (**self).correlation_masks()
+    }
+
+    #[inline]
+    fn fragment_density_map_attachment(&self) -> Option<(usize, ImageLayout)> {
+        (**self).fragment_density_map_attachment()
+    }
 }
 
 /// Iterator to the attachments of a `RenderPassDesc`.
@@ -523,6 +566,45 @@ pub struct PassDescription {
 
     /// Indices of attachments that will be preserved during this pass.
     pub preserve_attachments: Vec<usize>, // TODO: Vec is slow
+
+    /// Index and layout of the attachment to use as fragment shading rate attachment, along with
+    /// the width and height in pixels of the area of the framebuffer covered by each of its
+    /// texels.
+    ///
+    /// Using a fragment shading rate attachment requires the `khr_fragment_shading_rate`
+    /// extension and the `attachment_fragment_shading_rate` feature to be enabled on the device.
+    /// The texel size must be a power of two.
+    pub fragment_shading_rate_attachment: Option<(usize, ImageLayout, [u32; 2])>,
+
+    /// Views that the subpass renders to. Each bit set in the mask corresponds to one layer of
+    /// the attachments, and each draw command is broadcast to all of them.
+    ///
+    /// A mask of 0 disables multiview. Otherwise, multiview requires the `khr_multiview`
+    /// extension and the `multiview` feature to be enabled on the device, and the view mask of
+    /// all the subpasses of the render pass must be non-zero.
+    pub view_mask: u32,
+
+    /// Index and layout of the attachment that the depth-stencil attachment is resolved into at
+    /// the end of the subpass, along with the resolve mode of the depth and stencil aspects.
+    ///
+    /// Resolving the depth-stencil attachment requires the `khr_depth_stencil_resolve` and
+    /// `khr_create_renderpass2` extensions to be enabled on the device. The depth-stencil
+    /// attachment must be multisampled, and the resolve attachment must have one sample and the
+    /// same format.
+    pub depth_stencil_resolve: Option<(usize, ImageLayout, ResolveMode, ResolveMode)>,
+
+    /// If `Some`, the subpass is rasterized with this number of samples even though its
+    /// attachments have only one sample. The implementation renders to implicit multisampled
+    /// attachments and resolves them into the attachments at the end of the subpass, which saves
+    /// the memory of the multisampled attachments on tiled architectures.
+    ///
+    /// This requires the `ext_multisampled_render_to_single_sampled` and
+    /// `khr_create_renderpass2` extensions and the `multisampled_render_to_single_sampled`
+    /// feature. The number of samples must be a power of two greater than one, the attachments
+    /// of the subpass must have either one sample or this number of samples, and the images of
+    /// the single-sampled attachments must have been created with the
+    /// `multisampled_render_to_single_sampled` flag.
+    pub multisampled_render_to_single_sampled: Option<u32>,
 }
 
 /// Describes a dependency between two passes of a render pass.
@@ -563,6 +645,78 @@ pub struct PassDependencyDescription {
     /// Passing `false` is always safer than passing `true`, but in practice you rarely need to
     /// pass `false`.
     pub by_region: bool,
+
+    /// If `Some`, the dependency is view-local: each view of the destination subpass only
+    /// depends on one view of the source subpass. The value is the offset between the index of
+    /// the destination view and the index of the source view.
+    ///
+    /// Only allowed if the render pass uses multiview. Must be `Some(0)` or `None` if the source
+    /// and destination subpasses are the same.
+    pub view_local: Option<i32>,
+}
+
+impl PassDependencyDescription {
+    /// Value of `source_subpass` or `destination_subpass` that refers to the commands that are
+    /// outside of the render pass, before it for the source and after it for the destination.
+    pub const EXTERNAL_SUBPASS: usize = vk::SUBPASS_EXTERNAL as usize;
+
+    /// Builds the dependency that is automatically added between a subpass and the one that
+    /// follows it by the `single_pass_renderpass!` and `ordered_passes_renderpass!` macros and
+    /// by `RuntimeRenderPassDesc::with_automatic_dependencies`.
+    ///
+    /// All the graphics stages of the next subpass wait for all the graphics stages of
+    /// `subpass`, for all kinds of accesses, region by region. This is always correct but often
+    /// too conservative, and it can be used as a base for a more precise dependency.
+    #[inline]
+    pub fn sequential(subpass: usize) -> PassDependencyDescription {
+        PassDependencyDescription {
+            source_subpass: subpass,
+            destination_subpass: subpass + 1,
+            source_stages: PipelineStages {
+                all_graphics: true,
+                ..PipelineStages::none()
+            },
+            destination_stages: PipelineStages {
+                all_graphics: true,
+                ..PipelineStages::none()
+            },
+            source_access: AccessFlagBits::all(),
+            destination_access: AccessFlagBits::all(),
+            by_region: true,
+            view_local: None,
+        }
+    }
+}
+
+/// Returns the dependencies of a render pass with `num_subpasses` subpasses, where the
+/// automatically derived dependencies are overridden or augmented by `explicit`.
+///
+/// The automatically derived dependencies are one `PassDependencyDescription::sequential`
+/// dependency between each subpass and the next one. Each dependency of `explicit` replaces the
+/// derived dependency that has the same source and destination subpasses, if any, and is added
+/// to the list otherwise. This makes it possible, for example, to relax the dependency between
+/// two subpasses or to add dependencies on `PassDependencyDescription::EXTERNAL_SUBPASS`.
+pub fn derive_dependencies<I>(num_subpasses: usize, explicit: I) -> Vec<PassDependencyDescription>
+where
+    I: IntoIterator<Item = PassDependencyDescription>,
+{
+    let mut dependencies: Vec<_> = (0..num_subpasses.saturating_sub(1))
+        .map(PassDependencyDescription::sequential)
+        .collect();
+
+    for dependency in explicit {
+        let existing = dependencies.iter_mut().find(|d| {
+            d.source_subpass == dependency.source_subpass
+                && d.destination_subpass == dependency.destination_subpass
+        });
+
+        match existing {
+            Some(existing) => *existing = dependency,
+            None => dependencies.push(dependency),
+        }
+    }
+
+    dependencies
 }
 
 /// Describes what the implementation should do with an attachment after all the subpasses have
@@ -615,3 +769,61 @@ pub enum LoadOp {
     /// instead.
     DontCare = vk::ATTACHMENT_LOAD_OP_DONT_CARE,
 }
+
+/// Describes how the samples of a multisampled depth or stencil attachment are combined when it
+/// is resolved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ResolveMode {
+    /// The aspect is not resolved.
+    None = vk::RESOLVE_MODE_NONE,
+
+    /// The value of sample 0 is used. Always supported for both aspects.
+    SampleZero = vk::RESOLVE_MODE_SAMPLE_ZERO_BIT,
+
+    /// The average of the samples is used. Only supported for the depth aspect.
+    Average = vk::RESOLVE_MODE_AVERAGE_BIT,
+
+    /// The minimum of the samples is used.
+    Min = vk::RESOLVE_MODE_MIN_BIT,
+
+    /// The maximum of the samples is used.
+    Max = vk::RESOLVE_MODE_MAX_BIT,
+}
+
+/// Set of resolve modes supported by a physical device for one aspect.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ResolveModes {
+    /// `ResolveMode::SampleZero`.
+    pub sample_zero: bool,
+    /// `ResolveMode::Average`.
+    pub average: bool,
+    /// `ResolveMode::Min`.
+    pub min: bool,
+    /// `ResolveMode::Max`.
+    pub max: bool,
+}
+
+impl ResolveModes {
+    /// Returns true if `mode` is in the set. `ResolveMode::None` is always supported.
+    #[inline]
+    pub fn contains(&self, mode: ResolveMode) -> bool {
+        match mode {
+            ResolveMode::None => true,
+            ResolveMode::SampleZero => self.sample_zero,
+            ResolveMode::Average => self.average,
+            ResolveMode::Min => self.min,
+            ResolveMode::Max => self.max,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_vulkan_bits(value: vk::ResolveModeFlags) -> ResolveModes {
+        ResolveModes {
+            sample_zero: (value & vk::RESOLVE_MODE_SAMPLE_ZERO_BIT) != 0,
+            average: (value & vk::RESOLVE_MODE_AVERAGE_BIT) != 0,
+            min: (value & vk::RESOLVE_MODE_MIN_BIT) != 0,
+            max: (value & vk::RESOLVE_MODE_MAX_BIT) != 0,
+        }
+    }
+}
@@ -57,6 +57,10 @@ unsafe impl RenderPassDesc for EmptySinglePassRenderPassDesc {
                 input_attachments: vec![],
                 resolve_attachments: vec![],
                 preserve_attachments: vec![],
+                fragment_shading_rate_attachment: None,
+                view_mask: 0,
+                depth_stencil_resolve: None,
+                multisampled_render_to_single_sampled: None,
             })
         } else {
             None
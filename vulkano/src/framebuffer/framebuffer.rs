@@ -31,6 +31,7 @@ use crate::framebuffer::RenderPassDesc;
 use crate::framebuffer::RenderPassDescClearValues;
 use crate::framebuffer::RenderPassSys;
 use crate::image::view::ImageViewAbstract;
+use crate::image::ImageLayout;
 
 use crate::check_errors;
 use crate::vk;
@@ -144,13 +145,47 @@ where
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 enum FramebufferBuilderDimensions {
     AutoIdentical(Option<[u32; 3]>),
     AutoSmaller(Option<[u32; 3]>),
     Specific([u32; 3]),
 }
 
+impl<Rp> FramebufferBuilder<Rp, ()>
+where
+    Rp: RenderPassAbstract,
+{
+    /// Adds all the attachments of the framebuffer at once.
+    ///
+    /// Contrary to `add`, the number of attachments only needs to be known at runtime, which makes
+    /// it possible to build framebuffers generically, for example for the render passes of a
+    /// render graph. Each image view is checked against the render pass in the same way as with
+    /// `add`, in the order of the render pass attachments.
+    pub fn add_all(
+        self,
+        attachments: Vec<Arc<dyn ImageViewAbstract + Send + Sync>>,
+    ) -> Result<
+        FramebufferBuilder<Rp, Vec<Arc<dyn ImageViewAbstract + Send + Sync>>>,
+        FramebufferCreationError,
+    > {
+        let mut builder = FramebufferBuilder {
+            render_pass: self.render_pass,
+            raw_ids: self.raw_ids,
+            dimensions: self.dimensions,
+            attachments: Vec::with_capacity(attachments.len()),
+        };
+
+        for attachment in attachments {
+            builder.dimensions = builder.check_attachment(&*attachment)?;
+            builder.raw_ids.push(attachment.inner().internal_object());
+            builder.attachments.push(attachment);
+        }
+
+        Ok(builder)
+    }
+}
+
 impl<Rp, A> FramebufferBuilder<Rp, A>
 where
     Rp: RenderPassAbstract,
@@ -165,6 +200,28 @@ where
     ) -> Result<FramebufferBuilder<Rp, (A, T)>, FramebufferCreationError>
     where
         T: ImageViewAbstract,
+    {
+        let dimensions = self.check_attachment(&attachment)?;
+
+        let mut raw_ids = self.raw_ids;
+        raw_ids.push(attachment.inner().internal_object());
+
+        Ok(FramebufferBuilder {
+            render_pass: self.render_pass,
+            raw_ids,
+            dimensions,
+            attachments: (self.attachments, attachment),
+        })
+    }
+
+    // Checks that `attachment` can be the next attachment of the framebuffer, and returns the
+    // dimensions of the framebuffer once it is added.
+    fn check_attachment<T>(
+        &self,
+        attachment: &T,
+    ) -> Result<FramebufferBuilderDimensions, FramebufferCreationError>
+    where
+        T: ?Sized + ImageViewAbstract,
     {
         if self.raw_ids.len() >= self.render_pass.num_attachments() {
             return Err(FramebufferCreationError::AttachmentsCountMismatch {
@@ -173,7 +230,7 @@ where
             });
         }
 
-        match ensure_image_view_compatible(&self.render_pass, self.raw_ids.len(), &attachment) {
+        match ensure_image_view_compatible(&self.render_pass, self.raw_ids.len(), attachment) {
             Ok(()) => (),
             Err(err) => return Err(FramebufferCreationError::IncompatibleAttachment(err)),
         };
@@ -182,13 +239,35 @@ where
         let array_layers = attachment.array_layers();
         debug_assert_eq!(image_dimensions.depth(), 1);
 
-        let view_dimensions = [
+        let mut view_dimensions = [
             image_dimensions.width(),
             image_dimensions.height(),
             array_layers.end - array_layers.start,
         ];
 
+        // With multiview, each view is rendered to one layer of the attachments while the
+        // framebuffer itself only has one layer.
+        let multiview_layers = multiview_layers(&self.render_pass);
+        if multiview_layers != 0 {
+            if view_dimensions[2] < multiview_layers {
+                return Err(FramebufferCreationError::MultiviewAttachmentLayersTooFew {
+                    required: multiview_layers,
+                    obtained: view_dimensions[2],
+                });
+            }
+            view_dimensions[2] = 1;
+        }
+
+        // The fragment density map covers the whole render area with texels that are larger than
+        // the fragments, and therefore doesn't take part in the dimensions of the framebuffer.
+        let is_density_map = self
+            .render_pass
+            .fragment_density_map_attachment()
+            .map(|(index, _)| index)
+            == Some(self.raw_ids.len());
+
         let dimensions = match self.dimensions {
+            dimensions if is_density_map => dimensions,
             FramebufferBuilderDimensions::AutoIdentical(None) => {
                 FramebufferBuilderDimensions::AutoIdentical(Some(view_dimensions))
             }
@@ -229,15 +308,7 @@ where
             }
         };
 
-        let mut raw_ids = self.raw_ids;
-        raw_ids.push(attachment.inner().internal_object());
-
-        Ok(FramebufferBuilder {
-            render_pass: self.render_pass,
-            raw_ids,
-            dimensions,
-            attachments: (self.attachments, attachment),
-        })
+        Ok(dimensions)
     }
 
     /// Turns this builder into a `FramebufferBuilder<Rp, Box<AttachmentsList>>`.
@@ -283,6 +354,10 @@ where
             }
         };
 
+        if multiview_layers(&self.render_pass) != 0 && dimensions[2] != 1 {
+            return Err(FramebufferCreationError::MultiviewLayersNotOne);
+        }
+
         // Checking the dimensions against the limits.
         {
             let limits = device.physical_device().limits();
@@ -315,7 +390,7 @@ where
             check_errors(vk.CreateFramebuffer(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -331,6 +406,18 @@ where
     }
 }
 
+// Returns the number of layers that the attachments of a framebuffer must have to contain all the
+// views of a render pass, or 0 if the render pass doesn't use multiview.
+pub(super) fn multiview_layers<Rp>(render_pass: &Rp) -> u32
+where
+    Rp: RenderPassDesc,
+{
+    let view_mask = (0..render_pass.num_subpasses())
+        .filter_map(|num| render_pass.subpass_desc(num))
+        .fold(0, |mask, pass| mask | pass.view_mask);
+    32 - view_mask.leading_zeros()
+}
+
 impl<Rp, A> Framebuffer<Rp, A> {
     /// Returns the width, height and layers of this framebuffer.
     #[inline]
@@ -423,6 +510,16 @@ where
     fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
         self.render_pass.dependency_desc(num)
     }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        self.render_pass.correlation_masks()
+    }
+
+    #[inline]
+    fn fragment_density_map_attachment(&self) -> Option<(usize, ImageLayout)> {
+        self.render_pass.fragment_density_map_attachment()
+    }
 }
 
 unsafe impl<C, Rp, A> RenderPassDescClearValues<C> for Framebuffer<Rp, A>
@@ -457,14 +554,18 @@ impl<Rp, A> Drop for Framebuffer<Rp, A> {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyFramebuffer(self.device.internal_object(), self.framebuffer, ptr::null());
+            vk.DestroyFramebuffer(
+                self.device.internal_object(),
+                self.framebuffer,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
 
 /// Opaque object that represents the internals of a framebuffer.
 #[derive(Debug, Copy, Clone)]
-pub struct FramebufferSys<'a>(vk::Framebuffer, PhantomData<&'a ()>);
+pub struct FramebufferSys<'a>(pub(super) vk::Framebuffer, pub(super) PhantomData<&'a ()>);
 
 unsafe impl<'a> VulkanObject for FramebufferSys<'a> {
     type Object = vk::Framebuffer;
@@ -502,6 +603,28 @@ pub enum FramebufferCreationError {
     IncompatibleAttachment(IncompatibleRenderPassAttachmentError),
     /// The framebuffer has no attachment and no dimension was specified.
     CantDetermineDimensions,
+    /// The render pass uses multiview, and an attachment doesn't have enough array layers to
+    /// contain all the views.
+    MultiviewAttachmentLayersTooFew {
+        /// Minimum number of array layers.
+        required: u32,
+        /// Number of array layers of the attachment.
+        obtained: u32,
+    },
+    /// The render pass uses multiview, but the number of layers of the framebuffer isn't 1.
+    MultiviewLayersNotOne,
+    /// The `khr_imageless_framebuffer` extension must be enabled in order to create an imageless
+    /// framebuffer.
+    ImagelessFramebufferExtensionNotEnabled,
+    /// The `imageless_framebuffer` feature must be enabled in order to create an imageless
+    /// framebuffer.
+    ImagelessFramebufferFeatureNotEnabled,
+    /// An image view attached to an imageless framebuffer doesn't match the description of its
+    /// attachment.
+    ImagelessAttachmentMismatch {
+        /// Index of the attachment.
+        attachment: usize,
+    },
 }
 
 impl From<OomError> for FramebufferCreationError {
@@ -545,6 +668,26 @@ impl fmt::Display for FramebufferCreationError {
                 FramebufferCreationError::CantDetermineDimensions => {
                     "the framebuffer has no attachment and no dimension was specified"
                 }
+                FramebufferCreationError::MultiviewAttachmentLayersTooFew { .. } => {
+                    "an attachment doesn't have enough array layers to contain all the views of \
+                     the render pass"
+                }
+                FramebufferCreationError::MultiviewLayersNotOne => {
+                    "the render pass uses multiview, but the number of layers of the framebuffer \
+                     isn't 1"
+                }
+                FramebufferCreationError::ImagelessFramebufferExtensionNotEnabled => {
+                    "the `khr_imageless_framebuffer` extension must be enabled in order to create \
+                     an imageless framebuffer"
+                }
+                FramebufferCreationError::ImagelessFramebufferFeatureNotEnabled => {
+                    "the `imageless_framebuffer` feature must be enabled in order to create an \
+                     imageless framebuffer"
+                }
+                FramebufferCreationError::ImagelessAttachmentMismatch { .. } => {
+                    "an image view attached to an imageless framebuffer doesn't match the \
+                     description of its attachment"
+                }
             }
         )
     }
@@ -566,6 +709,7 @@ mod tests {
     use crate::framebuffer::RenderPassDesc;
     use crate::image::attachment::AttachmentImage;
     use crate::image::view::ImageView;
+    use crate::image::view::ImageViewAbstract;
     use std::sync::Arc;
 
     #[test]
@@ -915,6 +1059,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn add_all() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = Arc::new(
+            single_pass_renderpass!(device.clone(),
+                attachments: {
+                    a: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    },
+                    b: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    }
+                },
+                pass: {
+                    color: [a, b],
+                    depth_stencil: {}
+                }
+            )
+            .unwrap(),
+        );
+
+        let view = || -> Arc<dyn ImageViewAbstract + Send + Sync> {
+            ImageView::new(
+                AttachmentImage::new(device.clone(), [256, 512], Format::R8G8B8A8Unorm).unwrap(),
+            )
+            .unwrap()
+        };
+
+        let framebuffer = Framebuffer::start(render_pass.clone())
+            .add_all(vec![view(), view()])
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(framebuffer.dimensions(), [256, 512, 1]);
+
+        match Framebuffer::start(render_pass).add_all(vec![view(), view(), view()]) {
+            Err(FramebufferCreationError::AttachmentsCountMismatch {
+                expected: 2,
+                obtained: 3,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn empty_working() {
         let (device, _) = gfx_dev_and_queue!();
@@ -0,0 +1,557 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::format::ClearValue;
+use crate::framebuffer::ensure_image_view_compatible;
+use crate::framebuffer::framebuffer::multiview_layers;
+use crate::framebuffer::AttachmentDescription;
+use crate::framebuffer::FramebufferAbstract;
+use crate::framebuffer::FramebufferCreationError;
+use crate::framebuffer::FramebufferSys;
+use crate::framebuffer::PassDependencyDescription;
+use crate::framebuffer::PassDescription;
+use crate::framebuffer::RenderPassAbstract;
+use crate::framebuffer::RenderPassDesc;
+use crate::framebuffer::RenderPassDescClearValues;
+use crate::framebuffer::RenderPassSys;
+use crate::image::view::ImageViewAbstract;
+use crate::image::ImageCreateFlags;
+use crate::image::ImageLayout;
+use crate::image::ImageUsage;
+
+use crate::check_errors;
+use crate::vk;
+use crate::VulkanObject;
+
+/// Describes the image views that will be attached to an attachment of an
+/// `ImagelessFramebuffer`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FramebufferAttachmentInfo {
+    /// Usage of the images of the views. Must be exactly the usage of the images.
+    pub usage: ImageUsage,
+    /// Creation flags of the images of the views. Must be exactly the flags of the images.
+    pub flags: ImageCreateFlags,
+    /// Width, height and number of array layers of the views.
+    pub dimensions: [u32; 3],
+}
+
+impl FramebufferAttachmentInfo {
+    /// Builds the description that matches an existing image view.
+    ///
+    /// This is convenient for swapchain images, which all share the same properties.
+    pub fn from_image_view<I>(image_view: &I) -> FramebufferAttachmentInfo
+    where
+        I: ?Sized + ImageViewAbstract,
+    {
+        let image = image_view.image().inner().image;
+        let array_layers = image_view.array_layers();
+
+        FramebufferAttachmentInfo {
+            usage: image.usage(),
+            flags: image.flags(),
+            dimensions: [
+                image_view.image().dimensions().width(),
+                image_view.image().dimensions().height(),
+                array_layers.end - array_layers.start,
+            ],
+        }
+    }
+}
+
+/// A framebuffer that is created from descriptions of its attachments instead of image views.
+///
+/// The image views are only given when the render pass begins, by calling `attach` and passing
+/// the result to `begin_render_pass`. This avoids creating one framebuffer for each image of a
+/// swapchain, or recreating framebuffers when the attachments are cycled.
+///
+/// Requires the `khr_imageless_framebuffer` extension and the `imageless_framebuffer` feature to
+/// be enabled on the device.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use vulkano::framebuffer::RenderPassAbstract;
+/// use vulkano::framebuffer::FramebufferAttachmentInfo;
+/// use vulkano::framebuffer::ImagelessFramebuffer;
+///
+/// # let render_pass: Arc<RenderPassAbstract + Send + Sync> = return;
+/// # let view: Arc<vulkano::image::view::ImageView<Arc<vulkano::image::AttachmentImage<vulkano::format::Format>>>> = return;
+/// let info = FramebufferAttachmentInfo::from_image_view(&view);
+/// let framebuffer = Arc::new(
+///     ImagelessFramebuffer::new(render_pass.clone(), info.dimensions, vec![info]).unwrap(),
+/// );
+///
+/// // Each frame:
+/// let attached = ImagelessFramebuffer::attach(&framebuffer, vec![view.clone()]).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ImagelessFramebuffer<Rp> {
+    device: Arc<Device>,
+    render_pass: Rp,
+    framebuffer: vk::Framebuffer,
+    dimensions: [u32; 3],
+    attachments: Vec<FramebufferAttachmentInfo>,
+}
+
+impl<Rp> ImagelessFramebuffer<Rp>
+where
+    Rp: RenderPassAbstract,
+{
+    /// Builds a new imageless framebuffer with the given dimensions and one description per
+    /// attachment of the render pass.
+    pub fn new(
+        render_pass: Rp,
+        dimensions: [u32; 3],
+        attachments: Vec<FramebufferAttachmentInfo>,
+    ) -> Result<ImagelessFramebuffer<Rp>, FramebufferCreationError> {
+        let device = render_pass.device().clone();
+
+        if !device.loaded_extensions_or_core().khr_imageless_framebuffer {
+            return Err(FramebufferCreationError::ImagelessFramebufferExtensionNotEnabled);
+        }
+        if !device.enabled_features().imageless_framebuffer {
+            return Err(FramebufferCreationError::ImagelessFramebufferFeatureNotEnabled);
+        }
+
+        if attachments.len() != render_pass.num_attachments() {
+            return Err(FramebufferCreationError::AttachmentsCountMismatch {
+                expected: render_pass.num_attachments(),
+                obtained: attachments.len(),
+            });
+        }
+
+        let multiview_layers = multiview_layers(&render_pass);
+        if multiview_layers != 0 && dimensions[2] != 1 {
+            return Err(FramebufferCreationError::MultiviewLayersNotOne);
+        }
+
+        // The fragment density map doesn't need to be as large as the framebuffer.
+        let density_map = render_pass.fragment_density_map_attachment().map(|r| r.0);
+
+        for (num, attachment) in attachments.iter().enumerate() {
+            if multiview_layers != 0 && attachment.dimensions[2] < multiview_layers {
+                return Err(FramebufferCreationError::MultiviewAttachmentLayersTooFew {
+                    required: multiview_layers,
+                    obtained: attachment.dimensions[2],
+                });
+            }
+
+            let view_dimensions = [
+                attachment.dimensions[0],
+                attachment.dimensions[1],
+                if multiview_layers != 0 {
+                    1
+                } else {
+                    attachment.dimensions[2]
+                },
+            ];
+            if density_map != Some(num) && (0..3).any(|i| view_dimensions[i] < dimensions[i]) {
+                return Err(FramebufferCreationError::AttachmentDimensionsIncompatible {
+                    expected: dimensions,
+                    obtained: view_dimensions,
+                });
+            }
+        }
+
+        {
+            let limits = device.physical_device().limits();
+            let limits = [
+                limits.max_framebuffer_width(),
+                limits.max_framebuffer_height(),
+                limits.max_framebuffer_layers(),
+            ];
+            if dimensions[0] > limits[0] || dimensions[1] > limits[1] || dimensions[2] > limits[2] {
+                return Err(FramebufferCreationError::DimensionsTooLarge);
+            }
+        }
+
+        let framebuffer = unsafe {
+            let vk = device.pointers();
+
+            // The views attached to each attachment must have the format of the attachment.
+            let formats = render_pass
+                .attachment_descs()
+                .map(|attachment| attachment.format as u32)
+                .collect::<SmallVec<[_; 8]>>();
+
+            let image_infos = attachments
+                .iter()
+                .zip(formats.iter())
+                .map(|(attachment, format)| vk::FramebufferAttachmentImageInfo {
+                    sType: vk::STRUCTURE_TYPE_FRAMEBUFFER_ATTACHMENT_IMAGE_INFO,
+                    pNext: ptr::null(),
+                    flags: attachment.flags.into(),
+                    usage: attachment.usage.to_usage_bits(),
+                    width: attachment.dimensions[0],
+                    height: attachment.dimensions[1],
+                    layerCount: attachment.dimensions[2],
+                    viewFormatCount: 1,
+                    pViewFormats: format,
+                })
+                .collect::<SmallVec<[_; 8]>>();
+
+            let attachments_info = vk::FramebufferAttachmentsCreateInfo {
+                sType: vk::STRUCTURE_TYPE_FRAMEBUFFER_ATTACHMENTS_CREATE_INFO,
+                pNext: ptr::null(),
+                attachmentImageInfoCount: image_infos.len() as u32,
+                pAttachmentImageInfos: image_infos.as_ptr(),
+            };
+
+            let infos = vk::FramebufferCreateInfo {
+                sType: vk::STRUCTURE_TYPE_FRAMEBUFFER_CREATE_INFO,
+                pNext: &attachments_info as *const _ as *const _,
+                flags: vk::FRAMEBUFFER_CREATE_IMAGELESS_BIT,
+                renderPass: render_pass.inner().internal_object(),
+                attachmentCount: image_infos.len() as u32,
+                pAttachments: ptr::null(),
+                width: dimensions[0],
+                height: dimensions[1],
+                layers: dimensions[2],
+            };
+
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateFramebuffer(
+                device.internal_object(),
+                &infos,
+                device.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(ImagelessFramebuffer {
+            device,
+            render_pass,
+            framebuffer,
+            dimensions,
+            attachments,
+        })
+    }
+
+    /// Binds image views to the framebuffer, in the order of the attachments of the render pass.
+    ///
+    /// The returned object can be passed to `begin_render_pass`. Each image view must match the
+    /// description of its attachment and be compatible with the render pass.
+    pub fn attach(
+        me: &Arc<Self>,
+        image_views: Vec<Arc<dyn ImageViewAbstract + Send + Sync>>,
+    ) -> Result<ImagelessFramebufferAttachments<Rp>, FramebufferCreationError> {
+        if image_views.len() != me.attachments.len() {
+            return Err(FramebufferCreationError::AttachmentsCountMismatch {
+                expected: me.attachments.len(),
+                obtained: image_views.len(),
+            });
+        }
+
+        for (num, image_view) in image_views.iter().enumerate() {
+            match ensure_image_view_compatible(&me.render_pass, num, image_view) {
+                Ok(()) => (),
+                Err(err) => return Err(FramebufferCreationError::IncompatibleAttachment(err)),
+            };
+
+            if FramebufferAttachmentInfo::from_image_view(image_view) != me.attachments[num] {
+                return Err(FramebufferCreationError::ImagelessAttachmentMismatch {
+                    attachment: num,
+                });
+            }
+        }
+
+        Ok(ImagelessFramebufferAttachments {
+            framebuffer: me.clone(),
+            image_views,
+        })
+    }
+}
+
+impl<Rp> ImagelessFramebuffer<Rp> {
+    /// Returns the width, height and layers of this framebuffer.
+    #[inline]
+    pub fn dimensions(&self) -> [u32; 3] {
+        self.dimensions
+    }
+
+    /// Returns the descriptions of the attachments of this framebuffer.
+    #[inline]
+    pub fn attachments(&self) -> &[FramebufferAttachmentInfo] {
+        &self.attachments
+    }
+
+    /// Returns the renderpass that was used to create this framebuffer.
+    #[inline]
+    pub fn render_pass(&self) -> &Rp {
+        &self.render_pass
+    }
+}
+
+unsafe impl<Rp> RenderPassDesc for ImagelessFramebuffer<Rp>
+where
+    Rp: RenderPassDesc,
+{
+    #[inline]
+    fn num_attachments(&self) -> usize {
+        self.render_pass.num_attachments()
+    }
+
+    #[inline]
+    fn attachment_desc(&self, num: usize) -> Option<AttachmentDescription> {
+        self.render_pass.attachment_desc(num)
+    }
+
+    #[inline]
+    fn num_subpasses(&self) -> usize {
+        self.render_pass.num_subpasses()
+    }
+
+    #[inline]
+    fn subpass_desc(&self, num: usize) -> Option<PassDescription> {
+        self.render_pass.subpass_desc(num)
+    }
+
+    #[inline]
+    fn num_dependencies(&self) -> usize {
+        self.render_pass.num_dependencies()
+    }
+
+    #[inline]
+    fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
+        self.render_pass.dependency_desc(num)
+    }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        self.render_pass.correlation_masks()
+    }
+
+    #[inline]
+    fn fragment_density_map_attachment(&self) -> Option<(usize, ImageLayout)> {
+        self.render_pass.fragment_density_map_attachment()
+    }
+}
+
+unsafe impl<C, Rp> RenderPassDescClearValues<C> for ImagelessFramebuffer<Rp>
+where
+    Rp: RenderPassDescClearValues<C>,
+{
+    #[inline]
+    fn convert_clear_values(&self, vals: C) -> Box<dyn Iterator<Item = ClearValue>> {
+        self.render_pass.convert_clear_values(vals)
+    }
+}
+
+unsafe impl<Rp> RenderPassAbstract for ImagelessFramebuffer<Rp>
+where
+    Rp: RenderPassAbstract,
+{
+    #[inline]
+    fn inner(&self) -> RenderPassSys<'_> {
+        self.render_pass.inner()
+    }
+}
+
+unsafe impl<Rp> DeviceOwned for ImagelessFramebuffer<Rp> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl<Rp> Drop for ImagelessFramebuffer<Rp> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroyFramebuffer(
+                self.device.internal_object(),
+                self.framebuffer,
+                self.device.alloc_ptr(),
+            );
+        }
+    }
+}
+
+/// An `ImagelessFramebuffer` along with the image views to use when beginning a render pass.
+///
+/// Created with `ImagelessFramebuffer::attach`. This object is cheap to create and clone, and
+/// implements `FramebufferAbstract` so that it can be passed to `begin_render_pass`.
+pub struct ImagelessFramebufferAttachments<Rp> {
+    framebuffer: Arc<ImagelessFramebuffer<Rp>>,
+    image_views: Vec<Arc<dyn ImageViewAbstract + Send + Sync>>,
+}
+
+impl<Rp> ImagelessFramebufferAttachments<Rp> {
+    /// Returns the framebuffer that the image views are attached to.
+    #[inline]
+    pub fn framebuffer(&self) -> &Arc<ImagelessFramebuffer<Rp>> {
+        &self.framebuffer
+    }
+}
+
+impl<Rp> Clone for ImagelessFramebufferAttachments<Rp> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ImagelessFramebufferAttachments {
+            framebuffer: self.framebuffer.clone(),
+            image_views: self.image_views.clone(),
+        }
+    }
+}
+
+unsafe impl<Rp> FramebufferAbstract for ImagelessFramebufferAttachments<Rp>
+where
+    Rp: RenderPassAbstract,
+{
+    #[inline]
+    fn inner(&self) -> FramebufferSys<'_> {
+        FramebufferSys(self.framebuffer.framebuffer, PhantomData)
+    }
+
+    #[inline]
+    fn dimensions(&self) -> [u32; 3] {
+        self.framebuffer.dimensions
+    }
+
+    #[inline]
+    fn attached_image_view(&self, index: usize) -> Option<&dyn ImageViewAbstract> {
+        self.image_views
+            .get(index)
+            .map(|view| &**view as &dyn ImageViewAbstract)
+    }
+
+    #[inline]
+    fn is_imageless(&self) -> bool {
+        true
+    }
+}
+
+unsafe impl<Rp> RenderPassDesc for ImagelessFramebufferAttachments<Rp>
+where
+    Rp: RenderPassDesc,
+{
+    #[inline]
+    fn num_attachments(&self) -> usize {
+        self.framebuffer.num_attachments()
+    }
+
+    #[inline]
+    fn attachment_desc(&self, num: usize) -> Option<AttachmentDescription> {
+        self.framebuffer.attachment_desc(num)
+    }
+
+    #[inline]
+    fn num_subpasses(&self) -> usize {
+        self.framebuffer.num_subpasses()
+    }
+
+    #[inline]
+    fn subpass_desc(&self, num: usize) -> Option<PassDescription> {
+        self.framebuffer.subpass_desc(num)
+    }
+
+    #[inline]
+    fn num_dependencies(&self) -> usize {
+        self.framebuffer.num_dependencies()
+    }
+
+    #[inline]
+    fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
+        self.framebuffer.dependency_desc(num)
+    }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        self.framebuffer.correlation_masks()
+    }
+
+    #[inline]
+    fn fragment_density_map_attachment(&self) -> Option<(usize, ImageLayout)> {
+        self.framebuffer.fragment_density_map_attachment()
+    }
+}
+
+unsafe impl<C, Rp> RenderPassDescClearValues<C> for ImagelessFramebufferAttachments<Rp>
+where
+    Rp: RenderPassDescClearValues<C>,
+{
+    #[inline]
+    fn convert_clear_values(&self, vals: C) -> Box<dyn Iterator<Item = ClearValue>> {
+        self.framebuffer.convert_clear_values(vals)
+    }
+}
+
+unsafe impl<Rp> RenderPassAbstract for ImagelessFramebufferAttachments<Rp>
+where
+    Rp: RenderPassAbstract,
+{
+    #[inline]
+    fn inner(&self) -> RenderPassSys<'_> {
+        self.framebuffer.render_pass.inner()
+    }
+}
+
+unsafe impl<Rp> DeviceOwned for ImagelessFramebufferAttachments<Rp> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.framebuffer.device
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Format;
+    use crate::framebuffer::FramebufferAttachmentInfo;
+    use crate::framebuffer::FramebufferCreationError;
+    use crate::framebuffer::ImagelessFramebuffer;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageUsage;
+    use std::sync::Arc;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = Arc::new(
+            single_pass_renderpass!(device.clone(),
+                attachments: {
+                    color: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    }
+                },
+                pass: {
+                    color: [color],
+                    depth_stencil: {}
+                }
+            )
+            .unwrap(),
+        );
+
+        let info = FramebufferAttachmentInfo {
+            usage: ImageUsage {
+                color_attachment: true,
+                ..ImageUsage::none()
+            },
+            flags: ImageCreateFlags::none(),
+            dimensions: [512, 512, 1],
+        };
+
+        match ImagelessFramebuffer::new(render_pass, [512, 512, 1], vec![info]) {
+            Err(FramebufferCreationError::ImagelessFramebufferExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+}
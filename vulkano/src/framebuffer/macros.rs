@@ -8,6 +8,92 @@
 // according to those terms.
 
 /// Builds a `RenderPass` object whose template parameter is of indeterminate type.
+///
+/// The optional `resolve` list makes the render pass resolve multisampled color attachments at
+/// the end of the pass. Each color attachment is resolved into the attachment at the same
+/// position in the list, which must have one sample and the same format.
+///
+/// ```
+/// # #[macro_use] extern crate vulkano;
+/// # fn main() {
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// use vulkano::format::Format;
+///
+/// let render_pass = single_pass_renderpass!(device.clone(),
+///     attachments: {
+///         intermediary: {
+///             load: Clear,
+///             store: DontCare,
+///             format: Format::R8G8B8A8Unorm,
+///             samples: 4,
+///         },
+///         color: {
+///             load: DontCare,
+///             store: Store,
+///             format: Format::R8G8B8A8Unorm,
+///             samples: 1,
+///         }
+///     },
+///     pass: {
+///         color: [intermediary],
+///         depth_stencil: {},
+///         resolve: [color],
+///     }
+/// ).unwrap();
+/// # }
+/// ```
+///
+/// The optional `dependencies` list contains `PassDependencyDescription`s that override or
+/// augment the dependencies that are automatically derived from the subpasses. A dependency
+/// replaces the derived one with the same source and destination subpasses, if any, and is
+/// added to the render pass otherwise. See `derive_dependencies` for more information.
+///
+/// ```
+/// # #[macro_use] extern crate vulkano;
+/// # fn main() {
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// use vulkano::format::Format;
+/// use vulkano::framebuffer::PassDependencyDescription;
+/// use vulkano::sync::AccessFlagBits;
+/// use vulkano::sync::PipelineStages;
+///
+/// let color_output = PipelineStages {
+///     color_attachment_output: true,
+///     ..PipelineStages::none()
+/// };
+///
+/// let render_pass = single_pass_renderpass!(device.clone(),
+///     attachments: {
+///         color: {
+///             load: Clear,
+///             store: Store,
+///             format: Format::R8G8B8A8Unorm,
+///             samples: 1,
+///         }
+///     },
+///     pass: {
+///         color: [color],
+///         depth_stencil: {}
+///     },
+///     dependencies: [
+///         // Waits for the previous uses of the color attachment before writing to it.
+///         PassDependencyDescription {
+///             source_subpass: PassDependencyDescription::EXTERNAL_SUBPASS,
+///             destination_subpass: 0,
+///             source_stages: color_output,
+///             destination_stages: color_output,
+///             source_access: AccessFlagBits::none(),
+///             destination_access: AccessFlagBits {
+///                 color_attachment_write: true,
+///                 ..AccessFlagBits::none()
+///             },
+///             by_region: false,
+///             view_local: None,
+///         }
+///     ]
+/// ).unwrap();
+/// # }
+/// ```
 #[macro_export]
 macro_rules! single_pass_renderpass {
     (
@@ -18,6 +104,8 @@ macro_rules! single_pass_renderpass {
             depth_stencil: {$($depth_atch:ident)*}$(,)*
             $(resolve: [$($resolve_atch:ident),*])*$(,)*
         }
+        $(, dependencies: [$($dependency:expr),* $(,)*])*
+        $(,)*
     ) => (
         $crate::ordered_passes_renderpass!(
             $device,
@@ -29,12 +117,17 @@ macro_rules! single_pass_renderpass {
                     input: [],
                     resolve: [$($($resolve_atch),*)*]
                 }
-            ]
+            ],
+            dependencies: [$($($dependency),*)*]
         )
     )
 }
 
 /// Builds a `RenderPass` object whose template parameter is of indeterminate type.
+///
+/// Each pass can have an optional `resolve` list, and the render pass an optional `dependencies`
+/// list, with the same meaning as in `single_pass_renderpass!`. By default, each subpass depends
+/// on the previous one with `PassDependencyDescription::sequential`.
 #[macro_export]
 macro_rules! ordered_passes_renderpass {
     (
@@ -61,6 +154,8 @@ macro_rules! ordered_passes_renderpass {
                 }
             ),*
         ]
+        $(, dependencies: [$($dependency:expr),* $(,)*])*
+        $(,)*
     ) => ({
         use $crate::framebuffer::RenderPassDesc;
 
@@ -76,13 +171,12 @@ macro_rules! ordered_passes_renderpass {
             use $crate::framebuffer::PassDescription;
             use $crate::framebuffer::PassDependencyDescription;
             use $crate::image::ImageLayout;
-            use $crate::sync::AccessFlagBits;
-            use $crate::sync::PipelineStages;
 
             pub struct CustomRenderPassDesc {
                 $(
                     pub $atch_name: (Format, u32),
                 )*
+                pub __dependencies: Vec<PassDependencyDescription>,
             }
 
             #[allow(unsafe_code)]
@@ -109,12 +203,12 @@ macro_rules! ordered_passes_renderpass {
 
                 #[inline]
                 fn num_dependencies(&self) -> usize {
-                    num_dependencies()
+                    self.__dependencies.len()
                 }
 
                 #[inline]
                 fn dependency_desc(&self, id: usize) -> Option<PassDependencyDescription> {
-                    dependency(id)
+                    self.__dependencies.get(id).cloned()
                 }
             }
 
@@ -209,7 +303,7 @@ macro_rules! ordered_passes_renderpass {
                             ],
                             resolve_attachments: vec![
                                 $($(
-                                    ($resolve_atch, ImageLayout::TransferDstOptimal)
+                                    ($resolve_atch, ImageLayout::ColorAttachmentOptimal)
                                 ),*)*
                             ],
                             preserve_attachments: (0 .. attachment_num).filter(|&a| {
@@ -218,7 +312,11 @@ macro_rules! ordered_passes_renderpass {
                                 $(if a == $input_atch { return false; })*
                                 $($(if a == $resolve_atch { return false; })*)*
                                 true
-                            }).collect()
+                            }).collect(),
+                            fragment_shading_rate_attachment: None,
+                            view_mask: 0,
+                            depth_stencil_resolve: None,
+                            multisampled_render_to_single_sampled: None,
                         };
 
                         assert!(desc.resolve_attachments.is_empty() ||
@@ -232,28 +330,10 @@ macro_rules! ordered_passes_renderpass {
                 None
             }
 
-            #[inline]
-            fn num_dependencies() -> usize {
-                num_subpasses().saturating_sub(1)
-            }
-
-            #[inline]
-            fn dependency(id: usize) -> Option<PassDependencyDescription> {
-                let num_passes = num_subpasses();
-
-                if id + 1 >= num_passes {
-                    return None;
-                }
-
-                Some(PassDependencyDescription {
-                    source_subpass: id,
-                    destination_subpass: id + 1,
-                    source_stages: PipelineStages { all_graphics: true, .. PipelineStages::none() },         // TODO: correct values
-                    destination_stages: PipelineStages { all_graphics: true, .. PipelineStages::none() },         // TODO: correct values
-                    source_access: AccessFlagBits::all(),         // TODO: correct values
-                    destination_access: AccessFlagBits::all(),         // TODO: correct values
-                    by_region: true,            // TODO: correct values
-                })
+            /// Returns the dependencies derived from the subpasses, overridden or augmented by
+            /// the ones given to the macro.
+            pub fn dependencies(explicit: Vec<PassDependencyDescription>) -> Vec<PassDependencyDescription> {
+                $crate::framebuffer::derive_dependencies(num_subpasses(), explicit)
             }
 
             /// Returns the initial and final layout of an attachment, given its num.
@@ -295,9 +375,9 @@ macro_rules! ordered_passes_renderpass {
                     $($(
                         if $resolve_atch == num {
                             if initial_layout.is_none() {
-                                initial_layout = Some(ImageLayout::TransferDstOptimal);
+                                initial_layout = Some(ImageLayout::ColorAttachmentOptimal);
                             }
-                            final_layout = Some(ImageLayout::TransferDstOptimal);
+                            final_layout = Some(ImageLayout::ColorAttachmentOptimal);
                         }
                     )*)*
 
@@ -330,6 +410,7 @@ macro_rules! ordered_passes_renderpass {
             $(
                 $atch_name: ($format, $samples),
             )*
+            __dependencies: scope::dependencies(vec![$($($dependency),*)*]),
         }.build_render_pass($device)
     });
 }
@@ -337,6 +418,8 @@ macro_rules! ordered_passes_renderpass {
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
+    use crate::framebuffer::PassDependencyDescription;
+    use crate::framebuffer::RenderPassDesc;
 
     #[test]
     fn single_pass_resolve() {
@@ -364,4 +447,42 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn explicit_dependencies() {
+        let (device, _) = gfx_dev_and_queue!();
+        let render_pass = ordered_passes_renderpass!(device.clone(),
+            attachments: {
+                a: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            passes: [
+                { color: [a], depth_stencil: {}, input: [] },
+                { color: [a], depth_stencil: {}, input: [] }
+            ],
+            dependencies: [
+                PassDependencyDescription {
+                    by_region: false,
+                    ..PassDependencyDescription::sequential(0)
+                },
+                PassDependencyDescription {
+                    source_subpass: PassDependencyDescription::EXTERNAL_SUBPASS,
+                    destination_subpass: 0,
+                    ..PassDependencyDescription::sequential(0)
+                },
+            ]
+        )
+        .unwrap();
+
+        assert_eq!(render_pass.num_dependencies(), 2);
+        assert!(!render_pass.dependency_desc(0).unwrap().by_region);
+        assert_eq!(
+            render_pass.dependency_desc(1).unwrap().source_subpass,
+            PassDependencyDescription::EXTERNAL_SUBPASS
+        );
+    }
 }
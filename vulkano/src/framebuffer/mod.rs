@@ -81,6 +81,10 @@
 //!
 //! See the documentation of the macro for more details. TODO: put link here
 //!
+//! If the attachments and subpasses are only known at runtime, for example because they are
+//! loaded from a file, you can instead create a `RuntimeRenderPassDesc` from lists of
+//! `AttachmentDescription`, `PassDescription` and `PassDependencyDescription`.
+//!
 //! Once a `RenderPass<_>` struct is created, it implements the same render-pass-related traits as
 //! its template parameter.
 //!
@@ -93,6 +97,7 @@
 pub use self::attachments_list::AttachmentsList;
 pub use self::compat_atch::ensure_image_view_compatible;
 pub use self::compat_atch::IncompatibleRenderPassAttachmentError;
+pub use self::desc::derive_dependencies;
 pub use self::desc::AttachmentDescription;
 pub use self::desc::LoadOp;
 pub use self::desc::PassDependencyDescription;
@@ -101,16 +106,24 @@ pub use self::desc::RenderPassDesc;
 pub use self::desc::RenderPassDescAttachments;
 pub use self::desc::RenderPassDescDependencies;
 pub use self::desc::RenderPassDescSubpasses;
+pub use self::desc::ResolveMode;
+pub use self::desc::ResolveModes;
 pub use self::desc::StoreOp;
 pub use self::empty::EmptySinglePassRenderPassDesc;
 pub use self::framebuffer::Framebuffer;
 pub use self::framebuffer::FramebufferBuilder;
 pub use self::framebuffer::FramebufferCreationError;
 pub use self::framebuffer::FramebufferSys;
+pub use self::imageless::FramebufferAttachmentInfo;
+pub use self::imageless::ImagelessFramebuffer;
+pub use self::imageless::ImagelessFramebufferAttachments;
+pub use self::runtime::RuntimeRenderPassDesc;
+pub use self::runtime::RuntimeRenderPassDescError;
 pub use self::sys::RenderPass;
 pub use self::sys::RenderPassCreationError;
 pub use self::sys::RenderPassSys;
 pub use self::traits::FramebufferAbstract;
+pub use self::traits::IncompatibleRenderPassError;
 pub use self::traits::RenderPassAbstract;
 pub use self::traits::RenderPassCompatible;
 pub use self::traits::RenderPassDescClearValues;
@@ -124,5 +137,7 @@ mod compat_atch;
 mod desc;
 mod empty;
 mod framebuffer;
+mod imageless;
+mod runtime;
 mod sys;
 mod traits;
@@ -0,0 +1,563 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use crate::format::ClearValue;
+use crate::format::FormatTy;
+use crate::framebuffer::derive_dependencies;
+use crate::framebuffer::AttachmentDescription;
+use crate::framebuffer::PassDependencyDescription;
+use crate::framebuffer::PassDescription;
+use crate::framebuffer::RenderPassDesc;
+use crate::framebuffer::RenderPassDescClearValues;
+use crate::image::ImageLayout;
+use crate::vk;
+
+/// Description of a render pass built from runtime values.
+///
+/// Contrary to the `single_pass_renderpass!` and `ordered_passes_renderpass!` macros, the
+/// attachments, subpasses and dependencies are regular values. This makes it possible to build
+/// render passes from data that is only known at runtime, for example a frame description loaded
+/// from a file.
+///
+/// The description is checked when it is created, so that an invalid description results in an
+/// error instead of a panic or undefined behavior when building the render pass.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::format::Format;
+/// use vulkano::framebuffer::AttachmentDescription;
+/// use vulkano::framebuffer::LoadOp;
+/// use vulkano::framebuffer::PassDescription;
+/// use vulkano::framebuffer::RenderPassDesc;
+/// use vulkano::framebuffer::RuntimeRenderPassDesc;
+/// use vulkano::framebuffer::StoreOp;
+/// use vulkano::image::ImageLayout;
+///
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// let color = AttachmentDescription {
+///     format: Format::R8G8B8A8Unorm,
+///     samples: 1,
+///     load: LoadOp::Clear,
+///     store: StoreOp::Store,
+///     stencil_load: LoadOp::DontCare,
+///     stencil_store: StoreOp::DontCare,
+///     initial_layout: ImageLayout::Undefined,
+///     final_layout: ImageLayout::ColorAttachmentOptimal,
+/// };
+///
+/// let pass = PassDescription {
+///     color_attachments: vec![(0, ImageLayout::ColorAttachmentOptimal)],
+///     depth_stencil: None,
+///     input_attachments: vec![],
+///     resolve_attachments: vec![],
+///     preserve_attachments: vec![],
+///     fragment_shading_rate_attachment: None,
+///     view_mask: 0,
+///     depth_stencil_resolve: None,
+///     multisampled_render_to_single_sampled: None,
+/// };
+///
+/// let desc = RuntimeRenderPassDesc::new(vec![color], vec![pass], vec![]).unwrap();
+/// let render_pass = desc.build_render_pass(device.clone()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuntimeRenderPassDesc {
+    attachments: Vec<AttachmentDescription>,
+    subpasses: Vec<PassDescription>,
+    dependencies: Vec<PassDependencyDescription>,
+    correlation_masks: Vec<u32>,
+    fragment_density_map_attachment: Option<(usize, ImageLayout)>,
+}
+
+impl RuntimeRenderPassDesc {
+    /// Builds a new description from its attachments, subpasses and dependencies.
+    ///
+    /// The indices in the subpasses refer to the list of attachments, and the indices in the
+    /// dependencies refer to the list of subpasses. A dependency can use
+    /// `PassDependencyDescription::EXTERNAL_SUBPASS` as its source or destination to refer to the
+    /// commands outside of the render pass.
+    pub fn new(
+        attachments: Vec<AttachmentDescription>,
+        subpasses: Vec<PassDescription>,
+        dependencies: Vec<PassDependencyDescription>,
+    ) -> Result<RuntimeRenderPassDesc, RuntimeRenderPassDescError> {
+        if subpasses.is_empty() {
+            return Err(RuntimeRenderPassDescError::NoSubpass);
+        }
+
+        for (num, attachment) in attachments.iter().enumerate() {
+            if !attachment.samples.is_power_of_two() {
+                return Err(RuntimeRenderPassDescError::InvalidSamplesCount { attachment: num });
+            }
+        }
+
+        for (num, pass) in subpasses.iter().enumerate() {
+            let indices = pass
+                .color_attachments
+                .iter()
+                .chain(pass.depth_stencil.iter())
+                .chain(pass.input_attachments.iter())
+                .chain(pass.resolve_attachments.iter())
+                .map(|&(index, _)| index)
+                .chain(pass.preserve_attachments.iter().cloned())
+                .chain(
+                    pass.fragment_shading_rate_attachment
+                        .iter()
+                        .map(|&(index, _, _)| index),
+                )
+                .chain(
+                    pass.depth_stencil_resolve
+                        .iter()
+                        .map(|&(index, _, _, _)| index),
+                );
+
+            for index in indices {
+                if index >= attachments.len() {
+                    return Err(RuntimeRenderPassDescError::AttachmentOutOfRange {
+                        subpass: num,
+                        attachment: index,
+                    });
+                }
+            }
+
+            let depth_stencil = pass.depth_stencil.iter().map(|&(index, _)| index).chain(
+                pass.depth_stencil_resolve
+                    .iter()
+                    .map(|&(index, _, _, _)| index),
+            );
+            for index in depth_stencil {
+                match attachments[index].format.ty() {
+                    FormatTy::Depth | FormatTy::Stencil | FormatTy::DepthStencil => (),
+                    _ => {
+                        return Err(RuntimeRenderPassDescError::NotDepthStencilFormat {
+                            subpass: num,
+                            attachment: index,
+                        })
+                    }
+                }
+            }
+
+            if !pass.resolve_attachments.is_empty()
+                && pass.resolve_attachments.len() != pass.color_attachments.len()
+            {
+                return Err(
+                    RuntimeRenderPassDescError::ResolveAttachmentsCountMismatch { subpass: num },
+                );
+            }
+
+            // Subpasses that render multisampled to single-sampled attachments can mix
+            // single-sampled and multisampled attachments, which is checked by the render pass.
+            if pass.multisampled_render_to_single_sampled.is_none() {
+                let mut samples = pass
+                    .color_attachments
+                    .iter()
+                    .chain(pass.depth_stencil.iter())
+                    .map(|&(index, _)| attachments[index].samples);
+                if let Some(first) = samples.next() {
+                    if samples.any(|s| s != first) {
+                        return Err(RuntimeRenderPassDescError::SamplesCountMismatch {
+                            subpass: num,
+                        });
+                    }
+                }
+            }
+        }
+
+        for (num, dependency) in dependencies.iter().enumerate() {
+            let in_range = |subpass: usize| {
+                subpass as u32 == vk::SUBPASS_EXTERNAL || subpass < subpasses.len()
+            };
+
+            if !in_range(dependency.source_subpass) || !in_range(dependency.destination_subpass) {
+                return Err(RuntimeRenderPassDescError::SubpassOutOfRange { dependency: num });
+            }
+
+            let external_source = dependency.source_subpass as u32 == vk::SUBPASS_EXTERNAL;
+            let external_destination =
+                dependency.destination_subpass as u32 == vk::SUBPASS_EXTERNAL;
+            if (external_source && external_destination)
+                || (!external_source
+                    && !external_destination
+                    && dependency.source_subpass > dependency.destination_subpass)
+            {
+                return Err(RuntimeRenderPassDescError::BackwardDependency { dependency: num });
+            }
+        }
+
+        Ok(RuntimeRenderPassDesc {
+            attachments,
+            subpasses,
+            dependencies,
+            correlation_masks: Vec::new(),
+            fragment_density_map_attachment: None,
+        })
+    }
+
+    /// Builds a new description from its attachments and subpasses, with the same dependencies
+    /// as the ones automatically derived by the render pass macros.
+    ///
+    /// The dependencies in `dependencies` override or augment the derived ones. See
+    /// `derive_dependencies` for more information.
+    pub fn with_automatic_dependencies(
+        attachments: Vec<AttachmentDescription>,
+        subpasses: Vec<PassDescription>,
+        dependencies: Vec<PassDependencyDescription>,
+    ) -> Result<RuntimeRenderPassDesc, RuntimeRenderPassDescError> {
+        let dependencies = derive_dependencies(subpasses.len(), dependencies);
+        RuntimeRenderPassDesc::new(attachments, subpasses, dependencies)
+    }
+
+    /// Returns the list of attachments of the render pass.
+    #[inline]
+    pub fn attachments(&self) -> &[AttachmentDescription] {
+        &self.attachments
+    }
+
+    /// Returns the list of subpasses of the render pass.
+    #[inline]
+    pub fn subpasses(&self) -> &[PassDescription] {
+        &self.subpasses
+    }
+
+    /// Returns the list of dependencies of the render pass.
+    #[inline]
+    pub fn dependencies(&self) -> &[PassDependencyDescription] {
+        &self.dependencies
+    }
+
+    /// Sets the correlation masks of the render pass, for render passes that use multiview.
+    ///
+    /// See `RenderPassDesc::correlation_masks`.
+    #[inline]
+    pub fn set_correlation_masks(&mut self, masks: Vec<u32>) {
+        self.correlation_masks = masks;
+    }
+
+    /// Sets the attachment that holds the fragment density map of the render pass.
+    ///
+    /// See `RenderPassDesc::fragment_density_map_attachment`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the attachment index is out of range.
+    ///
+    #[inline]
+    pub fn set_fragment_density_map_attachment(
+        &mut self,
+        attachment: Option<(usize, ImageLayout)>,
+    ) {
+        if let Some((index, _)) = attachment {
+            assert!(index < self.attachments.len());
+        }
+
+        self.fragment_density_map_attachment = attachment;
+    }
+}
+
+unsafe impl RenderPassDesc for RuntimeRenderPassDesc {
+    #[inline]
+    fn num_attachments(&self) -> usize {
+        self.attachments.len()
+    }
+
+    #[inline]
+    fn attachment_desc(&self, num: usize) -> Option<AttachmentDescription> {
+        self.attachments.get(num).cloned()
+    }
+
+    #[inline]
+    fn num_subpasses(&self) -> usize {
+        self.subpasses.len()
+    }
+
+    #[inline]
+    fn subpass_desc(&self, num: usize) -> Option<PassDescription> {
+        self.subpasses.get(num).cloned()
+    }
+
+    #[inline]
+    fn num_dependencies(&self) -> usize {
+        self.dependencies.len()
+    }
+
+    #[inline]
+    fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
+        self.dependencies.get(num).cloned()
+    }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        self.correlation_masks.clone()
+    }
+
+    #[inline]
+    fn fragment_density_map_attachment(&self) -> Option<(usize, ImageLayout)> {
+        self.fragment_density_map_attachment
+    }
+}
+
+unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for RuntimeRenderPassDesc {
+    fn convert_clear_values(
+        &self,
+        values: Vec<ClearValue>,
+    ) -> Box<dyn Iterator<Item = ClearValue>> {
+        // FIXME: safety checks
+        Box::new(values.into_iter())
+    }
+}
+
+/// Error that can happen when creating a `RuntimeRenderPassDesc`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RuntimeRenderPassDescError {
+    /// A render pass must contain at least one subpass.
+    NoSubpass,
+    /// The number of samples of an attachment is not a power of two.
+    InvalidSamplesCount { attachment: usize },
+    /// A subpass refers to an attachment that doesn't exist.
+    AttachmentOutOfRange { subpass: usize, attachment: usize },
+    /// The depth-stencil attachment or depth-stencil resolve attachment of a subpass doesn't have
+    /// a depth or stencil format.
+    NotDepthStencilFormat { subpass: usize, attachment: usize },
+    /// A subpass has resolve attachments, but not as many as it has color attachments.
+    ResolveAttachmentsCountMismatch { subpass: usize },
+    /// The color and depth-stencil attachments of a subpass don't all have the same number of
+    /// samples.
+    SamplesCountMismatch { subpass: usize },
+    /// A dependency refers to a subpass that doesn't exist.
+    SubpassOutOfRange { dependency: usize },
+    /// The source subpass of a dependency comes after its destination subpass, or both are
+    /// external.
+    BackwardDependency { dependency: usize },
+}
+
+impl error::Error for RuntimeRenderPassDescError {}
+
+impl fmt::Display for RuntimeRenderPassDescError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            RuntimeRenderPassDescError::NoSubpass => {
+                write!(fmt, "a render pass must contain at least one subpass")
+            }
+            RuntimeRenderPassDescError::InvalidSamplesCount { attachment } => write!(
+                fmt,
+                "the number of samples of attachment {} is not a power of two",
+                attachment
+            ),
+            RuntimeRenderPassDescError::AttachmentOutOfRange {
+                subpass,
+                attachment,
+            } => write!(
+                fmt,
+                "subpass {} refers to attachment {}, which doesn't exist",
+                subpass, attachment
+            ),
+            RuntimeRenderPassDescError::NotDepthStencilFormat {
+                subpass,
+                attachment,
+            } => write!(
+                fmt,
+                "the depth-stencil attachment {} of subpass {} doesn't have a depth or stencil \
+                 format",
+                attachment, subpass
+            ),
+            RuntimeRenderPassDescError::ResolveAttachmentsCountMismatch { subpass } => write!(
+                fmt,
+                "subpass {} doesn't have as many resolve attachments as color attachments",
+                subpass
+            ),
+            RuntimeRenderPassDescError::SamplesCountMismatch { subpass } => write!(
+                fmt,
+                "the attachments of subpass {} don't all have the same number of samples",
+                subpass
+            ),
+            RuntimeRenderPassDescError::SubpassOutOfRange { dependency } => write!(
+                fmt,
+                "dependency {} refers to a subpass that doesn't exist",
+                dependency
+            ),
+            RuntimeRenderPassDescError::BackwardDependency { dependency } => write!(
+                fmt,
+                "the source subpass of dependency {} comes after its destination subpass",
+                dependency
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Format;
+    use crate::framebuffer::AttachmentDescription;
+    use crate::framebuffer::LoadOp;
+    use crate::framebuffer::PassDependencyDescription;
+    use crate::framebuffer::PassDescription;
+    use crate::framebuffer::RenderPassDesc;
+    use crate::framebuffer::RuntimeRenderPassDesc;
+    use crate::framebuffer::RuntimeRenderPassDescError;
+    use crate::framebuffer::StoreOp;
+    use crate::image::ImageLayout;
+    use crate::sync::AccessFlagBits;
+    use crate::sync::PipelineStages;
+
+    fn attachment(format: Format) -> AttachmentDescription {
+        AttachmentDescription {
+            format,
+            samples: 1,
+            load: LoadOp::Clear,
+            store: StoreOp::Store,
+            stencil_load: LoadOp::DontCare,
+            stencil_store: StoreOp::DontCare,
+            initial_layout: ImageLayout::Undefined,
+            final_layout: ImageLayout::ColorAttachmentOptimal,
+        }
+    }
+
+    fn pass(color: Vec<usize>, depth_stencil: Option<usize>) -> PassDescription {
+        PassDescription {
+            color_attachments: color
+                .into_iter()
+                .map(|a| (a, ImageLayout::ColorAttachmentOptimal))
+                .collect(),
+            depth_stencil: depth_stencil.map(|a| (a, ImageLayout::DepthStencilAttachmentOptimal)),
+            input_attachments: vec![],
+            resolve_attachments: vec![],
+            preserve_attachments: vec![],
+            fragment_shading_rate_attachment: None,
+            view_mask: 0,
+            depth_stencil_resolve: None,
+            multisampled_render_to_single_sampled: None,
+        }
+    }
+
+    fn dependency(source_subpass: usize, destination_subpass: usize) -> PassDependencyDescription {
+        PassDependencyDescription {
+            source_subpass,
+            destination_subpass,
+            source_stages: PipelineStages {
+                all_graphics: true,
+                ..PipelineStages::none()
+            },
+            destination_stages: PipelineStages {
+                all_graphics: true,
+                ..PipelineStages::none()
+            },
+            source_access: AccessFlagBits::all(),
+            destination_access: AccessFlagBits::all(),
+            by_region: true,
+            view_local: None,
+        }
+    }
+
+    #[test]
+    fn desc() {
+        let desc = RuntimeRenderPassDesc::new(
+            vec![
+                attachment(Format::R8G8B8A8Unorm),
+                attachment(Format::D16Unorm),
+            ],
+            vec![pass(vec![0], Some(1)), pass(vec![0], None)],
+            vec![dependency(0, 1)],
+        )
+        .unwrap();
+
+        assert_eq!(desc.num_attachments(), 2);
+        assert_eq!(desc.num_subpasses(), 2);
+        assert_eq!(desc.num_dependencies(), 1);
+        assert_eq!(desc.has_depth(0), Some(true));
+        assert_eq!(desc.has_depth(1), Some(false));
+        assert_eq!(desc.num_color_attachments(1), Some(1));
+    }
+
+    #[test]
+    fn invalid_desc() {
+        let atch = || vec![attachment(Format::R8G8B8A8Unorm)];
+
+        assert_eq!(
+            RuntimeRenderPassDesc::new(atch(), vec![], vec![]).unwrap_err(),
+            RuntimeRenderPassDescError::NoSubpass
+        );
+        assert_eq!(
+            RuntimeRenderPassDesc::new(atch(), vec![pass(vec![1], None)], vec![]).unwrap_err(),
+            RuntimeRenderPassDescError::AttachmentOutOfRange {
+                subpass: 0,
+                attachment: 1
+            }
+        );
+        assert_eq!(
+            RuntimeRenderPassDesc::new(atch(), vec![pass(vec![], Some(0))], vec![]).unwrap_err(),
+            RuntimeRenderPassDescError::NotDepthStencilFormat {
+                subpass: 0,
+                attachment: 0
+            }
+        );
+        assert_eq!(
+            RuntimeRenderPassDesc::new(
+                atch(),
+                vec![pass(vec![0], None), pass(vec![0], None)],
+                vec![dependency(1, 0)]
+            )
+            .unwrap_err(),
+            RuntimeRenderPassDescError::BackwardDependency { dependency: 0 }
+        );
+    }
+
+    #[test]
+    fn build() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = RuntimeRenderPassDesc::new(
+            vec![attachment(Format::R8G8B8A8Unorm)],
+            vec![pass(vec![0], None)],
+            vec![],
+        )
+        .unwrap();
+        let _ = desc.build_render_pass(device).unwrap();
+    }
+
+    #[test]
+    fn automatic_dependencies() {
+        let external = PassDependencyDescription::EXTERNAL_SUBPASS;
+        let desc = RuntimeRenderPassDesc::with_automatic_dependencies(
+            vec![attachment(Format::R8G8B8A8Unorm)],
+            vec![
+                pass(vec![0], None),
+                pass(vec![0], None),
+                pass(vec![0], None),
+            ],
+            vec![
+                PassDependencyDescription {
+                    by_region: false,
+                    ..dependency(0, 1)
+                },
+                dependency(external, 0),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(desc.num_dependencies(), 3);
+        let overridden = desc.dependency_desc(0).unwrap();
+        assert_eq!(
+            (overridden.source_subpass, overridden.destination_subpass),
+            (0, 1)
+        );
+        assert!(!overridden.by_region);
+        let derived = desc.dependency_desc(1).unwrap();
+        assert_eq!(
+            (derived.source_subpass, derived.destination_subpass),
+            (1, 2)
+        );
+        assert!(derived.by_region);
+        assert_eq!(desc.dependency_desc(2).unwrap().source_subpass, external);
+    }
+}
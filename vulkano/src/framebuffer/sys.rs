@@ -12,6 +12,7 @@ use std::error;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
+use std::os::raw::c_void;
 use std::ptr;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -19,6 +20,8 @@ use std::sync::Mutex;
 use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::format::ClearValue;
+use crate::format::Format;
+use crate::format::FormatTy;
 use crate::framebuffer::AttachmentDescription;
 use crate::framebuffer::EmptySinglePassRenderPassDesc;
 use crate::framebuffer::LoadOp;
@@ -27,6 +30,9 @@ use crate::framebuffer::PassDescription;
 use crate::framebuffer::RenderPassAbstract;
 use crate::framebuffer::RenderPassDesc;
 use crate::framebuffer::RenderPassDescClearValues;
+use crate::framebuffer::ResolveMode;
+use crate::framebuffer::ResolveModes;
+use crate::image::ImageLayout;
 
 use crate::check_errors;
 use crate::vk;
@@ -125,6 +131,163 @@ where
             })
             .collect::<SmallVec<[_; 16]>>();
 
+        // Each color attachment of a subpass that has resolve attachments is resolved into the
+        // resolve attachment at the same index at the end of the subpass.
+        for pass in description.subpass_descs() {
+            if pass.resolve_attachments.is_empty() {
+                continue;
+            }
+
+            if pass.resolve_attachments.len() != pass.color_attachments.len() {
+                return Err(RenderPassCreationError::ResolveAttachmentsCountMismatch);
+            }
+
+            for (&(color, _), &(resolve, _)) in pass
+                .color_attachments
+                .iter()
+                .zip(pass.resolve_attachments.iter())
+            {
+                debug_assert!(color < attachments.len() && resolve < attachments.len());
+
+                if attachments[color].samples == 1 {
+                    return Err(RenderPassCreationError::ResolveSourceNotMultisampled);
+                }
+                if attachments[resolve].samples != 1 {
+                    return Err(RenderPassCreationError::ResolveAttachmentMultisampled);
+                }
+                if attachments[resolve].format != attachments[color].format {
+                    return Err(RenderPassCreationError::ResolveAttachmentFormatMismatch);
+                }
+            }
+        }
+
+        let aspects = description
+            .attachment_descs()
+            .map(|attachment| attachment_aspect_mask(attachment.format))
+            .collect::<SmallVec<[_; 16]>>();
+
+        // The depth-stencil attachment of a subpass can also be resolved, which is only possible
+        // with `vkCreateRenderPass2KHR`.
+        let depth_stencil_resolves = description
+            .subpass_descs()
+            .map(|pass| pass.depth_stencil_resolve)
+            .collect::<SmallVec<[_; 16]>>();
+
+        for (pass, resolve) in description
+            .subpass_descs()
+            .zip(depth_stencil_resolves.iter())
+        {
+            let (resolve, _, depth_mode, stencil_mode) = match *resolve {
+                Some(resolve) => resolve,
+                None => continue,
+            };
+
+            if !device.loaded_extensions_or_core().khr_depth_stencil_resolve
+                || !device.loaded_extensions_or_core().khr_create_renderpass2
+            {
+                return Err(RenderPassCreationError::DepthStencilResolveExtensionNotEnabled);
+            }
+
+            let depth_stencil = match pass.depth_stencil {
+                Some((depth_stencil, _)) => depth_stencil,
+                None => {
+                    return Err(RenderPassCreationError::DepthStencilResolveWithoutDepthStencil)
+                }
+            };
+            debug_assert!(depth_stencil < attachments.len() && resolve < attachments.len());
+
+            if attachments[depth_stencil].samples == 1 {
+                return Err(RenderPassCreationError::ResolveSourceNotMultisampled);
+            }
+            if attachments[resolve].samples != 1 {
+                return Err(RenderPassCreationError::ResolveAttachmentMultisampled);
+            }
+            if attachments[resolve].format != attachments[depth_stencil].format {
+                return Err(RenderPassCreationError::ResolveAttachmentFormatMismatch);
+            }
+
+            let physical_device = device.physical_device();
+            let properties = physical_device.extended_properties();
+            let has_depth = aspects[resolve] & vk::IMAGE_ASPECT_DEPTH_BIT != 0;
+            let has_stencil = aspects[resolve] & vk::IMAGE_ASPECT_STENCIL_BIT != 0;
+
+            if (!has_depth || depth_mode == ResolveMode::None)
+                && (!has_stencil || stencil_mode == ResolveMode::None)
+            {
+                return Err(RenderPassCreationError::NoDepthStencilResolveMode);
+            }
+
+            let supported = |modes: &Option<ResolveModes>, mode| {
+                modes.map(|modes| modes.contains(mode)).unwrap_or(false)
+            };
+            if (has_depth && !supported(properties.supported_depth_resolve_modes(), depth_mode))
+                || (has_stencil
+                    && !supported(properties.supported_stencil_resolve_modes(), stencil_mode))
+            {
+                return Err(RenderPassCreationError::UnsupportedResolveMode);
+            }
+
+            if has_depth && has_stencil && depth_mode != stencil_mode {
+                let independent = properties.independent_resolve().unwrap_or(false);
+                let independent_none = properties.independent_resolve_none().unwrap_or(false)
+                    && (depth_mode == ResolveMode::None || stencil_mode == ResolveMode::None);
+                if !independent && !independent_none {
+                    return Err(RenderPassCreationError::IndependentResolveNotSupported);
+                }
+            }
+        }
+
+        // Rendering multisampled to single-sampled attachments is also only possible with
+        // `vkCreateRenderPass2KHR`.
+        let multisampled_render_to_single_sampled = description
+            .subpass_descs()
+            .map(|pass| pass.multisampled_render_to_single_sampled)
+            .collect::<SmallVec<[_; 16]>>();
+
+        for (pass, samples) in description
+            .subpass_descs()
+            .zip(multisampled_render_to_single_sampled.iter())
+        {
+            let samples = match *samples {
+                Some(samples) => samples,
+                None => continue,
+            };
+
+            if !device
+                .loaded_extensions()
+                .ext_multisampled_render_to_single_sampled
+                || !device.loaded_extensions_or_core().khr_create_renderpass2
+            {
+                return Err(
+                    RenderPassCreationError::MultisampledRenderToSingleSampledExtensionNotEnabled,
+                );
+            }
+            if !device
+                .enabled_features()
+                .multisampled_render_to_single_sampled
+            {
+                return Err(
+                    RenderPassCreationError::MultisampledRenderToSingleSampledFeatureNotEnabled,
+                );
+            }
+
+            let valid_samples = samples > 1
+                && samples.is_power_of_two()
+                && pass
+                    .color_attachments
+                    .iter()
+                    .chain(pass.input_attachments.iter())
+                    .chain(pass.depth_stencil.iter())
+                    .all(|&(a, _)| {
+                        attachments[a].samples == 1 || attachments[a].samples == samples
+                    });
+            if !valid_samples {
+                return Err(
+                    RenderPassCreationError::InvalidMultisampledRenderToSingleSampledSamples,
+                );
+            }
+        }
+
         // We need to pass pointers to vkAttachmentReference structs when creating the render pass.
         // Therefore we need to allocate them in advance.
         //
@@ -135,29 +298,6 @@ where
             .subpass_descs()
             .flat_map(|pass| {
                 // Performing some validation with debug asserts.
-                debug_assert!(
-                    pass.resolve_attachments.is_empty()
-                        || pass.resolve_attachments.len() == pass.color_attachments.len()
-                );
-                debug_assert!(pass
-                    .resolve_attachments
-                    .iter()
-                    .all(|a| attachments[a.0].samples == 1));
-                debug_assert!(
-                    pass.resolve_attachments.is_empty()
-                        || pass
-                            .color_attachments
-                            .iter()
-                            .all(|a| attachments[a.0].samples > 1)
-                );
-                debug_assert!(
-                    pass.resolve_attachments.is_empty()
-                        || pass
-                            .resolve_attachments
-                            .iter()
-                            .zip(pass.color_attachments.iter())
-                            .all(|(r, c)| { attachments[r.0].format == attachments[c.0].format })
-                );
                 debug_assert!(pass
                     .color_attachments
                     .iter()
@@ -329,48 +469,143 @@ where
                     dstStageMask: dependency.destination_stages.into_vulkan_bits(),
                     srcAccessMask: dependency.source_access.into_vulkan_bits(),
                     dstAccessMask: dependency.destination_access.into_vulkan_bits(),
-                    dependencyFlags: if dependency.by_region {
-                        vk::DEPENDENCY_BY_REGION_BIT
-                    } else {
-                        0
+                    dependencyFlags: {
+                        let mut flags = 0;
+                        if dependency.by_region {
+                            flags |= vk::DEPENDENCY_BY_REGION_BIT;
+                        }
+                        if dependency.view_local.is_some() {
+                            flags |= vk::DEPENDENCY_VIEW_LOCAL_BIT;
+                        }
+                        flags
                     },
                 }
             })
             .collect::<SmallVec<[_; 16]>>();
 
-        let render_pass = unsafe {
-            let infos = vk::RenderPassCreateInfo {
-                sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0, // reserved
-                attachmentCount: attachments.len() as u32,
-                pAttachments: if attachments.is_empty() {
-                    ptr::null()
-                } else {
-                    attachments.as_ptr()
-                },
-                subpassCount: passes.len() as u32,
-                pSubpasses: if passes.is_empty() {
-                    ptr::null()
-                } else {
-                    passes.as_ptr()
-                },
-                dependencyCount: dependencies.len() as u32,
-                pDependencies: if dependencies.is_empty() {
-                    ptr::null()
-                } else {
-                    dependencies.as_ptr()
-                },
-            };
+        let multiview = MultiviewInfo::new(&device, &description)?;
+
+        let fragment_density_map = match description.fragment_density_map_attachment() {
+            Some((offset, img_la)) => {
+                if !device.loaded_extensions().ext_fragment_density_map {
+                    return Err(RenderPassCreationError::FragmentDensityMapExtensionNotEnabled);
+                }
+                if !device.enabled_features().fragment_density_map {
+                    return Err(RenderPassCreationError::FragmentDensityMapFeatureNotEnabled);
+                }
+                if img_la != ImageLayout::FragmentDensityMapOptimal
+                    && img_la != ImageLayout::General
+                {
+                    return Err(RenderPassCreationError::InvalidFragmentDensityMapLayout);
+                }
 
-            let mut output = MaybeUninit::uninit();
-            check_errors(vk.CreateRenderPass(
-                device.internal_object(),
-                &infos,
-                ptr::null(),
-                output.as_mut_ptr(),
-            ))?;
-            output.assume_init()
+                // The density map is only read by the implementation, and can't be used by the
+                // subpasses themselves.
+                debug_assert!(offset < attachments.len());
+                let used_by_subpass = description.subpass_descs().any(|pass| {
+                    pass.color_attachments
+                        .iter()
+                        .chain(pass.input_attachments.iter())
+                        .chain(pass.resolve_attachments.iter())
+                        .chain(pass.depth_stencil.iter())
+                        .any(|&(a, _)| a == offset)
+                        || pass.preserve_attachments.contains(&offset)
+                });
+                if used_by_subpass {
+                    return Err(RenderPassCreationError::FragmentDensityMapAttachmentUsedBySubpass);
+                }
+
+                Some(vk::AttachmentReference {
+                    attachment: offset as u32,
+                    layout: img_la as u32,
+                })
+            }
+            None => None,
+        };
+
+        let fragment_shading_rate_attachments = description
+            .subpass_descs()
+            .map(|pass| pass.fragment_shading_rate_attachment)
+            .collect::<SmallVec<[_; 16]>>();
+
+        let uses_fragment_shading_rate = fragment_shading_rate_attachments
+            .iter()
+            .any(|atch| atch.is_some());
+        let uses_depth_stencil_resolve = depth_stencil_resolves.iter().any(|r| r.is_some());
+        let uses_multisampled_render_to_single_sampled = multisampled_render_to_single_sampled
+            .iter()
+            .any(|s| s.is_some());
+
+        let render_pass = if uses_fragment_shading_rate
+            || uses_depth_stencil_resolve
+            || uses_multisampled_render_to_single_sampled
+        {
+            if uses_fragment_shading_rate {
+                if !device.loaded_extensions().khr_fragment_shading_rate
+                    || !device.loaded_extensions_or_core().khr_create_renderpass2
+                {
+                    return Err(RenderPassCreationError::FragmentShadingRateExtensionNotEnabled);
+                }
+                if !device.enabled_features().attachment_fragment_shading_rate {
+                    return Err(
+                        RenderPassCreationError::AttachmentFragmentShadingRateFeatureNotEnabled,
+                    );
+                }
+            }
+
+            let physical_device = device.physical_device();
+            let properties = physical_device.extended_properties();
+            let min_texel_size = properties
+                .min_fragment_shading_rate_attachment_texel_size()
+                .unwrap_or([0, 0]);
+            let max_texel_size = properties
+                .max_fragment_shading_rate_attachment_texel_size()
+                .unwrap_or([0, 0]);
+            for &(offset, _, texel_size) in fragment_shading_rate_attachments.iter().flatten() {
+                if offset >= attachments.len() {
+                    return Err(RenderPassCreationError::FragmentShadingRateAttachmentOutOfRange);
+                }
+                let valid = (0..2).all(|i| {
+                    texel_size[i].is_power_of_two()
+                        && texel_size[i] >= min_texel_size[i]
+                        && texel_size[i] <= max_texel_size[i]
+                });
+                if !valid {
+                    return Err(
+                        RenderPassCreationError::InvalidFragmentShadingRateAttachmentTexelSize,
+                    );
+                }
+            }
+
+            unsafe {
+                create_render_pass2(
+                    &device,
+                    &attachments,
+                    &passes,
+                    &dependencies,
+                    &RenderPass2Infos {
+                        aspects: &aspects,
+                        attachment_references: &attachment_references,
+                        fragment_shading_rate_attachments: &fragment_shading_rate_attachments,
+                        depth_stencil_resolves: &depth_stencil_resolves,
+                        multisampled_render_to_single_sampled:
+                            &multisampled_render_to_single_sampled,
+                    },
+                    &multiview,
+                    fragment_density_map.as_ref(),
+                )?
+            }
+        } else {
+            unsafe {
+                create_render_pass(
+                    &device,
+                    &attachments,
+                    &passes,
+                    &dependencies,
+                    &multiview,
+                    fragment_density_map.as_ref(),
+                )?
+            }
         };
 
         Ok(RenderPass {
@@ -382,6 +617,455 @@ where
     }
 }
 
+// View masks, view offsets and correlation masks of a render pass, checked against the device.
+struct MultiviewInfo {
+    view_masks: SmallVec<[u32; 16]>,
+    view_offsets: SmallVec<[i32; 16]>,
+    correlation_masks: Vec<u32>,
+}
+
+impl MultiviewInfo {
+    fn new<D>(device: &Device, description: &D) -> Result<MultiviewInfo, RenderPassCreationError>
+    where
+        D: RenderPassDesc,
+    {
+        let view_masks = description
+            .subpass_descs()
+            .map(|pass| pass.view_mask)
+            .collect::<SmallVec<[_; 16]>>();
+        let correlation_masks = description.correlation_masks();
+
+        let mut view_offsets = SmallVec::new();
+        let mut view_local = false;
+        for dependency in description.dependency_descs() {
+            if let Some(offset) = dependency.view_local {
+                if offset != 0 && dependency.source_subpass == dependency.destination_subpass {
+                    return Err(RenderPassCreationError::InvalidViewOffset);
+                }
+                view_local = true;
+            }
+            view_offsets.push(dependency.view_local.unwrap_or(0));
+        }
+
+        if view_masks.iter().all(|&mask| mask == 0) {
+            if view_local || !correlation_masks.is_empty() {
+                return Err(RenderPassCreationError::MultiviewRequired);
+            }
+        } else {
+            if !device.loaded_extensions_or_core().khr_multiview {
+                return Err(RenderPassCreationError::MultiviewExtensionNotEnabled);
+            }
+            if !device.enabled_features().multiview {
+                return Err(RenderPassCreationError::MultiviewFeatureNotEnabled);
+            }
+            if view_masks.contains(&0) {
+                return Err(RenderPassCreationError::InconsistentViewMasks);
+            }
+
+            let max_view_count = device
+                .physical_device()
+                .extended_properties()
+                .max_multiview_view_count()
+                .unwrap_or(0);
+            if view_masks
+                .iter()
+                .any(|&mask| 32 - mask.leading_zeros() > max_view_count)
+            {
+                return Err(RenderPassCreationError::MultiviewViewCountExceeded);
+            }
+
+            let mut correlated = 0;
+            for &mask in correlation_masks.iter() {
+                if correlated & mask != 0 {
+                    return Err(RenderPassCreationError::OverlappingCorrelationMasks);
+                }
+                correlated |= mask;
+            }
+        }
+
+        Ok(MultiviewInfo {
+            view_masks,
+            view_offsets,
+            correlation_masks,
+        })
+    }
+
+    #[inline]
+    fn is_enabled(&self) -> bool {
+        self.view_masks.iter().any(|&mask| mask != 0)
+    }
+}
+
+// Calls `vkCreateRenderPass` with the given attachments, subpasses and dependencies.
+unsafe fn create_render_pass(
+    device: &Device,
+    attachments: &[vk::AttachmentDescription],
+    passes: &[vk::SubpassDescription],
+    dependencies: &[vk::SubpassDependency],
+    multiview: &MultiviewInfo,
+    fragment_density_map: Option<&vk::AttachmentReference>,
+) -> Result<vk::RenderPass, RenderPassCreationError> {
+    let vk = device.pointers();
+
+    let multiview_info = vk::RenderPassMultiviewCreateInfo {
+        sType: vk::STRUCTURE_TYPE_RENDER_PASS_MULTIVIEW_CREATE_INFO,
+        pNext: ptr::null(),
+        subpassCount: multiview.view_masks.len() as u32,
+        pViewMasks: multiview.view_masks.as_ptr(),
+        dependencyCount: multiview.view_offsets.len() as u32,
+        pViewOffsets: if multiview.view_offsets.is_empty() {
+            ptr::null()
+        } else {
+            multiview.view_offsets.as_ptr()
+        },
+        correlationMaskCount: multiview.correlation_masks.len() as u32,
+        pCorrelationMasks: if multiview.correlation_masks.is_empty() {
+            ptr::null()
+        } else {
+            multiview.correlation_masks.as_ptr()
+        },
+    };
+
+    let multiview_ptr = if multiview.is_enabled() {
+        &multiview_info as *const _ as *const _
+    } else {
+        ptr::null()
+    };
+
+    // Chained in front of the multiview info, if any.
+    let fragment_density_map_info =
+        fragment_density_map.map(|reference| vk::RenderPassFragmentDensityMapCreateInfoEXT {
+            sType: vk::STRUCTURE_TYPE_RENDER_PASS_FRAGMENT_DENSITY_MAP_CREATE_INFO_EXT,
+            pNext: multiview_ptr,
+            fragmentDensityMapAttachment: vk::AttachmentReference {
+                attachment: reference.attachment,
+                layout: reference.layout,
+            },
+        });
+
+    let infos = vk::RenderPassCreateInfo {
+        sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO,
+        pNext: match fragment_density_map_info {
+            Some(ref info) => info as *const _ as *const _,
+            None => multiview_ptr,
+        },
+        flags: 0, // reserved
+        attachmentCount: attachments.len() as u32,
+        pAttachments: if attachments.is_empty() {
+            ptr::null()
+        } else {
+            attachments.as_ptr()
+        },
+        subpassCount: passes.len() as u32,
+        pSubpasses: if passes.is_empty() {
+            ptr::null()
+        } else {
+            passes.as_ptr()
+        },
+        dependencyCount: dependencies.len() as u32,
+        pDependencies: if dependencies.is_empty() {
+            ptr::null()
+        } else {
+            dependencies.as_ptr()
+        },
+    };
+
+    let mut output = MaybeUninit::uninit();
+    check_errors(vk.CreateRenderPass(
+        device.internal_object(),
+        &infos,
+        device.alloc_ptr(),
+        output.as_mut_ptr(),
+    ))?;
+    Ok(output.assume_init())
+}
+
+// Parameters of `vkCreateRenderPass2KHR` that are not passed to `vkCreateRenderPass`.
+struct RenderPass2Infos<'a> {
+    // Aspects of each attachment.
+    aspects: &'a [vk::ImageAspectFlags],
+    // The references that the pointers of the subpass descriptions point to.
+    attachment_references: &'a [vk::AttachmentReference],
+    // One entry per subpass.
+    fragment_shading_rate_attachments: &'a [Option<(usize, ImageLayout, [u32; 2])>],
+    // One entry per subpass.
+    depth_stencil_resolves: &'a [Option<(usize, ImageLayout, ResolveMode, ResolveMode)>],
+    // One entry per subpass.
+    multisampled_render_to_single_sampled: &'a [Option<u32>],
+}
+
+// Calls `vkCreateRenderPass2KHR`, which is required in order to pass fragment shading rate
+// attachments, depth-stencil resolve attachments and multisampled render to single-sampled infos.
+//
+// The structs built for `vkCreateRenderPass` are converted to their `2` equivalents. The pointers
+// of `passes` must point inside of `infos.attachment_references`.
+unsafe fn create_render_pass2(
+    device: &Device,
+    attachments: &[vk::AttachmentDescription],
+    passes: &[vk::SubpassDescription],
+    dependencies: &[vk::SubpassDependency],
+    infos: &RenderPass2Infos,
+    multiview: &MultiviewInfo,
+    fragment_density_map: Option<&vk::AttachmentReference>,
+) -> Result<vk::RenderPass, RenderPassCreationError> {
+    let RenderPass2Infos {
+        aspects,
+        attachment_references,
+        fragment_shading_rate_attachments,
+        depth_stencil_resolves,
+        multisampled_render_to_single_sampled,
+    } = *infos;
+    debug_assert_eq!(passes.len(), fragment_shading_rate_attachments.len());
+    debug_assert_eq!(passes.len(), depth_stencil_resolves.len());
+    debug_assert_eq!(passes.len(), multisampled_render_to_single_sampled.len());
+    let vk = device.pointers();
+
+    let attachments2 = attachments
+        .iter()
+        .map(|attachment| vk::AttachmentDescription2 {
+            sType: vk::STRUCTURE_TYPE_ATTACHMENT_DESCRIPTION_2,
+            pNext: ptr::null(),
+            flags: attachment.flags,
+            format: attachment.format,
+            samples: attachment.samples,
+            loadOp: attachment.loadOp,
+            storeOp: attachment.storeOp,
+            stencilLoadOp: attachment.stencilLoadOp,
+            stencilStoreOp: attachment.stencilStoreOp,
+            initialLayout: attachment.initialLayout,
+            finalLayout: attachment.finalLayout,
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let attachment_references2 = attachment_references
+        .iter()
+        .map(|reference| vk::AttachmentReference2 {
+            sType: vk::STRUCTURE_TYPE_ATTACHMENT_REFERENCE_2,
+            pNext: ptr::null(),
+            attachment: reference.attachment,
+            layout: reference.layout,
+            // Unused references don't have an attachment to take the aspects from.
+            aspectMask: if reference.attachment == vk::ATTACHMENT_UNUSED {
+                0
+            } else {
+                aspects[reference.attachment as usize]
+            },
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    // Translates a pointer inside of `attachment_references` to the same element of
+    // `attachment_references2`.
+    let translate = |reference: *const vk::AttachmentReference| {
+        if reference.is_null() {
+            ptr::null()
+        } else {
+            attachment_references2
+                .as_ptr()
+                .offset(reference.offset_from(attachment_references.as_ptr()))
+        }
+    };
+
+    let shading_rate_references = fragment_shading_rate_attachments
+        .iter()
+        .map(|atch| {
+            atch.map(|(offset, img_la, _)| vk::AttachmentReference2 {
+                sType: vk::STRUCTURE_TYPE_ATTACHMENT_REFERENCE_2,
+                pNext: ptr::null(),
+                attachment: offset as u32,
+                layout: img_la as u32,
+                aspectMask: 0, // ignored
+            })
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let shading_rate_infos = fragment_shading_rate_attachments
+        .iter()
+        .zip(shading_rate_references.iter())
+        .map(|(atch, reference)| {
+            atch.map(
+                |(_, _, texel_size)| vk::FragmentShadingRateAttachmentInfoKHR {
+                    sType: vk::STRUCTURE_TYPE_FRAGMENT_SHADING_RATE_ATTACHMENT_INFO_KHR,
+                    pNext: ptr::null(),
+                    pFragmentShadingRateAttachment: reference.as_ref().unwrap(),
+                    shadingRateAttachmentTexelSize: vk::Extent2D {
+                        width: texel_size[0],
+                        height: texel_size[1],
+                    },
+                },
+            )
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let depth_stencil_resolve_references = depth_stencil_resolves
+        .iter()
+        .map(|resolve| {
+            resolve.map(|(offset, img_la, _, _)| vk::AttachmentReference2 {
+                sType: vk::STRUCTURE_TYPE_ATTACHMENT_REFERENCE_2,
+                pNext: ptr::null(),
+                attachment: offset as u32,
+                layout: img_la as u32,
+                aspectMask: 0, // ignored
+            })
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    // Each of these structs is chained in front of the fragment shading rate info of its subpass.
+    let depth_stencil_resolve_infos = depth_stencil_resolves
+        .iter()
+        .zip(depth_stencil_resolve_references.iter())
+        .zip(shading_rate_infos.iter())
+        .map(|((resolve, reference), shading_rate_info)| {
+            resolve.map(|(_, _, depth_mode, stencil_mode)| {
+                vk::SubpassDescriptionDepthStencilResolve {
+                    sType: vk::STRUCTURE_TYPE_SUBPASS_DESCRIPTION_DEPTH_STENCIL_RESOLVE,
+                    pNext: shading_rate_info
+                        .as_ref()
+                        .map(|info| info as *const _ as *const _)
+                        .unwrap_or(ptr::null()),
+                    depthResolveMode: depth_mode as u32,
+                    stencilResolveMode: stencil_mode as u32,
+                    pDepthStencilResolveAttachment: reference.as_ref().unwrap(),
+                }
+            })
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    // Pointer to the first struct of the chain made of the depth-stencil resolve info and the
+    // fragment shading rate info of each subpass.
+    let resolve_chains = depth_stencil_resolve_infos
+        .iter()
+        .zip(shading_rate_infos.iter())
+        .map(
+            |(resolve_info, shading_rate_info)| match (resolve_info, shading_rate_info) {
+                (Some(info), _) => info as *const _ as *const c_void,
+                (None, Some(info)) => info as *const _ as *const c_void,
+                (None, None) => ptr::null(),
+            },
+        )
+        .collect::<SmallVec<[_; 16]>>();
+
+    // Each of these structs is chained in front of the other structs of its subpass.
+    let multisampled_infos = multisampled_render_to_single_sampled
+        .iter()
+        .zip(resolve_chains.iter())
+        .map(|(samples, &chain)| {
+            samples.map(|samples| vk::MultisampledRenderToSingleSampledInfoEXT {
+                sType: vk::STRUCTURE_TYPE_MULTISAMPLED_RENDER_TO_SINGLE_SAMPLED_INFO_EXT,
+                pNext: chain,
+                multisampledRenderToSingleSampledEnable: vk::TRUE,
+                rasterizationSamples: samples,
+            })
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let passes2 = passes
+        .iter()
+        .zip(resolve_chains.iter())
+        .zip(multisampled_infos.iter())
+        .zip(multiview.view_masks.iter())
+        .map(
+            |(((pass, &chain), multisampled_info), &view_mask)| vk::SubpassDescription2 {
+                sType: vk::STRUCTURE_TYPE_SUBPASS_DESCRIPTION_2,
+                pNext: match multisampled_info {
+                    Some(info) => info as *const _ as *const _,
+                    None => chain,
+                },
+                flags: pass.flags,
+                pipelineBindPoint: pass.pipelineBindPoint,
+                viewMask: view_mask,
+                inputAttachmentCount: pass.inputAttachmentCount,
+                pInputAttachments: translate(pass.pInputAttachments),
+                colorAttachmentCount: pass.colorAttachmentCount,
+                pColorAttachments: translate(pass.pColorAttachments),
+                pResolveAttachments: translate(pass.pResolveAttachments),
+                pDepthStencilAttachment: translate(pass.pDepthStencilAttachment),
+                preserveAttachmentCount: pass.preserveAttachmentCount,
+                pPreserveAttachments: pass.pPreserveAttachments,
+            },
+        )
+        .collect::<SmallVec<[_; 16]>>();
+
+    let dependencies2 = dependencies
+        .iter()
+        .zip(multiview.view_offsets.iter())
+        .map(|(dependency, &view_offset)| vk::SubpassDependency2 {
+            sType: vk::STRUCTURE_TYPE_SUBPASS_DEPENDENCY_2,
+            pNext: ptr::null(),
+            srcSubpass: dependency.srcSubpass,
+            dstSubpass: dependency.dstSubpass,
+            srcStageMask: dependency.srcStageMask,
+            dstStageMask: dependency.dstStageMask,
+            srcAccessMask: dependency.srcAccessMask,
+            dstAccessMask: dependency.dstAccessMask,
+            dependencyFlags: dependency.dependencyFlags,
+            viewOffset: view_offset,
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let fragment_density_map_info =
+        fragment_density_map.map(|reference| vk::RenderPassFragmentDensityMapCreateInfoEXT {
+            sType: vk::STRUCTURE_TYPE_RENDER_PASS_FRAGMENT_DENSITY_MAP_CREATE_INFO_EXT,
+            pNext: ptr::null(),
+            fragmentDensityMapAttachment: vk::AttachmentReference {
+                attachment: reference.attachment,
+                layout: reference.layout,
+            },
+        });
+
+    let infos = vk::RenderPassCreateInfo2 {
+        sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO_2,
+        pNext: match fragment_density_map_info {
+            Some(ref info) => info as *const _ as *const _,
+            None => ptr::null(),
+        },
+        flags: 0, // reserved
+        attachmentCount: attachments2.len() as u32,
+        pAttachments: if attachments2.is_empty() {
+            ptr::null()
+        } else {
+            attachments2.as_ptr()
+        },
+        subpassCount: passes2.len() as u32,
+        pSubpasses: if passes2.is_empty() {
+            ptr::null()
+        } else {
+            passes2.as_ptr()
+        },
+        dependencyCount: dependencies2.len() as u32,
+        pDependencies: if dependencies2.is_empty() {
+            ptr::null()
+        } else {
+            dependencies2.as_ptr()
+        },
+        correlatedViewMaskCount: multiview.correlation_masks.len() as u32,
+        pCorrelatedViewMasks: if multiview.correlation_masks.is_empty() {
+            ptr::null()
+        } else {
+            multiview.correlation_masks.as_ptr()
+        },
+    };
+
+    let mut output = MaybeUninit::uninit();
+    check_errors(vk.CreateRenderPass2KHR(
+        device.internal_object(),
+        &infos,
+        device.alloc_ptr(),
+        output.as_mut_ptr(),
+    ))?;
+    Ok(output.assume_init())
+}
+
+// Returns the aspects of an attachment of the given format, which must be set on input attachment
+// references passed to `vkCreateRenderPass2KHR`.
+fn attachment_aspect_mask(format: Format) -> vk::ImageAspectFlags {
+    match format.ty() {
+        FormatTy::Depth => vk::IMAGE_ASPECT_DEPTH_BIT,
+        FormatTy::Stencil => vk::IMAGE_ASPECT_STENCIL_BIT,
+        FormatTy::DepthStencil => vk::IMAGE_ASPECT_DEPTH_BIT | vk::IMAGE_ASPECT_STENCIL_BIT,
+        _ => vk::IMAGE_ASPECT_COLOR_BIT,
+    }
+}
+
 impl RenderPass<EmptySinglePassRenderPassDesc> {
     /// Builds a render pass with one subpass and no attachment.
     ///
@@ -468,6 +1152,16 @@ where
     fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
         self.desc.dependency_desc(num)
     }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        self.desc.correlation_masks()
+    }
+
+    #[inline]
+    fn fragment_density_map_attachment(&self) -> Option<(usize, ImageLayout)> {
+        self.desc.fragment_density_map_attachment()
+    }
 }
 
 unsafe impl<C, D> RenderPassDescClearValues<C> for RenderPass<D>
@@ -515,7 +1209,11 @@ impl<D> Drop for RenderPass<D> {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyRenderPass(self.device.internal_object(), self.render_pass, ptr::null());
+            vk.DestroyRenderPass(
+                self.device.internal_object(),
+                self.render_pass,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -542,6 +1240,73 @@ pub enum RenderPassCreationError {
     OomError(OomError),
     /// The maximum number of color attachments has been exceeded.
     ColorAttachmentsLimitExceeded,
+    /// The `khr_fragment_shading_rate` and `khr_create_renderpass2` extensions must be enabled in
+    /// order to use a fragment shading rate attachment.
+    FragmentShadingRateExtensionNotEnabled,
+    /// The `attachment_fragment_shading_rate` feature must be enabled in order to use a fragment
+    /// shading rate attachment.
+    AttachmentFragmentShadingRateFeatureNotEnabled,
+    /// The texel size of a fragment shading rate attachment is not a power of two, or is outside
+    /// of the range supported by the device.
+    InvalidFragmentShadingRateAttachmentTexelSize,
+    /// The fragment shading rate attachment of a subpass is not an attachment of the render pass.
+    FragmentShadingRateAttachmentOutOfRange,
+    /// The `khr_multiview` extension must be enabled in order to use a non-zero view mask.
+    MultiviewExtensionNotEnabled,
+    /// The `multiview` feature must be enabled in order to use a non-zero view mask.
+    MultiviewFeatureNotEnabled,
+    /// Some subpasses have a non-zero view mask and others don't.
+    InconsistentViewMasks,
+    /// A view mask uses more views than the `max_multiview_view_count` limit.
+    MultiviewViewCountExceeded,
+    /// Two correlation masks have a view in common.
+    OverlappingCorrelationMasks,
+    /// Correlation masks or view-local dependencies were given, but the subpasses don't use
+    /// multiview.
+    MultiviewRequired,
+    /// A view-local dependency of a subpass on itself has a non-zero view offset.
+    InvalidViewOffset,
+    /// A subpass has resolve attachments, but not as many as it has color attachments.
+    ResolveAttachmentsCountMismatch,
+    /// A color or depth-stencil attachment that is resolved has only one sample.
+    ResolveSourceNotMultisampled,
+    /// A resolve attachment has more than one sample.
+    ResolveAttachmentMultisampled,
+    /// A resolve attachment doesn't have the same format as the attachment that is resolved into
+    /// it.
+    ResolveAttachmentFormatMismatch,
+    /// The `khr_depth_stencil_resolve` and `khr_create_renderpass2` extensions must be enabled in
+    /// order to resolve a depth-stencil attachment.
+    DepthStencilResolveExtensionNotEnabled,
+    /// A subpass has a depth-stencil resolve attachment but no depth-stencil attachment.
+    DepthStencilResolveWithoutDepthStencil,
+    /// A depth-stencil resolve attachment doesn't resolve any aspect of its format.
+    NoDepthStencilResolveMode,
+    /// A depth or stencil resolve mode is not supported by the device.
+    UnsupportedResolveMode,
+    /// The depth and stencil resolve modes are different, which the device doesn't support.
+    IndependentResolveNotSupported,
+    /// The `ext_fragment_density_map` extension must be enabled in order to use a fragment
+    /// density map attachment.
+    FragmentDensityMapExtensionNotEnabled,
+    /// The `fragment_density_map` feature must be enabled in order to use a fragment density map
+    /// attachment.
+    FragmentDensityMapFeatureNotEnabled,
+    /// The layout of the fragment density map attachment is neither `FragmentDensityMapOptimal`
+    /// nor `General`.
+    InvalidFragmentDensityMapLayout,
+    /// The fragment density map attachment is also used by a subpass.
+    FragmentDensityMapAttachmentUsedBySubpass,
+    /// The `ext_multisampled_render_to_single_sampled` and `khr_create_renderpass2` extensions
+    /// must be enabled in order to render multisampled to single-sampled attachments.
+    MultisampledRenderToSingleSampledExtensionNotEnabled,
+    /// The `multisampled_render_to_single_sampled` feature must be enabled in order to render
+    /// multisampled to single-sampled attachments.
+    MultisampledRenderToSingleSampledFeatureNotEnabled,
+    /// The number of samples of a subpass that renders multisampled to single-sampled attachments
+    /// is not a power of two greater than one, or an attachment of the subpass has neither one
+    /// sample nor this number of samples.
+    InvalidMultisampledRenderToSingleSampledSamples,
 }
 
 impl error::Error for RenderPassCreationError {
@@ -565,6 +1330,104 @@ impl fmt::Display for RenderPassCreationError {
                 RenderPassCreationError::ColorAttachmentsLimitExceeded => {
                     "the maximum number of color attachments has been exceeded"
                 }
+                RenderPassCreationError::FragmentShadingRateExtensionNotEnabled => {
+                    "the `khr_fragment_shading_rate` and `khr_create_renderpass2` extensions must \
+                 be enabled in order to use a fragment shading rate attachment"
+                }
+                RenderPassCreationError::AttachmentFragmentShadingRateFeatureNotEnabled => {
+                    "the `attachment_fragment_shading_rate` feature must be enabled in order to \
+                 use a fragment shading rate attachment"
+                }
+                RenderPassCreationError::InvalidFragmentShadingRateAttachmentTexelSize => {
+                    "the texel size of a fragment shading rate attachment is not a power of two, \
+                 or is outside of the range supported by the device"
+                }
+                RenderPassCreationError::FragmentShadingRateAttachmentOutOfRange => {
+                    "the fragment shading rate attachment of a subpass is not an attachment of \
+                     the render pass"
+                }
+                RenderPassCreationError::MultiviewExtensionNotEnabled => {
+                    "the `khr_multiview` extension must be enabled in order to use a non-zero \
+                 view mask"
+                }
+                RenderPassCreationError::MultiviewFeatureNotEnabled => {
+                    "the `multiview` feature must be enabled in order to use a non-zero view mask"
+                }
+                RenderPassCreationError::InconsistentViewMasks => {
+                    "some subpasses have a non-zero view mask and others don't"
+                }
+                RenderPassCreationError::MultiviewViewCountExceeded => {
+                    "a view mask uses more views than the `max_multiview_view_count` limit"
+                }
+                RenderPassCreationError::OverlappingCorrelationMasks => {
+                    "two correlation masks have a view in common"
+                }
+                RenderPassCreationError::MultiviewRequired => {
+                    "correlation masks or view-local dependencies were given, but the subpasses \
+                 don't use multiview"
+                }
+                RenderPassCreationError::InvalidViewOffset => {
+                    "a view-local dependency of a subpass on itself has a non-zero view offset"
+                }
+                RenderPassCreationError::ResolveAttachmentsCountMismatch => {
+                    "a subpass has resolve attachments, but not as many as it has color attachments"
+                }
+                RenderPassCreationError::ResolveSourceNotMultisampled => {
+                    "a color or depth-stencil attachment that is resolved has only one sample"
+                }
+                RenderPassCreationError::ResolveAttachmentMultisampled => {
+                    "a resolve attachment has more than one sample"
+                }
+                RenderPassCreationError::ResolveAttachmentFormatMismatch => {
+                    "a resolve attachment doesn't have the same format as the attachment that is \
+                 resolved into it"
+                }
+                RenderPassCreationError::DepthStencilResolveExtensionNotEnabled => {
+                    "the `khr_depth_stencil_resolve` and `khr_create_renderpass2` extensions must \
+                 be enabled in order to resolve a depth-stencil attachment"
+                }
+                RenderPassCreationError::DepthStencilResolveWithoutDepthStencil => {
+                    "a subpass has a depth-stencil resolve attachment but no depth-stencil \
+                 attachment"
+                }
+                RenderPassCreationError::NoDepthStencilResolveMode => {
+                    "a depth-stencil resolve attachment doesn't resolve any aspect of its format"
+                }
+                RenderPassCreationError::UnsupportedResolveMode => {
+                    "a depth or stencil resolve mode is not supported by the device"
+                }
+                RenderPassCreationError::IndependentResolveNotSupported => {
+                    "the depth and stencil resolve modes are different, which the device doesn't \
+                 support"
+                }
+                RenderPassCreationError::FragmentDensityMapExtensionNotEnabled => {
+                    "the `ext_fragment_density_map` extension must be enabled in order to use a \
+                 fragment density map attachment"
+                }
+                RenderPassCreationError::FragmentDensityMapFeatureNotEnabled => {
+                    "the `fragment_density_map` feature must be enabled in order to use a \
+                 fragment density map attachment"
+                }
+                RenderPassCreationError::InvalidFragmentDensityMapLayout => {
+                    "the layout of the fragment density map attachment is neither \
+                 `FragmentDensityMapOptimal` nor `General`"
+                }
+                RenderPassCreationError::FragmentDensityMapAttachmentUsedBySubpass => {
+                    "the fragment density map attachment is also used by a subpass"
+                }
+                RenderPassCreationError::MultisampledRenderToSingleSampledExtensionNotEnabled => {
+                    "the `ext_multisampled_render_to_single_sampled` and `khr_create_renderpass2` \
+                 extensions must be enabled in order to render multisampled to single-sampled \
+                 attachments"
+                }
+                RenderPassCreationError::MultisampledRenderToSingleSampledFeatureNotEnabled => {
+                    "the `multisampled_render_to_single_sampled` feature must be enabled in order \
+                 to render multisampled to single-sampled attachments"
+                }
+                RenderPassCreationError::InvalidMultisampledRenderToSingleSampledSamples => {
+                    "the number of samples of a subpass that renders multisampled to \
+                 single-sampled attachments is invalid"
+                }
             }
         )
     }
@@ -593,8 +1456,15 @@ impl From<Error> for RenderPassCreationError {
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
+    use crate::framebuffer::AttachmentDescription;
+    use crate::framebuffer::LoadOp;
+    use crate::framebuffer::PassDescription;
     use crate::framebuffer::RenderPass;
     use crate::framebuffer::RenderPassCreationError;
+    use crate::framebuffer::ResolveMode;
+    use crate::framebuffer::RuntimeRenderPassDesc;
+    use crate::framebuffer::StoreOp;
+    use crate::image::ImageLayout;
 
     #[test]
     fn empty() {
@@ -656,4 +1526,273 @@ mod tests {
         assert_ne!(granularity[0], 0);
         assert_ne!(granularity[1], 0);
     }
+
+    #[test]
+    fn resolve_source_not_multisampled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 1, },
+                b: { load: DontCare, store: Store, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {},
+                resolve: [b],
+            }
+        };
+
+        match rp {
+            Err(RenderPassCreationError::ResolveSourceNotMultisampled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn resolve_format_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 4, },
+                b: { load: DontCare, store: Store, format: Format::B8G8R8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {},
+                resolve: [b],
+            }
+        };
+
+        match rp {
+            Err(RenderPassCreationError::ResolveAttachmentFormatMismatch) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multiview_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = RuntimeRenderPassDesc::new(
+            vec![],
+            vec![PassDescription {
+                color_attachments: vec![],
+                depth_stencil: None,
+                input_attachments: vec![],
+                resolve_attachments: vec![],
+                preserve_attachments: vec![],
+                fragment_shading_rate_attachment: None,
+                view_mask: 0b11,
+                depth_stencil_resolve: None,
+                multisampled_render_to_single_sampled: None,
+            }],
+            vec![],
+        )
+        .unwrap();
+
+        match RenderPass::new(device, desc) {
+            Err(RenderPassCreationError::MultiviewExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn correlation_masks_without_multiview() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut desc = RuntimeRenderPassDesc::new(
+            vec![],
+            vec![PassDescription {
+                color_attachments: vec![],
+                depth_stencil: None,
+                input_attachments: vec![],
+                resolve_attachments: vec![],
+                preserve_attachments: vec![],
+                fragment_shading_rate_attachment: None,
+                view_mask: 0,
+                depth_stencil_resolve: None,
+                multisampled_render_to_single_sampled: None,
+            }],
+            vec![],
+        )
+        .unwrap();
+        desc.set_correlation_masks(vec![0b11]);
+
+        match RenderPass::new(device, desc) {
+            Err(RenderPassCreationError::MultiviewRequired) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn depth_stencil_resolve_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let attachment = |samples| AttachmentDescription {
+            format: Format::D16Unorm,
+            samples,
+            load: LoadOp::Clear,
+            store: StoreOp::Store,
+            stencil_load: LoadOp::DontCare,
+            stencil_store: StoreOp::DontCare,
+            initial_layout: ImageLayout::Undefined,
+            final_layout: ImageLayout::DepthStencilAttachmentOptimal,
+        };
+
+        let desc = RuntimeRenderPassDesc::new(
+            vec![attachment(4), attachment(1)],
+            vec![PassDescription {
+                color_attachments: vec![],
+                depth_stencil: Some((0, ImageLayout::DepthStencilAttachmentOptimal)),
+                input_attachments: vec![],
+                resolve_attachments: vec![],
+                preserve_attachments: vec![],
+                fragment_shading_rate_attachment: None,
+                view_mask: 0,
+                depth_stencil_resolve: Some((
+                    1,
+                    ImageLayout::DepthStencilAttachmentOptimal,
+                    ResolveMode::SampleZero,
+                    ResolveMode::None,
+                )),
+                multisampled_render_to_single_sampled: None,
+            }],
+            vec![],
+        )
+        .unwrap();
+
+        match RenderPass::new(device, desc) {
+            Err(RenderPassCreationError::DepthStencilResolveExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn fragment_density_map_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut desc = RuntimeRenderPassDesc::new(
+            vec![AttachmentDescription {
+                format: Format::R8G8Unorm,
+                samples: 1,
+                load: LoadOp::Load,
+                store: StoreOp::DontCare,
+                stencil_load: LoadOp::DontCare,
+                stencil_store: StoreOp::DontCare,
+                initial_layout: ImageLayout::FragmentDensityMapOptimal,
+                final_layout: ImageLayout::FragmentDensityMapOptimal,
+            }],
+            vec![PassDescription {
+                color_attachments: vec![],
+                depth_stencil: None,
+                input_attachments: vec![],
+                resolve_attachments: vec![],
+                preserve_attachments: vec![],
+                fragment_shading_rate_attachment: None,
+                view_mask: 0,
+                depth_stencil_resolve: None,
+                multisampled_render_to_single_sampled: None,
+            }],
+            vec![],
+        )
+        .unwrap();
+        desc.set_fragment_density_map_attachment(Some((0, ImageLayout::FragmentDensityMapOptimal)));
+
+        match RenderPass::new(device, desc) {
+            Err(RenderPassCreationError::FragmentDensityMapExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn fragment_shading_rate_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = RuntimeRenderPassDesc::new(
+            vec![
+                AttachmentDescription {
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                    load: LoadOp::Clear,
+                    store: StoreOp::Store,
+                    stencil_load: LoadOp::DontCare,
+                    stencil_store: StoreOp::DontCare,
+                    initial_layout: ImageLayout::Undefined,
+                    final_layout: ImageLayout::ColorAttachmentOptimal,
+                },
+                AttachmentDescription {
+                    format: Format::R8Uint,
+                    samples: 1,
+                    load: LoadOp::Load,
+                    store: StoreOp::DontCare,
+                    stencil_load: LoadOp::DontCare,
+                    stencil_store: StoreOp::DontCare,
+                    initial_layout: ImageLayout::FragmentShadingRateAttachmentOptimal,
+                    final_layout: ImageLayout::FragmentShadingRateAttachmentOptimal,
+                },
+            ],
+            vec![PassDescription {
+                color_attachments: vec![(0, ImageLayout::ColorAttachmentOptimal)],
+                depth_stencil: None,
+                input_attachments: vec![],
+                resolve_attachments: vec![],
+                preserve_attachments: vec![],
+                fragment_shading_rate_attachment: Some((
+                    1,
+                    ImageLayout::FragmentShadingRateAttachmentOptimal,
+                    [16, 16],
+                )),
+                view_mask: 0,
+                depth_stencil_resolve: None,
+                multisampled_render_to_single_sampled: None,
+            }],
+            vec![],
+        )
+        .unwrap();
+
+        match RenderPass::new(device, desc) {
+            Err(RenderPassCreationError::FragmentShadingRateExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multisampled_render_to_single_sampled_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = RuntimeRenderPassDesc::new(
+            vec![AttachmentDescription {
+                format: Format::R8G8B8A8Unorm,
+                samples: 1,
+                load: LoadOp::Clear,
+                store: StoreOp::Store,
+                stencil_load: LoadOp::DontCare,
+                stencil_store: StoreOp::DontCare,
+                initial_layout: ImageLayout::Undefined,
+                final_layout: ImageLayout::ColorAttachmentOptimal,
+            }],
+            vec![PassDescription {
+                color_attachments: vec![(0, ImageLayout::ColorAttachmentOptimal)],
+                depth_stencil: None,
+                input_attachments: vec![],
+                resolve_attachments: vec![],
+                preserve_attachments: vec![],
+                fragment_shading_rate_attachment: None,
+                view_mask: 0,
+                depth_stencil_resolve: None,
+                multisampled_render_to_single_sampled: Some(4),
+            }],
+            vec![],
+        )
+        .unwrap();
+
+        match RenderPass::new(device, desc) {
+            Err(RenderPassCreationError::MultisampledRenderToSingleSampledExtensionNotEnabled) => {}
+            _ => panic!(),
+        }
+    }
 }
@@ -7,12 +7,18 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::cmp;
+use std::error;
+use std::fmt;
+
+use crate::descriptor::descriptor_set::DescriptorWrite;
 use crate::device::DeviceOwned;
 use crate::format::ClearValue;
 use crate::framebuffer::FramebufferSys;
 use crate::framebuffer::RenderPassDesc;
 use crate::framebuffer::RenderPassSys;
 use crate::image::view::ImageViewAbstract;
+use crate::image::ImageLayout;
 use crate::pipeline::shader::ShaderInterfaceDef;
 
 use crate::SafeDeref;
@@ -33,6 +39,13 @@ pub unsafe trait FramebufferAbstract: RenderPassAbstract {
     /// If the `index` is not between `0` and `num_attachments`, then `None` should be returned.
     fn attached_image_view(&self, index: usize) -> Option<&dyn ImageViewAbstract>;
 
+    /// Returns true if the framebuffer was created without images, in which case the image views
+    /// returned by `attached_image_view` are passed when beginning the render pass.
+    #[inline]
+    fn is_imageless(&self) -> bool {
+        false
+    }
+
     /// Returns the width of the framebuffer in pixels.
     #[inline]
     fn width(&self) -> u32 {
@@ -71,6 +84,11 @@ where
     fn attached_image_view(&self, index: usize) -> Option<&dyn ImageViewAbstract> {
         (**self).attached_image_view(index)
     }
+
+    #[inline]
+    fn is_imageless(&self) -> bool {
+        (**self).is_imageless()
+    }
 }
 
 /// Trait for objects that contain a Vulkan render pass object.
@@ -222,8 +240,18 @@ where
 {
     /// Returns `true` if this layout is compatible with the other layout, as defined in the
     /// `Render Pass Compatibility` section of the Vulkan specs.
-    // TODO: return proper error
-    fn is_compatible_with(&self, other: &Other) -> bool;
+    #[inline]
+    fn is_compatible_with(&self, other: &Other) -> bool {
+        self.ensure_compatible_with(other).is_ok()
+    }
+
+    /// Checks whether this layout is compatible with the other layout, as defined in the
+    /// `Render Pass Compatibility` section of the Vulkan specs, and returns the first
+    /// incompatibility that is found.
+    ///
+    /// Framebuffers and graphics pipelines that were created for a render pass can be used with
+    /// any other render pass that is compatible with it.
+    fn ensure_compatible_with(&self, other: &Other) -> Result<(), IncompatibleRenderPassError>;
 }
 
 unsafe impl<A: ?Sized, B: ?Sized> RenderPassCompatible<B> for A
@@ -231,9 +259,12 @@ where
     A: RenderPassDesc,
     B: RenderPassDesc,
 {
-    fn is_compatible_with(&self, other: &B) -> bool {
+    fn ensure_compatible_with(&self, other: &B) -> Result<(), IncompatibleRenderPassError> {
         if self.num_attachments() != other.num_attachments() {
-            return false;
+            return Err(IncompatibleRenderPassError::AttachmentsCountMismatch {
+                expected: self.num_attachments(),
+                obtained: other.num_attachments(),
+            });
         }
 
         for atch_num in 0..self.num_attachments() {
@@ -241,13 +272,272 @@ where
             let other_atch = other.attachment_desc(atch_num).unwrap();
 
             if !my_atch.is_compatible_with(&other_atch) {
-                return false;
+                return Err(IncompatibleRenderPassError::AttachmentMismatch {
+                    attachment: atch_num,
+                });
             }
         }
 
-        return true;
+        if self.num_subpasses() != other.num_subpasses() {
+            return Err(IncompatibleRenderPassError::SubpassesCountMismatch {
+                expected: self.num_subpasses(),
+                obtained: other.num_subpasses(),
+            });
+        }
+
+        // Two attachment references are compatible if they refer to attachments with the same
+        // format and number of samples, or are both unused.
+        let my_reference = |index: Option<usize>| {
+            index.map(|index| {
+                let attachment = self.attachment_desc(index).unwrap();
+                (attachment.format, attachment.samples)
+            })
+        };
+        let other_reference = |index: Option<usize>| {
+            index.map(|index| {
+                let attachment = other.attachment_desc(index).unwrap();
+                (attachment.format, attachment.samples)
+            })
+        };
+        // Lists of references of different lengths are compared as if the shortest was padded
+        // with unused references.
+        let references_compatible =
+            |mine: &[(usize, ImageLayout)], others: &[(usize, ImageLayout)]| {
+                (0..cmp::max(mine.len(), others.len())).all(|i| {
+                    my_reference(mine.get(i).map(|r| r.0))
+                        == other_reference(others.get(i).map(|r| r.0))
+                })
+            };
+
+        // Resolve attachments are ignored if the render passes only have one subpass.
+        let single_subpass = self.num_subpasses() == 1;
+
+        for subpass in 0..self.num_subpasses() {
+            let mine = self.subpass_desc(subpass).unwrap();
+            let others = other.subpass_desc(subpass).unwrap();
+
+            if !references_compatible(&mine.color_attachments, &others.color_attachments) {
+                return Err(IncompatibleRenderPassError::ColorAttachmentsMismatch { subpass });
+            }
+            if !references_compatible(&mine.input_attachments, &others.input_attachments) {
+                return Err(IncompatibleRenderPassError::InputAttachmentsMismatch { subpass });
+            }
+            if my_reference(mine.depth_stencil.map(|r| r.0))
+                != other_reference(others.depth_stencil.map(|r| r.0))
+            {
+                return Err(
+                    IncompatibleRenderPassError::DepthStencilAttachmentMismatch { subpass },
+                );
+            }
+
+            if !single_subpass {
+                if !references_compatible(&mine.resolve_attachments, &others.resolve_attachments) {
+                    return Err(IncompatibleRenderPassError::ResolveAttachmentsMismatch {
+                        subpass,
+                    });
+                }
+
+                let my_resolve = mine.depth_stencil_resolve;
+                let other_resolve = others.depth_stencil_resolve;
+                if my_reference(my_resolve.map(|r| r.0))
+                    != other_reference(other_resolve.map(|r| r.0))
+                    || my_resolve.map(|r| (r.2, r.3)) != other_resolve.map(|r| (r.2, r.3))
+                {
+                    return Err(IncompatibleRenderPassError::ResolveAttachmentsMismatch {
+                        subpass,
+                    });
+                }
+            }
 
-        // FIXME: finish
+            if mine.preserve_attachments != others.preserve_attachments {
+                return Err(IncompatibleRenderPassError::PreserveAttachmentsMismatch { subpass });
+            }
+
+            let my_fsr = mine.fragment_shading_rate_attachment;
+            let other_fsr = others.fragment_shading_rate_attachment;
+            if my_reference(my_fsr.map(|r| r.0)) != other_reference(other_fsr.map(|r| r.0))
+                || my_fsr.map(|r| r.2) != other_fsr.map(|r| r.2)
+            {
+                return Err(
+                    IncompatibleRenderPassError::FragmentShadingRateAttachmentMismatch { subpass },
+                );
+            }
+
+            if mine.view_mask != others.view_mask {
+                return Err(IncompatibleRenderPassError::ViewMaskMismatch { subpass });
+            }
+
+            if mine.multisampled_render_to_single_sampled
+                != others.multisampled_render_to_single_sampled
+            {
+                return Err(
+                    IncompatibleRenderPassError::MultisampledRenderToSingleSampledMismatch {
+                        subpass,
+                    },
+                );
+            }
+        }
+
+        if self.num_dependencies() != other.num_dependencies() {
+            return Err(IncompatibleRenderPassError::DependenciesMismatch);
+        }
+
+        for dependency in 0..self.num_dependencies() {
+            let mine = self.dependency_desc(dependency).unwrap();
+            let others = other.dependency_desc(dependency).unwrap();
+
+            let identical = mine.source_subpass == others.source_subpass
+                && mine.destination_subpass == others.destination_subpass
+                && mine.source_stages.into_vulkan_bits() == others.source_stages.into_vulkan_bits()
+                && mine.destination_stages.into_vulkan_bits()
+                    == others.destination_stages.into_vulkan_bits()
+                && mine.source_access.into_vulkan_bits() == others.source_access.into_vulkan_bits()
+                && mine.destination_access.into_vulkan_bits()
+                    == others.destination_access.into_vulkan_bits()
+                && mine.by_region == others.by_region
+                && mine.view_local == others.view_local;
+            if !identical {
+                return Err(IncompatibleRenderPassError::DependenciesMismatch);
+            }
+        }
+
+        if self.correlation_masks() != other.correlation_masks() {
+            return Err(IncompatibleRenderPassError::CorrelationMasksMismatch);
+        }
+
+        let my_density_map = self.fragment_density_map_attachment().map(|r| r.0);
+        let other_density_map = other.fragment_density_map_attachment().map(|r| r.0);
+        if my_reference(my_density_map) != other_reference(other_density_map) {
+            return Err(IncompatibleRenderPassError::FragmentDensityMapAttachmentMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+/// Error that is returned when two render passes are not compatible.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IncompatibleRenderPassError {
+    /// The render passes don't have the same number of attachments.
+    AttachmentsCountMismatch { expected: usize, obtained: usize },
+    /// An attachment doesn't have the same format or number of samples in both render passes.
+    AttachmentMismatch { attachment: usize },
+    /// The render passes don't have the same number of subpasses.
+    SubpassesCountMismatch { expected: usize, obtained: usize },
+    /// The color attachments of a subpass are not compatible.
+    ColorAttachmentsMismatch { subpass: usize },
+    /// The input attachments of a subpass are not compatible.
+    InputAttachmentsMismatch { subpass: usize },
+    /// The depth-stencil attachments of a subpass are not compatible.
+    DepthStencilAttachmentMismatch { subpass: usize },
+    /// The resolve attachments or depth-stencil resolve of a subpass are not compatible.
+    ResolveAttachmentsMismatch { subpass: usize },
+    /// The preserve attachments of a subpass are not identical.
+    PreserveAttachmentsMismatch { subpass: usize },
+    /// The fragment shading rate attachments of a subpass are not compatible.
+    FragmentShadingRateAttachmentMismatch { subpass: usize },
+    /// The view masks of a subpass are not identical.
+    ViewMaskMismatch { subpass: usize },
+    /// A subpass doesn't render multisampled to single-sampled attachments with the same number
+    /// of samples in both render passes.
+    MultisampledRenderToSingleSampledMismatch { subpass: usize },
+    /// The dependencies of the render passes are not identical.
+    DependenciesMismatch,
+    /// The correlation masks of the render passes are not identical.
+    CorrelationMasksMismatch,
+    /// The fragment density map attachments of the render passes are not compatible.
+    FragmentDensityMapAttachmentMismatch,
+}
+
+impl error::Error for IncompatibleRenderPassError {}
+
+impl fmt::Display for IncompatibleRenderPassError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            IncompatibleRenderPassError::AttachmentsCountMismatch { expected, obtained } => write!(
+                fmt,
+                "the render passes don't have the same number of attachments (expected {}, \
+                 obtained {})",
+                expected, obtained
+            ),
+            IncompatibleRenderPassError::AttachmentMismatch { attachment } => write!(
+                fmt,
+                "attachment {} doesn't have the same format or number of samples in both render \
+                 passes",
+                attachment
+            ),
+            IncompatibleRenderPassError::SubpassesCountMismatch { expected, obtained } => write!(
+                fmt,
+                "the render passes don't have the same number of subpasses (expected {}, \
+                 obtained {})",
+                expected, obtained
+            ),
+            IncompatibleRenderPassError::ColorAttachmentsMismatch { subpass } => write!(
+                fmt,
+                "the color attachments of subpass {} are not compatible",
+                subpass
+            ),
+            IncompatibleRenderPassError::InputAttachmentsMismatch { subpass } => write!(
+                fmt,
+                "the input attachments of subpass {} are not compatible",
+                subpass
+            ),
+            IncompatibleRenderPassError::DepthStencilAttachmentMismatch { subpass } => write!(
+                fmt,
+                "the depth-stencil attachments of subpass {} are not compatible",
+                subpass
+            ),
+            IncompatibleRenderPassError::ResolveAttachmentsMismatch { subpass } => write!(
+                fmt,
+                "the resolve attachments of subpass {} are not compatible",
+                subpass
+            ),
+            IncompatibleRenderPassError::PreserveAttachmentsMismatch { subpass } => write!(
+                fmt,
+                "the preserve attachments of subpass {} are not identical",
+                subpass
+            ),
+            IncompatibleRenderPassError::FragmentShadingRateAttachmentMismatch { subpass } => {
+                write!(
+                    fmt,
+                    "the fragment shading rate attachments of subpass {} are not compatible",
+                    subpass
+                )
+            }
+            IncompatibleRenderPassError::ViewMaskMismatch { subpass } => write!(
+                fmt,
+                "the view masks of subpass {} are not identical",
+                subpass
+            ),
+            IncompatibleRenderPassError::MultisampledRenderToSingleSampledMismatch { subpass } => {
+                write!(
+                    fmt,
+                    "subpass {} doesn't render multisampled to single-sampled attachments with \
+                     the same number of samples in both render passes",
+                    subpass
+                )
+            }
+            IncompatibleRenderPassError::DependenciesMismatch => {
+                write!(
+                    fmt,
+                    "the dependencies of the render passes are not identical"
+                )
+            }
+            IncompatibleRenderPassError::CorrelationMasksMismatch => {
+                write!(
+                    fmt,
+                    "the correlation masks of the render passes are not identical"
+                )
+            }
+            IncompatibleRenderPassError::FragmentDensityMapAttachmentMismatch => {
+                write!(
+                    fmt,
+                    "the fragment density map attachments of the render passes are not \
+                     compatible"
+                )
+            }
+        }
     }
 }
 
@@ -335,6 +625,55 @@ where
     pub fn num_samples(&self) -> Option<u32> {
         self.render_pass.num_samples(self.subpass_id)
     }
+
+    /// Returns the views that the subpass renders to, or 0 if it doesn't use multiview.
+    #[inline]
+    pub fn view_mask(&self) -> u32 {
+        self.render_pass
+            .subpass_desc(self.subpass_id as usize)
+            .unwrap()
+            .view_mask
+    }
+
+    /// Returns the descriptor writes that bind the input attachments of this subpass, taken from
+    /// `framebuffer`.
+    ///
+    /// The input attachment with index `i` in the subpass, which corresponds to
+    /// `input_attachment_index = i` in the shader, is written to binding `first_binding + i`.
+    /// Each descriptor uses the layout that the subpass uses for the attachment, which is the
+    /// layout that the image is in when the shader reads it.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `framebuffer` doesn't have one of the input attachments of this subpass.
+    ///
+    pub fn input_attachment_writes<F>(
+        &self,
+        framebuffer: &F,
+        first_binding: u32,
+    ) -> Vec<DescriptorWrite>
+    where
+        F: ?Sized + FramebufferAbstract,
+    {
+        self.render_pass
+            .subpass_desc(self.subpass_id as usize)
+            .unwrap()
+            .input_attachments
+            .into_iter()
+            .enumerate()
+            .map(|(num, (attachment, layout))| {
+                let image_view = framebuffer
+                    .attached_image_view(attachment)
+                    .expect("the framebuffer doesn't have an input attachment of the subpass");
+                DescriptorWrite::input_attachment_with_layout(
+                    first_binding + num as u32,
+                    0,
+                    image_view,
+                    layout,
+                )
+            })
+            .collect()
+    }
 }
 
 impl<L> Subpass<L> {
@@ -357,3 +696,138 @@ impl<L> Into<(L, u32)> for Subpass<L> {
         (self.render_pass, self.subpass_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::descriptor::descriptor::DescriptorType;
+    use crate::format::Format;
+    use crate::framebuffer::AttachmentDescription;
+    use crate::framebuffer::Framebuffer;
+    use crate::framebuffer::IncompatibleRenderPassError;
+    use crate::framebuffer::LoadOp;
+    use crate::framebuffer::PassDescription;
+    use crate::framebuffer::RenderPassCompatible;
+    use crate::framebuffer::RuntimeRenderPassDesc;
+    use crate::framebuffer::StoreOp;
+    use crate::framebuffer::Subpass;
+    use crate::image::view::ImageView;
+    use crate::image::AttachmentImage;
+    use crate::image::ImageLayout;
+
+    fn desc(formats: &[Format], load: LoadOp, num_passes: usize) -> RuntimeRenderPassDesc {
+        let attachments = formats
+            .iter()
+            .map(|&format| AttachmentDescription {
+                format,
+                samples: 1,
+                load,
+                store: StoreOp::Store,
+                stencil_load: LoadOp::DontCare,
+                stencil_store: StoreOp::DontCare,
+                initial_layout: ImageLayout::Undefined,
+                final_layout: ImageLayout::ColorAttachmentOptimal,
+            })
+            .collect();
+        let passes = (0..num_passes)
+            .map(|_| PassDescription {
+                color_attachments: (0..formats.len())
+                    .map(|a| (a, ImageLayout::ColorAttachmentOptimal))
+                    .collect(),
+                depth_stencil: None,
+                input_attachments: vec![],
+                resolve_attachments: vec![],
+                preserve_attachments: vec![],
+                fragment_shading_rate_attachment: None,
+                view_mask: 0,
+                depth_stencil_resolve: None,
+                multisampled_render_to_single_sampled: None,
+            })
+            .collect();
+        RuntimeRenderPassDesc::new(attachments, passes, vec![]).unwrap()
+    }
+
+    #[test]
+    fn compatible_different_load_ops() {
+        let a = desc(&[Format::R8G8B8A8Unorm], LoadOp::Clear, 1);
+        let b = desc(&[Format::R8G8B8A8Unorm], LoadOp::DontCare, 1);
+        assert_eq!(a.ensure_compatible_with(&b), Ok(()));
+    }
+
+    #[test]
+    fn incompatible_format() {
+        let a = desc(&[Format::R8G8B8A8Unorm], LoadOp::Clear, 1);
+        let b = desc(&[Format::B8G8R8A8Unorm], LoadOp::Clear, 1);
+        assert_eq!(
+            a.ensure_compatible_with(&b),
+            Err(IncompatibleRenderPassError::AttachmentMismatch { attachment: 0 })
+        );
+    }
+
+    #[test]
+    fn incompatible_subpasses_count() {
+        let a = desc(&[Format::R8G8B8A8Unorm], LoadOp::Clear, 1);
+        let b = desc(&[Format::R8G8B8A8Unorm], LoadOp::Clear, 2);
+        assert_eq!(
+            a.ensure_compatible_with(&b),
+            Err(IncompatibleRenderPassError::SubpassesCountMismatch {
+                expected: 1,
+                obtained: 2
+            })
+        );
+    }
+
+    #[test]
+    fn input_attachment_writes() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = Arc::new(
+            ordered_passes_renderpass!(device.clone(),
+                attachments: {
+                    a: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    },
+                    b: {
+                        load: Clear,
+                        store: Store,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    }
+                },
+                passes: [
+                    { color: [a], depth_stencil: {}, input: [] },
+                    { color: [b], depth_stencil: {}, input: [a] }
+                ]
+            )
+            .unwrap(),
+        );
+
+        let image = |device| {
+            let image =
+                AttachmentImage::input_attachment(device, [32, 32], Format::R8G8B8A8Unorm).unwrap();
+            ImageView::new(image).unwrap()
+        };
+        let framebuffer = Framebuffer::start(render_pass.clone())
+            .add(image(device.clone()))
+            .unwrap()
+            .add(image(device.clone()))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let writes = Subpass::from(render_pass.clone(), 1)
+            .unwrap()
+            .input_attachment_writes(&framebuffer, 0);
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].ty(), DescriptorType::InputAttachment);
+
+        let writes = Subpass::from(render_pass, 0)
+            .unwrap()
+            .input_attachment_writes(&framebuffer, 0);
+        assert!(writes.is_empty());
+    }
+}
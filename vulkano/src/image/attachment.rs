@@ -32,11 +32,10 @@ use crate::image::ImageDimensions;
 use crate::image::ImageInner;
 use crate::image::ImageLayout;
 use crate::image::ImageUsage;
-use crate::memory::pool::AllocFromRequirementsFilter;
 use crate::memory::pool::AllocLayout;
-use crate::memory::pool::MappingRequirement;
-use crate::memory::pool::MemoryPool;
+use crate::memory::pool::MemoryAllocator;
 use crate::memory::pool::MemoryPoolAlloc;
+use crate::memory::pool::MemoryUsage;
 use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPoolAlloc;
 use crate::memory::DedicatedAlloc;
@@ -388,6 +387,7 @@ impl<F> AttachmentImage<F> {
     }
 
     // All constructors dispatch to this one.
+    #[inline]
     fn new_impl(
         device: Arc<Device>,
         dimensions: [u32; 2],
@@ -397,6 +397,28 @@ impl<F> AttachmentImage<F> {
     ) -> Result<Arc<AttachmentImage<F>>, ImageCreationError>
     where
         F: FormatDesc,
+    {
+        let pool = Device::standard_pool(&device);
+        AttachmentImage::with_allocator(device, dimensions, samples, format, base_usage, &pool)
+    }
+}
+
+impl<F, A> AttachmentImage<F, PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc,
+{
+    /// Same as `multisampled_with_usage`, but allocates the memory of the image from `allocator`.
+    pub fn with_allocator<P>(
+        device: Arc<Device>,
+        dimensions: [u32; 2],
+        samples: u32,
+        format: F,
+        base_usage: ImageUsage,
+        allocator: &P,
+    ) -> Result<Arc<AttachmentImage<F, PotentialDedicatedAllocation<A>>>, ImageCreationError>
+    where
+        F: FormatDesc,
+        P: MemoryAllocator<Alloc = A>,
     {
         // TODO: check dimensions against the max_framebuffer_width/height/layers limits
 
@@ -435,19 +457,12 @@ impl<F> AttachmentImage<F> {
             )?
         };
 
-        let memory = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
+        let memory = MemoryAllocator::alloc_for_usage(
+            allocator,
             &mem_reqs,
             AllocLayout::Optimal,
-            MappingRequirement::DoNotMap,
+            MemoryUsage::DeviceOnly,
             DedicatedAlloc::Image(&image),
-            |t| {
-                if t.is_device_local() {
-                    AllocFromRequirementsFilter::Preferred
-                } else {
-                    AllocFromRequirementsFilter::Allowed
-                }
-            },
         )?;
         debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
         unsafe {
@@ -32,11 +32,10 @@ use crate::image::ImageLayout;
 use crate::image::ImageUsage;
 use crate::image::MipmapsCount;
 use crate::instance::QueueFamily;
-use crate::memory::pool::AllocFromRequirementsFilter;
 use crate::memory::pool::AllocLayout;
-use crate::memory::pool::MappingRequirement;
-use crate::memory::pool::MemoryPool;
+use crate::memory::pool::MemoryAllocator;
 use crate::memory::pool::MemoryPoolAlloc;
+use crate::memory::pool::MemoryUsage;
 use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPoolAlloc;
 use crate::memory::DedicatedAlloc;
@@ -252,68 +251,16 @@ impl<F> ImmutableImage<F> {
         I: IntoIterator<Item = QueueFamily<'a>>,
         M: Into<MipmapsCount>,
     {
-        let queue_families = queue_families
-            .into_iter()
-            .map(|f| f.id())
-            .collect::<SmallVec<[u32; 4]>>();
-
-        let (image, mem_reqs) = unsafe {
-            let sharing = if queue_families.len() >= 2 {
-                Sharing::Concurrent(queue_families.iter().cloned())
-            } else {
-                Sharing::Exclusive
-            };
-
-            UnsafeImage::new(
-                device.clone(),
-                usage,
-                format.format(),
-                flags,
-                dimensions,
-                1,
-                mipmaps,
-                sharing,
-                false,
-                false,
-            )?
-        };
-
-        let memory = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
-            &mem_reqs,
-            AllocLayout::Optimal,
-            MappingRequirement::DoNotMap,
-            DedicatedAlloc::Image(&image),
-            |t| {
-                if t.is_device_local() {
-                    AllocFromRequirementsFilter::Preferred
-                } else {
-                    AllocFromRequirementsFilter::Allowed
-                }
-            },
-        )?;
-        debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
-        unsafe {
-            image.bind_memory(memory.memory(), memory.offset())?;
-        }
-
-        let image = Arc::new(ImmutableImage {
-            image,
-            memory,
+        let pool = Device::standard_pool(&device);
+        let infos = UninitializedInfos {
             dimensions,
             format,
-            initialized: AtomicBool::new(false),
+            mipmaps,
+            usage,
+            flags,
             layout,
-        });
-
-        let init = ImmutableImageInitialization {
-            image: image.clone(),
-            used: AtomicBool::new(false),
-            mip_levels_access: 0..image.mipmap_levels(),
-            layer_levels_access: 0..image.dimensions().array_layers(),
         };
-
-        Ok((image, init))
+        ImmutableImage::uninitialized_impl(device, infos, queue_families, &pool)
     }
 
     /// Construct an ImmutableImage from the contents of `iter`.
@@ -337,22 +284,85 @@ impl<F> ImmutableImage<F> {
         I: ExactSizeIterator<Item = P>,
         Format: AcceptsPixels<P>,
     {
-        let source = CpuAccessibleBuffer::from_iter(
+        let pool = Device::standard_pool(queue.device());
+        ImmutableImage::from_iter_with_allocator(iter, dimensions, mipmaps, format, queue, &pool)
+    }
+
+    /// Construct an ImmutableImage containing a copy of the data in `source`.
+    pub fn from_buffer<B, P>(
+        source: B,
+        dimensions: ImageDimensions,
+        mipmaps: MipmapsCount,
+        format: F,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        B: BufferAccess + TypedBufferAccess<Content = [P]> + 'static + Clone + Send + Sync,
+        P: Send + Sync + Clone + 'static,
+        F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
+        Format: AcceptsPixels<P>,
+    {
+        let pool = Device::standard_pool(source.device());
+        ImmutableImage::from_buffer_with_allocator(
+            source, dimensions, mipmaps, format, queue, &pool,
+        )
+    }
+}
+
+impl<F, A> ImmutableImage<F, PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc,
+{
+    /// Same as `from_iter`, but allocates the memory of the image, and of the buffer used for the
+    /// upload, from `allocator`.
+    pub fn from_iter_with_allocator<P, I, Al>(
+        iter: I,
+        dimensions: ImageDimensions,
+        mipmaps: MipmapsCount,
+        format: F,
+        queue: Arc<Queue>,
+        allocator: &Al,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        P: Send + Sync + Clone + 'static,
+        F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
+        I: ExactSizeIterator<Item = P>,
+        Format: AcceptsPixels<P>,
+        A: Send + Sync + 'static,
+        Al: MemoryAllocator<Alloc = A>,
+    {
+        let source = CpuAccessibleBuffer::from_iter_with_allocator(
             queue.device().clone(),
             BufferUsage::transfer_source(),
             false,
             iter,
+            allocator,
         )?;
-        ImmutableImage::from_buffer(source, dimensions, mipmaps, format, queue)
+        ImmutableImage::from_buffer_with_allocator(
+            source, dimensions, mipmaps, format, queue, allocator,
+        )
     }
 
-    /// Construct an ImmutableImage containing a copy of the data in `source`.
-    pub fn from_buffer<B, P>(
+    /// Same as `from_buffer`, but allocates the memory of the image from `allocator`.
+    pub fn from_buffer_with_allocator<B, P, Al>(
         source: B,
         dimensions: ImageDimensions,
         mipmaps: MipmapsCount,
         format: F,
         queue: Arc<Queue>,
+        allocator: &Al,
     ) -> Result<
         (
             Arc<Self>,
@@ -365,6 +375,8 @@ impl<F> ImmutableImage<F> {
         P: Send + Sync + Clone + 'static,
         F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
         Format: AcceptsPixels<P>,
+        A: Send + Sync + 'static,
+        Al: MemoryAllocator<Alloc = A>,
     {
         let need_to_generate_mipmaps = has_mipmaps(mipmaps);
         let usage = ImageUsage {
@@ -376,15 +388,19 @@ impl<F> ImmutableImage<F> {
         let flags = ImageCreateFlags::none();
         let layout = ImageLayout::ShaderReadOnlyOptimal;
 
-        let (image, initializer) = ImmutableImage::uninitialized(
-            source.device().clone(),
+        let infos = UninitializedInfos {
             dimensions,
             format,
             mipmaps,
             usage,
             flags,
             layout,
+        };
+        let (image, initializer) = ImmutableImage::uninitialized_impl(
+            source.device().clone(),
+            infos,
             source.device().active_queue_families(),
+            allocator,
         )?;
 
         let init = SubImage::new(
@@ -428,6 +444,103 @@ impl<F> ImmutableImage<F> {
 
         Ok((image, future))
     }
+
+    // Internal implementation of `uninitialized`, which allocates the memory of the image from
+    // `allocator`.
+    fn uninitialized_impl<'a, I, M, Al>(
+        device: Arc<Device>,
+        infos: UninitializedInfos<F, M>,
+        queue_families: I,
+        allocator: &Al,
+    ) -> Result<UninitializedImage<F, A>, ImageCreationError>
+    where
+        F: FormatDesc,
+        I: IntoIterator<Item = QueueFamily<'a>>,
+        Al: MemoryAllocator<Alloc = A>,
+        M: Into<MipmapsCount>,
+    {
+        let UninitializedInfos {
+            dimensions,
+            format,
+            mipmaps,
+            usage,
+            flags,
+            layout,
+        } = infos;
+
+        let queue_families = queue_families
+            .into_iter()
+            .map(|f| f.id())
+            .collect::<SmallVec<[u32; 4]>>();
+
+        let (image, mem_reqs) = unsafe {
+            let sharing = if queue_families.len() >= 2 {
+                Sharing::Concurrent(queue_families.iter().cloned())
+            } else {
+                Sharing::Exclusive
+            };
+
+            UnsafeImage::new(
+                device.clone(),
+                usage,
+                format.format(),
+                flags,
+                dimensions,
+                1,
+                mipmaps,
+                sharing,
+                false,
+                false,
+            )?
+        };
+
+        let memory = MemoryAllocator::alloc_for_usage(
+            allocator,
+            &mem_reqs,
+            AllocLayout::Optimal,
+            MemoryUsage::DeviceOnly,
+            DedicatedAlloc::Image(&image),
+        )?;
+        debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
+        unsafe {
+            image.bind_memory(memory.memory(), memory.offset())?;
+        }
+
+        let image = Arc::new(ImmutableImage {
+            image,
+            memory,
+            dimensions,
+            format,
+            initialized: AtomicBool::new(false),
+            layout,
+        });
+
+        let init = ImmutableImageInitialization {
+            image: image.clone(),
+            used: AtomicBool::new(false),
+            mip_levels_access: 0..image.mipmap_levels(),
+            layer_levels_access: 0..image.dimensions().array_layers(),
+        };
+
+        Ok((image, init))
+    }
+}
+
+// Image and initialization access returned by `uninitialized_impl`.
+type UninitializedImage<F, A> = (
+    Arc<ImmutableImage<F, PotentialDedicatedAllocation<A>>>,
+    ImmutableImageInitialization<F, PotentialDedicatedAllocation<A>>,
+);
+
+// Parameters of `ImmutableImage::uninitialized`, shared with the constructors that take an
+// allocator.
+struct UninitializedInfos<F, M> {
+    dimensions: ImageDimensions,
+    format: F,
+    mipmaps: M,
+    usage: ImageUsage,
+    flags: ImageCreateFlags,
+    layout: ImageLayout,
 }
 
 impl<F, A> ImmutableImage<F, A> {
@@ -39,6 +39,9 @@ pub enum ImageLayout {
     TransferDstOptimal = vk::IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL,
     Preinitialized = vk::IMAGE_LAYOUT_PREINITIALIZED,
     PresentSrc = vk::IMAGE_LAYOUT_PRESENT_SRC_KHR,
+    FragmentShadingRateAttachmentOptimal =
+        vk::IMAGE_LAYOUT_FRAGMENT_SHADING_RATE_ATTACHMENT_OPTIMAL_KHR,
+    FragmentDensityMapOptimal = vk::IMAGE_LAYOUT_FRAGMENT_DENSITY_MAP_OPTIMAL_EXT,
 }
 
 /// The set of layouts to use for an image when used in descriptor of various kinds.
@@ -223,6 +223,10 @@ pub struct ImageCreateFlags {
     pub mutable_format: bool,
     pub cube_compatible: bool,
     pub array_2d_compatible: bool,
+    pub multisampled_render_to_single_sampled: bool,
+    /// The image is a protected image, whose memory must be of a protected memory type. Requires
+    /// the `protected_memory` feature.
+    pub protected: bool,
 }
 
 impl ImageCreateFlags {
@@ -234,6 +238,8 @@ impl ImageCreateFlags {
             mutable_format: true,
             cube_compatible: true,
             array_2d_compatible: true,
+            multisampled_render_to_single_sampled: true,
+            protected: true,
         }
     }
 
@@ -263,6 +269,12 @@ impl From<ImageCreateFlags> for vk::ImageCreateFlags {
         if flags.array_2d_compatible {
             vk_flags |= vk::IMAGE_CREATE_2D_ARRAY_COMPATIBLE_BIT_KHR
         };
+        if flags.multisampled_render_to_single_sampled {
+            vk_flags |= vk::IMAGE_CREATE_MULTISAMPLED_RENDER_TO_SINGLE_SAMPLED_BIT_EXT
+        };
+        if flags.protected {
+            vk_flags |= vk::IMAGE_CREATE_PROTECTED_BIT
+        };
         vk_flags
     }
 }
@@ -14,6 +14,11 @@ use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+#[cfg(target_os = "linux")]
+use std::fs::File;
+#[cfg(windows)]
+use std::os::raw::c_void;
+
 use crate::buffer::BufferAccess;
 use crate::device::Device;
 use crate::format::ClearValue;
@@ -31,14 +36,20 @@ use crate::image::ImageInner;
 use crate::image::ImageLayout;
 use crate::image::ImageUsage;
 use crate::instance::QueueFamily;
-use crate::memory::pool::AllocFromRequirementsFilter;
+use crate::memory::pool::choose_allocation_memory_type;
 use crate::memory::pool::AllocLayout;
 use crate::memory::pool::MappingRequirement;
+use crate::memory::pool::MemoryAllocator;
 use crate::memory::pool::MemoryPool;
 use crate::memory::pool::MemoryPoolAlloc;
+use crate::memory::pool::MemoryUsage;
 use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPool;
 use crate::memory::DedicatedAlloc;
+use crate::memory::DeviceMemory;
+#[cfg(any(target_os = "linux", windows))]
+use crate::memory::DeviceMemoryAllocError;
+use crate::memory::ExternalMemoryHandleType;
 use crate::sync::AccessError;
 use crate::sync::Sharing;
 
@@ -98,6 +109,8 @@ impl<F> StorageImage<F> {
             depth_stencil_attachment: is_depth,
             input_attachment: true,
             transient_attachment: false,
+            fragment_shading_rate_attachment: false,
+            fragment_density_map: false,
         };
         let flags = ImageCreateFlags::none();
 
@@ -113,6 +126,113 @@ impl<F> StorageImage<F> {
         flags: ImageCreateFlags,
         queue_families: I,
     ) -> Result<Arc<StorageImage<F>>, ImageCreationError>
+    where
+        F: FormatDesc,
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        let pool = Device::standard_pool(&device);
+        StorageImage::with_allocator(
+            device,
+            dimensions,
+            format,
+            usage,
+            flags,
+            queue_families,
+            &pool,
+        )
+    }
+
+    /// Same as `with_usage`, but the memory of the image can be exported with any of the given
+    /// handle types, for example to share it with another process or another API.
+    ///
+    /// The image always gets a dedicated allocation, so that the exported memory doesn't contain
+    /// anything else.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_memory` extension isn't loaded.
+    ///
+    pub fn with_exportable<'a, I>(
+        device: Arc<Device>,
+        dimensions: ImageDimensions,
+        format: F,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+        queue_families: I,
+        handle_types: ExternalMemoryHandleType,
+    ) -> Result<Arc<StorageImage<F>>, ImageCreationError>
+    where
+        F: FormatDesc,
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        assert!(device.loaded_extensions_or_core().khr_external_memory);
+
+        let queue_families = queue_families
+            .into_iter()
+            .map(|f| f.id())
+            .collect::<SmallVec<[u32; 4]>>();
+
+        let (image, mem_reqs) = unsafe {
+            let sharing = if queue_families.len() >= 2 {
+                Sharing::Concurrent(queue_families.iter().cloned())
+            } else {
+                Sharing::Exclusive
+            };
+
+            UnsafeImage::new_with_external_memory(
+                device.clone(),
+                usage,
+                format.format(),
+                flags,
+                dimensions,
+                sharing,
+                handle_types,
+            )?
+        };
+
+        let policy = device.memory_type_policy();
+        let mem_ty = choose_allocation_memory_type(
+            &device,
+            &mem_reqs,
+            |t| policy.device_only.filter(t),
+            MappingRequirement::DoNotMap,
+        );
+        let memory = DeviceMemory::dedicated_alloc_with_exportable(
+            device.clone(),
+            mem_ty,
+            mem_reqs.size,
+            DedicatedAlloc::Image(&image),
+            handle_types,
+        )?;
+        unsafe {
+            image.bind_memory(&memory, 0)?;
+        }
+
+        Ok(Arc::new(StorageImage {
+            image,
+            memory: PotentialDedicatedAllocation::Dedicated(memory),
+            dimensions,
+            format,
+            queue_families,
+            gpu_lock: AtomicUsize::new(0),
+        }))
+    }
+}
+
+impl<F, A> StorageImage<F, A>
+where
+    A: MemoryAllocator,
+{
+    /// Same as `with_usage`, but allocates the memory of the image from `allocator`.
+    pub fn with_allocator<'a, I>(
+        device: Arc<Device>,
+        dimensions: ImageDimensions,
+        format: F,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+        queue_families: I,
+        allocator: &A,
+    ) -> Result<Arc<StorageImage<F, A>>, ImageCreationError>
     where
         F: FormatDesc,
         I: IntoIterator<Item = QueueFamily<'a>>,
@@ -143,19 +263,12 @@ impl<F> StorageImage<F> {
             )?
         };
 
-        let memory = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
+        let memory = MemoryAllocator::alloc_for_usage(
+            allocator,
             &mem_reqs,
             AllocLayout::Optimal,
-            MappingRequirement::DoNotMap,
+            MemoryUsage::DeviceOnly,
             DedicatedAlloc::Image(&image),
-            |t| {
-                if t.is_device_local() {
-                    AllocFromRequirementsFilter::Preferred
-                } else {
-                    AllocFromRequirementsFilter::Allowed
-                }
-            },
         )?;
         debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
         unsafe {
@@ -182,6 +295,28 @@ where
     pub fn dimensions(&self) -> ImageDimensions {
         self.dimensions
     }
+
+    /// Exports a file descriptor for the memory of the image. The image must have been created
+    /// with `with_exportable` and the same handle type. Only works on Linux.
+    #[inline]
+    #[cfg(target_os = "linux")]
+    pub fn export_fd(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+    ) -> Result<File, DeviceMemoryAllocError> {
+        self.memory.memory().export_fd(handle_type)
+    }
+
+    /// Exports a Windows handle for the memory of the image. The image must have been created
+    /// with `with_exportable` and the same handle type. Only works on Windows.
+    #[inline]
+    #[cfg(windows)]
+    pub fn export_win32_handle(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+    ) -> Result<*mut c_void, DeviceMemoryAllocError> {
+        self.memory.memory().export_win32_handle(handle_type)
+    }
 }
 
 unsafe impl<F, A> ImageAccess for StorageImage<F, A>
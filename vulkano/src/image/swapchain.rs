@@ -18,6 +18,9 @@ use crate::format::FormatDesc;
 use crate::image::traits::ImageAccess;
 use crate::image::traits::ImageClearValue;
 use crate::image::traits::ImageContent;
+use crate::image::view::ImageView;
+use crate::image::view::ImageViewCreationError;
+use crate::image::view::ImageViewType;
 use crate::image::ImageDescriptorLayouts;
 use crate::image::ImageInner;
 use crate::image::ImageLayout;
@@ -26,6 +29,9 @@ use crate::sync::AccessError;
 
 use crate::OomError;
 
+// A view of one array layer of a swapchain image.
+type LayerView<W> = Arc<ImageView<Arc<SwapchainImage<W>>>>;
+
 /// An image that is part of a swapchain.
 ///
 /// Creating a `SwapchainImage` is automatically done when creating a swapchain.
@@ -70,12 +76,43 @@ impl<W> SwapchainImage<W> {
         [dims.width(), dims.height()]
     }
 
+    /// Returns the number of array layers of the image.
+    ///
+    /// This is the number of layers that was passed when creating the swapchain, for example 2
+    /// for stereo presentation.
+    #[inline]
+    pub fn layers(&self) -> u32 {
+        self.my_image().image.dimensions().array_layers()
+    }
+
     /// Returns the swapchain this image belongs to.
     #[inline]
     pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
         &self.swapchain
     }
 
+    /// Creates a two-dimensional view of a single array layer of the image.
+    ///
+    /// This is useful with swapchains that have multiple layers, for example to render each eye
+    /// of a stereo swapchain separately. Use `ImageView::new` to create a view of all the layers.
+    #[inline]
+    pub fn layer_view(
+        image: &Arc<SwapchainImage<W>>,
+        layer: u32,
+    ) -> Result<LayerView<W>, ImageViewCreationError> {
+        ImageView::with_type_ranges(image.clone(), ImageViewType::Dim2d, 0..1, layer..layer + 1)
+    }
+
+    /// Creates a two-dimensional view of each array layer of the image, in order.
+    #[inline]
+    pub fn layer_views(
+        image: &Arc<SwapchainImage<W>>,
+    ) -> Result<Vec<LayerView<W>>, ImageViewCreationError> {
+        (0..image.layers())
+            .map(|layer| SwapchainImage::layer_view(image, layer))
+            .collect()
+    }
+
     #[inline]
     fn my_image(&self) -> ImageInner {
         self.swapchain.raw_image(self.image_offset).unwrap()
@@ -112,7 +149,7 @@ unsafe impl<W> ImageAccess for SwapchainImage<W> {
     #[inline]
     fn descriptor_layouts(&self) -> Option<ImageDescriptorLayouts> {
         Some(ImageDescriptorLayouts {
-            storage_image: ImageLayout::ShaderReadOnlyOptimal,
+            storage_image: ImageLayout::General,
             combined_image_sampler: ImageLayout::ShaderReadOnlyOptimal,
             sampled_image: ImageLayout::ShaderReadOnlyOptimal,
             input_attachment: ImageLayout::ShaderReadOnlyOptimal,
@@ -169,7 +206,7 @@ unsafe impl<W> ImageAccess for SwapchainImage<W> {
 
     #[inline]
     fn current_layer_levels_access(&self) -> std::ops::Range<u32> {
-        0..1
+        0..self.layers()
     }
 }
 
@@ -202,3 +239,86 @@ impl<W> Hash for SwapchainImage<W> {
         ImageAccess::inner(self).hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::device::Device;
+    use crate::device::DeviceExtensions;
+    use crate::features::Features;
+    use crate::image::ImageUsage;
+    use crate::image::SwapchainImage;
+    use crate::instance::Instance;
+    use crate::instance::InstanceExtensions;
+    use crate::instance::PhysicalDevice;
+    use crate::swapchain::FullscreenExclusive;
+    use crate::swapchain::PresentMode;
+    use crate::swapchain::Surface;
+    use crate::swapchain::Swapchain;
+    use crate::swapchain::SwapchainOptions;
+    use crate::sync::SharingMode;
+
+    #[test]
+    fn layer_views() {
+        let extensions = InstanceExtensions {
+            khr_surface: true,
+            ext_headless_surface: true,
+            ..InstanceExtensions::none()
+        };
+        let instance = match Instance::new(None, &extensions, None) {
+            Ok(instance) => instance,
+            Err(_) => return,
+        };
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(physical) => physical,
+            None => return,
+        };
+        let queue_family = match physical.queue_families().find(|q| q.supports_graphics()) {
+            Some(queue_family) => queue_family,
+            None => return,
+        };
+        let device_extensions = DeviceExtensions {
+            khr_swapchain: true,
+            ..DeviceExtensions::none()
+        };
+        let (device, _) = match Device::new(
+            physical,
+            &Features::none(),
+            &device_extensions,
+            [(queue_family, 0.5)].iter().cloned(),
+        ) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+
+        let surface = Surface::headless(instance.clone(), ()).unwrap();
+        let caps = surface.capabilities(physical).unwrap();
+        if caps.max_image_array_layers < 2 {
+            return;
+        }
+        let (format, color_space) = caps.supported_formats[0];
+
+        let (_, images) = Swapchain::new(
+            device,
+            surface,
+            caps.min_image_count,
+            format,
+            caps.current_extent.unwrap_or(caps.min_image_extent),
+            2,
+            ImageUsage::color_attachment(),
+            SharingMode::Exclusive,
+            caps.current_transform,
+            caps.supported_composite_alpha.iter().next().unwrap(),
+            PresentMode::Fifo,
+            FullscreenExclusive::Default,
+            None,
+            true,
+            color_space,
+            SwapchainOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(images[0].layers(), 2);
+        assert_eq!(SwapchainImage::layer_views(&images[0]).unwrap().len(), 2);
+        assert!(SwapchainImage::layer_view(&images[0], 2).is_err());
+    }
+}
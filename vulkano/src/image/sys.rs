@@ -36,6 +36,7 @@ use crate::image::ImageUsage;
 use crate::image::MipmapsCount;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocError;
+use crate::memory::ExternalMemoryHandleType;
 use crate::memory::MemoryRequirements;
 use crate::sync::Sharing;
 
@@ -119,6 +120,57 @@ impl UnsafeImage {
             sharing,
             linear_tiling,
             preinitialized_layout,
+            ExternalMemoryHandleType::none(),
+        )
+    }
+
+    /// Same as `new`, but the memory bound to the image can be exported with or imported from
+    /// the given handle types.
+    ///
+    /// The image has one sample and one mipmap level, an optimal tiling and an undefined initial
+    /// layout.
+    ///
+    /// # Safety
+    ///
+    /// - The memory bound to the image must be allocated with the same handle types in its export
+    ///   or import infos.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_types` isn't empty and the `khr_external_memory` extension isn't
+    ///   loaded.
+    /// - Same panics as `new`.
+    ///
+    #[inline]
+    pub unsafe fn new_with_external_memory<I>(
+        device: Arc<Device>,
+        usage: ImageUsage,
+        format: Format,
+        flags: ImageCreateFlags,
+        dimensions: ImageDimensions,
+        sharing: Sharing<I>,
+        handle_types: ExternalMemoryHandleType,
+    ) -> Result<(UnsafeImage, MemoryRequirements), ImageCreationError>
+    where
+        I: Iterator<Item = u32>,
+    {
+        let sharing = match sharing {
+            Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
+            Sharing::Concurrent(ids) => (vk::SHARING_MODE_CONCURRENT, ids.collect()),
+        };
+
+        UnsafeImage::new_impl(
+            device,
+            usage,
+            format,
+            flags,
+            dimensions,
+            1,
+            MipmapsCount::One,
+            sharing,
+            false,
+            false,
+            handle_types,
         )
     }
 
@@ -134,6 +186,7 @@ impl UnsafeImage {
         (sh_mode, sh_indices): (vk::SharingMode, SmallVec<[u32; 8]>),
         linear_tiling: bool,
         preinitialized_layout: bool,
+        handle_types: ExternalMemoryHandleType,
     ) -> Result<(UnsafeImage, MemoryRequirements), ImageCreationError> {
         // TODO: doesn't check that the proper features are enabled
 
@@ -179,7 +232,7 @@ impl UnsafeImage {
             {
                 return Err(ImageCreationError::UnsupportedUsage);
             }
-            if device.loaded_extensions().khr_maintenance1 {
+            if device.loaded_extensions_or_core().khr_maintenance1 {
                 if usage.transfer_source && !features.transfer_src {
                     return Err(ImageCreationError::UnsupportedUsage);
                 }
@@ -368,6 +421,10 @@ impl UnsafeImage {
             }
         }
 
+        if flags.protected && !device.enabled_features().protected_memory {
+            return Err(ImageCreationError::ProtectedMemoryFeatureNotEnabled);
+        }
+
         // Decoding the dimensions.
         let (ty, extent, array_layers) = match dimensions {
             ImageDimensions::Dim1d {
@@ -429,6 +486,15 @@ impl UnsafeImage {
             }
         }
 
+        if flags.multisampled_render_to_single_sampled
+            && !(device
+                .enabled_features()
+                .multisampled_render_to_single_sampled
+                && num_samples == 1)
+        {
+            return Err(ImageCreationError::CreationFlagRequirementsNotMet);
+        }
+
         // Checking the dimensions against the limits.
         if array_layers > device.physical_device().limits().max_image_array_layers() {
             let err = ImageCreationError::UnsupportedDimensions { dimensions };
@@ -512,9 +578,29 @@ impl UnsafeImage {
 
         // Everything now ok. Creating the image.
         let image = {
+            let handle_bits = handle_types.to_bits();
+            let external_info = if handle_bits != 0 {
+                assert!(
+                    device.loaded_extensions_or_core().khr_external_memory,
+                    "the `khr_external_memory` extension must be loaded to create an image with \
+                     external memory"
+                );
+
+                Some(vk::ExternalMemoryImageCreateInfo {
+                    sType: vk::STRUCTURE_TYPE_EXTERNAL_MEMORY_IMAGE_CREATE_INFO,
+                    pNext: ptr::null(),
+                    handleTypes: handle_bits,
+                })
+            } else {
+                None
+            };
+
             let infos = vk::ImageCreateInfo {
                 sType: vk::STRUCTURE_TYPE_IMAGE_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: external_info
+                    .as_ref()
+                    .map(|info| info as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
                 flags: flags.into(),
                 imageType: ty,
                 format: format as u32,
@@ -542,20 +628,23 @@ impl UnsafeImage {
             check_errors(vk.CreateImage(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
         };
 
-        let mem_reqs = if device.loaded_extensions().khr_get_memory_requirements2 {
+        let mem_reqs = if device
+            .loaded_extensions_or_core()
+            .khr_get_memory_requirements2
+        {
             let infos = vk::ImageMemoryRequirementsInfo2KHR {
                 sType: vk::STRUCTURE_TYPE_IMAGE_MEMORY_REQUIREMENTS_INFO_2_KHR,
                 pNext: ptr::null_mut(),
                 image,
             };
 
-            let mut output2 = if device.loaded_extensions().khr_dedicated_allocation {
+            let mut output2 = if device.loaded_extensions_or_core().khr_dedicated_allocation {
                 Some(vk::MemoryDedicatedRequirementsKHR {
                     sType: vk::STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR,
                     pNext: ptr::null(),
@@ -580,8 +669,8 @@ impl UnsafeImage {
 
             let mut out = MemoryRequirements::from_vulkan_reqs(output.memoryRequirements);
             if let Some(output2) = output2 {
-                debug_assert_eq!(output2.requiresDedicatedAllocation, 0);
                 out.prefer_dedicated = output2.prefersDedicatedAllocation != 0;
+                out.requires_dedicated = output2.requiresDedicatedAllocation != 0;
             }
             out
         } else {
@@ -880,7 +969,11 @@ impl Drop for UnsafeImage {
 
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyImage(self.device.internal_object(), self.image, ptr::null());
+            vk.DestroyImage(
+                self.device.internal_object(),
+                self.image,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -924,6 +1017,8 @@ pub enum ImageCreationError {
     UnsupportedUsage,
     /// The `shader_storage_image_multisample` feature must be enabled to create such an image.
     ShaderStorageImageMultisampleFeatureNotEnabled,
+    /// The `protected_memory` feature must be enabled to create a protected image.
+    ProtectedMemoryFeatureNotEnabled,
 }
 
 impl error::Error for ImageCreationError {
@@ -967,6 +1062,9 @@ impl fmt::Display for ImageCreationError {
                     "the `shader_storage_image_multisample` feature must be enabled to create such \
                  an image"
                 }
+                ImageCreationError::ProtectedMemoryFeatureNotEnabled => {
+                    "the `protected_memory` feature must be enabled to create a protected image"
+                }
             }
         )
     }
@@ -1380,4 +1478,41 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn protected_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        let res = unsafe {
+            UnsafeImage::new(
+                device,
+                usage,
+                Format::R8G8B8A8Unorm,
+                ImageCreateFlags {
+                    protected: true,
+                    ..ImageCreateFlags::none()
+                },
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                1,
+                1,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                false,
+            )
+        };
+
+        match res {
+            Err(ImageCreationError::ProtectedMemoryFeatureNotEnabled) => (),
+            _ => panic!(),
+        };
+    }
 }
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::ffi::CStr;
 use std::hash::Hash;
 use std::hash::Hasher;
 
@@ -26,13 +27,34 @@ use crate::image::ImageDimensions;
 use crate::image::ImageLayout;
 use crate::sync::AccessError;
 
+use crate::OomError;
 use crate::SafeDeref;
+use crate::VulkanHandle;
+use crate::VulkanObject;
 
 /// Trait for types that represent the way a GPU can access an image.
 pub unsafe trait ImageAccess {
     /// Returns the inner unsafe image object used by this image.
     fn inner(&self) -> ImageInner;
 
+    /// Assigns a human-readable name to the image for debugging purposes.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_debug_utils` extension isn't loaded on the instance.
+    ///
+    #[inline]
+    fn set_debug_name(&self, name: &CStr) -> Result<(), OomError> {
+        let image = self.inner().image;
+        unsafe {
+            image.device().set_object_name_raw(
+                UnsafeImage::TYPE,
+                image.internal_object().value(),
+                name,
+            )
+        }
+    }
+
     /// Returns the format of this image.
     #[inline]
     fn format(&self) -> Format {
@@ -47,6 +47,14 @@ pub struct ImageUsage {
     /// Can be used as an input attachment. In other words, you can draw to it in a subpass then
     /// read from it in a following pass.
     pub input_attachment: bool,
+
+    /// Can be used as a fragment shading rate attachment, with the `VK_KHR_fragment_shading_rate`
+    /// extension.
+    pub fragment_shading_rate_attachment: bool,
+
+    /// Can be used as a fragment density map attachment, with the `VK_EXT_fragment_density_map`
+    /// extension.
+    pub fragment_density_map: bool,
 }
 
 impl ImageUsage {
@@ -63,6 +71,8 @@ impl ImageUsage {
             depth_stencil_attachment: true,
             transient_attachment: true,
             input_attachment: true,
+            fragment_shading_rate_attachment: true,
+            fragment_density_map: true,
         }
     }
 
@@ -90,6 +100,8 @@ impl ImageUsage {
             depth_stencil_attachment: false,
             transient_attachment: false,
             input_attachment: false,
+            fragment_shading_rate_attachment: false,
+            fragment_density_map: false,
         }
     }
 
@@ -105,6 +117,8 @@ impl ImageUsage {
             depth_stencil_attachment: false,
             transient_attachment: false,
             input_attachment: false,
+            fragment_shading_rate_attachment: false,
+            fragment_density_map: false,
         }
     }
 
@@ -120,6 +134,8 @@ impl ImageUsage {
             depth_stencil_attachment: true,
             transient_attachment: false,
             input_attachment: false,
+            fragment_shading_rate_attachment: false,
+            fragment_density_map: false,
         }
     }
 
@@ -135,6 +151,8 @@ impl ImageUsage {
             depth_stencil_attachment: false,
             transient_attachment: true,
             input_attachment: false,
+            fragment_shading_rate_attachment: false,
+            fragment_density_map: false,
         }
     }
 
@@ -150,6 +168,8 @@ impl ImageUsage {
             depth_stencil_attachment: true,
             transient_attachment: true,
             input_attachment: false,
+            fragment_shading_rate_attachment: false,
+            fragment_density_map: false,
         }
     }
 
@@ -180,6 +200,12 @@ impl ImageUsage {
         if self.input_attachment {
             result |= vk::IMAGE_USAGE_INPUT_ATTACHMENT_BIT;
         }
+        if self.fragment_shading_rate_attachment {
+            result |= vk::IMAGE_USAGE_FRAGMENT_SHADING_RATE_ATTACHMENT_BIT_KHR;
+        }
+        if self.fragment_density_map {
+            result |= vk::IMAGE_USAGE_FRAGMENT_DENSITY_MAP_BIT_EXT;
+        }
         result
     }
 
@@ -194,6 +220,10 @@ impl ImageUsage {
             depth_stencil_attachment: (val & vk::IMAGE_USAGE_DEPTH_STENCIL_ATTACHMENT_BIT) != 0,
             transient_attachment: (val & vk::IMAGE_USAGE_TRANSIENT_ATTACHMENT_BIT) != 0,
             input_attachment: (val & vk::IMAGE_USAGE_INPUT_ATTACHMENT_BIT) != 0,
+            fragment_shading_rate_attachment: (val
+                & vk::IMAGE_USAGE_FRAGMENT_SHADING_RATE_ATTACHMENT_BIT_KHR)
+                != 0,
+            fragment_density_map: (val & vk::IMAGE_USAGE_FRAGMENT_DENSITY_MAP_BIT_EXT) != 0,
         }
     }
 }
@@ -212,6 +242,9 @@ impl BitOr for ImageUsage {
             depth_stencil_attachment: self.depth_stencil_attachment || rhs.depth_stencil_attachment,
             transient_attachment: self.transient_attachment || rhs.transient_attachment,
             input_attachment: self.input_attachment || rhs.input_attachment,
+            fragment_shading_rate_attachment: self.fragment_shading_rate_attachment
+                || rhs.fragment_shading_rate_attachment,
+            fragment_density_map: self.fragment_density_map || rhs.fragment_density_map,
         }
     }
 }
@@ -295,7 +295,7 @@ impl UnsafeImageView {
             check_errors(vk.CreateImageView(
                 image.device().internal_object(),
                 &infos,
-                ptr::null(),
+                image.device().alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -331,7 +331,11 @@ impl Drop for UnsafeImageView {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyImageView(self.device.internal_object(), self.view, ptr::null());
+            vk.DestroyImageView(
+                self.device.internal_object(),
+                self.view,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
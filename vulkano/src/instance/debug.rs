@@ -36,7 +36,21 @@
 //! be callable. If you don't store the return value of `DebugCallback`'s constructor in a
 //! variable, it will be immediately destroyed and your callback will not work.
 //!
+//! # Debug printf
+//!
+//! Shaders can print messages with `debugPrintfEXT` (from the `GL_EXT_debug_printf` GLSL
+//! extension) or `printf` (in HLSL). To receive them, enable the validation layers and the
+//! `debug_printf` validation feature when creating the instance with
+//! `Instance::with_validation_features`, enable the `khr_shader_non_semantic_info` device
+//! extension, and register a callback with `DebugCallback::debug_printf`.
+//!
+//! The messages are tagged with the pipeline, stage and entry point that printed them, if the
+//! validation layers report them. This requires enabling the `printf_verbose` setting of the
+//! validation layers, for example with the `VK_LAYER_PRINTF_VERBOSE=1` environment variable.
+//!
 
+use smallvec::SmallVec;
+use std::collections::HashMap;
 use std::error;
 use std::ffi::CStr;
 use std::fmt;
@@ -45,8 +59,10 @@ use std::os::raw::c_void;
 use std::panic;
 use std::ptr;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use crate::instance::Instance;
+use crate::pipeline::reflect::ShaderStage;
 
 use crate::check_errors;
 use crate::vk;
@@ -181,7 +197,7 @@ impl DebugCallback {
             check_errors(vk.CreateDebugUtilsMessengerEXT(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -214,6 +230,56 @@ impl DebugCallback {
     }
 }
 
+impl DebugCallback {
+    /// Initializes a callback that receives the messages printed by shaders.
+    ///
+    /// The `debug_printf` validation feature must have been enabled when creating the instance.
+    /// Messages that don't come from shaders are ignored.
+    pub fn debug_printf<F>(
+        instance: &Arc<Instance>,
+        user_callback: F,
+    ) -> Result<DebugCallback, DebugCallbackCreationError>
+    where
+        F: Fn(&DebugPrintfMessage) + Send + 'static + panic::RefUnwindSafe,
+    {
+        if !instance.enabled_validation_features().debug_printf {
+            return Err(DebugCallbackCreationError::DebugPrintfNotEnabled);
+        }
+
+        let registry = instance.debug_printf_registry().clone();
+
+        DebugCallback::new(
+            instance,
+            MessageSeverity {
+                information: true,
+                warning: true,
+                ..MessageSeverity::none()
+            },
+            MessageType::all(),
+            move |msg| {
+                let is_printf = msg
+                    .layer_prefix
+                    .map(|id| id.contains("DEBUG-PRINTF"))
+                    .unwrap_or(false);
+                if !is_printf {
+                    return;
+                }
+
+                let pipeline = parse_handle(msg.description, "Pipeline");
+                let stage = parse_stage(msg.description);
+                let entry_point = pipeline.and_then(|p| registry.entry_point(p, stage));
+
+                user_callback(&DebugPrintfMessage {
+                    text: msg.description,
+                    pipeline,
+                    stage: stage.or_else(|| entry_point.as_ref().map(|&(stage, _)| stage)),
+                    entry_point: entry_point.as_ref().map(|(_, name)| &name[..]),
+                });
+            },
+        )
+    }
+}
+
 impl Drop for DebugCallback {
     #[inline]
     fn drop(&mut self) {
@@ -222,7 +288,7 @@ impl Drop for DebugCallback {
             vk.DestroyDebugUtilsMessengerEXT(
                 self.instance.internal_object(),
                 self.debug_report_callback,
-                ptr::null(),
+                self.instance.alloc_ptr(),
             );
         }
     }
@@ -240,6 +306,101 @@ pub struct Message<'a> {
     pub description: &'a str,
 }
 
+/// A message printed by a shader, received by a callback created with
+/// `DebugCallback::debug_printf`.
+#[derive(Debug, Copy, Clone)]
+pub struct DebugPrintfMessage<'a> {
+    /// The message reported by the validation layers. If the `printf_verbose` setting of the
+    /// validation layers is enabled, the text printed by the shader is preceded by the location of
+    /// the call.
+    pub text: &'a str,
+    /// The handle of the pipeline whose shader printed the message, if known.
+    pub pipeline: Option<u64>,
+    /// The stage of the shader that printed the message, if known.
+    pub stage: Option<ShaderStage>,
+    /// The name of the entry point of the shader that printed the message, if known.
+    pub entry_point: Option<&'a str>,
+}
+
+// Stages and names of the entry points of a pipeline.
+type PipelineEntryPoints = SmallVec<[(ShaderStage, String); 5]>;
+
+// Entry points of the pipelines of an instance that has the `debug_printf` validation feature,
+// used to tag the messages printed by shaders.
+#[derive(Debug, Default)]
+pub(crate) struct DebugPrintfRegistry {
+    pipelines: Mutex<HashMap<u64, PipelineEntryPoints>>,
+}
+
+impl DebugPrintfRegistry {
+    // Registers the entry points of a new pipeline.
+    pub(crate) fn register<I>(&self, pipeline: u64, entry_points: I)
+    where
+        I: IntoIterator<Item = (ShaderStage, String)>,
+    {
+        self.pipelines
+            .lock()
+            .unwrap()
+            .insert(pipeline, entry_points.into_iter().collect());
+    }
+
+    // Unregisters a pipeline that is destroyed.
+    pub(crate) fn unregister(&self, pipeline: u64) {
+        self.pipelines.lock().unwrap().remove(&pipeline);
+    }
+
+    // Returns the entry point of the given stage of a pipeline. If the stage is unknown, only
+    // returns an entry point if the pipeline has a single one.
+    fn entry_point(
+        &self,
+        pipeline: u64,
+        stage: Option<ShaderStage>,
+    ) -> Option<(ShaderStage, String)> {
+        let pipelines = self.pipelines.lock().unwrap();
+        let entry_points = pipelines.get(&pipeline)?;
+        match stage {
+            Some(stage) => entry_points.iter().find(|&&(s, _)| s == stage).cloned(),
+            None if entry_points.len() == 1 => Some(entry_points[0].clone()),
+            None => None,
+        }
+    }
+}
+
+// Parses the hexadecimal handle that follows `object` in a message of the validation layers,
+// such as `Pipeline (0x1234)` or `VkPipeline 0x1234[name]`.
+fn parse_handle(text: &str, object: &str) -> Option<u64> {
+    text.match_indices(object).find_map(|(index, _)| {
+        let rest = &text[index + object.len()..];
+        let rest = rest.trim_start_matches([' ', '(']);
+        if !rest.starts_with("0x") {
+            return None;
+        }
+        let digits: String = rest[2..]
+            .chars()
+            .take_while(|c| c.is_ascii_hexdigit())
+            .collect();
+        u64::from_str_radix(&digits, 16).ok()
+    })
+}
+
+// Parses the `Stage = ...` part of a message of the validation layers.
+fn parse_stage(text: &str) -> Option<ShaderStage> {
+    let index = text.find("Stage = ")?;
+    let rest = &text[index + "Stage = ".len()..];
+    let stages = [
+        ("Vertex", ShaderStage::Vertex),
+        ("Tessellation Control", ShaderStage::TessellationControl),
+        ("Tessellation Eval", ShaderStage::TessellationEvaluation),
+        ("Geometry", ShaderStage::Geometry),
+        ("Fragment", ShaderStage::Fragment),
+        ("Compute", ShaderStage::Compute),
+    ];
+    stages
+        .iter()
+        .find(|&&(name, _)| rest.starts_with(name))
+        .map(|&(_, stage)| stage)
+}
+
 /// Severity of message.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct MessageSeverity {
@@ -334,6 +495,8 @@ impl MessageType {
 pub enum DebugCallbackCreationError {
     /// The `EXT_debug_report` extension was not enabled.
     MissingExtension,
+    /// The `debug_printf` validation feature was not enabled when creating the instance.
+    DebugPrintfNotEnabled,
 }
 
 impl error::Error for DebugCallbackCreationError {}
@@ -348,6 +511,9 @@ impl fmt::Display for DebugCallbackCreationError {
                 DebugCallbackCreationError::MissingExtension => {
                     "the `EXT_debug_report` extension was not enabled"
                 }
+                DebugCallbackCreationError::DebugPrintfNotEnabled => {
+                    "the `debug_printf` validation feature was not enabled"
+                }
             }
         )
     }
@@ -376,4 +542,50 @@ mod tests {
             let _ = callback;
         });
     }
+
+    #[test]
+    fn parse_debug_printf_tags() {
+        let text = "Command buffer (0x55d1). Draw Index 0. Pipeline (0xd1a0). Shader Module \
+                    (0x4b00). Shader Instruction Index = 92.  Stage = Fragment.  Fragment \
+                    coord (x,y) = (0.5, 0.5). value = 3";
+        assert_eq!(parse_handle(text, "Pipeline"), Some(0xd1a0));
+        assert_eq!(parse_handle(text, "Shader Module"), Some(0x4b00));
+        assert_eq!(parse_stage(text), Some(ShaderStage::Fragment));
+
+        assert_eq!(
+            parse_handle("VkPipeline 0x2a[main]", "Pipeline"),
+            Some(0x2a)
+        );
+        assert_eq!(parse_handle("value = 3", "Pipeline"), None);
+        assert_eq!(
+            parse_stage("Stage = Tessellation Eval."),
+            Some(ShaderStage::TessellationEvaluation)
+        );
+    }
+
+    #[test]
+    fn debug_printf_registry() {
+        let registry = DebugPrintfRegistry::default();
+        registry.register(
+            1,
+            vec![
+                (ShaderStage::Vertex, "vs_main".to_owned()),
+                (ShaderStage::Fragment, "fs_main".to_owned()),
+            ],
+        );
+        registry.register(2, vec![(ShaderStage::Compute, "main".to_owned())]);
+
+        assert_eq!(
+            registry.entry_point(1, Some(ShaderStage::Fragment)),
+            Some((ShaderStage::Fragment, "fs_main".to_owned()))
+        );
+        assert_eq!(registry.entry_point(1, None), None);
+        assert_eq!(
+            registry.entry_point(2, None),
+            Some((ShaderStage::Compute, "main".to_owned()))
+        );
+
+        registry.unregister(2);
+        assert_eq!(registry.entry_point(2, None), None);
+    }
 }
@@ -18,6 +18,7 @@ use crate::check_errors;
 use crate::extensions::SupportedExtensionsError;
 use crate::instance::loader;
 use crate::instance::loader::LoadingError;
+use crate::instance::Version;
 use crate::vk;
 
 macro_rules! instance_extensions {
@@ -157,9 +158,34 @@ instance_extensions! {
     mvk_macos_surface => b"VK_MVK_macos_surface",
     mvk_moltenvk => b"VK_MVK_moltenvk",     // TODO: confirm that it's an instance extension
     nn_vi_surface => b"VK_NN_vi_surface",
+    ext_headless_surface => b"VK_EXT_headless_surface",
+    ext_surface_maintenance1 => b"VK_EXT_surface_maintenance1",
+    ext_display_surface_counter => b"VK_EXT_display_surface_counter",
     ext_swapchain_colorspace => b"VK_EXT_swapchain_colorspace",
     khr_get_physical_device_properties2 => b"VK_KHR_get_physical_device_properties2",
+    khr_device_group_creation => b"VK_KHR_device_group_creation",
     khr_get_surface_capabilities2 => b"VK_KHR_get_surface_capabilities2",
+    khr_surface_protected_capabilities => b"VK_KHR_surface_protected_capabilities",
+    ext_validation_features => b"VK_EXT_validation_features",
+    ext_layer_settings => b"VK_EXT_layer_settings",
+    khr_portability_enumeration => b"VK_KHR_portability_enumeration",
+}
+
+impl InstanceExtensions {
+    /// Returns the extensions whose functionality is part of the given version of Vulkan.
+    ///
+    /// An instance that uses this version doesn't need to enable them, and their functions are
+    /// loaded from the core API instead.
+    pub fn core_in(version: Version) -> InstanceExtensions {
+        let mut extensions = InstanceExtensions::none();
+
+        if version >= Version::V1_1 {
+            extensions.khr_device_group_creation = true;
+            extensions.khr_get_physical_device_properties2 = true;
+        }
+
+        extensions
+    }
 }
 
 /// This helper type can only be instantiated inside this module.
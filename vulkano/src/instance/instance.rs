@@ -9,6 +9,7 @@
 
 use smallvec::SmallVec;
 use std::borrow::Cow;
+use std::cmp;
 use std::error;
 use std::ffi::CStr;
 use std::ffi::CString;
@@ -18,19 +19,32 @@ use std::hash::Hasher;
 use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
+use std::os::raw::c_void;
 use std::ptr;
 use std::slice;
 use std::sync::Arc;
 
+use crate::alloc::AllocationCallbacks;
 use crate::check_errors;
+use crate::descriptor::descriptor::ShaderStages;
+use crate::device::extension_structs::zeroed_struct;
+use crate::device::DeviceExtensions;
+use crate::device::ExtensionFeatures;
+use crate::device::ExtensionProperties;
+use crate::framebuffer::ResolveModes;
+use crate::instance::debug::DebugPrintfRegistry;
+use crate::instance::layer_settings::RawLayerSettings;
 use crate::instance::limits::Limits;
 use crate::instance::loader;
 use crate::instance::loader::FunctionPointers;
 use crate::instance::loader::Loader;
 use crate::instance::loader::LoadingError;
+use crate::instance::LayerSetting;
+use crate::instance::ToolProperties;
 use crate::vk;
 use crate::Error;
 use crate::OomError;
+use crate::Success;
 use crate::VulkanObject;
 
 use crate::features::{Features, FeaturesFfi};
@@ -94,11 +108,14 @@ use crate::version::Version;
 // TODO: mention that extensions must be supported by layers as well
 pub struct Instance {
     instance: vk::Instance,
-    //alloc: Option<Box<Alloc + Send + Sync>>,
+    alloc: Option<AllocationCallbacks>,
     physical_devices: Vec<PhysicalDeviceInfos>,
     vk: vk::InstancePointers,
     extensions: RawInstanceExtensions,
     layers: SmallVec<[CString; 16]>,
+    api_version: Version,
+    validation_features: ValidationFeatures,
+    debug_printf_registry: Arc<DebugPrintfRegistry>,
     function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader + Send + Sync>>>,
 }
 
@@ -150,7 +167,123 @@ impl Instance {
             app_infos,
             extensions.into(),
             layers,
+            ValidationFeatures::none(),
+            &[],
             OwnedOrRef::Ref(loader::auto_loader()?),
+            None,
+        )
+    }
+
+    /// Same as `new`, but also enables or disables features of the validation layers.
+    ///
+    /// The `ext_validation_features` extension must be enabled, unless `validation_features`
+    /// is `ValidationFeatures::none()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use vulkano::instance::Instance;
+    /// use vulkano::instance::InstanceExtensions;
+    /// use vulkano::instance::ValidationFeatures;
+    ///
+    /// let extensions = InstanceExtensions {
+    ///     ext_validation_features: true,
+    ///     .. InstanceExtensions::none()
+    /// };
+    ///
+    /// let validation_features = ValidationFeatures {
+    ///     best_practices: true,
+    ///     synchronization_validation: true,
+    ///     .. ValidationFeatures::none()
+    /// };
+    ///
+    /// let instance = Instance::with_validation_features(None, &extensions,
+    ///                                                   Some("VK_LAYER_KHRONOS_validation"),
+    ///                                                   validation_features)
+    ///     .unwrap();
+    /// ```
+    pub fn with_validation_features<'a, L, Ext>(
+        app_infos: Option<&ApplicationInfo>,
+        extensions: Ext,
+        layers: L,
+        validation_features: ValidationFeatures,
+    ) -> Result<Arc<Instance>, InstanceCreationError>
+    where
+        L: IntoIterator<Item = &'a str>,
+        Ext: Into<RawInstanceExtensions>,
+    {
+        let layers = layers
+            .into_iter()
+            .map(|layer| CString::new(layer).unwrap())
+            .collect::<SmallVec<[_; 16]>>();
+
+        Instance::new_inner(
+            app_infos,
+            extensions.into(),
+            layers,
+            validation_features,
+            &[],
+            OwnedOrRef::Ref(loader::auto_loader()?),
+            None,
+        )
+    }
+
+    /// Same as `new`, but also passes settings to the layers.
+    ///
+    /// The `ext_layer_settings` extension must be enabled, unless `layer_settings` is empty. This
+    /// extension is provided by the layers that support it, such as the validation layers.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use vulkano::instance::Instance;
+    /// use vulkano::instance::InstanceExtensions;
+    /// use vulkano::instance::LayerSetting;
+    /// use vulkano::instance::LayerSettingValue;
+    ///
+    /// let extensions = InstanceExtensions {
+    ///     ext_layer_settings: true,
+    ///     .. InstanceExtensions::none()
+    /// };
+    ///
+    /// let layer_settings = [
+    ///     LayerSetting::new("VK_LAYER_KHRONOS_validation", "validate_best_practices",
+    ///                       LayerSettingValue::Bool(vec![true])),
+    /// ];
+    ///
+    /// let instance = Instance::with_layer_settings(None, &extensions,
+    ///                                              Some("VK_LAYER_KHRONOS_validation"),
+    ///                                              &layer_settings)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// - Panics if a layer name, a setting name or a string value contains a null character.
+    ///
+    pub fn with_layer_settings<'a, L, Ext>(
+        app_infos: Option<&ApplicationInfo>,
+        extensions: Ext,
+        layers: L,
+        layer_settings: &[LayerSetting],
+    ) -> Result<Arc<Instance>, InstanceCreationError>
+    where
+        L: IntoIterator<Item = &'a str>,
+        Ext: Into<RawInstanceExtensions>,
+    {
+        let layers = layers
+            .into_iter()
+            .map(|layer| CString::new(layer).unwrap())
+            .collect::<SmallVec<[_; 16]>>();
+
+        Instance::new_inner(
+            app_infos,
+            extensions.into(),
+            layers,
+            ValidationFeatures::none(),
+            layer_settings,
+            OwnedOrRef::Ref(loader::auto_loader()?),
+            None,
         )
     }
 
@@ -174,7 +307,42 @@ impl Instance {
             app_infos,
             extensions.into(),
             layers,
+            ValidationFeatures::none(),
+            &[],
             OwnedOrRef::Owned(loader),
+            None,
+        )
+    }
+
+    /// Same as `new`, but provides an allocator that will be used by the Vulkan library whenever
+    /// it needs to allocate memory on the host.
+    ///
+    /// The allocator is used for the instance and for all the objects that belong to it, and is
+    /// inherited by the devices created from it unless they are given their own with
+    /// `Device::with_alloc`.
+    pub fn with_alloc<'a, L, Ext>(
+        app_infos: Option<&ApplicationInfo>,
+        extensions: Ext,
+        layers: L,
+        alloc: AllocationCallbacks,
+    ) -> Result<Arc<Instance>, InstanceCreationError>
+    where
+        L: IntoIterator<Item = &'a str>,
+        Ext: Into<RawInstanceExtensions>,
+    {
+        let layers = layers
+            .into_iter()
+            .map(|layer| CString::new(layer).unwrap())
+            .collect::<SmallVec<[_; 16]>>();
+
+        Instance::new_inner(
+            app_infos,
+            extensions.into(),
+            layers,
+            ValidationFeatures::none(),
+            &[],
+            OwnedOrRef::Ref(loader::auto_loader()?),
+            Some(alloc),
         )
     }
 
@@ -182,8 +350,32 @@ impl Instance {
         app_infos: Option<&ApplicationInfo>,
         extensions: RawInstanceExtensions,
         layers: SmallVec<[CString; 16]>,
+        validation_features: ValidationFeatures,
+        layer_settings: &[LayerSetting],
         function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader + Send + Sync>>>,
+        alloc: Option<AllocationCallbacks>,
     ) -> Result<Arc<Instance>, InstanceCreationError> {
+        if validation_features != ValidationFeatures::none()
+            && !InstanceExtensions::from(&extensions).ext_validation_features
+        {
+            return Err(InstanceCreationError::ValidationFeaturesExtensionNotEnabled);
+        }
+        if validation_features.debug_printf && validation_features.gpu_assisted {
+            return Err(InstanceCreationError::DebugPrintfAndGpuAssistedEnabled);
+        }
+        if !layer_settings.is_empty() && !InstanceExtensions::from(&extensions).ext_layer_settings {
+            return Err(InstanceCreationError::LayerSettingsExtensionNotEnabled);
+        }
+
+        // A Vulkan 1.0 loader rejects any higher version, and a more recent loader only provides
+        // the functionality of the lower of the two versions.
+        let api_version = cmp::min(
+            app_infos
+                .and_then(|a| a.api_version)
+                .unwrap_or(Version::V1_1),
+            function_pointers.api_version()?,
+        );
+
         // TODO: For now there are still buggy drivers that will segfault if you don't pass any
         //       appinfos. Therefore for now we ensure that it can't be `None`.
         let def = Default::default();
@@ -236,12 +428,7 @@ impl Instance {
                     .engine_version
                     .map(|v| v.into_vulkan_version())
                     .unwrap_or(0),
-                apiVersion: Version {
-                    major: 1,
-                    minor: 1,
-                    patch: 0,
-                }
-                .into_vulkan_version(), // TODO:
+                apiVersion: api_version.into_vulkan_version(),
             })
         } else {
             None
@@ -258,13 +445,45 @@ impl Instance {
             .map(|extension| extension.as_ptr())
             .collect::<SmallVec<[_; 32]>>();
 
+        let enabled_validation_features = validation_features.enabled_to_vulkan();
+        let disabled_validation_features = validation_features.disabled_to_vulkan();
+        let mut validation_features_info = vk::ValidationFeaturesEXT {
+            sType: vk::STRUCTURE_TYPE_VALIDATION_FEATURES_EXT,
+            pNext: ptr::null(),
+            enabledValidationFeatureCount: enabled_validation_features.len() as u32,
+            pEnabledValidationFeatures: enabled_validation_features.as_ptr(),
+            disabledValidationFeatureCount: disabled_validation_features.len() as u32,
+            pDisabledValidationFeatures: disabled_validation_features.as_ptr(),
+        };
+
+        let raw_layer_settings = RawLayerSettings::new(layer_settings);
+        let layer_settings_info = vk::LayerSettingsCreateInfoEXT {
+            sType: vk::STRUCTURE_TYPE_LAYER_SETTINGS_CREATE_INFO_EXT,
+            pNext: ptr::null(),
+            settingCount: raw_layer_settings.as_slice().len() as u32,
+            pSettings: raw_layer_settings.as_slice().as_ptr(),
+        };
+
+        let mut next: *const c_void = ptr::null();
+        if !raw_layer_settings.is_empty() {
+            next = &layer_settings_info as *const _ as *const _;
+        }
+        if !enabled_validation_features.is_empty() || !disabled_validation_features.is_empty() {
+            validation_features_info.pNext = next;
+            next = &validation_features_info as *const _ as *const _;
+        }
+
         // Creating the Vulkan instance.
         let instance = unsafe {
             let mut output = MaybeUninit::uninit();
             let infos = vk::InstanceCreateInfo {
                 sType: vk::STRUCTURE_TYPE_INSTANCE_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0,
+                pNext: next,
+                flags: if InstanceExtensions::from(&extensions).khr_portability_enumeration {
+                    vk::INSTANCE_CREATE_ENUMERATE_PORTABILITY_BIT_KHR
+                } else {
+                    0
+                },
                 pApplicationInfo: if let Some(app) = app_infos.as_ref() {
                     app as *const _
                 } else {
@@ -277,7 +496,11 @@ impl Instance {
             };
 
             let entry_points = function_pointers.entry_points();
-            check_errors(entry_points.CreateInstance(&infos, ptr::null(), output.as_mut_ptr()))?;
+            check_errors(entry_points.CreateInstance(
+                &infos,
+                AllocationCallbacks::as_ptr(alloc.as_ref()),
+                output.as_mut_ptr(),
+            ))?;
             output.assume_init()
         };
 
@@ -303,10 +526,11 @@ impl Instance {
             CString::new(b"VK_KHR_get_physical_device_properties2".to_vec()).unwrap();
 
         // Getting the properties of all physical devices.
-        // If possible, we use VK_KHR_get_physical_device_properties2.
-        let physical_devices = if extensions
-            .iter()
-            .any(|v| *v == vk_khr_get_physical_device_properties2)
+        // If possible, we use VK_KHR_get_physical_device_properties2 or its core equivalent.
+        let physical_devices = if api_version >= Version::V1_1
+            || extensions
+                .iter()
+                .any(|v| *v == vk_khr_get_physical_device_properties2)
         {
             Instance::init_physical_devices2(&vk, physical_devices, &extensions)
         } else {
@@ -315,11 +539,14 @@ impl Instance {
 
         Ok(Arc::new(Instance {
             instance: instance,
-            //alloc: None,
+            alloc,
             physical_devices: physical_devices,
             vk: vk,
             extensions: extensions,
             layers: layers,
+            api_version,
+            validation_features,
+            debug_printf_registry: Arc::new(DebugPrintfRegistry::default()),
             function_pointers: function_pointers,
         }))
     }
@@ -383,6 +610,9 @@ impl Instance {
 
         for device in physical_devices.into_iter() {
             let mut extended_properties = PhysicalDeviceExtendedProperties::empty();
+            let device_extensions = Instance::device_extension_names(vk, device);
+            let supports_extension =
+                |name: &[u8]| device_extensions.iter().any(|ext| ext.to_bytes() == name);
 
             let properties: vk::PhysicalDeviceProperties = unsafe {
                 let mut subgroup_properties = vk::PhysicalDeviceSubgroupProperties {
@@ -394,6 +624,235 @@ impl Instance {
                     quadOperationsInAllStages: 0,
                 };
 
+                let mut conservative_rasterization_properties =
+                    vk::PhysicalDeviceConservativeRasterizationPropertiesEXT {
+                        sType:
+                            vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_CONSERVATIVE_RASTERIZATION_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        ..mem::zeroed()
+                    };
+
+                let mut vertex_attribute_divisor_properties =
+                    vk::PhysicalDeviceVertexAttributeDivisorPropertiesEXT {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_VERTEX_ATTRIBUTE_DIVISOR_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        maxVertexAttribDivisor: 0,
+                    };
+
+                let mut fragment_shading_rate_properties =
+                    vk::PhysicalDeviceFragmentShadingRatePropertiesKHR {
+                        sType:
+                            vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_FRAGMENT_SHADING_RATE_PROPERTIES_KHR,
+                        pNext: ptr::null_mut(),
+                        ..mem::zeroed()
+                    };
+
+                let mut line_rasterization_properties =
+                    vk::PhysicalDeviceLineRasterizationPropertiesEXT {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_LINE_RASTERIZATION_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        lineSubPixelPrecisionBits: 0,
+                    };
+
+                let mut provoking_vertex_properties =
+                    vk::PhysicalDeviceProvokingVertexPropertiesEXT {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROVOKING_VERTEX_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        provokingVertexModePerPipeline: 0,
+                        transformFeedbackPreservesTriangleFanProvokingVertex: 0,
+                    };
+
+                let mut blend_operation_advanced_properties =
+                    vk::PhysicalDeviceBlendOperationAdvancedPropertiesEXT {
+                        sType:
+                            vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_BLEND_OPERATION_ADVANCED_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        advancedBlendMaxColorAttachments: 0,
+                        advancedBlendIndependentBlend: 0,
+                        advancedBlendNonPremultipliedSrcColor: 0,
+                        advancedBlendNonPremultipliedDstColor: 0,
+                        advancedBlendCorrelatedOverlap: 0,
+                        advancedBlendAllOperations: 0,
+                    };
+
+                let mut sample_locations_properties =
+                    vk::PhysicalDeviceSampleLocationsPropertiesEXT {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SAMPLE_LOCATIONS_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        sampleLocationSampleCounts: 0,
+                        maxSampleLocationGridSize: vk::Extent2D {
+                            width: 0,
+                            height: 0,
+                        },
+                        sampleLocationCoordinateRange: [0.0, 0.0],
+                        sampleLocationSubPixelBits: 0,
+                        variableSampleLocations: 0,
+                    };
+
+                let mut shader_module_identifier_properties =
+                    vk::PhysicalDeviceShaderModuleIdentifierPropertiesEXT {
+                        sType:
+                            vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_MODULE_IDENTIFIER_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        shaderModuleIdentifierAlgorithmUUID: [0; vk::UUID_SIZE as usize],
+                    };
+
+                let mut multiview_properties = vk::PhysicalDeviceMultiviewProperties {
+                    sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_PROPERTIES,
+                    pNext: ptr::null_mut(),
+                    maxMultiviewViewCount: 0,
+                    maxMultiviewInstanceIndex: 0,
+                };
+
+                let mut depth_stencil_resolve_properties =
+                    vk::PhysicalDeviceDepthStencilResolveProperties {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DEPTH_STENCIL_RESOLVE_PROPERTIES,
+                        pNext: ptr::null_mut(),
+                        supportedDepthResolveModes: 0,
+                        supportedStencilResolveModes: 0,
+                        independentResolveNone: 0,
+                        independentResolve: 0,
+                    };
+
+                let mut external_memory_host_properties =
+                    vk::PhysicalDeviceExternalMemoryHostPropertiesEXT {
+                        sType:
+                            vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTERNAL_MEMORY_HOST_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        minImportedHostPointerAlignment: 0,
+                    };
+
+                let mut portability_subset_properties =
+                    vk::PhysicalDevicePortabilitySubsetPropertiesKHR {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PORTABILITY_SUBSET_PROPERTIES_KHR,
+                        pNext: ptr::null_mut(),
+                        minVertexInputBindingStrideAlignment: 0,
+                    };
+
+                let mut protected_memory_properties = vk::PhysicalDeviceProtectedMemoryProperties {
+                    sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_PROPERTIES,
+                    pNext: ptr::null_mut(),
+                    protectedNoFault: 0,
+                };
+
+                let mut driver_properties = vk::PhysicalDeviceDriverProperties {
+                    sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DRIVER_PROPERTIES,
+                    pNext: ptr::null_mut(),
+                    ..mem::zeroed()
+                };
+
+                let mut fragment_density_map_properties =
+                    vk::PhysicalDeviceFragmentDensityMapPropertiesEXT {
+                        sType:
+                            vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_FRAGMENT_DENSITY_MAP_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        minFragmentDensityTexelSize: vk::Extent2D {
+                            width: 0,
+                            height: 0,
+                        },
+                        maxFragmentDensityTexelSize: vk::Extent2D {
+                            width: 0,
+                            height: 0,
+                        },
+                        fragmentDensityInvocations: 0,
+                    };
+
+                // Only chain the structs of extensions that the device supports.
+                let mut next: *mut c_void = ptr::null_mut();
+
+                let conservative_rasterization =
+                    supports_extension(b"VK_EXT_conservative_rasterization");
+                if conservative_rasterization {
+                    conservative_rasterization_properties.pNext = next;
+                    next = &mut conservative_rasterization_properties as *mut _ as *mut _;
+                }
+
+                let vertex_attribute_divisor =
+                    supports_extension(b"VK_EXT_vertex_attribute_divisor");
+                if vertex_attribute_divisor {
+                    vertex_attribute_divisor_properties.pNext = next;
+                    next = &mut vertex_attribute_divisor_properties as *mut _ as *mut _;
+                }
+
+                let fragment_shading_rate = supports_extension(b"VK_KHR_fragment_shading_rate");
+                if fragment_shading_rate {
+                    fragment_shading_rate_properties.pNext = next;
+                    next = &mut fragment_shading_rate_properties as *mut _ as *mut _;
+                }
+
+                let line_rasterization = supports_extension(b"VK_EXT_line_rasterization");
+                if line_rasterization {
+                    line_rasterization_properties.pNext = next;
+                    next = &mut line_rasterization_properties as *mut _ as *mut _;
+                }
+
+                let provoking_vertex = supports_extension(b"VK_EXT_provoking_vertex");
+                if provoking_vertex {
+                    provoking_vertex_properties.pNext = next;
+                    next = &mut provoking_vertex_properties as *mut _ as *mut _;
+                }
+
+                let blend_operation_advanced =
+                    supports_extension(b"VK_EXT_blend_operation_advanced");
+                if blend_operation_advanced {
+                    blend_operation_advanced_properties.pNext = next;
+                    next = &mut blend_operation_advanced_properties as *mut _ as *mut _;
+                }
+
+                let sample_locations = supports_extension(b"VK_EXT_sample_locations");
+                if sample_locations {
+                    sample_locations_properties.pNext = next;
+                    next = &mut sample_locations_properties as *mut _ as *mut _;
+                }
+
+                let shader_module_identifier =
+                    supports_extension(b"VK_EXT_shader_module_identifier");
+                if shader_module_identifier {
+                    shader_module_identifier_properties.pNext = next;
+                    next = &mut shader_module_identifier_properties as *mut _ as *mut _;
+                }
+
+                let multiview = supports_extension(b"VK_KHR_multiview");
+                if multiview {
+                    multiview_properties.pNext = next;
+                    next = &mut multiview_properties as *mut _ as *mut _;
+                }
+
+                let depth_stencil_resolve = supports_extension(b"VK_KHR_depth_stencil_resolve");
+                if depth_stencil_resolve {
+                    depth_stencil_resolve_properties.pNext = next;
+                    next = &mut depth_stencil_resolve_properties as *mut _ as *mut _;
+                }
+
+                let fragment_density_map = supports_extension(b"VK_EXT_fragment_density_map");
+                if fragment_density_map {
+                    fragment_density_map_properties.pNext = next;
+                    next = &mut fragment_density_map_properties as *mut _ as *mut _;
+                }
+
+                let external_memory_host = supports_extension(b"VK_EXT_external_memory_host");
+                if external_memory_host {
+                    external_memory_host_properties.pNext = next;
+                    next = &mut external_memory_host_properties as *mut _ as *mut _;
+                }
+
+                let portability_subset = supports_extension(b"VK_KHR_portability_subset");
+                if portability_subset {
+                    portability_subset_properties.pNext = next;
+                    next = &mut portability_subset_properties as *mut _ as *mut _;
+                }
+
+                let driver = supports_extension(b"VK_KHR_driver_properties");
+                if driver {
+                    driver_properties.pNext = next;
+                    next = &mut driver_properties as *mut _ as *mut _;
+                }
+
+                protected_memory_properties.pNext = next;
+                next = &mut protected_memory_properties as *mut _ as *mut _;
+
+                subgroup_properties.pNext = next as *const _;
+
                 let mut output = vk::PhysicalDeviceProperties2KHR {
                     sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROPERTIES_2_KHR,
                     pNext: &mut subgroup_properties,
@@ -404,10 +863,259 @@ impl Instance {
 
                 extended_properties = PhysicalDeviceExtendedProperties {
                     subgroup_size: Some(subgroup_properties.subgroupSize),
+                    subgroup_supported_stages: Some(ShaderStages::from_vulkan_bits(
+                        subgroup_properties.supportedStages,
+                    )),
+                    subgroup_supported_operations: Some(SubgroupFeatures::from_vulkan_bits(
+                        subgroup_properties.supportedOperations,
+                    )),
+                    subgroup_quad_operations_in_all_stages: Some(
+                        subgroup_properties.quadOperationsInAllStages != vk::FALSE,
+                    ),
+                    protected_no_fault: Some(
+                        protected_memory_properties.protectedNoFault != vk::FALSE,
+                    ),
 
                     ..extended_properties
                 };
 
+                if conservative_rasterization {
+                    let props = &conservative_rasterization_properties;
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        primitive_overestimation_size: Some(props.primitiveOverestimationSize),
+                        max_extra_primitive_overestimation_size: Some(
+                            props.maxExtraPrimitiveOverestimationSize,
+                        ),
+                        extra_primitive_overestimation_size_granularity: Some(
+                            props.extraPrimitiveOverestimationSizeGranularity,
+                        ),
+                        primitive_underestimation: Some(
+                            props.primitiveUnderestimation != vk::FALSE,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if vertex_attribute_divisor {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        max_vertex_attrib_divisor: Some(
+                            vertex_attribute_divisor_properties.maxVertexAttribDivisor,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if fragment_shading_rate {
+                    let props = &fragment_shading_rate_properties;
+                    let min_texel_size = &props.minFragmentShadingRateAttachmentTexelSize;
+                    let max_texel_size = &props.maxFragmentShadingRateAttachmentTexelSize;
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        min_fragment_shading_rate_attachment_texel_size: Some([
+                            min_texel_size.width,
+                            min_texel_size.height,
+                        ]),
+                        max_fragment_shading_rate_attachment_texel_size: Some([
+                            max_texel_size.width,
+                            max_texel_size.height,
+                        ]),
+                        max_fragment_size: Some([
+                            props.maxFragmentSize.width,
+                            props.maxFragmentSize.height,
+                        ]),
+                        fragment_shading_rate_non_trivial_combiner_ops: Some(
+                            props.fragmentShadingRateNonTrivialCombinerOps != vk::FALSE,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if line_rasterization {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        line_sub_pixel_precision_bits: Some(
+                            line_rasterization_properties.lineSubPixelPrecisionBits,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if provoking_vertex {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        provoking_vertex_mode_per_pipeline: Some(
+                            provoking_vertex_properties.provokingVertexModePerPipeline != 0,
+                        ),
+                        transform_feedback_preserves_triangle_fan_provoking_vertex: Some(
+                            provoking_vertex_properties
+                                .transformFeedbackPreservesTriangleFanProvokingVertex
+                                != 0,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if blend_operation_advanced {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        advanced_blend_max_color_attachments: Some(
+                            blend_operation_advanced_properties.advancedBlendMaxColorAttachments,
+                        ),
+                        advanced_blend_independent_blend: Some(
+                            blend_operation_advanced_properties.advancedBlendIndependentBlend != 0,
+                        ),
+                        advanced_blend_non_premultiplied_src_color: Some(
+                            blend_operation_advanced_properties
+                                .advancedBlendNonPremultipliedSrcColor
+                                != 0,
+                        ),
+                        advanced_blend_non_premultiplied_dst_color: Some(
+                            blend_operation_advanced_properties
+                                .advancedBlendNonPremultipliedDstColor
+                                != 0,
+                        ),
+                        advanced_blend_correlated_overlap: Some(
+                            blend_operation_advanced_properties.advancedBlendCorrelatedOverlap != 0,
+                        ),
+                        advanced_blend_all_operations: Some(
+                            blend_operation_advanced_properties.advancedBlendAllOperations != 0,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if sample_locations {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        sample_location_sample_counts: Some(
+                            sample_locations_properties.sampleLocationSampleCounts,
+                        ),
+                        max_sample_location_grid_size: Some([
+                            sample_locations_properties.maxSampleLocationGridSize.width,
+                            sample_locations_properties.maxSampleLocationGridSize.height,
+                        ]),
+                        sample_location_coordinate_range: Some(
+                            sample_locations_properties.sampleLocationCoordinateRange,
+                        ),
+                        sample_location_sub_pixel_bits: Some(
+                            sample_locations_properties.sampleLocationSubPixelBits,
+                        ),
+                        variable_sample_locations: Some(
+                            sample_locations_properties.variableSampleLocations != 0,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if shader_module_identifier {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        shader_module_identifier_algorithm_uuid: Some(
+                            shader_module_identifier_properties.shaderModuleIdentifierAlgorithmUUID,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if multiview {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        max_multiview_view_count: Some(multiview_properties.maxMultiviewViewCount),
+                        max_multiview_instance_index: Some(
+                            multiview_properties.maxMultiviewInstanceIndex,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if depth_stencil_resolve {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        supported_depth_resolve_modes: Some(ResolveModes::from_vulkan_bits(
+                            depth_stencil_resolve_properties.supportedDepthResolveModes,
+                        )),
+                        supported_stencil_resolve_modes: Some(ResolveModes::from_vulkan_bits(
+                            depth_stencil_resolve_properties.supportedStencilResolveModes,
+                        )),
+                        independent_resolve_none: Some(
+                            depth_stencil_resolve_properties.independentResolveNone != 0,
+                        ),
+                        independent_resolve: Some(
+                            depth_stencil_resolve_properties.independentResolve != 0,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if fragment_density_map {
+                    let min_texel_size =
+                        fragment_density_map_properties.minFragmentDensityTexelSize;
+                    let max_texel_size =
+                        fragment_density_map_properties.maxFragmentDensityTexelSize;
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        min_fragment_density_texel_size: Some([
+                            min_texel_size.width,
+                            min_texel_size.height,
+                        ]),
+                        max_fragment_density_texel_size: Some([
+                            max_texel_size.width,
+                            max_texel_size.height,
+                        ]),
+                        fragment_density_invocations: Some(
+                            fragment_density_map_properties.fragmentDensityInvocations != 0,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if external_memory_host {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        min_imported_host_pointer_alignment: Some(
+                            external_memory_host_properties.minImportedHostPointerAlignment,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if portability_subset {
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        min_vertex_input_binding_stride_alignment: Some(
+                            portability_subset_properties.minVertexInputBindingStrideAlignment,
+                        ),
+
+                        ..extended_properties
+                    };
+                }
+
+                if driver {
+                    let version = driver_properties.conformanceVersion;
+                    extended_properties = PhysicalDeviceExtendedProperties {
+                        driver_id: Some(DriverId::from_vulkan(driver_properties.driverID)),
+                        driver_name: Some(
+                            CStr::from_ptr(driver_properties.driverName.as_ptr())
+                                .to_string_lossy()
+                                .into_owned(),
+                        ),
+                        driver_info: Some(
+                            CStr::from_ptr(driver_properties.driverInfo.as_ptr())
+                                .to_string_lossy()
+                                .into_owned(),
+                        ),
+                        conformance_version: Some(ConformanceVersion {
+                            major: version.major,
+                            minor: version.minor,
+                            subminor: version.subminor,
+                            patch: version.patch,
+                        }),
+
+                        ..extended_properties
+                    };
+                }
+
                 output.properties
             };
 
@@ -463,13 +1171,55 @@ impl Instance {
         output
     }
 
-    /*/// Same as `new`, but provides an allocator that will be used by the Vulkan library whenever
-    /// it needs to allocate memory on the host.
-    ///
-    /// Note that this allocator can be overridden when you create a `Device`, a `MemoryPool`, etc.
-    pub fn with_alloc(app_infos: Option<&ApplicationInfo>, alloc: Box<Alloc + Send + Sync>) -> Arc<Instance> {
-        unimplemented!()
-    }*/
+    /// Returns the names of the extensions supported by a physical device.
+    fn device_extension_names(
+        vk: &vk::InstancePointers,
+        device: vk::PhysicalDevice,
+    ) -> Vec<CString> {
+        unsafe {
+            let mut num = 0;
+            if check_errors(vk.EnumerateDeviceExtensionProperties(
+                device,
+                ptr::null(),
+                &mut num,
+                ptr::null_mut(),
+            ))
+            .is_err()
+            {
+                return Vec::new();
+            }
+
+            let mut properties: Vec<vk::ExtensionProperties> = Vec::with_capacity(num as usize);
+            if check_errors(vk.EnumerateDeviceExtensionProperties(
+                device,
+                ptr::null(),
+                &mut num,
+                properties.as_mut_ptr(),
+            ))
+            .is_err()
+            {
+                return Vec::new();
+            }
+            properties.set_len(num as usize);
+
+            properties
+                .iter()
+                .map(|p| CStr::from_ptr(p.extensionName.as_ptr()).to_owned())
+                .collect()
+        }
+    }
+
+    /// Returns the host allocation callbacks that were passed when creating the instance, if
+    /// any.
+    #[inline]
+    pub fn alloc(&self) -> Option<&AllocationCallbacks> {
+        self.alloc.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn alloc_ptr(&self) -> *const vk::AllocationCallbacks {
+        AllocationCallbacks::as_ptr(self.alloc.as_ref())
+    }
 
     /// Grants access to the Vulkan functions of the instance.
     #[inline]
@@ -501,44 +1251,164 @@ impl Instance {
         &self.extensions
     }
 
+    /// Returns the version of Vulkan used by the instance.
+    ///
+    /// This is the lower of `ApplicationInfo::api_version` and of the version supported by the
+    /// loader.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
+    /// Returns the extensions that have been loaded, plus the ones whose functionality is part
+    /// of the version of Vulkan used by the instance.
+    ///
+    /// The functions of the latter are loaded from the core API.
+    #[inline]
+    pub fn loaded_extensions_or_core(&self) -> InstanceExtensions {
+        self.loaded_extensions()
+            .union(&InstanceExtensions::core_in(self.api_version))
+    }
+
     /// Returns the list of layers requested when creating this instance.
     #[doc(hidden)]
     #[inline]
     pub fn loaded_layers(&self) -> slice::Iter<CString> {
         self.layers.iter()
     }
-}
 
-impl fmt::Debug for Instance {
+    /// Returns the features of the validation layers that were enabled when creating this
+    /// instance.
     #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "<Vulkan instance {:?}>", self.instance)
+    pub fn enabled_validation_features(&self) -> ValidationFeatures {
+        self.validation_features
     }
-}
-
-unsafe impl VulkanObject for Instance {
-    type Object = vk::Instance;
-
-    const TYPE: vk::ObjectType = vk::OBJECT_TYPE_INSTANCE;
 
+    // Returns the entry points of the pipelines created with this instance, used to tag debug
+    // printf messages.
     #[inline]
-    fn internal_object(&self) -> vk::Instance {
-        self.instance
+    pub(crate) fn debug_printf_registry(&self) -> &Arc<DebugPrintfRegistry> {
+        &self.debug_printf_registry
     }
 }
 
-impl Drop for Instance {
-    #[inline]
-    fn drop(&mut self) {
-        unsafe {
-            self.vk.DestroyInstance(self.instance, ptr::null());
-        }
-    }
+/// Features of the validation layers that can be enabled when creating an instance.
+///
+/// These features are only taken into account if the validation layers are enabled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ValidationFeatures {
+    /// Instruments shaders to detect invalid accesses to resources.
+    pub gpu_assisted: bool,
+    /// Makes GPU-assisted validation reserve a descriptor set binding slot for its own use.
+    pub gpu_assisted_reserve_binding_slot: bool,
+    /// Reports usages of the API that are valid but may be suboptimal.
+    pub best_practices: bool,
+    /// Sends the output of `debugPrintfEXT` calls in shaders to the debug messenger. Can't be
+    /// enabled at the same time as `gpu_assisted`.
+    ///
+    /// Use `DebugCallback::debug_printf` to receive the messages.
+    pub debug_printf: bool,
+    /// Detects hazards caused by missing synchronization.
+    pub synchronization_validation: bool,
+
+    /// Disables the validation of shaders.
+    pub disable_shaders: bool,
+    /// Disables the detection of objects that are used concurrently from multiple threads.
+    pub disable_thread_safety: bool,
+    /// Disables the validation of the parameters of the API calls.
+    pub disable_api_parameters: bool,
+    /// Disables the detection of objects that are used after being destroyed, or that are
+    /// leaked.
+    pub disable_object_lifetimes: bool,
+    /// Disables the core validation checks, such as the checks of the command buffers and of
+    /// the descriptor sets.
+    pub disable_core_checks: bool,
+    /// Disables the wrapping of handles by the validation layers, which makes the non-dispatchable
+    /// handles unique even if the implementation doesn't guarantee it.
+    pub disable_unique_handles: bool,
 }
 
-impl PartialEq for Instance {
+impl ValidationFeatures {
+    /// Builds a `ValidationFeatures` that neither enables nor disables anything.
     #[inline]
-    fn eq(&self, other: &Self) -> bool {
+    pub fn none() -> ValidationFeatures {
+        ValidationFeatures::default()
+    }
+
+    fn enabled_to_vulkan(&self) -> SmallVec<[vk::ValidationFeatureEnableEXT; 5]> {
+        let mut features = SmallVec::new();
+        if self.gpu_assisted {
+            features.push(vk::VALIDATION_FEATURE_ENABLE_GPU_ASSISTED_EXT);
+        }
+        if self.gpu_assisted_reserve_binding_slot {
+            features.push(vk::VALIDATION_FEATURE_ENABLE_GPU_ASSISTED_RESERVE_BINDING_SLOT_EXT);
+        }
+        if self.best_practices {
+            features.push(vk::VALIDATION_FEATURE_ENABLE_BEST_PRACTICES_EXT);
+        }
+        if self.debug_printf {
+            features.push(vk::VALIDATION_FEATURE_ENABLE_DEBUG_PRINTF_EXT);
+        }
+        if self.synchronization_validation {
+            features.push(vk::VALIDATION_FEATURE_ENABLE_SYNCHRONIZATION_VALIDATION_EXT);
+        }
+        features
+    }
+
+    fn disabled_to_vulkan(&self) -> SmallVec<[vk::ValidationFeatureDisableEXT; 6]> {
+        let mut features = SmallVec::new();
+        if self.disable_shaders {
+            features.push(vk::VALIDATION_FEATURE_DISABLE_SHADERS_EXT);
+        }
+        if self.disable_thread_safety {
+            features.push(vk::VALIDATION_FEATURE_DISABLE_THREAD_SAFETY_EXT);
+        }
+        if self.disable_api_parameters {
+            features.push(vk::VALIDATION_FEATURE_DISABLE_API_PARAMETERS_EXT);
+        }
+        if self.disable_object_lifetimes {
+            features.push(vk::VALIDATION_FEATURE_DISABLE_OBJECT_LIFETIMES_EXT);
+        }
+        if self.disable_core_checks {
+            features.push(vk::VALIDATION_FEATURE_DISABLE_CORE_CHECKS_EXT);
+        }
+        if self.disable_unique_handles {
+            features.push(vk::VALIDATION_FEATURE_DISABLE_UNIQUE_HANDLES_EXT);
+        }
+        features
+    }
+}
+
+impl fmt::Debug for Instance {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan instance {:?}>", self.instance)
+    }
+}
+
+unsafe impl VulkanObject for Instance {
+    type Object = vk::Instance;
+
+    const TYPE: vk::ObjectType = vk::OBJECT_TYPE_INSTANCE;
+
+    #[inline]
+    fn internal_object(&self) -> vk::Instance {
+        self.instance
+    }
+}
+
+impl Drop for Instance {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            self.vk.DestroyInstance(self.instance, self.alloc_ptr());
+        }
+    }
+}
+
+impl PartialEq for Instance {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
         self.instance == other.instance
     }
 }
@@ -581,6 +1451,11 @@ pub struct ApplicationInfo<'a> {
     pub engine_name: Option<Cow<'a, str>>,
     /// An opaque number that contains the version number of the engine.
     pub engine_version: Option<Version>,
+    /// Highest version of Vulkan that the application is designed to use. Defaults to 1.1.
+    ///
+    /// The instance uses the lower of this version and of the version supported by the loader.
+    /// See `Instance::api_version`.
+    pub api_version: Option<Version>,
 }
 
 impl<'a> ApplicationInfo<'a> {
@@ -606,6 +1481,7 @@ impl<'a> ApplicationInfo<'a> {
             application_version: Some(version),
             engine_name: None,
             engine_version: None,
+            api_version: None,
         }
     }
 }
@@ -633,130 +1509,679 @@ macro_rules! app_info_from_cargo_toml {
             application_version: Some(version),
             engine_name: None,
             engine_version: None,
+            api_version: None,
         }
     }};
 }
 
-impl<'a> Default for ApplicationInfo<'a> {
-    fn default() -> ApplicationInfo<'a> {
-        ApplicationInfo {
-            application_name: None,
-            application_version: None,
-            engine_name: None,
-            engine_version: None,
-        }
+impl<'a> Default for ApplicationInfo<'a> {
+    fn default() -> ApplicationInfo<'a> {
+        ApplicationInfo {
+            application_name: None,
+            application_version: None,
+            engine_name: None,
+            engine_version: None,
+            api_version: None,
+        }
+    }
+}
+
+/// Error that can happen when creating an instance.
+#[derive(Clone, Debug)]
+pub enum InstanceCreationError {
+    /// Failed to load the Vulkan shared library.
+    LoadingError(LoadingError),
+    /// Not enough memory.
+    OomError(OomError),
+    /// Failed to initialize for an implementation-specific reason.
+    InitializationFailed,
+    /// One of the requested layers is missing.
+    LayerNotPresent,
+    /// One of the requested extensions is missing.
+    ExtensionNotPresent,
+    /// The version requested is not supported by the implementation.
+    // TODO: more info about this once the question of the version has been resolved
+    IncompatibleDriver,
+    /// The `ext_validation_features` extension must be enabled in order to enable or disable
+    /// features of the validation layers.
+    ValidationFeaturesExtensionNotEnabled,
+    /// The `ext_layer_settings` extension must be enabled in order to pass settings to the
+    /// layers.
+    LayerSettingsExtensionNotEnabled,
+    /// The `debug_printf` and `gpu_assisted` validation features can't be enabled at the same
+    /// time.
+    DebugPrintfAndGpuAssistedEnabled,
+}
+
+impl error::Error for InstanceCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            InstanceCreationError::LoadingError(ref err) => Some(err),
+            InstanceCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InstanceCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                InstanceCreationError::LoadingError(_) =>
+                    "failed to load the Vulkan shared library",
+                InstanceCreationError::OomError(_) => "not enough memory available",
+                InstanceCreationError::InitializationFailed => "initialization failed",
+                InstanceCreationError::LayerNotPresent => "layer not present",
+                InstanceCreationError::ExtensionNotPresent => "extension not present",
+                InstanceCreationError::IncompatibleDriver => "incompatible driver",
+                InstanceCreationError::ValidationFeaturesExtensionNotEnabled => {
+                    "the `ext_validation_features` extension must be enabled in order to enable \
+                     or disable features of the validation layers"
+                }
+                InstanceCreationError::LayerSettingsExtensionNotEnabled => {
+                    "the `ext_layer_settings` extension must be enabled in order to pass settings \
+                     to the layers"
+                }
+                InstanceCreationError::DebugPrintfAndGpuAssistedEnabled => {
+                    "the `debug_printf` and `gpu_assisted` validation features can't be enabled \
+                     at the same time"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for InstanceCreationError {
+    #[inline]
+    fn from(err: OomError) -> InstanceCreationError {
+        InstanceCreationError::OomError(err)
+    }
+}
+
+impl From<LoadingError> for InstanceCreationError {
+    #[inline]
+    fn from(err: LoadingError) -> InstanceCreationError {
+        InstanceCreationError::LoadingError(err)
+    }
+}
+
+impl From<Error> for InstanceCreationError {
+    #[inline]
+    fn from(err: Error) -> InstanceCreationError {
+        match err {
+            err @ Error::OutOfHostMemory => InstanceCreationError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => InstanceCreationError::OomError(OomError::from(err)),
+            Error::InitializationFailed => InstanceCreationError::InitializationFailed,
+            Error::LayerNotPresent => InstanceCreationError::LayerNotPresent,
+            Error::ExtensionNotPresent => InstanceCreationError::ExtensionNotPresent,
+            Error::IncompatibleDriver => InstanceCreationError::IncompatibleDriver,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+struct PhysicalDeviceInfos {
+    device: vk::PhysicalDevice,
+    properties: vk::PhysicalDeviceProperties,
+    extended_properties: PhysicalDeviceExtendedProperties,
+    queue_families: Vec<vk::QueueFamilyProperties>,
+    memory: vk::PhysicalDeviceMemoryProperties,
+    available_features: Features,
+}
+
+/// Represents additional information related to Physical Devices fetched from
+/// `vkGetPhysicalDeviceProperties` call. Certain features available only when
+/// appropriate `Instance` extensions enabled. The core extension required
+/// for this features is `InstanceExtensions::khr_get_physical_device_properties2`
+///
+/// TODO: Only a small subset of available properties(https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceProperties2.html) is implemented at this moment.
+pub struct PhysicalDeviceExtendedProperties {
+    subgroup_size: Option<u32>,
+    subgroup_supported_stages: Option<ShaderStages>,
+    subgroup_supported_operations: Option<SubgroupFeatures>,
+    subgroup_quad_operations_in_all_stages: Option<bool>,
+    primitive_overestimation_size: Option<f32>,
+    max_extra_primitive_overestimation_size: Option<f32>,
+    extra_primitive_overestimation_size_granularity: Option<f32>,
+    primitive_underestimation: Option<bool>,
+    max_vertex_attrib_divisor: Option<u32>,
+    min_fragment_shading_rate_attachment_texel_size: Option<[u32; 2]>,
+    max_fragment_shading_rate_attachment_texel_size: Option<[u32; 2]>,
+    max_fragment_size: Option<[u32; 2]>,
+    fragment_shading_rate_non_trivial_combiner_ops: Option<bool>,
+    line_sub_pixel_precision_bits: Option<u32>,
+    provoking_vertex_mode_per_pipeline: Option<bool>,
+    transform_feedback_preserves_triangle_fan_provoking_vertex: Option<bool>,
+    advanced_blend_max_color_attachments: Option<u32>,
+    advanced_blend_independent_blend: Option<bool>,
+    advanced_blend_non_premultiplied_src_color: Option<bool>,
+    advanced_blend_non_premultiplied_dst_color: Option<bool>,
+    advanced_blend_correlated_overlap: Option<bool>,
+    advanced_blend_all_operations: Option<bool>,
+    sample_location_sample_counts: Option<u32>,
+    max_sample_location_grid_size: Option<[u32; 2]>,
+    sample_location_coordinate_range: Option<[f32; 2]>,
+    sample_location_sub_pixel_bits: Option<u32>,
+    variable_sample_locations: Option<bool>,
+    shader_module_identifier_algorithm_uuid: Option<[u8; 16]>,
+    max_multiview_view_count: Option<u32>,
+    max_multiview_instance_index: Option<u32>,
+    supported_depth_resolve_modes: Option<ResolveModes>,
+    supported_stencil_resolve_modes: Option<ResolveModes>,
+    independent_resolve_none: Option<bool>,
+    independent_resolve: Option<bool>,
+    min_fragment_density_texel_size: Option<[u32; 2]>,
+    max_fragment_density_texel_size: Option<[u32; 2]>,
+    fragment_density_invocations: Option<bool>,
+    min_imported_host_pointer_alignment: Option<u64>,
+    min_vertex_input_binding_stride_alignment: Option<u32>,
+    driver_id: Option<DriverId>,
+    driver_name: Option<String>,
+    driver_info: Option<String>,
+    conformance_version: Option<ConformanceVersion>,
+    protected_no_fault: Option<bool>,
+}
+
+impl PhysicalDeviceExtendedProperties {
+    fn empty() -> Self {
+        Self {
+            subgroup_size: None,
+            subgroup_supported_stages: None,
+            subgroup_supported_operations: None,
+            subgroup_quad_operations_in_all_stages: None,
+            primitive_overestimation_size: None,
+            max_extra_primitive_overestimation_size: None,
+            extra_primitive_overestimation_size_granularity: None,
+            primitive_underestimation: None,
+            max_vertex_attrib_divisor: None,
+            min_fragment_shading_rate_attachment_texel_size: None,
+            max_fragment_shading_rate_attachment_texel_size: None,
+            max_fragment_size: None,
+            fragment_shading_rate_non_trivial_combiner_ops: None,
+            line_sub_pixel_precision_bits: None,
+            provoking_vertex_mode_per_pipeline: None,
+            transform_feedback_preserves_triangle_fan_provoking_vertex: None,
+            advanced_blend_max_color_attachments: None,
+            advanced_blend_independent_blend: None,
+            advanced_blend_non_premultiplied_src_color: None,
+            advanced_blend_non_premultiplied_dst_color: None,
+            advanced_blend_correlated_overlap: None,
+            advanced_blend_all_operations: None,
+            sample_location_sample_counts: None,
+            max_sample_location_grid_size: None,
+            sample_location_coordinate_range: None,
+            sample_location_sub_pixel_bits: None,
+            variable_sample_locations: None,
+            shader_module_identifier_algorithm_uuid: None,
+            max_multiview_view_count: None,
+            max_multiview_instance_index: None,
+            supported_depth_resolve_modes: None,
+            supported_stencil_resolve_modes: None,
+            independent_resolve_none: None,
+            independent_resolve: None,
+            min_fragment_density_texel_size: None,
+            max_fragment_density_texel_size: None,
+            fragment_density_invocations: None,
+            min_imported_host_pointer_alignment: None,
+            min_vertex_input_binding_stride_alignment: None,
+            driver_id: None,
+            driver_name: None,
+            driver_info: None,
+            conformance_version: None,
+            protected_no_fault: None,
+        }
+    }
+
+    /// The default number of invocations in each subgroup
+    ///
+    /// See https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceSubgroupProperties.html for details
+    #[inline]
+    pub fn subgroup_size(&self) -> &Option<u32> {
+        &self.subgroup_size
+    }
+
+    /// The shader stages in which subgroup operations are supported.
+    ///
+    /// Only available with Vulkan 1.1 or `VK_KHR_get_physical_device_properties2`. It is always
+    /// at least `compute`.
+    #[inline]
+    pub fn subgroup_supported_stages(&self) -> &Option<ShaderStages> {
+        &self.subgroup_supported_stages
+    }
+
+    /// The kinds of subgroup operations supported in the stages of `subgroup_supported_stages`.
+    ///
+    /// Only available with Vulkan 1.1 or `VK_KHR_get_physical_device_properties2`. It always
+    /// includes `basic`.
+    #[inline]
+    pub fn subgroup_supported_operations(&self) -> &Option<SubgroupFeatures> {
+        &self.subgroup_supported_operations
+    }
+
+    /// Whether quad subgroup operations are supported in all stages, or only in the fragment and
+    /// compute stages.
+    #[inline]
+    pub fn subgroup_quad_operations_in_all_stages(&self) -> &Option<bool> {
+        &self.subgroup_quad_operations_in_all_stages
+    }
+
+    /// The size, in pixels, by which primitives are extended when using overestimation
+    /// conservative rasterization.
+    ///
+    /// Only available if the device supports `VK_EXT_conservative_rasterization`.
+    #[inline]
+    pub fn primitive_overestimation_size(&self) -> &Option<f32> {
+        &self.primitive_overestimation_size
+    }
+
+    /// The maximum extra size, in pixels, that can be added to the overestimation of primitives
+    /// when using overestimation conservative rasterization.
+    ///
+    /// Only available if the device supports `VK_EXT_conservative_rasterization`.
+    #[inline]
+    pub fn max_extra_primitive_overestimation_size(&self) -> &Option<f32> {
+        &self.max_extra_primitive_overestimation_size
+    }
+
+    /// The granularity of the extra overestimation size.
+    ///
+    /// Only available if the device supports `VK_EXT_conservative_rasterization`.
+    #[inline]
+    pub fn extra_primitive_overestimation_size_granularity(&self) -> &Option<f32> {
+        &self.extra_primitive_overestimation_size_granularity
+    }
+
+    /// Whether underestimation conservative rasterization is supported.
+    ///
+    /// Only available if the device supports `VK_EXT_conservative_rasterization`.
+    #[inline]
+    pub fn primitive_underestimation(&self) -> &Option<bool> {
+        &self.primitive_underestimation
+    }
+
+    /// The maximum value of the divisor of a per-instance vertex binding.
+    ///
+    /// Only available if the device supports `VK_EXT_vertex_attribute_divisor`.
+    #[inline]
+    pub fn max_vertex_attrib_divisor(&self) -> &Option<u32> {
+        &self.max_vertex_attrib_divisor
+    }
+
+    /// The minimum width and height of the area of the framebuffer covered by each texel of a
+    /// fragment shading rate attachment.
+    ///
+    /// Only available if the device supports `VK_KHR_fragment_shading_rate`.
+    #[inline]
+    pub fn min_fragment_shading_rate_attachment_texel_size(&self) -> &Option<[u32; 2]> {
+        &self.min_fragment_shading_rate_attachment_texel_size
+    }
+
+    /// The maximum width and height of the area of the framebuffer covered by each texel of a
+    /// fragment shading rate attachment.
+    ///
+    /// Only available if the device supports `VK_KHR_fragment_shading_rate`.
+    #[inline]
+    pub fn max_fragment_shading_rate_attachment_texel_size(&self) -> &Option<[u32; 2]> {
+        &self.max_fragment_shading_rate_attachment_texel_size
+    }
+
+    /// The maximum width and height of a fragment that is shaded with a coarse shading rate.
+    ///
+    /// Only available if the device supports `VK_KHR_fragment_shading_rate`.
+    #[inline]
+    pub fn max_fragment_size(&self) -> &Option<[u32; 2]> {
+        &self.max_fragment_size
+    }
+
+    /// Whether combiner operations other than `Keep` and `Replace` are supported.
+    ///
+    /// Only available if the device supports `VK_KHR_fragment_shading_rate`.
+    #[inline]
+    pub fn fragment_shading_rate_non_trivial_combiner_ops(&self) -> &Option<bool> {
+        &self.fragment_shading_rate_non_trivial_combiner_ops
+    }
+
+    /// The number of bits of subpixel precision in framebuffer coordinates when rasterizing
+    /// lines with the Bresenham or smooth modes.
+    ///
+    /// Only available if the device supports `VK_EXT_line_rasterization`.
+    #[inline]
+    pub fn line_sub_pixel_precision_bits(&self) -> &Option<u32> {
+        &self.line_sub_pixel_precision_bits
+    }
+
+    /// Whether pipelines with different provoking vertex modes can be used in the same render
+    /// pass instance.
+    ///
+    /// Only available if the device supports `VK_EXT_provoking_vertex`.
+    #[inline]
+    pub fn provoking_vertex_mode_per_pipeline(&self) -> &Option<bool> {
+        &self.provoking_vertex_mode_per_pipeline
+    }
+
+    /// Whether the order of vertices of triangle fans is preserved when captured by transform
+    /// feedback with the last vertex as provoking vertex.
+    ///
+    /// Only available if the device supports `VK_EXT_provoking_vertex`.
+    #[inline]
+    pub fn transform_feedback_preserves_triangle_fan_provoking_vertex(&self) -> &Option<bool> {
+        &self.transform_feedback_preserves_triangle_fan_provoking_vertex
+    }
+
+    /// The maximum number of color attachments that can use an advanced blend operation in a subpass.
+    ///
+    /// Only available if the device supports `VK_EXT_blend_operation_advanced`.
+    #[inline]
+    pub fn advanced_blend_max_color_attachments(&self) -> &Option<u32> {
+        &self.advanced_blend_max_color_attachments
+    }
+
+    /// Whether color attachments can use different advanced blend operations.
+    ///
+    /// Only available if the device supports `VK_EXT_blend_operation_advanced`.
+    #[inline]
+    pub fn advanced_blend_independent_blend(&self) -> &Option<bool> {
+        &self.advanced_blend_independent_blend
+    }
+
+    /// Whether the source color can be treated as non-premultiplied by advanced blend operations.
+    ///
+    /// Only available if the device supports `VK_EXT_blend_operation_advanced`.
+    #[inline]
+    pub fn advanced_blend_non_premultiplied_src_color(&self) -> &Option<bool> {
+        &self.advanced_blend_non_premultiplied_src_color
+    }
+
+    /// Whether the destination color can be treated as non-premultiplied by advanced blend operations.
+    ///
+    /// Only available if the device supports `VK_EXT_blend_operation_advanced`.
+    #[inline]
+    pub fn advanced_blend_non_premultiplied_dst_color(&self) -> &Option<bool> {
+        &self.advanced_blend_non_premultiplied_dst_color
+    }
+
+    /// Whether overlap modes other than `BlendOverlap::Uncorrelated` are supported.
+    ///
+    /// Only available if the device supports `VK_EXT_blend_operation_advanced`.
+    #[inline]
+    pub fn advanced_blend_correlated_overlap(&self) -> &Option<bool> {
+        &self.advanced_blend_correlated_overlap
+    }
+
+    /// Whether all the advanced blend operations are supported. If false, only the operations for
+    /// which `BlendOp::requires_advanced_blend_all_operations` returns false can be used.
+    ///
+    /// Only available if the device supports `VK_EXT_blend_operation_advanced`.
+    #[inline]
+    pub fn advanced_blend_all_operations(&self) -> &Option<bool> {
+        &self.advanced_blend_all_operations
+    }
+
+    /// The sample counts that support custom sample locations, as a bitmask where each bit
+    /// corresponds to a number of samples.
+    ///
+    /// Only available if the device supports `VK_EXT_sample_locations`.
+    #[inline]
+    pub fn sample_location_sample_counts(&self) -> &Option<u32> {
+        &self.sample_location_sample_counts
+    }
+
+    /// The maximum width and height of the grid of custom sample locations, for all the sample
+    /// counts.
+    ///
+    /// Only available if the device supports `VK_EXT_sample_locations`.
+    #[inline]
+    pub fn max_sample_location_grid_size(&self) -> &Option<[u32; 2]> {
+        &self.max_sample_location_grid_size
+    }
+
+    /// The range that the coordinates of custom sample locations are clamped to.
+    ///
+    /// Only available if the device supports `VK_EXT_sample_locations`.
+    #[inline]
+    pub fn sample_location_coordinate_range(&self) -> &Option<[f32; 2]> {
+        &self.sample_location_coordinate_range
+    }
+
+    /// The number of bits of subpixel precision of custom sample locations.
+    ///
+    /// Only available if the device supports `VK_EXT_sample_locations`.
+    #[inline]
+    pub fn sample_location_sub_pixel_bits(&self) -> &Option<u32> {
+        &self.sample_location_sub_pixel_bits
+    }
+
+    /// Whether the custom sample locations can be different for each subpass of a render pass.
+    ///
+    /// Only available if the device supports `VK_EXT_sample_locations`.
+    #[inline]
+    pub fn variable_sample_locations(&self) -> &Option<bool> {
+        &self.variable_sample_locations
+    }
+
+    /// Identifies the algorithm used to compute shader module identifiers. Identifiers can only
+    /// be reused on devices that report the same value.
+    ///
+    /// Only available if the device supports `VK_EXT_shader_module_identifier`.
+    #[inline]
+    pub fn shader_module_identifier_algorithm_uuid(&self) -> &Option<[u8; 16]> {
+        &self.shader_module_identifier_algorithm_uuid
+    }
+
+    /// The maximum number of views of a multiview subpass. Only the lowest bits of a view mask up
+    /// to this number can be set.
+    ///
+    /// Only available if the device supports `VK_KHR_multiview`.
+    #[inline]
+    pub fn max_multiview_view_count(&self) -> &Option<u32> {
+        &self.max_multiview_view_count
+    }
+
+    /// The maximum instance index that can be used when drawing in a multiview subpass.
+    ///
+    /// Only available if the device supports `VK_KHR_multiview`.
+    #[inline]
+    pub fn max_multiview_instance_index(&self) -> &Option<u32> {
+        &self.max_multiview_instance_index
+    }
+
+    /// The modes that can be used to resolve the depth aspect of a depth-stencil attachment.
+    ///
+    /// Only available if the device supports `VK_KHR_depth_stencil_resolve`.
+    #[inline]
+    pub fn supported_depth_resolve_modes(&self) -> &Option<ResolveModes> {
+        &self.supported_depth_resolve_modes
+    }
+
+    /// The modes that can be used to resolve the stencil aspect of a depth-stencil attachment.
+    ///
+    /// Only available if the device supports `VK_KHR_depth_stencil_resolve`.
+    #[inline]
+    pub fn supported_stencil_resolve_modes(&self) -> &Option<ResolveModes> {
+        &self.supported_stencil_resolve_modes
+    }
+
+    /// Whether the depth and stencil aspects can have different resolve modes if one of them is
+    /// `ResolveMode::None`.
+    ///
+    /// Only available if the device supports `VK_KHR_depth_stencil_resolve`.
+    #[inline]
+    pub fn independent_resolve_none(&self) -> &Option<bool> {
+        &self.independent_resolve_none
+    }
+
+    /// Whether the depth and stencil aspects can have any combination of resolve modes.
+    ///
+    /// Only available if the device supports `VK_KHR_depth_stencil_resolve`.
+    #[inline]
+    pub fn independent_resolve(&self) -> &Option<bool> {
+        &self.independent_resolve
+    }
+
+    /// The minimum width and height of the area of the framebuffer covered by each texel of a
+    /// fragment density map.
+    ///
+    /// Only available if the device supports `VK_EXT_fragment_density_map`.
+    #[inline]
+    pub fn min_fragment_density_texel_size(&self) -> &Option<[u32; 2]> {
+        &self.min_fragment_density_texel_size
+    }
+
+    /// The maximum width and height of the area of the framebuffer covered by each texel of a
+    /// fragment density map.
+    ///
+    /// Only available if the device supports `VK_EXT_fragment_density_map`.
+    #[inline]
+    pub fn max_fragment_density_texel_size(&self) -> &Option<[u32; 2]> {
+        &self.max_fragment_density_texel_size
     }
-}
 
-/// Error that can happen when creating an instance.
-#[derive(Clone, Debug)]
-pub enum InstanceCreationError {
-    /// Failed to load the Vulkan shared library.
-    LoadingError(LoadingError),
-    /// Not enough memory.
-    OomError(OomError),
-    /// Failed to initialize for an implementation-specific reason.
-    InitializationFailed,
-    /// One of the requested layers is missing.
-    LayerNotPresent,
-    /// One of the requested extensions is missing.
-    ExtensionNotPresent,
-    /// The version requested is not supported by the implementation.
-    // TODO: more info about this once the question of the version has been resolved
-    IncompatibleDriver,
-}
+    /// Whether the implementation may invoke additional fragment shader invocations for each
+    /// covered sample in a fragment whose density is reduced.
+    ///
+    /// Only available if the device supports `VK_EXT_fragment_density_map`.
+    #[inline]
+    pub fn fragment_density_invocations(&self) -> &Option<bool> {
+        &self.fragment_density_invocations
+    }
 
-impl error::Error for InstanceCreationError {
+    /// The alignment, in bytes, that the pointer and the size of an imported host allocation
+    /// must have.
+    ///
+    /// Only available if the device supports `VK_EXT_external_memory_host`.
     #[inline]
-    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-        match *self {
-            InstanceCreationError::LoadingError(ref err) => Some(err),
-            InstanceCreationError::OomError(ref err) => Some(err),
-            _ => None,
-        }
+    pub fn min_imported_host_pointer_alignment(&self) -> &Option<u64> {
+        &self.min_imported_host_pointer_alignment
     }
-}
 
-impl fmt::Display for InstanceCreationError {
+    /// The alignment, in bytes, that the strides of the vertex input bindings must have.
+    ///
+    /// Only available if the device supports `VK_KHR_portability_subset`. The other devices
+    /// don't have any requirement.
     #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(
-            fmt,
-            "{}",
-            match *self {
-                InstanceCreationError::LoadingError(_) =>
-                    "failed to load the Vulkan shared library",
-                InstanceCreationError::OomError(_) => "not enough memory available",
-                InstanceCreationError::InitializationFailed => "initialization failed",
-                InstanceCreationError::LayerNotPresent => "layer not present",
-                InstanceCreationError::ExtensionNotPresent => "extension not present",
-                InstanceCreationError::IncompatibleDriver => "incompatible driver",
-            }
-        )
+    pub fn min_vertex_input_binding_stride_alignment(&self) -> &Option<u32> {
+        &self.min_vertex_input_binding_stride_alignment
     }
-}
 
-impl From<OomError> for InstanceCreationError {
+    /// The driver of the physical device.
+    ///
+    /// Only available if the device supports `VK_KHR_driver_properties`.
     #[inline]
-    fn from(err: OomError) -> InstanceCreationError {
-        InstanceCreationError::OomError(err)
+    pub fn driver_id(&self) -> &Option<DriverId> {
+        &self.driver_id
     }
-}
 
-impl From<LoadingError> for InstanceCreationError {
+    /// The name of the driver.
+    ///
+    /// Only available if the device supports `VK_KHR_driver_properties`.
     #[inline]
-    fn from(err: LoadingError) -> InstanceCreationError {
-        InstanceCreationError::LoadingError(err)
+    pub fn driver_name(&self) -> &Option<String> {
+        &self.driver_name
     }
-}
 
-impl From<Error> for InstanceCreationError {
+    /// Additional information about the driver, such as its version. The format of this string
+    /// depends on the driver.
+    ///
+    /// Only available if the device supports `VK_KHR_driver_properties`.
     #[inline]
-    fn from(err: Error) -> InstanceCreationError {
-        match err {
-            err @ Error::OutOfHostMemory => InstanceCreationError::OomError(OomError::from(err)),
-            err @ Error::OutOfDeviceMemory => InstanceCreationError::OomError(OomError::from(err)),
-            Error::InitializationFailed => InstanceCreationError::InitializationFailed,
-            Error::LayerNotPresent => InstanceCreationError::LayerNotPresent,
-            Error::ExtensionNotPresent => InstanceCreationError::ExtensionNotPresent,
-            Error::IncompatibleDriver => InstanceCreationError::IncompatibleDriver,
-            _ => panic!("unexpected error: {:?}", err),
-        }
+    pub fn driver_info(&self) -> &Option<String> {
+        &self.driver_info
     }
-}
 
-struct PhysicalDeviceInfos {
-    device: vk::PhysicalDevice,
-    properties: vk::PhysicalDeviceProperties,
-    extended_properties: PhysicalDeviceExtendedProperties,
-    queue_families: Vec<vk::QueueFamilyProperties>,
-    memory: vk::PhysicalDeviceMemoryProperties,
-    available_features: Features,
+    /// The version of the Vulkan conformance test suite that the driver passed.
+    ///
+    /// Only available if the device supports `VK_KHR_driver_properties`.
+    #[inline]
+    pub fn conformance_version(&self) -> &Option<ConformanceVersion> {
+        &self.conformance_version
+    }
+
+    /// Whether the device guarantees that a protected queue writing to unprotected memory, or an
+    /// unprotected queue reading protected memory, doesn't cause a fault. Without this guarantee,
+    /// such accesses have undefined results and may lose the device.
+    ///
+    /// Only available if the physical device properties were queried with
+    /// `VkPhysicalDeviceProperties2`.
+    #[inline]
+    pub fn protected_no_fault(&self) -> &Option<bool> {
+        &self.protected_no_fault
+    }
 }
 
-/// Represents additional information related to Physical Devices fetched from
-/// `vkGetPhysicalDeviceProperties` call. Certain features available only when
-/// appropriate `Instance` extensions enabled. The core extension required
-/// for this features is `InstanceExtensions::khr_get_physical_device_properties2`
+/// Describes kinds of subgroup operations.
 ///
-/// TODO: Only a small subset of available properties(https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceProperties2.html) is implemented at this moment.
-pub struct PhysicalDeviceExtendedProperties {
-    subgroup_size: Option<u32>,
+/// Used both for the operations supported by a physical device and for the operations that a
+/// shader module requires.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SubgroupFeatures {
+    /// Electing an invocation and synchronizing the subgroup, with the `GroupNonUniform`
+    /// capability.
+    pub basic: bool,
+    /// Evaluating a condition across the subgroup, with `GroupNonUniformVote`.
+    pub vote: bool,
+    /// Arithmetic reductions and scans across the subgroup, with `GroupNonUniformArithmetic`.
+    pub arithmetic: bool,
+    /// Ballots and broadcasts, with `GroupNonUniformBallot`.
+    pub ballot: bool,
+    /// Reading values of arbitrary invocations, with `GroupNonUniformShuffle`.
+    pub shuffle: bool,
+    /// Reading values of invocations at a relative position, with
+    /// `GroupNonUniformShuffleRelative`.
+    pub shuffle_relative: bool,
+    /// Arithmetic operations on clusters of invocations, with `GroupNonUniformClustered`.
+    pub clustered: bool,
+    /// Operations on quads of invocations, with `GroupNonUniformQuad`.
+    pub quad: bool,
+    /// Operations on partitions of the subgroup, with `GroupNonUniformPartitionedNV`.
+    pub partitioned: bool,
 }
 
-impl PhysicalDeviceExtendedProperties {
-    fn empty() -> Self {
-        Self {
-            subgroup_size: None,
+impl SubgroupFeatures {
+    /// Builds a `SubgroupFeatures` with all the members set to `false`.
+    #[inline]
+    pub fn none() -> SubgroupFeatures {
+        SubgroupFeatures::default()
+    }
+
+    /// Returns true if all the operations of `other` are also in `self`.
+    #[inline]
+    pub fn is_superset_of(&self, other: &SubgroupFeatures) -> bool {
+        other.difference(self) == SubgroupFeatures::none()
+    }
+
+    /// Returns the operations of `self` that are not in `other`.
+    #[inline]
+    pub fn difference(&self, other: &SubgroupFeatures) -> SubgroupFeatures {
+        SubgroupFeatures {
+            basic: self.basic && !other.basic,
+            vote: self.vote && !other.vote,
+            arithmetic: self.arithmetic && !other.arithmetic,
+            ballot: self.ballot && !other.ballot,
+            shuffle: self.shuffle && !other.shuffle,
+            shuffle_relative: self.shuffle_relative && !other.shuffle_relative,
+            clustered: self.clustered && !other.clustered,
+            quad: self.quad && !other.quad,
+            partitioned: self.partitioned && !other.partitioned,
         }
     }
 
-    /// The default number of invocations in each subgroup
-    ///
-    /// See https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceSubgroupProperties.html for details
     #[inline]
-    pub fn subgroup_size(&self) -> &Option<u32> {
-        &self.subgroup_size
+    pub(crate) fn from_vulkan_bits(value: vk::SubgroupFeatureFlags) -> SubgroupFeatures {
+        SubgroupFeatures {
+            basic: (value & vk::SUBGROUP_FEATURE_BASIC_BIT) != 0,
+            vote: (value & vk::SUBGROUP_FEATURE_VOTE_BIT) != 0,
+            arithmetic: (value & vk::SUBGROUP_FEATURE_ARITHMETIC_BIT) != 0,
+            ballot: (value & vk::SUBGROUP_FEATURE_BALLOT_BIT) != 0,
+            shuffle: (value & vk::SUBGROUP_FEATURE_SHUFFLE_BIT) != 0,
+            shuffle_relative: (value & vk::SUBGROUP_FEATURE_SHUFFLE_RELATIVE_BIT) != 0,
+            clustered: (value & vk::SUBGROUP_FEATURE_CLUSTERED_BIT) != 0,
+            quad: (value & vk::SUBGROUP_FEATURE_QUAD_BIT) != 0,
+            partitioned: (value & vk::SUBGROUP_FEATURE_PARTITIONED_BIT_NV) != 0,
+        }
     }
 }
 
@@ -904,18 +2329,120 @@ impl<'a> PhysicalDevice<'a> {
     }
 
     /// Returns the version of Vulkan supported by this device.
+    ///
+    /// A device created from this physical device uses the lower of this version and of
+    /// `Instance::api_version`.
     #[inline]
     pub fn api_version(&self) -> Version {
         let val = self.infos().properties.apiVersion;
         Version::from_vulkan_version(val)
     }
 
+    /// Returns the tools that are currently active on this physical device, such as validation
+    /// layers, profilers or frame capture tools.
+    ///
+    /// This can be used to adapt the behavior of the application, for example to disable the
+    /// code paths that are sensitive to timings while a frame is being captured.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the physical device doesn't support the `ext_tooling_info` extension, and
+    ///   neither the physical device nor the instance support Vulkan 1.3.
+    ///
+    pub fn tool_properties(&self) -> Result<Vec<ToolProperties>, OomError> {
+        assert!(
+            DeviceExtensions::supported_by_device(*self).ext_tooling_info
+                || cmp::min(self.api_version(), self.instance.api_version()) >= Version::V1_3,
+            "the `ext_tooling_info` extension must be supported by the physical device"
+        );
+
+        unsafe {
+            let vk = self.instance.pointers();
+
+            let properties = loop {
+                let mut num = 0;
+                check_errors(vk.GetPhysicalDeviceToolPropertiesEXT(
+                    self.internal_object(),
+                    &mut num,
+                    ptr::null_mut(),
+                ))?;
+
+                let mut properties: Vec<vk::PhysicalDeviceToolPropertiesEXT> = (0..num)
+                    .map(|_| vk::PhysicalDeviceToolPropertiesEXT {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_TOOL_PROPERTIES_EXT,
+                        pNext: ptr::null_mut(),
+                        ..mem::zeroed()
+                    })
+                    .collect();
+                let result = check_errors(vk.GetPhysicalDeviceToolPropertiesEXT(
+                    self.internal_object(),
+                    &mut num,
+                    properties.as_mut_ptr(),
+                ))?;
+
+                if !matches!(result, Success::Incomplete) {
+                    properties.truncate(num as usize);
+                    break properties;
+                }
+            };
+
+            Ok(properties.iter().map(ToolProperties::from_vulkan).collect())
+        }
+    }
+
     /// Returns the Vulkan features that are supported by this physical device.
     #[inline]
     pub fn supported_features(&self) -> &'a Features {
         &self.infos().available_features
     }
 
+    /// Queries an extension feature structure that isn't covered by `Features`.
+    ///
+    /// The structure is chained to `VkPhysicalDeviceFeatures2` alone. The extension it belongs
+    /// to should be supported by the physical device, otherwise the content of the returned
+    /// structure is unspecified.
+    pub fn extension_features<T>(&self) -> T
+    where
+        T: ExtensionFeatures,
+    {
+        unsafe {
+            let mut features: T = zeroed_struct(T::STRUCTURE_TYPE);
+            let mut output = vk::PhysicalDeviceFeatures2KHR {
+                sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2_KHR,
+                pNext: &mut features as *mut T as *const c_void,
+                features: mem::zeroed(),
+            };
+
+            let vk = self.instance.pointers();
+            vk.GetPhysicalDeviceFeatures2KHR(self.internal_object(), &mut output);
+            features
+        }
+    }
+
+    /// Queries an extension property structure that isn't covered by the properties of the
+    /// physical device.
+    ///
+    /// The structure is chained to `VkPhysicalDeviceProperties2` alone. The extension it belongs
+    /// to should be supported by the physical device, otherwise the content of the returned
+    /// structure is unspecified.
+    pub fn extension_properties<T>(&self) -> T
+    where
+        T: ExtensionProperties,
+    {
+        unsafe {
+            let mut properties: T = zeroed_struct(T::STRUCTURE_TYPE);
+            let mut output = vk::PhysicalDeviceProperties2KHR {
+                sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROPERTIES_2_KHR,
+                pNext: &mut properties as *mut T as *mut _,
+                properties: mem::zeroed(),
+            };
+
+            let vk = self.instance.pointers();
+            vk.GetPhysicalDeviceProperties2KHR(self.internal_object(), &mut output);
+            properties
+        }
+    }
+
     /// Builds an iterator that enumerates all the queue families on this physical device.
     #[inline]
     pub fn queue_families(&self) -> QueueFamiliesIter<'a> {
@@ -1079,6 +2606,96 @@ impl<'a> Iterator for PhysicalDevicesIter<'a> {
 
 impl<'a> ExactSizeIterator for PhysicalDevicesIter<'a> {}
 
+/// Group of physical devices that can be used together by a single logical device.
+///
+/// Every physical device belongs to exactly one group. A group with more than one physical
+/// device, for example several GPUs linked together, can be passed to
+/// `Device::with_device_group` in order to use them as one device.
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceGroup<'a> {
+    physical_devices: SmallVec<[PhysicalDevice<'a>; 4]>,
+    subset_allocation: bool,
+}
+
+impl<'a> PhysicalDeviceGroup<'a> {
+    /// Enumerates the physical device groups of the instance.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_device_group_creation` extension isn't loaded and the instance
+    ///   doesn't use Vulkan 1.1.
+    ///
+    pub fn enumerate(
+        instance: &'a Arc<Instance>,
+    ) -> Result<Vec<PhysicalDeviceGroup<'a>>, OomError> {
+        assert!(
+            instance
+                .loaded_extensions_or_core()
+                .khr_device_group_creation,
+            "the `khr_device_group_creation` extension must be loaded"
+        );
+
+        let groups = unsafe {
+            let vk = instance.pointers();
+
+            let mut num = 0;
+            check_errors(vk.EnumeratePhysicalDeviceGroupsKHR(
+                instance.internal_object(),
+                &mut num,
+                ptr::null_mut(),
+            ))?;
+
+            let mut groups: Vec<vk::PhysicalDeviceGroupProperties> = (0..num)
+                .map(|_| vk::PhysicalDeviceGroupProperties {
+                    sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_GROUP_PROPERTIES,
+                    pNext: ptr::null_mut(),
+                    ..mem::zeroed()
+                })
+                .collect();
+            check_errors(vk.EnumeratePhysicalDeviceGroupsKHR(
+                instance.internal_object(),
+                &mut num,
+                groups.as_mut_ptr(),
+            ))?;
+            groups.set_len(num as usize);
+            groups
+        };
+
+        Ok(groups
+            .into_iter()
+            .map(|group| {
+                let physical_devices = group.physicalDevices[..group.physicalDeviceCount as usize]
+                    .iter()
+                    .map(|&handle| {
+                        PhysicalDevice::enumerate(instance)
+                            .find(|p| p.internal_object() == handle)
+                            .expect("the implementation returned an unknown physical device")
+                    })
+                    .collect();
+
+                PhysicalDeviceGroup {
+                    physical_devices,
+                    subset_allocation: group.subsetAllocation != vk::FALSE,
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the physical devices of the group.
+    #[inline]
+    pub fn physical_devices(&self) -> &[PhysicalDevice<'a>] {
+        &self.physical_devices
+    }
+
+    /// Returns true if memory can be allocated on a subset of the physical devices of the group,
+    /// with `DeviceMemoryBuilder::device_mask`. Otherwise the memory is always allocated on all
+    /// of them.
+    #[inline]
+    pub fn subset_allocation(&self) -> bool {
+        self.subset_allocation
+    }
+}
+
 /// Type of a physical device.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 #[repr(u32)]
@@ -1095,6 +2712,91 @@ pub enum PhysicalDeviceType {
     Other = 0,
 }
 
+/// Identifier of the driver of a physical device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DriverId {
+    AmdProprietary,
+    AmdOpenSource,
+    MesaRadv,
+    NvidiaProprietary,
+    IntelProprietaryWindows,
+    IntelOpenSourceMesa,
+    ImaginationProprietary,
+    QualcommProprietary,
+    ArmProprietary,
+    GoogleSwiftshader,
+    GgpProprietary,
+    BroadcomProprietary,
+    MesaLlvmpipe,
+    MoltenVk,
+    CoreaviProprietary,
+    JuiceProprietary,
+    VerisiliconProprietary,
+    MesaTurnip,
+    MesaV3dv,
+    MesaPanvk,
+    SamsungProprietary,
+    MesaVenus,
+    MesaDozen,
+    MesaNvk,
+    ImaginationOpenSourceMesa,
+    /// A driver that vulkano doesn't know about, with its raw identifier.
+    Unknown(u32),
+}
+
+impl DriverId {
+    fn from_vulkan(val: vk::DriverId) -> DriverId {
+        match val {
+            vk::DRIVER_ID_AMD_PROPRIETARY => DriverId::AmdProprietary,
+            vk::DRIVER_ID_AMD_OPEN_SOURCE => DriverId::AmdOpenSource,
+            vk::DRIVER_ID_MESA_RADV => DriverId::MesaRadv,
+            vk::DRIVER_ID_NVIDIA_PROPRIETARY => DriverId::NvidiaProprietary,
+            vk::DRIVER_ID_INTEL_PROPRIETARY_WINDOWS => DriverId::IntelProprietaryWindows,
+            vk::DRIVER_ID_INTEL_OPEN_SOURCE_MESA => DriverId::IntelOpenSourceMesa,
+            vk::DRIVER_ID_IMAGINATION_PROPRIETARY => DriverId::ImaginationProprietary,
+            vk::DRIVER_ID_QUALCOMM_PROPRIETARY => DriverId::QualcommProprietary,
+            vk::DRIVER_ID_ARM_PROPRIETARY => DriverId::ArmProprietary,
+            vk::DRIVER_ID_GOOGLE_SWIFTSHADER => DriverId::GoogleSwiftshader,
+            vk::DRIVER_ID_GGP_PROPRIETARY => DriverId::GgpProprietary,
+            vk::DRIVER_ID_BROADCOM_PROPRIETARY => DriverId::BroadcomProprietary,
+            vk::DRIVER_ID_MESA_LLVMPIPE => DriverId::MesaLlvmpipe,
+            vk::DRIVER_ID_MOLTENVK => DriverId::MoltenVk,
+            vk::DRIVER_ID_COREAVI_PROPRIETARY => DriverId::CoreaviProprietary,
+            vk::DRIVER_ID_JUICE_PROPRIETARY => DriverId::JuiceProprietary,
+            vk::DRIVER_ID_VERISILICON_PROPRIETARY => DriverId::VerisiliconProprietary,
+            vk::DRIVER_ID_MESA_TURNIP => DriverId::MesaTurnip,
+            vk::DRIVER_ID_MESA_V3DV => DriverId::MesaV3dv,
+            vk::DRIVER_ID_MESA_PANVK => DriverId::MesaPanvk,
+            vk::DRIVER_ID_SAMSUNG_PROPRIETARY => DriverId::SamsungProprietary,
+            vk::DRIVER_ID_MESA_VENUS => DriverId::MesaVenus,
+            vk::DRIVER_ID_MESA_DOZEN => DriverId::MesaDozen,
+            vk::DRIVER_ID_MESA_NVK => DriverId::MesaNvk,
+            vk::DRIVER_ID_IMAGINATION_OPEN_SOURCE_MESA => DriverId::ImaginationOpenSourceMesa,
+            val => DriverId::Unknown(val),
+        }
+    }
+}
+
+/// Version of the Vulkan conformance test suite.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ConformanceVersion {
+    pub major: u8,
+    pub minor: u8,
+    pub subminor: u8,
+    pub patch: u8,
+}
+
+impl fmt::Display for ConformanceVersion {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.subminor, self.patch
+        )
+    }
+}
+
 /// Represents a queue family in a physical device.
 ///
 /// A queue family is group of one or multiple queues. All queues of one family have the same
@@ -1177,6 +2879,12 @@ impl<'a> QueueFamily<'a> {
         (self.flags() & vk::QUEUE_SPARSE_BINDING_BIT) != 0
     }
 
+    /// Returns true if protected queues can be created in this family.
+    #[inline]
+    pub fn supports_protected(&self) -> bool {
+        (self.flags() & vk::QUEUE_PROTECTED_BIT) != 0
+    }
+
     /// Internal utility function that returns the flags of this queue family.
     #[inline]
     fn flags(&self) -> u32 {
@@ -1300,6 +3008,14 @@ impl<'a> MemoryType<'a> {
         (self.flags() & vk::MEMORY_PROPERTY_LAZILY_ALLOCATED_BIT) != 0
     }
 
+    /// Memory of this type can only be used by protected resources, and accessed by protected
+    /// queues. Memory of this type is never host-visible, and requires the `protected_memory`
+    /// feature.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        (self.flags() & vk::MEMORY_PROPERTY_PROTECTED_BIT) != 0
+    }
+
     /// Internal utility function that returns the flags of this queue family.
     #[inline]
     fn flags(&self) -> u32 {
@@ -1413,13 +3129,143 @@ impl<'a> ExactSizeIterator for MemoryHeapsIter<'a> {}
 
 #[cfg(test)]
 mod tests {
+    use crate::device::DeviceExtensions;
     use crate::instance;
+    use crate::vk;
 
     #[test]
     fn create_instance() {
         let _ = instance!();
     }
 
+    #[test]
+    fn api_version() {
+        let instance = instance!();
+        assert!(instance.api_version() >= instance::Version::V1_0);
+        assert!(instance.api_version() <= instance::Version::V1_1);
+
+        let app_infos = instance::ApplicationInfo {
+            api_version: Some(instance::Version::V1_0),
+            ..Default::default()
+        };
+        let instance = instance::Instance::new(
+            Some(&app_infos),
+            &instance::InstanceExtensions::none(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(instance.api_version(), instance::Version::V1_0);
+        assert!(
+            !instance
+                .loaded_extensions_or_core()
+                .khr_get_physical_device_properties2
+        );
+    }
+
+    #[test]
+    fn validation_features_require_extension() {
+        let validation_features = instance::ValidationFeatures {
+            disable_thread_safety: true,
+            ..instance::ValidationFeatures::none()
+        };
+
+        match instance::Instance::with_validation_features(
+            None,
+            &instance::InstanceExtensions::none(),
+            None,
+            validation_features,
+        ) {
+            Err(instance::InstanceCreationError::ValidationFeaturesExtensionNotEnabled) => (),
+            Err(instance::InstanceCreationError::LoadingError(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn layer_settings_require_extension() {
+        let layer_settings = [instance::LayerSetting::new(
+            "VK_LAYER_KHRONOS_validation",
+            "validate_sync",
+            instance::LayerSettingValue::Bool(vec![true]),
+        )];
+
+        match instance::Instance::with_layer_settings(
+            None,
+            &instance::InstanceExtensions::none(),
+            None,
+            &layer_settings,
+        ) {
+            Err(instance::InstanceCreationError::LayerSettingsExtensionNotEnabled) => (),
+            Err(instance::InstanceCreationError::LoadingError(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn portability_enumeration() {
+        let supported = match instance::InstanceExtensions::supported_by_core() {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+        if !supported.khr_portability_enumeration {
+            return;
+        }
+
+        let instance = instance!();
+        let extensions = instance::InstanceExtensions {
+            khr_portability_enumeration: true,
+            ..instance::InstanceExtensions::none()
+        };
+        let portability_instance = instance::Instance::new(None, &extensions, None).unwrap();
+
+        // The portability devices are listed in addition to the other ones.
+        assert!(
+            instance::PhysicalDevice::enumerate(&portability_instance).count()
+                >= instance::PhysicalDevice::enumerate(&instance).count()
+        );
+    }
+
+    #[test]
+    fn portability_subset_properties() {
+        let instance = instance!();
+        let properties2 = instance
+            .loaded_extensions_or_core()
+            .khr_get_physical_device_properties2;
+
+        for phys in instance::PhysicalDevice::enumerate(&instance) {
+            let portability_subset =
+                DeviceExtensions::supported_by_device(phys).khr_portability_subset;
+            assert_eq!(
+                phys.extended_properties()
+                    .min_vertex_input_binding_stride_alignment()
+                    .is_some(),
+                properties2 && portability_subset
+            );
+        }
+    }
+
+    #[test]
+    fn validation_features_to_vulkan() {
+        let validation_features = instance::ValidationFeatures {
+            best_practices: true,
+            disable_shaders: true,
+            disable_unique_handles: true,
+            ..instance::ValidationFeatures::none()
+        };
+
+        assert_eq!(
+            &validation_features.enabled_to_vulkan()[..],
+            &[vk::VALIDATION_FEATURE_ENABLE_BEST_PRACTICES_EXT]
+        );
+        assert_eq!(
+            &validation_features.disabled_to_vulkan()[..],
+            &[
+                vk::VALIDATION_FEATURE_DISABLE_SHADERS_EXT,
+                vk::VALIDATION_FEATURE_DISABLE_UNIQUE_HANDLES_EXT,
+            ]
+        );
+    }
+
     #[test]
     fn queue_family_by_id() {
         let instance = instance!();
@@ -1437,4 +3283,41 @@ mod tests {
         let by_id = phys.queue_family_by_id(queue_family.id()).unwrap();
         assert_eq!(by_id.id(), queue_family.id());
     }
+
+    #[test]
+    fn driver_properties() {
+        let instance = instance!();
+
+        for phys in instance::PhysicalDevice::enumerate(&instance) {
+            let properties = phys.extended_properties();
+            assert_eq!(
+                properties.driver_id().is_some(),
+                properties.driver_name().is_some()
+            );
+            assert_eq!(
+                properties.driver_id().is_some(),
+                properties.conformance_version().is_some()
+            );
+        }
+    }
+
+    #[test]
+    fn driver_id_from_vulkan() {
+        assert_eq!(
+            instance::DriverId::from_vulkan(vk::DRIVER_ID_MESA_RADV),
+            instance::DriverId::MesaRadv
+        );
+        assert_eq!(
+            instance::DriverId::from_vulkan(0x7fff_ffff),
+            instance::DriverId::Unknown(0x7fff_ffff)
+        );
+
+        let version = instance::ConformanceVersion {
+            major: 1,
+            minor: 2,
+            subminor: 7,
+            patch: 1,
+        };
+        assert_eq!(version.to_string(), "1.2.7.1");
+    }
 }
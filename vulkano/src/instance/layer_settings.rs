@@ -0,0 +1,213 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::os::raw::c_void;
+
+use crate::vk;
+
+/// Setting of a layer, passed at instance creation with `Instance::with_layer_settings`.
+///
+/// This is an alternative to configuring the layers with environment variables or with a
+/// `vk_layer_settings.txt` file. The names and the types of the settings are documented by each
+/// layer.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::instance::LayerSetting;
+/// use vulkano::instance::LayerSettingValue;
+///
+/// let settings = [
+///     LayerSetting::new(
+///         "VK_LAYER_KHRONOS_validation",
+///         "validate_sync",
+///         LayerSettingValue::Bool(vec![true]),
+///     ),
+///     LayerSetting::new(
+///         "VK_LAYER_KHRONOS_validation",
+///         "report_flags",
+///         LayerSettingValue::String(vec!["error".into(), "warn".into()]),
+///     ),
+/// ];
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerSetting {
+    /// Name of the layer that the setting applies to.
+    pub layer_name: String,
+    /// Name of the setting.
+    pub setting_name: String,
+    /// Values of the setting. Most settings have a single value.
+    pub value: LayerSettingValue,
+}
+
+impl LayerSetting {
+    /// Builds a setting.
+    #[inline]
+    pub fn new<L, S>(layer_name: L, setting_name: S, value: LayerSettingValue) -> LayerSetting
+    where
+        L: Into<String>,
+        S: Into<String>,
+    {
+        LayerSetting {
+            layer_name: layer_name.into(),
+            setting_name: setting_name.into(),
+            value,
+        }
+    }
+}
+
+/// Values of a `LayerSetting`. The type must match the one that the layer expects.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayerSettingValue {
+    Bool(Vec<bool>),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Uint32(Vec<u32>),
+    Uint64(Vec<u64>),
+    Float32(Vec<f32>),
+    Float64(Vec<f64>),
+    String(Vec<String>),
+}
+
+/// Vulkan version of a list of settings, which holds the memory that the pointers refer to.
+pub(crate) struct RawLayerSettings {
+    settings: Vec<vk::LayerSettingEXT>,
+    _strings: Vec<CString>,
+    _bools: Vec<Vec<vk::Bool32>>,
+    _string_ptrs: Vec<Vec<*const c_char>>,
+}
+
+impl RawLayerSettings {
+    /// Converts the settings. The result must not outlive `settings`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if a name or a string value contains a null character.
+    ///
+    pub(crate) fn new(settings: &[LayerSetting]) -> RawLayerSettings {
+        let mut strings = Vec::new();
+        let mut bools = Vec::new();
+        let mut string_ptrs = Vec::new();
+
+        // Moving a `CString` or a `Vec` into the lists doesn't move its content, so the pointers
+        // stay valid.
+        let mut c_string = |s: &str| {
+            let s = CString::new(s).unwrap();
+            let ptr = s.as_ptr();
+            strings.push(s);
+            ptr
+        };
+
+        let settings = settings
+            .iter()
+            .map(|setting| {
+                let (ty, count, values) = match setting.value {
+                    LayerSettingValue::Bool(ref v) => {
+                        let v: Vec<vk::Bool32> = v
+                            .iter()
+                            .map(|&b| if b { vk::TRUE } else { vk::FALSE })
+                            .collect();
+                        let ptr = v.as_ptr() as *const c_void;
+                        let count = v.len();
+                        bools.push(v);
+                        (vk::LAYER_SETTING_TYPE_BOOL32_EXT, count, ptr)
+                    }
+                    LayerSettingValue::Int32(ref v) => {
+                        (vk::LAYER_SETTING_TYPE_INT32_EXT, v.len(), v.as_ptr() as _)
+                    }
+                    LayerSettingValue::Int64(ref v) => {
+                        (vk::LAYER_SETTING_TYPE_INT64_EXT, v.len(), v.as_ptr() as _)
+                    }
+                    LayerSettingValue::Uint32(ref v) => {
+                        (vk::LAYER_SETTING_TYPE_UINT32_EXT, v.len(), v.as_ptr() as _)
+                    }
+                    LayerSettingValue::Uint64(ref v) => {
+                        (vk::LAYER_SETTING_TYPE_UINT64_EXT, v.len(), v.as_ptr() as _)
+                    }
+                    LayerSettingValue::Float32(ref v) => {
+                        (vk::LAYER_SETTING_TYPE_FLOAT32_EXT, v.len(), v.as_ptr() as _)
+                    }
+                    LayerSettingValue::Float64(ref v) => {
+                        (vk::LAYER_SETTING_TYPE_FLOAT64_EXT, v.len(), v.as_ptr() as _)
+                    }
+                    LayerSettingValue::String(ref v) => {
+                        let v: Vec<*const c_char> = v.iter().map(|s| c_string(s)).collect();
+                        let ptr = v.as_ptr() as *const c_void;
+                        let count = v.len();
+                        string_ptrs.push(v);
+                        (vk::LAYER_SETTING_TYPE_STRING_EXT, count, ptr)
+                    }
+                };
+
+                vk::LayerSettingEXT {
+                    pLayerName: c_string(&setting.layer_name),
+                    pSettingName: c_string(&setting.setting_name),
+                    ty,
+                    valueCount: count as u32,
+                    pValues: values,
+                }
+            })
+            .collect();
+
+        RawLayerSettings {
+            settings,
+            _strings: strings,
+            _bools: bools,
+            _string_ptrs: string_ptrs,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[vk::LayerSettingEXT] {
+        &self.settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RawLayerSettings;
+    use crate::instance::LayerSetting;
+    use crate::instance::LayerSettingValue;
+    use crate::vk;
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+
+    #[test]
+    fn to_vulkan() {
+        let settings = [
+            LayerSetting::new("layer", "a", LayerSettingValue::Bool(vec![true, false])),
+            LayerSetting::new("layer", "b", LayerSettingValue::String(vec!["foo".into()])),
+        ];
+        let raw = RawLayerSettings::new(&settings);
+        let raw = raw.as_slice();
+
+        assert_eq!(raw.len(), 2);
+        unsafe {
+            assert_eq!(CStr::from_ptr(raw[0].pLayerName).to_str().unwrap(), "layer");
+            assert_eq!(CStr::from_ptr(raw[0].pSettingName).to_str().unwrap(), "a");
+            assert_eq!(raw[0].ty, vk::LAYER_SETTING_TYPE_BOOL32_EXT);
+            assert_eq!(raw[0].valueCount, 2);
+            let values = raw[0].pValues as *const vk::Bool32;
+            assert_eq!(*values.offset(0), vk::TRUE);
+            assert_eq!(*values.offset(1), vk::FALSE);
+
+            assert_eq!(raw[1].ty, vk::LAYER_SETTING_TYPE_STRING_EXT);
+            assert_eq!(raw[1].valueCount, 1);
+            let values = raw[1].pValues as *const *const c_char;
+            assert_eq!(CStr::from_ptr(*values).to_str().unwrap(), "foo");
+        }
+    }
+}
@@ -21,7 +21,10 @@
 //! By default vulkano will use the `auto_loader()` function, which tries to automatically load
 //! a Vulkan implementation from the system.
 
+use crate::check_errors;
+use crate::instance::Version;
 use crate::vk;
+use crate::OomError;
 use crate::SafeDeref;
 use lazy_static::lazy_static;
 use shared_library;
@@ -109,6 +112,73 @@ unsafe impl Loader for DynamicLibraryLoader {
     }
 }
 
+/// Implementation of `Loader` that forwards to a `vkGetInstanceProcAddr` function provided by the
+/// user.
+///
+/// This is useful if the Vulkan implementation is linked to the application, for example
+/// MoltenVK on iOS, or if the application runs in an environment that doesn't allow loading
+/// dynamic libraries.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::os::raw::c_char;
+/// use vulkano::instance::InstanceExtensions;
+/// use vulkano::instance::Instance;
+/// use vulkano::instance::loader::FunctionPointers;
+/// use vulkano::instance::loader::ProcAddrLoader;
+///
+/// extern "system" fn get_instance_proc_addr(
+///     instance: usize,
+///     name: *const c_char,
+/// ) -> extern "system" fn() -> () {
+///     // Forward to the Vulkan implementation.
+/// #   unimplemented!()
+/// }
+///
+/// let loader = unsafe { ProcAddrLoader::new(get_instance_proc_addr) };
+/// let instance = Instance::with_loader(FunctionPointers::new(Box::new(loader)), None,
+///                                      &InstanceExtensions::none(), None).unwrap();
+/// ```
+pub struct ProcAddrLoader {
+    get_proc_addr: extern "system" fn(
+        instance: vk::Instance,
+        name: *const c_char,
+    ) -> extern "system" fn() -> (),
+}
+
+impl ProcAddrLoader {
+    /// Builds a loader that calls `get_instance_proc_addr`.
+    ///
+    /// # Safety
+    ///
+    /// - `get_instance_proc_addr` must behave like the `vkGetInstanceProcAddr` function of a valid
+    ///   Vulkan implementation.
+    ///
+    #[inline]
+    pub unsafe fn new(
+        get_instance_proc_addr: extern "system" fn(
+            instance: vk::Instance,
+            name: *const c_char,
+        ) -> extern "system" fn() -> (),
+    ) -> ProcAddrLoader {
+        ProcAddrLoader {
+            get_proc_addr: get_instance_proc_addr,
+        }
+    }
+}
+
+unsafe impl Loader for ProcAddrLoader {
+    #[inline]
+    fn get_instance_proc_addr(
+        &self,
+        instance: vk::Instance,
+        name: *const c_char,
+    ) -> extern "system" fn() -> () {
+        (self.get_proc_addr)(instance, name)
+    }
+}
+
 /// Wraps around a loader and contains function pointers.
 pub struct FunctionPointers<L> {
     loader: L,
@@ -137,6 +207,30 @@ impl<L> FunctionPointers<L> {
         &self.entry_points
     }
 
+    /// Returns the highest version of Vulkan that the loader supports for instances.
+    ///
+    /// Returns 1.0 if the loader predates Vulkan 1.1, which didn't have a way to query it.
+    pub fn api_version(&self) -> Result<Version, OomError>
+    where
+        L: Loader,
+    {
+        type Pfn = extern "system" fn(*mut u32) -> vk::Result;
+
+        unsafe {
+            let name = b"vkEnumerateInstanceVersion\0";
+            let ptr =
+                self.get_instance_proc_addr(0, name.as_ptr() as *const c_char) as *const c_void;
+            if ptr.is_null() {
+                return Ok(Version::V1_0);
+            }
+
+            let enumerate_instance_version: Pfn = mem::transmute(ptr);
+            let mut version = 0;
+            check_errors(enumerate_instance_version(&mut version))?;
+            Ok(Version::from_vulkan_version(version))
+        }
+    }
+
     /// Calls `get_instance_proc_addr` on the underlying loader.
     #[inline]
     pub fn get_instance_proc_addr(
@@ -159,23 +253,38 @@ impl<L> FunctionPointers<L> {
 ///
 /// This is provided as a macro and not as a regular function, because the macro contains an
 /// `extern {}` block.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate vulkano;
+/// use vulkano::instance::Instance;
+/// use vulkano::instance::InstanceExtensions;
+/// use vulkano::instance::loader::FunctionPointers;
+///
+/// # fn main() {
+/// let loader = statically_linked_vulkan_loader!();
+/// let instance = Instance::with_loader(FunctionPointers::new(Box::new(loader)), None,
+///                                      &InstanceExtensions::none(), None).unwrap();
+/// # }
+/// ```
 // TODO: should this be unsafe?
 #[macro_export]
 macro_rules! statically_linked_vulkan_loader {
     () => {{
         extern "C" {
             fn vkGetInstanceProcAddr(
-                instance: vk::Instance,
-                pName: *const c_char,
-            ) -> vk::PFN_vkVoidFunction;
+                instance: usize,
+                pName: *const ::std::os::raw::c_char,
+            ) -> extern "system" fn() -> ();
         }
 
         struct StaticallyLinkedVulkanLoader;
-        unsafe impl Loader for StaticallyLinkedVulkanLoader {
+        unsafe impl $crate::instance::loader::Loader for StaticallyLinkedVulkanLoader {
             fn get_instance_proc_addr(
                 &self,
-                instance: vk::Instance,
-                name: *const c_char,
+                instance: usize,
+                name: *const ::std::os::raw::c_char,
             ) -> extern "system" fn() -> () {
                 unsafe { vkGetInstanceProcAddr(instance, name) }
             }
@@ -194,7 +303,7 @@ pub fn auto_loader(
 ) -> Result<&'static FunctionPointers<Box<dyn Loader + Send + Sync>>, LoadingError> {
     #[cfg(target_os = "ios")]
     #[allow(non_snake_case)]
-    fn def_loader_impl() -> Result<Box<Loader + Send + Sync>, LoadingError> {
+    fn def_loader_impl() -> Result<Box<dyn Loader + Send + Sync>, LoadingError> {
         let loader = statically_linked_vulkan_loader!();
         Ok(Box::new(loader))
     }
@@ -274,7 +383,14 @@ impl fmt::Display for LoadingError {
 #[cfg(test)]
 mod tests {
     use crate::instance::loader::DynamicLibraryLoader;
+    use crate::instance::loader::FunctionPointers;
     use crate::instance::loader::LoadingError;
+    use crate::instance::loader::ProcAddrLoader;
+    use crate::instance::Version;
+    use crate::vk;
+    use std::ffi::CStr;
+    use std::mem;
+    use std::os::raw::c_char;
 
     #[test]
     fn dl_open_error() {
@@ -285,4 +401,35 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn proc_addr_loader_forwards() {
+        extern "system" fn unused() {}
+
+        extern "system" fn enumerate_instance_version(version: *mut u32) -> vk::Result {
+            unsafe { *version = Version::V1_2.into_vulkan_version() };
+            vk::SUCCESS
+        }
+
+        extern "system" fn get_instance_proc_addr(
+            _: vk::Instance,
+            name: *const c_char,
+        ) -> extern "system" fn() -> () {
+            let name = unsafe { CStr::from_ptr(name) };
+            if name.to_bytes() == b"vkEnumerateInstanceVersion" {
+                unsafe {
+                    mem::transmute::<
+                        extern "system" fn(*mut u32) -> vk::Result,
+                        extern "system" fn() -> (),
+                    >(enumerate_instance_version)
+                }
+            } else {
+                unused
+            }
+        }
+
+        let loader = unsafe { ProcAddrLoader::new(get_instance_proc_addr) };
+        let ptrs = FunctionPointers::new(loader);
+        assert_eq!(ptrs.api_version().unwrap(), Version::V1_2);
+    }
 }
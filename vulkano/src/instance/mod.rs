@@ -109,6 +109,8 @@
 pub use self::extensions::InstanceExtensions;
 pub use self::extensions::RawInstanceExtensions;
 pub use self::instance::ApplicationInfo;
+pub use self::instance::ConformanceVersion;
+pub use self::instance::DriverId;
 pub use self::instance::Instance;
 pub use self::instance::InstanceCreationError;
 pub use self::instance::MemoryHeap;
@@ -116,16 +118,27 @@ pub use self::instance::MemoryHeapsIter;
 pub use self::instance::MemoryType;
 pub use self::instance::MemoryTypesIter;
 pub use self::instance::PhysicalDevice;
+pub use self::instance::PhysicalDeviceGroup;
 pub use self::instance::PhysicalDeviceType;
 pub use self::instance::PhysicalDevicesIter;
 pub use self::instance::QueueFamiliesIter;
 pub use self::instance::QueueFamily;
+pub use self::instance::SubgroupFeatures;
+pub use self::instance::ValidationFeatures;
+pub use self::layer_settings::LayerSetting;
+pub use self::layer_settings::LayerSettingValue;
 pub use self::layers::layers_list;
 pub use self::layers::LayerProperties;
 pub use self::layers::LayersIterator;
 pub use self::layers::LayersListError;
 pub use self::limits::Limits;
 pub use self::loader::LoadingError;
+pub use self::selector::NoSuitablePhysicalDeviceError;
+pub use self::selector::PhysicalDeviceSelector;
+pub use self::selector::RejectedPhysicalDevice;
+pub use self::selector::RejectionReason;
+pub use self::tooling::ToolProperties;
+pub use self::tooling::ToolPurposes;
 pub use crate::version::Version;
 
 pub mod debug;
@@ -133,5 +146,8 @@ pub mod loader;
 
 mod extensions;
 mod instance;
+mod layer_settings;
 mod layers;
 mod limits;
+mod selector;
+mod tooling;
@@ -0,0 +1,382 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::device::DeviceExtensions;
+use crate::features::Features;
+use crate::instance::Instance;
+use crate::instance::PhysicalDevice;
+use crate::instance::PhysicalDeviceType;
+use crate::instance::QueueFamily;
+use crate::swapchain::Surface;
+
+/// Helper that chooses the physical device to use.
+///
+/// The physical devices that don't support the requirements are discarded. Among the remaining
+/// ones, discrete GPUs are preferred over integrated GPUs, which are preferred over virtual GPUs,
+/// then CPUs, then the other types of devices. Devices of the same type are ranked by the total
+/// size of their device-local memory heaps.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::device::DeviceExtensions;
+/// use vulkano::instance::Instance;
+/// use vulkano::instance::InstanceExtensions;
+/// use vulkano::instance::PhysicalDeviceSelector;
+///
+/// # let instance = match Instance::new(None, &InstanceExtensions::none(), None) {
+/// #     Ok(i) => i,
+/// #     Err(_) => return,
+/// # };
+/// let result = PhysicalDeviceSelector::new()
+///     .extensions(&DeviceExtensions {
+///         khr_storage_buffer_storage_class: true,
+///         .. DeviceExtensions::none()
+///     })
+///     .graphics()
+///     .select(&instance);
+///
+/// match result {
+///     Ok(physical) => println!("Using {}", physical.name()),
+///     Err(err) => println!("{}", err),
+/// }
+/// ```
+pub struct PhysicalDeviceSelector<'a> {
+    extensions: DeviceExtensions,
+    features: Features,
+    graphics: bool,
+    compute: bool,
+    sparse_binding: bool,
+    present: Vec<PresentCheck<'a>>,
+}
+
+// Returns whether a queue family can present to one of the required surfaces.
+type PresentCheck<'a> = Box<dyn Fn(QueueFamily) -> bool + 'a>;
+
+impl<'a> PhysicalDeviceSelector<'a> {
+    /// Builds a selector without any requirement.
+    #[inline]
+    pub fn new() -> PhysicalDeviceSelector<'a> {
+        PhysicalDeviceSelector {
+            extensions: DeviceExtensions::none(),
+            features: Features::none(),
+            graphics: false,
+            compute: false,
+            sparse_binding: false,
+            present: Vec::new(),
+        }
+    }
+
+    /// Requires the physical device to support the given extensions. Replaces the previously
+    /// required extensions.
+    #[inline]
+    pub fn extensions(mut self, extensions: &DeviceExtensions) -> Self {
+        self.extensions = *extensions;
+        self
+    }
+
+    /// Requires the physical device to support the given features. Replaces the previously
+    /// required features.
+    #[inline]
+    pub fn features(mut self, features: &Features) -> Self {
+        self.features = features.clone();
+        self
+    }
+
+    /// Requires the physical device to have a queue family that supports graphics operations.
+    #[inline]
+    pub fn graphics(mut self) -> Self {
+        self.graphics = true;
+        self
+    }
+
+    /// Requires the physical device to have a queue family that supports compute operations.
+    #[inline]
+    pub fn compute(mut self) -> Self {
+        self.compute = true;
+        self
+    }
+
+    /// Requires the physical device to have a queue family that supports sparse binding
+    /// operations.
+    #[inline]
+    pub fn sparse_binding(mut self) -> Self {
+        self.sparse_binding = true;
+        self
+    }
+
+    /// Requires the physical device to have a queue family that can present to `surface`.
+    ///
+    /// Can be called multiple times to require support for several surfaces.
+    #[inline]
+    pub fn present_to<W>(mut self, surface: &'a Surface<W>) -> Self
+    where
+        W: 'a,
+    {
+        self.present.push(Box::new(move |family| {
+            surface.is_supported(family).unwrap_or(false)
+        }));
+        self
+    }
+
+    /// Returns the best physical device of `instance` that satisfies the requirements.
+    ///
+    /// If no physical device qualifies, the returned error indicates why each of them was
+    /// rejected.
+    pub fn select<'i>(
+        &self,
+        instance: &'i Arc<Instance>,
+    ) -> Result<PhysicalDevice<'i>, NoSuitablePhysicalDeviceError> {
+        let mut rejected = Vec::new();
+        let mut best: Option<(PhysicalDevice, (u32, usize))> = None;
+
+        for physical in PhysicalDevice::enumerate(instance) {
+            let reasons = self.check(physical);
+
+            if !reasons.is_empty() {
+                rejected.push(RejectedPhysicalDevice {
+                    index: physical.index(),
+                    name: physical.name().to_owned(),
+                    reasons,
+                });
+                continue;
+            }
+
+            let score = score(physical);
+            match best {
+                Some((_, best_score)) if best_score >= score => (),
+                _ => best = Some((physical, score)),
+            }
+        }
+
+        match best {
+            Some((physical, _)) => Ok(physical),
+            None => Err(NoSuitablePhysicalDeviceError { rejected }),
+        }
+    }
+
+    // Returns the list of requirements that `physical` doesn't satisfy.
+    fn check(&self, physical: PhysicalDevice) -> Vec<RejectionReason> {
+        let mut reasons = Vec::new();
+
+        let missing_extensions = self
+            .extensions
+            .difference(&DeviceExtensions::supported_by_device(physical));
+        if missing_extensions != DeviceExtensions::none() {
+            reasons.push(RejectionReason::MissingExtensions(missing_extensions));
+        }
+
+        let missing_features = self.features.difference(physical.supported_features());
+        if missing_features != Features::none() {
+            reasons.push(RejectionReason::MissingFeatures(missing_features));
+        }
+
+        if self.graphics && !physical.queue_families().any(|q| q.supports_graphics()) {
+            reasons.push(RejectionReason::NoGraphicsQueue);
+        }
+
+        if self.compute && !physical.queue_families().any(|q| q.supports_compute()) {
+            reasons.push(RejectionReason::NoComputeQueue);
+        }
+
+        if self.sparse_binding
+            && !physical
+                .queue_families()
+                .any(|q| q.supports_sparse_binding())
+        {
+            reasons.push(RejectionReason::NoSparseBindingQueue);
+        }
+
+        for present in self.present.iter() {
+            if !physical.queue_families().any(present) {
+                reasons.push(RejectionReason::NoPresentQueue);
+                break;
+            }
+        }
+
+        reasons
+    }
+}
+
+impl<'a> Default for PhysicalDeviceSelector<'a> {
+    #[inline]
+    fn default() -> PhysicalDeviceSelector<'a> {
+        PhysicalDeviceSelector::new()
+    }
+}
+
+impl<'a> fmt::Debug for PhysicalDeviceSelector<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("PhysicalDeviceSelector")
+            .field("extensions", &self.extensions)
+            .field("features", &self.features)
+            .field("graphics", &self.graphics)
+            .field("compute", &self.compute)
+            .field("sparse_binding", &self.sparse_binding)
+            .field("present_surfaces", &self.present.len())
+            .finish()
+    }
+}
+
+// Returns the rank of a physical device that satisfies the requirements. Higher is better.
+fn score(physical: PhysicalDevice) -> (u32, usize) {
+    let ty = match physical.ty() {
+        PhysicalDeviceType::DiscreteGpu => 4,
+        PhysicalDeviceType::IntegratedGpu => 3,
+        PhysicalDeviceType::VirtualGpu => 2,
+        PhysicalDeviceType::Cpu => 1,
+        PhysicalDeviceType::Other => 0,
+    };
+
+    let memory = physical
+        .memory_heaps()
+        .filter(|h| h.is_device_local())
+        .map(|h| h.size())
+        .sum();
+
+    (ty, memory)
+}
+
+/// Error returned by `PhysicalDeviceSelector::select` when no physical device qualifies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoSuitablePhysicalDeviceError {
+    /// Physical devices that were rejected, with the reasons. Empty if the instance doesn't have
+    /// any physical device.
+    pub rejected: Vec<RejectedPhysicalDevice>,
+}
+
+impl error::Error for NoSuitablePhysicalDeviceError {}
+
+impl fmt::Display for NoSuitablePhysicalDeviceError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.rejected.is_empty() {
+            return write!(fmt, "no physical device is available");
+        }
+
+        write!(fmt, "no physical device satisfies the requirements")?;
+        for device in self.rejected.iter() {
+            write!(fmt, "; `{}` was rejected because ", device.name)?;
+            for (num, reason) in device.reasons.iter().enumerate() {
+                if num != 0 {
+                    write!(fmt, ", ")?;
+                }
+                write!(fmt, "{}", reason)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Physical device that doesn't satisfy the requirements of a `PhysicalDeviceSelector`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedPhysicalDevice {
+    /// Index of the physical device, as returned by `PhysicalDevice::index`.
+    pub index: usize,
+    /// Name of the physical device.
+    pub name: String,
+    /// Requirements that the physical device doesn't satisfy. Never empty.
+    pub reasons: Vec<RejectionReason>,
+}
+
+/// Requirement of a `PhysicalDeviceSelector` that a physical device doesn't satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// Some of the required extensions aren't supported.
+    MissingExtensions(DeviceExtensions),
+    /// Some of the required features aren't supported.
+    MissingFeatures(Features),
+    /// No queue family supports graphics operations.
+    NoGraphicsQueue,
+    /// No queue family supports compute operations.
+    NoComputeQueue,
+    /// No queue family supports sparse binding operations.
+    NoSparseBindingQueue,
+    /// No queue family can present to one of the surfaces.
+    NoPresentQueue,
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            RejectionReason::MissingExtensions(ref extensions) => {
+                write!(fmt, "the extensions {:?} aren't supported", extensions)
+            }
+            RejectionReason::MissingFeatures(_) => {
+                write!(fmt, "some of the required features aren't supported")
+            }
+            RejectionReason::NoGraphicsQueue => {
+                write!(fmt, "no queue family supports graphics operations")
+            }
+            RejectionReason::NoComputeQueue => {
+                write!(fmt, "no queue family supports compute operations")
+            }
+            RejectionReason::NoSparseBindingQueue => {
+                write!(fmt, "no queue family supports sparse binding operations")
+            }
+            RejectionReason::NoPresentQueue => {
+                write!(fmt, "no queue family can present to the surface")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::Features;
+    use crate::instance::PhysicalDevice;
+    use crate::instance::PhysicalDeviceSelector;
+    use crate::instance::RejectionReason;
+
+    #[test]
+    fn no_requirements() {
+        let instance = instance!();
+
+        let result = PhysicalDeviceSelector::new().select(&instance);
+        assert_eq!(
+            result.is_ok(),
+            PhysicalDevice::enumerate(&instance).next().is_some()
+        );
+    }
+
+    #[test]
+    fn unsupported_features() {
+        let instance = instance!();
+
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let unsupported = Features::all().difference(physical.supported_features());
+        if unsupported == Features::none() {
+            return;
+        }
+
+        let err = match PhysicalDeviceSelector::new()
+            .features(&Features::all())
+            .select(&instance)
+        {
+            Ok(_) => return, // Another device supports everything.
+            Err(err) => err,
+        };
+
+        let rejected = err
+            .rejected
+            .iter()
+            .find(|d| d.index == physical.index())
+            .unwrap();
+        assert!(rejected
+            .reasons
+            .contains(&RejectionReason::MissingFeatures(unsupported)));
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use crate::vk;
+
+/// Tool that is active on a physical device, such as a validation layer, a profiler or a frame
+/// capture tool. Returned by `PhysicalDevice::tool_properties`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ToolProperties {
+    /// Name of the tool.
+    pub name: String,
+
+    /// Version of the tool.
+    pub version: String,
+
+    /// What the tool does.
+    pub purposes: ToolPurposes,
+
+    /// Human-readable description of the tool.
+    pub description: String,
+
+    /// Name of the layer that implements the tool, or `None` if it isn't implemented by a layer.
+    pub layer: Option<String>,
+}
+
+impl ToolProperties {
+    pub(crate) fn from_vulkan(val: &vk::PhysicalDeviceToolPropertiesEXT) -> ToolProperties {
+        let layer = to_string(&val.layer);
+
+        ToolProperties {
+            name: to_string(&val.name),
+            version: to_string(&val.version),
+            purposes: ToolPurposes::from(val.purposes),
+            description: to_string(&val.description),
+            layer: if layer.is_empty() { None } else { Some(layer) },
+        }
+    }
+}
+
+/// What an active tool does.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ToolPurposes {
+    /// The tool validates the usage of the API.
+    pub validation: bool,
+    /// The tool reports profiling information.
+    pub profiling: bool,
+    /// The tool captures the API calls, for example to replay a frame later.
+    pub tracing: bool,
+    /// The tool provides additional extensions or features.
+    pub additional_features: bool,
+    /// The tool changes the behavior of the API, for example by hiding extensions or features.
+    pub modifying_features: bool,
+    /// The tool reports messages through a debug callback.
+    pub debug_reporting: bool,
+    /// The tool uses the debug names and labels of the objects, or provides debug markers.
+    pub debug_markers: bool,
+}
+
+impl ToolPurposes {
+    /// Builds a `ToolPurposes` with all the members set to `false`.
+    #[inline]
+    pub fn none() -> ToolPurposes {
+        ToolPurposes::default()
+    }
+}
+
+impl From<vk::ToolPurposeFlagsEXT> for ToolPurposes {
+    #[inline]
+    fn from(val: vk::ToolPurposeFlagsEXT) -> ToolPurposes {
+        ToolPurposes {
+            validation: (val & vk::TOOL_PURPOSE_VALIDATION_BIT_EXT) != 0,
+            profiling: (val & vk::TOOL_PURPOSE_PROFILING_BIT_EXT) != 0,
+            tracing: (val & vk::TOOL_PURPOSE_TRACING_BIT_EXT) != 0,
+            additional_features: (val & vk::TOOL_PURPOSE_ADDITIONAL_FEATURES_BIT_EXT) != 0,
+            modifying_features: (val & vk::TOOL_PURPOSE_MODIFYING_FEATURES_BIT_EXT) != 0,
+            debug_reporting: (val & vk::TOOL_PURPOSE_DEBUG_REPORTING_BIT_EXT) != 0,
+            debug_markers: (val & vk::TOOL_PURPOSE_DEBUG_MARKERS_BIT_EXT) != 0,
+        }
+    }
+}
+
+#[inline]
+fn to_string(val: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(val.as_ptr()).to_string_lossy().into_owned() }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::device::DeviceExtensions;
+    use crate::instance::PhysicalDevice;
+    use crate::instance::ToolPurposes;
+    use crate::vk;
+
+    #[test]
+    fn purposes_from_vulkan() {
+        let purposes = ToolPurposes::from(
+            vk::TOOL_PURPOSE_VALIDATION_BIT_EXT | vk::TOOL_PURPOSE_DEBUG_REPORTING_BIT_EXT,
+        );
+        assert_eq!(
+            purposes,
+            ToolPurposes {
+                validation: true,
+                debug_reporting: true,
+                ..ToolPurposes::none()
+            }
+        );
+    }
+
+    #[test]
+    fn query_tools() {
+        let instance = instance!();
+
+        for physical in PhysicalDevice::enumerate(&instance) {
+            if !DeviceExtensions::supported_by_device(physical).ext_tooling_info {
+                continue;
+            }
+
+            for tool in physical.tool_properties().unwrap() {
+                assert!(!tool.name.is_empty());
+            }
+        }
+    }
+}
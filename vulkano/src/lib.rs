@@ -63,6 +63,9 @@
 #![allow(unused_variables)] // TODO: remove
 
 extern crate vk_sys as vk;
+// Lets the code generated by the derive macros refer to `::vulkano` in the tests of this crate.
+#[cfg(test)]
+extern crate self as vulkano;
 
 pub use half;
 use std::error;
@@ -75,6 +78,7 @@ use std::sync::MutexGuard;
 mod tests;
 #[macro_use]
 mod extensions;
+pub mod alloc;
 pub mod buffer;
 pub mod command_buffer;
 pub mod descriptor;
@@ -185,6 +189,7 @@ enum Success {
     EventReset = vk::EVENT_RESET,
     Incomplete = vk::INCOMPLETE,
     Suboptimal = vk::SUBOPTIMAL_KHR,
+    PipelineCompileRequired = vk::PIPELINE_COMPILE_REQUIRED_EXT,
 }
 
 /// All possible errors returned by any Vulkan function.
@@ -213,6 +218,7 @@ pub(crate) enum Error {
     ValidationFailed = vk::ERROR_VALIDATION_FAILED_EXT,
     OutOfPoolMemory = vk::ERROR_OUT_OF_POOL_MEMORY_KHR,
     FullscreenExclusiveLost = vk::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT,
+    InvalidExternalHandle = vk::ERROR_INVALID_EXTERNAL_HANDLE,
 }
 
 /// Checks whether the result returned correctly.
@@ -238,11 +244,13 @@ fn check_errors(result: vk::Result) -> Result<Success, Error> {
         vk::ERROR_SURFACE_LOST_KHR => Err(Error::SurfaceLost),
         vk::ERROR_NATIVE_WINDOW_IN_USE_KHR => Err(Error::NativeWindowInUse),
         vk::SUBOPTIMAL_KHR => Ok(Success::Suboptimal),
+        vk::PIPELINE_COMPILE_REQUIRED_EXT => Ok(Success::PipelineCompileRequired),
         vk::ERROR_OUT_OF_DATE_KHR => Err(Error::OutOfDate),
         vk::ERROR_INCOMPATIBLE_DISPLAY_KHR => Err(Error::IncompatibleDisplay),
         vk::ERROR_VALIDATION_FAILED_EXT => Err(Error::ValidationFailed),
         vk::ERROR_OUT_OF_POOL_MEMORY_KHR => Err(Error::OutOfPoolMemory),
         vk::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => Err(Error::FullscreenExclusiveLost),
+        vk::ERROR_INVALID_EXTERNAL_HANDLE => Err(Error::InvalidExternalHandle),
         vk::ERROR_INVALID_SHADER_NV => panic!(
             "Vulkan function returned \
                                                VK_ERROR_INVALID_SHADER_NV"
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::cmp;
 use std::error;
 use std::fmt;
 use std::marker::PhantomData;
@@ -16,6 +17,7 @@ use std::ops::DerefMut;
 use std::ops::Range;
 use std::os::raw::c_void;
 use std::ptr;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -36,28 +38,14 @@ use crate::Error;
 use crate::OomError;
 use crate::VulkanObject;
 
-pub struct BaseOutStructure {
-    pub s_type: i32,
-    pub p_next: *mut BaseOutStructure,
-}
-
-pub(crate) unsafe fn ptr_chain_iter<T>(ptr: &mut T) -> impl Iterator<Item = *mut BaseOutStructure> {
-    let ptr: *mut BaseOutStructure = ptr as *mut T as _;
-    (0..).scan(ptr, |p_ptr, _| {
-        if p_ptr.is_null() {
-            return None;
-        }
-        let n_ptr = (**p_ptr).p_next as *mut BaseOutStructure;
-        let old = *p_ptr;
-        *p_ptr = n_ptr;
-        Some(old)
-    })
-}
-
-pub unsafe trait ExtendsMemoryAllocateInfo {}
-unsafe impl ExtendsMemoryAllocateInfo for vk::MemoryDedicatedAllocateInfoKHR {}
-unsafe impl ExtendsMemoryAllocateInfo for vk::ExportMemoryAllocateInfo {}
-unsafe impl ExtendsMemoryAllocateInfo for vk::ImportMemoryFdInfoKHR {}
+// The handle types that are exported and imported with `VK_KHR_external_memory_win32`.
+const WIN32_HANDLE_TYPES: vk::ExternalMemoryHandleTypeFlagBits =
+    vk::EXTERNAL_MEMORY_HANDLE_TYPE_OPAQUE_WIN32_BIT
+        | vk::EXTERNAL_MEMORY_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT
+        | vk::EXTERNAL_MEMORY_HANDLE_TYPE_D3D11_TEXTURE_BIT
+        | vk::EXTERNAL_MEMORY_HANDLE_TYPE_D3D11_TEXTURE_KMT_BIT
+        | vk::EXTERNAL_MEMORY_HANDLE_TYPE_D3D12_HEAP_BIT
+        | vk::EXTERNAL_MEMORY_HANDLE_TYPE_D3D12_RESOURCE_BIT;
 
 /// Represents memory that has been allocated.
 ///
@@ -102,6 +90,9 @@ pub struct DeviceMemoryBuilder<'a> {
     dedicated_info: Option<vk::MemoryDedicatedAllocateInfoKHR>,
     export_info: Option<vk::ExportMemoryAllocateInfo>,
     import_info: Option<vk::ImportMemoryFdInfoKHR>,
+    host_pointer_info: Option<vk::ImportMemoryHostPointerInfoEXT>,
+    priority_info: Option<vk::MemoryPriorityAllocateInfoEXT>,
+    flags_info: Option<vk::MemoryAllocateFlagsInfo>,
     marker: PhantomData<&'a ()>,
 }
 
@@ -122,6 +113,9 @@ impl<'a> DeviceMemoryBuilder<'a> {
             dedicated_info: None,
             export_info: None,
             import_info: None,
+            host_pointer_info: None,
+            priority_info: None,
+            flags_info: None,
             marker: PhantomData,
         }
     }
@@ -136,7 +130,7 @@ impl<'a> DeviceMemoryBuilder<'a> {
     pub fn dedicated_info(mut self, dedicated: DedicatedAlloc<'a>) -> DeviceMemoryBuilder {
         assert!(self.dedicated_info.is_none());
 
-        let mut dedicated_info = match dedicated {
+        let dedicated_info = match dedicated {
             DedicatedAlloc::Buffer(buffer) => vk::MemoryDedicatedAllocateInfoKHR {
                 sType: vk::STRUCTURE_TYPE_MEMORY_DEDICATED_ALLOCATE_INFO_KHR,
                 pNext: ptr::null(),
@@ -152,7 +146,6 @@ impl<'a> DeviceMemoryBuilder<'a> {
             DedicatedAlloc::None => return self,
         };
 
-        self = self.push_next(&mut dedicated_info);
         self.dedicated_info = Some(dedicated_info);
         self
     }
@@ -168,13 +161,12 @@ impl<'a> DeviceMemoryBuilder<'a> {
     ) -> DeviceMemoryBuilder<'a> {
         assert!(self.export_info.is_none());
 
-        let mut export_info = vk::ExportMemoryAllocateInfo {
+        let export_info = vk::ExportMemoryAllocateInfo {
             sType: vk::STRUCTURE_TYPE_EXPORT_MEMORY_ALLOCATE_INFO,
             pNext: ptr::null(),
             handleTypes: handle_types.to_bits(),
         };
 
-        self = self.push_next(&mut export_info);
         self.export_info = Some(export_info);
         self
     }
@@ -191,34 +183,121 @@ impl<'a> DeviceMemoryBuilder<'a> {
         handle_types: ExternalMemoryHandleType,
     ) -> DeviceMemoryBuilder<'a> {
         assert!(self.import_info.is_none());
+        assert!(self.host_pointer_info.is_none());
 
-        let mut import_info = vk::ImportMemoryFdInfoKHR {
+        let import_info = vk::ImportMemoryFdInfoKHR {
             sType: vk::STRUCTURE_TYPE_IMPORT_MEMORY_FD_INFO_KHR,
             pNext: ptr::null(),
             handleType: handle_types.to_bits(),
             fd: fd.into_raw_fd(),
         };
 
-        self = self.push_next(&mut import_info);
         self.import_info = Some(import_info);
         self
     }
 
-    // Private function -- no doc comment needed!  Copied shamelessly and poorly from Ash.
-    fn push_next<T: ExtendsMemoryAllocateInfo>(mut self, next: &mut T) -> DeviceMemoryBuilder<'a> {
-        unsafe {
-            let next_ptr = next as *mut T as *mut BaseOutStructure;
-            let last_next = ptr_chain_iter(&mut self.allocate.pNext).last().unwrap();
-            (*last_next).p_next = next_ptr as _;
-        }
+    /// Sets an optional field to import an existing host allocation in the `DeviceMemoryBuilder`.
+    ///
+    /// `handle_type` must be either `host_allocation` or `host_mapped_foreign_memory`. The
+    /// pointer and the size of the allocation must be multiples of
+    /// `min_imported_host_pointer_alignment`, and the memory type must be one of those returned
+    /// by `DeviceMemory::host_pointer_memory_type_bits`.
+    ///
+    /// # Safety
+    ///
+    /// - `pointer` must point to a host allocation of at least the size of the device memory,
+    ///   which must stay valid until the device memory has been destroyed.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if an import info has already been set.
+    /// - Panics if `handle_type` isn't a single host handle type.
+    ///
+    pub unsafe fn import_host_pointer(
+        mut self,
+        pointer: *mut c_void,
+        handle_type: ExternalMemoryHandleType,
+    ) -> DeviceMemoryBuilder<'a> {
+        assert!(self.import_info.is_none());
+        assert!(self.host_pointer_info.is_none());
 
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_MEMORY_HANDLE_TYPE_HOST_ALLOCATION_BIT_EXT
+                || bits == vk::EXTERNAL_MEMORY_HANDLE_TYPE_HOST_MAPPED_FOREIGN_MEMORY_BIT_EXT,
+            "the handle type must be either host_allocation or host_mapped_foreign_memory"
+        );
+
+        self.host_pointer_info = Some(vk::ImportMemoryHostPointerInfoEXT {
+            sType: vk::STRUCTURE_TYPE_IMPORT_MEMORY_HOST_POINTER_INFO_EXT,
+            pNext: ptr::null(),
+            handleType: bits,
+            pHostPointer: pointer,
+        });
+        self
+    }
+
+    /// Sets the priority of the allocation, between 0.0 and 1.0. The default priority is 0.5.
+    ///
+    /// When the device runs out of memory, the allocations with a lower priority are moved to
+    /// system memory first. Use a high priority for the resources that are accessed the most,
+    /// such as render targets. Requires the `ext_memory_priority` extension, and has no effect
+    /// unless the `memory_priority` feature is enabled.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the priority has already been set.
+    /// - Panics if `priority` is not between 0.0 and 1.0.
+    ///
+    pub fn priority(mut self, priority: f32) -> DeviceMemoryBuilder<'a> {
+        assert!(self.priority_info.is_none());
+        assert!(
+            (0.0..=1.0).contains(&priority),
+            "the priority must be between 0.0 and 1.0"
+        );
+
+        self.priority_info = Some(vk::MemoryPriorityAllocateInfoEXT {
+            sType: vk::STRUCTURE_TYPE_MEMORY_PRIORITY_ALLOCATE_INFO_EXT,
+            pNext: ptr::null(),
+            priority,
+        });
+        self
+    }
+
+    /// Sets the physical devices of the device group on which the memory is allocated. Bit `i` of
+    /// the mask corresponds to the physical device `i` of `Device::device_group`.
+    ///
+    /// Requires the `khr_device_group` extension.
+    ///
+    /// By default, the memory is allocated on all the physical devices. Unless the group
+    /// supports `PhysicalDeviceGroup::subset_allocation`, device-local memory is still allocated
+    /// on all of them.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device mask has already been set.
+    /// - Panics if `mask` isn't a valid device mask for the device.
+    ///
+    pub fn device_mask(mut self, mask: u32) -> DeviceMemoryBuilder<'a> {
+        assert!(self.flags_info.is_none());
+        assert!(
+            self.device.is_valid_device_mask(mask),
+            "the device mask must only contain physical devices of the device group"
+        );
+
+        self.flags_info = Some(vk::MemoryAllocateFlagsInfo {
+            sType: vk::STRUCTURE_TYPE_MEMORY_ALLOCATE_FLAGS_INFO,
+            pNext: ptr::null(),
+            flags: vk::MEMORY_ALLOCATE_DEVICE_MASK_BIT,
+            deviceMask: mask,
+        });
         self
     }
 
     /// Creates a `DeviceMemory` object on success, consuming the `DeviceMemoryBuilder`.  An error
     /// is returned if the requested allocation is too large or if the total number of allocations
     /// would exceed per-device limits.
-    pub fn build(self) -> Result<Arc<DeviceMemory>, DeviceMemoryAllocError> {
+    pub fn build(mut self) -> Result<Arc<DeviceMemory>, DeviceMemoryAllocError> {
         if self.allocate.allocationSize == 0 {
             return Err(DeviceMemoryAllocError::InvalidSize)?;
         }
@@ -255,24 +334,87 @@ impl<'a> DeviceMemoryBuilder<'a> {
             return Err(DeviceMemoryAllocError::SpecViolation(1713));
         }
 
+        // VUID-vkAllocateMemory-pAllocateInfo-01872: "If the protectedMemory feature is not
+        // enabled, the pAllocateInfo->memoryTypeIndex must not indicate a memory type that reports
+        // VK_MEMORY_PROPERTY_PROTECTED_BIT."
+        if memory_type.is_protected() && !self.device.enabled_features().protected_memory {
+            return Err(DeviceMemoryAllocError::SpecViolation(1872));
+        }
+
         let mut export_handle_bits = 0;
         if self.dedicated_info.is_some() {
-            if !self.device.loaded_extensions().khr_dedicated_allocation {
+            if !self
+                .device
+                .loaded_extensions_or_core()
+                .khr_dedicated_allocation
+            {
                 return Err(DeviceMemoryAllocError::MissingExtension(
                     "khr_dedicated_allocation",
                 ));
             }
         }
 
+        if self.priority_info.is_some() && !self.device.loaded_extensions().ext_memory_priority {
+            return Err(DeviceMemoryAllocError::MissingExtension(
+                "ext_memory_priority",
+            ));
+        }
+
+        if self.flags_info.is_some() && !self.device.loaded_extensions_or_core().khr_device_group {
+            return Err(DeviceMemoryAllocError::MissingExtension("khr_device_group"));
+        }
+
+        if let Some(ref host_pointer_info) = self.host_pointer_info {
+            if !self.device.loaded_extensions().ext_external_memory_host {
+                return Err(DeviceMemoryAllocError::MissingExtension(
+                    "ext_external_memory_host",
+                ));
+            }
+
+            let alignment = self
+                .device
+                .physical_device()
+                .extended_properties()
+                .min_imported_host_pointer_alignment()
+                .unwrap_or(1);
+
+            // VUID-VkImportMemoryHostPointerInfoEXT-pHostPointer-01749: "pHostPointer must be a
+            // pointer aligned to an integer multiple of
+            // VkPhysicalDeviceExternalMemoryHostPropertiesEXT::minImportedHostPointerAlignment".
+            if !(host_pointer_info.pHostPointer as u64).is_multiple_of(alignment) {
+                return Err(DeviceMemoryAllocError::SpecViolation(1749));
+            }
+
+            // VUID-VkMemoryAllocateInfo-allocationSize-01745: "the allocationSize member must be
+            // an integer multiple of
+            // VkPhysicalDeviceExternalMemoryHostPropertiesEXT::minImportedHostPointerAlignment".
+            if !self.allocate.allocationSize.is_multiple_of(alignment) {
+                return Err(DeviceMemoryAllocError::SpecViolation(1745));
+            }
+
+            // VUID-VkMemoryAllocateInfo-memoryTypeIndex-01744: "the memoryTypeIndex member must be
+            // one of the memory types returned by vkGetMemoryHostPointerPropertiesEXT".
+            let memory_type_bits = unsafe {
+                get_host_pointer_memory_type_bits(
+                    &self.device,
+                    host_pointer_info.pHostPointer,
+                    host_pointer_info.handleType,
+                )?
+            };
+            if memory_type_bits & (1 << self.allocate.memoryTypeIndex) == 0 {
+                return Err(DeviceMemoryAllocError::SpecViolation(1744));
+            }
+        }
+
         if self.export_info.is_some() || self.import_info.is_some() {
             // TODO: check exportFromImportedHandleTypes
             export_handle_bits = match self.export_info {
-                Some(export_info) => export_info.handleTypes,
+                Some(ref export_info) => export_info.handleTypes,
                 None => 0,
             };
 
             let import_handle_bits = match self.import_info {
-                Some(import_info) => import_info.handleType,
+                Some(ref import_info) => import_info.handleType,
                 None => 0,
             };
 
@@ -307,29 +449,76 @@ impl<'a> DeviceMemoryBuilder<'a> {
                     ));
                 }
             }
+
+            if (export_handle_bits | import_handle_bits) & WIN32_HANDLE_TYPES != 0
+                && !self.device.loaded_extensions().khr_external_memory_win32
+            {
+                return Err(DeviceMemoryAllocError::MissingExtension(
+                    "khr_external_memory_win32",
+                ));
+            }
+        }
+
+        // The structures are chained here rather than when they are set, because the builder is
+        // moved around until then.
+        let mut next: *const c_void = ptr::null();
+        if let Some(ref mut info) = self.dedicated_info {
+            info.pNext = next;
+            next = info as *const _ as *const _;
+        }
+        if let Some(ref mut info) = self.export_info {
+            info.pNext = next;
+            next = info as *const _ as *const _;
+        }
+        if let Some(ref mut info) = self.import_info {
+            info.pNext = next;
+            next = info as *const _ as *const _;
+        }
+        if let Some(ref mut info) = self.host_pointer_info {
+            info.pNext = next;
+            next = info as *const _ as *const _;
+        }
+        if let Some(ref mut info) = self.priority_info {
+            info.pNext = next;
+            next = info as *const _ as *const _;
         }
+        if let Some(ref mut info) = self.flags_info {
+            info.pNext = next;
+            next = info as *const _ as *const _;
+        }
+        self.allocate.pNext = next;
 
         let memory = unsafe {
             let physical_device = self.device.physical_device();
-            let mut allocation_count = self
-                .device
-                .allocation_count()
-                .lock()
-                .expect("Poisoned mutex");
-
-            if *allocation_count >= physical_device.limits().max_memory_allocation_count() {
+            let max_allocation_count = physical_device.limits().max_memory_allocation_count();
+            let allocation_count = self.device.allocation_count();
+
+            // The allocation is counted before calling `vkAllocateMemory`, so that concurrent
+            // allocations can't exceed the limit, and uncounted if it fails.
+            if allocation_count
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                    if count < max_allocation_count {
+                        Some(count + 1)
+                    } else {
+                        None
+                    }
+                })
+                .is_err()
+            {
                 return Err(DeviceMemoryAllocError::TooManyObjects);
             }
             let vk = self.device.pointers();
 
             let mut output = MaybeUninit::uninit();
-            check_errors(vk.AllocateMemory(
+            if let Err(err) = check_errors(vk.AllocateMemory(
                 self.device.internal_object(),
                 &self.allocate,
-                ptr::null(),
+                self.device.alloc_ptr(),
                 output.as_mut_ptr(),
-            ))?;
-            *allocation_count += 1;
+            )) {
+                allocation_count.fetch_sub(1, Ordering::SeqCst);
+                return Err(err.into());
+            }
             output.assume_init()
         };
 
@@ -463,6 +652,89 @@ impl DeviceMemory {
         Ok(Arc::try_unwrap(memory).unwrap())
     }
 
+    /// Same as `dedicated_alloc`, but the memory can be exported with any of the given handle
+    /// types.
+    ///
+    /// The buffer or image passed in `resource` must have been created with the same handle
+    /// types, see `UnsafeBuffer::new_with_external_memory` and
+    /// `UnsafeImage::new_with_external_memory`. Some implementations require exported memory to
+    /// be a dedicated allocation, which is why a resource can be passed.
+    #[inline]
+    pub fn dedicated_alloc_with_exportable(
+        device: Arc<Device>,
+        memory_type: MemoryType,
+        size: usize,
+        resource: DedicatedAlloc,
+        handle_types: ExternalMemoryHandleType,
+    ) -> Result<DeviceMemory, DeviceMemoryAllocError> {
+        let memory = DeviceMemoryBuilder::new(device, memory_type.id(), size)
+            .export_info(handle_types)
+            .dedicated_info(resource)
+            .build()?;
+
+        // Will never panic because we call the DeviceMemoryBuilder internally, and that only
+        // returns an atomically refcounted DeviceMemory object on success.
+        Ok(Arc::try_unwrap(memory).unwrap())
+    }
+
+    /// Imports an existing host allocation as device memory, without copying it.
+    ///
+    /// Buffers that are bound to the memory must have been created with the `host_allocation`
+    /// handle type, see `UnsafeBuffer::new_with_external_memory`. The memory type must be one of
+    /// those returned by `host_pointer_memory_type_bits`.
+    ///
+    /// # Safety
+    ///
+    /// - `pointer` must point to a host allocation of at least `size` bytes, which must stay
+    ///   valid until the returned memory has been destroyed.
+    ///
+    #[inline]
+    pub unsafe fn import_host_pointer(
+        device: Arc<Device>,
+        memory_type: MemoryType,
+        pointer: *mut c_void,
+        size: usize,
+    ) -> Result<DeviceMemory, DeviceMemoryAllocError> {
+        let memory = DeviceMemoryBuilder::new(device, memory_type.id(), size)
+            .import_host_pointer(
+                pointer,
+                ExternalMemoryHandleType {
+                    host_allocation: true,
+                    ..ExternalMemoryHandleType::none()
+                },
+            )
+            .build()?;
+
+        // Will never panic because we call the DeviceMemoryBuilder internally, and that only
+        // returns an atomically refcounted DeviceMemory object on success.
+        Ok(Arc::try_unwrap(memory).unwrap())
+    }
+
+    /// Returns the memory types that a host allocation can be imported into, as a bitmask where
+    /// bit `i` corresponds to the memory type whose id is `i`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_external_memory_host` extension isn't loaded.
+    ///
+    pub fn host_pointer_memory_type_bits(
+        device: &Device,
+        pointer: *const c_void,
+    ) -> Result<u32, DeviceMemoryAllocError> {
+        assert!(
+            device.loaded_extensions().ext_external_memory_host,
+            "the ext_external_memory_host extension must be loaded"
+        );
+
+        unsafe {
+            get_host_pointer_memory_type_bits(
+                device,
+                pointer as *mut _,
+                vk::EXTERNAL_MEMORY_HANDLE_TYPE_HOST_ALLOCATION_BIT_EXT,
+            )
+        }
+    }
+
     /// Same as `alloc_and_map`, but allows exportable file descriptor on Linux.
     #[inline]
     #[cfg(target_os = "linux")]
@@ -542,6 +814,31 @@ impl DeviceMemory {
         self.size
     }
 
+    /// Returns the handle types that the memory can be exported with.
+    #[inline]
+    pub fn export_handle_types(&self) -> ExternalMemoryHandleType {
+        self.handle_types
+    }
+
+    /// Changes the priority of the memory, between 0.0 and 1.0, after it has been allocated.
+    ///
+    /// See `DeviceMemoryBuilder::priority`. When the memory comes from a pool, the priority
+    /// applies to the whole block, and therefore to the other resources of the block as well.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `pageable_device_local_memory` feature is not enabled on the device.
+    /// - Panics if `priority` is not between 0.0 and 1.0.
+    ///
+    pub fn set_priority(&self, priority: f32) {
+        check_priority(&self.device, priority);
+
+        unsafe {
+            let vk = self.device.pointers();
+            vk.SetDeviceMemoryPriorityEXT(self.device.internal_object(), self.memory, priority);
+        }
+    }
+
     /// Exports the device memory into a Unix file descriptor.  The caller retains ownership of the
     /// file, as per the Vulkan spec.
     ///
@@ -591,6 +888,70 @@ impl DeviceMemory {
         let file = unsafe { File::from_raw_fd(fd) };
         Ok(file)
     }
+
+    /// Exports the device memory into a Windows handle.
+    ///
+    /// `handle_type` must be a single Windows handle type. The caller owns the returned handle
+    /// and must close it with `CloseHandle`, except for the `opaque_win32_kmt` and
+    /// `d3d11_texture_kmt` handle types.
+    #[inline]
+    #[cfg(windows)]
+    pub fn export_win32_handle(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+    ) -> Result<*mut c_void, DeviceMemoryAllocError> {
+        let vk = self.device.pointers();
+
+        // VUID-VkMemoryGetWin32HandleInfoKHR-handleType-00664: "handleType must be defined as an
+        // NT handle or a global share handle".
+        let bits = handle_type.to_bits();
+        if bits.count_ones() != 1 || bits & WIN32_HANDLE_TYPES == 0 {
+            return Err(DeviceMemoryAllocError::SpecViolation(664))?;
+        }
+
+        // VUID-VkMemoryGetWin32HandleInfoKHR-handleType-00662: "handleType must have been
+        // included in VkExportMemoryAllocateInfo::handleTypes when memory was created".
+        if bits & self.handle_types.to_bits() == 0 {
+            return Err(DeviceMemoryAllocError::SpecViolation(662))?;
+        }
+
+        unsafe {
+            let info = vk::MemoryGetWin32HandleInfoKHR {
+                sType: vk::STRUCTURE_TYPE_MEMORY_GET_WIN32_HANDLE_INFO_KHR,
+                pNext: ptr::null(),
+                memory: self.memory,
+                handleType: bits,
+            };
+
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.GetMemoryWin32HandleKHR(
+                self.device.internal_object(),
+                &info,
+                output.as_mut_ptr(),
+            ))?;
+            Ok(output.assume_init())
+        }
+    }
+}
+
+unsafe fn get_host_pointer_memory_type_bits(
+    device: &Device,
+    pointer: *mut c_void,
+    handle_type: vk::ExternalMemoryHandleTypeFlagBits,
+) -> Result<u32, DeviceMemoryAllocError> {
+    let vk = device.pointers();
+    let mut output = vk::MemoryHostPointerPropertiesEXT {
+        sType: vk::STRUCTURE_TYPE_MEMORY_HOST_POINTER_PROPERTIES_EXT,
+        pNext: ptr::null_mut(),
+        memoryTypeBits: 0,
+    };
+    check_errors(vk.GetMemoryHostPointerPropertiesEXT(
+        device.internal_object(),
+        handle_type,
+        pointer,
+        &mut output,
+    ))?;
+    Ok(output.memoryTypeBits)
 }
 
 unsafe impl DeviceOwned for DeviceMemory {
@@ -626,17 +987,30 @@ impl Drop for DeviceMemory {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.FreeMemory(self.device.internal_object(), self.memory, ptr::null());
-            let mut allocation_count = self
-                .device
+            vk.FreeMemory(
+                self.device.internal_object(),
+                self.memory,
+                self.device.alloc_ptr(),
+            );
+            self.device
                 .allocation_count()
-                .lock()
-                .expect("Poisoned mutex");
-            *allocation_count -= 1;
+                .fetch_sub(1, Ordering::SeqCst);
         }
     }
 }
 
+// Checks the requirements of `DeviceMemory::set_priority`.
+pub(crate) fn check_priority(device: &Device, priority: f32) {
+    assert!(
+        device.enabled_features().pageable_device_local_memory,
+        "the pageable_device_local_memory feature must be enabled"
+    );
+    assert!(
+        (0.0..=1.0).contains(&priority),
+        "the priority must be between 0.0 and 1.0"
+    );
+}
+
 /// Represents memory that has been allocated and mapped in CPU accessible space.
 ///
 /// Can be obtained with `DeviceMemory::alloc_and_map`. The function will panic if the memory type
@@ -710,25 +1084,14 @@ impl MappedDeviceMemory {
     where
         T: Content,
     {
-        let vk = self.memory.device().pointers();
         let pointer = T::ref_from_ptr(
             (self.pointer as usize + range.start) as *mut _,
             range.end - range.start,
         )
         .unwrap(); // TODO: error
 
-        if !self.coherent {
-            let range = vk::MappedMemoryRange {
-                sType: vk::STRUCTURE_TYPE_MAPPED_MEMORY_RANGE,
-                pNext: ptr::null(),
-                memory: self.memory.internal_object(),
-                offset: range.start as u64,
-                size: (range.end - range.start) as u64,
-            };
-
-            // TODO: check result?
-            vk.InvalidateMappedMemoryRanges(self.memory.device().internal_object(), 1, &range);
-        }
+        // TODO: check result?
+        let _ = self.invalidate_range(range.clone());
 
         CpuAccess {
             pointer: pointer,
@@ -737,6 +1100,96 @@ impl MappedDeviceMemory {
             range: range,
         }
     }
+
+    /// Makes the writes that the CPU has made to `range` visible to the device.
+    ///
+    /// Does nothing if the memory is host-coherent. Otherwise, the range is extended to
+    /// multiples of the `non_coherent_atom_size` limit of the physical device, as required by
+    /// Vulkan. `read_write` already calls this function when the access is dropped.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `range` is out of the bounds of the memory.
+    ///
+    pub fn flush_range(&self, range: Range<usize>) -> Result<(), OomError> {
+        assert!(range.start <= range.end && range.end <= self.memory.size());
+
+        if self.coherent {
+            return Ok(());
+        }
+
+        let range = self.atom_aligned_range(range);
+
+        unsafe {
+            let vk = self.memory.device().pointers();
+            check_errors(vk.FlushMappedMemoryRanges(
+                self.memory.device().internal_object(),
+                1,
+                &range,
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Makes the writes that the device has made to `range` visible to the CPU.
+    ///
+    /// Does nothing if the memory is host-coherent. Otherwise, the range is extended to
+    /// multiples of the `non_coherent_atom_size` limit of the physical device, as required by
+    /// Vulkan. `read_write` already calls this function before giving access to the memory.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `range` is out of the bounds of the memory.
+    ///
+    /// # Safety
+    ///
+    /// - The writes that the CPU has made to the extended range and that haven't been flushed
+    ///   yet may be discarded.
+    ///
+    pub unsafe fn invalidate_range(&self, range: Range<usize>) -> Result<(), OomError> {
+        assert!(range.start <= range.end && range.end <= self.memory.size());
+
+        if self.coherent {
+            return Ok(());
+        }
+
+        let range = self.atom_aligned_range(range);
+
+        let vk = self.memory.device().pointers();
+        check_errors(vk.InvalidateMappedMemoryRanges(
+            self.memory.device().internal_object(),
+            1,
+            &range,
+        ))?;
+
+        Ok(())
+    }
+
+    // VUID-VkMappedMemoryRange-offset-00687 and VUID-VkMappedMemoryRange-size-01390: the offset
+    // must be a multiple of `nonCoherentAtomSize`, and the size must be a multiple of it as well
+    // unless the range ends at the end of the memory.
+    fn atom_aligned_range(&self, range: Range<usize>) -> vk::MappedMemoryRange {
+        let atom_size = self
+            .memory
+            .device()
+            .physical_device()
+            .limits()
+            .non_coherent_atom_size() as usize;
+        let start = range.start / atom_size * atom_size;
+        let end = cmp::min(
+            range.end.div_ceil(atom_size) * atom_size,
+            self.memory.size(),
+        );
+
+        vk::MappedMemoryRange {
+            sType: vk::STRUCTURE_TYPE_MAPPED_MEMORY_RANGE,
+            pNext: ptr::null(),
+            memory: self.memory.internal_object(),
+            offset: start as u64,
+            size: (end - start) as u64,
+        }
+    }
 }
 
 impl AsRef<DeviceMemory> for MappedDeviceMemory {
@@ -948,22 +1401,8 @@ impl<'a, T: ?Sized + 'a> Drop for CpuAccess<'a, T> {
     #[inline]
     fn drop(&mut self) {
         // If the memory doesn't have the `coherent` flag, we need to flush the data.
-        if !self.coherent {
-            let vk = self.mem.as_ref().device().pointers();
-
-            let range = vk::MappedMemoryRange {
-                sType: vk::STRUCTURE_TYPE_MAPPED_MEMORY_RANGE,
-                pNext: ptr::null(),
-                memory: self.mem.as_ref().internal_object(),
-                offset: self.range.start as u64,
-                size: (self.range.end - self.range.start) as u64,
-            };
-
-            // TODO: check result?
-            unsafe {
-                vk.FlushMappedMemoryRanges(self.mem.as_ref().device().internal_object(), 1, &range);
-            }
-        }
+        // TODO: check result?
+        let _ = self.mem.flush_range(self.range.clone());
     }
 }
 
@@ -988,6 +1427,8 @@ pub enum DeviceMemoryAllocError {
     MissingExtension(&'static str),
     /// Invalid Size
     InvalidSize,
+    /// The imported handle or host pointer is not valid.
+    InvalidExternalHandle,
 }
 
 impl error::Error for DeviceMemoryAllocError {
@@ -1023,6 +1464,9 @@ impl fmt::Display for DeviceMemoryAllocError {
                 write!(fmt, "Implicit spec violation failed {}", e)
             }
             DeviceMemoryAllocError::InvalidSize => write!(fmt, "invalid size"),
+            DeviceMemoryAllocError::InvalidExternalHandle => {
+                write!(fmt, "the imported handle or host pointer is not valid")
+            }
         }
     }
 }
@@ -1036,6 +1480,7 @@ impl From<Error> for DeviceMemoryAllocError {
             }
             Error::TooManyObjects => DeviceMemoryAllocError::TooManyObjects,
             Error::MemoryMapFailed => DeviceMemoryAllocError::MemoryMapFailed,
+            Error::InvalidExternalHandle => DeviceMemoryAllocError::InvalidExternalHandle,
             _ => panic!("unexpected error: {:?}", err),
         }
     }
@@ -1050,9 +1495,14 @@ impl From<OomError> for DeviceMemoryAllocError {
 
 #[cfg(test)]
 mod tests {
+    use crate::memory::pool::StdMemoryPool;
     use crate::memory::DeviceMemory;
     use crate::memory::DeviceMemoryAllocError;
+    use crate::memory::DeviceMemoryBuilder;
+    use crate::memory::ExternalMemoryHandleType;
     use crate::OomError;
+    use std::sync::atomic::Ordering;
+    use std::thread;
 
     #[test]
     fn create() {
@@ -1061,6 +1511,136 @@ mod tests {
         let _ = DeviceMemory::alloc(device.clone(), mem_ty, 256).unwrap();
     }
 
+    #[test]
+    fn flush_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device
+            .physical_device()
+            .memory_types()
+            .find(|m| m.is_host_visible())
+            .unwrap();
+        let memory = DeviceMemory::alloc_and_map(device.clone(), mem_ty, 256).unwrap();
+
+        memory.flush_range(3..17).unwrap();
+        unsafe {
+            memory.invalidate_range(0..256).unwrap();
+        }
+        assert_should_panic!({
+            let _ = memory.flush_range(0..257);
+        });
+    }
+
+    #[test]
+    fn pool_priority_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let pool = StdMemoryPool::new(device);
+        assert_should_panic!(
+            "the pageable_device_local_memory feature must be enabled",
+            {
+                pool.set_priority(0.5);
+            }
+        );
+    }
+
+    #[test]
+    fn priority_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        if device.loaded_extensions().ext_memory_priority {
+            return;
+        }
+
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+        match DeviceMemoryBuilder::new(device.clone(), mem_ty.id(), 256)
+            .priority(1.0)
+            .build()
+        {
+            Err(DeviceMemoryAllocError::MissingExtension("ext_memory_priority")) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn device_mask_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        if device.loaded_extensions_or_core().khr_device_group {
+            return;
+        }
+
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+        match DeviceMemoryBuilder::new(device.clone(), mem_ty.id(), 256)
+            .device_mask(1)
+            .build()
+        {
+            Err(DeviceMemoryAllocError::MissingExtension("khr_device_group")) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_device_mask() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+
+        assert_should_panic!(
+            "the device mask must only contain physical devices of the device group",
+            {
+                let _ = DeviceMemoryBuilder::new(device.clone(), mem_ty.id(), 256).device_mask(0);
+            }
+        );
+    }
+
+    #[test]
+    fn win32_export_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        if device.loaded_extensions().khr_external_memory_win32 {
+            return;
+        }
+
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+        match DeviceMemoryBuilder::new(device.clone(), mem_ty.id(), 256)
+            .export_info(ExternalMemoryHandleType {
+                opaque_win32: true,
+                ..ExternalMemoryHandleType::none()
+            })
+            .build()
+        {
+            Err(DeviceMemoryAllocError::MissingExtension("khr_external_memory_win32")) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn host_pointer_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        if device.loaded_extensions().ext_external_memory_host {
+            return;
+        }
+
+        let mut data = vec![0u8; 4096];
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+        match unsafe {
+            DeviceMemory::import_host_pointer(
+                device.clone(),
+                mem_ty,
+                data.as_mut_ptr() as *mut _,
+                data.len(),
+            )
+        } {
+            Err(DeviceMemoryAllocError::MissingExtension("ext_external_memory_host")) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn priority_out_of_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+        assert_should_panic!("the priority must be between 0.0 and 1.0", {
+            let _ = DeviceMemoryBuilder::new(device.clone(), mem_ty.id(), 256).priority(1.5);
+        });
+    }
+
     #[test]
     fn zero_size() {
         let (device, _) = gfx_dev_and_queue!();
@@ -1116,13 +1696,39 @@ mod tests {
     fn allocation_count() {
         let (device, _) = gfx_dev_and_queue!();
         let mem_ty = device.physical_device().memory_types().next().unwrap();
-        assert_eq!(*device.allocation_count().lock().unwrap(), 0);
+        assert_eq!(device.allocation_count().load(Ordering::SeqCst), 0);
         let mem1 = DeviceMemory::alloc(device.clone(), mem_ty, 256).unwrap();
-        assert_eq!(*device.allocation_count().lock().unwrap(), 1);
+        assert_eq!(device.allocation_count().load(Ordering::SeqCst), 1);
         {
             let mem2 = DeviceMemory::alloc(device.clone(), mem_ty, 256).unwrap();
-            assert_eq!(*device.allocation_count().lock().unwrap(), 2);
+            assert_eq!(device.allocation_count().load(Ordering::SeqCst), 2);
         }
-        assert_eq!(*device.allocation_count().lock().unwrap(), 1);
+        assert_eq!(device.allocation_count().load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn concurrent_allocation_count() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device.physical_device().memory_types().next().unwrap().id();
+
+        let threads = (0..4)
+            .map(|_| {
+                let device = device.clone();
+                thread::spawn(move || {
+                    let mem_ty = device.physical_device().memory_type_by_id(mem_ty).unwrap();
+                    (0..8)
+                        .map(|_| DeviceMemory::alloc(device.clone(), mem_ty, 256).unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        let allocs = threads
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(device.allocation_count().load(Ordering::SeqCst), 32);
+        drop(allocs);
+        assert_eq!(device.allocation_count().load(Ordering::SeqCst), 0);
     }
 }
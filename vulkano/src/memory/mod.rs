@@ -100,6 +100,7 @@ pub use self::device_memory::DeviceMemoryBuilder;
 pub use self::device_memory::DeviceMemoryMapping;
 pub use self::device_memory::MappedDeviceMemory;
 pub use self::external_memory_handle_type::ExternalMemoryHandleType;
+pub use self::pool::MemoryAllocator;
 pub use self::pool::MemoryPool;
 
 mod device_memory;
@@ -128,6 +129,11 @@ pub struct MemoryRequirements {
     /// > **Note**: As its name says, using a dedicated allocation is an optimization and not a
     /// > requirement.
     pub prefer_dedicated: bool,
+
+    /// True if the implementation requires a dedicated allocation, which can be the case for
+    /// resources that are imported from or exported to external APIs. If the
+    /// `khr_dedicated_allocation` extension isn't enabled, then this will be false.
+    pub requires_dedicated: bool,
 }
 
 impl MemoryRequirements {
@@ -138,6 +144,47 @@ impl MemoryRequirements {
             alignment: reqs.alignment as usize,
             memory_type_bits: reqs.memoryTypeBits,
             prefer_dedicated: false,
+            requires_dedicated: false,
+        }
+    }
+}
+
+/// How much memory of a heap is used, and how much the process can use, as returned by
+/// `Device::memory_budget`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryHeapBudget {
+    /// Number of bytes of the heap that are currently used by the process, including the
+    /// allocations that weren't made with vulkano.
+    pub usage: usize,
+
+    /// Number of bytes of the heap that the process can use before allocations start failing or
+    /// degrading performance. This value can change over time, depending on the other
+    /// applications that use the device.
+    pub budget: usize,
+}
+
+/// Ways in which a physical device of a device group can access the memory of another one, as
+/// returned by `Device::peer_memory_features`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PeerMemoryFeatures {
+    /// The memory can be the source of copy commands.
+    pub copy_src: bool,
+    /// The memory can be the destination of copy commands.
+    pub copy_dst: bool,
+    /// The memory can be read by any kind of access.
+    pub generic_src: bool,
+    /// The memory can be written by any kind of access.
+    pub generic_dst: bool,
+}
+
+impl PeerMemoryFeatures {
+    #[inline]
+    pub(crate) fn from_bits(bits: vk::PeerMemoryFeatureFlags) -> PeerMemoryFeatures {
+        PeerMemoryFeatures {
+            copy_src: (bits & vk::PEER_MEMORY_FEATURE_COPY_SRC_BIT) != 0,
+            copy_dst: (bits & vk::PEER_MEMORY_FEATURE_COPY_DST_BIT) != 0,
+            generic_src: (bits & vk::PEER_MEMORY_FEATURE_GENERIC_SRC_BIT) != 0,
+            generic_dst: (bits & vk::PEER_MEMORY_FEATURE_GENERIC_DST_BIT) != 0,
         }
     }
 }
@@ -0,0 +1,138 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::memory::pool::AllocLayout;
+use crate::memory::pool::MappingRequirement;
+use crate::memory::pool::MemoryPool;
+use crate::memory::pool::PotentialDedicatedAllocation;
+use crate::memory::DedicatedAlloc;
+use crate::memory::DeviceMemoryAllocError;
+use crate::memory::MemoryRequirements;
+
+/// How a resource accesses its memory, which determines the memory type that it is allocated
+/// from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MemoryUsage {
+    /// The memory is only accessed by the GPU.
+    DeviceOnly,
+    /// The memory is written by the CPU and read by the GPU.
+    Upload,
+    /// The memory is written by the GPU and read by the CPU.
+    Download,
+    /// The memory is written by the CPU every frame and read by the GPU.
+    Dynamic,
+}
+
+impl MemoryUsage {
+    /// Returns whether the memory must be mapped to be used.
+    #[inline]
+    pub fn mapping_requirement(&self) -> MappingRequirement {
+        match *self {
+            MemoryUsage::DeviceOnly => MappingRequirement::DoNotMap,
+            MemoryUsage::Upload | MemoryUsage::Download | MemoryUsage::Dynamic => {
+                MappingRequirement::Map
+            }
+        }
+    }
+}
+
+/// General-purpose allocator of device memory, used by the buffer and image constructors.
+///
+/// The standard allocator is `Device::standard_pool`, which sub-allocates the resources from
+/// large blocks of each memory type. The trait is implemented for every `MemoryPool`, so that
+/// programs with their own memory strategy can implement `MemoryPool` and pass their allocator
+/// to the constructors that take one.
+///
+/// # Safety
+///
+/// - The memory returned by `alloc_for_usage` must match the requirements, with the same
+///   conditions as `MemoryPool::alloc_from_requirements`.
+pub unsafe trait MemoryAllocator: MemoryPool {
+    /// Allocates memory for a resource from the memory type that the `MemoryTypePolicy` of the
+    /// device chooses for `usage`.
+    ///
+    /// The memory is mapped if the usage needs the CPU to access it. See
+    /// `MemoryPool::alloc_from_requirements` for the other parameters.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if no memory type of `requirements` matches `usage`.
+    /// - Panics if `requirements.requires_dedicated` is true and `dedicated` is `None`.
+    ///
+    #[inline]
+    fn alloc_for_usage(
+        &self,
+        requirements: &MemoryRequirements,
+        layout: AllocLayout,
+        usage: MemoryUsage,
+        dedicated: DedicatedAlloc,
+    ) -> Result<PotentialDedicatedAllocation<Self::Alloc>, DeviceMemoryAllocError> {
+        let filter = self.device().memory_type_policy().for_usage(usage);
+        self.alloc_from_requirements(
+            requirements,
+            layout,
+            usage.mapping_requirement(),
+            dedicated,
+            |ty| filter.filter(ty),
+        )
+    }
+}
+
+unsafe impl<P> MemoryAllocator for P where P: MemoryPool {}
+
+#[cfg(test)]
+mod tests {
+    use crate::device::Device;
+    use crate::memory::pool::AllocLayout;
+    use crate::memory::pool::MemoryAllocator;
+    use crate::memory::pool::MemoryPoolAlloc;
+    use crate::memory::pool::MemoryUsage;
+    use crate::memory::DedicatedAlloc;
+    use crate::memory::MemoryRequirements;
+
+    #[test]
+    fn alloc_for_usage() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = Device::standard_pool(&device);
+        let requirements = MemoryRequirements {
+            size: 256,
+            alignment: 16,
+            memory_type_bits: !0,
+            prefer_dedicated: false,
+            requires_dedicated: false,
+        };
+
+        let alloc = pool
+            .alloc_for_usage(
+                &requirements,
+                AllocLayout::Linear,
+                MemoryUsage::Upload,
+                DedicatedAlloc::None,
+            )
+            .unwrap();
+        assert!(alloc.mapped_memory().is_some());
+        assert_eq!(alloc.offset() % 16, 0);
+
+        let alloc = pool
+            .alloc_for_usage(
+                &requirements,
+                AllocLayout::Linear,
+                MemoryUsage::DeviceOnly,
+                DedicatedAlloc::None,
+            )
+            .unwrap();
+        if device
+            .physical_device()
+            .memory_types()
+            .any(|ty| ty.is_device_local())
+        {
+            assert!(alloc.memory().memory_type().is_device_local());
+        }
+    }
+}
@@ -0,0 +1,571 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Sub-allocation of the blocks of device memory of the standard memory pool.
+
+use crate::memory::pool::MemoryPoolStatistics;
+use fnv::FnvHashMap;
+use std::cmp;
+use std::collections::BTreeSet;
+use std::ptr;
+use std::sync::Arc;
+
+/// Size of the smallest node that the buddy allocator hands out.
+const MIN_NODE_SIZE: usize = 256;
+
+/// Size of the first block that is allocated for a memory type. Each new block is twice as large
+/// as the previous one, until the preferred block size of the heap is reached.
+const FIRST_BLOCK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
+
+/// Largest block size that is used for heaps of more than 1 GB.
+const LARGE_HEAP_BLOCK_SIZE: usize = 256 * 1024 * 1024; // 256 MB
+
+/// Buddy allocator that keeps track of the free ranges of a single block of memory.
+///
+/// The range is split into nodes whose size is a power of two, and each node starts at a multiple
+/// of its size. An allocation is given the smallest free node that is large enough for both its
+/// size and its alignment, splitting larger nodes in two halves (the buddies) as needed. When a
+/// node is freed, it is merged back with its buddy if the buddy is free as well.
+#[derive(Debug)]
+pub(crate) struct BuddyAllocator {
+    // The free nodes, indexed by order. A node of order `n` has a size of `MIN_NODE_SIZE << n`.
+    free: Vec<BTreeSet<usize>>,
+    // The order of the allocated nodes, indexed by offset.
+    allocated: FnvHashMap<usize, usize>,
+    size: usize,
+    free_size: usize,
+}
+
+impl BuddyAllocator {
+    /// Builds an allocator for a range of `size` bytes.
+    ///
+    /// If `size` is not a power of two, the range is split into the largest nodes that fit, and
+    /// the nodes at the end of the range are never merged into larger ones.
+    pub fn new(size: usize) -> BuddyAllocator {
+        let size = size - size % MIN_NODE_SIZE;
+        let num_orders = order_of(cmp::max(size, MIN_NODE_SIZE).next_power_of_two()) + 1;
+        let mut free = vec![BTreeSet::new(); num_orders];
+
+        let mut offset = 0;
+        while offset < size {
+            let mut order = num_orders - 1;
+            while offset % node_size(order) != 0 || offset + node_size(order) > size {
+                order -= 1;
+            }
+            free[order].insert(offset);
+            offset += node_size(order);
+        }
+
+        BuddyAllocator {
+            free,
+            allocated: FnvHashMap::default(),
+            size,
+            free_size: size,
+        }
+    }
+
+    /// Returns the size of the range managed by the allocator.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of bytes that aren't allocated.
+    #[inline]
+    pub fn free_size(&self) -> usize {
+        self.free_size
+    }
+
+    /// Returns the number of allocations.
+    #[inline]
+    pub fn num_allocations(&self) -> usize {
+        self.allocated.len()
+    }
+
+    /// Returns the size of the largest free node.
+    #[inline]
+    pub fn largest_free_node(&self) -> usize {
+        self.free
+            .iter()
+            .rposition(|nodes| !nodes.is_empty())
+            .map_or(0, node_size)
+    }
+
+    /// Returns the size of the node of the allocation that starts at `offset`.
+    #[inline]
+    pub fn node_size_at(&self, offset: usize) -> Option<usize> {
+        self.allocated.get(&offset).map(|&order| node_size(order))
+    }
+
+    /// Returns true if nothing is allocated.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.allocated.is_empty()
+    }
+
+    /// Allocates `size` bytes aligned to `alignment`, which must be a power of two. Returns the
+    /// offset of the allocation, or `None` if there is no free node large enough.
+    pub fn alloc(&mut self, size: usize, alignment: usize) -> Option<usize> {
+        debug_assert!(alignment.is_power_of_two());
+
+        let order = order_of(cmp::max(size, alignment).next_power_of_two());
+        let mut current = (order..self.free.len()).find(|&o| !self.free[o].is_empty())?;
+        let offset = *self.free[current].iter().next().unwrap();
+        self.free[current].remove(&offset);
+
+        // Split the node until it has the right size, freeing the upper halves.
+        while current > order {
+            current -= 1;
+            self.free[current].insert(offset + node_size(current));
+        }
+
+        self.allocated.insert(offset, order);
+        self.free_size -= node_size(order);
+        Some(offset)
+    }
+
+    /// Frees the allocation that starts at `offset`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if there is no allocation at `offset`.
+    ///
+    pub fn free(&mut self, offset: usize) {
+        let mut order = self
+            .allocated
+            .remove(&offset)
+            .expect("no allocation at this offset");
+        self.free_size += node_size(order);
+
+        let mut offset = offset;
+        while order + 1 < self.free.len() {
+            let buddy = offset ^ node_size(order);
+            if !self.free[order].remove(&buddy) {
+                break;
+            }
+            offset = cmp::min(offset, buddy);
+            order += 1;
+        }
+
+        self.free[order].insert(offset);
+    }
+}
+
+#[inline]
+fn node_size(order: usize) -> usize {
+    MIN_NODE_SIZE << order
+}
+
+// Returns the order of the smallest node that can hold `size` bytes, which must be a power of two.
+#[inline]
+fn order_of(size: usize) -> usize {
+    cmp::max(size, MIN_NODE_SIZE).trailing_zeros() as usize
+        - MIN_NODE_SIZE.trailing_zeros() as usize
+}
+
+/// The blocks of device memory that have been allocated for a memory type, and that resources
+/// are sub-allocated from.
+///
+/// New blocks grow from `FIRST_BLOCK_SIZE` up to the preferred block size of the heap, so that
+/// small programs don't reserve large amounts of memory. Allocations that are larger than half
+/// the preferred block size get a block of their own. When blocks become empty, only one of them
+/// is kept around for future allocations and the others are freed.
+#[derive(Debug)]
+pub(crate) struct BlockList<M> {
+    blocks: Vec<Block<M>>,
+    preferred_block_size: usize,
+    next_block_size: usize,
+}
+
+#[derive(Debug)]
+struct Block<M> {
+    memory: Arc<M>,
+    // `None` if the block is dedicated to a single large allocation.
+    allocator: Option<BuddyAllocator>,
+    // True if the memory of the block can be exported.
+    exportable: bool,
+    size: usize,
+}
+
+impl<M> BlockList<M> {
+    /// Builds an empty list of blocks for a memory type whose heap has the given size.
+    pub fn new(heap_size: usize) -> BlockList<M> {
+        // Same heuristic as most allocators: small heaps are split into 8 blocks at most.
+        let preferred_block_size = if heap_size <= 1024 * 1024 * 1024 {
+            cmp::max(
+                FIRST_BLOCK_SIZE,
+                (heap_size / 8 + 1).next_power_of_two() / 2,
+            )
+        } else {
+            LARGE_HEAP_BLOCK_SIZE
+        };
+
+        BlockList {
+            blocks: Vec::new(),
+            preferred_block_size,
+            next_block_size: cmp::min(FIRST_BLOCK_SIZE, preferred_block_size),
+        }
+    }
+
+    /// Returns the number of blocks of device memory.
+    #[inline]
+    pub fn num_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the blocks of device memory.
+    #[inline]
+    pub fn memories(&self) -> impl Iterator<Item = &Arc<M>> {
+        self.blocks.iter().map(|block| &block.memory)
+    }
+
+    /// Returns statistics about the blocks and the allocations.
+    pub fn statistics(&self) -> MemoryPoolStatistics {
+        let mut stats = MemoryPoolStatistics {
+            num_blocks: self.blocks.len(),
+            ..MemoryPoolStatistics::default()
+        };
+
+        for block in &self.blocks {
+            match block.allocator {
+                Some(ref allocator) => {
+                    stats.num_allocations += allocator.num_allocations();
+                    stats.allocated += allocator.size();
+                    stats.used += allocator.size() - allocator.free_size();
+                    stats.largest_free_range =
+                        cmp::max(stats.largest_free_range, allocator.largest_free_node());
+                }
+                None => {
+                    stats.num_allocations += 1;
+                    stats.allocated += block.size;
+                    stats.used += block.size;
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Sub-allocates `size` bytes aligned to `alignment` from a block, calling `new_block` with
+    /// the size of the block to allocate if none of the existing blocks has enough free space.
+    ///
+    /// Returns the block and the offset of the allocation within the block.
+    pub fn alloc<F, E>(
+        &mut self,
+        size: usize,
+        alignment: usize,
+        exportable: bool,
+        new_block: F,
+    ) -> Result<(Arc<M>, usize), E>
+    where
+        F: FnOnce(usize) -> Result<M, E>,
+    {
+        if size > self.preferred_block_size / 2 {
+            let memory = Arc::new(new_block(size)?);
+            self.blocks.push(Block {
+                memory: memory.clone(),
+                allocator: None,
+                exportable,
+                size,
+            });
+            return Ok((memory, 0));
+        }
+
+        for block in self.blocks.iter_mut() {
+            if block.exportable != exportable {
+                continue;
+            }
+
+            if let Some(ref mut allocator) = block.allocator {
+                if let Some(offset) = allocator.alloc(size, alignment) {
+                    return Ok((block.memory.clone(), offset));
+                }
+            }
+        }
+
+        let block_size = cmp::max(
+            self.next_block_size,
+            cmp::max(size, alignment).next_power_of_two(),
+        );
+        let memory = Arc::new(new_block(block_size)?);
+        let mut allocator = BuddyAllocator::new(block_size);
+        let offset = allocator.alloc(size, alignment).unwrap();
+        self.blocks.push(Block {
+            memory: memory.clone(),
+            allocator: Some(allocator),
+            exportable,
+            size: block_size,
+        });
+        self.next_block_size = cmp::min(block_size * 2, self.preferred_block_size);
+
+        Ok((memory, offset))
+    }
+
+    /// Plans the moves that empty the least used blocks, by reallocating the allocations that
+    /// they contain in the other blocks.
+    ///
+    /// `movable` contains the block and the offset of each allocation that can be moved. A block
+    /// is only emptied if all of its allocations are movable. Returns, for each allocation that is
+    /// moved, its index in `movable` and the block and offset of its new location, which are
+    /// allocated already. The old locations stay allocated until they are freed.
+    pub fn defragment(&mut self, movable: &[(&M, usize)]) -> Vec<(usize, Arc<M>, usize)> {
+        let num_blocks = self.blocks.len();
+
+        let mut movable_per_block = vec![Vec::new(); num_blocks];
+        for (index, &(memory, _)) in movable.iter().enumerate() {
+            if let Some(block) = self.block_index(memory) {
+                movable_per_block[block].push(index);
+            }
+        }
+
+        // The blocks are emptied starting with the least used ones, and their allocations are
+        // moved to the most used ones first.
+        let used: Vec<usize> = self
+            .blocks
+            .iter()
+            .map(|block| {
+                block.allocator.as_ref().map_or(block.size, |allocator| {
+                    allocator.size() - allocator.free_size()
+                })
+            })
+            .collect();
+        let mut sources: Vec<usize> = (0..num_blocks)
+            .filter(|&i| {
+                matches!(self.blocks[i].allocator, Some(ref allocator)
+                    if !allocator.is_empty()
+                        && allocator.num_allocations() == movable_per_block[i].len())
+            })
+            .collect();
+        sources.sort_by_key(|&i| used[i]);
+        let mut destinations: Vec<usize> = (0..num_blocks)
+            .filter(|&i| self.blocks[i].allocator.is_some())
+            .collect();
+        destinations.sort_by_key(|&i| cmp::Reverse(used[i]));
+
+        let mut emptied = vec![false; num_blocks];
+        let mut filled = vec![false; num_blocks];
+        let mut moves = Vec::new();
+
+        for &source in &sources {
+            if filled[source] {
+                continue;
+            }
+
+            // Placing the largest allocations first wastes the least space.
+            let mut indices = movable_per_block[source].clone();
+            let source_allocator = self.blocks[source].allocator.as_ref().unwrap();
+            indices.sort_by_key(|&index| {
+                cmp::Reverse(source_allocator.node_size_at(movable[index].1).unwrap())
+            });
+            let node_sizes: Vec<usize> = indices
+                .iter()
+                .map(|&index| source_allocator.node_size_at(movable[index].1).unwrap())
+                .collect();
+            let exportable = self.blocks[source].exportable;
+
+            let mut block_moves = Vec::with_capacity(indices.len());
+            for (&index, &node) in indices.iter().zip(node_sizes.iter()) {
+                let mut destination = None;
+                for &dest in &destinations {
+                    if dest == source || emptied[dest] || self.blocks[dest].exportable != exportable
+                    {
+                        continue;
+                    }
+                    let allocator = self.blocks[dest].allocator.as_mut().unwrap();
+                    if let Some(offset) = allocator.alloc(node, node) {
+                        destination = Some((dest, offset));
+                        break;
+                    }
+                }
+
+                match destination {
+                    Some((dest, offset)) => block_moves.push((index, dest, offset)),
+                    None => break,
+                }
+            }
+
+            if block_moves.len() == indices.len() {
+                emptied[source] = true;
+                for (index, dest, offset) in block_moves {
+                    filled[dest] = true;
+                    moves.push((index, self.blocks[dest].memory.clone(), offset));
+                }
+            } else {
+                // The block can't be emptied completely, so nothing is moved out of it.
+                for (_, dest, offset) in block_moves {
+                    self.blocks[dest].allocator.as_mut().unwrap().free(offset);
+                }
+            }
+        }
+
+        moves
+    }
+
+    #[inline]
+    fn block_index(&self, memory: &M) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|block| ptr::eq(&*block.memory, memory))
+    }
+
+    /// Frees the allocation at `offset` in the block `memory`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `memory` isn't one of the blocks, or if there is no allocation at `offset`.
+    ///
+    pub fn free(&mut self, memory: &M, offset: usize) {
+        let index = self
+            .block_index(memory)
+            .expect("the memory doesn't belong to the pool");
+
+        let keep = match self.blocks[index].allocator {
+            Some(ref mut allocator) => {
+                allocator.free(offset);
+                !allocator.is_empty() || !self.blocks.iter().enumerate().any(|(i, block)| {
+                    i != index
+                        && matches!(block.allocator, Some(ref allocator) if allocator.is_empty())
+                })
+            }
+            None => false,
+        };
+
+        if !keep {
+            self.blocks.swap_remove(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockList;
+    use super::BuddyAllocator;
+    use super::FIRST_BLOCK_SIZE;
+    use super::MIN_NODE_SIZE;
+    use std::sync::Arc;
+
+    #[test]
+    fn alloc_free_merge() {
+        let mut allocator = BuddyAllocator::new(4096);
+        let a = allocator.alloc(100, 1).unwrap();
+        let b = allocator.alloc(1000, 1).unwrap();
+        let c = allocator.alloc(256, 256).unwrap();
+        assert_eq!(allocator.free_size(), 4096 - 256 - 1024 - 256);
+        assert_eq!(allocator.num_allocations(), 3);
+        assert_eq!(allocator.largest_free_node(), 2048);
+
+        allocator.free(a);
+        allocator.free(b);
+        allocator.free(c);
+        assert!(allocator.is_empty());
+        assert_eq!(allocator.free_size(), 4096);
+
+        // Everything has been merged back into a single node.
+        assert_eq!(allocator.alloc(4096, 1), Some(0));
+    }
+
+    #[test]
+    fn alignment() {
+        let mut allocator = BuddyAllocator::new(64 * 1024);
+        allocator.alloc(MIN_NODE_SIZE, 1).unwrap();
+        let offset = allocator.alloc(512, 4096).unwrap();
+        assert_eq!(offset % 4096, 0);
+    }
+
+    #[test]
+    fn exhaustion() {
+        let mut allocator = BuddyAllocator::new(1024);
+        for _ in 0..4 {
+            assert!(allocator.alloc(MIN_NODE_SIZE, 1).is_some());
+        }
+        assert!(allocator.alloc(1, 1).is_none());
+    }
+
+    #[test]
+    fn non_power_of_two_size() {
+        let mut allocator = BuddyAllocator::new(3 * 1024);
+        assert_eq!(allocator.size(), 3 * 1024);
+        assert!(allocator.alloc(2048, 1).is_some());
+        assert!(allocator.alloc(1024, 1).is_some());
+        assert!(allocator.alloc(1, 1).is_none());
+    }
+
+    #[test]
+    fn block_growth_and_release() {
+        let mut blocks = BlockList::<usize>::new(16 * 1024 * 1024 * 1024);
+        let mut sizes = Vec::new();
+        let mut new_block = |size| -> Result<usize, ()> {
+            sizes.push(size);
+            Ok(size)
+        };
+
+        let (first, a) = blocks
+            .alloc(FIRST_BLOCK_SIZE, 1, false, &mut new_block)
+            .unwrap();
+        let (second, b) = blocks.alloc(1024, 1, false, &mut new_block).unwrap();
+        let (third, c) = blocks.alloc(1024, 1, true, &mut new_block).unwrap();
+        assert_eq!(blocks.num_blocks(), 3);
+        let stats = blocks.statistics();
+        assert_eq!(stats.num_blocks, 3);
+        assert_eq!(stats.num_allocations, 3);
+        assert_eq!(stats.allocated, FIRST_BLOCK_SIZE * 7);
+        assert_eq!(stats.used, FIRST_BLOCK_SIZE + 2048);
+        assert_eq!(stats.largest_free_range, FIRST_BLOCK_SIZE * 2);
+        assert_eq!(
+            sizes,
+            vec![FIRST_BLOCK_SIZE, FIRST_BLOCK_SIZE * 2, FIRST_BLOCK_SIZE * 4]
+        );
+
+        // Only one empty block is kept.
+        blocks.free(&first, a);
+        blocks.free(&second, b);
+        assert_eq!(blocks.num_blocks(), 2);
+        blocks.free(&third, c);
+        assert_eq!(blocks.num_blocks(), 1);
+    }
+
+    #[test]
+    fn defragment_empties_least_used_block() {
+        let mut blocks = BlockList::<usize>::new(16 * 1024 * 1024 * 1024);
+        let new_block = |size| -> Result<usize, ()> { Ok(size) };
+
+        // Fill the first block, then allocate from a second one.
+        let mut first = Vec::new();
+        for _ in 0..8 {
+            first.push(blocks.alloc(1024 * 1024, 1, false, new_block).unwrap());
+        }
+        let a = blocks.alloc(2 * 1024 * 1024, 1, false, new_block).unwrap();
+        let b = blocks.alloc(4096, 1, false, new_block).unwrap();
+        assert_eq!(blocks.num_blocks(), 2);
+        assert!(Arc::ptr_eq(&a.0, &b.0) && !Arc::ptr_eq(&a.0, &first[0].0));
+
+        // There is no room in the first block.
+        let movable = vec![(&*a.0, a.1), (&*b.0, b.1)];
+        assert!(blocks.defragment(&movable).is_empty());
+
+        // Only one of the two allocations fits, so the second block can't be emptied.
+        blocks.free(&first[0].0, first[0].1);
+        assert!(blocks.defragment(&movable).is_empty());
+        assert_eq!(blocks.statistics().num_allocations, 9);
+
+        blocks.free(&a.0, a.1);
+        let movable = vec![(&*b.0, b.1)];
+        let moves = blocks.defragment(&movable);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].0, 0);
+        assert!(Arc::ptr_eq(&moves[0].1, &first[0].0));
+        assert_eq!(moves[0].2, first[0].1);
+
+        // Freeing the old location empties the second block.
+        blocks.free(&b.0, b.1);
+        let stats = blocks.statistics();
+        assert_eq!(stats.num_allocations, 8);
+        assert_eq!(stats.used, 7 * 1024 * 1024 + MIN_NODE_SIZE * 16);
+    }
+}
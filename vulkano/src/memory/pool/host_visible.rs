@@ -8,13 +8,15 @@
 // according to those terms.
 
 use std::cmp;
-use std::ops::Range;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 use crate::device::Device;
 use crate::instance::Instance;
 use crate::instance::MemoryType;
+use crate::memory::device_memory::check_priority;
+use crate::memory::pool::buddy::BlockList;
+use crate::memory::pool::MemoryPoolStatistics;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocError;
 use crate::memory::MappedDeviceMemory;
@@ -24,8 +26,10 @@ use crate::memory::MappedDeviceMemory;
 pub struct StdHostVisibleMemoryTypePool {
     device: Arc<Device>,
     memory_type: u32,
-    // TODO: obviously very inefficient
-    occupied: Mutex<Vec<(Arc<MappedDeviceMemory>, Vec<Range<usize>>)>>,
+    // The blocks of memory that the allocations are taken from.
+    blocks: Mutex<BlockList<MappedDeviceMemory>>,
+    // Priority set with `set_priority`, applied to the blocks allocated later.
+    priority: Mutex<Option<f32>>,
 }
 
 impl StdHostVisibleMemoryTypePool {
@@ -51,10 +55,28 @@ impl StdHostVisibleMemoryTypePool {
         Arc::new(StdHostVisibleMemoryTypePool {
             device: device.clone(),
             memory_type: memory_type.id(),
-            occupied: Mutex::new(Vec::new()),
+            blocks: Mutex::new(BlockList::new(memory_type.heap().size())),
+            priority: Mutex::new(None),
         })
     }
 
+    // Allocations in non-coherent memory are aligned to `nonCoherentAtomSize`, so that flushing
+    // or invalidating one of them never touches another one.
+    #[inline]
+    fn flush_alignment(&self, alignment: usize) -> usize {
+        let memory_type = self.memory_type();
+        if memory_type.is_host_coherent() {
+            alignment
+        } else {
+            let atom_size = self
+                .device
+                .physical_device()
+                .limits()
+                .non_coherent_atom_size() as usize;
+            cmp::max(alignment, atom_size)
+        }
+    }
+
     /// Allocates memory from the pool.
     ///
     /// # Panic
@@ -69,61 +91,24 @@ impl StdHostVisibleMemoryTypePool {
     ) -> Result<StdHostVisibleMemoryTypePoolAlloc, DeviceMemoryAllocError> {
         assert!(size != 0);
         assert!(alignment != 0);
+        let alignment = me.flush_alignment(alignment);
 
-        #[inline]
-        fn align(val: usize, al: usize) -> usize {
-            al * (1 + (val - 1) / al)
-        }
-
-        // Find a location.
-        let mut occupied = me.occupied.lock().unwrap();
-
-        // Try finding an entry in already-allocated chunks.
-        for &mut (ref dev_mem, ref mut entries) in occupied.iter_mut() {
-            // Try find some free space in-between two entries.
-            for i in 0..entries.len().saturating_sub(1) {
-                let entry1 = entries[i].clone();
-                let entry1_end = align(entry1.end, alignment);
-                let entry2 = entries[i + 1].clone();
-                if entry1_end + size <= entry2.start {
-                    entries.insert(i + 1, entry1_end..entry1_end + size);
-                    return Ok(StdHostVisibleMemoryTypePoolAlloc {
-                        pool: me.clone(),
-                        memory: dev_mem.clone(),
-                        offset: entry1_end,
-                        size: size,
-                    });
-                }
-            }
-
-            // Try append at the end.
-            let last_end = entries.last().map(|e| align(e.end, alignment)).unwrap_or(0);
-            if last_end + size <= (**dev_mem).as_ref().size() {
-                entries.push(last_end..last_end + size);
-                return Ok(StdHostVisibleMemoryTypePoolAlloc {
-                    pool: me.clone(),
-                    memory: dev_mem.clone(),
-                    offset: last_end,
-                    size: size,
-                });
-            }
-        }
-
-        // We need to allocate a new block.
-        let new_block = {
-            const MIN_BLOCK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
-            let to_alloc = cmp::max(MIN_BLOCK_SIZE, size.next_power_of_two());
-            let new_block =
-                DeviceMemory::alloc_and_map(me.device.clone(), me.memory_type(), to_alloc)?;
-            Arc::new(new_block)
-        };
+        let mut blocks = me.blocks.lock().unwrap();
+        let (memory, offset) = blocks.alloc(size, alignment, false, |block_size| {
+            DeviceMemory::alloc_and_map(me.device.clone(), me.memory_type(), block_size).inspect(
+                |memory| {
+                    if let Some(priority) = *me.priority.lock().unwrap() {
+                        memory.as_ref().set_priority(priority);
+                    }
+                },
+            )
+        })?;
 
-        occupied.push((new_block.clone(), vec![0..size]));
         Ok(StdHostVisibleMemoryTypePoolAlloc {
             pool: me.clone(),
-            memory: new_block,
-            offset: 0,
-            size: size,
+            memory,
+            offset,
+            size,
         })
     }
 
@@ -136,67 +121,93 @@ impl StdHostVisibleMemoryTypePool {
     ) -> Result<StdHostVisibleMemoryTypePoolAlloc, DeviceMemoryAllocError> {
         assert!(size != 0);
         assert!(alignment != 0);
+        let alignment = me.flush_alignment(alignment);
 
-        #[inline]
-        fn align(val: usize, al: usize) -> usize {
-            al * (1 + (val - 1) / al)
-        }
-
-        // Find a location.
-        let mut occupied = me.occupied.lock().unwrap();
-
-        // Try finding an entry in already-allocated chunks.
-        for &mut (ref dev_mem, ref mut entries) in occupied.iter_mut() {
-            // Try find some free space in-between two entries.
-            for i in 0..entries.len().saturating_sub(1) {
-                let entry1 = entries[i].clone();
-                let entry1_end = align(entry1.end, alignment);
-                let entry2 = entries[i + 1].clone();
-                if entry1_end + size <= entry2.start {
-                    entries.insert(i + 1, entry1_end..entry1_end + size);
-                    return Ok(StdHostVisibleMemoryTypePoolAlloc {
-                        pool: me.clone(),
-                        memory: dev_mem.clone(),
-                        offset: entry1_end,
-                        size,
-                    });
-                }
-            }
-
-            // Try append at the end.
-            let last_end = entries.last().map(|e| align(e.end, alignment)).unwrap_or(0);
-            if last_end + size <= (**dev_mem).as_ref().size() {
-                entries.push(last_end..last_end + size);
-                return Ok(StdHostVisibleMemoryTypePoolAlloc {
-                    pool: me.clone(),
-                    memory: dev_mem.clone(),
-                    offset: last_end,
-                    size,
-                });
-            }
-        }
-
-        // We need to allocate a new block.
-        let new_block = {
-            const MIN_BLOCK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
-            let to_alloc = cmp::max(MIN_BLOCK_SIZE, size.next_power_of_two());
-            let new_block = DeviceMemory::alloc_and_map_with_exportable_fd(
+        let mut blocks = me.blocks.lock().unwrap();
+        let (memory, offset) = blocks.alloc(size, alignment, true, |block_size| {
+            DeviceMemory::alloc_and_map_with_exportable_fd(
                 me.device.clone(),
                 me.memory_type(),
-                to_alloc,
-            )?;
-            Arc::new(new_block)
-        };
+                block_size,
+            )
+            .inspect(|memory| {
+                if let Some(priority) = *me.priority.lock().unwrap() {
+                    memory.as_ref().set_priority(priority);
+                }
+            })
+        })?;
 
-        occupied.push((new_block.clone(), vec![0..size]));
         Ok(StdHostVisibleMemoryTypePoolAlloc {
             pool: me.clone(),
-            memory: new_block,
-            offset: 0,
+            memory,
+            offset,
             size,
         })
     }
 
+    /// Sets the priority of all the blocks of the pool, including the ones that are allocated
+    /// later. See `DeviceMemory::set_priority`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `pageable_device_local_memory` feature is not enabled on the device.
+    /// - Panics if `priority` is not between 0.0 and 1.0.
+    ///
+    pub fn set_priority(&self, priority: f32) {
+        check_priority(&self.device, priority);
+
+        let blocks = self.blocks.lock().unwrap();
+        for memory in blocks.memories() {
+            let memory: &DeviceMemory = (**memory).as_ref();
+            memory.set_priority(priority);
+        }
+        *self.priority.lock().unwrap() = Some(priority);
+    }
+
+    /// Reallocates the given allocations so that the least used blocks of the pool can be freed.
+    ///
+    /// Returns, for each allocation that has been moved, its index in `allocations` and its new
+    /// location. The content isn't copied. The blocks are freed once the old allocations have
+    /// been dropped. See `StdMemoryPool::defragment`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the allocations doesn't belong to this pool.
+    ///
+    pub fn defragment(
+        me: &Arc<Self>,
+        allocations: &[&StdHostVisibleMemoryTypePoolAlloc],
+    ) -> Vec<(usize, StdHostVisibleMemoryTypePoolAlloc)> {
+        let movable: Vec<(&MappedDeviceMemory, usize)> = allocations
+            .iter()
+            .map(|alloc| {
+                assert!(Arc::ptr_eq(&alloc.pool, me));
+                (&*alloc.memory, alloc.offset)
+            })
+            .collect();
+
+        let mut blocks = me.blocks.lock().unwrap();
+        blocks
+            .defragment(&movable)
+            .into_iter()
+            .map(|(index, memory, offset)| {
+                let alloc = StdHostVisibleMemoryTypePoolAlloc {
+                    pool: me.clone(),
+                    memory,
+                    offset,
+                    size: allocations[index].size,
+                };
+                (index, alloc)
+            })
+            .collect()
+    }
+
+    /// Returns statistics about the memory allocated by this pool.
+    #[inline]
+    pub fn statistics(&self) -> MemoryPoolStatistics {
+        self.blocks.lock().unwrap().statistics()
+    }
+
     /// Returns the device this pool operates on.
     #[inline]
     pub fn device(&self) -> &Arc<Device> {
@@ -222,6 +233,11 @@ pub struct StdHostVisibleMemoryTypePoolAlloc {
 }
 
 impl StdHostVisibleMemoryTypePoolAlloc {
+    #[inline]
+    pub(crate) fn pool(&self) -> &Arc<StdHostVisibleMemoryTypePool> {
+        &self.pool
+    }
+
     #[inline]
     pub fn memory(&self) -> &MappedDeviceMemory {
         &self.memory
@@ -240,13 +256,7 @@ impl StdHostVisibleMemoryTypePoolAlloc {
 
 impl Drop for StdHostVisibleMemoryTypePoolAlloc {
     fn drop(&mut self) {
-        let mut occupied = self.pool.occupied.lock().unwrap();
-
-        let entries = occupied
-            .iter_mut()
-            .find(|e| &*e.0 as *const MappedDeviceMemory == &*self.memory)
-            .unwrap();
-
-        entries.1.retain(|e| e.start != self.offset);
+        let mut blocks = self.pool.blocks.lock().unwrap();
+        blocks.free(&self.memory, self.offset);
     }
 }
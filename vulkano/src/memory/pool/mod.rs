@@ -15,19 +15,29 @@ use crate::memory::DeviceMemoryAllocError;
 use crate::memory::MappedDeviceMemory;
 use crate::memory::MemoryRequirements;
 
+pub use self::allocator::MemoryAllocator;
+pub use self::allocator::MemoryUsage;
 pub use self::host_visible::StdHostVisibleMemoryTypePool;
 pub use self::host_visible::StdHostVisibleMemoryTypePoolAlloc;
 pub use self::non_host_visible::StdNonHostVisibleMemoryTypePool;
 pub use self::non_host_visible::StdNonHostVisibleMemoryTypePoolAlloc;
+pub use self::policy::MemoryTypeFilter;
+pub use self::policy::MemoryTypePolicy;
+pub use self::policy::MemoryTypeProperties;
+pub use self::pool::DefragmentationMove;
 pub use self::pool::StdMemoryPool;
 pub use self::pool::StdMemoryPoolAlloc;
+use std::cmp;
 use std::sync::Arc;
 
+mod allocator;
+mod buddy;
 mod host_visible;
 mod non_host_visible;
+mod policy;
 mod pool;
 
-fn choose_allocation_memory_type<'s, F>(
+pub(crate) fn choose_allocation_memory_type<'s, F>(
     device: &'s Arc<Device>,
     requirements: &MemoryRequirements,
     mut filter: F,
@@ -62,6 +72,25 @@ where
     mem_ty
 }
 
+// Returns true if a resource with the given requirements should get an allocation of its own.
+fn use_dedicated_allocation(
+    device: &Device,
+    requirements: &MemoryRequirements,
+    dedicated: &DedicatedAlloc,
+) -> bool {
+    let possible = device.loaded_extensions_or_core().khr_dedicated_allocation
+        && !matches!(*dedicated, DedicatedAlloc::None);
+
+    if requirements.requires_dedicated {
+        assert!(
+            possible,
+            "the resource requires a dedicated allocation, but no resource was passed"
+        );
+    }
+
+    possible && (requirements.prefer_dedicated || requirements.requires_dedicated)
+}
+
 /// Pool of GPU-visible memory that can be allocated from.
 pub unsafe trait MemoryPool: DeviceOwned {
     /// Object that represents a single allocation. Its destructor should free the chunk.
@@ -111,7 +140,9 @@ pub unsafe trait MemoryPool: DeviceOwned {
     /// Chooses a memory type and allocates memory from it.
     ///
     /// Contrary to `alloc_generic`, this function may allocate a whole new block of memory
-    /// dedicated to a resource based on `requirements.prefer_dedicated`.
+    /// dedicated to a resource based on `requirements.prefer_dedicated` and
+    /// `requirements.requires_dedicated`, if `dedicated` is not `None` and the
+    /// `khr_dedicated_allocation` extension is enabled.
     ///
     /// `filter` can be used to restrict the memory types and to indicate which are preferred.
     /// If `map` is `MappingRequirement::Map`, then non-host-visible memory types will
@@ -134,6 +165,7 @@ pub unsafe trait MemoryPool: DeviceOwned {
     ///
     /// - Panics if no memory type could be found, which can happen if `filter` is too restrictive.
     // TODO: ^ is this a good idea?
+    /// - Panics if `requirements.requires_dedicated` is true and `dedicated` is `None`.
     /// - Panics if `size` is 0.
     /// - Panics if `alignment` is 0.
     ///
@@ -152,19 +184,7 @@ pub unsafe trait MemoryPool: DeviceOwned {
         let mem_ty = choose_allocation_memory_type(self.device(), requirements, filter, map);
 
         // Redirect to `self.alloc_generic` if we don't perform a dedicated allocation.
-        if !requirements.prefer_dedicated
-            || !self.device().loaded_extensions().khr_dedicated_allocation
-        {
-            let alloc = self.alloc_generic(
-                mem_ty,
-                requirements.size,
-                requirements.alignment,
-                layout,
-                map,
-            )?;
-            return Ok(alloc.into());
-        }
-        if let DedicatedAlloc::None = dedicated {
+        if !use_dedicated_allocation(self.device(), requirements, &dedicated) {
             let alloc = self.alloc_generic(
                 mem_ty,
                 requirements.size,
@@ -212,23 +232,15 @@ pub unsafe trait MemoryPool: DeviceOwned {
         F: FnMut(MemoryType) -> AllocFromRequirementsFilter,
     {
         assert!(self.device().loaded_extensions().khr_external_memory_fd);
-        assert!(self.device().loaded_extensions().khr_external_memory);
+        assert!(
+            self.device()
+                .loaded_extensions_or_core()
+                .khr_external_memory
+        );
 
         let mem_ty = choose_allocation_memory_type(self.device(), requirements, filter, map);
 
-        if !requirements.prefer_dedicated
-            || !self.device().loaded_extensions().khr_dedicated_allocation
-        {
-            let alloc = self.alloc_generic_with_exportable_fd(
-                mem_ty,
-                requirements.size,
-                requirements.alignment,
-                layout,
-                map,
-            )?;
-            return Ok(alloc.into());
-        }
-        if let DedicatedAlloc::None = dedicated {
+        if !use_dedicated_allocation(self.device(), requirements, &dedicated) {
             let alloc = self.alloc_generic_with_exportable_fd(
                 mem_ty,
                 requirements.size,
@@ -262,6 +274,57 @@ pub unsafe trait MemoryPool: DeviceOwned {
     }
 }
 
+/// Statistics about the memory allocated by `StdMemoryPool`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct MemoryPoolStatistics {
+    /// Number of blocks of device memory that have been allocated.
+    pub num_blocks: usize,
+
+    /// Number of sub-allocations that are alive.
+    pub num_allocations: usize,
+
+    /// Total size in bytes of the blocks of device memory.
+    pub allocated: usize,
+
+    /// Number of bytes of the blocks that are handed out to sub-allocations, including the
+    /// padding that rounds them up.
+    pub used: usize,
+
+    /// Size in bytes of the largest range that is free in a single block. Allocations that are
+    /// larger than this need a new block.
+    pub largest_free_range: usize,
+}
+
+impl MemoryPoolStatistics {
+    /// Returns the number of bytes of the blocks that aren't used.
+    #[inline]
+    pub fn free(&self) -> usize {
+        self.allocated - self.used
+    }
+
+    /// Returns a value between 0.0 and 1.0 that indicates how fragmented the free memory is. 0.0
+    /// means that all the free memory is in a single range, and values close to 1.0 mean that it
+    /// is scattered in many small ranges.
+    #[inline]
+    pub fn fragmentation(&self) -> f32 {
+        if self.free() == 0 {
+            0.0
+        } else {
+            1.0 - self.largest_free_range as f32 / self.free() as f32
+        }
+    }
+
+    /// Adds the statistics of `other` to these statistics.
+    #[inline]
+    pub(crate) fn merge(&mut self, other: &MemoryPoolStatistics) {
+        self.num_blocks += other.num_blocks;
+        self.num_allocations += other.num_allocations;
+        self.allocated += other.allocated;
+        self.used += other.used;
+        self.largest_free_range = cmp::max(self.largest_free_range, other.largest_free_range);
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum AllocFromRequirementsFilter {
     Preferred,
@@ -348,3 +411,104 @@ impl<A> From<A> for PotentialDedicatedAllocation<A> {
         PotentialDedicatedAllocation::Generic(alloc)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::iter::Empty;
+
+    use crate::buffer::sys::SparseLevel;
+    use crate::buffer::sys::UnsafeBuffer;
+    use crate::buffer::BufferUsage;
+    use crate::device::Device;
+    use crate::memory::pool::use_dedicated_allocation;
+    use crate::memory::pool::AllocFromRequirementsFilter;
+    use crate::memory::pool::AllocLayout;
+    use crate::memory::pool::MappingRequirement;
+    use crate::memory::pool::MemoryPool;
+    use crate::memory::pool::MemoryPoolStatistics;
+    use crate::memory::pool::PotentialDedicatedAllocation;
+    use crate::memory::DedicatedAlloc;
+    use crate::memory::MemoryRequirements;
+    use crate::sync::Sharing;
+
+    #[test]
+    fn statistics_fragmentation() {
+        let stats = MemoryPoolStatistics {
+            num_blocks: 1,
+            num_allocations: 2,
+            allocated: 1024,
+            used: 512,
+            largest_free_range: 128,
+        };
+        assert_eq!(stats.free(), 512);
+        assert_eq!(stats.fragmentation(), 0.75);
+
+        let mut total = MemoryPoolStatistics::default();
+        total.merge(&stats);
+        total.merge(&MemoryPoolStatistics {
+            largest_free_range: 256,
+            ..stats
+        });
+        assert_eq!(total.num_blocks, 2);
+        assert_eq!(total.allocated, 2048);
+        assert_eq!(total.largest_free_range, 256);
+    }
+
+    #[test]
+    fn required_dedicated_allocation() {
+        let (device, _) = gfx_dev_and_queue!();
+        let (buffer, mut requirements) = unsafe {
+            UnsafeBuffer::new(
+                device.clone(),
+                128,
+                BufferUsage::all(),
+                Sharing::Exclusive::<Empty<_>>,
+                SparseLevel::none(),
+            )
+        }
+        .unwrap();
+
+        requirements.prefer_dedicated = false;
+        requirements.requires_dedicated = false;
+        assert!(!use_dedicated_allocation(
+            &device,
+            &requirements,
+            &DedicatedAlloc::Buffer(&buffer)
+        ));
+
+        if !device.loaded_extensions_or_core().khr_dedicated_allocation {
+            return;
+        }
+
+        requirements.requires_dedicated = true;
+        let alloc = MemoryPool::alloc_from_requirements(
+            &Device::standard_pool(&device),
+            &requirements,
+            AllocLayout::Linear,
+            MappingRequirement::DoNotMap,
+            DedicatedAlloc::Buffer(&buffer),
+            |_| AllocFromRequirementsFilter::Allowed,
+        )
+        .unwrap();
+        match alloc {
+            PotentialDedicatedAllocation::Dedicated(_) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn required_dedicated_allocation_without_resource() {
+        let (device, _) = gfx_dev_and_queue!();
+        let requirements = MemoryRequirements {
+            size: 128,
+            alignment: 1,
+            memory_type_bits: !0,
+            prefer_dedicated: false,
+            requires_dedicated: true,
+        };
+
+        assert_should_panic!("requires a dedicated allocation", {
+            use_dedicated_allocation(&device, &requirements, &DedicatedAlloc::None);
+        });
+    }
+}
@@ -7,14 +7,15 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use std::cmp;
-use std::ops::Range;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 use crate::device::Device;
 use crate::instance::Instance;
 use crate::instance::MemoryType;
+use crate::memory::device_memory::check_priority;
+use crate::memory::pool::buddy::BlockList;
+use crate::memory::pool::MemoryPoolStatistics;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocError;
 
@@ -23,8 +24,10 @@ use crate::memory::DeviceMemoryAllocError;
 pub struct StdNonHostVisibleMemoryTypePool {
     device: Arc<Device>,
     memory_type: u32,
-    // TODO: obviously very inefficient
-    occupied: Mutex<Vec<(Arc<DeviceMemory>, Vec<Range<usize>>)>>,
+    // The blocks of memory that the allocations are taken from.
+    blocks: Mutex<BlockList<DeviceMemory>>,
+    // Priority set with `set_priority`, applied to the blocks allocated later.
+    priority: Mutex<Option<f32>>,
 }
 
 impl StdNonHostVisibleMemoryTypePool {
@@ -51,7 +54,8 @@ impl StdNonHostVisibleMemoryTypePool {
         Arc::new(StdNonHostVisibleMemoryTypePool {
             device: device.clone(),
             memory_type: memory_type.id(),
-            occupied: Mutex::new(Vec::new()),
+            blocks: Mutex::new(BlockList::new(memory_type.heap().size())),
+            priority: Mutex::new(None),
         })
     }
 
@@ -70,59 +74,20 @@ impl StdNonHostVisibleMemoryTypePool {
         assert!(size != 0);
         assert!(alignment != 0);
 
-        #[inline]
-        fn align(val: usize, al: usize) -> usize {
-            al * (1 + (val - 1) / al)
-        }
-
-        // Find a location.
-        let mut occupied = me.occupied.lock().unwrap();
-
-        // Try finding an entry in already-allocated chunks.
-        for &mut (ref dev_mem, ref mut entries) in occupied.iter_mut() {
-            // Try find some free space in-between two entries.
-            for i in 0..entries.len().saturating_sub(1) {
-                let entry1 = entries[i].clone();
-                let entry1_end = align(entry1.end, alignment);
-                let entry2 = entries[i + 1].clone();
-                if entry1_end + size <= entry2.start {
-                    entries.insert(i + 1, entry1_end..entry1_end + size);
-                    return Ok(StdNonHostVisibleMemoryTypePoolAlloc {
-                        pool: me.clone(),
-                        memory: dev_mem.clone(),
-                        offset: entry1_end,
-                        size: size,
-                    });
+        let mut blocks = me.blocks.lock().unwrap();
+        let (memory, offset) = blocks.alloc(size, alignment, false, |block_size| {
+            DeviceMemory::alloc(me.device.clone(), me.memory_type(), block_size).inspect(|memory| {
+                if let Some(priority) = *me.priority.lock().unwrap() {
+                    memory.set_priority(priority);
                 }
-            }
-
-            // Try append at the end.
-            let last_end = entries.last().map(|e| align(e.end, alignment)).unwrap_or(0);
-            if last_end + size <= dev_mem.size() {
-                entries.push(last_end..last_end + size);
-                return Ok(StdNonHostVisibleMemoryTypePoolAlloc {
-                    pool: me.clone(),
-                    memory: dev_mem.clone(),
-                    offset: last_end,
-                    size: size,
-                });
-            }
-        }
+            })
+        })?;
 
-        // We need to allocate a new block.
-        let new_block = {
-            const MIN_BLOCK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
-            let to_alloc = cmp::max(MIN_BLOCK_SIZE, size.next_power_of_two());
-            let new_block = DeviceMemory::alloc(me.device.clone(), me.memory_type(), to_alloc)?;
-            Arc::new(new_block)
-        };
-
-        occupied.push((new_block.clone(), vec![0..size]));
         Ok(StdNonHostVisibleMemoryTypePoolAlloc {
             pool: me.clone(),
-            memory: new_block,
-            offset: 0,
-            size: size,
+            memory,
+            offset,
+            size,
         })
     }
 
@@ -136,66 +101,86 @@ impl StdNonHostVisibleMemoryTypePool {
         assert!(size != 0);
         assert!(alignment != 0);
 
-        #[inline]
-        fn align(val: usize, al: usize) -> usize {
-            al * (1 + (val - 1) / al)
-        }
-
-        // Find a location.
-        let mut occupied = me.occupied.lock().unwrap();
-
-        // Try finding an entry in already-allocated chunks.
-        for &mut (ref dev_mem, ref mut entries) in occupied.iter_mut() {
-            // Try find some free space in-between two entries.
-            for i in 0..entries.len().saturating_sub(1) {
-                let entry1 = entries[i].clone();
-                let entry1_end = align(entry1.end, alignment);
-                let entry2 = entries[i + 1].clone();
-                if entry1_end + size <= entry2.start {
-                    entries.insert(i + 1, entry1_end..entry1_end + size);
-                    return Ok(StdNonHostVisibleMemoryTypePoolAlloc {
-                        pool: me.clone(),
-                        memory: dev_mem.clone(),
-                        offset: entry1_end,
-                        size,
-                    });
-                }
-            }
+        let mut blocks = me.blocks.lock().unwrap();
+        let (memory, offset) = blocks.alloc(size, alignment, true, |block_size| {
+            DeviceMemory::alloc_with_exportable_fd(me.device.clone(), me.memory_type(), block_size)
+                .inspect(|memory| {
+                    if let Some(priority) = *me.priority.lock().unwrap() {
+                        memory.set_priority(priority);
+                    }
+                })
+        })?;
 
-            // Try append at the end.
-            let last_end = entries.last().map(|e| align(e.end, alignment)).unwrap_or(0);
-            if last_end + size <= dev_mem.size() {
-                entries.push(last_end..last_end + size);
-                return Ok(StdNonHostVisibleMemoryTypePoolAlloc {
-                    pool: me.clone(),
-                    memory: dev_mem.clone(),
-                    offset: last_end,
-                    size,
-                });
-            }
-        }
-
-        // We need to allocate a new block.
-        let new_block = {
-            const MIN_BLOCK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
-            let to_alloc = cmp::max(MIN_BLOCK_SIZE, size.next_power_of_two());
-            let new_block = DeviceMemory::alloc_with_exportable_fd(
-                me.device.clone(),
-                me.memory_type(),
-                to_alloc,
-            )?;
-            Arc::new(new_block)
-        };
-
-        occupied.push((new_block.clone(), vec![0..size]));
         Ok(StdNonHostVisibleMemoryTypePoolAlloc {
             pool: me.clone(),
-            memory: new_block,
-            offset: 0,
+            memory,
+            offset,
             size,
         })
     }
 
+    /// Sets the priority of all the blocks of the pool, including the ones that are allocated
+    /// later. See `DeviceMemory::set_priority`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `pageable_device_local_memory` feature is not enabled on the device.
+    /// - Panics if `priority` is not between 0.0 and 1.0.
+    ///
+    pub fn set_priority(&self, priority: f32) {
+        check_priority(&self.device, priority);
+
+        let blocks = self.blocks.lock().unwrap();
+        for memory in blocks.memories() {
+            memory.set_priority(priority);
+        }
+        *self.priority.lock().unwrap() = Some(priority);
+    }
+
+    /// Reallocates the given allocations so that the least used blocks of the pool can be freed.
+    ///
+    /// Returns, for each allocation that has been moved, its index in `allocations` and its new
+    /// location. The content isn't copied. The blocks are freed once the old allocations have
+    /// been dropped. See `StdMemoryPool::defragment`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the allocations doesn't belong to this pool.
+    ///
+    pub fn defragment(
+        me: &Arc<Self>,
+        allocations: &[&StdNonHostVisibleMemoryTypePoolAlloc],
+    ) -> Vec<(usize, StdNonHostVisibleMemoryTypePoolAlloc)> {
+        let movable: Vec<(&DeviceMemory, usize)> = allocations
+            .iter()
+            .map(|alloc| {
+                assert!(Arc::ptr_eq(&alloc.pool, me));
+                (&*alloc.memory, alloc.offset)
+            })
+            .collect();
+
+        let mut blocks = me.blocks.lock().unwrap();
+        blocks
+            .defragment(&movable)
+            .into_iter()
+            .map(|(index, memory, offset)| {
+                let alloc = StdNonHostVisibleMemoryTypePoolAlloc {
+                    pool: me.clone(),
+                    memory,
+                    offset,
+                    size: allocations[index].size,
+                };
+                (index, alloc)
+            })
+            .collect()
+    }
+
+    /// Returns statistics about the memory allocated by this pool.
+    #[inline]
+    pub fn statistics(&self) -> MemoryPoolStatistics {
+        self.blocks.lock().unwrap().statistics()
+    }
+
     /// Returns the device this pool operates on.
     #[inline]
     pub fn device(&self) -> &Arc<Device> {
@@ -221,6 +206,11 @@ pub struct StdNonHostVisibleMemoryTypePoolAlloc {
 }
 
 impl StdNonHostVisibleMemoryTypePoolAlloc {
+    #[inline]
+    pub(crate) fn pool(&self) -> &Arc<StdNonHostVisibleMemoryTypePool> {
+        &self.pool
+    }
+
     #[inline]
     pub fn memory(&self) -> &DeviceMemory {
         &self.memory
@@ -239,13 +229,7 @@ impl StdNonHostVisibleMemoryTypePoolAlloc {
 
 impl Drop for StdNonHostVisibleMemoryTypePoolAlloc {
     fn drop(&mut self) {
-        let mut occupied = self.pool.occupied.lock().unwrap();
-
-        let entries = occupied
-            .iter_mut()
-            .find(|e| &*e.0 as *const DeviceMemory == &*self.memory)
-            .unwrap();
-
-        entries.1.retain(|e| e.start != self.offset);
+        let mut blocks = self.pool.blocks.lock().unwrap();
+        blocks.free(&self.memory, self.offset);
     }
 }
@@ -0,0 +1,236 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::instance::MemoryType;
+use crate::memory::pool::AllocFromRequirementsFilter;
+use crate::memory::pool::MemoryUsage;
+
+/// List of properties of a memory type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryTypeProperties {
+    pub device_local: bool,
+    pub host_visible: bool,
+    pub host_coherent: bool,
+    pub host_cached: bool,
+    pub lazily_allocated: bool,
+}
+
+impl MemoryTypeProperties {
+    /// Builds a `MemoryTypeProperties` with all values set to false. Useful as a default value.
+    #[inline]
+    pub fn none() -> MemoryTypeProperties {
+        MemoryTypeProperties {
+            device_local: false,
+            host_visible: false,
+            host_coherent: false,
+            host_cached: false,
+            lazily_allocated: false,
+        }
+    }
+
+    /// Returns true if the memory type has all the properties of `self`.
+    #[inline]
+    pub fn all_of(&self, ty: MemoryType) -> bool {
+        (!self.device_local || ty.is_device_local())
+            && (!self.host_visible || ty.is_host_visible())
+            && (!self.host_coherent || ty.is_host_coherent())
+            && (!self.host_cached || ty.is_host_cached())
+            && (!self.lazily_allocated || ty.is_lazily_allocated())
+    }
+
+    /// Returns true if the memory type has at least one of the properties of `self`.
+    #[inline]
+    pub fn any_of(&self, ty: MemoryType) -> bool {
+        (self.device_local && ty.is_device_local())
+            || (self.host_visible && ty.is_host_visible())
+            || (self.host_coherent && ty.is_host_coherent())
+            || (self.host_cached && ty.is_host_cached())
+            || (self.lazily_allocated && ty.is_lazily_allocated())
+    }
+}
+
+/// Describes which memory types are chosen for a kind of resource.
+///
+/// A memory type is preferred if it has all the `preferred` properties and none of the
+/// `not_preferred` properties. If no memory type is preferred, the first one that doesn't have
+/// any of the `forbidden` properties is used.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryTypeFilter {
+    pub preferred: MemoryTypeProperties,
+    pub not_preferred: MemoryTypeProperties,
+    pub forbidden: MemoryTypeProperties,
+}
+
+impl MemoryTypeFilter {
+    /// Builds a `MemoryTypeFilter` that allows every memory type without preferring any.
+    #[inline]
+    pub fn any() -> MemoryTypeFilter {
+        MemoryTypeFilter {
+            preferred: MemoryTypeProperties::none(),
+            not_preferred: MemoryTypeProperties::none(),
+            forbidden: MemoryTypeProperties::none(),
+        }
+    }
+
+    /// Builds a `MemoryTypeFilter` that prefers the memory types with the given properties.
+    #[inline]
+    pub fn preferring(preferred: MemoryTypeProperties) -> MemoryTypeFilter {
+        MemoryTypeFilter {
+            preferred,
+            ..MemoryTypeFilter::any()
+        }
+    }
+
+    /// Returns how suitable a memory type is, to be passed to
+    /// `MemoryPool::alloc_from_requirements`.
+    #[inline]
+    pub fn filter(&self, ty: MemoryType) -> AllocFromRequirementsFilter {
+        if self.forbidden.any_of(ty) {
+            AllocFromRequirementsFilter::Forbidden
+        } else if self.preferred.all_of(ty) && !self.not_preferred.any_of(ty) {
+            AllocFromRequirementsFilter::Preferred
+        } else {
+            AllocFromRequirementsFilter::Allowed
+        }
+    }
+}
+
+/// Policy that controls how the buffers and images of vulkano choose their memory type.
+///
+/// Each resource kind has its own filter. The policy of a device can be changed with
+/// `Device::set_memory_type_policy`, and is used for the resources created afterwards.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::memory::pool::MemoryTypeFilter;
+/// use vulkano::memory::pool::MemoryTypePolicy;
+/// use vulkano::memory::pool::MemoryTypeProperties;
+///
+/// // Put the dynamic buffers in the device-local memory that the CPU can write to directly.
+/// let _policy = MemoryTypePolicy {
+///     dynamic: MemoryTypeFilter::preferring(MemoryTypeProperties {
+///         device_local: true,
+///         host_visible: true,
+///         .. MemoryTypeProperties::none()
+///     }),
+///     .. MemoryTypePolicy::default()
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryTypePolicy {
+    /// Resources that are only accessed by the GPU, such as `DeviceLocalBuffer`,
+    /// `ImmutableBuffer` and the images.
+    pub device_only: MemoryTypeFilter,
+
+    /// Buffers that the CPU writes to and the GPU reads from, such as `CpuAccessibleBuffer`
+    /// when it is not host-cached.
+    pub upload: MemoryTypeFilter,
+
+    /// Buffers that the GPU writes to and the CPU reads from, such as `CpuAccessibleBuffer`
+    /// when it is host-cached.
+    pub download: MemoryTypeFilter,
+
+    /// Buffers whose content is written by the CPU every frame, such as the ones of
+    /// `CpuBufferPool`.
+    pub dynamic: MemoryTypeFilter,
+}
+
+impl MemoryTypePolicy {
+    /// Returns the filter of the resources with the given usage.
+    #[inline]
+    pub fn for_usage(&self, usage: MemoryUsage) -> MemoryTypeFilter {
+        match usage {
+            MemoryUsage::DeviceOnly => self.device_only,
+            MemoryUsage::Upload => self.upload,
+            MemoryUsage::Download => self.download,
+            MemoryUsage::Dynamic => self.dynamic,
+        }
+    }
+}
+
+impl Default for MemoryTypePolicy {
+    #[inline]
+    fn default() -> MemoryTypePolicy {
+        let host_cached = MemoryTypeProperties {
+            host_cached: true,
+            ..MemoryTypeProperties::none()
+        };
+
+        MemoryTypePolicy {
+            device_only: MemoryTypeFilter::preferring(MemoryTypeProperties {
+                device_local: true,
+                ..MemoryTypeProperties::none()
+            }),
+            upload: MemoryTypeFilter {
+                not_preferred: host_cached,
+                ..MemoryTypeFilter::any()
+            },
+            download: MemoryTypeFilter::preferring(host_cached),
+            dynamic: MemoryTypeFilter::any(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::pool::AllocFromRequirementsFilter;
+    use crate::memory::pool::MemoryTypeFilter;
+    use crate::memory::pool::MemoryTypePolicy;
+    use crate::memory::pool::MemoryTypeProperties;
+
+    #[test]
+    fn default_policy() {
+        let (device, _) = gfx_dev_and_queue!();
+        let policy = MemoryTypePolicy::default();
+
+        for ty in device.physical_device().memory_types() {
+            let device_only = policy.device_only.filter(ty);
+            assert_eq!(
+                device_only == AllocFromRequirementsFilter::Preferred,
+                ty.is_device_local()
+            );
+
+            let upload = policy.upload.filter(ty);
+            let download = policy.download.filter(ty);
+            assert_eq!(
+                upload == AllocFromRequirementsFilter::Preferred,
+                !ty.is_host_cached()
+            );
+            assert_eq!(
+                download == AllocFromRequirementsFilter::Preferred,
+                ty.is_host_cached()
+            );
+
+            assert_eq!(
+                policy.dynamic.filter(ty),
+                AllocFromRequirementsFilter::Preferred
+            );
+        }
+    }
+
+    #[test]
+    fn forbidden() {
+        let (device, _) = gfx_dev_and_queue!();
+        let filter = MemoryTypeFilter {
+            forbidden: MemoryTypeProperties {
+                host_cached: true,
+                ..MemoryTypeProperties::none()
+            },
+            ..MemoryTypeFilter::any()
+        };
+
+        for ty in device.physical_device().memory_types() {
+            assert_eq!(
+                filter.filter(ty) == AllocFromRequirementsFilter::Forbidden,
+                ty.is_host_cached()
+            );
+        }
+    }
+}
@@ -17,10 +17,12 @@ use std::sync::Mutex;
 use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::instance::MemoryType;
+use crate::memory::device_memory::check_priority;
 use crate::memory::pool::AllocLayout;
 use crate::memory::pool::MappingRequirement;
 use crate::memory::pool::MemoryPool;
 use crate::memory::pool::MemoryPoolAlloc;
+use crate::memory::pool::MemoryPoolStatistics;
 use crate::memory::pool::StdHostVisibleMemoryTypePool;
 use crate::memory::pool::StdHostVisibleMemoryTypePoolAlloc;
 use crate::memory::pool::StdNonHostVisibleMemoryTypePool;
@@ -29,6 +31,12 @@ use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocError;
 use crate::memory::MappedDeviceMemory;
 
+/// Standard implementation of `MemoryPool` and `MemoryAllocator`, which is returned by
+/// `Device::standard_pool` and used by the buffer and image constructors.
+///
+/// Memory is allocated in large blocks for each memory type, and resources are sub-allocated from
+/// these blocks with a buddy allocator. Linear and optimal resources are taken from different
+/// blocks, so that they never need to be separated by the `buffer_image_granularity` limit.
 #[derive(Debug)]
 pub struct StdMemoryPool {
     device: Arc<Device>,
@@ -36,6 +44,9 @@ pub struct StdMemoryPool {
     // For each memory type index, stores the associated pool.
     pools:
         Mutex<HashMap<(u32, AllocLayout, MappingRequirement), Pool, BuildHasherDefault<FnvHasher>>>,
+
+    // Priority set with `set_priority`, applied to the pools created later.
+    priority: Mutex<Option<f32>>,
 }
 
 impl StdMemoryPool {
@@ -48,8 +59,156 @@ impl StdMemoryPool {
         Arc::new(StdMemoryPool {
             device: device.clone(),
             pools: Mutex::new(HashMap::with_capacity_and_hasher(cap, hasher)),
+            priority: Mutex::new(None),
         })
     }
+
+    /// Sets the priority of all the memory of the pool, including the memory that is allocated
+    /// later.
+    ///
+    /// With the `pageable_device_local_memory` feature, the implementation uses the priority to
+    /// choose which memory to move out of the device-local heaps when they are overcommitted.
+    /// Resources can be allocated from a pool with a custom priority by using the
+    /// `_with_allocator` constructors of the buffer and image types. See also `DeviceMemory::set_priority`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `pageable_device_local_memory` feature is not enabled on the device.
+    /// - Panics if `priority` is not between 0.0 and 1.0.
+    ///
+    pub fn set_priority(&self, priority: f32) {
+        check_priority(&self.device, priority);
+
+        let pools = self.pools.lock().unwrap();
+        for pool in pools.values() {
+            match *pool {
+                Pool::HostVisible(ref pool) => pool.set_priority(priority),
+                Pool::NonHostVisible(ref pool) => pool.set_priority(priority),
+            }
+        }
+        *self.priority.lock().unwrap() = Some(priority);
+    }
+
+    /// Returns statistics about all the memory allocated by this pool.
+    ///
+    /// Dedicated allocations aren't made from the pool and aren't included.
+    pub fn statistics(&self) -> MemoryPoolStatistics {
+        let pools = self.pools.lock().unwrap();
+        let mut stats = MemoryPoolStatistics::default();
+        for pool in pools.values() {
+            stats.merge(&pool.statistics());
+        }
+        stats
+    }
+
+    /// Returns statistics about the memory allocated by this pool from the given memory type.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the memory type doesn't belong to the physical device of the pool.
+    ///
+    pub fn memory_type_statistics(&self, memory_type: MemoryType) -> MemoryPoolStatistics {
+        assert_eq!(
+            memory_type.physical_device().index(),
+            self.device.physical_device().index()
+        );
+
+        let pools = self.pools.lock().unwrap();
+        let mut stats = MemoryPoolStatistics::default();
+        for (&(id, _, _), pool) in pools.iter() {
+            if id == memory_type.id() {
+                stats.merge(&pool.statistics());
+            }
+        }
+        stats
+    }
+}
+
+impl StdMemoryPool {
+    /// Moves allocations out of the least used blocks of memory, so that these blocks can be
+    /// freed and the memory of long-running programs doesn't become too fragmented.
+    ///
+    /// `allocations` contains the allocations that are allowed to move, usually those of the
+    /// buffers whose content can be copied. A block is only freed if all of its allocations are
+    /// in the list. For each allocation that is moved, the returned list contains its index in
+    /// `allocations` and its new location.
+    ///
+    /// The content isn't copied and the resources aren't modified. For each move, you must create
+    /// a new resource bound to the new allocation, copy the content of the old resource to it
+    /// on the GPU, and replace the old resource with the new one everywhere it is used, including
+    /// in descriptor sets. The blocks are freed once the old resources have been dropped. See
+    /// `DeviceLocalBuffer::defragment` for an implementation of these steps.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the allocations doesn't belong to this pool.
+    ///
+    pub fn defragment(
+        me: &Arc<StdMemoryPool>,
+        allocations: &[&StdMemoryPoolAlloc],
+    ) -> Vec<DefragmentationMove> {
+        for alloc in allocations {
+            assert!(Arc::ptr_eq(&alloc.pool, me));
+        }
+
+        let pools = me.pools.lock().unwrap();
+        let mut moves = Vec::new();
+
+        for pool in pools.values() {
+            match *pool {
+                Pool::HostVisible(ref pool) => {
+                    let (indices, allocs): (Vec<usize>, Vec<_>) = allocations
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, alloc)| match alloc.inner {
+                            StdMemoryPoolAllocInner::HostVisible(ref alloc)
+                                if Arc::ptr_eq(alloc.pool(), pool) =>
+                            {
+                                Some((index, alloc))
+                            }
+                            _ => None,
+                        })
+                        .unzip();
+                    for (index, alloc) in StdHostVisibleMemoryTypePool::defragment(pool, &allocs) {
+                        moves.push(DefragmentationMove {
+                            index: indices[index],
+                            allocation: StdMemoryPoolAlloc {
+                                inner: StdMemoryPoolAllocInner::HostVisible(alloc),
+                                pool: me.clone(),
+                            },
+                        });
+                    }
+                }
+                Pool::NonHostVisible(ref pool) => {
+                    let (indices, allocs): (Vec<usize>, Vec<_>) = allocations
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, alloc)| match alloc.inner {
+                            StdMemoryPoolAllocInner::NonHostVisible(ref alloc)
+                                if Arc::ptr_eq(alloc.pool(), pool) =>
+                            {
+                                Some((index, alloc))
+                            }
+                            _ => None,
+                        })
+                        .unzip();
+                    for (index, alloc) in StdNonHostVisibleMemoryTypePool::defragment(pool, &allocs)
+                    {
+                        moves.push(DefragmentationMove {
+                            index: indices[index],
+                            allocation: StdMemoryPoolAlloc {
+                                inner: StdMemoryPoolAllocInner::NonHostVisible(alloc),
+                                pool: me.clone(),
+                            },
+                        });
+                    }
+                }
+            }
+        }
+
+        moves.sort_by_key(|m| m.index);
+        moves
+    }
 }
 
 fn generic_allocation(
@@ -88,6 +247,9 @@ fn generic_allocation(
         Entry::Vacant(entry) => {
             if memory_type_host_visible {
                 let pool = StdHostVisibleMemoryTypePool::new(mem_pool.device.clone(), memory_type);
+                if let Some(priority) = *mem_pool.priority.lock().unwrap() {
+                    pool.set_priority(priority);
+                }
                 entry.insert(Pool::HostVisible(pool.clone()));
                 let alloc = StdHostVisibleMemoryTypePool::alloc(&pool, size, alignment)?;
                 let inner = StdMemoryPoolAllocInner::HostVisible(alloc);
@@ -98,6 +260,9 @@ fn generic_allocation(
             } else {
                 let pool =
                     StdNonHostVisibleMemoryTypePool::new(mem_pool.device.clone(), memory_type);
+                if let Some(priority) = *mem_pool.priority.lock().unwrap() {
+                    pool.set_priority(priority);
+                }
                 entry.insert(Pool::NonHostVisible(pool.clone()));
                 let alloc = StdNonHostVisibleMemoryTypePool::alloc(&pool, size, alignment)?;
                 let inner = StdMemoryPoolAllocInner::NonHostVisible(alloc);
@@ -151,6 +316,9 @@ fn generit_allocation_with_exportable_fd(
         Entry::Vacant(entry) => {
             if memory_type_host_visible {
                 let pool = StdHostVisibleMemoryTypePool::new(mem_pool.device.clone(), memory_type);
+                if let Some(priority) = *mem_pool.priority.lock().unwrap() {
+                    pool.set_priority(priority);
+                }
                 entry.insert(Pool::HostVisible(pool.clone()));
                 let alloc =
                     StdHostVisibleMemoryTypePool::alloc_with_exportable_fd(&pool, size, alignment)?;
@@ -162,6 +330,9 @@ fn generit_allocation_with_exportable_fd(
             } else {
                 let pool =
                     StdNonHostVisibleMemoryTypePool::new(mem_pool.device.clone(), memory_type);
+                if let Some(priority) = *mem_pool.priority.lock().unwrap() {
+                    pool.set_priority(priority);
+                }
                 entry.insert(Pool::NonHostVisible(pool.clone()));
                 let alloc = StdNonHostVisibleMemoryTypePool::alloc_with_exportable_fd(
                     &pool, size, alignment,
@@ -224,6 +395,25 @@ enum Pool {
     NonHostVisible(Arc<StdNonHostVisibleMemoryTypePool>),
 }
 
+impl Pool {
+    #[inline]
+    fn statistics(&self) -> MemoryPoolStatistics {
+        match *self {
+            Pool::HostVisible(ref pool) => pool.statistics(),
+            Pool::NonHostVisible(ref pool) => pool.statistics(),
+        }
+    }
+}
+
+/// An allocation that has been moved by `StdMemoryPool::defragment`.
+#[derive(Debug)]
+pub struct DefragmentationMove {
+    /// Index of the allocation in the list that was passed to `defragment`.
+    pub index: usize,
+    /// The new location of the allocation.
+    pub allocation: StdMemoryPoolAlloc,
+}
+
 #[derive(Debug)]
 pub struct StdMemoryPoolAlloc {
     inner: StdMemoryPoolAllocInner,
@@ -271,3 +461,90 @@ enum StdMemoryPoolAllocInner {
     NonHostVisible(StdNonHostVisibleMemoryTypePoolAlloc),
     HostVisible(StdHostVisibleMemoryTypePoolAlloc),
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::pool::AllocLayout;
+    use crate::memory::pool::MappingRequirement;
+    use crate::memory::pool::MemoryPool;
+    use crate::memory::pool::MemoryPoolAlloc;
+    use crate::memory::pool::StdMemoryPool;
+    use crate::VulkanObject;
+
+    #[test]
+    fn statistics() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = StdMemoryPool::new(device.clone());
+        let ty = device.physical_device().memory_types().next().unwrap();
+        assert_eq!(pool.statistics().num_blocks, 0);
+
+        let a = pool
+            .alloc_generic(
+                ty,
+                1024,
+                1,
+                AllocLayout::Linear,
+                MappingRequirement::DoNotMap,
+            )
+            .unwrap();
+        let b = pool
+            .alloc_generic(
+                ty,
+                4096,
+                1,
+                AllocLayout::Linear,
+                MappingRequirement::DoNotMap,
+            )
+            .unwrap();
+        let stats = pool.statistics();
+        assert_eq!(stats.num_blocks, 1);
+        assert_eq!(stats.num_allocations, 2);
+        assert!(stats.used >= 1024 + 4096);
+        assert!(stats.allocated >= stats.used + stats.largest_free_range);
+        assert_eq!(pool.memory_type_statistics(ty), stats);
+
+        drop(a);
+        drop(b);
+        let stats = pool.statistics();
+        assert_eq!(stats.num_allocations, 0);
+        assert_eq!(stats.used, 0);
+        assert_eq!(stats.fragmentation(), 0.0);
+    }
+
+    #[test]
+    fn defragment() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = StdMemoryPool::new(device.clone());
+        let ty = device.physical_device().memory_types().next().unwrap();
+        let alloc = |size| {
+            pool.alloc_generic(
+                ty,
+                size,
+                1,
+                AllocLayout::Linear,
+                MappingRequirement::DoNotMap,
+            )
+            .unwrap()
+        };
+
+        // Fill the first block, then allocate from a second one.
+        let mut first = (0..8).map(|_| alloc(1024 * 1024)).collect::<Vec<_>>();
+        let second = alloc(4096);
+        assert_eq!(pool.statistics().num_blocks, 2);
+        assert!(StdMemoryPool::defragment(&pool, &[&second]).is_empty());
+
+        // Once there is room in the first block, the allocation of the second block is moved.
+        first.remove(0);
+        let moves = StdMemoryPool::defragment(&pool, &[&second]);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].index, 0);
+        assert_eq!(
+            moves[0].allocation.memory().internal_object(),
+            first[0].memory().internal_object()
+        );
+        assert_eq!(moves[0].allocation.size(), second.size());
+
+        drop(second);
+        assert_eq!(pool.statistics().num_allocations, 8);
+    }
+}
@@ -37,6 +37,10 @@ pub struct Blend {
     /// If you pass `None`, then this state will be considered as dynamic and the blend constants
     /// will need to be set when you build the command buffer.
     pub blend_constants: Option<[f32; 4]>,
+
+    /// Parameters of the advanced blend operations. Ignored if no attachment uses an advanced
+    /// blend operation.
+    pub advanced: AdvancedBlend,
 }
 
 impl Blend {
@@ -47,6 +51,7 @@ impl Blend {
             logic_op: None,
             attachments: AttachmentsBlend::Collective(AttachmentBlend::pass_through()),
             blend_constants: Some([0.0, 0.0, 0.0, 0.0]),
+            advanced: AdvancedBlend::default(),
         }
     }
 
@@ -57,6 +62,7 @@ impl Blend {
             logic_op: None,
             attachments: AttachmentsBlend::Collective(AttachmentBlend::alpha_blending()),
             blend_constants: Some([0.0, 0.0, 0.0, 0.0]),
+            advanced: AdvancedBlend::default(),
         }
     }
 }
@@ -68,10 +74,40 @@ pub enum AttachmentsBlend {
     Collective(AttachmentBlend),
 
     /// Each attachment will behave differently. Note that this requires enabling the
-    /// `independent_blend` feature.
+    /// `independent_blend` feature, unless all the elements are identical.
     Individual(Vec<AttachmentBlend>),
 }
 
+impl AttachmentsBlend {
+    /// Replaces the states of some attachments, turning the blending into individual blending
+    /// for `num_attachments` attachments if it was collective.
+    ///
+    /// Attachments past the end of an individual list are set to pass-through.
+    pub(crate) fn with_overrides<'a, I>(
+        self,
+        overrides: I,
+        num_attachments: u32,
+    ) -> AttachmentsBlend
+    where
+        I: IntoIterator<Item = &'a (u32, AttachmentBlend)>,
+    {
+        let mut attachments = match self {
+            AttachmentsBlend::Collective(blend) => vec![blend; num_attachments as usize],
+            AttachmentsBlend::Individual(blend) => blend,
+        };
+
+        for &(attachment, ref blend) in overrides {
+            let index = attachment as usize;
+            if attachments.len() <= index {
+                attachments.resize(index + 1, AttachmentBlend::pass_through());
+            }
+            attachments[index] = blend.clone();
+        }
+
+        AttachmentsBlend::Individual(attachments)
+    }
+}
+
 /// Describes how the blending system should behave for an individual attachment.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AttachmentBlend {
@@ -231,6 +267,12 @@ impl Default for LogicOp {
     }
 }
 
+/// The operation that combines the source and destination values when blending.
+///
+/// The operations after `Max` are advanced blend operations, as provided by the
+/// `VK_EXT_blend_operation_advanced` extension. They mostly correspond to the compositing
+/// operations of 2D graphics libraries. When an advanced operation is used, the color and alpha
+/// operations must be the same, and the blend factors are ignored.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u32)]
 pub enum BlendOp {
@@ -239,6 +281,128 @@ pub enum BlendOp {
     ReverseSubtract = vk::BLEND_OP_REVERSE_SUBTRACT,
     Min = vk::BLEND_OP_MIN,
     Max = vk::BLEND_OP_MAX,
+    Zero = vk::BLEND_OP_ZERO_EXT,
+    Src = vk::BLEND_OP_SRC_EXT,
+    Dst = vk::BLEND_OP_DST_EXT,
+    SrcOver = vk::BLEND_OP_SRC_OVER_EXT,
+    DstOver = vk::BLEND_OP_DST_OVER_EXT,
+    SrcIn = vk::BLEND_OP_SRC_IN_EXT,
+    DstIn = vk::BLEND_OP_DST_IN_EXT,
+    SrcOut = vk::BLEND_OP_SRC_OUT_EXT,
+    DstOut = vk::BLEND_OP_DST_OUT_EXT,
+    SrcAtop = vk::BLEND_OP_SRC_ATOP_EXT,
+    DstAtop = vk::BLEND_OP_DST_ATOP_EXT,
+    Xor = vk::BLEND_OP_XOR_EXT,
+    Multiply = vk::BLEND_OP_MULTIPLY_EXT,
+    Screen = vk::BLEND_OP_SCREEN_EXT,
+    Overlay = vk::BLEND_OP_OVERLAY_EXT,
+    Darken = vk::BLEND_OP_DARKEN_EXT,
+    Lighten = vk::BLEND_OP_LIGHTEN_EXT,
+    ColorDodge = vk::BLEND_OP_COLORDODGE_EXT,
+    ColorBurn = vk::BLEND_OP_COLORBURN_EXT,
+    HardLight = vk::BLEND_OP_HARDLIGHT_EXT,
+    SoftLight = vk::BLEND_OP_SOFTLIGHT_EXT,
+    Difference = vk::BLEND_OP_DIFFERENCE_EXT,
+    Exclusion = vk::BLEND_OP_EXCLUSION_EXT,
+    Invert = vk::BLEND_OP_INVERT_EXT,
+    InvertRgb = vk::BLEND_OP_INVERT_RGB_EXT,
+    LinearDodge = vk::BLEND_OP_LINEARDODGE_EXT,
+    LinearBurn = vk::BLEND_OP_LINEARBURN_EXT,
+    VividLight = vk::BLEND_OP_VIVIDLIGHT_EXT,
+    LinearLight = vk::BLEND_OP_LINEARLIGHT_EXT,
+    PinLight = vk::BLEND_OP_PINLIGHT_EXT,
+    HardMix = vk::BLEND_OP_HARDMIX_EXT,
+    HslHue = vk::BLEND_OP_HSL_HUE_EXT,
+    HslSaturation = vk::BLEND_OP_HSL_SATURATION_EXT,
+    HslColor = vk::BLEND_OP_HSL_COLOR_EXT,
+    HslLuminosity = vk::BLEND_OP_HSL_LUMINOSITY_EXT,
+    Plus = vk::BLEND_OP_PLUS_EXT,
+    PlusClamped = vk::BLEND_OP_PLUS_CLAMPED_EXT,
+    PlusClampedAlpha = vk::BLEND_OP_PLUS_CLAMPED_ALPHA_EXT,
+    PlusDarker = vk::BLEND_OP_PLUS_DARKER_EXT,
+    Minus = vk::BLEND_OP_MINUS_EXT,
+    MinusClamped = vk::BLEND_OP_MINUS_CLAMPED_EXT,
+    Contrast = vk::BLEND_OP_CONTRAST_EXT,
+    InvertOvg = vk::BLEND_OP_INVERT_OVG_EXT,
+    Red = vk::BLEND_OP_RED_EXT,
+    Green = vk::BLEND_OP_GREEN_EXT,
+    Blue = vk::BLEND_OP_BLUE_EXT,
+}
+
+impl BlendOp {
+    /// Returns true if the operation is one of the advanced blend operations of
+    /// `VK_EXT_blend_operation_advanced`.
+    #[inline]
+    pub fn is_advanced(&self) -> bool {
+        *self as u32 >= vk::BLEND_OP_ZERO_EXT
+    }
+
+    /// Returns true if the operation is an advanced blend operation that can only be used if the
+    /// `advanced_blend_all_operations` property of the device is true.
+    #[inline]
+    pub fn requires_advanced_blend_all_operations(&self) -> bool {
+        self.is_advanced()
+            && !matches!(
+                *self,
+                BlendOp::Multiply
+                    | BlendOp::Screen
+                    | BlendOp::Overlay
+                    | BlendOp::Darken
+                    | BlendOp::Lighten
+                    | BlendOp::ColorDodge
+                    | BlendOp::ColorBurn
+                    | BlendOp::HardLight
+                    | BlendOp::SoftLight
+                    | BlendOp::Difference
+                    | BlendOp::Exclusion
+                    | BlendOp::HslHue
+                    | BlendOp::HslSaturation
+                    | BlendOp::HslColor
+                    | BlendOp::HslLuminosity
+            )
+    }
+}
+
+/// Parameters of the advanced blend operations of a graphics pipeline.
+///
+/// These are ignored if none of the color attachments use an advanced blend operation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AdvancedBlend {
+    /// Whether the source color is considered to be premultiplied by its alpha. Setting this to
+    /// false requires the `advanced_blend_non_premultiplied_src_color` property.
+    pub src_premultiplied: bool,
+
+    /// Whether the destination color is considered to be premultiplied by its alpha. Setting
+    /// this to false requires the `advanced_blend_non_premultiplied_dst_color` property.
+    pub dst_premultiplied: bool,
+
+    /// How the coverage of the source and destination are assumed to overlap.
+    pub overlap: BlendOverlap,
+}
+
+impl Default for AdvancedBlend {
+    #[inline]
+    fn default() -> AdvancedBlend {
+        AdvancedBlend {
+            src_premultiplied: true,
+            dst_premultiplied: true,
+            overlap: BlendOverlap::Uncorrelated,
+        }
+    }
+}
+
+/// How the coverage of the source and destination of an advanced blend operation overlap.
+///
+/// Anything other than `Uncorrelated` requires the `advanced_blend_correlated_overlap` property.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BlendOverlap {
+    /// The source and destination coverage are uncorrelated.
+    Uncorrelated = vk::BLEND_OVERLAP_UNCORRELATED_EXT,
+    /// The source and destination coverage are considered to have minimal overlap.
+    Disjoint = vk::BLEND_OVERLAP_DISJOINT_EXT,
+    /// The source and destination coverage are considered to have maximal overlap.
+    Conjoint = vk::BLEND_OVERLAP_CONJOINT_EXT,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -264,3 +428,55 @@ pub enum BlendFactor {
     Src1Alpha = vk::BLEND_FACTOR_SRC1_ALPHA,
     OneMinusSrc1Alpha = vk::BLEND_FACTOR_ONE_MINUS_SRC1_ALPHA,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::blend::AttachmentBlend;
+    use crate::pipeline::blend::AttachmentsBlend;
+    use crate::pipeline::blend::BlendOp;
+
+    #[test]
+    fn advanced_blend_ops() {
+        assert!(!BlendOp::Add.is_advanced());
+        assert!(!BlendOp::Max.is_advanced());
+        assert!(BlendOp::Zero.is_advanced());
+        assert!(BlendOp::Blue.is_advanced());
+
+        assert!(!BlendOp::Add.requires_advanced_blend_all_operations());
+        assert!(!BlendOp::Multiply.requires_advanced_blend_all_operations());
+        assert!(!BlendOp::HslLuminosity.requires_advanced_blend_all_operations());
+        assert!(BlendOp::SrcOver.requires_advanced_blend_all_operations());
+        assert!(BlendOp::Plus.requires_advanced_blend_all_operations());
+    }
+
+    #[test]
+    fn overrides_keep_collective_state() {
+        let blend = AttachmentsBlend::Collective(AttachmentBlend::alpha_blending())
+            .with_overrides(&[(1, AttachmentBlend::pass_through())], 4);
+
+        assert_eq!(
+            blend,
+            AttachmentsBlend::Individual(vec![
+                AttachmentBlend::alpha_blending(),
+                AttachmentBlend::pass_through(),
+                AttachmentBlend::alpha_blending(),
+                AttachmentBlend::alpha_blending(),
+            ])
+        );
+    }
+
+    #[test]
+    fn overrides_extend_individual_state() {
+        let blend = AttachmentsBlend::Individual(vec![AttachmentBlend::alpha_blending()])
+            .with_overrides(&[(2, AttachmentBlend::alpha_blending())], 3);
+
+        assert_eq!(
+            blend,
+            AttachmentsBlend::Individual(vec![
+                AttachmentBlend::alpha_blending(),
+                AttachmentBlend::pass_through(),
+                AttachmentBlend::alpha_blending(),
+            ])
+        );
+    }
+}
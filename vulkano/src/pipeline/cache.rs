@@ -125,7 +125,7 @@ impl PipelineCache {
             check_errors(vk.CreatePipelineCache(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -243,7 +243,11 @@ impl Drop for PipelineCache {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyPipelineCache(self.device.internal_object(), self.cache, ptr::null());
+            vk.DestroyPipelineCache(
+                self.device.internal_object(),
+                self.cache,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
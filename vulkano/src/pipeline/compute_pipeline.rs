@@ -8,6 +8,7 @@
 // according to those terms.
 
 use std::error;
+use std::ffi::CStr;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
@@ -26,16 +27,26 @@ use crate::descriptor::pipeline_layout::PipelineLayoutNotSupersetError;
 use crate::descriptor::pipeline_layout::PipelineLayoutSuperset;
 use crate::descriptor::pipeline_layout::PipelineLayoutSys;
 use crate::pipeline::cache::PipelineCache;
+use crate::pipeline::executable;
+use crate::pipeline::executable::PipelineExecutableError;
+use crate::pipeline::executable::PipelineExecutableInternalRepresentation;
+use crate::pipeline::executable::PipelineExecutableProperties;
+use crate::pipeline::executable::PipelineExecutableStatistic;
+use crate::pipeline::reflect::ShaderStage;
 use crate::pipeline::shader::EntryPointAbstract;
 use crate::pipeline::shader::SpecializationConstants;
+use crate::pipeline::shader::SpecializationConstantsError;
 
 use crate::check_errors;
 use crate::device::Device;
 use crate::device::DeviceOwned;
+use crate::features::Features;
+use crate::instance::SubgroupFeatures;
 use crate::vk;
 use crate::Error;
 use crate::OomError;
 use crate::SafeDeref;
+use crate::Success;
 use crate::VulkanObject;
 
 /// A pipeline object that describes to the Vulkan implementation how it should perform compute
@@ -52,6 +63,8 @@ use crate::VulkanObject;
 pub struct ComputePipeline<Pl> {
     inner: Inner,
     pipeline_layout: Pl,
+    capture_statistics: bool,
+    capture_internal_representations: bool,
 }
 
 struct Inner {
@@ -113,6 +126,40 @@ impl<Pl> ComputePipeline<Pl> {
         }
     }
 
+    /// Same as `with_pipeline_layout`, but also asks the implementation to keep statistics and
+    /// internal representations of the executables it compiles for the pipeline. They can be
+    /// queried with `executable_statistics` and `executable_internal_representations`.
+    ///
+    /// Capturing either requires the `khr_pipeline_executable_properties` device extension and
+    /// the `pipeline_executable_info` feature.
+    pub fn with_capture<Cs>(
+        device: Arc<Device>,
+        shader: &Cs,
+        specialization: &Cs::SpecializationConstants,
+        pipeline_layout: Pl,
+        cache: Option<Arc<PipelineCache>>,
+        capture_statistics: bool,
+        capture_internal_representations: bool,
+    ) -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
+    where
+        Cs::PipelineLayout: Clone,
+        Cs: EntryPointAbstract,
+        Pl: PipelineLayoutAbstract,
+    {
+        unsafe {
+            PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout, shader.layout())?;
+            ComputePipeline::create(
+                device,
+                shader,
+                specialization,
+                pipeline_layout,
+                cache,
+                capture_statistics,
+                capture_internal_representations,
+            )
+        }
+    }
+
     /// Same as `with_pipeline_layout`, but doesn't check whether the pipeline layout is a
     /// superset of what the shader expects.
     pub unsafe fn with_unchecked_pipeline_layout<Cs>(
@@ -122,6 +169,31 @@ impl<Pl> ComputePipeline<Pl> {
         pipeline_layout: Pl,
         cache: Option<Arc<PipelineCache>>,
     ) -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
+    where
+        Cs::PipelineLayout: Clone,
+        Cs: EntryPointAbstract,
+        Pl: PipelineLayoutAbstract,
+    {
+        ComputePipeline::create(
+            device,
+            shader,
+            specialization,
+            pipeline_layout,
+            cache,
+            false,
+            false,
+        )
+    }
+
+    unsafe fn create<Cs>(
+        device: Arc<Device>,
+        shader: &Cs,
+        specialization: &Cs::SpecializationConstants,
+        pipeline_layout: Pl,
+        cache: Option<Arc<PipelineCache>>,
+        capture_statistics: bool,
+        capture_internal_representations: bool,
+    ) -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
     where
         Cs::PipelineLayout: Clone,
         Cs: EntryPointAbstract,
@@ -129,6 +201,70 @@ impl<Pl> ComputePipeline<Pl> {
     {
         let vk = device.pointers();
 
+        shader
+            .module()
+            .check_specialization_constants(specialization)?;
+
+        let missing = shader.module().missing_features(device.enabled_features());
+        if missing != Features::none() {
+            return Err(ComputePipelineCreationError::ShaderFeaturesNotEnabled {
+                missing: Box::new(missing),
+            });
+        }
+
+        if let Some(reflection) = shader.module().reflection() {
+            let required = reflection.subgroup_features();
+            if required != SubgroupFeatures::none() {
+                let physical_device = device.physical_device();
+                let properties = physical_device.extended_properties();
+                let supported = match *properties.subgroup_supported_stages() {
+                    Some(stages) if stages.compute => properties
+                        .subgroup_supported_operations()
+                        .unwrap_or_else(SubgroupFeatures::none),
+                    _ => SubgroupFeatures::none(),
+                };
+
+                if !supported.is_superset_of(&required) {
+                    return Err(
+                        ComputePipelineCreationError::SubgroupOperationsNotSupported {
+                            missing: required.difference(&supported),
+                        },
+                    );
+                }
+            }
+        }
+
+        let identifier_info = shader.module().stage_identifier_info();
+        if identifier_info.is_some() && !device.enabled_features().pipeline_creation_cache_control {
+            return Err(
+                ComputePipelineCreationError::PipelineCreationCacheControlFeatureNotEnabled,
+            );
+        }
+
+        let mut flags = 0;
+        if identifier_info.is_some() {
+            flags |= vk::PIPELINE_CREATE_FAIL_ON_PIPELINE_COMPILE_REQUIRED_BIT_EXT;
+        }
+        if capture_statistics || capture_internal_representations {
+            if !device
+                .loaded_extensions()
+                .khr_pipeline_executable_properties
+            {
+                return Err(
+                    ComputePipelineCreationError::PipelineExecutablePropertiesExtensionNotEnabled,
+                );
+            }
+            if !device.enabled_features().pipeline_executable_info {
+                return Err(ComputePipelineCreationError::PipelineExecutableInfoFeatureNotEnabled);
+            }
+        }
+        if capture_statistics {
+            flags |= vk::PIPELINE_CREATE_CAPTURE_STATISTICS_BIT_KHR;
+        }
+        if capture_internal_representations {
+            flags |= vk::PIPELINE_CREATE_CAPTURE_INTERNAL_REPRESENTATIONS_BIT_KHR;
+        }
+
         let pipeline = {
             let spec_descriptors = Cs::SpecializationConstants::descriptors();
             let specialization = vk::SpecializationInfo {
@@ -140,7 +276,9 @@ impl<Pl> ComputePipeline<Pl> {
 
             let stage = vk::PipelineShaderStageCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: identifier_info
+                    .as_ref()
+                    .map_or(ptr::null(), |info| info as *const _ as *const _),
                 flags: 0,
                 stage: vk::SHADER_STAGE_COMPUTE_BIT,
                 module: shader.module().internal_object(),
@@ -155,7 +293,7 @@ impl<Pl> ComputePipeline<Pl> {
             let infos = vk::ComputePipelineCreateInfo {
                 sType: vk::STRUCTURE_TYPE_COMPUTE_PIPELINE_CREATE_INFO,
                 pNext: ptr::null(),
-                flags: 0,
+                flags,
                 stage: stage,
                 layout: PipelineLayoutAbstract::sys(&pipeline_layout).internal_object(),
                 basePipelineHandle: 0,
@@ -168,23 +306,41 @@ impl<Pl> ComputePipeline<Pl> {
             };
 
             let mut output = MaybeUninit::uninit();
-            check_errors(vk.CreateComputePipelines(
+            let success = check_errors(vk.CreateComputePipelines(
                 device.internal_object(),
                 cache_handle,
                 1,
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
+            if let Success::PipelineCompileRequired = success {
+                return Err(ComputePipelineCreationError::PipelineCompileRequired);
+            }
             output.assume_init()
         };
 
+        // Registering the entry point, so that the messages printed by the shader can be tagged
+        // with it.
+        let instance = device.instance();
+        if instance.enabled_validation_features().debug_printf {
+            instance.debug_printf_registry().register(
+                pipeline,
+                Some((
+                    ShaderStage::Compute,
+                    shader.name().to_string_lossy().into_owned(),
+                )),
+            );
+        }
+
         Ok(ComputePipeline {
             inner: Inner {
                 device: device.clone(),
                 pipeline: pipeline,
             },
             pipeline_layout: pipeline_layout,
+            capture_statistics,
+            capture_internal_representations,
         })
     }
 }
@@ -203,11 +359,82 @@ impl<Pl> ComputePipeline<Pl> {
         &self.inner.device
     }
 
+    /// Assigns a human-readable name to the pipeline for debugging purposes.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_debug_utils` extension isn't loaded on the instance.
+    ///
+    #[inline]
+    pub fn set_debug_name(&self, name: &CStr) -> Result<(), OomError> {
+        unsafe {
+            self.device()
+                .set_object_name_raw(vk::OBJECT_TYPE_PIPELINE, self.inner.pipeline, name)
+        }
+    }
+
     /// Returns the pipeline layout used in this compute pipeline.
     #[inline]
     pub fn layout(&self) -> &Pl {
         &self.pipeline_layout
     }
+
+    /// Returns the properties of the executables that the implementation compiled for this
+    /// pipeline.
+    ///
+    /// Requires the `khr_pipeline_executable_properties` device extension and the
+    /// `pipeline_executable_info` feature.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutableError> {
+        unsafe { executable::executable_properties(&self.inner.device, self.inner.pipeline) }
+    }
+
+    /// Returns the statistics of the executable of index `executable_index`, in the order
+    /// returned by `executable_properties`.
+    ///
+    /// The pipeline must have been built by `with_capture` with `capture_statistics` enabled.
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutableError> {
+        if !self.capture_statistics {
+            return Err(PipelineExecutableError::StatisticsNotCaptured);
+        }
+
+        unsafe {
+            executable::executable_statistics(
+                &self.inner.device,
+                self.inner.pipeline,
+                executable_index,
+            )
+        }
+    }
+
+    /// Returns the internal representations of the executable of index `executable_index`, in
+    /// the order returned by `executable_properties`.
+    ///
+    /// The pipeline must have been built by `with_capture` with
+    /// `capture_internal_representations` enabled.
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+        if !self.capture_internal_representations {
+            return Err(PipelineExecutableError::InternalRepresentationsNotCaptured);
+        }
+
+        unsafe {
+            executable::executable_internal_representations(
+                &self.inner.device,
+                self.inner.pipeline,
+                executable_index,
+            )
+        }
+    }
 }
 
 /// Trait implemented on all compute pipelines.
@@ -320,9 +547,18 @@ unsafe impl<Pl> VulkanObject for ComputePipeline<Pl> {
 impl Drop for Inner {
     #[inline]
     fn drop(&mut self) {
+        let instance = self.device.instance();
+        if instance.enabled_validation_features().debug_printf {
+            instance.debug_printf_registry().unregister(self.pipeline);
+        }
+
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyPipeline(self.device.internal_object(), self.pipeline, ptr::null());
+            vk.DestroyPipeline(
+                self.device.internal_object(),
+                self.pipeline,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -336,6 +572,31 @@ pub enum ComputePipelineCreationError {
     PipelineLayoutCreationError(PipelineLayoutCreationError),
     /// The pipeline layout is not compatible with what the shader expects.
     IncompatiblePipelineLayout(PipelineLayoutNotSupersetError),
+    /// The specialization constants don't match the declarations of the shader module.
+    IncompatibleSpecializationConstants(SpecializationConstantsError),
+    /// The shader module was built from an identifier, but the `pipeline_creation_cache_control`
+    /// feature is not enabled.
+    PipelineCreationCacheControlFeatureNotEnabled,
+    /// The shader module was built from an identifier, and the pipeline is not in the pipeline
+    /// cache. The pipeline must be created from the SPIR-V code instead.
+    PipelineCompileRequired,
+    /// The `khr_pipeline_executable_properties` extension must be enabled in order to capture
+    /// statistics or internal representations.
+    PipelineExecutablePropertiesExtensionNotEnabled,
+    /// The `pipeline_executable_info` feature must be enabled in order to capture statistics or
+    /// internal representations.
+    PipelineExecutableInfoFeatureNotEnabled,
+    /// The shader requires features that are not enabled on the device.
+    ShaderFeaturesNotEnabled {
+        /// The features that are required but not enabled.
+        missing: Box<Features>,
+    },
+    /// The shader uses kinds of subgroup operations that the device doesn't support in compute
+    /// shaders.
+    SubgroupOperationsNotSupported {
+        /// The kinds of operations that are used but not supported.
+        missing: SubgroupFeatures,
+    },
 }
 
 impl error::Error for ComputePipelineCreationError {
@@ -345,6 +606,13 @@ impl error::Error for ComputePipelineCreationError {
             ComputePipelineCreationError::OomError(ref err) => Some(err),
             ComputePipelineCreationError::PipelineLayoutCreationError(ref err) => Some(err),
             ComputePipelineCreationError::IncompatiblePipelineLayout(ref err) => Some(err),
+            ComputePipelineCreationError::IncompatibleSpecializationConstants(ref err) => Some(err),
+            ComputePipelineCreationError::PipelineCreationCacheControlFeatureNotEnabled => None,
+            ComputePipelineCreationError::PipelineCompileRequired => None,
+            ComputePipelineCreationError::PipelineExecutablePropertiesExtensionNotEnabled => None,
+            ComputePipelineCreationError::PipelineExecutableInfoFeatureNotEnabled => None,
+            ComputePipelineCreationError::ShaderFeaturesNotEnabled { .. } => None,
+            ComputePipelineCreationError::SubgroupOperationsNotSupported { .. } => None,
         }
     }
 }
@@ -363,6 +631,30 @@ impl fmt::Display for ComputePipelineCreationError {
                 ComputePipelineCreationError::IncompatiblePipelineLayout(_) => {
                     "the pipeline layout is not compatible with what the shader expects"
                 }
+                ComputePipelineCreationError::IncompatibleSpecializationConstants(_) => {
+                    "the specialization constants don't match the declarations of the shader module"
+                }
+                ComputePipelineCreationError::PipelineCreationCacheControlFeatureNotEnabled => {
+                    "the `pipeline_creation_cache_control` feature must be enabled to use a shader \
+                     module identifier"
+                }
+                ComputePipelineCreationError::PipelineCompileRequired => {
+                    "the pipeline is not in the pipeline cache and must be compiled"
+                }
+                ComputePipelineCreationError::PipelineExecutablePropertiesExtensionNotEnabled => {
+                    "the `khr_pipeline_executable_properties` extension must be enabled in order to \
+                     capture statistics or internal representations"
+                }
+                ComputePipelineCreationError::PipelineExecutableInfoFeatureNotEnabled => {
+                    "the `pipeline_executable_info` feature must be enabled in order to capture \
+                     statistics or internal representations"
+                }
+                ComputePipelineCreationError::ShaderFeaturesNotEnabled { .. } => {
+                    "the shader requires features that are not enabled on the device"
+                }
+                ComputePipelineCreationError::SubgroupOperationsNotSupported { .. } => {
+                    "the shader uses subgroup operations that the device doesn't support"
+                }
             }
         )
     }
@@ -389,6 +681,13 @@ impl From<PipelineLayoutNotSupersetError> for ComputePipelineCreationError {
     }
 }
 
+impl From<SpecializationConstantsError> for ComputePipelineCreationError {
+    #[inline]
+    fn from(err: SpecializationConstantsError) -> ComputePipelineCreationError {
+        ComputePipelineCreationError::IncompatibleSpecializationConstants(err)
+    }
+}
+
 impl From<Error> for ComputePipelineCreationError {
     #[inline]
     fn from(err: Error) -> ComputePipelineCreationError {
@@ -417,14 +716,59 @@ mod tests {
     use crate::descriptor::pipeline_layout::PipelineLayoutAbstract;
     use crate::descriptor::pipeline_layout::PipelineLayoutDesc;
     use crate::descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+    use crate::device::Device;
+    use crate::device::DeviceExtensions;
+    use crate::features::Features;
+    use crate::instance;
+    use crate::pipeline::executable::PipelineExecutableError;
+    use crate::pipeline::shader::EntryPointAbstract;
     use crate::pipeline::shader::ShaderModule;
+    use crate::pipeline::shader::ShaderModuleIdentifierError;
     use crate::pipeline::shader::SpecializationConstants;
+    use crate::pipeline::shader::SpecializationConstantsError;
     use crate::pipeline::shader::SpecializationMapEntry;
     use crate::pipeline::ComputePipeline;
-    use std::ffi::CStr;
-    use std::sync::Arc;
+    use crate::pipeline::ComputePipelineCreationError;
     use crate::sync::now;
     use crate::sync::GpuFuture;
+    use std::ffi::CStr;
+    use std::sync::Arc;
+
+    // A compute shader that writes the value of a specialization constant to a buffer:
+    //
+    // #version 450
+    //
+    // layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+    //
+    // layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+    //
+    // layout(set = 0, binding = 0) buffer Output {
+    //     int write;
+    // } write;
+    //
+    // void main() {
+    //     write.write = VALUE;
+    // }
+    const SPEC_CONSTANT_MODULE: [u8; 480] = [
+        3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 14, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0, 11,
+        0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0, 0, 0, 0, 14, 0,
+        3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 5, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0,
+        0, 0, 16, 0, 6, 0, 4, 0, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 3, 0, 3, 0,
+        2, 0, 0, 0, 194, 1, 0, 0, 5, 0, 4, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 4,
+        0, 7, 0, 0, 0, 79, 117, 116, 112, 117, 116, 0, 0, 6, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 119,
+        114, 105, 116, 101, 0, 0, 0, 5, 0, 4, 0, 9, 0, 0, 0, 119, 114, 105, 116, 101, 0, 0, 0, 5,
+        0, 4, 0, 11, 0, 0, 0, 86, 65, 76, 85, 69, 0, 0, 0, 72, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 35,
+        0, 0, 0, 0, 0, 0, 0, 71, 0, 3, 0, 7, 0, 0, 0, 3, 0, 0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 34, 0,
+        0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 33, 0, 0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 11, 0, 0,
+        0, 1, 0, 0, 0, 83, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0,
+        21, 0, 4, 0, 6, 0, 0, 0, 32, 0, 0, 0, 1, 0, 0, 0, 30, 0, 3, 0, 7, 0, 0, 0, 6, 0, 0, 0, 32,
+        0, 4, 0, 8, 0, 0, 0, 2, 0, 0, 0, 7, 0, 0, 0, 59, 0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 2, 0, 0,
+        0, 43, 0, 4, 0, 6, 0, 0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 50, 0, 4, 0, 6, 0, 0, 0, 11, 0, 0, 0,
+        239, 190, 173, 222, 32, 0, 4, 0, 12, 0, 0, 0, 2, 0, 0, 0, 6, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0,
+        0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2, 0, 5, 0, 0, 0, 65, 0, 5, 0, 12, 0, 0, 0,
+        13, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 62, 0, 3, 0, 13, 0, 0, 0, 11, 0, 0, 0, 253, 0, 1, 0,
+        56, 0, 1, 0,
+    ];
 
     // TODO: test for basic creation
     // TODO: test for pipeline layout error
@@ -437,45 +781,7 @@ mod tests {
 
         let (device, queue) = gfx_dev_and_queue!();
 
-        let module = unsafe {
-            /*
-            #version 450
-
-            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
-
-            layout(constant_id = 83) const int VALUE = 0xdeadbeef;
-
-            layout(set = 0, binding = 0) buffer Output {
-                int write;
-            } write;
-
-            void main() {
-                write.write = VALUE;
-            }
-            */
-            const MODULE: [u8; 480] = [
-                3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 14, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0,
-                0, 11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0,
-                0, 0, 0, 14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 5, 0, 0, 0, 4, 0, 0, 0,
-                109, 97, 105, 110, 0, 0, 0, 0, 16, 0, 6, 0, 4, 0, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 1,
-                0, 0, 0, 1, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1, 0, 0, 5, 0, 4, 0, 4, 0, 0, 0,
-                109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 4, 0, 7, 0, 0, 0, 79, 117, 116, 112, 117, 116,
-                0, 0, 6, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 119, 114, 105, 116, 101, 0, 0, 0, 5, 0,
-                4, 0, 9, 0, 0, 0, 119, 114, 105, 116, 101, 0, 0, 0, 5, 0, 4, 0, 11, 0, 0, 0, 86,
-                65, 76, 85, 69, 0, 0, 0, 72, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 35, 0, 0, 0, 0, 0, 0,
-                0, 71, 0, 3, 0, 7, 0, 0, 0, 3, 0, 0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 34, 0, 0, 0, 0, 0,
-                0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 33, 0, 0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 11, 0, 0, 0,
-                1, 0, 0, 0, 83, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0,
-                0, 21, 0, 4, 0, 6, 0, 0, 0, 32, 0, 0, 0, 1, 0, 0, 0, 30, 0, 3, 0, 7, 0, 0, 0, 6, 0,
-                0, 0, 32, 0, 4, 0, 8, 0, 0, 0, 2, 0, 0, 0, 7, 0, 0, 0, 59, 0, 4, 0, 8, 0, 0, 0, 9,
-                0, 0, 0, 2, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 50, 0, 4, 0,
-                6, 0, 0, 0, 11, 0, 0, 0, 239, 190, 173, 222, 32, 0, 4, 0, 12, 0, 0, 0, 2, 0, 0, 0,
-                6, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2,
-                0, 5, 0, 0, 0, 65, 0, 5, 0, 12, 0, 0, 0, 13, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 62,
-                0, 3, 0, 13, 0, 0, 0, 11, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
-            ];
-            ShaderModule::new(device.clone(), &MODULE).unwrap()
-        };
+        let module = unsafe { ShaderModule::new(device.clone(), &SPEC_CONSTANT_MODULE).unwrap() };
 
         let shader = unsafe {
             #[derive(Debug, Copy, Clone)]
@@ -572,4 +878,180 @@ mod tests {
         let data_buffer_content = data_buffer.read().unwrap();
         assert_eq!(*data_buffer_content, 0x12345678);
     }
+
+    #[test]
+    fn spec_constants_size_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let words: Vec<u32> = SPEC_CONSTANT_MODULE
+            .chunks(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        let module =
+            unsafe { ShaderModule::from_words_with_reflection(device.clone(), &words).unwrap() };
+        let shader = module.reflected_compute_entry_point("main").unwrap();
+
+        #[repr(C)]
+        struct SpecConsts {
+            value: i64,
+        }
+        unsafe impl SpecializationConstants for SpecConsts {
+            fn descriptors() -> &'static [SpecializationMapEntry] {
+                static DESCRIPTORS: [SpecializationMapEntry; 1] = [SpecializationMapEntry {
+                    constant_id: 83,
+                    offset: 0,
+                    size: 8,
+                }];
+                &DESCRIPTORS
+            }
+        }
+
+        match ComputePipeline::new(device.clone(), &shader, &SpecConsts { value: 5 }, None) {
+            Err(ComputePipelineCreationError::IncompatibleSpecializationConstants(
+                SpecializationConstantsError::SizeMismatch {
+                    constant_id: 83,
+                    expected: 4,
+                    obtained: 8,
+                },
+            )) => (),
+            _ => panic!(),
+        }
+    }
+
+    fn spec_constant_module(device: &Arc<Device>) -> Arc<ShaderModule> {
+        let words: Vec<u32> = SPEC_CONSTANT_MODULE
+            .chunks(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        unsafe { ShaderModule::from_words_with_reflection(device.clone(), &words).unwrap() }
+    }
+
+    #[test]
+    fn identifier_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let module = spec_constant_module(&device);
+        assert_eq!(
+            module.identifier(),
+            Err(ShaderModuleIdentifierError::FeatureNotEnabled)
+        );
+        assert!(!module.is_identifier_only());
+    }
+
+    #[test]
+    fn identifier_requires_cache_control() {
+        let (device, _) =
+            gfx_dev_and_queue!(shader_module_identifier; ext_shader_module_identifier);
+
+        let module = spec_constant_module(&device);
+        let shader = module.reflected_compute_entry_point::<()>("main").unwrap();
+        let identifier = module.identifier().unwrap();
+
+        let identified = unsafe { ShaderModule::from_identifier(device.clone(), identifier) };
+        let identified = identified.unwrap();
+        assert!(identified.is_identifier_only());
+        assert_eq!(identified.identifier(), Ok(identifier));
+
+        let identified_shader = unsafe {
+            identified.compute_entry_point::<(), _>(shader.name(), shader.layout().clone())
+        };
+        assert_eq!(
+            ComputePipeline::new(device.clone(), &identified_shader, &(), None).err(),
+            Some(ComputePipelineCreationError::PipelineCreationCacheControlFeatureNotEnabled)
+        );
+    }
+
+    #[test]
+    fn executable_info_not_captured() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let module = spec_constant_module(&device);
+        let shader = module.reflected_compute_entry_point::<()>("main").unwrap();
+        let pipeline = ComputePipeline::new(device.clone(), &shader, &(), None).unwrap();
+
+        assert_eq!(
+            pipeline.executable_properties(),
+            Err(PipelineExecutableError::ExtensionNotEnabled)
+        );
+        assert_eq!(
+            pipeline.executable_statistics(0),
+            Err(PipelineExecutableError::StatisticsNotCaptured)
+        );
+        assert_eq!(
+            pipeline.executable_internal_representations(0),
+            Err(PipelineExecutableError::InternalRepresentationsNotCaptured)
+        );
+
+        let layout = shader.layout().clone().build(device.clone()).unwrap();
+        assert_eq!(
+            ComputePipeline::with_capture(device.clone(), &shader, &(), layout, None, true, false)
+                .err(),
+            Some(ComputePipelineCreationError::PipelineExecutablePropertiesExtensionNotEnabled)
+        );
+    }
+
+    #[test]
+    fn executable_statistics() {
+        let instance = instance!();
+
+        let phys = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let queue_family = match phys.queue_families().find(|q| q.supports_compute()) {
+            Some(q) => q,
+            None => return,
+        };
+
+        let features = Features {
+            pipeline_executable_info: true,
+            ..Features::none()
+        };
+        if !phys.supported_features().superset_of(&features) {
+            return;
+        }
+
+        let extensions = DeviceExtensions {
+            khr_pipeline_executable_properties: true,
+            ..DeviceExtensions::none()
+        };
+        let (device, _) = match Device::new(
+            phys,
+            &features,
+            &extensions,
+            std::iter::once((queue_family, 0.5)),
+        ) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+
+        let module = spec_constant_module(&device);
+        let shader = module.reflected_compute_entry_point::<()>("main").unwrap();
+        let layout = shader.layout().clone().build(device.clone()).unwrap();
+        let pipeline =
+            ComputePipeline::with_capture(device.clone(), &shader, &(), layout, None, true, false)
+                .unwrap();
+
+        let properties = pipeline.executable_properties().unwrap();
+        for (index, properties) in properties.iter().enumerate() {
+            assert!(properties.stages.compute);
+            assert!(pipeline.executable_statistics(index as u32).is_ok());
+        }
+        assert_eq!(
+            pipeline.executable_internal_representations(0),
+            Err(PipelineExecutableError::InternalRepresentationsNotCaptured)
+        );
+
+        match pipeline.executable_statistics(properties.len() as u32) {
+            Err(PipelineExecutableError::ExecutableIndexOutOfRange {
+                executable_index,
+                num_executables,
+            }) => {
+                assert_eq!(executable_index, properties.len() as u32);
+                assert_eq!(num_executables, properties.len() as u32);
+            }
+            _ => panic!(),
+        }
+    }
 }
@@ -0,0 +1,457 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Introspection of the executables that the implementation compiled for a pipeline.
+//!
+//! With the `VK_KHR_pipeline_executable_properties` extension, the implementation can report
+//! which executables it produced for a pipeline, statistics about each of them such as register
+//! usage or spilling, and their internal representations such as disassembly.
+//!
+//! Statistics and internal representations are only available if they were requested when
+//! building the pipeline, with `GraphicsPipelineBuilder::capture_statistics` and
+//! `capture_internal_representations`, or with `ComputePipeline::with_capture`. They are meant
+//! to be looked at by a developer and their content varies between implementations.
+
+use std::error;
+use std::ffi::CStr;
+use std::fmt;
+use std::mem;
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::check_errors;
+use crate::descriptor::descriptor::ShaderStages;
+use crate::device::Device;
+use crate::vk;
+use crate::Error;
+use crate::OomError;
+use crate::VulkanObject;
+
+/// Properties of an executable of a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineExecutableProperties {
+    /// The shader stages that were compiled into this executable.
+    pub stages: ShaderStages,
+    /// A short name of the executable.
+    pub name: String,
+    /// A description of the executable.
+    pub description: String,
+    /// The subgroup size the executable was compiled with, or 0 for stages without subgroups.
+    pub subgroup_size: u32,
+}
+
+/// A statistic about an executable of a pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineExecutableStatistic {
+    /// A short name of the statistic.
+    pub name: String,
+    /// A description of the statistic.
+    pub description: String,
+    /// The value of the statistic.
+    pub value: PipelineExecutableStatisticValue,
+}
+
+/// The value of a `PipelineExecutableStatistic`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PipelineExecutableStatisticValue {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+}
+
+/// An internal representation of an executable of a pipeline, for example its disassembly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineExecutableInternalRepresentation {
+    /// A short name of the representation.
+    pub name: String,
+    /// A description of the representation.
+    pub description: String,
+    /// If true, `data` is text encoded in UTF-8. Otherwise it is in an opaque binary format.
+    pub is_text: bool,
+    /// The content of the representation. Text doesn't include the terminating null character.
+    pub data: Vec<u8>,
+}
+
+// Checks that the extension and feature needed to query pipeline executables are enabled.
+fn check_enabled(device: &Device) -> Result<(), PipelineExecutableError> {
+    if !device
+        .loaded_extensions()
+        .khr_pipeline_executable_properties
+    {
+        return Err(PipelineExecutableError::ExtensionNotEnabled);
+    }
+
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(PipelineExecutableError::FeatureNotEnabled);
+    }
+
+    Ok(())
+}
+
+#[inline]
+unsafe fn string_from_array(array: &[c_char]) -> String {
+    CStr::from_ptr(array.as_ptr())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Queries the executables of `pipeline`.
+///
+/// # Safety
+///
+/// - `pipeline` must be a valid pipeline that was created from `device`.
+///
+pub(crate) unsafe fn executable_properties(
+    device: &Device,
+    pipeline: vk::Pipeline,
+) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutableError> {
+    check_enabled(device)?;
+
+    let vk = device.pointers();
+    let infos = vk::PipelineInfoKHR {
+        sType: vk::STRUCTURE_TYPE_PIPELINE_INFO_KHR,
+        pNext: ptr::null(),
+        pipeline,
+    };
+
+    let mut num = 0;
+    check_errors(vk.GetPipelineExecutablePropertiesKHR(
+        device.internal_object(),
+        &infos,
+        &mut num,
+        ptr::null_mut(),
+    ))?;
+
+    let mut properties: Vec<vk::PipelineExecutablePropertiesKHR> = (0..num)
+        .map(|_| {
+            let mut properties: vk::PipelineExecutablePropertiesKHR = mem::zeroed();
+            properties.sType = vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_PROPERTIES_KHR;
+            properties
+        })
+        .collect();
+    check_errors(vk.GetPipelineExecutablePropertiesKHR(
+        device.internal_object(),
+        &infos,
+        &mut num,
+        properties.as_mut_ptr(),
+    ))?;
+    properties.truncate(num as usize);
+
+    Ok(properties
+        .iter()
+        .map(|properties| PipelineExecutableProperties {
+            stages: ShaderStages::from_vulkan_bits(properties.stages),
+            name: string_from_array(&properties.name),
+            description: string_from_array(&properties.description),
+            subgroup_size: properties.subgroupSize,
+        })
+        .collect())
+}
+
+// Checks that `executable_index` is less than the number of executables of `pipeline`.
+unsafe fn check_executable_index(
+    device: &Device,
+    pipeline: vk::Pipeline,
+    executable_index: u32,
+) -> Result<(), PipelineExecutableError> {
+    let vk = device.pointers();
+    let infos = vk::PipelineInfoKHR {
+        sType: vk::STRUCTURE_TYPE_PIPELINE_INFO_KHR,
+        pNext: ptr::null(),
+        pipeline,
+    };
+
+    let mut num = 0;
+    check_errors(vk.GetPipelineExecutablePropertiesKHR(
+        device.internal_object(),
+        &infos,
+        &mut num,
+        ptr::null_mut(),
+    ))?;
+
+    if executable_index >= num {
+        return Err(PipelineExecutableError::ExecutableIndexOutOfRange {
+            executable_index,
+            num_executables: num,
+        });
+    }
+
+    Ok(())
+}
+
+/// Queries the statistics of the executable of index `executable_index` of `pipeline`.
+///
+/// # Safety
+///
+/// - `pipeline` must be a valid pipeline that was created from `device` with the
+///   `PIPELINE_CREATE_CAPTURE_STATISTICS_BIT_KHR` flag.
+///
+pub(crate) unsafe fn executable_statistics(
+    device: &Device,
+    pipeline: vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutableError> {
+    check_enabled(device)?;
+    check_executable_index(device, pipeline, executable_index)?;
+
+    let vk = device.pointers();
+    let infos = vk::PipelineExecutableInfoKHR {
+        sType: vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_INFO_KHR,
+        pNext: ptr::null(),
+        pipeline,
+        executableIndex: executable_index,
+    };
+
+    let mut num = 0;
+    check_errors(vk.GetPipelineExecutableStatisticsKHR(
+        device.internal_object(),
+        &infos,
+        &mut num,
+        ptr::null_mut(),
+    ))?;
+
+    let mut statistics: Vec<vk::PipelineExecutableStatisticKHR> = (0..num)
+        .map(|_| {
+            let mut statistic: vk::PipelineExecutableStatisticKHR = mem::zeroed();
+            statistic.sType = vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_STATISTIC_KHR;
+            statistic
+        })
+        .collect();
+    check_errors(vk.GetPipelineExecutableStatisticsKHR(
+        device.internal_object(),
+        &infos,
+        &mut num,
+        statistics.as_mut_ptr(),
+    ))?;
+    statistics.truncate(num as usize);
+
+    // Statistics in a format that this version of vulkano doesn't know are skipped.
+    Ok(statistics
+        .iter()
+        .filter_map(|statistic| {
+            Some(PipelineExecutableStatistic {
+                name: string_from_array(&statistic.name),
+                description: string_from_array(&statistic.description),
+                value: statistic_value(statistic)?,
+            })
+        })
+        .collect())
+}
+
+// Decodes the value of a statistic, or returns `None` if its format is unknown.
+#[inline]
+unsafe fn statistic_value(
+    statistic: &vk::PipelineExecutableStatisticKHR,
+) -> Option<PipelineExecutableStatisticValue> {
+    Some(match statistic.format {
+        vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_BOOL32_KHR => {
+            PipelineExecutableStatisticValue::Bool(statistic.value.b32 != vk::FALSE)
+        }
+        vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_INT64_KHR => {
+            PipelineExecutableStatisticValue::I64(statistic.value.i64)
+        }
+        vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_UINT64_KHR => {
+            PipelineExecutableStatisticValue::U64(statistic.value.u64)
+        }
+        vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_FLOAT64_KHR => {
+            PipelineExecutableStatisticValue::F64(statistic.value.f64)
+        }
+        _ => return None,
+    })
+}
+
+/// Queries the internal representations of the executable of index `executable_index` of
+/// `pipeline`.
+///
+/// # Safety
+///
+/// - `pipeline` must be a valid pipeline that was created from `device` with the
+///   `PIPELINE_CREATE_CAPTURE_INTERNAL_REPRESENTATIONS_BIT_KHR` flag.
+///
+pub(crate) unsafe fn executable_internal_representations(
+    device: &Device,
+    pipeline: vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+    check_enabled(device)?;
+    check_executable_index(device, pipeline, executable_index)?;
+
+    let vk = device.pointers();
+    let infos = vk::PipelineExecutableInfoKHR {
+        sType: vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_INFO_KHR,
+        pNext: ptr::null(),
+        pipeline,
+        executableIndex: executable_index,
+    };
+
+    let mut num = 0;
+    check_errors(vk.GetPipelineExecutableInternalRepresentationsKHR(
+        device.internal_object(),
+        &infos,
+        &mut num,
+        ptr::null_mut(),
+    ))?;
+
+    // The first query with null data pointers only writes the size of each representation.
+    let mut representations: Vec<vk::PipelineExecutableInternalRepresentationKHR> = (0..num)
+        .map(|_| {
+            let mut representation: vk::PipelineExecutableInternalRepresentationKHR = mem::zeroed();
+            representation.sType =
+                vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_INTERNAL_REPRESENTATION_KHR;
+            representation
+        })
+        .collect();
+    check_errors(vk.GetPipelineExecutableInternalRepresentationsKHR(
+        device.internal_object(),
+        &infos,
+        &mut num,
+        representations.as_mut_ptr(),
+    ))?;
+    representations.truncate(num as usize);
+
+    let mut data: Vec<Vec<u8>> = representations
+        .iter()
+        .map(|representation| vec![0; representation.dataSize])
+        .collect();
+    for (representation, data) in representations.iter_mut().zip(data.iter_mut()) {
+        representation.pData = data.as_mut_ptr() as *mut _;
+    }
+    check_errors(vk.GetPipelineExecutableInternalRepresentationsKHR(
+        device.internal_object(),
+        &infos,
+        &mut num,
+        representations.as_mut_ptr(),
+    ))?;
+
+    Ok(representations
+        .iter()
+        .zip(data)
+        .map(|(representation, mut data)| {
+            let is_text = representation.isText != vk::FALSE;
+            data.truncate(representation.dataSize);
+            if is_text {
+                if let Some(end) = data.iter().position(|&b| b == 0) {
+                    data.truncate(end);
+                }
+            }
+
+            PipelineExecutableInternalRepresentation {
+                name: string_from_array(&representation.name),
+                description: string_from_array(&representation.description),
+                is_text,
+                data,
+            }
+        })
+        .collect())
+}
+
+/// Error that can happen when querying the executables of a pipeline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PipelineExecutableError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The `VK_KHR_pipeline_executable_properties` extension was not enabled.
+    ExtensionNotEnabled,
+    /// The `pipeline_executable_info` feature was not enabled.
+    FeatureNotEnabled,
+    /// The pipeline was not built with `capture_statistics` enabled.
+    StatisticsNotCaptured,
+    /// The pipeline was not built with `capture_internal_representations` enabled.
+    InternalRepresentationsNotCaptured,
+    /// The executable index is out of range.
+    ExecutableIndexOutOfRange {
+        /// Index that was requested.
+        executable_index: u32,
+        /// Number of executables of the pipeline.
+        num_executables: u32,
+    },
+}
+
+impl error::Error for PipelineExecutableError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            PipelineExecutableError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PipelineExecutableError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                PipelineExecutableError::OomError(_) => "not enough memory available",
+                PipelineExecutableError::ExtensionNotEnabled => {
+                    "the `VK_KHR_pipeline_executable_properties` extension was not enabled"
+                }
+                PipelineExecutableError::FeatureNotEnabled => {
+                    "the `pipeline_executable_info` feature was not enabled"
+                }
+                PipelineExecutableError::StatisticsNotCaptured => {
+                    "the pipeline was not built with `capture_statistics` enabled"
+                }
+                PipelineExecutableError::InternalRepresentationsNotCaptured => {
+                    "the pipeline was not built with `capture_internal_representations` enabled"
+                }
+                PipelineExecutableError::ExecutableIndexOutOfRange { .. } => {
+                    "the executable index is out of range"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for PipelineExecutableError {
+    #[inline]
+    fn from(err: OomError) -> PipelineExecutableError {
+        PipelineExecutableError::OomError(err)
+    }
+}
+
+impl From<Error> for PipelineExecutableError {
+    #[inline]
+    fn from(err: Error) -> PipelineExecutableError {
+        match err {
+            err @ Error::OutOfHostMemory => PipelineExecutableError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => {
+                PipelineExecutableError::OomError(OomError::from(err))
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::statistic_value;
+    use super::PipelineExecutableStatisticValue;
+    use crate::vk;
+    use std::mem;
+
+    #[test]
+    fn unknown_statistic_format() {
+        unsafe {
+            let mut statistic: vk::PipelineExecutableStatisticKHR = mem::zeroed();
+            statistic.format = vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_UINT64_KHR;
+            statistic.value.u64 = 12;
+            assert_eq!(
+                statistic_value(&statistic),
+                Some(PipelineExecutableStatisticValue::U64(12))
+            );
+
+            statistic.format = 0x7fff_ffff;
+            assert_eq!(statistic_value(&statistic), None);
+        }
+    }
+}
@@ -0,0 +1,306 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Compilation of GLSL shaders at runtime.
+//!
+//! This module is only available with the `shaderc` cargo feature. It compiles GLSL source code
+//! to SPIR-V with [shaderc](https://github.com/google/shaderc), then creates a reflected
+//! `ShaderModule` from it, exactly like `ShaderModule::from_words_with_reflection`.
+//!
+//! This is mostly useful for tools that need to iterate quickly on shaders. Applications should
+//! prefer compiling their shaders ahead of time with the `vulkano-shaders` crate.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::device::Device;
+use crate::instance::Version;
+use crate::pipeline::shader::ShaderModule;
+use crate::pipeline::shader::ShaderModuleCreationError;
+
+/// The stage that a GLSL shader is compiled for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GlslShaderKind {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    Compute,
+}
+
+impl From<GlslShaderKind> for shaderc::ShaderKind {
+    #[inline]
+    fn from(kind: GlslShaderKind) -> shaderc::ShaderKind {
+        match kind {
+            GlslShaderKind::Vertex => shaderc::ShaderKind::Vertex,
+            GlslShaderKind::TessellationControl => shaderc::ShaderKind::TessControl,
+            GlslShaderKind::TessellationEvaluation => shaderc::ShaderKind::TessEvaluation,
+            GlslShaderKind::Geometry => shaderc::ShaderKind::Geometry,
+            GlslShaderKind::Fragment => shaderc::ShaderKind::Fragment,
+            GlslShaderKind::Compute => shaderc::ShaderKind::Compute,
+        }
+    }
+}
+
+/// The kind of an `#include` directive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IncludeKind {
+    /// `#include "file"`, relative to the file that contains the directive.
+    Relative,
+    /// `#include <file>`, looked up in the include directories.
+    Standard,
+}
+
+/// The result of resolving an `#include` directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedInclude {
+    /// Name that uniquely identifies the included file, typically its absolute path. It is used
+    /// by the compiler to resolve nested includes and in error messages.
+    pub name: String,
+    /// Content of the included file.
+    pub content: String,
+}
+
+/// Callback that resolves `#include` directives.
+///
+/// Its parameters are the requested file, the kind of directive, the name of the file that
+/// contains the directive, and the depth of inclusion.
+pub type IncludeCallback<'a> =
+    dyn Fn(&str, IncludeKind, &str, usize) -> Result<ResolvedInclude, String> + 'a;
+
+/// Options for compiling a GLSL shader.
+pub struct GlslCompileOptions<'a> {
+    file_name: String,
+    entry_point: String,
+    macros: Vec<(String, Option<String>)>,
+    include_callback: Option<&'a IncludeCallback<'a>>,
+    vulkan_version: Version,
+    optimize: bool,
+    debug_info: bool,
+}
+
+impl<'a> GlslCompileOptions<'a> {
+    /// Builds the default options.
+    ///
+    /// The source is named `shader.glsl`, the entry point is `main`, and the shader targets
+    /// Vulkan 1.0 without optimizations. `#include` directives are rejected.
+    #[inline]
+    pub fn new() -> GlslCompileOptions<'a> {
+        GlslCompileOptions {
+            file_name: "shader.glsl".to_owned(),
+            entry_point: "main".to_owned(),
+            macros: Vec::new(),
+            include_callback: None,
+            vulkan_version: Version {
+                major: 1,
+                minor: 0,
+                patch: 0,
+            },
+            optimize: false,
+            debug_info: false,
+        }
+    }
+
+    /// Sets the name of the source, which is used in error messages and passed to the include
+    /// callback.
+    #[inline]
+    pub fn file_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.file_name = name.into();
+        self
+    }
+
+    /// Sets the name of the entry point of the shader.
+    #[inline]
+    pub fn entry_point<S: Into<String>>(mut self, name: S) -> Self {
+        self.entry_point = name.into();
+        self
+    }
+
+    /// Defines a preprocessor macro, like `#define name value`.
+    #[inline]
+    pub fn define<S: Into<String>>(mut self, name: S, value: Option<S>) -> Self {
+        self.macros.push((name.into(), value.map(Into::into)));
+        self
+    }
+
+    /// Sets the callback that resolves `#include` directives.
+    #[inline]
+    pub fn include_callback(mut self, callback: &'a IncludeCallback<'a>) -> Self {
+        self.include_callback = Some(callback);
+        self
+    }
+
+    /// Sets the version of Vulkan that the SPIR-V code targets. Must not be higher than the
+    /// version supported by the device.
+    #[inline]
+    pub fn vulkan_version(mut self, version: Version) -> Self {
+        self.vulkan_version = version;
+        self
+    }
+
+    /// Enables optimizations for performance.
+    #[inline]
+    pub fn optimize(mut self, optimize: bool) -> Self {
+        self.optimize = optimize;
+        self
+    }
+
+    /// Includes debug information, such as the names of variables, in the SPIR-V code.
+    #[inline]
+    pub fn debug_info(mut self, debug_info: bool) -> Self {
+        self.debug_info = debug_info;
+        self
+    }
+}
+
+impl<'a> Default for GlslCompileOptions<'a> {
+    #[inline]
+    fn default() -> GlslCompileOptions<'a> {
+        GlslCompileOptions::new()
+    }
+}
+
+impl<'a> fmt::Debug for GlslCompileOptions<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("GlslCompileOptions")
+            .field("file_name", &self.file_name)
+            .field("entry_point", &self.entry_point)
+            .field("macros", &self.macros)
+            .field("include_callback", &self.include_callback.is_some())
+            .field("vulkan_version", &self.vulkan_version)
+            .field("optimize", &self.optimize)
+            .field("debug_info", &self.debug_info)
+            .finish()
+    }
+}
+
+/// Compiles GLSL source code to SPIR-V words.
+pub fn compile_glsl(
+    source: &str,
+    kind: GlslShaderKind,
+    options: &GlslCompileOptions,
+) -> Result<Vec<u32>, ShaderModuleCreationError> {
+    let mut compiler = shaderc::Compiler::new().ok_or_else(|| {
+        ShaderModuleCreationError::CompilationFailed("failed to create the compiler".to_owned())
+    })?;
+    let mut compile_options = shaderc::CompileOptions::new().ok_or_else(|| {
+        ShaderModuleCreationError::CompilationFailed(
+            "failed to create the compile options".to_owned(),
+        )
+    })?;
+
+    compile_options.set_target_env(
+        shaderc::TargetEnv::Vulkan,
+        options.vulkan_version.into_vulkan_version(),
+    );
+    for &(ref name, ref value) in options.macros.iter() {
+        compile_options.add_macro_definition(name, value.as_ref().map(|v| &v[..]));
+    }
+    if options.optimize {
+        compile_options.set_optimization_level(shaderc::OptimizationLevel::Performance);
+    }
+    if options.debug_info {
+        compile_options.set_generate_debug_info();
+    }
+
+    let include_callback = options.include_callback;
+    compile_options.set_include_callback(move |requested, ty, requester, depth| {
+        let callback = match include_callback {
+            Some(callback) => callback,
+            None => return Err("`#include` directives are not supported".to_owned()),
+        };
+        let kind = match ty {
+            shaderc::IncludeType::Relative => IncludeKind::Relative,
+            shaderc::IncludeType::Standard => IncludeKind::Standard,
+        };
+        callback(requested, kind, requester, depth).map(|include| shaderc::ResolvedInclude {
+            resolved_name: include.name,
+            content: include.content,
+        })
+    });
+
+    let artifact = compiler
+        .compile_into_spirv(
+            source,
+            kind.into(),
+            &options.file_name,
+            &options.entry_point,
+            Some(&compile_options),
+        )
+        .map_err(|err| ShaderModuleCreationError::CompilationFailed(err.to_string()))?;
+
+    Ok(artifact.as_binary().to_vec())
+}
+
+impl ShaderModule {
+    /// Compiles GLSL source code and builds a new reflected shader module from it.
+    ///
+    /// Only available with the `shaderc` cargo feature.
+    ///
+    /// # Safety
+    ///
+    /// - The SPIR-V code may require some features that are not enabled. This isn't checked by
+    ///   this function.
+    ///
+    pub unsafe fn from_glsl(
+        device: Arc<Device>,
+        source: &str,
+        kind: GlslShaderKind,
+        options: &GlslCompileOptions,
+    ) -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
+        let spirv = compile_glsl(source, kind, options)?;
+        ShaderModule::from_words_with_reflection(device, &spirv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::glsl::compile_glsl;
+    use crate::pipeline::glsl::GlslCompileOptions;
+    use crate::pipeline::glsl::GlslShaderKind;
+    use crate::pipeline::glsl::IncludeKind;
+    use crate::pipeline::glsl::ResolvedInclude;
+    use crate::pipeline::reflect;
+    use crate::pipeline::shader::ShaderModuleCreationError;
+
+    #[test]
+    fn compile_with_include() {
+        let source = "
+            #version 450
+            #include \"common.glsl\"
+            layout(location = 0) out vec4 color;
+            void main() { color = COLOR; }
+        ";
+        let callback = |_: &str, _: IncludeKind, _: &str, _: usize| {
+            Ok(ResolvedInclude {
+                name: "common.glsl".to_owned(),
+                content: "#define COLOR vec4(1.0)".to_owned(),
+            })
+        };
+        let options = GlslCompileOptions::new().include_callback(&callback);
+
+        let spirv = compile_glsl(source, GlslShaderKind::Fragment, &options).unwrap();
+        let reflection = reflect::reflect(&spirv).unwrap();
+        assert!(reflection.entry_point("main").is_some());
+    }
+
+    #[test]
+    fn compilation_error() {
+        let options = GlslCompileOptions::new();
+        match compile_glsl(
+            "#version 450\nvoid main() {",
+            GlslShaderKind::Vertex,
+            &options,
+        ) {
+            Err(ShaderModuleCreationError::CompilationFailed(_)) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -14,37 +14,60 @@
 use smallvec::SmallVec;
 use std::mem;
 use std::mem::MaybeUninit;
+use std::ops::Range;
+use std::os::raw::c_void;
 use std::ptr;
 use std::sync::Arc;
 use std::u32;
 
 use crate::descriptor::pipeline_layout::PipelineLayoutAbstract;
 use crate::device::Device;
+use crate::features::Features;
 use crate::framebuffer::RenderPassAbstract;
 use crate::framebuffer::Subpass;
+use crate::pipeline::blend::AdvancedBlend;
 use crate::pipeline::blend::AttachmentBlend;
 use crate::pipeline::blend::AttachmentsBlend;
 use crate::pipeline::blend::Blend;
+use crate::pipeline::blend::BlendOp;
+use crate::pipeline::blend::BlendOverlap;
 use crate::pipeline::blend::LogicOp;
 use crate::pipeline::cache::PipelineCache;
 use crate::pipeline::depth_stencil::Compare;
 use crate::pipeline::depth_stencil::DepthBounds;
 use crate::pipeline::depth_stencil::DepthStencil;
+use crate::pipeline::depth_stencil::Stencil;
+use crate::pipeline::graphics_pipeline::ExtendedDynamicState;
 use crate::pipeline::graphics_pipeline::GraphicsPipeline;
+use crate::pipeline::graphics_pipeline::GraphicsPipelineAbstract;
 use crate::pipeline::graphics_pipeline::GraphicsPipelineCreationError;
+use crate::pipeline::graphics_pipeline::GraphicsPipelineLibraryParts;
 use crate::pipeline::graphics_pipeline::Inner as GraphicsPipelineInner;
 use crate::pipeline::input_assembly::PrimitiveTopology;
+use crate::pipeline::multisample::SampleLocations;
+use crate::pipeline::multisample::SampleLocationsState;
+use crate::pipeline::raster::ConservativeRasterization;
 use crate::pipeline::raster::CullMode;
 use crate::pipeline::raster::DepthBiasControl;
 use crate::pipeline::raster::FrontFace;
+use crate::pipeline::raster::LineRasterizationMode;
+use crate::pipeline::raster::LineStipple;
+use crate::pipeline::raster::LineStippleControl;
 use crate::pipeline::raster::PolygonMode;
+use crate::pipeline::raster::ProvokingVertexMode;
 use crate::pipeline::raster::Rasterization;
+use crate::pipeline::reflect::ShaderStage;
+use crate::pipeline::robustness::PipelineRobustness;
 use crate::pipeline::shader::EmptyEntryPointDummy;
 use crate::pipeline::shader::GraphicsEntryPointAbstract;
 use crate::pipeline::shader::GraphicsShaderType;
 use crate::pipeline::shader::ShaderInterfaceDefMatch;
 use crate::pipeline::shader::SpecializationConstants;
+use crate::pipeline::shading_rate::FragmentShadingRate;
+use crate::pipeline::shading_rate::FragmentShadingRateCombinerOp;
+use crate::pipeline::shading_rate::FragmentShadingRateState;
 use crate::pipeline::vertex::BufferlessDefinition;
+use crate::pipeline::vertex::InputRate;
 use crate::pipeline::vertex::SingleBufferDefinition;
 use crate::pipeline::vertex::VertexDefinition;
 use crate::pipeline::viewport::Scissor;
@@ -57,6 +80,7 @@ use crate::descriptor::pipeline_layout::PipelineLayoutDescTweaks;
 use crate::descriptor::pipeline_layout::PipelineLayoutSuperset;
 use crate::framebuffer::RenderPassSubpassInterface;
 use crate::vk;
+use crate::Success;
 use crate::VulkanObject;
 
 /// Prototype for a `GraphicsPipeline`.
@@ -73,11 +97,25 @@ pub struct GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss,
     viewport: Option<ViewportsState>,
     raster: Rasterization,
     multisample: vk::PipelineMultisampleStateCreateInfo,
+    // Storage for the `pSampleMask` member of `multisample`, which is only filled when building.
+    sample_mask: Option<[u32; 2]>,
+    fragment_shading_rate: FragmentShadingRateState,
+    extended_dynamic_state: ExtendedDynamicState,
     fragment_shader: Option<(Fs, Fss)>,
     depth_stencil: DepthStencil,
     blend: Blend,
+    // States set with `blend_attachment`, applied on top of `blend` when building since the
+    // number of color attachments may not be known yet.
+    blend_overrides: Vec<(u32, AttachmentBlend)>,
     render_pass: Option<Subpass<Rp>>,
     cache: Option<Arc<PipelineCache>>,
+    library_parts: GraphicsPipelineLibraryParts,
+    libraries: Vec<Arc<dyn GraphicsPipelineAbstract + Send + Sync>>,
+    link_time_optimization: bool,
+    capture_statistics: bool,
+    capture_internal_representations: bool,
+    sample_locations: SampleLocationsState,
+    robustness: PipelineRobustness,
 }
 
 // Additional parameters if tessellation is used.
@@ -123,11 +161,22 @@ impl
                     sType: vk::STRUCTURE_TYPE_PIPELINE_MULTISAMPLE_STATE_CREATE_INFO,
                     ..mem::zeroed()
                 },
+                sample_mask: None,
+                fragment_shading_rate: FragmentShadingRateState::Disabled,
+                extended_dynamic_state: ExtendedDynamicState::none(),
                 fragment_shader: None,
                 depth_stencil: DepthStencil::disabled(),
                 blend: Blend::pass_through(),
+                blend_overrides: Vec::new(),
                 render_pass: None,
                 cache: None,
+                library_parts: GraphicsPipelineLibraryParts::none(),
+                libraries: Vec::new(),
+                link_time_optimization: false,
+                capture_statistics: false,
+                capture_internal_representations: false,
+                sample_locations: SampleLocationsState::Disabled,
+                robustness: PipelineRobustness::device_default(),
             }
         }
     }
@@ -162,6 +211,10 @@ where
     Rp: RenderPassAbstract + RenderPassSubpassInterface<Fs::OutputDefinition>,
 {
     /// Builds the graphics pipeline, using an inferred a pipeline layout.
+    ///
+    /// The layout is the union of the descriptors and push constants ranges of all the shader
+    /// stages. Returns `IncompatibleShaderLayouts` if two stages declare incompatible descriptors
+    /// at the same location.
     // TODO: replace Box<PipelineLayoutAbstract> with a PipelineUnion struct without template params
     pub fn build(
         self,
@@ -234,31 +287,14 @@ where
                             .0
                             .layout()
                             .clone()
-                            .union(self.fragment_shader.as_ref().unwrap().0.layout().clone())
-                            .union(
-                                self.tessellation
-                                    .as_ref()
-                                    .unwrap()
-                                    .tessellation_control_shader
-                                    .0
-                                    .layout()
-                                    .clone(),
-                            ) // FIXME: unwrap()
-                            .union(
-                                self.tessellation
-                                    .as_ref()
-                                    .unwrap()
-                                    .tessellation_evaluation_shader
-                                    .0
-                                    .layout()
-                                    .clone(),
-                            ) // FIXME: unwrap()
-                            .union(self.geometry_shader.as_ref().unwrap().0.layout().clone()), // FIXME: unwrap()
+                            .try_union(self.fragment_shader.as_ref().unwrap().0.layout().clone())?
+                            .try_union(tess.tessellation_control_shader.0.layout().clone())?
+                            .try_union(tess.tessellation_evaluation_shader.0.layout().clone())?
+                            .try_union(gs.0.layout().clone())?,
                         dynamic_buffers.into_iter().cloned(),
                     )
-                    .build(device.clone())
-                    .unwrap(),
-                ) as Box<_>; // TODO: error
+                    .build(device.clone())?,
+                ) as Box<_>;
             } else {
                 if let Err(err) = tess
                     .tessellation_control_shader
@@ -299,30 +335,13 @@ where
                             .0
                             .layout()
                             .clone()
-                            .union(self.fragment_shader.as_ref().unwrap().0.layout().clone())
-                            .union(
-                                self.tessellation
-                                    .as_ref()
-                                    .unwrap()
-                                    .tessellation_control_shader
-                                    .0
-                                    .layout()
-                                    .clone(),
-                            ) // FIXME: unwrap()
-                            .union(
-                                self.tessellation
-                                    .as_ref()
-                                    .unwrap()
-                                    .tessellation_evaluation_shader
-                                    .0
-                                    .layout()
-                                    .clone(),
-                            ), // FIXME: unwrap()
+                            .try_union(self.fragment_shader.as_ref().unwrap().0.layout().clone())?
+                            .try_union(tess.tessellation_control_shader.0.layout().clone())?
+                            .try_union(tess.tessellation_evaluation_shader.0.layout().clone())?,
                         dynamic_buffers.into_iter().cloned(),
                     )
-                    .build(device.clone())
-                    .unwrap(),
-                ) as Box<_>; // TODO: error
+                    .build(device.clone())?,
+                ) as Box<_>;
             }
         } else {
             if let Some(ref geometry_shader) = self.geometry_shader {
@@ -354,13 +373,12 @@ where
                             .0
                             .layout()
                             .clone()
-                            .union(self.fragment_shader.as_ref().unwrap().0.layout().clone())
-                            .union(self.geometry_shader.as_ref().unwrap().0.layout().clone()), // FIXME: unwrap()
+                            .try_union(self.fragment_shader.as_ref().unwrap().0.layout().clone())?
+                            .try_union(geometry_shader.0.layout().clone())?,
                         dynamic_buffers.into_iter().cloned(),
                     )
-                    .build(device.clone())
-                    .unwrap(),
-                ) as Box<_>; // TODO: error
+                    .build(device.clone())?,
+                ) as Box<_>;
             } else {
                 if let Err(err) = self
                     .fragment_shader
@@ -383,12 +401,11 @@ where
                             .0
                             .layout()
                             .clone()
-                            .union(self.fragment_shader.as_ref().unwrap().0.layout().clone()),
+                            .try_union(self.fragment_shader.as_ref().unwrap().0.layout().clone())?,
                         dynamic_buffers.into_iter().cloned(),
                     )
-                    .build(device.clone())
-                    .unwrap(),
-                ) as Box<_>; // TODO: error
+                    .build(device.clone())?,
+                ) as Box<_>;
             }
         }
 
@@ -412,6 +429,61 @@ where
 
         let vk = device.pointers();
 
+        // Apply the states set with `blend_attachment` now that the subpass is known.
+        if !self.blend_overrides.is_empty() {
+            if let Some(subpass) = self.render_pass.as_ref() {
+                let num_atch = subpass.num_color_attachments();
+                let attachments = mem::replace(
+                    &mut self.blend.attachments,
+                    AttachmentsBlend::Individual(Vec::new()),
+                );
+                self.blend.attachments =
+                    attachments.with_overrides(&self.blend_overrides, num_atch);
+            }
+        }
+
+        // The parts of the pipeline that are created by this call. Parts that come from linked
+        // libraries are not part of it.
+        let created_parts = if !self.library_parts.is_empty() || !self.libraries.is_empty() {
+            if !device.loaded_extensions().ext_graphics_pipeline_library {
+                return Err(
+                    GraphicsPipelineCreationError::GraphicsPipelineLibraryExtensionNotEnabled,
+                );
+            }
+            if !device.enabled_features().graphics_pipeline_library {
+                return Err(
+                    GraphicsPipelineCreationError::GraphicsPipelineLibraryFeatureNotEnabled,
+                );
+            }
+
+            let mut linked_parts = GraphicsPipelineLibraryParts::none();
+            for library in self.libraries.iter() {
+                assert_eq!(device.internal_object(), library.device().internal_object());
+
+                let parts = library.library_parts();
+                if parts.is_empty() {
+                    return Err(GraphicsPipelineCreationError::NotAPipelineLibrary);
+                }
+                if parts.intersects(&linked_parts) || parts.intersects(&self.library_parts) {
+                    return Err(GraphicsPipelineCreationError::OverlappingLibraryParts);
+                }
+                linked_parts = linked_parts | parts;
+            }
+
+            if !self.library_parts.is_empty() {
+                self.library_parts
+            } else {
+                GraphicsPipelineLibraryParts {
+                    vertex_input_interface: !linked_parts.vertex_input_interface,
+                    pre_rasterization_shaders: !linked_parts.pre_rasterization_shaders,
+                    fragment_shader: !linked_parts.fragment_shader,
+                    fragment_output_interface: !linked_parts.fragment_output_interface,
+                }
+            }
+        } else {
+            GraphicsPipelineLibraryParts::all()
+        };
+
         // Checking that the pipeline layout matches the shader stages.
         // TODO: more details in the errors
         PipelineLayoutSuperset::ensure_superset_of(
@@ -448,9 +520,97 @@ where
             return Err(GraphicsPipelineCreationError::FragmentShaderRenderPassIncompatible);
         }
 
+        // Geometry and tessellation shaders require additional features with multiview.
+        if self.render_pass.as_ref().unwrap().view_mask() != 0 {
+            if self.geometry_shader.is_some()
+                && !device.enabled_features().multiview_geometry_shader
+            {
+                return Err(
+                    GraphicsPipelineCreationError::MultiviewGeometryShaderFeatureNotEnabled,
+                );
+            }
+            if self.tessellation.is_some()
+                && !device.enabled_features().multiview_tessellation_shader
+            {
+                return Err(
+                    GraphicsPipelineCreationError::MultiviewTessellationShaderFeatureNotEnabled,
+                );
+            }
+        }
+
         // Will contain the list of dynamic states. Filled throughout this function.
         let mut dynamic_states: SmallVec<[vk::DynamicState; 8]> = SmallVec::new();
 
+        // Checking the specialization constants against the shader modules.
+        let spec_error = |stage| {
+            move |error| GraphicsPipelineCreationError::IncompatibleSpecializationConstants {
+                stage,
+                error,
+            }
+        };
+        {
+            let (vs, constants) = self.vertex_shader.as_ref().unwrap();
+            vs.module()
+                .check_specialization_constants(constants)
+                .map_err(spec_error(ShaderStage::Vertex))?;
+        }
+        if let Some(ref tess) = self.tessellation {
+            let (tcs, constants) = &tess.tessellation_control_shader;
+            tcs.module()
+                .check_specialization_constants(constants)
+                .map_err(spec_error(ShaderStage::TessellationControl))?;
+            let (tes, constants) = &tess.tessellation_evaluation_shader;
+            tes.module()
+                .check_specialization_constants(constants)
+                .map_err(spec_error(ShaderStage::TessellationEvaluation))?;
+        }
+        if let Some((ref gs, ref constants)) = self.geometry_shader {
+            gs.module()
+                .check_specialization_constants(constants)
+                .map_err(spec_error(ShaderStage::Geometry))?;
+        }
+        {
+            let (fs, constants) = self.fragment_shader.as_ref().unwrap();
+            fs.module()
+                .check_specialization_constants(constants)
+                .map_err(spec_error(ShaderStage::Fragment))?;
+        }
+
+        // Checking the features required by the capabilities of the shader modules.
+        {
+            let mut modules = vec![(
+                self.vertex_shader.as_ref().unwrap().0.module(),
+                ShaderStage::Vertex,
+            )];
+            if let Some(ref tess) = self.tessellation {
+                modules.push((
+                    tess.tessellation_control_shader.0.module(),
+                    ShaderStage::TessellationControl,
+                ));
+                modules.push((
+                    tess.tessellation_evaluation_shader.0.module(),
+                    ShaderStage::TessellationEvaluation,
+                ));
+            }
+            if let Some((ref gs, _)) = self.geometry_shader {
+                modules.push((gs.module(), ShaderStage::Geometry));
+            }
+            modules.push((
+                self.fragment_shader.as_ref().unwrap().0.module(),
+                ShaderStage::Fragment,
+            ));
+
+            for (module, stage) in modules {
+                let missing = module.missing_features(device.enabled_features());
+                if missing != Features::none() {
+                    return Err(GraphicsPipelineCreationError::ShaderFeaturesNotEnabled {
+                        stage,
+                        missing: Box::new(missing),
+                    });
+                }
+            }
+        }
+
         // Creating the specialization constants of the various stages.
         let vertex_shader_specialization = {
             let spec_descriptors = Vss::descriptors();
@@ -510,7 +670,41 @@ where
             }
         };
 
-        // List of shader stages.
+        // Identifiers of the shader modules that were built from an identifier. They are chained to
+        // the stages, and must outlive them.
+        let vertex_shader_identifier = self
+            .vertex_shader
+            .as_ref()
+            .unwrap()
+            .0
+            .module()
+            .stage_identifier_info();
+        let fragment_shader_identifier = self
+            .fragment_shader
+            .as_ref()
+            .unwrap()
+            .0
+            .module()
+            .stage_identifier_info();
+        let geometry_shader_identifier = self
+            .geometry_shader
+            .as_ref()
+            .and_then(|gs| gs.0.module().stage_identifier_info());
+        let tess_shader_identifiers = self.tessellation.as_ref().map(|tess| {
+            (
+                tess.tessellation_control_shader
+                    .0
+                    .module()
+                    .stage_identifier_info(),
+                tess.tessellation_evaluation_shader
+                    .0
+                    .module()
+                    .stage_identifier_info(),
+            )
+        });
+
+        // List of shader stages. Stages that belong to parts that aren't created by this call must
+        // be left out.
         let stages = {
             let mut stages = SmallVec::<[_; 5]>::new();
 
@@ -521,7 +715,7 @@ where
 
             stages.push(vk::PipelineShaderStageCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: identifier_next(&vertex_shader_identifier),
                 flags: 0, // reserved
                 stage: vk::SHADER_STAGE_VERTEX_BIT,
                 module: self
@@ -542,7 +736,7 @@ where
 
             stages.push(vk::PipelineShaderStageCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: identifier_next(&fragment_shader_identifier),
                 flags: 0, // reserved
                 stage: vk::SHADER_STAGE_FRAGMENT_BIT,
                 module: self
@@ -563,7 +757,7 @@ where
 
                 stages.push(vk::PipelineShaderStageCreateInfo {
                     sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
-                    pNext: ptr::null(),
+                    pNext: identifier_next(&geometry_shader_identifier),
                     flags: 0, // reserved
                     stage: vk::SHADER_STAGE_GEOMETRY_BIT,
                     module: gs.0.module().internal_object(),
@@ -592,7 +786,7 @@ where
 
                 stages.push(vk::PipelineShaderStageCreateInfo {
                     sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
-                    pNext: ptr::null(),
+                    pNext: identifier_next(&tess_shader_identifiers.as_ref().unwrap().0),
                     flags: 0, // reserved
                     stage: vk::SHADER_STAGE_TESSELLATION_CONTROL_BIT,
                     module: tess
@@ -607,7 +801,7 @@ where
 
                 stages.push(vk::PipelineShaderStageCreateInfo {
                     sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
-                    pNext: ptr::null(),
+                    pNext: identifier_next(&tess_shader_identifiers.as_ref().unwrap().1),
                     flags: 0, // reserved
                     stage: vk::SHADER_STAGE_TESSELLATION_EVALUATION_BIT,
                     module: tess
@@ -621,16 +815,24 @@ where
                 });
             }
 
+            if !created_parts.pre_rasterization_shaders {
+                stages.retain(|stage| stage.stage == vk::SHADER_STAGE_FRAGMENT_BIT);
+            }
+            if !created_parts.fragment_shader {
+                stages.retain(|stage| stage.stage != vk::SHADER_STAGE_FRAGMENT_BIT);
+            }
+
             stages
         };
 
         // Vertex bindings.
-        let (binding_descriptions, attribute_descriptions) = {
+        let (binding_descriptions, attribute_descriptions, binding_divisors) = {
             let (buffers_iter, attribs_iter) = self
                 .vertex_input
                 .definition(self.vertex_shader.as_ref().unwrap().0.input())?;
 
             let mut binding_descriptions = SmallVec::<[_; 8]>::new();
+            let mut binding_divisors = SmallVec::<[_; 8]>::new();
             for (num, stride, rate) in buffers_iter {
                 if stride
                     > device
@@ -651,10 +853,55 @@ where
                     );
                 }
 
+                match rate {
+                    InputRate::Instance { divisor } if divisor != 1 => {
+                        if !device.loaded_extensions().ext_vertex_attribute_divisor {
+                            return Err(GraphicsPipelineCreationError::VertexAttributeDivisorExtensionNotEnabled);
+                        }
+
+                        if divisor == 0 {
+                            if !device
+                                .enabled_features()
+                                .vertex_attribute_instance_rate_zero_divisor
+                            {
+                                return Err(GraphicsPipelineCreationError::VertexAttributeInstanceRateZeroDivisorFeatureNotEnabled);
+                            }
+                        } else {
+                            if !device
+                                .enabled_features()
+                                .vertex_attribute_instance_rate_divisor
+                            {
+                                return Err(GraphicsPipelineCreationError::VertexAttributeInstanceRateDivisorFeatureNotEnabled);
+                            }
+
+                            let max = device
+                                .physical_device()
+                                .extended_properties()
+                                .max_vertex_attrib_divisor()
+                                .unwrap_or(1);
+                            if divisor > max {
+                                return Err(
+                                    GraphicsPipelineCreationError::MaxVertexAttribDivisorExceeded {
+                                        binding: num as usize,
+                                        max,
+                                        obtained: divisor,
+                                    },
+                                );
+                            }
+                        }
+
+                        binding_divisors.push(vk::VertexInputBindingDivisorDescriptionEXT {
+                            binding: num,
+                            divisor,
+                        });
+                    }
+                    _ => (),
+                }
+
                 binding_descriptions.push(vk::VertexInputBindingDescription {
                     binding: num as u32,
                     stride: stride as u32,
-                    inputRate: rate as u32,
+                    inputRate: rate.into(),
                 });
             }
 
@@ -693,7 +940,11 @@ where
                 });
             }
 
-            (binding_descriptions, attribute_descriptions)
+            (
+                binding_descriptions,
+                attribute_descriptions,
+                binding_divisors,
+            )
         };
 
         if binding_descriptions.len()
@@ -730,9 +981,23 @@ where
             );
         }
 
+        let vertex_input_divisor_state = if !binding_divisors.is_empty() {
+            Some(vk::PipelineVertexInputDivisorStateCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_PIPELINE_VERTEX_INPUT_DIVISOR_STATE_CREATE_INFO_EXT,
+                pNext: ptr::null(),
+                vertexBindingDivisorCount: binding_divisors.len() as u32,
+                pVertexBindingDivisors: binding_divisors.as_ptr(),
+            })
+        } else {
+            None
+        };
+
         let vertex_input_state = vk::PipelineVertexInputStateCreateInfo {
             sType: vk::STRUCTURE_TYPE_PIPELINE_VERTEX_INPUT_STATE_CREATE_INFO,
-            pNext: ptr::null(),
+            pNext: vertex_input_divisor_state
+                .as_ref()
+                .map(|d| d as *const _ as *const _)
+                .unwrap_or(ptr::null()),
             flags: 0, // reserved
             vertexBindingDescriptionCount: binding_descriptions.len() as u32,
             pVertexBindingDescriptions: binding_descriptions.as_ptr(),
@@ -740,8 +1005,12 @@ where
             pVertexAttributeDescriptions: attribute_descriptions.as_ptr(),
         };
 
+        // The static value is ignored if primitive restart is dynamic.
         if self.input_assembly.primitiveRestartEnable != vk::FALSE
-            && !self.input_assembly_topology.supports_primitive_restart()
+            && !self.extended_dynamic_state.primitive_restart_enable
+            && !self
+                .input_assembly_topology
+                .supports_primitive_restart_with_features(device.enabled_features())
         {
             return Err(
                 GraphicsPipelineCreationError::PrimitiveDoesntSupportPrimitiveRestart {
@@ -750,6 +1019,14 @@ where
             );
         }
 
+        // Portability subset implementations such as MoltenVK may not support triangle fans.
+        if self.input_assembly_topology == PrimitiveTopology::TriangleFan
+            && device.loaded_extensions().khr_portability_subset
+            && !device.enabled_features().triangle_fans
+        {
+            return Err(GraphicsPipelineCreationError::TriangleFansFeatureNotEnabled);
+        }
+
         // TODO: should check from the tess eval shader instead of the input assembly
         if let Some(ref gs) = self.geometry_shader {
             match gs.0.ty() {
@@ -878,6 +1155,11 @@ where
             if line_width != 1.0 && !device.enabled_features().wide_lines {
                 return Err(GraphicsPipelineCreationError::WideLinesFeatureNotEnabled);
             }
+
+            let range = device.physical_device().limits().line_width_range();
+            if !(line_width >= range[0] && line_width <= range[1]) {
+                return Err(GraphicsPipelineCreationError::LineWidthOutOfRange);
+            }
         } else {
             dynamic_states.push(vk::DYNAMIC_STATE_LINE_WIDTH);
         }
@@ -912,9 +1194,166 @@ where
             return Err(GraphicsPipelineCreationError::FillModeNonSolidFeatureNotEnabled);
         }
 
+        let conservative_rasterization = match self.raster.conservative {
+            ConservativeRasterization::Disabled => None,
+            conservative => {
+                if !device.loaded_extensions().ext_conservative_rasterization {
+                    return Err(
+                        GraphicsPipelineCreationError::ConservativeRasterizationExtensionNotEnabled,
+                    );
+                }
+
+                let physical_device = device.physical_device();
+                let properties = physical_device.extended_properties();
+                let extra_size = match conservative {
+                    ConservativeRasterization::Overestimate { extra_size } => {
+                        let max = properties
+                            .max_extra_primitive_overestimation_size()
+                            .unwrap_or(0.0);
+                        if !(extra_size >= 0.0 && extra_size <= max) {
+                            return Err(GraphicsPipelineCreationError::MaxExtraPrimitiveOverestimationSizeExceeded);
+                        }
+                        extra_size
+                    }
+                    ConservativeRasterization::Underestimate => {
+                        if !properties.primitive_underestimation().unwrap_or(false) {
+                            return Err(
+                                GraphicsPipelineCreationError::PrimitiveUnderestimationNotSupported,
+                            );
+                        }
+                        0.0
+                    }
+                    ConservativeRasterization::Disabled => unreachable!(),
+                };
+
+                Some(vk::PipelineRasterizationConservativeStateCreateInfoEXT {
+                    sType:
+                        vk::STRUCTURE_TYPE_PIPELINE_RASTERIZATION_CONSERVATIVE_STATE_CREATE_INFO_EXT,
+                    pNext: ptr::null(),
+                    flags: 0, // reserved
+                    conservativeRasterizationMode: conservative.mode(),
+                    extraPrimitiveOverestimationSize: extra_size,
+                })
+            }
+        };
+
+        let mut rasterization_next: *const c_void = conservative_rasterization
+            .as_ref()
+            .map(|c| c as *const _ as *const _)
+            .unwrap_or(ptr::null());
+
+        let line_rasterization = if self.raster.line_rasterization_mode
+            == LineRasterizationMode::Default
+            && self.raster.line_stipple == LineStippleControl::Disabled
+        {
+            None
+        } else {
+            if !device.loaded_extensions().ext_line_rasterization {
+                return Err(GraphicsPipelineCreationError::LineRasterizationExtensionNotEnabled);
+            }
+
+            let features = device.enabled_features();
+            let (mode_supported, stipple_supported) = match self.raster.line_rasterization_mode {
+                LineRasterizationMode::Default => (
+                    true,
+                    features.stippled_rectangular_lines
+                        && device.physical_device().limits().strict_lines() != 0,
+                ),
+                LineRasterizationMode::Rectangular => (
+                    features.rectangular_lines,
+                    features.stippled_rectangular_lines,
+                ),
+                LineRasterizationMode::Bresenham => {
+                    (features.bresenham_lines, features.stippled_bresenham_lines)
+                }
+                LineRasterizationMode::RectangularSmooth => {
+                    (features.smooth_lines, features.stippled_smooth_lines)
+                }
+            };
+            if !mode_supported {
+                return Err(GraphicsPipelineCreationError::LineRasterizationModeFeatureNotEnabled);
+            }
+
+            let (stippled, factor, pattern) = match self.raster.line_stipple {
+                LineStippleControl::Disabled => (false, 1, 0),
+                LineStippleControl::Dynamic => {
+                    dynamic_states.push(vk::DYNAMIC_STATE_LINE_STIPPLE_EXT);
+                    (true, 1, 0)
+                }
+                LineStippleControl::Static(stipple) => {
+                    if !(stipple.factor >= 1 && stipple.factor <= 256) {
+                        return Err(GraphicsPipelineCreationError::InvalidLineStippleFactor);
+                    }
+                    (true, stipple.factor, stipple.pattern)
+                }
+            };
+            if stippled && !stipple_supported {
+                return Err(GraphicsPipelineCreationError::LineStippleFeatureNotEnabled);
+            }
+
+            Some(vk::PipelineRasterizationLineStateCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_PIPELINE_RASTERIZATION_LINE_STATE_CREATE_INFO_EXT,
+                pNext: rasterization_next,
+                lineRasterizationMode: self.raster.line_rasterization_mode as u32,
+                stippledLineEnable: if stippled { vk::TRUE } else { vk::FALSE },
+                lineStippleFactor: factor,
+                lineStipplePattern: pattern,
+            })
+        };
+        if let Some(ref line_rasterization) = line_rasterization {
+            rasterization_next = line_rasterization as *const _ as *const _;
+        }
+
+        let provoking_vertex = match self.raster.provoking_vertex_mode {
+            ProvokingVertexMode::FirstVertex => None,
+            ProvokingVertexMode::LastVertex => {
+                if !device.loaded_extensions().ext_provoking_vertex {
+                    return Err(GraphicsPipelineCreationError::ProvokingVertexExtensionNotEnabled);
+                }
+                if !device.enabled_features().provoking_vertex_last {
+                    return Err(
+                        GraphicsPipelineCreationError::ProvokingVertexLastFeatureNotEnabled,
+                    );
+                }
+
+                Some(vk::PipelineRasterizationProvokingVertexStateCreateInfoEXT {
+                    sType:
+                        vk::STRUCTURE_TYPE_PIPELINE_RASTERIZATION_PROVOKING_VERTEX_STATE_CREATE_INFO_EXT,
+                    pNext: rasterization_next,
+                    provokingVertexMode: self.raster.provoking_vertex_mode as u32,
+                })
+            }
+        };
+        if let Some(ref provoking_vertex) = provoking_vertex {
+            rasterization_next = provoking_vertex as *const _ as *const _;
+        }
+
+        let depth_clip = match self.raster.depth_clip {
+            None => None,
+            Some(enable) => {
+                if !device.loaded_extensions().ext_depth_clip_enable {
+                    return Err(GraphicsPipelineCreationError::DepthClipEnableExtensionNotEnabled);
+                }
+                if !device.enabled_features().depth_clip_enable {
+                    return Err(GraphicsPipelineCreationError::DepthClipEnableFeatureNotEnabled);
+                }
+
+                Some(vk::PipelineRasterizationDepthClipStateCreateInfoEXT {
+                    sType:
+                        vk::STRUCTURE_TYPE_PIPELINE_RASTERIZATION_DEPTH_CLIP_STATE_CREATE_INFO_EXT,
+                    pNext: rasterization_next,
+                    flags: 0, // reserved
+                    depthClipEnable: if enable { vk::TRUE } else { vk::FALSE },
+                })
+            }
+        };
+        if let Some(ref depth_clip) = depth_clip {
+            rasterization_next = depth_clip as *const _ as *const _;
+        }
+
         let rasterization = vk::PipelineRasterizationStateCreateInfo {
             sType: vk::STRUCTURE_TYPE_PIPELINE_RASTERIZATION_STATE_CREATE_INFO,
-            pNext: ptr::null(),
+            pNext: rasterization_next,
             flags: 0, // reserved
             depthClampEnable: if self.raster.depth_clamp {
                 vk::TRUE
@@ -942,6 +1381,11 @@ where
             .unwrap()
             .num_samples()
             .unwrap_or(1);
+        self.multisample.pSampleMask = self
+            .sample_mask
+            .as_ref()
+            .map(|mask| mask.as_ptr())
+            .unwrap_or(ptr::null());
         if self.multisample.sampleShadingEnable != vk::FALSE {
             debug_assert!(
                 self.multisample.minSampleShading >= 0.0
@@ -957,6 +1401,101 @@ where
             }
         }
 
+        let sample_locations = match self.sample_locations {
+            SampleLocationsState::Disabled => None,
+            ref state => {
+                if !device.loaded_extensions().ext_sample_locations {
+                    return Err(GraphicsPipelineCreationError::SampleLocationsExtensionNotEnabled);
+                }
+
+                let sample_locations_info = match state {
+                    SampleLocationsState::Fixed(locations) => {
+                        let samples = self.multisample.rasterizationSamples;
+                        if locations.samples_per_pixel != samples {
+                            return Err(
+                                GraphicsPipelineCreationError::SampleLocationsSampleCountMismatch,
+                            );
+                        }
+
+                        let physical_device = device.physical_device();
+                        let properties = physical_device.extended_properties();
+                        if properties.sample_location_sample_counts().unwrap_or(0) & samples == 0 {
+                            return Err(
+                                GraphicsPipelineCreationError::SampleLocationsSampleCountNotSupported,
+                            );
+                        }
+
+                        if locations.locations.len() != locations.expected_len() {
+                            return Err(
+                                GraphicsPipelineCreationError::SampleLocationsCountMismatch {
+                                    expected: locations.expected_len() as u32,
+                                    obtained: locations.locations.len() as u32,
+                                },
+                            );
+                        }
+
+                        // The grid size must evenly divide the maximum grid size for this number
+                        // of samples.
+                        let max_grid_size = unsafe {
+                            let vk_i = physical_device.instance().pointers();
+                            let mut output = vk::MultisamplePropertiesEXT {
+                                sType: vk::STRUCTURE_TYPE_MULTISAMPLE_PROPERTIES_EXT,
+                                pNext: ptr::null_mut(),
+                                maxSampleLocationGridSize: vk::Extent2D {
+                                    width: 0,
+                                    height: 0,
+                                },
+                            };
+                            vk_i.GetPhysicalDeviceMultisamplePropertiesEXT(
+                                physical_device.internal_object(),
+                                samples,
+                                &mut output,
+                            );
+                            output.maxSampleLocationGridSize
+                        };
+                        let [width, height] = locations.grid_size;
+                        if width == 0
+                            || height == 0
+                            || max_grid_size.width % width != 0
+                            || max_grid_size.height % height != 0
+                        {
+                            return Err(
+                                GraphicsPipelineCreationError::InvalidSampleLocationsGridSize,
+                            );
+                        }
+
+                        locations.to_vulkan()
+                    }
+                    _ => {
+                        // The locations are ignored when they are dynamic.
+                        dynamic_states.push(vk::DYNAMIC_STATE_SAMPLE_LOCATIONS_EXT);
+                        vk::SampleLocationsInfoEXT {
+                            sType: vk::STRUCTURE_TYPE_SAMPLE_LOCATIONS_INFO_EXT,
+                            pNext: ptr::null(),
+                            sampleLocationsPerPixel: self.multisample.rasterizationSamples,
+                            sampleLocationGridSize: vk::Extent2D {
+                                width: 1,
+                                height: 1,
+                            },
+                            sampleLocationsCount: 0,
+                            pSampleLocations: ptr::null(),
+                        }
+                    }
+                };
+
+                Some(vk::PipelineSampleLocationsStateCreateInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_PIPELINE_SAMPLE_LOCATIONS_STATE_CREATE_INFO_EXT,
+                    pNext: ptr::null(),
+                    sampleLocationsEnable: vk::TRUE,
+                    sampleLocationsInfo: sample_locations_info,
+                })
+            }
+        };
+        self.multisample.pNext = sample_locations
+            .as_ref()
+            .map(|s| s as *const _ as *const _)
+            .unwrap_or(ptr::null());
+
         let depth_stencil = {
             let db = match self.depth_stencil.depth_bounds_test {
                 DepthBounds::Disabled => (vk::FALSE, 0.0, 0.0),
@@ -965,6 +1504,10 @@ where
                         return Err(GraphicsPipelineCreationError::DepthBoundsFeatureNotEnabled);
                     }
 
+                    if !(0.0..=1.0).contains(&range.start) || !(0.0..=1.0).contains(&range.end) {
+                        return Err(GraphicsPipelineCreationError::DepthBoundsOutOfRange);
+                    }
+
                     (vk::TRUE, range.start, range.end)
                 }
                 DepthBounds::Dynamic => {
@@ -1030,7 +1573,14 @@ where
                 return Err(GraphicsPipelineCreationError::NoStencilAttachment);
             }
 
-            // FIXME: stencil writability
+            let stencil_writes =
+                |stencil: &Stencil| !stencil.always_keep() && stencil.write_mask != Some(0);
+            if (stencil_writes(&self.depth_stencil.stencil_front)
+                || stencil_writes(&self.depth_stencil.stencil_back))
+                && !self.render_pass.as_ref().unwrap().has_writable_stencil()
+            {
+                return Err(GraphicsPipelineCreationError::NoStencilAttachment);
+            }
 
             vk::PipelineDepthStencilStateCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_DEPTH_STENCIL_STATE_CREATE_INFO,
@@ -1096,6 +1646,110 @@ where
             }
         };
 
+        // Advanced blend operations have additional requirements, and their parameters are
+        // passed in a separate struct.
+        let advanced_blend = {
+            let atch_blends: SmallVec<[&AttachmentBlend; 8]> = match self.blend.attachments {
+                AttachmentsBlend::Collective(ref blend) => {
+                    let num_atch = self.render_pass.as_ref().unwrap().num_color_attachments();
+                    (0..num_atch).map(|_| blend).collect()
+                }
+                AttachmentsBlend::Individual(ref blend) => blend.iter().collect(),
+            };
+
+            let mut advanced_ops: SmallVec<[BlendOp; 8]> = SmallVec::new();
+            for b in atch_blends
+                .iter()
+                .filter(|b| b.enabled && (b.color_op.is_advanced() || b.alpha_op.is_advanced()))
+            {
+                if b.color_op != b.alpha_op {
+                    return Err(GraphicsPipelineCreationError::AdvancedBlendOpMismatch);
+                }
+                advanced_ops.push(b.color_op);
+            }
+
+            if advanced_ops.is_empty() {
+                None
+            } else {
+                if !device.loaded_extensions().ext_blend_operation_advanced {
+                    return Err(GraphicsPipelineCreationError::AdvancedBlendExtensionNotEnabled);
+                }
+
+                let physical_device = device.physical_device();
+                let properties = physical_device.extended_properties();
+                let max = properties
+                    .advanced_blend_max_color_attachments()
+                    .unwrap_or(0);
+                if advanced_ops.len() as u32 > max {
+                    return Err(
+                        GraphicsPipelineCreationError::AdvancedBlendMaxColorAttachmentsExceeded {
+                            max,
+                            obtained: advanced_ops.len() as u32,
+                        },
+                    );
+                }
+
+                if !properties
+                    .advanced_blend_independent_blend()
+                    .unwrap_or(false)
+                    && advanced_ops.iter().any(|op| *op != advanced_ops[0])
+                {
+                    return Err(
+                        GraphicsPipelineCreationError::AdvancedBlendIndependentBlendNotSupported,
+                    );
+                }
+
+                if !properties.advanced_blend_all_operations().unwrap_or(false)
+                    && advanced_ops
+                        .iter()
+                        .any(|op| op.requires_advanced_blend_all_operations())
+                {
+                    return Err(GraphicsPipelineCreationError::AdvancedBlendOperationNotSupported);
+                }
+
+                let advanced = self.blend.advanced;
+                if (!advanced.src_premultiplied
+                    && !properties
+                        .advanced_blend_non_premultiplied_src_color()
+                        .unwrap_or(false))
+                    || (!advanced.dst_premultiplied
+                        && !properties
+                            .advanced_blend_non_premultiplied_dst_color()
+                            .unwrap_or(false))
+                {
+                    return Err(
+                        GraphicsPipelineCreationError::AdvancedBlendNonPremultipliedNotSupported,
+                    );
+                }
+
+                if advanced.overlap != BlendOverlap::Uncorrelated
+                    && !properties
+                        .advanced_blend_correlated_overlap()
+                        .unwrap_or(false)
+                {
+                    return Err(
+                        GraphicsPipelineCreationError::AdvancedBlendCorrelatedOverlapNotSupported,
+                    );
+                }
+
+                Some(vk::PipelineColorBlendAdvancedStateCreateInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_PIPELINE_COLOR_BLEND_ADVANCED_STATE_CREATE_INFO_EXT,
+                    pNext: ptr::null(),
+                    srcPremultiplied: if advanced.src_premultiplied {
+                        vk::TRUE
+                    } else {
+                        vk::FALSE
+                    },
+                    dstPremultiplied: if advanced.dst_premultiplied {
+                        vk::TRUE
+                    } else {
+                        vk::FALSE
+                    },
+                    blendOverlap: advanced.overlap as u32,
+                })
+            }
+        };
+
         let blend_atch: SmallVec<[vk::PipelineColorBlendAttachmentState; 8]> = {
             let num_atch = self.render_pass.as_ref().unwrap().num_color_attachments();
 
@@ -1110,7 +1764,9 @@ where
                         );
                     }
 
-                    if !device.enabled_features().independent_blend {
+                    if !device.enabled_features().independent_blend
+                        && blend.iter().skip(1).any(|b| *b != blend[0])
+                    {
                         return Err(
                             GraphicsPipelineCreationError::IndependentBlendFeatureNotEnabled,
                         );
@@ -1126,7 +1782,10 @@ where
 
         let blend = vk::PipelineColorBlendStateCreateInfo {
             sType: vk::STRUCTURE_TYPE_PIPELINE_COLOR_BLEND_STATE_CREATE_INFO,
-            pNext: ptr::null(),
+            pNext: advanced_blend
+                .as_ref()
+                .map(|a| a as *const _ as *const _)
+                .unwrap_or(ptr::null()),
             flags: 0, // reserved
             logicOpEnable: if self.blend.logic_op.is_some() {
                 if !device.enabled_features().logic_op {
@@ -1147,6 +1806,80 @@ where
             },
         };
 
+        let fragment_shading_rate = match self.fragment_shading_rate {
+            FragmentShadingRateState::Disabled => None,
+            FragmentShadingRateState::Fixed(shading_rate) => {
+                if !device.loaded_extensions().khr_fragment_shading_rate {
+                    return Err(
+                        GraphicsPipelineCreationError::FragmentShadingRateExtensionNotEnabled,
+                    );
+                }
+                if !device.enabled_features().pipeline_fragment_shading_rate {
+                    return Err(
+                        GraphicsPipelineCreationError::PipelineFragmentShadingRateFeatureNotEnabled,
+                    );
+                }
+                if !shading_rate.is_valid_fragment_size() {
+                    return Err(GraphicsPipelineCreationError::InvalidFragmentShadingRateSize);
+                }
+                if shading_rate.combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+                    && !device.enabled_features().primitive_fragment_shading_rate
+                {
+                    return Err(
+                        GraphicsPipelineCreationError::PrimitiveFragmentShadingRateFeatureNotEnabled,
+                    );
+                }
+                if shading_rate.combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+                    && !device.enabled_features().attachment_fragment_shading_rate
+                {
+                    return Err(
+                        GraphicsPipelineCreationError::AttachmentFragmentShadingRateFeatureNotEnabled,
+                    );
+                }
+                if !shading_rate.combiner_ops.iter().all(|op| op.is_trivial())
+                    && !device
+                        .physical_device()
+                        .extended_properties()
+                        .fragment_shading_rate_non_trivial_combiner_ops()
+                        .unwrap_or(false)
+                {
+                    return Err(
+                        GraphicsPipelineCreationError::NonTrivialFragmentShadingRateCombinerOpsNotSupported,
+                    );
+                }
+
+                Some(vk::PipelineFragmentShadingRateStateCreateInfoKHR {
+                    sType: vk::STRUCTURE_TYPE_PIPELINE_FRAGMENT_SHADING_RATE_STATE_CREATE_INFO_KHR,
+                    pNext: ptr::null(),
+                    fragmentSize: vk::Extent2D {
+                        width: shading_rate.fragment_size[0],
+                        height: shading_rate.fragment_size[1],
+                    },
+                    combinerOps: [
+                        shading_rate.combiner_ops[0].into(),
+                        shading_rate.combiner_ops[1].into(),
+                    ],
+                })
+            }
+            FragmentShadingRateState::Dynamic => {
+                if !device.loaded_extensions().khr_fragment_shading_rate {
+                    return Err(
+                        GraphicsPipelineCreationError::FragmentShadingRateExtensionNotEnabled,
+                    );
+                }
+                if !device.enabled_features().pipeline_fragment_shading_rate {
+                    return Err(
+                        GraphicsPipelineCreationError::PipelineFragmentShadingRateFeatureNotEnabled,
+                    );
+                }
+                dynamic_states.push(vk::DYNAMIC_STATE_FRAGMENT_SHADING_RATE_KHR);
+                None
+            }
+        };
+
+        self.extended_dynamic_state
+            .push_vulkan_states(&device, &mut dynamic_states)?;
+
         let dynamic_states = if !dynamic_states.is_empty() {
             Some(vk::PipelineDynamicStateCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_DYNAMIC_STATE_CREATE_INFO,
@@ -1159,11 +1892,123 @@ where
             None
         };
 
+        let mut flags = 0; // TODO: some other flags are available but none are critical
+
+        let uses_identifiers = stages.iter().any(|stage| !stage.pNext.is_null());
+        if uses_identifiers {
+            if !device.enabled_features().pipeline_creation_cache_control {
+                return Err(
+                    GraphicsPipelineCreationError::PipelineCreationCacheControlFeatureNotEnabled,
+                );
+            }
+            flags |= vk::PIPELINE_CREATE_FAIL_ON_PIPELINE_COMPILE_REQUIRED_BIT_EXT;
+        }
+        if self.capture_statistics || self.capture_internal_representations {
+            if !device
+                .loaded_extensions()
+                .khr_pipeline_executable_properties
+            {
+                return Err(
+                    GraphicsPipelineCreationError::PipelineExecutablePropertiesExtensionNotEnabled,
+                );
+            }
+            if !device.enabled_features().pipeline_executable_info {
+                return Err(GraphicsPipelineCreationError::PipelineExecutableInfoFeatureNotEnabled);
+            }
+        }
+        if self.capture_statistics {
+            flags |= vk::PIPELINE_CREATE_CAPTURE_STATISTICS_BIT_KHR;
+        }
+        if self.capture_internal_representations {
+            flags |= vk::PIPELINE_CREATE_CAPTURE_INTERNAL_REPRESENTATIONS_BIT_KHR;
+        }
+
+        if !self.robustness.is_device_default() {
+            if !device.loaded_extensions().ext_pipeline_robustness {
+                return Err(GraphicsPipelineCreationError::PipelineRobustnessExtensionNotEnabled);
+            }
+            if !device.enabled_features().pipeline_robustness {
+                return Err(GraphicsPipelineCreationError::PipelineRobustnessFeatureNotEnabled);
+            }
+            let supported = device.physical_device().supported_features();
+            if let Some(name) = self.robustness.unsupported_feature(supported) {
+                return Err(
+                    GraphicsPipelineCreationError::PipelineRobustnessFeatureNotSupported { name },
+                );
+            }
+        }
+
+        // The optional structs below are chained in reverse order through `next`.
+        let mut next: *const c_void = fragment_shading_rate
+            .as_ref()
+            .map(|f| f as *const _ as *const _)
+            .unwrap_or(ptr::null());
+
+        let library_handles: SmallVec<[vk::Pipeline; 4]> = self
+            .libraries
+            .iter()
+            .map(|library| GraphicsPipelineAbstract::inner(&**library).internal_object())
+            .collect();
+        let library_infos = if !library_handles.is_empty() {
+            if self.link_time_optimization {
+                flags |= vk::PIPELINE_CREATE_LINK_TIME_OPTIMIZATION_BIT_EXT;
+            }
+
+            Some(vk::PipelineLibraryCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_PIPELINE_LIBRARY_CREATE_INFO_KHR,
+                pNext: next,
+                libraryCount: library_handles.len() as u32,
+                pLibraries: library_handles.as_ptr(),
+            })
+        } else {
+            None
+        };
+        if let Some(ref library_infos) = library_infos {
+            next = library_infos as *const _ as *const _;
+        }
+
+        let robustness = if !self.robustness.is_device_default() {
+            Some(vk::PipelineRobustnessCreateInfoEXT {
+                pNext: next,
+                ..self.robustness.to_vulkan()
+            })
+        } else {
+            None
+        };
+        if let Some(ref robustness) = robustness {
+            next = robustness as *const _ as *const _;
+        }
+
+        if !self.library_parts.is_empty() {
+            flags |= vk::PIPELINE_CREATE_LIBRARY_BIT_KHR;
+            if self.link_time_optimization {
+                flags |= vk::PIPELINE_CREATE_RETAIN_LINK_TIME_OPTIMIZATION_INFO_BIT_EXT;
+            }
+        }
+
+        // Without this struct, a pipeline that links libraries is created as if it contained
+        // none of the parts, so it must also be provided when the libraries don't cover them all.
+        let graphics_library_infos = if (!self.library_parts.is_empty()
+            || !self.libraries.is_empty())
+            && !created_parts.is_empty()
+        {
+            Some(vk::GraphicsPipelineLibraryCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_GRAPHICS_PIPELINE_LIBRARY_CREATE_INFO_EXT,
+                pNext: next,
+                flags: created_parts.into_vulkan_bits(),
+            })
+        } else {
+            None
+        };
+        if let Some(ref graphics_library_infos) = graphics_library_infos {
+            next = graphics_library_infos as *const _ as *const _;
+        }
+
         let pipeline = unsafe {
             let infos = vk::GraphicsPipelineCreateInfo {
                 sType: vk::STRUCTURE_TYPE_GRAPHICS_PIPELINE_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0, // TODO: some flags are available but none are critical
+                pNext: next,
+                flags,
                 stageCount: stages.len() as u32,
                 pStages: stages.as_ptr(),
                 pVertexInputState: &vertex_input_state,
@@ -1200,14 +2045,17 @@ where
             };
 
             let mut output = MaybeUninit::uninit();
-            check_errors(vk.CreateGraphicsPipelines(
+            let success = check_errors(vk.CreateGraphicsPipelines(
                 device.internal_object(),
                 cache_handle,
                 1,
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
+            if let Success::PipelineCompileRequired = success {
+                return Err(GraphicsPipelineCreationError::PipelineCompileRequired);
+            }
             output.assume_init()
         };
 
@@ -1218,6 +2066,38 @@ where
             panic!("vkCreateGraphicsPipelines provided a NULL handle");
         }
 
+        // Registering the entry points, so that the messages printed by the shaders can be
+        // tagged with them.
+        let instance = device.instance();
+        if instance.enabled_validation_features().debug_printf {
+            let mut entry_points = SmallVec::<[_; 5]>::new();
+            if let Some((ref vs, _)) = self.vertex_shader {
+                entry_points.push((ShaderStage::Vertex, vs.name()));
+            }
+            if let Some(ref tess) = self.tessellation {
+                entry_points.push((
+                    ShaderStage::TessellationControl,
+                    tess.tessellation_control_shader.0.name(),
+                ));
+                entry_points.push((
+                    ShaderStage::TessellationEvaluation,
+                    tess.tessellation_evaluation_shader.0.name(),
+                ));
+            }
+            if let Some((ref gs, _)) = self.geometry_shader {
+                entry_points.push((ShaderStage::Geometry, gs.name()));
+            }
+            if let Some((ref fs, _)) = self.fragment_shader {
+                entry_points.push((ShaderStage::Fragment, fs.name()));
+            }
+            instance.debug_printf_registry().register(
+                pipeline,
+                entry_points
+                    .into_iter()
+                    .map(|(stage, name)| (stage, name.to_string_lossy().into_owned())),
+            );
+        }
+
         let (render_pass, render_pass_subpass) = self.render_pass.take().unwrap().into();
 
         Ok(GraphicsPipeline {
@@ -1241,8 +2121,20 @@ where
             dynamic_stencil_write_mask: self.depth_stencil.stencil_back.write_mask.is_none(),
             dynamic_stencil_reference: self.depth_stencil.stencil_back.reference.is_none(),
             dynamic_blend_constants: self.blend.blend_constants.is_none(),
+            dynamic_fragment_shading_rate: self.fragment_shading_rate.is_dynamic(),
+            dynamic_line_stipple: self.raster.line_stipple.is_dynamic(),
+            dynamic_sample_locations: self.sample_locations.is_dynamic(),
+            primitive_topology: self.input_assembly_topology,
+            primitive_restart_enable: self.input_assembly.primitiveRestartEnable != vk::FALSE,
+            extended_dynamic_state: self.extended_dynamic_state,
 
             num_viewports: self.viewport.as_ref().unwrap().num_viewports(),
+
+            library_parts: self.library_parts,
+            libraries: self.libraries,
+
+            capture_statistics: self.capture_statistics,
+            capture_internal_representations: self.capture_internal_representations,
         })
     }
 
@@ -1254,6 +2146,81 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
 {
     // TODO: add pipeline derivate system
 
+    /// Builds a pipeline library that only contains the given parts of the pipeline, instead of
+    /// a complete pipeline. The state of the other parts is ignored. A pipeline library can't be
+    /// used for drawing, but can be linked into other pipelines with `link_library`.
+    ///
+    /// Requires the `ext_graphics_pipeline_library` device extension and the
+    /// `graphics_pipeline_library` feature.
+    #[inline]
+    pub fn library(mut self, parts: GraphicsPipelineLibraryParts) -> Self {
+        self.library_parts = parts;
+        self
+    }
+
+    /// Links a pipeline library into the pipeline being built. The state of the parts contained
+    /// in the library is taken from the library, and the corresponding state of the builder is
+    /// ignored. Libraries that are linked together must not contain the same parts.
+    ///
+    /// If all four parts are provided by libraries, building the pipeline only links them
+    /// together, which is much faster than compiling it.
+    ///
+    /// Requires the `ext_graphics_pipeline_library` device extension and the
+    /// `graphics_pipeline_library` feature.
+    #[inline]
+    pub fn link_library<L>(mut self, library: Arc<L>) -> Self
+    where
+        L: GraphicsPipelineAbstract + Send + Sync + 'static,
+    {
+        self.libraries.push(library as Arc<_>);
+        self
+    }
+
+    /// If true, the implementation will optimize the linked pipeline as a whole, which makes
+    /// building slower but can make drawing faster. When building a library, this retains the
+    /// information necessary for this optimization in the library.
+    ///
+    /// The default value is `false`.
+    #[inline]
+    pub fn link_time_optimization(mut self, enabled: bool) -> Self {
+        self.link_time_optimization = enabled;
+        self
+    }
+
+    /// If true, the implementation will keep statistics about the executables it compiles for
+    /// the pipeline, which can be queried with `GraphicsPipeline::executable_statistics`.
+    ///
+    /// Requires the `khr_pipeline_executable_properties` device extension and the
+    /// `pipeline_executable_info` feature. The default value is `false`.
+    #[inline]
+    pub fn capture_statistics(mut self, enabled: bool) -> Self {
+        self.capture_statistics = enabled;
+        self
+    }
+
+    /// If true, the implementation will keep the internal representations of the executables it
+    /// compiles for the pipeline, which can be queried with
+    /// `GraphicsPipeline::executable_internal_representations`.
+    ///
+    /// Requires the `khr_pipeline_executable_properties` device extension and the
+    /// `pipeline_executable_info` feature. The default value is `false`.
+    #[inline]
+    pub fn capture_internal_representations(mut self, enabled: bool) -> Self {
+        self.capture_internal_representations = enabled;
+        self
+    }
+
+    /// Sets the behavior of out of bounds accesses made by the shaders of the pipeline,
+    /// overriding the robustness features enabled on the device.
+    ///
+    /// Requires the `ext_pipeline_robustness` device extension and the `pipeline_robustness`
+    /// feature, unless all the members are `DeviceDefault`, which is the default.
+    #[inline]
+    pub fn robustness(mut self, robustness: PipelineRobustness) -> Self {
+        self.robustness = robustness;
+        self
+    }
+
     /// Sets the vertex input.
     #[inline]
     pub fn vertex_input<T>(
@@ -1270,11 +2237,22 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
+            fragment_shading_rate: self.fragment_shading_rate,
+            extended_dynamic_state: self.extended_dynamic_state,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
+            blend_overrides: self.blend_overrides,
             render_pass: self.render_pass,
             cache: self.cache,
+            library_parts: self.library_parts,
+            libraries: self.libraries,
+            link_time_optimization: self.link_time_optimization,
+            capture_statistics: self.capture_statistics,
+            capture_internal_representations: self.capture_internal_representations,
+            sample_locations: self.sample_locations,
+            robustness: self.robustness,
         }
     }
 
@@ -1324,15 +2302,30 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
+            fragment_shading_rate: self.fragment_shading_rate,
+            extended_dynamic_state: self.extended_dynamic_state,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
+            blend_overrides: self.blend_overrides,
             render_pass: self.render_pass,
             cache: self.cache,
+            library_parts: self.library_parts,
+            libraries: self.libraries,
+            link_time_optimization: self.link_time_optimization,
+            capture_statistics: self.capture_statistics,
+            capture_internal_representations: self.capture_internal_representations,
+            sample_locations: self.sample_locations,
+            robustness: self.robustness,
         }
     }
 
     /// Sets whether primitive restart if enabled.
+    ///
+    /// Primitive restart is only supported by strip and fan topologies, unless the
+    /// `primitive_topology_list_restart` or `primitive_topology_patch_list_restart` features
+    /// are enabled. It can also be made dynamic with `ExtendedDynamicState`.
     #[inline]
     pub fn primitive_restart(mut self, enabled: bool) -> Self {
         self.input_assembly.primitiveRestartEnable = if enabled { vk::TRUE } else { vk::FALSE };
@@ -1483,11 +2476,22 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
+            fragment_shading_rate: self.fragment_shading_rate,
+            extended_dynamic_state: self.extended_dynamic_state,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
+            blend_overrides: self.blend_overrides,
             render_pass: self.render_pass,
             cache: self.cache,
+            library_parts: self.library_parts,
+            libraries: self.libraries,
+            link_time_optimization: self.link_time_optimization,
+            capture_statistics: self.capture_statistics,
+            capture_internal_representations: self.capture_internal_representations,
+            sample_locations: self.sample_locations,
+            robustness: self.robustness,
         }
     }
 
@@ -1520,11 +2524,22 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
+            fragment_shading_rate: self.fragment_shading_rate,
+            extended_dynamic_state: self.extended_dynamic_state,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
+            blend_overrides: self.blend_overrides,
             render_pass: self.render_pass,
             cache: self.cache,
+            library_parts: self.library_parts,
+            libraries: self.libraries,
+            link_time_optimization: self.link_time_optimization,
+            capture_statistics: self.capture_statistics,
+            capture_internal_representations: self.capture_internal_representations,
+            sample_locations: self.sample_locations,
+            robustness: self.robustness,
         }
     }
 
@@ -1604,12 +2619,32 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     /// If true, then the depth value of the vertices will be clamped to the range `[0.0 ; 1.0]`.
     /// If false, fragments whose depth is outside of this range will be discarded before the
     /// fragment shader even runs.
+    ///
+    /// Enabling depth clamping also disables depth clipping, unless `depth_clip` is called.
     #[inline]
     pub fn depth_clamp(mut self, clamp: bool) -> Self {
         self.raster.depth_clamp = clamp;
         self
     }
 
+    /// Sets whether primitives are clipped against the near and far planes, independently of
+    /// depth clamping.
+    ///
+    /// Requires the `ext_depth_clip_enable` device extension and the `depth_clip_enable`
+    /// feature.
+    #[inline]
+    pub fn depth_clip(mut self, clip: bool) -> Self {
+        self.raster.depth_clip = Some(clip);
+        self
+    }
+
+    /// Makes depth clipping depend on depth clamping, as in standard Vulkan. This is the default.
+    #[inline]
+    pub fn depth_clip_default(mut self) -> Self {
+        self.raster.depth_clip = None;
+        self
+    }
+
     // TODO: this won't work correctly
     /*/// Disables the fragment shader stage.
     #[inline]
@@ -1707,6 +2742,82 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
+    /// Sets the algorithm used to rasterize lines. The default is `LineRasterizationMode::Default`.
+    ///
+    /// Other modes require the `ext_line_rasterization` device extension and the corresponding
+    /// feature.
+    #[inline]
+    pub fn line_rasterization_mode(mut self, mode: LineRasterizationMode) -> Self {
+        self.raster.line_rasterization_mode = mode;
+        self
+    }
+
+    /// Draws lines with a stipple pattern. Each bit of `pattern` is used for `factor` consecutive
+    /// pixels, which must be between 1 and 256.
+    ///
+    /// Requires the `ext_line_rasterization` device extension and the stippled lines feature
+    /// that corresponds to the line rasterization mode.
+    #[inline]
+    pub fn line_stipple(mut self, factor: u32, pattern: u16) -> Self {
+        self.raster.line_stipple = LineStippleControl::Static(LineStipple { factor, pattern });
+        self
+    }
+
+    /// Sets the stipple pattern of lines as dynamic, which means that you will need to set this
+    /// value when drawing.
+    #[inline]
+    pub fn line_stipple_dynamic(mut self) -> Self {
+        self.raster.line_stipple = LineStippleControl::Dynamic;
+        self
+    }
+
+    /// Draws lines without a stipple pattern. This is the default.
+    #[inline]
+    pub fn line_stipple_disabled(mut self) -> Self {
+        self.raster.line_stipple = LineStippleControl::Disabled;
+        self
+    }
+
+    /// Sets the vertex of each primitive that provides the values of flat shaded inputs. The
+    /// default is `ProvokingVertexMode::FirstVertex`.
+    ///
+    /// `LastVertex` matches the default behavior of OpenGL, and requires the
+    /// `ext_provoking_vertex` device extension and the `provoking_vertex_last` feature.
+    #[inline]
+    pub fn provoking_vertex_mode(mut self, mode: ProvokingVertexMode) -> Self {
+        self.raster.provoking_vertex_mode = mode;
+        self
+    }
+
+    /// Disables conservative rasterization. This is the default.
+    #[inline]
+    pub fn conservative_rasterization_disabled(mut self) -> Self {
+        self.raster.conservative = ConservativeRasterization::Disabled;
+        self
+    }
+
+    /// Enables overestimation conservative rasterization. A fragment will be generated for every
+    /// pixel that is even partially covered by a primitive, after the primitive has been extended
+    /// by `extra_size` pixels on top of the device's `primitive_overestimation_size`.
+    ///
+    /// Requires the `ext_conservative_rasterization` device extension.
+    #[inline]
+    pub fn conservative_rasterization_overestimate(mut self, extra_size: f32) -> Self {
+        self.raster.conservative = ConservativeRasterization::Overestimate { extra_size };
+        self
+    }
+
+    /// Enables underestimation conservative rasterization. A fragment will only be generated for
+    /// pixels that are fully covered by a primitive.
+    ///
+    /// Requires the `ext_conservative_rasterization` device extension and the
+    /// `primitive_underestimation` property of the device.
+    #[inline]
+    pub fn conservative_rasterization_underestimate(mut self) -> Self {
+        self.raster.conservative = ConservativeRasterization::Underestimate;
+        self
+    }
+
     // TODO: missing DepthBiasControl
 
     /// Disables sample shading. The fragment shader will only be run once per fragment (ie. per
@@ -1744,13 +2855,21 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
-    // TODO: doc
+    /// Disables alpha-to-coverage.
+    ///
+    /// Alpha-to-coverage is disabled by default.
+    #[inline]
     pub fn alpha_to_coverage_disabled(mut self) -> Self {
         self.multisample.alphaToCoverageEnable = vk::FALSE;
         self
     }
 
-    // TODO: doc
+    /// Enables alpha-to-coverage. The alpha component of the first color output of the fragment
+    /// shader will be used to generate a coverage mask, which is then combined with the coverage
+    /// of the fragment. This is commonly used to render foliage with multisampling.
+    ///
+    /// Alpha-to-coverage is disabled by default.
+    #[inline]
     pub fn alpha_to_coverage_enabled(mut self) -> Self {
         self.multisample.alphaToCoverageEnable = vk::TRUE;
         self
@@ -1777,7 +2896,68 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
-    // TODO: rasterizationSamples and pSampleMask
+    /// Sets the sample mask. Bit `i` of the mask corresponds to sample `i` of the framebuffer,
+    /// with the first word holding samples 0 to 31 and the second word holding samples 32 to 63.
+    /// Samples whose bit is 0 are never written to.
+    ///
+    /// By default all the samples are enabled.
+    #[inline]
+    pub fn sample_mask(mut self, mask: [u32; 2]) -> Self {
+        self.sample_mask = Some(mask);
+        self
+    }
+
+    /// Enables all the samples. This is the default.
+    #[inline]
+    pub fn sample_mask_all(mut self) -> Self {
+        self.sample_mask = None;
+        self
+    }
+
+    /// Sets the pipeline fragment shading rate, and how it is combined with the primitive and
+    /// attachment shading rates. See the `shading_rate` module for more information.
+    ///
+    /// Setting a shading rate requires the `VK_KHR_fragment_shading_rate` extension and the
+    /// `pipeline_fragment_shading_rate` feature to be enabled on the device. Using the primitive
+    /// or attachment shading rates in the combiner operations requires respectively the
+    /// `primitive_fragment_shading_rate` or `attachment_fragment_shading_rate` feature.
+    #[inline]
+    pub fn fragment_shading_rate(mut self, shading_rate: FragmentShadingRate) -> Self {
+        self.fragment_shading_rate = FragmentShadingRateState::Fixed(shading_rate);
+        self
+    }
+
+    /// Sets the fragment shading rate as dynamic. It will need to be set before drawing.
+    ///
+    /// This requires the `VK_KHR_fragment_shading_rate` extension to be enabled on the device.
+    #[inline]
+    pub fn fragment_shading_rate_dynamic(mut self) -> Self {
+        self.fragment_shading_rate = FragmentShadingRateState::Dynamic;
+        self
+    }
+
+    /// Sets each fragment to cover a single pixel. This is the default.
+    #[inline]
+    pub fn fragment_shading_rate_disabled(mut self) -> Self {
+        self.fragment_shading_rate = FragmentShadingRateState::Disabled;
+        self
+    }
+
+    /// Sets which of the states of the `VK_EXT_extended_dynamic_state` family of extensions are
+    /// dynamic. They will need to be set before drawing, and the values passed to the other
+    /// methods of the builder for these states are ignored.
+    ///
+    /// Each dynamic state requires its extension and feature to be enabled on the device. See
+    /// the documentation of `ExtendedDynamicState` for more information.
+    ///
+    /// By default none of these states are dynamic.
+    #[inline]
+    pub fn extended_dynamic_state(mut self, dynamic_state: ExtendedDynamicState) -> Self {
+        self.extended_dynamic_state = dynamic_state;
+        self
+    }
+
+    // TODO: rasterizationSamples
 
     /// Sets the fragment shader to use.
     ///
@@ -1803,11 +2983,22 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
+            fragment_shading_rate: self.fragment_shading_rate,
+            extended_dynamic_state: self.extended_dynamic_state,
             fragment_shader: Some((shader, specialization_constants)),
             depth_stencil: self.depth_stencil,
             blend: self.blend,
+            blend_overrides: self.blend_overrides,
             render_pass: self.render_pass,
             cache: self.cache,
+            library_parts: self.library_parts,
+            libraries: self.libraries,
+            link_time_optimization: self.link_time_optimization,
+            capture_statistics: self.capture_statistics,
+            capture_internal_representations: self.capture_internal_representations,
+            sample_locations: self.sample_locations,
+            robustness: self.robustness,
         }
     }
 
@@ -1845,20 +3036,114 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
-    // TODO: missing tons of depth-stencil stuff
+    /// Sets the comparison to use between the depth value of each fragment and the depth value
+    /// currently in the depth buffer.
+    ///
+    /// The depth test is disabled if `compare` is `Always` and depth writing is disabled.
+    #[inline]
+    pub fn depth_compare(mut self, compare: Compare) -> Self {
+        self.depth_stencil.depth_compare = compare;
+        self
+    }
+
+    /// Enables the depth bounds test. Fragments whose existing depth value in the depth buffer is
+    /// outside of `range` will be discarded.
+    ///
+    /// Enabling the depth bounds test requires the `depth_bounds` feature to be enabled on the
+    /// device. Both bounds must be between 0.0 and 1.0.
+    #[inline]
+    pub fn depth_bounds(mut self, range: Range<f32>) -> Self {
+        self.depth_stencil.depth_bounds_test = DepthBounds::Fixed(range);
+        self
+    }
+
+    /// Enables the depth bounds test, with the bounds set as dynamic. They will need to be set
+    /// before drawing.
+    ///
+    /// Enabling the depth bounds test requires the `depth_bounds` feature to be enabled on the
+    /// device.
+    #[inline]
+    pub fn depth_bounds_dynamic(mut self) -> Self {
+        self.depth_stencil.depth_bounds_test = DepthBounds::Dynamic;
+        self
+    }
+
+    /// Sets the depth bounds test as disabled. This is the default.
+    #[inline]
+    pub fn depth_bounds_disabled(mut self) -> Self {
+        self.depth_stencil.depth_bounds_test = DepthBounds::Disabled;
+        self
+    }
+
+    /// Sets the stencil operations to use for points, lines and triangles whose front is facing
+    /// the user.
+    ///
+    /// > **Note**: The compare mask, write mask and reference must be either all fixed in both
+    /// > the front and the back stencil state, or all dynamic in both.
+    #[inline]
+    pub fn stencil_front(mut self, stencil: Stencil) -> Self {
+        self.depth_stencil.stencil_front = stencil;
+        self
+    }
+
+    /// Sets the stencil operations to use for triangles whose back is facing the user.
+    ///
+    /// > **Note**: The compare mask, write mask and reference must be either all fixed in both
+    /// > the front and the back stencil state, or all dynamic in both.
+    #[inline]
+    pub fn stencil_back(mut self, stencil: Stencil) -> Self {
+        self.depth_stencil.stencil_back = stencil;
+        self
+    }
+
+    /// Sets the same stencil operations for both front-facing and back-facing primitives.
+    ///
+    /// > **Note**: This is equivalent to calling both `stencil_front` and `stencil_back` with
+    /// > the same value.
+    #[inline]
+    pub fn stencil_front_and_back(mut self, stencil: Stencil) -> Self {
+        self.depth_stencil.stencil_front = stencil;
+        self.depth_stencil.stencil_back = stencil;
+        self
+    }
 
+    /// Sets the same blending state for all the color attachments of the subpass.
     #[inline]
     pub fn blend_collective(mut self, blend: AttachmentBlend) -> Self {
         self.blend.attachments = AttachmentsBlend::Collective(blend);
+        self.blend_overrides.clear();
         self
     }
 
+    /// Sets a different blending state for each color attachment of the subpass.
+    ///
+    /// The iterator must produce exactly one element per color attachment of the subpass, in
+    /// order. Using blending states that are not all identical requires the `independent_blend`
+    /// feature to be enabled on the device.
     #[inline]
     pub fn blend_individual<I>(mut self, blend: I) -> Self
     where
         I: IntoIterator<Item = AttachmentBlend>,
     {
         self.blend.attachments = AttachmentsBlend::Individual(blend.into_iter().collect());
+        self.blend_overrides.clear();
+        self
+    }
+
+    /// Sets the blending state of the color attachment at index `attachment` of the subpass,
+    /// leaving the other attachments untouched.
+    ///
+    /// The state is applied when building the pipeline. If the blending is collective, the other
+    /// attachments of the subpass keep the collective state. If it is individual, attachments
+    /// past the end of the list are set to pass-through, and after that there must be exactly
+    /// one state per color attachment of the subpass.
+    ///
+    /// Using blending states that are not all identical requires the `independent_blend` feature
+    /// to be enabled on the device.
+    #[inline]
+    pub fn blend_attachment(mut self, attachment: u32, blend: AttachmentBlend) -> Self {
+        self.blend_overrides.retain(|&(a, _)| a != attachment);
+        self.blend_overrides.push((attachment, blend));
         self
     }
 
@@ -1887,6 +3172,44 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
+    /// Sets the parameters of the advanced blend operations, which are used if an attachment uses
+    /// one of the advanced `BlendOp`s of `VK_EXT_blend_operation_advanced`.
+    ///
+    /// The default treats both colors as premultiplied with uncorrelated overlap.
+    #[inline]
+    pub fn blend_advanced(mut self, advanced: AdvancedBlend) -> Self {
+        self.blend.advanced = advanced;
+        self
+    }
+
+    /// Sets custom locations for the samples within the pixels, instead of the standard locations
+    /// of the device.
+    ///
+    /// The number of samples per pixel of `locations` must be the number of samples of the
+    /// subpass. Requires the `ext_sample_locations` device extension.
+    #[inline]
+    pub fn sample_locations(mut self, locations: SampleLocations) -> Self {
+        self.sample_locations = SampleLocationsState::Fixed(locations);
+        self
+    }
+
+    /// Uses custom sample locations, but sets them as dynamic, which means that you will need to
+    /// set them when drawing.
+    ///
+    /// Requires the `ext_sample_locations` device extension.
+    #[inline]
+    pub fn sample_locations_dynamic(mut self) -> Self {
+        self.sample_locations = SampleLocationsState::Dynamic;
+        self
+    }
+
+    /// Uses the standard sample locations of the device. This is the default.
+    #[inline]
+    pub fn sample_locations_disabled(mut self) -> Self {
+        self.sample_locations = SampleLocationsState::Disabled;
+        self
+    }
+
     /// Sets the blend constant. The default is `[0.0, 0.0, 0.0, 0.0]`.
     ///
     /// The blend constant is used for some blending calculations. It is irrelevant otherwise.
@@ -1921,11 +3244,22 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
+            fragment_shading_rate: self.fragment_shading_rate,
+            extended_dynamic_state: self.extended_dynamic_state,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
+            blend_overrides: self.blend_overrides,
             render_pass: Some(subpass),
             cache: self.cache,
+            library_parts: self.library_parts,
+            libraries: self.libraries,
+            link_time_optimization: self.link_time_optimization,
+            capture_statistics: self.capture_statistics,
+            capture_internal_representations: self.capture_internal_representations,
+            sample_locations: self.sample_locations,
+            robustness: self.robustness,
         }
     }
 
@@ -1978,11 +3312,31 @@ where
                 alphaToCoverageEnable: self.multisample.alphaToCoverageEnable,
                 alphaToOneEnable: self.multisample.alphaToOneEnable,
             },
+            sample_mask: self.sample_mask,
+            fragment_shading_rate: self.fragment_shading_rate,
+            extended_dynamic_state: self.extended_dynamic_state,
             fragment_shader: self.fragment_shader.clone(),
             depth_stencil: self.depth_stencil.clone(),
             blend: self.blend.clone(),
+            blend_overrides: self.blend_overrides.clone(),
             render_pass: self.render_pass.clone(),
             cache: self.cache.clone(),
+            library_parts: self.library_parts,
+            libraries: self.libraries.clone(),
+            link_time_optimization: self.link_time_optimization,
+            capture_statistics: self.capture_statistics,
+            capture_internal_representations: self.capture_internal_representations,
+            sample_locations: self.sample_locations.clone(),
+            robustness: self.robustness,
         }
     }
 }
+
+// Returns the `pNext` pointer of a shader stage whose module may be built from an identifier.
+#[inline]
+fn identifier_next(
+    info: &Option<vk::PipelineShaderStageModuleIdentifierCreateInfoEXT>,
+) -> *const c_void {
+    info.as_ref()
+        .map_or(ptr::null(), |info| info as *const _ as *const _)
+}
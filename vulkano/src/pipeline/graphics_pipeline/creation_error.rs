@@ -11,9 +11,14 @@ use std::error;
 use std::fmt;
 use std::u32;
 
+use crate::descriptor::pipeline_layout::PipelineLayoutCreationError;
+use crate::descriptor::pipeline_layout::PipelineLayoutDescUnionError;
 use crate::descriptor::pipeline_layout::PipelineLayoutNotSupersetError;
+use crate::features::Features;
 use crate::pipeline::input_assembly::PrimitiveTopology;
+use crate::pipeline::reflect::ShaderStage;
 use crate::pipeline::shader::ShaderInterfaceMismatchError;
+use crate::pipeline::shader::SpecializationConstantsError;
 use crate::pipeline::vertex::IncompatibleVertexDefinitionError;
 use crate::Error;
 use crate::OomError;
@@ -27,6 +32,12 @@ pub enum GraphicsPipelineCreationError {
     /// The pipeline layout is not compatible with what the shaders expect.
     IncompatiblePipelineLayout(PipelineLayoutNotSupersetError),
 
+    /// The layouts of the shader stages can't be merged into a single pipeline layout.
+    IncompatibleShaderLayouts(PipelineLayoutDescUnionError),
+
+    /// Error while creating the pipeline layout from the shader stages.
+    PipelineLayoutCreationError(PipelineLayoutCreationError),
+
     /// The interface between the vertex shader and the geometry shader mismatches.
     VertexGeometryStagesMismatch(ShaderInterfaceMismatchError),
 
@@ -69,6 +80,28 @@ pub enum GraphicsPipelineCreationError {
         obtained: usize,
     },
 
+    /// The maximum divisor of a per-instance vertex binding has been exceeded.
+    MaxVertexAttribDivisorExceeded {
+        /// Index of the faulty binding.
+        binding: usize,
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
+    /// The `ext_vertex_attribute_divisor` extension must be enabled in order to use a
+    /// per-instance vertex binding divisor different from 1.
+    VertexAttributeDivisorExtensionNotEnabled,
+
+    /// The `vertex_attribute_instance_rate_divisor` feature must be enabled in order to use a
+    /// per-instance vertex binding divisor different from 1.
+    VertexAttributeInstanceRateDivisorFeatureNotEnabled,
+
+    /// The `vertex_attribute_instance_rate_zero_divisor` feature must be enabled in order to use
+    /// a per-instance vertex binding divisor of 0.
+    VertexAttributeInstanceRateZeroDivisorFeatureNotEnabled,
+
     /// The maximum number of vertex sources has been exceeded.
     MaxVertexInputBindingsExceeded {
         /// Maximum allowed value.
@@ -120,9 +153,43 @@ pub enum GraphicsPipelineCreationError {
     /// The `wide_lines` feature must be enabled in order to use a line width greater than 1.0.
     WideLinesFeatureNotEnabled,
 
+    /// The line width is outside of the `line_width_range` limit of the device.
+    LineWidthOutOfRange,
+
+    /// The `ext_line_rasterization` extension must be enabled in order to use a line
+    /// rasterization mode other than `Default` or stippled lines.
+    LineRasterizationExtensionNotEnabled,
+
+    /// The feature that corresponds to the line rasterization mode must be enabled in order to
+    /// use it.
+    LineRasterizationModeFeatureNotEnabled,
+
+    /// The stippled lines feature that corresponds to the line rasterization mode must be
+    /// enabled in order to use stippled lines.
+    LineStippleFeatureNotEnabled,
+
+    /// The line stipple factor is not between 1 and 256.
+    InvalidLineStippleFactor,
+
+    /// The `ext_provoking_vertex` extension must be enabled in order to use a provoking vertex
+    /// mode other than `FirstVertex`.
+    ProvokingVertexExtensionNotEnabled,
+
+    /// The `provoking_vertex_last` feature must be enabled in order to use the last vertex as
+    /// provoking vertex.
+    ProvokingVertexLastFeatureNotEnabled,
+
     /// The `depth_clamp` feature must be enabled in order to use depth clamping.
     DepthClampFeatureNotEnabled,
 
+    /// The `ext_depth_clip_enable` extension must be enabled in order to control depth clipping
+    /// independently of depth clamping.
+    DepthClipEnableExtensionNotEnabled,
+
+    /// The `depth_clip_enable` feature must be enabled in order to control depth clipping
+    /// independently of depth clamping.
+    DepthClipEnableFeatureNotEnabled,
+
     /// The `depth_bias_clamp` feature must be enabled in order to use a depth bias clamp different
     /// from 0.0.
     DepthBiasClampFeatureNotEnabled,
@@ -134,6 +201,96 @@ pub enum GraphicsPipelineCreationError {
     /// The `depth_bounds` feature must be enabled in order to use depth bounds testing.
     DepthBoundsFeatureNotEnabled,
 
+    /// The `triangle_fans` feature must be enabled in order to use the `TriangleFan` topology on
+    /// a device that enables `khr_portability_subset`.
+    TriangleFansFeatureNotEnabled,
+
+    /// The minimum or maximum depth bounds are not between 0.0 and 1.0.
+    DepthBoundsOutOfRange,
+
+    /// The `ext_conservative_rasterization` extension must be enabled in order to use
+    /// conservative rasterization.
+    ConservativeRasterizationExtensionNotEnabled,
+
+    /// The extra primitive overestimation size is negative or greater than the
+    /// `max_extra_primitive_overestimation_size` of the device.
+    MaxExtraPrimitiveOverestimationSizeExceeded,
+
+    /// The device doesn't support underestimation conservative rasterization.
+    PrimitiveUnderestimationNotSupported,
+
+    /// The `ext_graphics_pipeline_library` extension must be enabled in order to use pipeline
+    /// libraries.
+    GraphicsPipelineLibraryExtensionNotEnabled,
+
+    /// The `graphics_pipeline_library` feature must be enabled in order to use pipeline
+    /// libraries.
+    GraphicsPipelineLibraryFeatureNotEnabled,
+
+    /// One of the pipelines to link is not a pipeline library.
+    NotAPipelineLibrary,
+
+    /// Two of the pipeline libraries to link, or a library to link and the library being built,
+    /// contain the same parts.
+    OverlappingLibraryParts,
+
+    /// The `khr_fragment_shading_rate` extension must be enabled in order to set a fragment
+    /// shading rate.
+    FragmentShadingRateExtensionNotEnabled,
+
+    /// The `pipeline_fragment_shading_rate` feature must be enabled in order to set a fixed
+    /// fragment shading rate.
+    PipelineFragmentShadingRateFeatureNotEnabled,
+
+    /// The `primitive_fragment_shading_rate` feature must be enabled in order to combine the
+    /// primitive shading rate.
+    PrimitiveFragmentShadingRateFeatureNotEnabled,
+
+    /// The `attachment_fragment_shading_rate` feature must be enabled in order to combine the
+    /// attachment shading rate.
+    AttachmentFragmentShadingRateFeatureNotEnabled,
+
+    /// The width and height of the fragment shading rate must be 1, 2 or 4.
+    InvalidFragmentShadingRateSize,
+
+    /// The device doesn't support combiner operations other than `Keep` and `Replace`.
+    NonTrivialFragmentShadingRateCombinerOpsNotSupported,
+
+    /// An extension must be enabled in order to make one of the requested states dynamic.
+    ExtendedDynamicStateExtensionNotEnabled {
+        /// Name of the missing extension.
+        name: &'static str,
+    },
+
+    /// A feature must be enabled in order to make one of the requested states dynamic.
+    ExtendedDynamicStateFeatureNotEnabled {
+        /// Name of the missing feature.
+        name: &'static str,
+    },
+
+    /// The `ext_pipeline_robustness` extension must be enabled in order to set the robustness of
+    /// the pipeline.
+    PipelineRobustnessExtensionNotEnabled,
+
+    /// The `pipeline_robustness` feature must be enabled in order to set the robustness of the
+    /// pipeline.
+    PipelineRobustnessFeatureNotEnabled,
+
+    /// One of the requested robustness behaviors requires a feature that the physical device
+    /// doesn't support.
+    PipelineRobustnessFeatureNotSupported {
+        /// Name of the unsupported feature.
+        name: &'static str,
+    },
+
+    /// The `khr_pipeline_executable_properties` extension must be enabled in order to capture
+    /// statistics or internal representations.
+    PipelineExecutablePropertiesExtensionNotEnabled,
+
+    /// The `pipeline_executable_info` feature must be enabled in order to capture statistics or
+    /// internal representations.
+    PipelineExecutableInfoFeatureNotEnabled,
+
     /// The requested stencil test is invalid.
     WrongStencilState,
 
@@ -157,6 +314,39 @@ pub enum GraphicsPipelineCreationError {
     /// The `logic_op` feature must be enabled in order to use logic operations.
     LogicOpFeatureNotEnabled,
 
+    /// The `ext_blend_operation_advanced` extension must be enabled in order to use advanced
+    /// blend operations.
+    AdvancedBlendExtensionNotEnabled,
+
+    /// An attachment uses an advanced blend operation, but its color and alpha operations are
+    /// not the same.
+    AdvancedBlendOpMismatch,
+
+    /// The maximum number of color attachments that can use advanced blend operations has been
+    /// exceeded.
+    AdvancedBlendMaxColorAttachmentsExceeded {
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
+    /// The `advanced_blend_independent_blend` property of the device is false, but attachments
+    /// use different advanced blend operations.
+    AdvancedBlendIndependentBlendNotSupported,
+
+    /// The `advanced_blend_all_operations` property of the device is false, but an attachment
+    /// uses an advanced blend operation that requires it.
+    AdvancedBlendOperationNotSupported,
+
+    /// The device doesn't support treating the source or destination color as non-premultiplied
+    /// in advanced blend operations.
+    AdvancedBlendNonPremultipliedNotSupported,
+
+    /// The `advanced_blend_correlated_overlap` property of the device is false, but an overlap
+    /// mode other than `Uncorrelated` was requested.
+    AdvancedBlendCorrelatedOverlapNotSupported,
+
     /// The depth test requires a depth attachment but render pass has no depth attachment, or
     /// depth writing is enabled and the depth attachment is read-only.
     NoDepthAttachment,
@@ -180,8 +370,61 @@ pub enum GraphicsPipelineCreationError {
     /// The `sample_rate_shading` feature must be enabled in order to use sample shading.
     SampleRateShadingFeatureNotEnabled,
 
+    /// The `ext_sample_locations` extension must be enabled in order to use custom sample
+    /// locations.
+    SampleLocationsExtensionNotEnabled,
+
+    /// The number of samples per pixel of the sample locations is not the number of samples of
+    /// the subpass.
+    SampleLocationsSampleCountMismatch,
+
+    /// The device doesn't support custom sample locations for the number of samples of the
+    /// subpass.
+    SampleLocationsSampleCountNotSupported,
+
+    /// The number of sample locations doesn't match the number of samples per pixel and the grid
+    /// size.
+    SampleLocationsCountMismatch {
+        /// Expected number of locations.
+        expected: u32,
+        /// Number of locations that were passed.
+        obtained: u32,
+    },
+
+    /// The grid size of the sample locations doesn't evenly divide the maximum grid size of the
+    /// device for this number of samples.
+    InvalidSampleLocationsGridSize,
+
     /// The `alpha_to_one` feature must be enabled in order to use alpha-to-one.
     AlphaToOneFeatureNotEnabled,
+
+    /// The specialization constants of a shader stage don't match the declarations of its
+    /// shader module.
+    IncompatibleSpecializationConstants {
+        /// The shader stage whose specialization constants are incompatible.
+        stage: ShaderStage,
+        /// The incompatibility.
+        error: SpecializationConstantsError,
+    },
+    /// One of the shader modules was built from an identifier, but the
+    /// `pipeline_creation_cache_control` feature is not enabled.
+    PipelineCreationCacheControlFeatureNotEnabled,
+    /// One of the shader modules was built from an identifier, and the pipeline is not in the
+    /// pipeline cache. The pipeline must be created from the SPIR-V code instead.
+    PipelineCompileRequired,
+    /// The shader module of a stage requires features that are not enabled on the device.
+    ShaderFeaturesNotEnabled {
+        /// The shader stage whose module requires the features.
+        stage: ShaderStage,
+        /// The features that are required but not enabled.
+        missing: Box<Features>,
+    },
+    /// The `multiview_geometry_shader` feature must be enabled in order to use geometry shaders
+    /// in a subpass that uses multiview.
+    MultiviewGeometryShaderFeatureNotEnabled,
+    /// The `multiview_tessellation_shader` feature must be enabled in order to use tessellation
+    /// shaders in a subpass that uses multiview.
+    MultiviewTessellationShaderFeatureNotEnabled,
 }
 
 impl error::Error for GraphicsPipelineCreationError {
@@ -190,6 +433,8 @@ impl error::Error for GraphicsPipelineCreationError {
         match *self {
             GraphicsPipelineCreationError::OomError(ref err) => Some(err),
             GraphicsPipelineCreationError::IncompatiblePipelineLayout(ref err) => Some(err),
+            GraphicsPipelineCreationError::IncompatibleShaderLayouts(ref err) => Some(err),
+            GraphicsPipelineCreationError::PipelineLayoutCreationError(ref err) => Some(err),
             GraphicsPipelineCreationError::VertexGeometryStagesMismatch(ref err) => Some(err),
             GraphicsPipelineCreationError::VertexTessControlStagesMismatch(ref err) => Some(err),
             GraphicsPipelineCreationError::VertexFragmentStagesMismatch(ref err) => Some(err),
@@ -198,6 +443,10 @@ impl error::Error for GraphicsPipelineCreationError {
             GraphicsPipelineCreationError::TessEvalFragmentStagesMismatch(ref err) => Some(err),
             GraphicsPipelineCreationError::GeometryFragmentStagesMismatch(ref err) => Some(err),
             GraphicsPipelineCreationError::IncompatibleVertexDefinition(ref err) => Some(err),
+            GraphicsPipelineCreationError::IncompatibleSpecializationConstants {
+                ref error,
+                ..
+            } => Some(error),
             _ => None,
         }
     }
@@ -240,6 +489,12 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 GraphicsPipelineCreationError::IncompatiblePipelineLayout(_) => {
                     "the pipeline layout is not compatible with what the shaders expect"
                 }
+                GraphicsPipelineCreationError::IncompatibleShaderLayouts(_) => {
+                    "the layouts of the shader stages can't be merged into a single pipeline layout"
+                }
+                GraphicsPipelineCreationError::PipelineLayoutCreationError(_) => {
+                    "error while creating the pipeline layout from the shader stages"
+                }
                 GraphicsPipelineCreationError::FragmentShaderRenderPassIncompatible => {
                     "the output of the fragment shader is not compatible with what the render pass \
                  subpass expects"
@@ -251,6 +506,21 @@ impl fmt::Display for GraphicsPipelineCreationError {
                     "the maximum stride value for vertex input (ie. the distance between two vertex \
                  elements) has been exceeded"
                 }
+                GraphicsPipelineCreationError::MaxVertexAttribDivisorExceeded { .. } => {
+                    "the maximum divisor of a per-instance vertex binding has been exceeded"
+                }
+                GraphicsPipelineCreationError::VertexAttributeDivisorExtensionNotEnabled => {
+                    "the `ext_vertex_attribute_divisor` extension must be enabled in order to use a \
+                 per-instance vertex binding divisor different from 1"
+                }
+                GraphicsPipelineCreationError::VertexAttributeInstanceRateDivisorFeatureNotEnabled => {
+                    "the `vertex_attribute_instance_rate_divisor` feature must be enabled in order \
+                 to use a per-instance vertex binding divisor different from 1"
+                }
+                GraphicsPipelineCreationError::VertexAttributeInstanceRateZeroDivisorFeatureNotEnabled => {
+                    "the `vertex_attribute_instance_rate_zero_divisor` feature must be enabled in \
+                 order to use a per-instance vertex binding divisor of 0"
+                }
                 GraphicsPipelineCreationError::MaxVertexInputBindingsExceeded { .. } => {
                     "the maximum number of vertex sources has been exceeded"
                 }
@@ -283,9 +553,42 @@ impl fmt::Display for GraphicsPipelineCreationError {
                     "the `wide_lines` feature must be enabled in order to use a line width \
                  greater than 1.0"
                 }
+                GraphicsPipelineCreationError::LineWidthOutOfRange => {
+                    "the line width is outside of the `line_width_range` limit of the device"
+                }
+                GraphicsPipelineCreationError::LineRasterizationExtensionNotEnabled => {
+                    "the `ext_line_rasterization` extension must be enabled in order to use a line \
+                 rasterization mode other than `Default` or stippled lines"
+                }
+                GraphicsPipelineCreationError::LineRasterizationModeFeatureNotEnabled => {
+                    "the feature that corresponds to the line rasterization mode must be enabled"
+                }
+                GraphicsPipelineCreationError::LineStippleFeatureNotEnabled => {
+                    "the stippled lines feature that corresponds to the line rasterization mode \
+                 must be enabled in order to use stippled lines"
+                }
+                GraphicsPipelineCreationError::InvalidLineStippleFactor => {
+                    "the line stipple factor is not between 1 and 256"
+                }
+                GraphicsPipelineCreationError::ProvokingVertexExtensionNotEnabled => {
+                    "the `ext_provoking_vertex` extension must be enabled in order to use a \
+                 provoking vertex mode other than `FirstVertex`"
+                }
+                GraphicsPipelineCreationError::ProvokingVertexLastFeatureNotEnabled => {
+                    "the `provoking_vertex_last` feature must be enabled in order to use the last \
+                 vertex as provoking vertex"
+                }
                 GraphicsPipelineCreationError::DepthClampFeatureNotEnabled => {
                     "the `depth_clamp` feature must be enabled in order to use depth clamping"
                 }
+                GraphicsPipelineCreationError::DepthClipEnableExtensionNotEnabled => {
+                    "the `ext_depth_clip_enable` extension must be enabled in order to control \
+                 depth clipping independently of depth clamping"
+                }
+                GraphicsPipelineCreationError::DepthClipEnableFeatureNotEnabled => {
+                    "the `depth_clip_enable` feature must be enabled in order to control depth \
+                 clipping independently of depth clamping"
+                }
                 GraphicsPipelineCreationError::DepthBiasClampFeatureNotEnabled => {
                     "the `depth_bias_clamp` feature must be enabled in order to use a depth bias \
                  clamp different from 0.0."
@@ -297,6 +600,88 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 GraphicsPipelineCreationError::DepthBoundsFeatureNotEnabled => {
                     "the `depth_bounds` feature must be enabled in order to use depth bounds testing"
                 }
+                GraphicsPipelineCreationError::TriangleFansFeatureNotEnabled => {
+                    "the `triangle_fans` feature must be enabled in order to use the `TriangleFan` \
+                     topology on a portability subset device"
+                }
+                GraphicsPipelineCreationError::DepthBoundsOutOfRange => {
+                    "the minimum or maximum depth bounds are not between 0.0 and 1.0"
+                }
+                GraphicsPipelineCreationError::ConservativeRasterizationExtensionNotEnabled => {
+                    "the `ext_conservative_rasterization` extension must be enabled in order to use \
+                 conservative rasterization"
+                }
+                GraphicsPipelineCreationError::MaxExtraPrimitiveOverestimationSizeExceeded => {
+                    "the extra primitive overestimation size is negative or greater than the \
+                 `max_extra_primitive_overestimation_size` of the device"
+                }
+                GraphicsPipelineCreationError::PrimitiveUnderestimationNotSupported => {
+                    "the device doesn't support underestimation conservative rasterization"
+                }
+                GraphicsPipelineCreationError::GraphicsPipelineLibraryExtensionNotEnabled => {
+                    "the `ext_graphics_pipeline_library` extension must be enabled in order to use \
+                 pipeline libraries"
+                }
+                GraphicsPipelineCreationError::GraphicsPipelineLibraryFeatureNotEnabled => {
+                    "the `graphics_pipeline_library` feature must be enabled in order to use \
+                 pipeline libraries"
+                }
+                GraphicsPipelineCreationError::NotAPipelineLibrary => {
+                    "one of the pipelines to link is not a pipeline library"
+                }
+                GraphicsPipelineCreationError::OverlappingLibraryParts => {
+                    "two of the pipeline libraries contain the same parts"
+                }
+                GraphicsPipelineCreationError::FragmentShadingRateExtensionNotEnabled => {
+                    "the `khr_fragment_shading_rate` extension must be enabled in order to set a \
+                 fragment shading rate"
+                }
+                GraphicsPipelineCreationError::PipelineFragmentShadingRateFeatureNotEnabled => {
+                    "the `pipeline_fragment_shading_rate` feature must be enabled in order to set \
+                 a fixed fragment shading rate"
+                }
+                GraphicsPipelineCreationError::PrimitiveFragmentShadingRateFeatureNotEnabled => {
+                    "the `primitive_fragment_shading_rate` feature must be enabled in order to \
+                 combine the primitive shading rate"
+                }
+                GraphicsPipelineCreationError::AttachmentFragmentShadingRateFeatureNotEnabled => {
+                    "the `attachment_fragment_shading_rate` feature must be enabled in order to \
+                 combine the attachment shading rate"
+                }
+                GraphicsPipelineCreationError::InvalidFragmentShadingRateSize => {
+                    "the width and height of the fragment shading rate must be 1, 2 or 4"
+                }
+                GraphicsPipelineCreationError::NonTrivialFragmentShadingRateCombinerOpsNotSupported => {
+                    "the device doesn't support combiner operations other than `Keep` and \
+                 `Replace`"
+                }
+                GraphicsPipelineCreationError::ExtendedDynamicStateExtensionNotEnabled { .. } => {
+                    "an extension must be enabled in order to make one of the requested states \
+                 dynamic"
+                }
+                GraphicsPipelineCreationError::ExtendedDynamicStateFeatureNotEnabled { .. } => {
+                    "a feature must be enabled in order to make one of the requested states dynamic"
+                }
+                GraphicsPipelineCreationError::PipelineRobustnessExtensionNotEnabled => {
+                    "the `ext_pipeline_robustness` extension must be enabled in order to set the \
+                 robustness of the pipeline"
+                }
+                GraphicsPipelineCreationError::PipelineRobustnessFeatureNotEnabled => {
+                    "the `pipeline_robustness` feature must be enabled in order to set the \
+                 robustness of the pipeline"
+                }
+                GraphicsPipelineCreationError::PipelineRobustnessFeatureNotSupported { .. } => {
+                    "one of the requested robustness behaviors requires a feature that the \
+                 physical device doesn't support"
+                }
+                GraphicsPipelineCreationError::PipelineExecutablePropertiesExtensionNotEnabled => {
+                    "the `khr_pipeline_executable_properties` extension must be enabled in order to \
+                 capture statistics or internal representations"
+                }
+                GraphicsPipelineCreationError::PipelineExecutableInfoFeatureNotEnabled => {
+                    "the `pipeline_executable_info` feature must be enabled in order to capture \
+                 statistics or internal representations"
+                }
                 GraphicsPipelineCreationError::WrongStencilState => {
                     "the requested stencil test is invalid"
                 }
@@ -321,6 +706,35 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 GraphicsPipelineCreationError::LogicOpFeatureNotEnabled => {
                     "the `logic_op` feature must be enabled in order to use logic operations"
                 }
+                GraphicsPipelineCreationError::AdvancedBlendExtensionNotEnabled => {
+                    "the `ext_blend_operation_advanced` extension must be enabled in order to use \
+                 advanced blend operations"
+                }
+                GraphicsPipelineCreationError::AdvancedBlendOpMismatch => {
+                    "an attachment uses an advanced blend operation, but its color and alpha \
+                 operations are not the same"
+                }
+                GraphicsPipelineCreationError::AdvancedBlendMaxColorAttachmentsExceeded {
+                    ..
+                } => {
+                    "the maximum number of color attachments that can use advanced blend \
+                 operations has been exceeded"
+                }
+                GraphicsPipelineCreationError::AdvancedBlendIndependentBlendNotSupported => {
+                    "the device doesn't support using different advanced blend operations per \
+                 attachment"
+                }
+                GraphicsPipelineCreationError::AdvancedBlendOperationNotSupported => {
+                    "the device doesn't support all the advanced blend operations"
+                }
+                GraphicsPipelineCreationError::AdvancedBlendNonPremultipliedNotSupported => {
+                    "the device doesn't support treating colors as non-premultiplied in advanced \
+                 blend operations"
+                }
+                GraphicsPipelineCreationError::AdvancedBlendCorrelatedOverlapNotSupported => {
+                    "the device doesn't support overlap modes other than `Uncorrelated` in \
+                 advanced blend operations"
+                }
                 GraphicsPipelineCreationError::NoDepthAttachment => {
                     "the depth attachment of the render pass does not match the depth test"
                 }
@@ -340,9 +754,52 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 GraphicsPipelineCreationError::SampleRateShadingFeatureNotEnabled => {
                     "the `sample_rate_shading` feature must be enabled in order to use sample shading"
                 }
+                GraphicsPipelineCreationError::SampleLocationsExtensionNotEnabled => {
+                    "the `ext_sample_locations` extension must be enabled in order to use custom \
+                 sample locations"
+                }
+                GraphicsPipelineCreationError::SampleLocationsSampleCountMismatch => {
+                    "the number of samples per pixel of the sample locations is not the number of \
+                 samples of the subpass"
+                }
+                GraphicsPipelineCreationError::SampleLocationsSampleCountNotSupported => {
+                    "the device doesn't support custom sample locations for the number of samples \
+                 of the subpass"
+                }
+                GraphicsPipelineCreationError::SampleLocationsCountMismatch { .. } => {
+                    "the number of sample locations doesn't match the number of samples per pixel \
+                 and the grid size"
+                }
+                GraphicsPipelineCreationError::InvalidSampleLocationsGridSize => {
+                    "the grid size of the sample locations doesn't evenly divide the maximum grid \
+                 size of the device"
+                }
                 GraphicsPipelineCreationError::AlphaToOneFeatureNotEnabled => {
                     "the `alpha_to_one` feature must be enabled in order to use alpha-to-one"
                 }
+                GraphicsPipelineCreationError::IncompatibleSpecializationConstants { .. } => {
+                    "the specialization constants of a shader stage don't match the declarations \
+                 of its shader module"
+                }
+                GraphicsPipelineCreationError::PipelineCreationCacheControlFeatureNotEnabled => {
+                    "the `pipeline_creation_cache_control` feature must be enabled to use shader \
+                 module identifiers"
+                }
+                GraphicsPipelineCreationError::PipelineCompileRequired => {
+                    "the pipeline is not in the pipeline cache and must be compiled"
+                }
+                GraphicsPipelineCreationError::ShaderFeaturesNotEnabled { .. } => {
+                    "the shader module of a stage requires features that are not enabled on the \
+                 device"
+                }
+                GraphicsPipelineCreationError::MultiviewGeometryShaderFeatureNotEnabled => {
+                    "the `multiview_geometry_shader` feature must be enabled in order to use \
+                 geometry shaders in a subpass that uses multiview"
+                }
+                GraphicsPipelineCreationError::MultiviewTessellationShaderFeatureNotEnabled => {
+                    "the `multiview_tessellation_shader` feature must be enabled in order to use \
+                 tessellation shaders in a subpass that uses multiview"
+                }
             }
         )
     }
@@ -362,6 +819,20 @@ impl From<PipelineLayoutNotSupersetError> for GraphicsPipelineCreationError {
     }
 }
 
+impl From<PipelineLayoutDescUnionError> for GraphicsPipelineCreationError {
+    #[inline]
+    fn from(err: PipelineLayoutDescUnionError) -> GraphicsPipelineCreationError {
+        GraphicsPipelineCreationError::IncompatibleShaderLayouts(err)
+    }
+}
+
+impl From<PipelineLayoutCreationError> for GraphicsPipelineCreationError {
+    #[inline]
+    fn from(err: PipelineLayoutCreationError) -> GraphicsPipelineCreationError {
+        GraphicsPipelineCreationError::PipelineLayoutCreationError(err)
+    }
+}
+
 impl From<IncompatibleVertexDefinitionError> for GraphicsPipelineCreationError {
     #[inline]
     fn from(err: IncompatibleVertexDefinitionError) -> GraphicsPipelineCreationError {
@@ -0,0 +1,243 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+
+use crate::device::Device;
+use crate::pipeline::graphics_pipeline::GraphicsPipelineCreationError;
+use crate::vk;
+
+/// States of a graphics pipeline that can be made dynamic with the `VK_EXT_extended_dynamic_state`,
+/// `VK_EXT_extended_dynamic_state2` and `VK_EXT_extended_dynamic_state3` extensions, as well as
+/// with `VK_EXT_color_write_enable`.
+///
+/// The value of a dynamic state is ignored when building the pipeline, and must instead be set
+/// in the `DynamicState` passed to each draw command. This allows a single pipeline to be used
+/// where otherwise one pipeline would be needed for each combination of values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ExtendedDynamicState {
+    /// The cull mode. Requires the `extended_dynamic_state` feature.
+    pub cull_mode: bool,
+    /// The front face. Requires the `extended_dynamic_state` feature.
+    pub front_face: bool,
+    /// The primitive topology. Requires the `extended_dynamic_state` feature.
+    ///
+    /// The topology set before drawing must be of the same class (points, lines, triangles or
+    /// patches) as the topology of the pipeline.
+    pub primitive_topology: bool,
+    /// Whether the depth test is enabled. Requires the `extended_dynamic_state` feature.
+    pub depth_test_enable: bool,
+    /// Whether depth writes are enabled. Requires the `extended_dynamic_state` feature.
+    pub depth_write_enable: bool,
+    /// The depth compare operation. Requires the `extended_dynamic_state` feature.
+    pub depth_compare_op: bool,
+    /// Whether primitive restart is enabled. Requires the `extended_dynamic_state2` feature.
+    pub primitive_restart_enable: bool,
+    /// Whether rasterizer discard is enabled. Requires the `extended_dynamic_state2` feature.
+    pub rasterizer_discard_enable: bool,
+    /// Whether depth bias is enabled. Requires the `extended_dynamic_state2` feature.
+    pub depth_bias_enable: bool,
+    /// The logic operation. Requires the `extended_dynamic_state2_logic_op` feature.
+    pub logic_op: bool,
+    /// Whether blending is enabled for each color attachment. Requires the
+    /// `extended_dynamic_state3_color_blend_enable` feature.
+    pub color_blend_enable: bool,
+    /// The blend equation of each color attachment. Requires the
+    /// `extended_dynamic_state3_color_blend_equation` feature.
+    pub color_blend_equation: bool,
+    /// The color write mask of each color attachment. Requires the
+    /// `extended_dynamic_state3_color_write_mask` feature.
+    pub color_write_mask: bool,
+    /// Whether writes to each color attachment are enabled. Requires the `color_write_enable`
+    /// feature of the `ext_color_write_enable` extension.
+    ///
+    /// Unlike the color write mask, this allows masking off attachments without enabling any
+    /// other state of the extended dynamic state extensions.
+    pub color_write_enable: bool,
+}
+
+impl ExtendedDynamicState {
+    /// Builds an `ExtendedDynamicState` with none of the states set as dynamic.
+    #[inline]
+    pub fn none() -> ExtendedDynamicState {
+        ExtendedDynamicState::default()
+    }
+
+    /// Returns true if none of the states are set as dynamic.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == ExtendedDynamicState::none()
+    }
+
+    // Checks that the extensions and features needed for the dynamic states are enabled, and
+    // appends the corresponding values to `dynamic_states`.
+    pub(crate) fn push_vulkan_states(
+        &self,
+        device: &Device,
+        dynamic_states: &mut SmallVec<[vk::DynamicState; 8]>,
+    ) -> Result<(), GraphicsPipelineCreationError> {
+        let extensions = device.loaded_extensions();
+        let features = device.enabled_features();
+
+        let states = [
+            (
+                self.cull_mode,
+                vk::DYNAMIC_STATE_CULL_MODE_EXT,
+                extensions.ext_extended_dynamic_state,
+                "ext_extended_dynamic_state",
+                features.extended_dynamic_state,
+                "extended_dynamic_state",
+            ),
+            (
+                self.front_face,
+                vk::DYNAMIC_STATE_FRONT_FACE_EXT,
+                extensions.ext_extended_dynamic_state,
+                "ext_extended_dynamic_state",
+                features.extended_dynamic_state,
+                "extended_dynamic_state",
+            ),
+            (
+                self.primitive_topology,
+                vk::DYNAMIC_STATE_PRIMITIVE_TOPOLOGY_EXT,
+                extensions.ext_extended_dynamic_state,
+                "ext_extended_dynamic_state",
+                features.extended_dynamic_state,
+                "extended_dynamic_state",
+            ),
+            (
+                self.depth_test_enable,
+                vk::DYNAMIC_STATE_DEPTH_TEST_ENABLE_EXT,
+                extensions.ext_extended_dynamic_state,
+                "ext_extended_dynamic_state",
+                features.extended_dynamic_state,
+                "extended_dynamic_state",
+            ),
+            (
+                self.depth_write_enable,
+                vk::DYNAMIC_STATE_DEPTH_WRITE_ENABLE_EXT,
+                extensions.ext_extended_dynamic_state,
+                "ext_extended_dynamic_state",
+                features.extended_dynamic_state,
+                "extended_dynamic_state",
+            ),
+            (
+                self.depth_compare_op,
+                vk::DYNAMIC_STATE_DEPTH_COMPARE_OP_EXT,
+                extensions.ext_extended_dynamic_state,
+                "ext_extended_dynamic_state",
+                features.extended_dynamic_state,
+                "extended_dynamic_state",
+            ),
+            (
+                self.primitive_restart_enable,
+                vk::DYNAMIC_STATE_PRIMITIVE_RESTART_ENABLE_EXT,
+                extensions.ext_extended_dynamic_state2,
+                "ext_extended_dynamic_state2",
+                features.extended_dynamic_state2,
+                "extended_dynamic_state2",
+            ),
+            (
+                self.rasterizer_discard_enable,
+                vk::DYNAMIC_STATE_RASTERIZER_DISCARD_ENABLE_EXT,
+                extensions.ext_extended_dynamic_state2,
+                "ext_extended_dynamic_state2",
+                features.extended_dynamic_state2,
+                "extended_dynamic_state2",
+            ),
+            (
+                self.depth_bias_enable,
+                vk::DYNAMIC_STATE_DEPTH_BIAS_ENABLE_EXT,
+                extensions.ext_extended_dynamic_state2,
+                "ext_extended_dynamic_state2",
+                features.extended_dynamic_state2,
+                "extended_dynamic_state2",
+            ),
+            (
+                self.logic_op,
+                vk::DYNAMIC_STATE_LOGIC_OP_EXT,
+                extensions.ext_extended_dynamic_state2,
+                "ext_extended_dynamic_state2",
+                features.extended_dynamic_state2_logic_op,
+                "extended_dynamic_state2_logic_op",
+            ),
+            (
+                self.color_blend_enable,
+                vk::DYNAMIC_STATE_COLOR_BLEND_ENABLE_EXT,
+                extensions.ext_extended_dynamic_state3,
+                "ext_extended_dynamic_state3",
+                features.extended_dynamic_state3_color_blend_enable,
+                "extended_dynamic_state3_color_blend_enable",
+            ),
+            (
+                self.color_blend_equation,
+                vk::DYNAMIC_STATE_COLOR_BLEND_EQUATION_EXT,
+                extensions.ext_extended_dynamic_state3,
+                "ext_extended_dynamic_state3",
+                features.extended_dynamic_state3_color_blend_equation,
+                "extended_dynamic_state3_color_blend_equation",
+            ),
+            (
+                self.color_write_mask,
+                vk::DYNAMIC_STATE_COLOR_WRITE_MASK_EXT,
+                extensions.ext_extended_dynamic_state3,
+                "ext_extended_dynamic_state3",
+                features.extended_dynamic_state3_color_write_mask,
+                "extended_dynamic_state3_color_write_mask",
+            ),
+            (
+                self.color_write_enable,
+                vk::DYNAMIC_STATE_COLOR_WRITE_ENABLE_EXT,
+                extensions.ext_color_write_enable,
+                "ext_color_write_enable",
+                features.color_write_enable,
+                "color_write_enable",
+            ),
+        ];
+
+        for &(dynamic, state, extension_enabled, extension, feature_enabled, feature) in
+            states.iter()
+        {
+            if !dynamic {
+                continue;
+            }
+            if !extension_enabled {
+                return Err(
+                    GraphicsPipelineCreationError::ExtendedDynamicStateExtensionNotEnabled {
+                        name: extension,
+                    },
+                );
+            }
+            if !feature_enabled {
+                return Err(
+                    GraphicsPipelineCreationError::ExtendedDynamicStateFeatureNotEnabled {
+                        name: feature,
+                    },
+                );
+            }
+            dynamic_states.push(state);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::ExtendedDynamicState;
+
+    #[test]
+    fn none_is_empty() {
+        assert!(ExtendedDynamicState::none().is_empty());
+        assert!(!ExtendedDynamicState {
+            cull_mode: true,
+            ..ExtendedDynamicState::none()
+        }
+        .is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::ops::BitOr;
+
+use crate::vk;
+
+/// Parts of a graphics pipeline that can be compiled separately as a pipeline library, with the
+/// `VK_EXT_graphics_pipeline_library` extension.
+///
+/// A pipeline library only contains the state of the parts it was created with. Libraries whose
+/// parts don't overlap can then be linked together into a complete pipeline, which is much
+/// cheaper than compiling the complete pipeline from scratch.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct GraphicsPipelineLibraryParts {
+    /// The vertex input and input assembly states.
+    pub vertex_input_interface: bool,
+    /// The vertex, tessellation and geometry shaders, along with the viewport and rasterization
+    /// states.
+    pub pre_rasterization_shaders: bool,
+    /// The fragment shader, along with the depth-stencil state.
+    pub fragment_shader: bool,
+    /// The multisample and color blending states.
+    pub fragment_output_interface: bool,
+}
+
+impl GraphicsPipelineLibraryParts {
+    /// Builds a `GraphicsPipelineLibraryParts` with none of the parts.
+    #[inline]
+    pub fn none() -> GraphicsPipelineLibraryParts {
+        GraphicsPipelineLibraryParts {
+            vertex_input_interface: false,
+            pre_rasterization_shaders: false,
+            fragment_shader: false,
+            fragment_output_interface: false,
+        }
+    }
+
+    /// Builds a `GraphicsPipelineLibraryParts` with all of the parts.
+    #[inline]
+    pub fn all() -> GraphicsPipelineLibraryParts {
+        GraphicsPipelineLibraryParts {
+            vertex_input_interface: true,
+            pre_rasterization_shaders: true,
+            fragment_shader: true,
+            fragment_output_interface: true,
+        }
+    }
+
+    /// Returns true if none of the parts are set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == GraphicsPipelineLibraryParts::none()
+    }
+
+    /// Returns true if `self` and `other` have at least one part in common.
+    #[inline]
+    pub fn intersects(&self, other: &GraphicsPipelineLibraryParts) -> bool {
+        (self.vertex_input_interface && other.vertex_input_interface)
+            || (self.pre_rasterization_shaders && other.pre_rasterization_shaders)
+            || (self.fragment_shader && other.fragment_shader)
+            || (self.fragment_output_interface && other.fragment_output_interface)
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::GraphicsPipelineLibraryFlagsEXT {
+        let mut result = 0;
+        if self.vertex_input_interface {
+            result |= vk::GRAPHICS_PIPELINE_LIBRARY_VERTEX_INPUT_INTERFACE_BIT_EXT;
+        }
+        if self.pre_rasterization_shaders {
+            result |= vk::GRAPHICS_PIPELINE_LIBRARY_PRE_RASTERIZATION_SHADERS_BIT_EXT;
+        }
+        if self.fragment_shader {
+            result |= vk::GRAPHICS_PIPELINE_LIBRARY_FRAGMENT_SHADER_BIT_EXT;
+        }
+        if self.fragment_output_interface {
+            result |= vk::GRAPHICS_PIPELINE_LIBRARY_FRAGMENT_OUTPUT_INTERFACE_BIT_EXT;
+        }
+        result
+    }
+}
+
+impl BitOr for GraphicsPipelineLibraryParts {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        GraphicsPipelineLibraryParts {
+            vertex_input_interface: self.vertex_input_interface || rhs.vertex_input_interface,
+            pre_rasterization_shaders: self.pre_rasterization_shaders
+                || rhs.pre_rasterization_shaders,
+            fragment_shader: self.fragment_shader || rhs.fragment_shader,
+            fragment_output_interface: self.fragment_output_interface
+                || rhs.fragment_output_interface,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::GraphicsPipelineLibraryParts;
+
+    #[test]
+    fn union_and_intersection() {
+        let vertex = GraphicsPipelineLibraryParts {
+            vertex_input_interface: true,
+            ..GraphicsPipelineLibraryParts::none()
+        };
+        let fragment = GraphicsPipelineLibraryParts {
+            fragment_shader: true,
+            fragment_output_interface: true,
+            ..GraphicsPipelineLibraryParts::none()
+        };
+
+        assert!(!vertex.intersects(&fragment));
+        assert!((vertex | fragment).intersects(&fragment));
+        assert!(GraphicsPipelineLibraryParts::none().is_empty());
+        assert_eq!(
+            GraphicsPipelineLibraryParts::all().into_vulkan_bits(),
+            0b1111
+        );
+    }
+}
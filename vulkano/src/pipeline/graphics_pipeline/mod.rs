@@ -7,11 +7,11 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::ffi::CStr;
 use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::marker::PhantomData;
-use std::ptr;
 use std::sync::Arc;
 use std::u32;
 
@@ -33,22 +33,32 @@ use crate::framebuffer::RenderPassDesc;
 use crate::framebuffer::RenderPassDescClearValues;
 use crate::framebuffer::RenderPassSys;
 use crate::framebuffer::Subpass;
+use crate::pipeline::executable;
+use crate::pipeline::executable::PipelineExecutableError;
+use crate::pipeline::executable::PipelineExecutableInternalRepresentation;
+use crate::pipeline::executable::PipelineExecutableProperties;
+use crate::pipeline::executable::PipelineExecutableStatistic;
+use crate::pipeline::input_assembly::PrimitiveTopology;
 use crate::pipeline::shader::EmptyEntryPointDummy;
 use crate::pipeline::vertex::BufferlessDefinition;
 use crate::pipeline::vertex::IncompatibleVertexDefinitionError;
 use crate::pipeline::vertex::VertexDefinition;
 use crate::pipeline::vertex::VertexSource;
 use crate::vk;
+use crate::OomError;
 use crate::SafeDeref;
 use crate::VulkanObject;
 
 pub use self::builder::GraphicsPipelineBuilder;
 pub use self::creation_error::GraphicsPipelineCreationError;
+pub use self::extended_dynamic_state::ExtendedDynamicState;
+pub use self::library::GraphicsPipelineLibraryParts;
 
 mod builder;
 mod creation_error;
-// FIXME: restore
-//mod tests;
+mod extended_dynamic_state;
+mod library;
+pub(crate) mod tests;
 
 /// Defines how the implementation should perform a draw operation.
 ///
@@ -72,8 +82,21 @@ pub struct GraphicsPipeline<VertexDefinition, Layout, RenderP> {
     dynamic_stencil_write_mask: bool,
     dynamic_stencil_reference: bool,
     dynamic_blend_constants: bool,
+    dynamic_fragment_shading_rate: bool,
+    dynamic_line_stipple: bool,
+    dynamic_sample_locations: bool,
+    primitive_topology: PrimitiveTopology,
+    primitive_restart_enable: bool,
+    extended_dynamic_state: ExtendedDynamicState,
 
     num_viewports: u32,
+
+    library_parts: GraphicsPipelineLibraryParts,
+    // Pipeline libraries that were linked into this pipeline, kept alive for as long as it is.
+    libraries: Vec<Arc<dyn GraphicsPipelineAbstract + Send + Sync>>,
+
+    capture_statistics: bool,
+    capture_internal_representations: bool,
 }
 
 #[derive(PartialEq, Eq, Hash)]
@@ -115,6 +138,62 @@ impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
     pub fn device(&self) -> &Arc<Device> {
         &self.inner.device
     }
+
+    /// Returns the properties of the executables that the implementation compiled for this
+    /// pipeline.
+    ///
+    /// Requires the `khr_pipeline_executable_properties` device extension and the
+    /// `pipeline_executable_info` feature.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutableError> {
+        unsafe { executable::executable_properties(&self.inner.device, self.inner.pipeline) }
+    }
+
+    /// Returns the statistics of the executable of index `executable_index`, in the order
+    /// returned by `executable_properties`.
+    ///
+    /// The pipeline must have been built with `capture_statistics` enabled.
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutableError> {
+        if !self.capture_statistics {
+            return Err(PipelineExecutableError::StatisticsNotCaptured);
+        }
+
+        unsafe {
+            executable::executable_statistics(
+                &self.inner.device,
+                self.inner.pipeline,
+                executable_index,
+            )
+        }
+    }
+
+    /// Returns the internal representations of the executable of index `executable_index`, in
+    /// the order returned by `executable_properties`.
+    ///
+    /// The pipeline must have been built with `capture_internal_representations` enabled.
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+        if !self.capture_internal_representations {
+            return Err(PipelineExecutableError::InternalRepresentationsNotCaptured);
+        }
+
+        unsafe {
+            executable::executable_internal_representations(
+                &self.inner.device,
+                self.inner.pipeline,
+                executable_index,
+            )
+        }
+    }
 }
 
 impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp>
@@ -140,6 +219,20 @@ where
 }
 
 impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
+    /// Assigns a human-readable name to the pipeline for debugging purposes.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_debug_utils` extension isn't loaded on the instance.
+    ///
+    #[inline]
+    pub fn set_debug_name(&self, name: &CStr) -> Result<(), OomError> {
+        unsafe {
+            self.device()
+                .set_object_name_raw(vk::OBJECT_TYPE_PIPELINE, self.inner.pipeline, name)
+        }
+    }
+
     /// Returns the render pass used in the constructor.
     #[inline]
     pub fn render_pass(&self) -> &Rp {
@@ -193,6 +286,51 @@ impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
     pub fn has_dynamic_stencil_reference(&self) -> bool {
         self.dynamic_stencil_reference
     }
+
+    /// Returns true if the fragment shading rate used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_fragment_shading_rate(&self) -> bool {
+        self.dynamic_fragment_shading_rate
+    }
+
+    /// Returns true if the line stipple used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_line_stipple(&self) -> bool {
+        self.dynamic_line_stipple
+    }
+
+    /// Returns true if the sample locations used by this pipeline are dynamic.
+    #[inline]
+    pub fn has_dynamic_sample_locations(&self) -> bool {
+        self.dynamic_sample_locations
+    }
+
+    /// Returns the primitive topology of the pipeline. If the topology is dynamic, the one set
+    /// before drawing must be of the same class.
+    #[inline]
+    pub fn primitive_topology(&self) -> PrimitiveTopology {
+        self.primitive_topology
+    }
+
+    /// Returns true if primitive restart is enabled when it is not dynamic.
+    #[inline]
+    pub fn primitive_restart_enable(&self) -> bool {
+        self.primitive_restart_enable
+    }
+
+    /// Returns the states of the extended dynamic state extensions that are dynamic in this
+    /// pipeline.
+    #[inline]
+    pub fn extended_dynamic_state(&self) -> ExtendedDynamicState {
+        self.extended_dynamic_state
+    }
+
+    /// If this pipeline is a pipeline library, returns the parts it contains. Returns an empty
+    /// value for regular pipelines.
+    #[inline]
+    pub fn library_parts(&self) -> GraphicsPipelineLibraryParts {
+        self.library_parts
+    }
 }
 
 unsafe impl<Mv, L, Rp> PipelineLayoutAbstract for GraphicsPipeline<Mv, L, Rp>
@@ -323,9 +461,18 @@ unsafe impl<Mv, L, Rp> VulkanObject for GraphicsPipeline<Mv, L, Rp> {
 impl Drop for Inner {
     #[inline]
     fn drop(&mut self) {
+        let instance = self.device.instance();
+        if instance.enabled_validation_features().debug_printf {
+            instance.debug_printf_registry().unregister(self.pipeline);
+        }
+
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyPipeline(self.device.internal_object(), self.pipeline, ptr::null());
+            vk.DestroyPipeline(
+                self.device.internal_object(),
+                self.pipeline,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -380,6 +527,30 @@ pub unsafe trait GraphicsPipelineAbstract:
 
     /// Returns true if the stencil references used by this pipeline are dynamic.
     fn has_dynamic_stencil_reference(&self) -> bool;
+
+    /// Returns true if the fragment shading rate used by this pipeline is dynamic.
+    fn has_dynamic_fragment_shading_rate(&self) -> bool;
+
+    /// Returns true if the line stipple used by this pipeline is dynamic.
+    fn has_dynamic_line_stipple(&self) -> bool;
+
+    /// Returns true if the sample locations used by this pipeline are dynamic.
+    fn has_dynamic_sample_locations(&self) -> bool;
+
+    /// Returns the primitive topology of the pipeline. If the topology is dynamic, the one set
+    /// before drawing must be of the same class.
+    fn primitive_topology(&self) -> PrimitiveTopology;
+
+    /// Returns true if primitive restart is enabled when it is not dynamic.
+    fn primitive_restart_enable(&self) -> bool;
+
+    /// Returns the states of the extended dynamic state extensions that are dynamic in this
+    /// pipeline.
+    fn extended_dynamic_state(&self) -> ExtendedDynamicState;
+
+    /// If this pipeline is a pipeline library, returns the parts it contains. Returns an empty
+    /// value for regular pipelines, which can be bound for drawing.
+    fn library_parts(&self) -> GraphicsPipelineLibraryParts;
 }
 
 unsafe impl<Mv, L, Rp> GraphicsPipelineAbstract for GraphicsPipeline<Mv, L, Rp>
@@ -437,6 +608,41 @@ where
     fn has_dynamic_stencil_reference(&self) -> bool {
         self.dynamic_stencil_reference
     }
+
+    #[inline]
+    fn has_dynamic_fragment_shading_rate(&self) -> bool {
+        self.dynamic_fragment_shading_rate
+    }
+
+    #[inline]
+    fn has_dynamic_line_stipple(&self) -> bool {
+        self.dynamic_line_stipple
+    }
+
+    #[inline]
+    fn has_dynamic_sample_locations(&self) -> bool {
+        self.dynamic_sample_locations
+    }
+
+    #[inline]
+    fn primitive_topology(&self) -> PrimitiveTopology {
+        self.primitive_topology
+    }
+
+    #[inline]
+    fn primitive_restart_enable(&self) -> bool {
+        self.primitive_restart_enable
+    }
+
+    #[inline]
+    fn extended_dynamic_state(&self) -> ExtendedDynamicState {
+        self.extended_dynamic_state
+    }
+
+    #[inline]
+    fn library_parts(&self) -> GraphicsPipelineLibraryParts {
+        self.library_parts
+    }
 }
 
 unsafe impl<T> GraphicsPipelineAbstract for T
@@ -493,6 +699,41 @@ where
     fn has_dynamic_stencil_reference(&self) -> bool {
         (**self).has_dynamic_stencil_reference()
     }
+
+    #[inline]
+    fn has_dynamic_fragment_shading_rate(&self) -> bool {
+        (**self).has_dynamic_fragment_shading_rate()
+    }
+
+    #[inline]
+    fn has_dynamic_line_stipple(&self) -> bool {
+        (**self).has_dynamic_line_stipple()
+    }
+
+    #[inline]
+    fn has_dynamic_sample_locations(&self) -> bool {
+        (**self).has_dynamic_sample_locations()
+    }
+
+    #[inline]
+    fn primitive_topology(&self) -> PrimitiveTopology {
+        (**self).primitive_topology()
+    }
+
+    #[inline]
+    fn primitive_restart_enable(&self) -> bool {
+        (**self).primitive_restart_enable()
+    }
+
+    #[inline]
+    fn extended_dynamic_state(&self) -> ExtendedDynamicState {
+        (**self).extended_dynamic_state()
+    }
+
+    #[inline]
+    fn library_parts(&self) -> GraphicsPipelineLibraryParts {
+        (**self).library_parts()
+    }
 }
 
 impl<Mv, L, Rp> PartialEq for GraphicsPipeline<Mv, L, Rp>
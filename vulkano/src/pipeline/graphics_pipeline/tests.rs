@@ -12,22 +12,153 @@
 #![cfg(test)]
 
 use std::ffi::CString;
-use format::Format;
-use framebuffer::Subpass;
-use descriptor::pipeline_layout::EmptyPipelineDesc;
-use descriptor::pipeline_layout::PipelineLayoutDesc;
-use pipeline::GraphicsPipeline;
-use pipeline::GraphicsPipelineParams;
-use pipeline::GraphicsPipelineCreationError;
-use pipeline::blend::Blend;
-use pipeline::depth_stencil::DepthStencil;
-use pipeline::input_assembly::InputAssembly;
-use pipeline::input_assembly::PrimitiveTopology;
-use pipeline::multisample::Multisample;
-use pipeline::shader::ShaderModule;
-use pipeline::shader::EmptyShaderInterfaceDef;
-use pipeline::vertex::SingleBufferDefinition;
-use pipeline::viewport::ViewportsState;
+use std::sync::Arc;
+
+use crate::descriptor::pipeline_layout::EmptyPipelineDesc;
+use crate::descriptor::pipeline_layout::RuntimePipelineDesc;
+use crate::device::Device;
+use crate::format::Format;
+use crate::framebuffer::AttachmentDescription;
+use crate::framebuffer::LoadOp;
+use crate::framebuffer::PassDescription;
+use crate::framebuffer::RenderPass;
+use crate::framebuffer::RenderPassDesc;
+use crate::framebuffer::RuntimeRenderPassDesc;
+use crate::framebuffer::StoreOp;
+use crate::framebuffer::Subpass;
+use crate::image::ImageLayout;
+use crate::pipeline::depth_stencil::Stencil;
+use crate::pipeline::depth_stencil::StencilOp;
+use crate::pipeline::input_assembly::PrimitiveTopology;
+use crate::pipeline::raster::ProvokingVertexMode;
+use crate::pipeline::reflect::ShaderStage;
+use crate::pipeline::shader::EmptyEntryPointDummy;
+use crate::pipeline::shader::EmptyShaderInterfaceDef;
+use crate::pipeline::shader::GraphicsEntryPoint;
+use crate::pipeline::shader::GraphicsShaderType;
+use crate::pipeline::shader::RuntimeShaderInterfaceDef;
+use crate::pipeline::shader::ShaderModule;
+use crate::pipeline::vertex::SingleBufferDefinition;
+use crate::pipeline::vertex::SingleInstanceBufferDefinition;
+use crate::pipeline::ExtendedDynamicState;
+use crate::pipeline::GraphicsPipeline;
+use crate::pipeline::GraphicsPipelineBuilder;
+use crate::pipeline::GraphicsPipelineCreationError;
+use crate::pipeline::GraphicsPipelineLibraryParts;
+
+#[derive(Default, Debug, Copy, Clone)]
+pub(crate) struct BasicVertex {
+    position: [f32; 2],
+}
+crate::impl_vertex!(BasicVertex, position);
+
+/// Returns the modules of `BASIC_VS` and `BASIC_FS`.
+pub(crate) fn basic_shaders(device: &Arc<Device>) -> (Arc<ShaderModule>, Arc<ShaderModule>) {
+    fn words(bytes: &[u8]) -> Vec<u32> {
+        bytes
+            .chunks(4)
+            .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+            .collect()
+    }
+
+    unsafe {
+        (
+            ShaderModule::from_words_with_reflection(device.clone(), &words(&BASIC_VS)).unwrap(),
+            ShaderModule::from_words_with_reflection(device.clone(), &words(&BASIC_FS)).unwrap(),
+        )
+    }
+}
+
+/// Returns the first subpass of a render pass with a single color attachment, and a
+/// depth-stencil attachment of format `depth` if provided.
+pub(crate) fn basic_subpass(
+    device: &Arc<Device>,
+    depth: Option<Format>,
+) -> Subpass<Arc<RenderPass<RuntimeRenderPassDesc>>> {
+    let attachment = |format, layout| AttachmentDescription {
+        format,
+        samples: 1,
+        load: LoadOp::Clear,
+        store: StoreOp::Store,
+        stencil_load: LoadOp::DontCare,
+        stencil_store: StoreOp::DontCare,
+        initial_layout: ImageLayout::Undefined,
+        final_layout: layout,
+    };
+
+    let mut attachments = vec![attachment(
+        Format::R8G8B8A8Unorm,
+        ImageLayout::ColorAttachmentOptimal,
+    )];
+    if let Some(depth) = depth {
+        attachments.push(attachment(
+            depth,
+            ImageLayout::DepthStencilAttachmentOptimal,
+        ));
+    }
+
+    let pass = PassDescription {
+        color_attachments: vec![(0, ImageLayout::ColorAttachmentOptimal)],
+        depth_stencil: depth.map(|_| (1, ImageLayout::DepthStencilAttachmentOptimal)),
+        input_attachments: vec![],
+        resolve_attachments: vec![],
+        preserve_attachments: vec![],
+        fragment_shading_rate_attachment: None,
+        view_mask: 0,
+        depth_stencil_resolve: None,
+        multisampled_render_to_single_sampled: None,
+    };
+
+    let render_pass = RuntimeRenderPassDesc::new(attachments, vec![pass], vec![])
+        .unwrap()
+        .build_render_pass(device.clone())
+        .unwrap();
+    Subpass::from(Arc::new(render_pass), 0).unwrap()
+}
+
+/// Starts building a graphics pipeline that uses the modules of `basic_shaders` and the given
+/// subpass.
+pub(crate) fn basic_pipeline<'a>(
+    vs: &'a ShaderModule,
+    fs: &'a ShaderModule,
+    subpass: Subpass<Arc<RenderPass<RuntimeRenderPassDesc>>>,
+) -> GraphicsPipelineBuilder<
+    SingleBufferDefinition<BasicVertex>,
+    BasicEntryPoint<'a>,
+    (),
+    EmptyEntryPointDummy,
+    (),
+    EmptyEntryPointDummy,
+    (),
+    EmptyEntryPointDummy,
+    (),
+    BasicEntryPoint<'a>,
+    (),
+    Arc<RenderPass<RuntimeRenderPassDesc>>,
+> {
+    GraphicsPipeline::start()
+        .vertex_input_single_buffer::<BasicVertex>()
+        .vertex_shader(
+            vs.reflected_graphics_entry_point("main", ShaderStage::Vertex)
+                .unwrap(),
+            (),
+        )
+        .viewports_dynamic_scissors_irrelevant(1)
+        .fragment_shader(
+            fs.reflected_graphics_entry_point("main", ShaderStage::Fragment)
+                .unwrap(),
+            (),
+        )
+        .render_pass(subpass)
+}
+
+type BasicEntryPoint<'a> = GraphicsEntryPoint<
+    'a,
+    (),
+    RuntimeShaderInterfaceDef,
+    RuntimeShaderInterfaceDef,
+    RuntimePipelineDesc,
+>;
 
 #[test]
 fn create() {
@@ -36,32 +167,56 @@ fn create() {
     let vs = unsafe { ShaderModule::new(device.clone(), &BASIC_VS).unwrap() };
     let fs = unsafe { ShaderModule::new(device.clone(), &BASIC_FS).unwrap() };
 
-    let _ = GraphicsPipeline::new(&device, GraphicsPipelineParams {
-        vertex_input: SingleBufferDefinition::<()>::new(),
-        vertex_shader: unsafe {
-            vs.vertex_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyPipelineDesc)
-        },
-        input_assembly: InputAssembly::triangle_list(),
-        tessellation: None,
-        geometry_shader: None,
-        viewport: ViewportsState::Dynamic { num: 1 },
-        raster: Default::default(),
-        multisample: Multisample::disabled(),
-        fragment_shader: unsafe {
-            fs.fragment_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyPipelineDesc)
-        },
-        depth_stencil: DepthStencil::disabled(),
-        blend: Blend::pass_through(),
-        render_pass: Subpass::from(simple_rp::CustomRenderPass::new(&device, &{
-            simple_rp::Formats { color: (Format::R8G8B8A8Unorm, 1) }
-        }).unwrap(), 0).unwrap(),
-    }).unwrap();
+    let _ = GraphicsPipeline::start()
+        .vertex_input(SingleBufferDefinition::<()>::new())
+        .vertex_shader(
+            unsafe {
+                vs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Vertex,
+                )
+            },
+            (),
+        )
+        .viewports_dynamic_scissors_irrelevant(1)
+        .fragment_shader(
+            unsafe {
+                fs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Fragment,
+                )
+            },
+            (),
+        )
+        .render_pass(
+            Subpass::from(
+                single_pass_renderpass!(device.clone(),
+                    attachments: {
+                        color: {
+                            load: Clear,
+                            store: Store,
+                            format: Format::R8G8B8A8Unorm,
+                            samples: 1,
+                        }
+                    },
+                    pass: {
+                        color: [color],
+                        depth_stencil: {}
+                    }
+                )
+                .unwrap(),
+                0,
+            )
+            .unwrap(),
+        )
+        .build(device.clone())
+        .unwrap();
 }
 
 #[test]
@@ -71,39 +226,61 @@ fn bad_primitive_restart() {
     let vs = unsafe { ShaderModule::new(device.clone(), &BASIC_VS).unwrap() };
     let fs = unsafe { ShaderModule::new(device.clone(), &BASIC_FS).unwrap() };
 
-    let result = GraphicsPipeline::new(&device, GraphicsPipelineParams {
-        vertex_input: SingleBufferDefinition::<()>::new(),
-        vertex_shader: unsafe {
-            vs.vertex_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyPipelineDesc)
-        },
-        input_assembly: InputAssembly {
-            topology: PrimitiveTopology::TriangleList,
-            primitive_restart_enable: true,
-        },
-        tessellation: None,
-        geometry_shader: None,
-        viewport: ViewportsState::Dynamic { num: 1 },
-        raster: Default::default(),
-        multisample: Multisample::disabled(),
-        fragment_shader: unsafe {
-            fs.fragment_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyPipelineDesc)
-        },
-        depth_stencil: DepthStencil::disabled(),
-        blend: Blend::pass_through(),
-        render_pass: Subpass::from(simple_rp::CustomRenderPass::new(&device, &{
-            simple_rp::Formats { color: (Format::R8G8B8A8Unorm, 1) }
-        }).unwrap(), 0).unwrap(),
-    });
+    let result = GraphicsPipeline::start()
+        .vertex_input(SingleBufferDefinition::<()>::new())
+        .vertex_shader(
+            unsafe {
+                vs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Vertex,
+                )
+            },
+            (),
+        )
+        .primitive_topology(PrimitiveTopology::TriangleList)
+        .primitive_restart(true)
+        .viewports_dynamic_scissors_irrelevant(1)
+        .fragment_shader(
+            unsafe {
+                fs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Fragment,
+                )
+            },
+            (),
+        )
+        .render_pass(
+            Subpass::from(
+                single_pass_renderpass!(device.clone(),
+                    attachments: {
+                        color: {
+                            load: Clear,
+                            store: Store,
+                            format: Format::R8G8B8A8Unorm,
+                            samples: 1,
+                        }
+                    },
+                    pass: {
+                        color: [color],
+                        depth_stencil: {}
+                    }
+                )
+                .unwrap(),
+                0,
+            )
+            .unwrap(),
+        )
+        .build(device.clone());
 
     match result {
         Err(GraphicsPipelineCreationError::PrimitiveDoesntSupportPrimitiveRestart { .. }) => (),
-        _ => panic!()
+        _ => panic!(),
     }
 }
 
@@ -114,36 +291,59 @@ fn multi_viewport_feature() {
     let vs = unsafe { ShaderModule::new(device.clone(), &BASIC_VS).unwrap() };
     let fs = unsafe { ShaderModule::new(device.clone(), &BASIC_FS).unwrap() };
 
-    let result = GraphicsPipeline::new(&device, GraphicsPipelineParams {
-        vertex_input: SingleBufferDefinition::<()>::new(),
-        vertex_shader: unsafe {
-            vs.vertex_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyPipelineDesc)
-        },
-        input_assembly: InputAssembly::triangle_list(),
-        tessellation: None,
-        geometry_shader: None,
-        viewport: ViewportsState::Dynamic { num: 2 },
-        raster: Default::default(),
-        multisample: Multisample::disabled(),
-        fragment_shader: unsafe {
-            fs.fragment_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyPipelineDesc)
-        },
-        depth_stencil: DepthStencil::disabled(),
-        blend: Blend::pass_through(),
-        render_pass: Subpass::from(simple_rp::CustomRenderPass::new(&device, &{
-            simple_rp::Formats { color: (Format::R8G8B8A8Unorm, 1) }
-        }).unwrap(), 0).unwrap(),
-    });
+    let result = GraphicsPipeline::start()
+        .vertex_input(SingleBufferDefinition::<()>::new())
+        .vertex_shader(
+            unsafe {
+                vs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Vertex,
+                )
+            },
+            (),
+        )
+        .viewports_dynamic_scissors_irrelevant(2)
+        .fragment_shader(
+            unsafe {
+                fs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Fragment,
+                )
+            },
+            (),
+        )
+        .render_pass(
+            Subpass::from(
+                single_pass_renderpass!(device.clone(),
+                    attachments: {
+                        color: {
+                            load: Clear,
+                            store: Store,
+                            format: Format::R8G8B8A8Unorm,
+                            samples: 1,
+                        }
+                    },
+                    pass: {
+                        color: [color],
+                        depth_stencil: {}
+                    }
+                )
+                .unwrap(),
+                0,
+            )
+            .unwrap(),
+        )
+        .build(device.clone());
 
     match result {
         Err(GraphicsPipelineCreationError::MultiViewportFeatureNotEnabled) => (),
-        _ => panic!()
+        _ => panic!(),
     }
 }
 
@@ -154,36 +354,59 @@ fn max_viewports() {
     let vs = unsafe { ShaderModule::new(device.clone(), &BASIC_VS).unwrap() };
     let fs = unsafe { ShaderModule::new(device.clone(), &BASIC_FS).unwrap() };
 
-    let result = GraphicsPipeline::new(&device, GraphicsPipelineParams {
-        vertex_input: SingleBufferDefinition::<()>::new(),
-        vertex_shader: unsafe {
-            vs.vertex_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyPipelineDesc)
-        },
-        input_assembly: InputAssembly::triangle_list(),
-        tessellation: None,
-        geometry_shader: None,
-        viewport: ViewportsState::Dynamic { num: !0 },
-        raster: Default::default(),
-        multisample: Multisample::disabled(),
-        fragment_shader: unsafe {
-            fs.fragment_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyPipelineDesc)
-        },
-        depth_stencil: DepthStencil::disabled(),
-        blend: Blend::pass_through(),
-        render_pass: Subpass::from(simple_rp::CustomRenderPass::new(&device, &{
-            simple_rp::Formats { color: (Format::R8G8B8A8Unorm, 1) }
-        }).unwrap(), 0).unwrap(),
-    });
+    let result = GraphicsPipeline::start()
+        .vertex_input(SingleBufferDefinition::<()>::new())
+        .vertex_shader(
+            unsafe {
+                vs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Vertex,
+                )
+            },
+            (),
+        )
+        .viewports_dynamic_scissors_irrelevant(!0)
+        .fragment_shader(
+            unsafe {
+                fs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Fragment,
+                )
+            },
+            (),
+        )
+        .render_pass(
+            Subpass::from(
+                single_pass_renderpass!(device.clone(),
+                    attachments: {
+                        color: {
+                            load: Clear,
+                            store: Store,
+                            format: Format::R8G8B8A8Unorm,
+                            samples: 1,
+                        }
+                    },
+                    pass: {
+                        color: [color],
+                        depth_stencil: {}
+                    }
+                )
+                .unwrap(),
+                0,
+            )
+            .unwrap(),
+        )
+        .build(device.clone());
 
     match result {
         Err(GraphicsPipelineCreationError::MaxViewportsExceeded { .. }) => (),
-        _ => panic!()
+        _ => panic!(),
     }
 }
 
@@ -194,58 +417,282 @@ fn no_depth_attachment() {
     let vs = unsafe { ShaderModule::new(device.clone(), &BASIC_VS).unwrap() };
     let fs = unsafe { ShaderModule::new(device.clone(), &BASIC_FS).unwrap() };
 
-    let result = GraphicsPipeline::new(&device, GraphicsPipelineParams {
-        vertex_input: SingleBufferDefinition::<()>::new(),
-        vertex_shader: unsafe {
-            vs.vertex_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyShaderInterfaceDef,
-                                                        EmptyPipelineDesc)
-        },
-        input_assembly: InputAssembly::triangle_list(),
-        tessellation: None,
-        geometry_shader: None,
-        viewport: ViewportsState::Dynamic { num: 1 },
-        raster: Default::default(),
-        multisample: Multisample::disabled(),
-        fragment_shader: unsafe {
-            fs.fragment_shader_entry_point::<(), _, _, _>(&CString::new("main").unwrap(),
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyShaderInterfaceDef,
-                                                          EmptyPipelineDesc)
-        },
-        depth_stencil: DepthStencil::simple_depth_test(),
-        blend: Blend::pass_through(),
-        render_pass: Subpass::from(simple_rp::CustomRenderPass::new(&device, &{
-            simple_rp::Formats { color: (Format::R8G8B8A8Unorm, 1) }
-        }).unwrap(), 0).unwrap(),
-    });
+    let result = GraphicsPipeline::start()
+        .vertex_input(SingleBufferDefinition::<()>::new())
+        .vertex_shader(
+            unsafe {
+                vs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Vertex,
+                )
+            },
+            (),
+        )
+        .viewports_dynamic_scissors_irrelevant(1)
+        .depth_stencil_simple_depth()
+        .fragment_shader(
+            unsafe {
+                fs.graphics_entry_point::<(), _, _, _>(
+                    &CString::new("main").unwrap(),
+                    EmptyShaderInterfaceDef,
+                    EmptyShaderInterfaceDef,
+                    EmptyPipelineDesc,
+                    GraphicsShaderType::Fragment,
+                )
+            },
+            (),
+        )
+        .render_pass(
+            Subpass::from(
+                single_pass_renderpass!(device.clone(),
+                    attachments: {
+                        color: {
+                            load: Clear,
+                            store: Store,
+                            format: Format::R8G8B8A8Unorm,
+                            samples: 1,
+                        }
+                    },
+                    pass: {
+                        color: [color],
+                        depth_stencil: {}
+                    }
+                )
+                .unwrap(),
+                0,
+            )
+            .unwrap(),
+        )
+        .build(device.clone());
 
     match result {
         Err(GraphicsPipelineCreationError::NoDepthAttachment) => (),
-        _ => panic!()
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn depth_bounds_out_of_range() {
+    let (device, _) = gfx_dev_and_queue!(depth_bounds);
+    let (vs, fs) = basic_shaders(&device);
+
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, Some(Format::D16Unorm)))
+        .depth_bounds(0.5..2.0)
+        .build(device.clone());
+
+    match result {
+        Err(GraphicsPipelineCreationError::DepthBoundsOutOfRange) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn stencil_write_without_stencil_attachment() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    let stencil = Stencil {
+        pass_op: StencilOp::Replace,
+        ..Stencil::default()
+    };
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, Some(Format::D16Unorm)))
+        .stencil_front_and_back(stencil)
+        .build(device.clone());
+
+    match result {
+        Err(GraphicsPipelineCreationError::NoStencilAttachment) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn conservative_rasterization_extension() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .conservative_rasterization_overestimate(0.0)
+        .build(device.clone());
+
+    match result {
+        Err(GraphicsPipelineCreationError::ConservativeRasterizationExtensionNotEnabled) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn line_stipple_extension() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .line_stipple(2, 0xf0f0)
+        .build(device.clone());
+
+    match result {
+        Err(GraphicsPipelineCreationError::LineRasterizationExtensionNotEnabled) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn provoking_vertex_extension() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .provoking_vertex_mode(ProvokingVertexMode::LastVertex)
+        .build(device.clone());
+
+    match result {
+        Err(GraphicsPipelineCreationError::ProvokingVertexExtensionNotEnabled) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn provoking_vertex_last() {
+    let (device, _) = gfx_dev_and_queue!(provoking_vertex_last; ext_provoking_vertex);
+    let (vs, fs) = basic_shaders(&device);
+
+    basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .provoking_vertex_mode(ProvokingVertexMode::LastVertex)
+        .build(device.clone())
+        .unwrap();
+}
+
+#[test]
+fn color_write_enable_extension() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .extended_dynamic_state(ExtendedDynamicState {
+            color_write_enable: true,
+            ..ExtendedDynamicState::none()
+        })
+        .build(device.clone());
+
+    match result {
+        Err(GraphicsPipelineCreationError::ExtendedDynamicStateExtensionNotEnabled {
+            name: "ext_color_write_enable",
+        }) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn depth_clip_extension() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .depth_clip(true)
+        .build(device.clone());
+
+    match result {
+        Err(GraphicsPipelineCreationError::DepthClipEnableExtensionNotEnabled) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn depth_clip_with_depth_clamp() {
+    let (device, _) = gfx_dev_and_queue!(depth_clamp, depth_clip_enable; ext_depth_clip_enable);
+    let (vs, fs) = basic_shaders(&device);
+
+    basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .depth_clamp(true)
+        .depth_clip(true)
+        .build(device.clone())
+        .unwrap();
+}
+
+#[test]
+fn sample_mask() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .sample_mask([0x1, 0x0])
+        .build(device.clone())
+        .unwrap();
+}
+
+#[test]
+fn vertex_attribute_divisor_extension() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .vertex_input(SingleInstanceBufferDefinition::<BasicVertex>::with_divisor(
+            2,
+        ))
+        .build(device.clone());
+
+    match result {
+        Err(GraphicsPipelineCreationError::VertexAttributeDivisorExtensionNotEnabled) => (),
+        _ => panic!(),
     }
 }
 
+#[test]
+fn vertex_attribute_divisor_one() {
+    let (device, _) = gfx_dev_and_queue!();
+    let (vs, fs) = basic_shaders(&device);
+
+    // A divisor of 1 is the default instance rate, and doesn't need the extension.
+    basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .vertex_input(SingleInstanceBufferDefinition::<BasicVertex>::with_divisor(
+            1,
+        ))
+        .build(device.clone())
+        .unwrap();
+}
+
+#[test]
+fn fragment_shading_rate_dynamic_feature() {
+    let (device, _) = gfx_dev_and_queue!(; khr_fragment_shading_rate, khr_create_renderpass2);
+    let (vs, fs) = basic_shaders(&device);
+
+    let result = basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .fragment_shading_rate_dynamic()
+        .build(device.clone());
 
-mod simple_rp {
-    use format::Format;
-
-    single_pass_renderpass!{
-        attachments: {
-            color: {
-                load: Clear,
-                store: Store,
-                format: Format,
-            }
-        },
-        pass: {
-            color: [color],
-            depth_stencil: {}
-        }
+    match result {
+        Err(GraphicsPipelineCreationError::PipelineFragmentShadingRateFeatureNotEnabled) => (),
+        _ => panic!(),
     }
 }
 
+#[test]
+fn link_partial_library() {
+    let (device, _) = gfx_dev_and_queue!(graphics_pipeline_library;
+                                         khr_pipeline_library, ext_graphics_pipeline_library);
+    let (vs, fs) = basic_shaders(&device);
+
+    let library = Arc::new(
+        basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+            .library(GraphicsPipelineLibraryParts {
+                vertex_input_interface: true,
+                pre_rasterization_shaders: true,
+                ..GraphicsPipelineLibraryParts::none()
+            })
+            .build(device.clone())
+            .unwrap(),
+    );
+
+    // The fragment parts are not in the library, so they are created with the pipeline.
+    let pipeline = basic_pipeline(&vs, &fs, basic_subpass(&device, None))
+        .link_library(library)
+        .build(device.clone())
+        .unwrap();
+    assert!(pipeline.library_parts().is_empty());
+}
+
 /*
     #version 450
 
@@ -258,54 +705,41 @@ mod simple_rp {
         gl_Position = vec4(position, 0.0, 1.0);
     }
 */
-const BASIC_VS: [u8; 912] = [3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 27, 0, 0, 0, 0, 0, 0, 0, 17,
-                             0, 2, 0, 1, 0, 0, 0, 17, 0, 2, 0, 32, 0, 0, 0, 17, 0, 2, 0, 33, 0,
-                             0, 0, 11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100,
-                             46, 52, 53, 48, 0, 0, 0, 0, 14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0,
-                             15, 0, 7, 0, 0, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0,
-                             0, 13, 0, 0, 0, 18, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1, 0,
-                             0, 4, 0, 9, 0, 71, 76, 95, 65, 82, 66, 95, 115, 101, 112, 97,
-                             114, 97, 116, 101, 95, 115, 104, 97, 100, 101, 114, 95, 111, 98,
-                             106, 101, 99, 116, 115, 0, 0, 4, 0, 9, 0, 71, 76, 95, 65, 82, 66,
-                             95, 115, 104, 97, 100, 105, 110, 103, 95, 108, 97, 110, 103, 117,
-                             97, 103, 101, 95, 52, 50, 48, 112, 97, 99, 107, 0, 5, 0, 4, 0, 4,
-                             0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 6, 0, 11, 0, 0, 0,
-                             103, 108, 95, 80, 101, 114, 86, 101, 114, 116, 101, 120, 0, 0, 0,
-                             0, 6, 0, 6, 0, 11, 0, 0, 0, 0, 0, 0, 0, 103, 108, 95, 80, 111,
-                             115, 105, 116, 105, 111, 110, 0, 6, 0, 7, 0, 11, 0, 0, 0, 1, 0,
-                             0, 0, 103, 108, 95, 80, 111, 105, 110, 116, 83, 105, 122, 101, 0,
-                             0, 0, 0, 6, 0, 7, 0, 11, 0, 0, 0, 2, 0, 0, 0, 103, 108, 95, 67,
-                             108, 105, 112, 68, 105, 115, 116, 97, 110, 99, 101, 0, 6, 0, 7,
-                             0, 11, 0, 0, 0, 3, 0, 0, 0, 103, 108, 95, 67, 117, 108, 108, 68,
-                             105, 115, 116, 97, 110, 99, 101, 0, 5, 0, 3, 0, 13, 0, 0, 0, 0, 0,
-                             0, 0, 5, 0, 5, 0, 18, 0, 0, 0, 112, 111, 115, 105, 116, 105, 111,
-                             110, 0, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 0, 0, 0, 0, 11, 0, 0,
-                             0, 0, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 1, 0, 0, 0, 11, 0, 0, 0,
-                             1, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 2, 0, 0, 0, 11, 0, 0, 0, 3,
-                             0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 3, 0, 0, 0, 11, 0, 0, 0, 4, 0,
-                             0, 0, 71, 0, 3, 0, 11, 0, 0, 0, 2, 0, 0, 0, 71, 0, 4, 0, 18, 0, 0,
-                             0, 30, 0, 0, 0, 0, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0,
-                             3, 0, 0, 0, 2, 0, 0, 0, 22, 0, 3, 0, 6, 0, 0, 0, 32, 0, 0, 0, 23,
-                             0, 4, 0, 7, 0, 0, 0, 6, 0, 0, 0, 4, 0, 0, 0, 21, 0, 4, 0, 8, 0, 0,
-                             0, 32, 0, 0, 0, 0, 0, 0, 0, 43, 0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0,
-                             1, 0, 0, 0, 28, 0, 4, 0, 10, 0, 0, 0, 6, 0, 0, 0, 9, 0, 0, 0, 30,
-                             0, 6, 0, 11, 0, 0, 0, 7, 0, 0, 0, 6, 0, 0, 0, 10, 0, 0, 0, 10, 0,
-                             0, 0, 32, 0, 4, 0, 12, 0, 0, 0, 3, 0, 0, 0, 11, 0, 0, 0, 59, 0,
-                             4, 0, 12, 0, 0, 0, 13, 0, 0, 0, 3, 0, 0, 0, 21, 0, 4, 0, 14, 0,
-                             0, 0, 32, 0, 0, 0, 1, 0, 0, 0, 43, 0, 4, 0, 14, 0, 0, 0, 15, 0,
-                             0, 0, 0, 0, 0, 0, 23, 0, 4, 0, 16, 0, 0, 0, 6, 0, 0, 0, 2, 0, 0,
-                             0, 32, 0, 4, 0, 17, 0, 0, 0, 1, 0, 0, 0, 16, 0, 0, 0, 59, 0, 4,
-                             0, 17, 0, 0, 0, 18, 0, 0, 0, 1, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0,
-                             0, 20, 0, 0, 0, 0, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0, 0, 21, 0, 0,
-                             0, 0, 0, 128, 63, 32, 0, 4, 0, 25, 0, 0, 0, 3, 0, 0, 0, 7, 0, 0,
-                             0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0,
-                             248, 0, 2, 0, 5, 0, 0, 0, 61, 0, 4, 0, 16, 0, 0, 0, 19, 0, 0, 0,
-                             18, 0, 0, 0, 81, 0, 5, 0, 6, 0, 0, 0, 22, 0, 0, 0, 19, 0, 0, 0,
-                             0, 0, 0, 0, 81, 0, 5, 0, 6, 0, 0, 0, 23, 0, 0, 0, 19, 0, 0, 0, 1,
-                             0, 0, 0, 80, 0, 7, 0, 7, 0, 0, 0, 24, 0, 0, 0, 22, 0, 0, 0, 23,
-                             0, 0, 0, 20, 0, 0, 0, 21, 0, 0, 0, 65, 0, 5, 0, 25, 0, 0, 0, 26,
-                             0, 0, 0, 13, 0, 0, 0, 15, 0, 0, 0, 62, 0, 3, 0, 26, 0, 0, 0, 24,
-                             0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0];
+const BASIC_VS: [u8; 912] = [
+    3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 27, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0, 17, 0,
+    2, 0, 32, 0, 0, 0, 17, 0, 2, 0, 33, 0, 0, 0, 11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115,
+    116, 100, 46, 52, 53, 48, 0, 0, 0, 0, 14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 7, 0, 0, 0,
+    0, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 13, 0, 0, 0, 18, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0,
+    0, 194, 1, 0, 0, 4, 0, 9, 0, 71, 76, 95, 65, 82, 66, 95, 115, 101, 112, 97, 114, 97, 116, 101,
+    95, 115, 104, 97, 100, 101, 114, 95, 111, 98, 106, 101, 99, 116, 115, 0, 0, 4, 0, 9, 0, 71, 76,
+    95, 65, 82, 66, 95, 115, 104, 97, 100, 105, 110, 103, 95, 108, 97, 110, 103, 117, 97, 103, 101,
+    95, 52, 50, 48, 112, 97, 99, 107, 0, 5, 0, 4, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 5,
+    0, 6, 0, 11, 0, 0, 0, 103, 108, 95, 80, 101, 114, 86, 101, 114, 116, 101, 120, 0, 0, 0, 0, 6,
+    0, 6, 0, 11, 0, 0, 0, 0, 0, 0, 0, 103, 108, 95, 80, 111, 115, 105, 116, 105, 111, 110, 0, 6, 0,
+    7, 0, 11, 0, 0, 0, 1, 0, 0, 0, 103, 108, 95, 80, 111, 105, 110, 116, 83, 105, 122, 101, 0, 0,
+    0, 0, 6, 0, 7, 0, 11, 0, 0, 0, 2, 0, 0, 0, 103, 108, 95, 67, 108, 105, 112, 68, 105, 115, 116,
+    97, 110, 99, 101, 0, 6, 0, 7, 0, 11, 0, 0, 0, 3, 0, 0, 0, 103, 108, 95, 67, 117, 108, 108, 68,
+    105, 115, 116, 97, 110, 99, 101, 0, 5, 0, 3, 0, 13, 0, 0, 0, 0, 0, 0, 0, 5, 0, 5, 0, 18, 0, 0,
+    0, 112, 111, 115, 105, 116, 105, 111, 110, 0, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 0, 0, 0, 0,
+    11, 0, 0, 0, 0, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 1, 0, 0, 0, 11, 0, 0, 0, 1, 0, 0, 0, 72, 0,
+    5, 0, 11, 0, 0, 0, 2, 0, 0, 0, 11, 0, 0, 0, 3, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 3, 0, 0, 0,
+    11, 0, 0, 0, 4, 0, 0, 0, 71, 0, 3, 0, 11, 0, 0, 0, 2, 0, 0, 0, 71, 0, 4, 0, 18, 0, 0, 0, 30, 0,
+    0, 0, 0, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0, 22, 0, 3, 0, 6,
+    0, 0, 0, 32, 0, 0, 0, 23, 0, 4, 0, 7, 0, 0, 0, 6, 0, 0, 0, 4, 0, 0, 0, 21, 0, 4, 0, 8, 0, 0, 0,
+    32, 0, 0, 0, 0, 0, 0, 0, 43, 0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 1, 0, 0, 0, 28, 0, 4, 0, 10, 0,
+    0, 0, 6, 0, 0, 0, 9, 0, 0, 0, 30, 0, 6, 0, 11, 0, 0, 0, 7, 0, 0, 0, 6, 0, 0, 0, 10, 0, 0, 0,
+    10, 0, 0, 0, 32, 0, 4, 0, 12, 0, 0, 0, 3, 0, 0, 0, 11, 0, 0, 0, 59, 0, 4, 0, 12, 0, 0, 0, 13,
+    0, 0, 0, 3, 0, 0, 0, 21, 0, 4, 0, 14, 0, 0, 0, 32, 0, 0, 0, 1, 0, 0, 0, 43, 0, 4, 0, 14, 0, 0,
+    0, 15, 0, 0, 0, 0, 0, 0, 0, 23, 0, 4, 0, 16, 0, 0, 0, 6, 0, 0, 0, 2, 0, 0, 0, 32, 0, 4, 0, 17,
+    0, 0, 0, 1, 0, 0, 0, 16, 0, 0, 0, 59, 0, 4, 0, 17, 0, 0, 0, 18, 0, 0, 0, 1, 0, 0, 0, 43, 0, 4,
+    0, 6, 0, 0, 0, 20, 0, 0, 0, 0, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0, 0, 21, 0, 0, 0, 0, 0, 128, 63,
+    32, 0, 4, 0, 25, 0, 0, 0, 3, 0, 0, 0, 7, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0,
+    0, 3, 0, 0, 0, 248, 0, 2, 0, 5, 0, 0, 0, 61, 0, 4, 0, 16, 0, 0, 0, 19, 0, 0, 0, 18, 0, 0, 0,
+    81, 0, 5, 0, 6, 0, 0, 0, 22, 0, 0, 0, 19, 0, 0, 0, 0, 0, 0, 0, 81, 0, 5, 0, 6, 0, 0, 0, 23, 0,
+    0, 0, 19, 0, 0, 0, 1, 0, 0, 0, 80, 0, 7, 0, 7, 0, 0, 0, 24, 0, 0, 0, 22, 0, 0, 0, 23, 0, 0, 0,
+    20, 0, 0, 0, 21, 0, 0, 0, 65, 0, 5, 0, 25, 0, 0, 0, 26, 0, 0, 0, 13, 0, 0, 0, 15, 0, 0, 0, 62,
+    0, 3, 0, 26, 0, 0, 0, 24, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
+];
 
 /*
     #version 450
@@ -319,26 +753,21 @@ const BASIC_VS: [u8; 912] = [3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 27, 0, 0, 0, 0
         f_color = vec4(1.0, 0.0, 0.0, 1.0);
     }
 */
-const BASIC_FS: [u8; 420] = [3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 13, 0, 0, 0, 0, 0, 0, 0, 17,
-                             0, 2, 0, 1, 0, 0, 0, 11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46,
-                             115, 116, 100, 46, 52, 53,48, 0, 0, 0, 0, 14, 0, 3, 0, 0, 0, 0,
-                             0, 1, 0, 0, 0, 15, 0, 6, 0, 4, 0, 0, 0, 4, 0, 0, 0, 109, 97,
-                             105, 110, 0, 0, 0, 0, 9, 0, 0, 0, 16, 0, 3, 0, 4, 0, 0, 0, 7, 0,
-                             0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1, 0, 0, 4, 0, 9, 0, 71, 76,
-                             95, 65, 82, 66, 95, 115, 101, 112, 97, 114, 97, 116, 101, 95,
-                             115, 104, 97, 100, 101, 114, 95, 111, 98, 106, 101, 99, 116, 115,
-                             0, 0, 4, 0, 9, 0, 71, 76, 95, 65, 82, 66, 95, 115, 104, 97, 100,
-                             105, 110, 103, 95, 108, 97, 110, 103, 117, 97, 103, 101, 95, 52,
-                             50, 48, 112, 97, 99, 107, 0, 5, 0, 4, 0, 4, 0, 0, 0, 109, 97,
-                             105, 110, 0, 0, 0, 0, 5, 0, 4, 0, 9, 0, 0, 0, 102, 95, 99, 111,
-                             108, 111, 114, 0, 71, 0, 4, 0, 9, 0, 0, 0, 30, 0, 0, 0, 0, 0,
-                             0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0,
-                             0, 22, 0, 3, 0, 6, 0, 0, 0, 32, 0, 0, 0, 23, 0, 4, 0, 7, 0, 0,
-                             0, 6, 0, 0, 0, 4, 0, 0, 0, 32, 0, 4, 0, 8, 0, 0, 0, 3, 0, 0, 0,
-                             7, 0, 0, 0, 59, 0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 3, 0, 0, 0, 43,
-                             0, 4, 0, 6, 0, 0, 0, 10, 0, 0, 0, 0, 0, 128, 63, 43, 0, 4, 0, 6,
-                             0, 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 44, 0, 7, 0, 7, 0, 0, 0, 12, 0,
-                             0, 0, 10, 0, 0, 0, 11, 0, 0, 0, 11, 0, 0, 0, 10, 0, 0, 0, 54, 0,
-                             5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2,
-                             0, 5, 0, 0, 0, 62, 0, 3, 0, 9, 0, 0, 0, 12, 0, 0, 0, 253, 0, 1,
-                             0, 56, 0, 1, 0];
+const BASIC_FS: [u8; 420] = [
+    3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 13, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0, 11, 0,
+    6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0, 0, 0, 0, 14, 0, 3, 0,
+    0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 6, 0, 4, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 9,
+    0, 0, 0, 16, 0, 3, 0, 4, 0, 0, 0, 7, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1, 0, 0, 4, 0, 9, 0,
+    71, 76, 95, 65, 82, 66, 95, 115, 101, 112, 97, 114, 97, 116, 101, 95, 115, 104, 97, 100, 101,
+    114, 95, 111, 98, 106, 101, 99, 116, 115, 0, 0, 4, 0, 9, 0, 71, 76, 95, 65, 82, 66, 95, 115,
+    104, 97, 100, 105, 110, 103, 95, 108, 97, 110, 103, 117, 97, 103, 101, 95, 52, 50, 48, 112, 97,
+    99, 107, 0, 5, 0, 4, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 4, 0, 9, 0, 0, 0, 102,
+    95, 99, 111, 108, 111, 114, 0, 71, 0, 4, 0, 9, 0, 0, 0, 30, 0, 0, 0, 0, 0, 0, 0, 19, 0, 2, 0,
+    2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0, 22, 0, 3, 0, 6, 0, 0, 0, 32, 0, 0, 0, 23, 0,
+    4, 0, 7, 0, 0, 0, 6, 0, 0, 0, 4, 0, 0, 0, 32, 0, 4, 0, 8, 0, 0, 0, 3, 0, 0, 0, 7, 0, 0, 0, 59,
+    0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 3, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0, 0, 10, 0, 0, 0, 0, 0, 128,
+    63, 43, 0, 4, 0, 6, 0, 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 44, 0, 7, 0, 7, 0, 0, 0, 12, 0, 0, 0, 10,
+    0, 0, 0, 11, 0, 0, 0, 11, 0, 0, 0, 10, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0,
+    0, 3, 0, 0, 0, 248, 0, 2, 0, 5, 0, 0, 0, 62, 0, 3, 0, 9, 0, 0, 0, 12, 0, 0, 0, 253, 0, 1, 0,
+    56, 0, 1, 0,
+];
@@ -0,0 +1,359 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Compilation of HLSL shaders at runtime.
+//!
+//! This module is only available with the `hassle-rs` cargo feature. It compiles HLSL source code
+//! to SPIR-V with the [DirectX Shader Compiler](https://github.com/microsoft/DirectXShaderCompiler),
+//! then creates a reflected `ShaderModule` from it, exactly like
+//! `ShaderModule::from_words_with_reflection`.
+//!
+//! The DXC library (`dxcompiler.dll`, `libdxcompiler.so` or `libdxcompiler.dylib`) is loaded
+//! when compiling, and must be available in the library search path at that moment.
+//!
+//! HLSL binds resources to registers (`register(b0, space1)`), while Vulkan binds them to
+//! descriptor set bindings. DXC maps the space to the descriptor set and the register number to
+//! the binding, which means that `b0`, `t0`, `s0` and `u0` of the same space would all end up
+//! on the same binding. Use `HlslCompileOptions::register_shift` to move each register class to
+//! its own range of bindings.
+
+use std::sync::Arc;
+
+use crate::device::Device;
+use crate::instance::Version;
+use crate::pipeline::shader::ShaderModule;
+use crate::pipeline::shader::ShaderModuleCreationError;
+
+/// The stage that an HLSL shader is compiled for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HlslShaderKind {
+    Vertex,
+    /// Equivalent to a tessellation control shader.
+    Hull,
+    /// Equivalent to a tessellation evaluation shader.
+    Domain,
+    Geometry,
+    /// Equivalent to a fragment shader.
+    Pixel,
+    Compute,
+}
+
+impl HlslShaderKind {
+    // Prefix of the target profile of the shader kind.
+    #[inline]
+    fn profile_prefix(&self) -> &'static str {
+        match *self {
+            HlslShaderKind::Vertex => "vs",
+            HlslShaderKind::Hull => "hs",
+            HlslShaderKind::Domain => "ds",
+            HlslShaderKind::Geometry => "gs",
+            HlslShaderKind::Pixel => "ps",
+            HlslShaderKind::Compute => "cs",
+        }
+    }
+}
+
+/// A class of HLSL registers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HlslRegisterClass {
+    /// `b` registers, used by constant buffers.
+    ConstantBuffer,
+    /// `t` registers, used by textures and read-only buffers.
+    Texture,
+    /// `s` registers, used by samplers.
+    Sampler,
+    /// `u` registers, used by unordered access views.
+    UnorderedAccess,
+}
+
+impl HlslRegisterClass {
+    // Name of the DXC option that shifts the registers of this class.
+    #[inline]
+    fn shift_option(&self) -> &'static str {
+        match *self {
+            HlslRegisterClass::ConstantBuffer => "-fvk-b-shift",
+            HlslRegisterClass::Texture => "-fvk-t-shift",
+            HlslRegisterClass::Sampler => "-fvk-s-shift",
+            HlslRegisterClass::UnorderedAccess => "-fvk-u-shift",
+        }
+    }
+}
+
+/// The register spaces that a register shift applies to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HlslRegisterSpace {
+    /// Only the given space, which is also the descriptor set.
+    Space(u32),
+    /// All the spaces.
+    All,
+}
+
+/// Options for compiling an HLSL shader.
+#[derive(Debug, Clone)]
+pub struct HlslCompileOptions {
+    file_name: String,
+    entry_point: String,
+    shader_model: (u32, u32),
+    macros: Vec<(String, Option<String>)>,
+    register_shifts: Vec<(HlslRegisterClass, HlslRegisterSpace, u32)>,
+    vulkan_version: Version,
+    invert_y: bool,
+    optimize: bool,
+    debug_info: bool,
+}
+
+impl HlslCompileOptions {
+    /// Builds the default options.
+    ///
+    /// The source is named `shader.hlsl`, the entry point is `main`, the shader model is 6.0 and
+    /// the shader targets Vulkan 1.0 without optimizations. No registers are shifted.
+    #[inline]
+    pub fn new() -> HlslCompileOptions {
+        HlslCompileOptions {
+            file_name: "shader.hlsl".to_owned(),
+            entry_point: "main".to_owned(),
+            shader_model: (6, 0),
+            macros: Vec::new(),
+            register_shifts: Vec::new(),
+            vulkan_version: Version {
+                major: 1,
+                minor: 0,
+                patch: 0,
+            },
+            invert_y: false,
+            optimize: false,
+            debug_info: false,
+        }
+    }
+
+    /// Sets the name of the source, which is used in error messages.
+    #[inline]
+    pub fn file_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.file_name = name.into();
+        self
+    }
+
+    /// Sets the name of the entry point of the shader.
+    ///
+    /// Note that the entry point of the resulting SPIR-V module keeps this name.
+    #[inline]
+    pub fn entry_point<S: Into<String>>(mut self, name: S) -> Self {
+        self.entry_point = name.into();
+        self
+    }
+
+    /// Sets the shader model, for example `(6, 2)` for the `vs_6_2` profile of a vertex shader.
+    #[inline]
+    pub fn shader_model(mut self, major: u32, minor: u32) -> Self {
+        self.shader_model = (major, minor);
+        self
+    }
+
+    /// Defines a preprocessor macro, like `#define name value`.
+    #[inline]
+    pub fn define<S: Into<String>>(mut self, name: S, value: Option<S>) -> Self {
+        self.macros.push((name.into(), value.map(Into::into)));
+        self
+    }
+
+    /// Adds `shift` to the binding of every register of the given class in the given space.
+    ///
+    /// For example, shifting the `t` registers by 16 and the `s` registers by 32 makes `t0`
+    /// binding 16 and `s0` binding 32.
+    #[inline]
+    pub fn register_shift(
+        mut self,
+        class: HlslRegisterClass,
+        space: HlslRegisterSpace,
+        shift: u32,
+    ) -> Self {
+        self.register_shifts.push((class, space, shift));
+        self
+    }
+
+    /// Sets the version of Vulkan that the SPIR-V code targets. Must not be higher than the
+    /// version supported by the device.
+    ///
+    /// DXC only supports Vulkan 1.0, 1.1 and 1.2.
+    #[inline]
+    pub fn vulkan_version(mut self, version: Version) -> Self {
+        self.vulkan_version = version;
+        self
+    }
+
+    /// Negates the Y coordinate of the position output by the shader, to account for the
+    /// different orientation of the Vulkan and D3D coordinate systems.
+    #[inline]
+    pub fn invert_y(mut self, invert_y: bool) -> Self {
+        self.invert_y = invert_y;
+        self
+    }
+
+    /// Enables optimizations for performance.
+    #[inline]
+    pub fn optimize(mut self, optimize: bool) -> Self {
+        self.optimize = optimize;
+        self
+    }
+
+    /// Includes debug information, such as the names of variables, in the SPIR-V code.
+    #[inline]
+    pub fn debug_info(mut self, debug_info: bool) -> Self {
+        self.debug_info = debug_info;
+        self
+    }
+
+    // Returns the target profile for the given kind of shader, for example `ps_6_0`.
+    fn target_profile(&self, kind: HlslShaderKind) -> String {
+        format!(
+            "{}_{}_{}",
+            kind.profile_prefix(),
+            self.shader_model.0,
+            self.shader_model.1
+        )
+    }
+
+    // Returns the arguments passed to DXC.
+    fn arguments(&self) -> Vec<String> {
+        let mut args = vec![
+            "-spirv".to_owned(),
+            format!(
+                "-fspv-target-env=vulkan{}.{}",
+                self.vulkan_version.major, self.vulkan_version.minor
+            ),
+        ];
+
+        for &(class, space, shift) in self.register_shifts.iter() {
+            args.push(class.shift_option().to_owned());
+            args.push(shift.to_string());
+            args.push(match space {
+                HlslRegisterSpace::Space(space) => space.to_string(),
+                HlslRegisterSpace::All => "all".to_owned(),
+            });
+        }
+
+        if self.invert_y {
+            args.push("-fvk-invert-y".to_owned());
+        }
+        args.push(if self.optimize { "-O3" } else { "-Od" }.to_owned());
+        if self.debug_info {
+            args.push("-Zi".to_owned());
+        }
+
+        args
+    }
+}
+
+impl Default for HlslCompileOptions {
+    #[inline]
+    fn default() -> HlslCompileOptions {
+        HlslCompileOptions::new()
+    }
+}
+
+/// Compiles HLSL source code to SPIR-V words.
+pub fn compile_hlsl(
+    source: &str,
+    kind: HlslShaderKind,
+    options: &HlslCompileOptions,
+) -> Result<Vec<u32>, ShaderModuleCreationError> {
+    let profile = options.target_profile(kind);
+    let args = options.arguments();
+    let args: Vec<&str> = args.iter().map(|arg| &arg[..]).collect();
+    let defines: Vec<(&str, Option<&str>)> = options
+        .macros
+        .iter()
+        .map(|&(ref name, ref value)| (&name[..], value.as_ref().map(|v| &v[..])))
+        .collect();
+
+    let bytes = hassle_rs::compile_hlsl(
+        &options.file_name,
+        source,
+        &options.entry_point,
+        &profile,
+        &args,
+        &defines,
+    )
+    .map_err(|err| ShaderModuleCreationError::CompilationFailed(err.to_string()))?;
+
+    if bytes.len() % 4 != 0 {
+        return Err(ShaderModuleCreationError::CompilationFailed(
+            "the compiler returned an incomplete SPIR-V module".to_owned(),
+        ));
+    }
+
+    Ok(bytes
+        .chunks(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect())
+}
+
+impl ShaderModule {
+    /// Compiles HLSL source code and builds a new reflected shader module from it.
+    ///
+    /// Only available with the `hassle-rs` cargo feature.
+    ///
+    /// # Safety
+    ///
+    /// - The SPIR-V code may require some features that are not enabled. This isn't checked by
+    ///   this function.
+    ///
+    pub unsafe fn from_hlsl(
+        device: Arc<Device>,
+        source: &str,
+        kind: HlslShaderKind,
+        options: &HlslCompileOptions,
+    ) -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
+        let spirv = compile_hlsl(source, kind, options)?;
+        ShaderModule::from_words_with_reflection(device, &spirv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instance::Version;
+    use crate::pipeline::hlsl::HlslCompileOptions;
+    use crate::pipeline::hlsl::HlslRegisterClass;
+    use crate::pipeline::hlsl::HlslRegisterSpace;
+    use crate::pipeline::hlsl::HlslShaderKind;
+
+    #[test]
+    fn target_profile() {
+        let options = HlslCompileOptions::new().shader_model(6, 2);
+        assert_eq!(options.target_profile(HlslShaderKind::Pixel), "ps_6_2");
+        assert_eq!(options.target_profile(HlslShaderKind::Hull), "hs_6_2");
+    }
+
+    #[test]
+    fn register_shift_arguments() {
+        let options = HlslCompileOptions::new()
+            .vulkan_version(Version {
+                major: 1,
+                minor: 1,
+                patch: 0,
+            })
+            .register_shift(HlslRegisterClass::Texture, HlslRegisterSpace::Space(1), 16)
+            .register_shift(HlslRegisterClass::Sampler, HlslRegisterSpace::All, 32);
+
+        assert_eq!(
+            options.arguments(),
+            vec![
+                "-spirv",
+                "-fspv-target-env=vulkan1.1",
+                "-fvk-t-shift",
+                "16",
+                "1",
+                "-fvk-s-shift",
+                "32",
+                "all",
+                "-Od",
+            ]
+        );
+    }
+}
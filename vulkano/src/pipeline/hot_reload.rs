@@ -0,0 +1,796 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reloading of shaders and pipelines when the shader files change.
+//!
+//! A `HotReloader` watches a list of shader files. When one of them is modified, it is read
+//! again, compiled to SPIR-V with the compiler function given when creating the reloader, and
+//! turned into a new reflected `ShaderModule`. Then every pipeline that was registered with this
+//! shader is rebuilt, reusing the pipeline cache of the reloader.
+//!
+//! Compiling shaders and building pipelines can take a long time, so this happens in the
+//! background and is never visible immediately. The new shader modules and pipelines are only
+//! made current when you call `HotReloader::swap`, which you should do between two frames. This
+//! way, all the pipelines used while recording the command buffers of a frame come from the same
+//! version of the shaders.
+//!
+//! Errors can't be returned when they happen in the background. They are stored instead, and
+//! retrieved with `HotReloader::take_errors`. When a shader fails to compile, the previous version
+//! stays current. When one of the pipelines that use the modified shaders fails to build, or its
+//! build function panics, none of these shaders and pipelines are replaced.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use std::time::Duration;
+//! # use vulkano::device::Device;
+//! # use vulkano::pipeline::ComputePipeline;
+//! use vulkano::pipeline::hot_reload::load_spirv;
+//! use vulkano::pipeline::hot_reload::HotReloader;
+//!
+//! # let device: Arc<Device> = return;
+//! let reloader = unsafe {
+//!     HotReloader::new(device.clone(), None, Some(Duration::from_millis(500)), load_spirv)
+//! };
+//! let shader = reloader.watch("shaders/blur.spv").unwrap();
+//!
+//! let pipeline = reloader
+//!     .pipeline(&[&shader], |modules, cache| {
+//!         let entry_point = modules[0].reflected_compute_entry_point::<()>("main").unwrap();
+//!         let pipeline = ComputePipeline::new(device.clone(), &entry_point, &(), cache)?;
+//!         Ok(Arc::new(pipeline))
+//!     })
+//!     .unwrap();
+//!
+//! loop {
+//!     // Make the pipelines that were rebuilt since the last frame current.
+//!     reloader.swap();
+//!     for error in reloader.take_errors() {
+//!         println!("{}", error);
+//!     }
+//!
+//!     let compute_pipeline = pipeline.get();
+//!     // ... record and submit the commands of the frame ...
+//! #   break;
+//! }
+//! ```
+
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::device::Device;
+use crate::pipeline::cache::PipelineCache;
+use crate::pipeline::shader::ShaderModule;
+use crate::pipeline::shader::ShaderModuleCreationError;
+
+/// Function that turns the content of a shader file into SPIR-V words.
+///
+/// It receives the path of the file and its content.
+pub type ShaderCompiler =
+    dyn Fn(&Path, &[u8]) -> Result<Vec<u32>, ShaderModuleCreationError> + Send + Sync;
+
+/// Error returned by the functions that build a pipeline for a `HotReloader`.
+pub type PipelineBuildError = Box<dyn error::Error + Send + Sync>;
+
+/// Compiler for files that already contain SPIR-V code, in little endian.
+///
+/// Can be passed to `HotReloader::new` when the shaders are compiled by an external tool, such
+/// as `glslangValidator` running in watch mode.
+pub fn load_spirv(_path: &Path, content: &[u8]) -> Result<Vec<u32>, ShaderModuleCreationError> {
+    if !content.len().is_multiple_of(4) {
+        return Err(ShaderModuleCreationError::CompilationFailed(
+            "the size of the SPIR-V file is not a multiple of 4".to_owned(),
+        ));
+    }
+
+    Ok(content
+        .chunks(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect())
+}
+
+/// Watches shader files, and rebuilds the shader modules and pipelines that use them when they
+/// change.
+///
+/// See the documentation of the module for more information.
+pub struct HotReloader {
+    inner: Arc<Inner>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+struct Inner {
+    device: Arc<Device>,
+    cache: Option<Arc<PipelineCache>>,
+    compiler: Box<ShaderCompiler>,
+    state: Mutex<State>,
+    // Held while checking for changes, so that two reloads never stage pipelines at the same time.
+    reloading: Mutex<()>,
+    running: AtomicBool,
+}
+
+struct State {
+    watcher: Watcher,
+    shaders: Vec<ShaderState>,
+    pipelines: Vec<Arc<dyn PipelineSlot>>,
+    errors: Vec<HotReloadError>,
+}
+
+struct ShaderState {
+    current: Arc<ShaderModule>,
+    // New version of the module, made current by the next call to `swap`.
+    pending: Option<Arc<ShaderModule>>,
+}
+
+impl ShaderState {
+    #[inline]
+    fn latest(&self) -> &Arc<ShaderModule> {
+        self.pending.as_ref().unwrap_or(&self.current)
+    }
+}
+
+impl HotReloader {
+    /// Builds a new `HotReloader`.
+    ///
+    /// `cache` is passed to the functions that build the pipelines. If `poll_interval` is
+    /// `Some`, a background thread checks the files for changes at this interval. Otherwise,
+    /// nothing happens until you call `check_for_changes` yourself.
+    ///
+    /// # Safety
+    ///
+    /// The shader modules are created with `ShaderModule::from_words_with_reflection`, and the
+    /// same requirements apply to the SPIR-V code returned by `compiler`:
+    ///
+    /// - The SPIR-V code may require some features that are not enabled. This isn't checked.
+    ///
+    pub unsafe fn new<F>(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        poll_interval: Option<Duration>,
+        compiler: F,
+    ) -> HotReloader
+    where
+        F: Fn(&Path, &[u8]) -> Result<Vec<u32>, ShaderModuleCreationError> + Send + Sync + 'static,
+    {
+        let inner = Arc::new(Inner {
+            device,
+            cache,
+            compiler: Box::new(compiler),
+            state: Mutex::new(State {
+                watcher: Watcher::new(),
+                shaders: Vec::new(),
+                pipelines: Vec::new(),
+                errors: Vec::new(),
+            }),
+            reloading: Mutex::new(()),
+            running: AtomicBool::new(true),
+        });
+
+        let thread = poll_interval.map(|interval| {
+            let inner = inner.clone();
+            thread::spawn(move || {
+                while inner.running.load(Ordering::SeqCst) {
+                    thread::park_timeout(interval);
+                    if inner.running.load(Ordering::SeqCst) {
+                        inner.check_for_changes();
+                    }
+                }
+            })
+        });
+
+        HotReloader { inner, thread }
+    }
+
+    /// Returns the device used to create the shader modules.
+    #[inline]
+    pub fn device(&self) -> &Arc<Device> {
+        &self.inner.device
+    }
+
+    /// Returns the pipeline cache passed to the functions that build the pipelines.
+    #[inline]
+    pub fn cache(&self) -> Option<&Arc<PipelineCache>> {
+        self.inner.cache.as_ref()
+    }
+
+    /// Starts watching a shader file.
+    ///
+    /// The file is read and compiled immediately, and an error is returned if this fails.
+    pub fn watch<P>(&self, path: P) -> Result<HotShader, HotReloadError>
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+        let module = self.inner.load(&path)?;
+
+        let mut state = self.inner.state.lock().unwrap();
+        let index = state.watcher.add(path);
+        state.shaders.push(ShaderState {
+            current: module,
+            pending: None,
+        });
+
+        Ok(HotShader {
+            inner: self.inner.clone(),
+            index,
+        })
+    }
+
+    /// Registers a pipeline that uses the given shaders.
+    ///
+    /// `build` receives the modules of `shaders`, in the same order, and the pipeline cache of
+    /// the reloader. It is called immediately to build the first version of the pipeline, then
+    /// from the background every time one of the shaders changes. A panic in the background is
+    /// caught and returned by `take_errors` as `HotReloadError::PipelineBuildPanicked`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the shaders was obtained from another reloader.
+    ///
+    pub fn pipeline<P, F>(
+        &self,
+        shaders: &[&HotShader],
+        build: F,
+    ) -> Result<HotPipeline<P>, HotReloadError>
+    where
+        P: Send + Sync + 'static,
+        F: Fn(
+                &[Arc<ShaderModule>],
+                Option<Arc<PipelineCache>>,
+            ) -> Result<Arc<P>, PipelineBuildError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        for shader in shaders {
+            assert!(Arc::ptr_eq(&shader.inner, &self.inner));
+        }
+
+        let indices: Vec<usize> = shaders.iter().map(|shader| shader.index).collect();
+        let modules = self.inner.latest_modules(&indices);
+        let pipeline =
+            build(&modules, self.inner.cache.clone()).map_err(HotReloadError::PipelineCreation)?;
+
+        let slot = Arc::new(Slot {
+            shaders: indices,
+            build: Box::new(build),
+            current: Mutex::new(pipeline),
+            staged: Mutex::new(None),
+            pending: Mutex::new(None),
+        });
+        self.inner
+            .state
+            .lock()
+            .unwrap()
+            .pipelines
+            .push(slot.clone());
+
+        Ok(HotPipeline { slot })
+    }
+
+    /// Checks the shader files for changes, and rebuilds the modified shaders and the pipelines
+    /// that use them.
+    ///
+    /// This blocks until everything is rebuilt. If the reloader was created with a polling
+    /// interval, this is already done regularly in the background.
+    #[inline]
+    pub fn check_for_changes(&self) {
+        self.inner.check_for_changes();
+    }
+
+    /// Makes the shader modules and pipelines rebuilt since the previous call current.
+    ///
+    /// Call this between two frames. Returns the number of pipelines that were replaced.
+    pub fn swap(&self) -> usize {
+        let mut state = self.inner.state.lock().unwrap();
+
+        for shader in state.shaders.iter_mut() {
+            if let Some(module) = shader.pending.take() {
+                shader.current = module;
+            }
+        }
+
+        state
+            .pipelines
+            .iter()
+            .filter(|pipeline| pipeline.swap())
+            .count()
+    }
+
+    /// Returns the errors that happened in the background since the previous call.
+    #[inline]
+    pub fn take_errors(&self) -> Vec<HotReloadError> {
+        let mut state = self.inner.state.lock().unwrap();
+        state.errors.drain(..).collect()
+    }
+}
+
+impl Drop for HotReloader {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            self.inner.running.store(false, Ordering::SeqCst);
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+impl fmt::Debug for HotReloader {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.inner.state.lock().unwrap();
+        fmt.debug_struct("HotReloader")
+            .field("device", &self.inner.device)
+            .field("shaders", &state.watcher.paths().collect::<Vec<_>>())
+            .field("pipelines", &state.pipelines.len())
+            .finish()
+    }
+}
+
+impl Inner {
+    // Reads and compiles a shader file.
+    fn load(&self, path: &Path) -> Result<Arc<ShaderModule>, HotReloadError> {
+        let content = fs::read(path).map_err(|error| HotReloadError::Io {
+            path: path.to_owned(),
+            error,
+        })?;
+
+        // A panic of the compiler must not kill the polling thread.
+        panic::catch_unwind(AssertUnwindSafe(|| (self.compiler)(path, &content)))
+            .unwrap_or_else(|_| {
+                Err(ShaderModuleCreationError::CompilationFailed(
+                    "the shader compiler panicked".to_owned(),
+                ))
+            })
+            .and_then(|spirv| unsafe {
+                ShaderModule::from_words_with_reflection(self.device.clone(), &spirv)
+            })
+            .map_err(|error| HotReloadError::ShaderModuleCreation {
+                path: path.to_owned(),
+                error,
+            })
+    }
+
+    // Returns the most recent version of the modules of the given shaders.
+    fn latest_modules(&self, indices: &[usize]) -> Vec<Arc<ShaderModule>> {
+        self.modules(indices, &[])
+    }
+
+    // Same as `latest_modules`, but the modules in `loaded` take precedence.
+    fn modules(
+        &self,
+        indices: &[usize],
+        loaded: &[(usize, Arc<ShaderModule>)],
+    ) -> Vec<Arc<ShaderModule>> {
+        let state = self.state.lock().unwrap();
+        indices
+            .iter()
+            .map(|&index| match loaded.iter().find(|(i, _)| *i == index) {
+                Some((_, module)) => module.clone(),
+                None => state.shaders[index].latest().clone(),
+            })
+            .collect()
+    }
+
+    fn check_for_changes(&self) {
+        let _reloading = self.reloading.lock().unwrap();
+
+        // The lock is not held while compiling and building, so that `swap` never has to wait.
+        let changed: Vec<(usize, PathBuf)> = {
+            let mut state = self.state.lock().unwrap();
+            let changed = state.watcher.changed();
+            changed
+                .into_iter()
+                .map(|index| (index, state.watcher.path(index).to_owned()))
+                .collect()
+        };
+
+        if changed.is_empty() {
+            return;
+        }
+
+        let mut errors = Vec::new();
+        let mut loaded = Vec::new();
+        for (index, path) in changed {
+            match self.load(&path) {
+                Ok(module) => loaded.push((index, module)),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        let pipelines: Vec<Arc<dyn PipelineSlot>> = {
+            let state = self.state.lock().unwrap();
+            state
+                .pipelines
+                .iter()
+                .filter(|pipeline| {
+                    pipeline
+                        .shaders()
+                        .iter()
+                        .any(|&index| loaded.iter().any(|(i, _)| *i == index))
+                })
+                .cloned()
+                .collect()
+        };
+
+        let mut all_rebuilt = true;
+        for pipeline in pipelines.iter() {
+            let modules = self.modules(pipeline.shaders(), &loaded);
+            if let Err(error) = pipeline.rebuild(&modules, self.cache.clone()) {
+                errors.push(error);
+                all_rebuilt = false;
+            }
+        }
+
+        // The new modules and pipelines become pending at the same time, and only if all the
+        // pipelines could be rebuilt, so that `swap` never makes current a module without the
+        // pipelines that were built from it.
+        let mut state = self.state.lock().unwrap();
+        for pipeline in pipelines.iter() {
+            pipeline.unstage(all_rebuilt);
+        }
+        if all_rebuilt {
+            for (index, module) in loaded {
+                state.shaders[index].pending = Some(module);
+            }
+        }
+        state.errors.extend(errors);
+    }
+}
+
+/// A shader file watched by a `HotReloader`.
+pub struct HotShader {
+    inner: Arc<Inner>,
+    index: usize,
+}
+
+impl HotShader {
+    /// Returns the path of the file.
+    #[inline]
+    pub fn path(&self) -> PathBuf {
+        let state = self.inner.state.lock().unwrap();
+        state.watcher.path(self.index).to_owned()
+    }
+
+    /// Returns the current version of the shader module.
+    ///
+    /// A new version only becomes current when `HotReloader::swap` is called.
+    #[inline]
+    pub fn module(&self) -> Arc<ShaderModule> {
+        let state = self.inner.state.lock().unwrap();
+        state.shaders[self.index].current.clone()
+    }
+}
+
+impl fmt::Debug for HotShader {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("HotShader")
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+/// A pipeline that is rebuilt by a `HotReloader` when its shaders change.
+pub struct HotPipeline<P> {
+    slot: Arc<Slot<P>>,
+}
+
+impl<P> HotPipeline<P> {
+    /// Returns the current version of the pipeline.
+    ///
+    /// A new version only becomes current when `HotReloader::swap` is called.
+    #[inline]
+    pub fn get(&self) -> Arc<P> {
+        self.slot.current.lock().unwrap().clone()
+    }
+}
+
+impl<P> Clone for HotPipeline<P> {
+    #[inline]
+    fn clone(&self) -> HotPipeline<P> {
+        HotPipeline {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+impl<P> fmt::Debug for HotPipeline<P> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("HotPipeline")
+            .field("shaders", &self.slot.shaders)
+            .finish()
+    }
+}
+
+// Type-erased access to the pipelines of a reloader.
+trait PipelineSlot: Send + Sync {
+    // Indices of the shaders used by the pipeline.
+    fn shaders(&self) -> &[usize];
+
+    // Builds a new version of the pipeline, which is staged until `unstage` is called.
+    fn rebuild(
+        &self,
+        modules: &[Arc<ShaderModule>],
+        cache: Option<Arc<PipelineCache>>,
+    ) -> Result<(), HotReloadError>;
+
+    // Makes the staged version pending if `keep` is true, otherwise discards it.
+    fn unstage(&self, keep: bool);
+
+    // Makes the pending version current, if any. Returns true if there was one.
+    fn swap(&self) -> bool;
+}
+
+type BuildFn<P> = dyn Fn(&[Arc<ShaderModule>], Option<Arc<PipelineCache>>) -> Result<Arc<P>, PipelineBuildError>
+    + Send
+    + Sync;
+
+struct Slot<P> {
+    shaders: Vec<usize>,
+    build: Box<BuildFn<P>>,
+    current: Mutex<Arc<P>>,
+    staged: Mutex<Option<Arc<P>>>,
+    pending: Mutex<Option<Arc<P>>>,
+}
+
+impl<P> PipelineSlot for Slot<P>
+where
+    P: Send + Sync,
+{
+    #[inline]
+    fn shaders(&self) -> &[usize] {
+        &self.shaders
+    }
+
+    fn rebuild(
+        &self,
+        modules: &[Arc<ShaderModule>],
+        cache: Option<Arc<PipelineCache>>,
+    ) -> Result<(), HotReloadError> {
+        let pipeline = panic::catch_unwind(AssertUnwindSafe(|| (self.build)(modules, cache)))
+            .map_err(|_| HotReloadError::PipelineBuildPanicked)?
+            .map_err(HotReloadError::PipelineCreation)?;
+        *self.staged.lock().unwrap() = Some(pipeline);
+        Ok(())
+    }
+
+    fn unstage(&self, keep: bool) {
+        let staged = self.staged.lock().unwrap().take();
+        if keep && staged.is_some() {
+            *self.pending.lock().unwrap() = staged;
+        }
+    }
+
+    fn swap(&self) -> bool {
+        match self.pending.lock().unwrap().take() {
+            Some(pipeline) => {
+                *self.current.lock().unwrap() = pipeline;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Detects the modifications of a list of files by polling their metadata.
+struct Watcher {
+    files: Vec<(PathBuf, Option<(SystemTime, u64)>)>,
+}
+
+impl Watcher {
+    #[inline]
+    fn new() -> Watcher {
+        Watcher { files: Vec::new() }
+    }
+
+    // Adds a file and returns its index.
+    fn add(&mut self, path: PathBuf) -> usize {
+        let stamp = Watcher::stamp(&path);
+        self.files.push((path, stamp));
+        self.files.len() - 1
+    }
+
+    #[inline]
+    fn path(&self, index: usize) -> &Path {
+        &self.files[index].0
+    }
+
+    #[inline]
+    fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|file| file.0.as_path())
+    }
+
+    // Returns the indices of the files that changed since the previous call. Files that can't be
+    // accessed, for example while an editor is replacing them, are not considered changed.
+    fn changed(&mut self) -> Vec<usize> {
+        let mut changed = Vec::new();
+
+        for (index, &mut (ref path, ref mut stamp)) in self.files.iter_mut().enumerate() {
+            let new_stamp = Watcher::stamp(path);
+            if new_stamp.is_some() && new_stamp != *stamp {
+                *stamp = new_stamp;
+                changed.push(index);
+            }
+        }
+
+        changed
+    }
+
+    // The modification time alone isn't enough on file systems with a coarse resolution.
+    fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+}
+
+/// Error that can happen when reloading a shader or a pipeline.
+#[derive(Debug)]
+pub enum HotReloadError {
+    /// The shader file couldn't be read.
+    Io { path: PathBuf, error: io::Error },
+    /// The shader couldn't be compiled, or the shader module couldn't be created.
+    ShaderModuleCreation {
+        path: PathBuf,
+        error: ShaderModuleCreationError,
+    },
+    /// The function that builds a pipeline returned an error.
+    PipelineCreation(PipelineBuildError),
+    /// The function that builds a pipeline panicked.
+    PipelineBuildPanicked,
+}
+
+impl error::Error for HotReloadError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            HotReloadError::Io { ref error, .. } => Some(error),
+            HotReloadError::ShaderModuleCreation { ref error, .. } => Some(error),
+            HotReloadError::PipelineCreation(ref error) => Some(&**error),
+            HotReloadError::PipelineBuildPanicked => None,
+        }
+    }
+}
+
+impl fmt::Display for HotReloadError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            HotReloadError::Io { ref path, .. } => {
+                write!(fmt, "the shader file {} couldn't be read", path.display())
+            }
+            HotReloadError::ShaderModuleCreation { ref path, .. } => write!(
+                fmt,
+                "the shader module couldn't be created from {}",
+                path.display()
+            ),
+            HotReloadError::PipelineCreation(_) => {
+                write!(fmt, "the pipeline couldn't be rebuilt")
+            }
+            HotReloadError::PipelineBuildPanicked => {
+                write!(fmt, "the function that builds the pipeline panicked")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::hot_reload::load_spirv;
+    use crate::pipeline::hot_reload::HotReloadError;
+    use crate::pipeline::hot_reload::PipelineSlot;
+    use crate::pipeline::hot_reload::Slot;
+    use crate::pipeline::hot_reload::Watcher;
+    use crate::pipeline::shader::ShaderModuleCreationError;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::atomic::AtomicU32;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn load_spirv_little_endian() {
+        let words = load_spirv(Path::new("a.spv"), &[0x03, 0x02, 0x23, 0x07, 1, 0, 0, 0]).unwrap();
+        assert_eq!(words, vec![0x07230203, 1]);
+
+        match load_spirv(Path::new("a.spv"), &[0x03, 0x02, 0x23]) {
+            Err(ShaderModuleCreationError::CompilationFailed(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn watcher_detects_changes() {
+        let dir = std::env::temp_dir().join(format!("vulkano-hot-reload-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("shader.spv");
+        fs::write(&path, [0u8; 4]).unwrap();
+
+        let mut watcher = Watcher::new();
+        assert_eq!(watcher.add(path.clone()), 0);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, [0u8; 8]).unwrap();
+        assert_eq!(watcher.changed(), vec![0]);
+        assert!(watcher.changed().is_empty());
+
+        // A file that disappears isn't considered changed until it comes back.
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.changed().is_empty());
+        fs::write(&path, [0u8; 12]).unwrap();
+        assert_eq!(watcher.changed(), vec![0]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // A slot whose build function returns 1, 2, ... and panics on its third call.
+    fn counting_slot() -> Slot<u32> {
+        let calls = AtomicU32::new(0);
+        Slot {
+            shaders: vec![0],
+            build: Box::new(move |_, _| {
+                let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                if call == 3 {
+                    panic!("build failure");
+                }
+                Ok(Arc::new(call))
+            }),
+            current: Mutex::new(Arc::new(0)),
+            staged: Mutex::new(None),
+            pending: Mutex::new(None),
+        }
+    }
+
+    #[test]
+    fn slot_staging() {
+        let slot = counting_slot();
+
+        // A staged pipeline only becomes pending if it is kept.
+        slot.rebuild(&[], None).unwrap();
+        slot.unstage(false);
+        assert!(!slot.swap());
+        assert_eq!(*slot.current.lock().unwrap().clone(), 0);
+
+        slot.rebuild(&[], None).unwrap();
+        slot.unstage(true);
+        assert!(slot.swap());
+        assert_eq!(*slot.current.lock().unwrap().clone(), 2);
+        assert!(!slot.swap());
+    }
+
+    #[test]
+    fn slot_build_panic() {
+        let slot = counting_slot();
+        slot.rebuild(&[], None).unwrap();
+        slot.rebuild(&[], None).unwrap();
+
+        match slot.rebuild(&[], None) {
+            Err(HotReloadError::PipelineBuildPanicked) => (),
+            _ => panic!(),
+        }
+
+        // The slot is still usable after the panic.
+        slot.rebuild(&[], None).unwrap();
+        slot.unstage(true);
+        assert!(slot.swap());
+        assert_eq!(*slot.current.lock().unwrap().clone(), 4);
+    }
+}
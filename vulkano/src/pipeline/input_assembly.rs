@@ -12,6 +12,7 @@
 //! The input assembly is the stage where lists of vertices are turned into primitives.
 //!
 
+use crate::features::Features;
 use crate::vk;
 
 /// How the input assembly stage should behave.
@@ -99,6 +100,60 @@ impl PrimitiveTopology {
             _ => false,
         }
     }
+
+    /// Returns true if this primitive topology supports using primitive restart on a device
+    /// with the given features enabled.
+    ///
+    /// List topologies support primitive restart with the `primitive_topology_list_restart`
+    /// feature, and patch lists with the `primitive_topology_patch_list_restart` feature.
+    #[inline]
+    pub fn supports_primitive_restart_with_features(&self, features: &Features) -> bool {
+        match *self {
+            PrimitiveTopology::PointList
+            | PrimitiveTopology::LineList
+            | PrimitiveTopology::TriangleList
+            | PrimitiveTopology::LineListWithAdjacency
+            | PrimitiveTopology::TriangleListWithAdjacency => {
+                features.primitive_topology_list_restart
+            }
+            PrimitiveTopology::PatchList { .. } => features.primitive_topology_patch_list_restart,
+            _ => self.supports_primitive_restart(),
+        }
+    }
+
+    /// Returns true if both topologies are of the same class, which is points, lines, triangles
+    /// or patches.
+    ///
+    /// When the topology of a pipeline is dynamic, the topology set before drawing must be of the
+    /// same class as the one of the pipeline.
+    #[inline]
+    pub fn is_same_class(&self, other: &PrimitiveTopology) -> bool {
+        #[derive(PartialEq)]
+        enum Class {
+            Points,
+            Lines,
+            Triangles,
+            Patches,
+        }
+
+        fn class(topology: &PrimitiveTopology) -> Class {
+            match *topology {
+                PrimitiveTopology::PointList => Class::Points,
+                PrimitiveTopology::LineList
+                | PrimitiveTopology::LineStrip
+                | PrimitiveTopology::LineListWithAdjacency
+                | PrimitiveTopology::LineStripWithAdjacency => Class::Lines,
+                PrimitiveTopology::TriangleList
+                | PrimitiveTopology::TriangleStrip
+                | PrimitiveTopology::TriangleFan
+                | PrimitiveTopology::TriangleListWithAdjacency
+                | PrimitiveTopology::TriangleStripWithAdjacency => Class::Triangles,
+                PrimitiveTopology::PatchList { .. } => Class::Patches,
+            }
+        }
+
+        class(self) == class(other)
+    }
 }
 
 /// Trait for types that can be used as indices by the GPU.
@@ -129,3 +184,39 @@ pub enum IndexType {
     U16 = vk::INDEX_TYPE_UINT16,
     U32 = vk::INDEX_TYPE_UINT32,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::features::Features;
+    use crate::pipeline::input_assembly::PrimitiveTopology;
+
+    #[test]
+    fn list_restart_requires_feature() {
+        let none = Features::none();
+        assert!(PrimitiveTopology::TriangleStrip.supports_primitive_restart_with_features(&none));
+        assert!(!PrimitiveTopology::TriangleList.supports_primitive_restart_with_features(&none));
+
+        let features = Features {
+            primitive_topology_list_restart: true,
+            ..Features::none()
+        };
+        assert!(PrimitiveTopology::TriangleList.supports_primitive_restart_with_features(&features));
+        assert!(!PrimitiveTopology::PatchList {
+            vertices_per_patch: 3
+        }
+        .supports_primitive_restart_with_features(&features));
+    }
+
+    #[test]
+    fn topology_classes() {
+        assert!(PrimitiveTopology::LineList.is_same_class(&PrimitiveTopology::LineStrip));
+        assert!(PrimitiveTopology::TriangleFan.is_same_class(&PrimitiveTopology::TriangleList));
+        assert!(!PrimitiveTopology::PointList.is_same_class(&PrimitiveTopology::LineList));
+        assert!(PrimitiveTopology::PatchList {
+            vertices_per_patch: 3
+        }
+        .is_same_class(&PrimitiveTopology::PatchList {
+            vertices_per_patch: 4
+        }));
+    }
+}
@@ -79,21 +79,37 @@ pub use self::compute_pipeline::ComputePipeline;
 pub use self::compute_pipeline::ComputePipelineAbstract;
 pub use self::compute_pipeline::ComputePipelineCreationError;
 pub use self::compute_pipeline::ComputePipelineSys;
+pub use self::graphics_pipeline::ExtendedDynamicState;
 pub use self::graphics_pipeline::GraphicsPipeline;
 pub use self::graphics_pipeline::GraphicsPipelineAbstract;
 pub use self::graphics_pipeline::GraphicsPipelineBuilder;
 pub use self::graphics_pipeline::GraphicsPipelineCreationError;
+pub use self::graphics_pipeline::GraphicsPipelineLibraryParts;
 pub use self::graphics_pipeline::GraphicsPipelineSys;
 
 mod compute_pipeline;
 mod graphics_pipeline;
+#[cfg(test)]
+pub(crate) use self::graphics_pipeline::tests as graphics_pipeline_tests;
 
 pub mod blend;
 pub mod cache;
 pub mod depth_stencil;
+pub mod executable;
+#[cfg(feature = "shaderc")]
+pub mod glsl;
+#[cfg(feature = "hassle-rs")]
+pub mod hlsl;
+pub mod hot_reload;
 pub mod input_assembly;
 pub mod multisample;
 pub mod raster;
+pub mod reflect;
+pub mod robustness;
 pub mod shader;
+pub mod shader_object;
+pub mod shading_rate;
 pub mod vertex;
 pub mod viewport;
+#[cfg(feature = "naga")]
+pub mod wgsl;
@@ -31,9 +31,15 @@
 //!
 //! If `alpha_to_one` is true, the alpha value of all the samples will be forced to 1.0 (or the
 //! maximum possible value) after the effects of `alpha_to_coverage` have been applied.
+//!
+//! With the `VK_EXT_sample_locations` extension, the locations of the samples within each pixel
+//! can be chosen with `SampleLocations` instead of using the standard locations.
 
 // TODO: handle some weird behaviors with non-floating-point targets
 
+use crate::vk;
+use std::ptr;
+
 /// State of the multisampling.
 ///
 /// See the documentation in this module.
@@ -59,3 +65,90 @@ impl Multisample {
         }
     }
 }
+
+/// Describes the sample locations state of a graphics pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SampleLocationsState {
+    /// The standard sample locations of the device are used. This is the default.
+    Disabled,
+
+    /// The sample locations are fixed when the pipeline is built.
+    Fixed(SampleLocations),
+
+    /// The sample locations will need to be specified when you submit a draw command.
+    Dynamic,
+}
+
+impl SampleLocationsState {
+    /// Returns true if equal to `SampleLocationsState::Dynamic`.
+    #[inline]
+    pub fn is_dynamic(&self) -> bool {
+        matches!(*self, SampleLocationsState::Dynamic)
+    }
+}
+
+impl Default for SampleLocationsState {
+    #[inline]
+    fn default() -> SampleLocationsState {
+        SampleLocationsState::Disabled
+    }
+}
+
+/// Custom locations of the samples within the pixels.
+///
+/// The locations are given for a grid of `grid_size` pixels, which is repeated over the whole
+/// framebuffer. `locations` contains `samples_per_pixel` locations for each pixel of the grid, the
+/// pixels being in row-major order. Each location is a pair of coordinates relative to the
+/// top-left corner of its pixel, which are clamped to the `sample_location_coordinate_range`
+/// property of the device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleLocations {
+    /// The number of samples per pixel. Must be equal to the number of samples of the subpass.
+    pub samples_per_pixel: u32,
+
+    /// The width and height in pixels of the grid of locations.
+    pub grid_size: [u32; 2],
+
+    /// The location of each sample of each pixel of the grid.
+    pub locations: Vec<[f32; 2]>,
+}
+
+impl SampleLocations {
+    /// Returns the number of elements that `locations` must contain.
+    #[inline]
+    pub fn expected_len(&self) -> usize {
+        self.samples_per_pixel as usize * self.grid_size[0] as usize * self.grid_size[1] as usize
+    }
+
+    // Builds the Vulkan struct, which borrows the locations of `self`.
+    #[inline]
+    pub(crate) fn to_vulkan(&self) -> vk::SampleLocationsInfoEXT {
+        vk::SampleLocationsInfoEXT {
+            sType: vk::STRUCTURE_TYPE_SAMPLE_LOCATIONS_INFO_EXT,
+            pNext: ptr::null(),
+            sampleLocationsPerPixel: self.samples_per_pixel,
+            sampleLocationGridSize: vk::Extent2D {
+                width: self.grid_size[0],
+                height: self.grid_size[1],
+            },
+            sampleLocationsCount: self.locations.len() as u32,
+            pSampleLocations: self.locations.as_ptr() as *const _,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::multisample::SampleLocations;
+
+    #[test]
+    fn sample_locations_expected_len() {
+        let locations = SampleLocations {
+            samples_per_pixel: 4,
+            grid_size: [2, 1],
+            locations: vec![[0.5, 0.5]; 8],
+        };
+        assert_eq!(locations.expected_len(), 8);
+        assert_eq!(locations.to_vulkan().sampleLocationsCount, 8);
+    }
+}
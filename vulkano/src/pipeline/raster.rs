@@ -18,10 +18,21 @@ use crate::vk;
 /// State of the rasterizer.
 #[derive(Clone, Debug)]
 pub struct Rasterization {
-    /// If true, then the depth value of the vertices will be clamped to [0.0 ; 1.0]. If false,
-    /// fragments whose depth is outside of this range will be discarded.
+    /// If true, then the depth value of the fragments will be clamped to the depth range of the
+    /// viewport. Requires the `depth_clamp` feature.
+    ///
+    /// Unless `depth_clip` says otherwise, this also disables depth clipping.
     pub depth_clamp: bool,
 
+    /// Whether primitives are clipped against the near and far planes of the view volume, and
+    /// thus discarded when they are outside of the depth range.
+    ///
+    /// If `None`, depth clipping is enabled if and only if `depth_clamp` is false, which is the
+    /// standard Vulkan behavior. Any other value requires the `ext_depth_clip_enable` device
+    /// extension and the `depth_clip_enable` feature, and allows for example to clamp the depth
+    /// while still clipping, or to disable clipping without clamping when rendering shadow maps.
+    pub depth_clip: Option<bool>,
+
     /// If true, all the fragments will be discarded. This is usually used when your vertex shader
     /// has some side effects and you don't need to run the fragment shader.
     pub rasterizer_discard: bool,
@@ -43,6 +54,23 @@ pub struct Rasterization {
     pub line_width: Option<f32>,
 
     pub depth_bias: DepthBiasControl,
+
+    /// Conservative rasterization mode. Anything other than `Disabled` requires the
+    /// `ext_conservative_rasterization` device extension to be enabled.
+    pub conservative: ConservativeRasterization,
+
+    /// The algorithm used to rasterize lines. Anything other than `Default` requires the
+    /// `ext_line_rasterization` device extension to be enabled.
+    pub line_rasterization_mode: LineRasterizationMode,
+
+    /// The stipple pattern of lines. Requires the `ext_line_rasterization` device extension to
+    /// be enabled if not `Disabled`.
+    pub line_stipple: LineStippleControl,
+
+    /// The vertex of each primitive whose outputs are used for flat shaded inputs of the fragment
+    /// shader. Anything other than `FirstVertex` requires the `ext_provoking_vertex` device
+    /// extension to be enabled.
+    pub provoking_vertex_mode: ProvokingVertexMode,
 }
 
 impl Default for Rasterization {
@@ -50,16 +78,144 @@ impl Default for Rasterization {
     fn default() -> Rasterization {
         Rasterization {
             depth_clamp: false,
+            depth_clip: None,
             rasterizer_discard: false,
             polygon_mode: Default::default(),
             cull_mode: Default::default(),
             front_face: Default::default(),
             line_width: Some(1.0),
             depth_bias: DepthBiasControl::Disabled,
+            conservative: ConservativeRasterization::Disabled,
+            line_rasterization_mode: LineRasterizationMode::Default,
+            line_stipple: LineStippleControl::Disabled,
+            provoking_vertex_mode: ProvokingVertexMode::FirstVertex,
+        }
+    }
+}
+
+/// Conservative rasterization mode, as provided by the `VK_EXT_conservative_rasterization`
+/// extension.
+///
+/// Normally a pixel is covered by a primitive if its sample points lie inside of the primitive.
+/// Conservative rasterization instead looks at the whole area of the pixel.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ConservativeRasterization {
+    /// Regular rasterization.
+    Disabled,
+
+    /// A fragment is generated for every pixel that is even partially covered by the primitive.
+    ///
+    /// `extra_size` is an additional size, in pixels, by which the primitive is extended on top
+    /// of the `primitive_overestimation_size` of the device. It must be between 0.0 and the
+    /// `max_extra_primitive_overestimation_size` of the device.
+    Overestimate { extra_size: f32 },
+
+    /// A fragment is only generated for pixels that are fully covered by the primitive.
+    ///
+    /// Requires the `primitive_underestimation` property of the device.
+    Underestimate,
+}
+
+impl ConservativeRasterization {
+    #[inline]
+    pub(crate) fn mode(&self) -> vk::ConservativeRasterizationModeEXT {
+        match *self {
+            ConservativeRasterization::Disabled => vk::CONSERVATIVE_RASTERIZATION_MODE_DISABLED_EXT,
+            ConservativeRasterization::Overestimate { .. } => {
+                vk::CONSERVATIVE_RASTERIZATION_MODE_OVERESTIMATE_EXT
+            }
+            ConservativeRasterization::Underestimate => {
+                vk::CONSERVATIVE_RASTERIZATION_MODE_UNDERESTIMATE_EXT
+            }
         }
     }
 }
 
+impl Default for ConservativeRasterization {
+    #[inline]
+    fn default() -> ConservativeRasterization {
+        ConservativeRasterization::Disabled
+    }
+}
+
+/// The algorithm used to rasterize lines, as provided by the `VK_EXT_line_rasterization`
+/// extension.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LineRasterizationMode {
+    /// Rectangular lines if the `strict_lines` limit of the device is true, otherwise
+    /// parallelograms. This is the only mode available without the extension.
+    Default = vk::LINE_RASTERIZATION_MODE_DEFAULT_EXT,
+
+    /// Lines are rasterized as rectangles centered on the line. Requires the `rectangular_lines`
+    /// feature.
+    Rectangular = vk::LINE_RASTERIZATION_MODE_RECTANGULAR_EXT,
+
+    /// Lines are rasterized with the Bresenham algorithm, in the same way as in most CAD
+    /// software. Requires the `bresenham_lines` feature.
+    Bresenham = vk::LINE_RASTERIZATION_MODE_BRESENHAM_EXT,
+
+    /// Lines are rasterized as antialiased rectangles, the coverage of each fragment being
+    /// multiplied into its alpha. Requires the `smooth_lines` feature.
+    RectangularSmooth = vk::LINE_RASTERIZATION_MODE_RECTANGULAR_SMOOTH_EXT,
+}
+
+impl Default for LineRasterizationMode {
+    #[inline]
+    fn default() -> LineRasterizationMode {
+        LineRasterizationMode::Default
+    }
+}
+
+/// Controls the stipple pattern of lines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LineStippleControl {
+    Disabled,
+    Dynamic,
+    Static(LineStipple),
+}
+
+impl LineStippleControl {
+    #[inline]
+    pub fn is_dynamic(&self) -> bool {
+        matches!(*self, LineStippleControl::Dynamic)
+    }
+}
+
+/// The stipple pattern of lines.
+///
+/// The pattern is a mask of 16 bits, starting from the least significant bit. Each bit is used
+/// for `factor` consecutive pixels of a line, and a pixel is only drawn if its bit is 1. The
+/// pattern restarts at the beginning of each line strip.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LineStipple {
+    /// The number of pixels covered by each bit of the pattern. Must be between 1 and 256.
+    pub factor: u32,
+    /// The bit pattern.
+    pub pattern: u16,
+}
+
+/// The vertex of each primitive that provides the values of flat shaded fragment shader inputs,
+/// as provided by the `VK_EXT_provoking_vertex` extension.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ProvokingVertexMode {
+    /// The first vertex of the primitive. This is the Vulkan convention, and the only mode
+    /// available without the extension.
+    FirstVertex = vk::PROVOKING_VERTEX_MODE_FIRST_VERTEX_EXT,
+
+    /// The last vertex of the primitive, which is the default convention of OpenGL. Requires
+    /// the `provoking_vertex_last` feature.
+    LastVertex = vk::PROVOKING_VERTEX_MODE_LAST_VERTEX_EXT,
+}
+
+impl Default for ProvokingVertexMode {
+    #[inline]
+    fn default() -> ProvokingVertexMode {
+        ProvokingVertexMode::FirstVertex
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum DepthBiasControl {
     Disabled,
@@ -91,7 +247,7 @@ pub struct DepthBias {
 /// clockwise or counter-clockwise correspond to the front and the back of each triangle. Then
 /// `cull_mode` lets you specify whether front faces should be discarded, back faces should be
 /// discarded, or none, or both.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum CullMode {
     /// No culling.
@@ -112,7 +268,7 @@ impl Default for CullMode {
 }
 
 /// Specifies which triangle orientation corresponds to the front or the triangle.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum FrontFace {
     /// Triangles whose vertices are oriented counter-clockwise on the screen will be considered
@@ -0,0 +1,1337 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reflection of SPIR-V code at runtime.
+//!
+//! The `vulkano-shaders` crate generates the interface of shaders at compile time, which isn't
+//! possible when the shaders are only known at runtime, for example when they are loaded from the
+//! disk. Instead, the `reflect` function of this module parses SPIR-V code and returns a
+//! description of each of its entry points:
+//!
+//! - The descriptors and push constants, as a `RuntimePipelineDesc` that can be passed to
+//!   `ShaderModule::graphics_entry_point` or `ShaderModule::compute_entry_point`, or used to
+//!   create a pipeline layout.
+//! - The input and output interfaces, as `RuntimeShaderInterfaceDef`s that can be passed to
+//!   `ShaderModule::graphics_entry_point`.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::pipeline::reflect;
+//!
+//! # fn load_spirv() -> Vec<u8> { Vec::new() }
+//! let spirv = load_spirv();
+//!
+//! match reflect::reflect_bytes(&spirv) {
+//!     Ok(reflection) => {
+//!         for entry_point in reflection.entry_points() {
+//!             println!("{:?}: {:?}", entry_point.name(), entry_point.ty());
+//!         }
+//!     }
+//!     Err(err) => println!("invalid SPIR-V: {}", err),
+//! }
+//! ```
+
+use std::borrow::Cow;
+use std::cmp;
+use std::collections::HashSet;
+use std::error;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::fmt;
+
+use crate::descriptor::descriptor::DescriptorBufferDesc;
+use crate::descriptor::descriptor::DescriptorDesc;
+use crate::descriptor::descriptor::DescriptorDescTy;
+use crate::descriptor::descriptor::DescriptorImageDesc;
+use crate::descriptor::descriptor::DescriptorImageDescArray;
+use crate::descriptor::descriptor::DescriptorImageDescDimensions;
+use crate::descriptor::descriptor::ShaderStages;
+use crate::descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+use crate::descriptor::pipeline_layout::RuntimePipelineDesc;
+use crate::features::Features;
+use crate::format::Format;
+use crate::instance::SubgroupFeatures;
+use crate::pipeline::shader::GeometryShaderExecutionMode;
+use crate::pipeline::shader::GraphicsShaderType;
+use crate::pipeline::shader::RuntimeShaderInterfaceDef;
+use crate::pipeline::shader::ShaderInterfaceDefEntry;
+
+use self::spirv::*;
+
+pub(crate) mod spirv;
+
+/// Reflects the SPIR-V code given as words.
+pub fn reflect(words: &[u32]) -> Result<ShaderReflection, ReflectError> {
+    ShaderReflection::from_spirv(&Spirv::parse(words)?)
+}
+
+/// Reflects the SPIR-V code given as bytes. Both endiannesses are accepted.
+pub fn reflect_bytes(bytes: &[u8]) -> Result<ShaderReflection, ReflectError> {
+    ShaderReflection::from_spirv(&Spirv::parse_bytes(bytes)?)
+}
+
+/// Description of a SPIR-V module obtained by reflection.
+#[derive(Debug, Clone)]
+pub struct ShaderReflection {
+    version: (u8, u8),
+    entry_points: Vec<EntryPointReflection>,
+    specialization_constants: Vec<SpecializationConstantReflection>,
+    subgroup_features: SubgroupFeatures,
+    required_features: Features,
+}
+
+impl ShaderReflection {
+    fn from_spirv(spirv: &Spirv) -> Result<ShaderReflection, ReflectError> {
+        let entry_points = spirv
+            .entry_points
+            .iter()
+            .map(|entry_point| EntryPointReflection::from_spirv(spirv, entry_point))
+            .filter_map(|entry_point| entry_point.transpose())
+            .collect::<Result<_, _>>()?;
+
+        let mut specialization_constants = Vec::new();
+        for &id in spirv.spec_constants.iter() {
+            // Specialization constants without a `SpecId` can't be specialized.
+            let constant_id = match spirv.decoration(id, DECORATION_SPEC_ID) {
+                Some(params) => *params.first().ok_or(ReflectError::IncompleteInstruction)?,
+                None => continue,
+            };
+            let result_type = spirv.constants[&id].0;
+            let ty = match *spirv.ty(result_type)? {
+                Type::Bool => SpecializationConstantType::Bool,
+                Type::Int { width, signed } => SpecializationConstantType::Int { width, signed },
+                Type::Float { width } => SpecializationConstantType::Float { width },
+                _ => return Err(ReflectError::UnsupportedType { id: result_type }),
+            };
+
+            specialization_constants.push(SpecializationConstantReflection {
+                constant_id,
+                ty,
+                name: spirv.names.get(&id).cloned(),
+            });
+        }
+        specialization_constants.sort_by_key(|c| c.constant_id);
+
+        let mut subgroup_features = SubgroupFeatures::none();
+        let mut required_features = Features::none();
+        for &capability in spirv.capabilities.iter() {
+            match capability {
+                CAPABILITY_FLOAT16 => required_features.shader_float16 = true,
+                CAPABILITY_FLOAT64 => required_features.shader_float64 = true,
+                CAPABILITY_INT64 => required_features.shader_int64 = true,
+                CAPABILITY_INT16 => required_features.shader_int16 = true,
+                CAPABILITY_INT8 => required_features.shader_int8 = true,
+                CAPABILITY_STORAGE_BUFFER_16BIT_ACCESS => {
+                    required_features.storage_buffer_16bit = true
+                }
+                CAPABILITY_UNIFORM_AND_STORAGE_BUFFER_16BIT_ACCESS => {
+                    required_features.storage_uniform_16bit = true
+                }
+                CAPABILITY_STORAGE_PUSH_CONSTANT_16 => {
+                    required_features.storage_push_constant_16bit = true
+                }
+                CAPABILITY_STORAGE_INPUT_OUTPUT_16 => {
+                    required_features.storage_input_output_16bit = true
+                }
+                CAPABILITY_STORAGE_BUFFER_8BIT_ACCESS => {
+                    required_features.storage_buffer_8bit = true
+                }
+                CAPABILITY_UNIFORM_AND_STORAGE_BUFFER_8BIT_ACCESS => {
+                    required_features.storage_uniform_8bit = true
+                }
+                CAPABILITY_STORAGE_PUSH_CONSTANT_8 => {
+                    required_features.storage_push_constant_8bit = true
+                }
+                CAPABILITY_GROUP_NON_UNIFORM => subgroup_features.basic = true,
+                CAPABILITY_GROUP_NON_UNIFORM_VOTE => subgroup_features.vote = true,
+                CAPABILITY_GROUP_NON_UNIFORM_ARITHMETIC => subgroup_features.arithmetic = true,
+                CAPABILITY_GROUP_NON_UNIFORM_BALLOT => subgroup_features.ballot = true,
+                CAPABILITY_GROUP_NON_UNIFORM_SHUFFLE => subgroup_features.shuffle = true,
+                CAPABILITY_GROUP_NON_UNIFORM_SHUFFLE_RELATIVE => {
+                    subgroup_features.shuffle_relative = true
+                }
+                CAPABILITY_GROUP_NON_UNIFORM_CLUSTERED => subgroup_features.clustered = true,
+                CAPABILITY_GROUP_NON_UNIFORM_QUAD => subgroup_features.quad = true,
+                CAPABILITY_GROUP_NON_UNIFORM_PARTITIONED_NV => subgroup_features.partitioned = true,
+                _ => (),
+            }
+        }
+
+        Ok(ShaderReflection {
+            version: spirv.version,
+            entry_points,
+            specialization_constants,
+            subgroup_features,
+            required_features,
+        })
+    }
+
+    /// Returns the version of SPIR-V of the module, as `(major, minor)`.
+    #[inline]
+    pub fn spirv_version(&self) -> (u8, u8) {
+        self.version
+    }
+
+    /// Returns the entry points of the module.
+    ///
+    /// Entry points of execution models that aren't supported by vulkano, such as kernels, are
+    /// not included.
+    #[inline]
+    pub fn entry_points(&self) -> &[EntryPointReflection] {
+        &self.entry_points
+    }
+
+    /// Returns the entry point with the given name, if any.
+    ///
+    /// If several entry points of different stages have this name, the first one is returned.
+    /// Use `entry_point_for_stage` to choose between them.
+    #[inline]
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPointReflection> {
+        self.entry_points
+            .iter()
+            .find(|entry_point| entry_point.name.to_bytes() == name.as_bytes())
+    }
+
+    /// Returns the specialization constants of the module, sorted by constant ID.
+    ///
+    /// Specialization constants that don't have a constant ID can't be specialized, and are not
+    /// included.
+    #[inline]
+    pub fn specialization_constants(&self) -> &[SpecializationConstantReflection] {
+        &self.specialization_constants
+    }
+
+    /// Returns the kinds of subgroup operations that the module uses, according to the
+    /// capabilities that it declares.
+    #[inline]
+    pub fn subgroup_features(&self) -> SubgroupFeatures {
+        self.subgroup_features
+    }
+
+    /// Returns the device features that the module requires according to the capabilities that
+    /// it declares.
+    ///
+    /// Only the capabilities of the 8-bit and 16-bit storage features and of the arithmetic types
+    /// of non-default widths are taken into account.
+    #[inline]
+    pub fn required_features(&self) -> &Features {
+        &self.required_features
+    }
+
+    /// Returns the specialization constant with the given constant ID, if any.
+    #[inline]
+    pub fn specialization_constant(
+        &self,
+        constant_id: u32,
+    ) -> Option<&SpecializationConstantReflection> {
+        self.specialization_constants
+            .iter()
+            .find(|c| c.constant_id == constant_id)
+    }
+
+    /// Returns the entry point with the given name and stage, if any.
+    #[inline]
+    pub fn entry_point_for_stage(
+        &self,
+        name: &str,
+        stage: ShaderStage,
+    ) -> Option<&EntryPointReflection> {
+        self.entry_points.iter().find(|entry_point| {
+            entry_point.name.to_bytes() == name.as_bytes() && entry_point.ty.stage() == stage
+        })
+    }
+}
+
+/// A specialization constant of a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecializationConstantReflection {
+    /// The constant ID, given by the `SpecId` decoration or by `layout(constant_id = N)`.
+    pub constant_id: u32,
+    /// The type of the constant.
+    pub ty: SpecializationConstantType,
+    /// The name of the constant, if the module contains debug information.
+    pub name: Option<String>,
+}
+
+/// Type of a specialization constant.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SpecializationConstantType {
+    /// A boolean, which is provided as a 32-bit `0` or `1`.
+    Bool,
+    /// An integer of `width` bits.
+    Int { width: u32, signed: bool },
+    /// A floating-point number of `width` bits.
+    Float { width: u32 },
+}
+
+impl SpecializationConstantType {
+    /// Returns the size in bytes of the value of the constant.
+    #[inline]
+    pub fn size(&self) -> usize {
+        match *self {
+            SpecializationConstantType::Bool => 4,
+            SpecializationConstantType::Int { width, .. } => width as usize / 8,
+            SpecializationConstantType::Float { width } => width as usize / 8,
+        }
+    }
+}
+
+/// A single shader stage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ShaderStage {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    Compute,
+}
+
+/// Type of an entry point.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EntryPointType {
+    /// The entry point of a stage of a graphics pipeline.
+    Graphics(GraphicsShaderType),
+    /// The entry point of a compute shader.
+    Compute,
+}
+
+impl EntryPointType {
+    /// Returns the shader stage of the entry point.
+    #[inline]
+    pub fn stage(&self) -> ShaderStage {
+        match *self {
+            EntryPointType::Graphics(GraphicsShaderType::Vertex) => ShaderStage::Vertex,
+            EntryPointType::Graphics(GraphicsShaderType::TessellationControl) => {
+                ShaderStage::TessellationControl
+            }
+            EntryPointType::Graphics(GraphicsShaderType::TessellationEvaluation) => {
+                ShaderStage::TessellationEvaluation
+            }
+            EntryPointType::Graphics(GraphicsShaderType::Geometry(_)) => ShaderStage::Geometry,
+            EntryPointType::Graphics(GraphicsShaderType::Fragment) => ShaderStage::Fragment,
+            EntryPointType::Compute => ShaderStage::Compute,
+        }
+    }
+
+    /// Returns the shader stage of the entry point, as a `ShaderStages`.
+    #[inline]
+    pub fn stages(&self) -> ShaderStages {
+        match *self {
+            EntryPointType::Graphics(GraphicsShaderType::Vertex) => ShaderStages {
+                vertex: true,
+                ..ShaderStages::none()
+            },
+            EntryPointType::Graphics(GraphicsShaderType::TessellationControl) => ShaderStages {
+                tessellation_control: true,
+                ..ShaderStages::none()
+            },
+            EntryPointType::Graphics(GraphicsShaderType::TessellationEvaluation) => ShaderStages {
+                tessellation_evaluation: true,
+                ..ShaderStages::none()
+            },
+            EntryPointType::Graphics(GraphicsShaderType::Geometry(_)) => ShaderStages {
+                geometry: true,
+                ..ShaderStages::none()
+            },
+            EntryPointType::Graphics(GraphicsShaderType::Fragment) => ShaderStages {
+                fragment: true,
+                ..ShaderStages::none()
+            },
+            EntryPointType::Compute => ShaderStages {
+                compute: true,
+                ..ShaderStages::none()
+            },
+        }
+    }
+}
+
+/// A descriptor used by an entry point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescriptorReflection {
+    /// Index of the descriptor set.
+    pub set: u32,
+    /// Binding of the descriptor within its set.
+    pub binding: u32,
+    /// Description of the descriptor. Its stages are the stage of the entry point.
+    pub desc: DescriptorDesc,
+    /// Name of the variable of the descriptor, or `None` if the name is unknown.
+    pub name: Option<String>,
+}
+
+/// Description of an entry point obtained by reflection.
+#[derive(Debug, Clone)]
+pub struct EntryPointReflection {
+    name: CString,
+    ty: EntryPointType,
+    descriptors: Vec<DescriptorReflection>,
+    push_constants: Option<PipelineLayoutDescPcRange>,
+    input: Vec<ShaderInterfaceDefEntry>,
+    output: Vec<ShaderInterfaceDefEntry>,
+}
+
+impl EntryPointReflection {
+    // Returns `None` if the execution model isn't supported.
+    fn from_spirv(
+        spirv: &Spirv,
+        entry_point: &EntryPoint,
+    ) -> Result<Option<EntryPointReflection>, ReflectError> {
+        let ty = match entry_point.execution_model {
+            0 => EntryPointType::Graphics(GraphicsShaderType::Vertex),
+            1 => EntryPointType::Graphics(GraphicsShaderType::TessellationControl),
+            2 => EntryPointType::Graphics(GraphicsShaderType::TessellationEvaluation),
+            3 => {
+                let modes = spirv
+                    .execution_modes
+                    .get(&entry_point.function)
+                    .map(|modes| &modes[..])
+                    .unwrap_or(&[]);
+                let mode = modes
+                    .iter()
+                    .filter_map(|&(mode, _)| match mode {
+                        19 => Some(GeometryShaderExecutionMode::Points),
+                        20 => Some(GeometryShaderExecutionMode::Lines),
+                        21 => Some(GeometryShaderExecutionMode::LinesWithAdjacency),
+                        22 => Some(GeometryShaderExecutionMode::Triangles),
+                        23 => Some(GeometryShaderExecutionMode::TrianglesWithAdjacency),
+                        _ => None,
+                    })
+                    .next()
+                    .ok_or(ReflectError::MissingGeometryInputMode)?;
+                EntryPointType::Graphics(GraphicsShaderType::Geometry(mode))
+            }
+            4 => EntryPointType::Graphics(GraphicsShaderType::Fragment),
+            5 => EntryPointType::Compute,
+            _ => return Ok(None),
+        };
+
+        let stages = ty.stages();
+        let variables = used_variables(spirv, entry_point);
+
+        let mut descriptors = Vec::new();
+        let mut push_constants: Option<(u32, u32)> = None;
+        let mut input = Vec::new();
+        let mut output = Vec::new();
+
+        // Variables are sorted so that the order of the output doesn't depend on the hash map.
+        let mut sorted_variables: Vec<u32> = variables.into_iter().collect();
+        sorted_variables.sort();
+
+        for &variable in sorted_variables.iter() {
+            let (pointer_ty, storage_class) = match spirv.variables.get(&variable) {
+                Some(&v) => v,
+                None => continue,
+            };
+            let pointee = match *spirv.ty(pointer_ty)? {
+                Type::Pointer { pointee, .. } => pointee,
+                _ => return Err(ReflectError::UnsupportedType { id: pointer_ty }),
+            };
+            let name = spirv.names.get(&variable).cloned();
+
+            match storage_class {
+                STORAGE_CLASS_UNIFORM_CONSTANT
+                | STORAGE_CLASS_UNIFORM
+                | STORAGE_CLASS_STORAGE_BUFFER => {
+                    let set = match spirv.decoration(variable, DECORATION_DESCRIPTOR_SET) {
+                        Some(params) if !params.is_empty() => params[0],
+                        _ => {
+                            return Err(ReflectError::MissingDecoration {
+                                id: variable,
+                                decoration: "DescriptorSet",
+                            })
+                        }
+                    };
+                    let binding = match spirv.decoration(variable, DECORATION_BINDING) {
+                        Some(params) if !params.is_empty() => params[0],
+                        _ => {
+                            return Err(ReflectError::MissingDecoration {
+                                id: variable,
+                                decoration: "Binding",
+                            })
+                        }
+                    };
+
+                    let (ty, readonly, array_count) =
+                        descriptor_infos(spirv, pointee, storage_class, false)?;
+                    let nonwritable = spirv
+                        .decoration(variable, DECORATION_NON_WRITABLE)
+                        .is_some();
+
+                    descriptors.push(DescriptorReflection {
+                        set,
+                        binding,
+                        desc: DescriptorDesc {
+                            ty,
+                            array_count,
+                            stages,
+                            readonly: readonly || nonwritable,
+                        },
+                        name,
+                    });
+                }
+                STORAGE_CLASS_PUSH_CONSTANT => {
+                    let (offset, end) = push_constants_range(spirv, pointee)?;
+                    push_constants = Some(match push_constants {
+                        Some((o, e)) => (cmp::min(o, offset), cmp::max(e, end)),
+                        None => (offset, end),
+                    });
+                }
+                STORAGE_CLASS_INPUT | STORAGE_CLASS_OUTPUT => {
+                    if is_builtin(spirv, variable, pointee) {
+                        continue;
+                    }
+
+                    let location = match spirv.decoration(variable, DECORATION_LOCATION) {
+                        Some(params) if !params.is_empty() => params[0],
+                        _ => {
+                            return Err(ReflectError::MissingDecoration {
+                                id: variable,
+                                decoration: "Location",
+                            })
+                        }
+                    };
+
+                    // The inputs of tessellation and geometry shaders, as well as the outputs of
+                    // tessellation control shaders, are arrays with one element per vertex.
+                    let per_vertex = matches!(
+                        (ty, storage_class),
+                        (
+                            EntryPointType::Graphics(GraphicsShaderType::TessellationControl),
+                            _
+                        ) | (
+                            EntryPointType::Graphics(GraphicsShaderType::TessellationEvaluation),
+                            STORAGE_CLASS_INPUT,
+                        ) | (
+                            EntryPointType::Graphics(GraphicsShaderType::Geometry(_)),
+                            STORAGE_CLASS_INPUT,
+                        )
+                    );
+
+                    let (format, locations) = interface_format(spirv, pointee, per_vertex)?;
+                    let entry = ShaderInterfaceDefEntry {
+                        location: location..location + locations,
+                        format,
+                        name: name.map(Cow::Owned),
+                    };
+
+                    if storage_class == STORAGE_CLASS_INPUT {
+                        input.push(entry);
+                    } else {
+                        output.push(entry);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        descriptors.sort_by_key(|d| (d.set, d.binding));
+        input.sort_by_key(|e| e.location.start);
+        output.sort_by_key(|e| e.location.start);
+
+        for interface in [&input, &output].iter() {
+            for (num, a) in interface.iter().enumerate() {
+                for b in interface.iter().skip(num + 1) {
+                    if a.location.end > b.location.start {
+                        return Err(ReflectError::OverlappingLocations {
+                            location: b.location.start,
+                        });
+                    }
+                }
+            }
+        }
+
+        let name =
+            CString::new(entry_point.name.as_bytes()).map_err(|_| ReflectError::InvalidString)?;
+
+        Ok(Some(EntryPointReflection {
+            name,
+            ty,
+            descriptors,
+            push_constants: push_constants.map(|(offset, end)| PipelineLayoutDescPcRange {
+                offset: offset as usize,
+                size: (end - offset) as usize,
+                stages,
+            }),
+            input,
+            output,
+        }))
+    }
+
+    /// Returns the name of the entry point.
+    #[inline]
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Returns the type of the entry point.
+    #[inline]
+    pub fn ty(&self) -> EntryPointType {
+        self.ty
+    }
+
+    /// Returns the descriptors used by the entry point, sorted by set and binding.
+    #[inline]
+    pub fn descriptors(&self) -> &[DescriptorReflection] {
+        &self.descriptors
+    }
+
+    /// Returns the range of push constants used by the entry point, if any.
+    #[inline]
+    pub fn push_constants(&self) -> Option<PipelineLayoutDescPcRange> {
+        self.push_constants
+    }
+
+    /// Builds the description of the pipeline layout used by the entry point.
+    pub fn pipeline_layout_desc(&self) -> RuntimePipelineDesc {
+        let num_sets = self
+            .descriptors
+            .iter()
+            .map(|d| d.set as usize + 1)
+            .max()
+            .unwrap_or(0);
+
+        let sets = (0..num_sets).map(|set| {
+            let descriptors: Vec<_> = self
+                .descriptors
+                .iter()
+                .filter(|d| d.set as usize == set)
+                .collect();
+            let num_bindings = descriptors
+                .iter()
+                .map(|d| d.binding as usize + 1)
+                .max()
+                .unwrap_or(0);
+
+            (0..num_bindings)
+                .map(|binding| {
+                    descriptors
+                        .iter()
+                        .find(|d| d.binding as usize == binding)
+                        .map(|d| d.desc.clone())
+                })
+                .collect::<Vec<_>>()
+        });
+
+        // There is a single push constants range, so they can't conflict.
+        RuntimePipelineDesc::new(sets, self.push_constants).unwrap()
+    }
+
+    /// Returns the input interface of the entry point.
+    ///
+    /// Built-in variables are not included. The elements are sorted by location.
+    #[inline]
+    pub fn input(&self) -> RuntimeShaderInterfaceDef {
+        unsafe { RuntimeShaderInterfaceDef::new(self.input.clone()) }
+    }
+
+    /// Returns the output interface of the entry point.
+    ///
+    /// Built-in variables are not included. The elements are sorted by location.
+    #[inline]
+    pub fn output(&self) -> RuntimeShaderInterfaceDef {
+        unsafe { RuntimeShaderInterfaceDef::new(self.output.clone()) }
+    }
+}
+
+/// Error that can happen when reflecting SPIR-V code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReflectError {
+    /// The code is too short to contain a SPIR-V header.
+    MissingHeader,
+    /// The code doesn't start with the SPIR-V magic number.
+    WrongHeader,
+    /// An instruction is truncated.
+    IncompleteInstruction,
+    /// A string literal is not valid UTF-8, or contains a nul character.
+    InvalidString,
+    /// An instruction refers to an id that isn't declared.
+    UnknownId {
+        /// The id.
+        id: u32,
+    },
+    /// A type can't be used where it is used, or is not supported by vulkano.
+    UnsupportedType {
+        /// The id of the type.
+        id: u32,
+    },
+    /// A variable is missing a decoration that is required.
+    MissingDecoration {
+        /// The id of the variable.
+        id: u32,
+        /// Name of the missing decoration.
+        decoration: &'static str,
+    },
+    /// A geometry shader entry point doesn't declare the type of its input primitives.
+    MissingGeometryInputMode,
+    /// Two elements of an interface occupy the same location.
+    OverlappingLocations {
+        /// The location.
+        location: u32,
+    },
+}
+
+impl error::Error for ReflectError {}
+
+impl fmt::Display for ReflectError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                ReflectError::MissingHeader => {
+                    "the code is too short to contain a SPIR-V header"
+                }
+                ReflectError::WrongHeader => {
+                    "the code doesn't start with the SPIR-V magic number"
+                }
+                ReflectError::IncompleteInstruction => "an instruction is truncated",
+                ReflectError::InvalidString => "a string literal is invalid",
+                ReflectError::UnknownId { .. } => {
+                    "an instruction refers to an id that isn't declared"
+                }
+                ReflectError::UnsupportedType { .. } => {
+                    "a type can't be used where it is used, or is not supported"
+                }
+                ReflectError::MissingDecoration { .. } => {
+                    "a variable is missing a decoration that is required"
+                }
+                ReflectError::MissingGeometryInputMode => {
+                    "a geometry shader doesn't declare the type of its input primitives"
+                }
+                ReflectError::OverlappingLocations { .. } => {
+                    "two elements of an interface occupy the same location"
+                }
+            }
+        )
+    }
+}
+
+// Finds the global variables that are used by an entry point.
+//
+// Starting with SPIR-V 1.4, all the global variables used by an entry point must be listed in its
+// interface. Before that, only the input and output variables are, and the functions called by the
+// entry point must be inspected.
+fn used_variables(spirv: &Spirv, entry_point: &EntryPoint) -> HashSet<u32> {
+    let mut found: HashSet<u32> = entry_point.interface.iter().cloned().collect();
+    let mut inspected = HashSet::new();
+    let mut to_inspect = vec![entry_point.function];
+
+    while let Some(function) = to_inspect.pop() {
+        if !inspected.insert(function) {
+            continue;
+        }
+
+        let body = spirv
+            .instructions
+            .iter()
+            .skip_while(|i| !(i.opcode == OP_FUNCTION && i.operands.get(1) == Some(&function)))
+            .skip(1)
+            .take_while(|i| i.opcode != OP_FUNCTION_END);
+
+        for instruction in body {
+            let ops = &instruction.operands;
+            let pointers: &[u32] = match instruction.opcode {
+                OP_LOAD
+                | OP_ACCESS_CHAIN
+                | OP_IN_BOUNDS_ACCESS_CHAIN
+                | OP_PTR_ACCESS_CHAIN
+                | OP_ARRAY_LENGTH
+                | OP_IMAGE_TEXEL_POINTER
+                | OP_COPY_OBJECT
+                | OP_ATOMIC_FLAG_TEST_AND_SET => ops.get(2..3).unwrap_or(&[]),
+                OP_STORE | OP_ATOMIC_STORE | OP_ATOMIC_FLAG_CLEAR => ops.get(0..1).unwrap_or(&[]),
+                OP_COPY_MEMORY | OP_COPY_MEMORY_SIZED => ops.get(0..2).unwrap_or(&[]),
+                op if (OP_ATOMIC_LOAD..=OP_ATOMIC_XOR).contains(&op) => {
+                    ops.get(2..3).unwrap_or(&[])
+                }
+                OP_FUNCTION_CALL => {
+                    if let Some(&callee) = ops.get(2) {
+                        to_inspect.push(callee);
+                    }
+                    ops.get(3..).unwrap_or(&[])
+                }
+                // We don't know which extended instructions take pointers, so every operand is
+                // considered as one.
+                OP_EXT_INST => ops.get(4..).unwrap_or(&[]),
+                _ => &[],
+            };
+
+            found.extend(
+                pointers
+                    .iter()
+                    .filter(|id| spirv.variables.contains_key(id)),
+            );
+        }
+    }
+
+    found
+}
+
+// Returns true if the variable, or one of the members of its type, is a built-in.
+fn is_builtin(spirv: &Spirv, variable: u32, pointee: u32) -> bool {
+    if spirv.decoration(variable, DECORATION_BUILT_IN).is_some() {
+        return true;
+    }
+
+    let mut ty = pointee;
+    loop {
+        match spirv.types.get(&ty) {
+            Some(&Type::Array { element, .. }) | Some(&Type::RuntimeArray { element }) => {
+                ty = element;
+            }
+            Some(Type::Struct { members }) => {
+                return (0..members.len() as u32).any(|m| {
+                    spirv
+                        .member_decoration(ty, m, DECORATION_BUILT_IN)
+                        .is_some()
+                });
+            }
+            _ => return false,
+        }
+    }
+}
+
+// Returns the descriptor type, whether it is read-only, and the number of array elements of a
+// descriptor whose variable points to `ty`.
+//
+// See also section 14.5.2 of the Vulkan specs: Descriptor Set Interface
+fn descriptor_infos(
+    spirv: &Spirv,
+    ty: u32,
+    storage_class: u32,
+    combined_image_sampler: bool,
+) -> Result<(DescriptorDescTy, bool, u32), ReflectError> {
+    match *spirv.ty(ty)? {
+        Type::Struct { ref members } => {
+            let block = spirv.decoration(ty, DECORATION_BLOCK).is_some();
+            let buffer_block = spirv.decoration(ty, DECORATION_BUFFER_BLOCK).is_some();
+            if !(block ^ buffer_block) {
+                return Err(ReflectError::UnsupportedType { id: ty });
+            }
+
+            let storage = buffer_block || storage_class == STORAGE_CLASS_STORAGE_BUFFER;
+            let nonwritable = (0..members.len() as u32).all(|m| {
+                spirv
+                    .member_decoration(ty, m, DECORATION_NON_WRITABLE)
+                    .is_some()
+            });
+
+            let desc = DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: None,
+                storage,
+            });
+
+            // Uniform buffers are never writable.
+            Ok((desc, !storage || nonwritable, 1))
+        }
+        Type::Image {
+            dim,
+            arrayed,
+            ms,
+            sampled,
+        } => {
+            let array_layers = if arrayed {
+                DescriptorImageDescArray::Arrayed { max_layers: None }
+            } else {
+                DescriptorImageDescArray::NonArrayed
+            };
+
+            // The `Sampled` operand is 1 for sampled images and 2 for storage images.
+            let storage = match sampled {
+                1 => false,
+                2 => true,
+                _ => return Err(ReflectError::UnsupportedType { id: ty }),
+            };
+
+            let dimensions = match dim {
+                0 => DescriptorImageDescDimensions::OneDimensional,
+                1 => DescriptorImageDescDimensions::TwoDimensional,
+                2 => DescriptorImageDescDimensions::ThreeDimensional,
+                3 => DescriptorImageDescDimensions::Cube,
+                // Buffer
+                5 if !combined_image_sampler => {
+                    let desc = DescriptorDescTy::TexelBuffer {
+                        storage,
+                        format: None,
+                    };
+                    return Ok((desc, !storage, 1));
+                }
+                // SubpassData
+                6 if !combined_image_sampler && storage => {
+                    let desc = DescriptorDescTy::InputAttachment {
+                        multisampled: ms,
+                        array_layers,
+                    };
+                    return Ok((desc, true, 1));
+                }
+                _ => return Err(ReflectError::UnsupportedType { id: ty }),
+            };
+
+            let desc = DescriptorImageDesc {
+                sampled: !storage,
+                dimensions,
+                format: None,
+                multisampled: ms,
+                array_layers,
+            };
+
+            if combined_image_sampler {
+                Ok((DescriptorDescTy::CombinedImageSampler(desc), true, 1))
+            } else {
+                // Sampled images are never writable.
+                Ok((DescriptorDescTy::Image(desc), !storage, 1))
+            }
+        }
+        Type::SampledImage { image } => descriptor_infos(spirv, image, storage_class, true),
+        Type::Sampler => Ok((DescriptorDescTy::Sampler, true, 1)),
+        Type::Array { element, length } => {
+            let (desc, readonly, count) =
+                descriptor_infos(spirv, element, storage_class, combined_image_sampler)?;
+            if count != 1 {
+                return Err(ReflectError::UnsupportedType { id: ty });
+            }
+            let length = spirv.constant_u64(length)? as u32;
+            Ok((desc, readonly, length))
+        }
+        _ => Err(ReflectError::UnsupportedType { id: ty }),
+    }
+}
+
+// Returns the offset of the first byte and the offset after the last byte of a push constants
+// block.
+fn push_constants_range(spirv: &Spirv, ty: u32) -> Result<(u32, u32), ReflectError> {
+    let members = match *spirv.ty(ty)? {
+        Type::Struct { ref members } => members,
+        _ => return Err(ReflectError::UnsupportedType { id: ty }),
+    };
+
+    let mut start = u32::MAX;
+    let mut end = 0;
+    for (num, &member) in members.iter().enumerate() {
+        let offset = match spirv.member_decoration(ty, num as u32, DECORATION_OFFSET) {
+            Some(params) if !params.is_empty() => params[0],
+            _ => {
+                return Err(ReflectError::MissingDecoration {
+                    id: ty,
+                    decoration: "Offset",
+                })
+            }
+        };
+        let matrix_stride = spirv
+            .member_decoration(ty, num as u32, DECORATION_MATRIX_STRIDE)
+            .and_then(|params| params.first().cloned());
+        let size = type_size(spirv, member, matrix_stride)?;
+
+        start = cmp::min(start, offset);
+        end = cmp::max(end, offset + size);
+    }
+
+    if start > end {
+        start = end;
+    }
+
+    Ok((start, end))
+}
+
+// Returns the size in bytes of a type that is part of a block with an explicit layout.
+fn type_size(spirv: &Spirv, ty: u32, matrix_stride: Option<u32>) -> Result<u32, ReflectError> {
+    Ok(match *spirv.ty(ty)? {
+        Type::Bool => 4,
+        Type::Int { width, .. } | Type::Float { width } => width / 8,
+        Type::Vector { component, count } => type_size(spirv, component, None)? * count,
+        Type::Matrix { column, count } => match matrix_stride {
+            Some(stride) => stride * count,
+            None => type_size(spirv, column, None)? * count,
+        },
+        Type::Array { element, length } => {
+            let length = spirv.constant_u64(length)? as u32;
+            let stride = match spirv.decoration(ty, DECORATION_ARRAY_STRIDE) {
+                Some(params) if !params.is_empty() => params[0],
+                _ => type_size(spirv, element, matrix_stride)?,
+            };
+            stride * length
+        }
+        Type::Struct { .. } => push_constants_range(spirv, ty)?.1,
+        _ => return Err(ReflectError::UnsupportedType { id: ty }),
+    })
+}
+
+// Returns the format and the number of locations occupied by an input or output variable.
+fn interface_format(
+    spirv: &Spirv,
+    ty: u32,
+    per_vertex: bool,
+) -> Result<(Format, u32), ReflectError> {
+    let ty = if per_vertex {
+        match *spirv.ty(ty)? {
+            Type::Array { element, .. } | Type::RuntimeArray { element } => element,
+            _ => return Err(ReflectError::UnsupportedType { id: ty }),
+        }
+    } else {
+        ty
+    };
+
+    match *spirv.ty(ty)? {
+        Type::Int { .. } | Type::Float { .. } => Ok((scalar_format(spirv, ty, 1)?, 1)),
+        Type::Vector { component, count } => {
+            let format = scalar_format(spirv, component, count)?;
+            // 64-bit vectors with more than two components occupy two locations, but their
+            // format would be larger than 128 bits.
+            Ok((format, 1))
+        }
+        Type::Matrix { column, count } => {
+            let (format, locations) = interface_format(spirv, column, false)?;
+            Ok((format, locations * count))
+        }
+        Type::Array { element, length } => {
+            let (format, locations) = interface_format(spirv, element, false)?;
+            let length = spirv.constant_u64(length)? as u32;
+            Ok((format, locations * length))
+        }
+        _ => Err(ReflectError::UnsupportedType { id: ty }),
+    }
+}
+
+// Returns the format of a vector of `count` components whose type is the scalar `ty`.
+fn scalar_format(spirv: &Spirv, ty: u32, count: u32) -> Result<Format, ReflectError> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Kind {
+        Sint,
+        Uint,
+        Sfloat,
+    }
+
+    let (kind, width) = match *spirv.ty(ty)? {
+        Type::Int {
+            width,
+            signed: true,
+        } => (Kind::Sint, width),
+        Type::Int {
+            width,
+            signed: false,
+        } => (Kind::Uint, width),
+        Type::Float { width } => (Kind::Sfloat, width),
+        _ => return Err(ReflectError::UnsupportedType { id: ty }),
+    };
+
+    let format = match (kind, width, count) {
+        (Kind::Sint, 8, 1) => Format::R8Sint,
+        (Kind::Sint, 8, 2) => Format::R8G8Sint,
+        (Kind::Sint, 8, 3) => Format::R8G8B8Sint,
+        (Kind::Sint, 8, 4) => Format::R8G8B8A8Sint,
+        (Kind::Uint, 8, 1) => Format::R8Uint,
+        (Kind::Uint, 8, 2) => Format::R8G8Uint,
+        (Kind::Uint, 8, 3) => Format::R8G8B8Uint,
+        (Kind::Uint, 8, 4) => Format::R8G8B8A8Uint,
+        (Kind::Sint, 16, 1) => Format::R16Sint,
+        (Kind::Sint, 16, 2) => Format::R16G16Sint,
+        (Kind::Sint, 16, 3) => Format::R16G16B16Sint,
+        (Kind::Sint, 16, 4) => Format::R16G16B16A16Sint,
+        (Kind::Uint, 16, 1) => Format::R16Uint,
+        (Kind::Uint, 16, 2) => Format::R16G16Uint,
+        (Kind::Uint, 16, 3) => Format::R16G16B16Uint,
+        (Kind::Uint, 16, 4) => Format::R16G16B16A16Uint,
+        (Kind::Sfloat, 16, 1) => Format::R16Sfloat,
+        (Kind::Sfloat, 16, 2) => Format::R16G16Sfloat,
+        (Kind::Sfloat, 16, 3) => Format::R16G16B16Sfloat,
+        (Kind::Sfloat, 16, 4) => Format::R16G16B16A16Sfloat,
+        (Kind::Sint, 32, 1) => Format::R32Sint,
+        (Kind::Sint, 32, 2) => Format::R32G32Sint,
+        (Kind::Sint, 32, 3) => Format::R32G32B32Sint,
+        (Kind::Sint, 32, 4) => Format::R32G32B32A32Sint,
+        (Kind::Uint, 32, 1) => Format::R32Uint,
+        (Kind::Uint, 32, 2) => Format::R32G32Uint,
+        (Kind::Uint, 32, 3) => Format::R32G32B32Uint,
+        (Kind::Uint, 32, 4) => Format::R32G32B32A32Uint,
+        (Kind::Sfloat, 32, 1) => Format::R32Sfloat,
+        (Kind::Sfloat, 32, 2) => Format::R32G32Sfloat,
+        (Kind::Sfloat, 32, 3) => Format::R32G32B32Sfloat,
+        (Kind::Sfloat, 32, 4) => Format::R32G32B32A32Sfloat,
+        (Kind::Sint, 64, 1) => Format::R64Sint,
+        (Kind::Sint, 64, 2) => Format::R64G64Sint,
+        (Kind::Uint, 64, 1) => Format::R64Uint,
+        (Kind::Uint, 64, 2) => Format::R64G64Uint,
+        (Kind::Sfloat, 64, 1) => Format::R64Sfloat,
+        (Kind::Sfloat, 64, 2) => Format::R64G64Sfloat,
+        _ => return Err(ReflectError::UnsupportedType { id: ty }),
+    };
+
+    Ok(format)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::descriptor::descriptor::DescriptorDescTy;
+    use crate::descriptor::pipeline_layout::PipelineLayoutDesc;
+    use crate::features::Features;
+    use crate::format::Format;
+    use crate::instance::SubgroupFeatures;
+    use crate::pipeline::reflect;
+    use crate::pipeline::reflect::EntryPointType;
+    use crate::pipeline::reflect::ReflectError;
+    use crate::pipeline::reflect::ShaderStage;
+    use crate::pipeline::reflect::SpecializationConstantReflection;
+    use crate::pipeline::reflect::SpecializationConstantType;
+    use crate::pipeline::shader::GraphicsShaderType;
+    use crate::pipeline::shader::ShaderInterfaceDef;
+
+    // Appends an instruction to `words`.
+    fn inst(words: &mut Vec<u32>, opcode: u32, operands: &[u32]) {
+        words.push(((operands.len() as u32 + 1) << 16) | opcode);
+        words.extend_from_slice(operands);
+    }
+
+    // Encodes a nul-terminated string literal.
+    fn string(s: &str) -> Vec<u32> {
+        let mut bytes = s.as_bytes().to_vec();
+        bytes.push(0);
+        while !bytes.len().is_multiple_of(4) {
+            bytes.push(0);
+        }
+        bytes
+            .chunks(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect()
+    }
+
+    // A SPIR-V 1.0 vertex shader equivalent to:
+    //
+    // layout(location = 0) in vec4 position;
+    // layout(location = 1) out vec2 uv;
+    // layout(set = 0, binding = 1) uniform Data { vec4 color; } data;
+    // layout(push_constant) uniform Pc { vec4 a; vec4 b; } pc;  // `b` at offset 16
+    // layout(constant_id = 7) const float scale = 1.0;
+    fn vertex_shader() -> Vec<u32> {
+        let mut w = vec![0x07230203, 0x00010000, 0, 100, 0];
+        inst(&mut w, 17, &[1]); // OpCapability Shader
+        inst(&mut w, 14, &[0, 1]); // OpMemoryModel Logical GLSL450
+        let mut ep = vec![0, 1];
+        ep.extend(string("main"));
+        ep.extend(&[10, 11]);
+        inst(&mut w, 15, &ep); // OpEntryPoint Vertex %1 "main" %10 %11
+        let mut name = vec![10];
+        name.extend(string("position"));
+        inst(&mut w, 5, &name);
+        inst(&mut w, 71, &[10, 30, 0]); // position Location 0
+        inst(&mut w, 71, &[11, 30, 1]); // uv Location 1
+        inst(&mut w, 71, &[20, 2]); // Data Block
+        inst(&mut w, 72, &[20, 0, 35, 0]); // Data.color Offset 0
+        inst(&mut w, 71, &[12, 34, 0]); // data DescriptorSet 0
+        inst(&mut w, 71, &[12, 33, 1]); // data Binding 1
+        inst(&mut w, 71, &[21, 2]); // Pc Block
+        inst(&mut w, 72, &[21, 0, 35, 0]); // Pc.a Offset 0
+        inst(&mut w, 72, &[21, 1, 35, 16]); // Pc.b Offset 16
+        inst(&mut w, 71, &[40, 1, 7]); // scale SpecId 7
+        inst(&mut w, 19, &[2]); // %2 = OpTypeVoid
+        inst(&mut w, 33, &[3, 2]); // %3 = OpTypeFunction %2
+        inst(&mut w, 22, &[4, 32]); // %4 = OpTypeFloat 32
+        inst(&mut w, 23, &[5, 4, 4]); // %5 = vec4
+        inst(&mut w, 23, &[6, 4, 2]); // %6 = vec2
+        inst(&mut w, 50, &[4, 40, 0x3f800000]); // %40 = OpSpecConstant float 1.0
+        inst(&mut w, 32, &[7, 1, 5]); // %7 = Input vec4*
+        inst(&mut w, 32, &[8, 3, 6]); // %8 = Output vec2*
+        inst(&mut w, 30, &[20, 5]); // %20 = struct Data
+        inst(&mut w, 30, &[21, 5, 5]); // %21 = struct Pc
+        inst(&mut w, 32, &[22, 2, 20]); // %22 = Uniform Data*
+        inst(&mut w, 32, &[23, 9, 21]); // %23 = PushConstant Pc*
+        inst(&mut w, 59, &[7, 10, 1]); // %10 = position
+        inst(&mut w, 59, &[8, 11, 3]); // %11 = uv
+        inst(&mut w, 59, &[22, 12, 2]); // %12 = data
+        inst(&mut w, 59, &[23, 13, 9]); // %13 = pc
+        inst(&mut w, 54, &[2, 1, 0, 3]); // %1 = OpFunction
+        inst(&mut w, 248, &[30]); // OpLabel
+        inst(&mut w, 61, &[5, 31, 10]); // OpLoad position
+        inst(&mut w, 65, &[22, 32, 12]); // OpAccessChain data
+        inst(&mut w, 65, &[23, 33, 13]); // OpAccessChain pc
+        inst(&mut w, 253, &[]); // OpReturn
+        inst(&mut w, 56, &[]); // OpFunctionEnd
+        w
+    }
+
+    #[test]
+    fn reflect_vertex_shader() {
+        let reflection = reflect::reflect(&vertex_shader()).unwrap();
+        assert_eq!(reflection.spirv_version(), (1, 0));
+
+        let entry_point = reflection.entry_point("main").unwrap();
+        assert_eq!(
+            entry_point.ty(),
+            EntryPointType::Graphics(GraphicsShaderType::Vertex)
+        );
+
+        let input: Vec<_> = entry_point.input().elements().collect();
+        assert_eq!(input.len(), 1);
+        assert_eq!(input[0].location, 0..1);
+        assert_eq!(input[0].format, Format::R32G32B32A32Sfloat);
+        assert_eq!(input[0].name.as_deref(), Some("position"));
+
+        let output: Vec<_> = entry_point.output().elements().collect();
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].location, 1..2);
+        assert_eq!(output[0].format, Format::R32G32Sfloat);
+
+        let descriptors = entry_point.descriptors();
+        assert_eq!(descriptors.len(), 1);
+        assert_eq!((descriptors[0].set, descriptors[0].binding), (0, 1));
+        match descriptors[0].desc.ty {
+            DescriptorDescTy::Buffer(ref desc) => assert!(!desc.storage),
+            _ => panic!(),
+        }
+        assert!(descriptors[0].desc.readonly);
+        assert!(descriptors[0].desc.stages.vertex);
+
+        let push_constants = entry_point.push_constants().unwrap();
+        assert_eq!((push_constants.offset, push_constants.size), (0, 32));
+
+        let layout = entry_point.pipeline_layout_desc();
+        assert_eq!(layout.num_sets(), 1);
+        assert_eq!(layout.num_bindings_in_set(0), Some(2));
+        assert!(layout.descriptor(0, 0).is_none());
+        assert!(layout.descriptor(0, 1).is_some());
+        assert_eq!(layout.num_push_constants_ranges(), 1);
+    }
+
+    #[test]
+    fn missing_descriptor_set() {
+        // Removes the `DescriptorSet` decoration of the uniform buffer.
+        let mut words = vertex_shader();
+        let decoration = [4 << 16 | 71, 12, 34, 0];
+        let position = words.windows(4).position(|w| w == decoration).unwrap();
+        words.drain(position..position + 4);
+
+        assert_eq!(
+            reflect::reflect(&words).unwrap_err(),
+            ReflectError::MissingDecoration {
+                id: 12,
+                decoration: "DescriptorSet",
+            }
+        );
+    }
+
+    #[test]
+    fn reflect_specialization_constants() {
+        let reflection = reflect::reflect(&vertex_shader()).unwrap();
+        assert_eq!(
+            reflection.specialization_constants(),
+            &[SpecializationConstantReflection {
+                constant_id: 7,
+                ty: SpecializationConstantType::Float { width: 32 },
+                name: None,
+            }]
+        );
+        assert_eq!(reflection.specialization_constant(7).unwrap().ty.size(), 4);
+        assert!(reflection.specialization_constant(0).is_none());
+    }
+
+    #[test]
+    fn reflect_subgroup_features() {
+        let reflection = reflect::reflect(&vertex_shader()).unwrap();
+        assert_eq!(reflection.subgroup_features(), SubgroupFeatures::none());
+
+        // Adds `OpCapability GroupNonUniform` and `OpCapability GroupNonUniformArithmetic` after
+        // `OpCapability Shader`.
+        let mut words = vertex_shader();
+        words.splice(7..7, vec![2 << 16 | 17, 61, 2 << 16 | 17, 63]);
+        let reflection = reflect::reflect(&words).unwrap();
+        assert_eq!(
+            reflection.subgroup_features(),
+            SubgroupFeatures {
+                basic: true,
+                arithmetic: true,
+                ..SubgroupFeatures::none()
+            }
+        );
+    }
+
+    #[test]
+    fn reflect_required_features() {
+        let reflection = reflect::reflect(&vertex_shader()).unwrap();
+        assert_eq!(reflection.required_features(), &Features::none());
+
+        // Adds `OpCapability Float16` and `OpCapability StorageBuffer8BitAccess` after
+        // `OpCapability Shader`.
+        let mut words = vertex_shader();
+        words.splice(7..7, vec![2 << 16 | 17, 9, 2 << 16 | 17, 4448]);
+        let reflection = reflect::reflect(&words).unwrap();
+        assert_eq!(
+            reflection.required_features(),
+            &Features {
+                shader_float16: true,
+                storage_buffer_8bit: true,
+                ..Features::none()
+            }
+        );
+    }
+
+    #[test]
+    fn entry_points_with_same_name() {
+        // Adds `OpEntryPoint Fragment %1 "main"` after the vertex entry point.
+        let mut words = vertex_shader();
+        let mut ep = vec![4, 1];
+        ep.extend(string("main"));
+        let position = words.iter().position(|&w| w & 0xffff == 15).unwrap();
+        let position = position + (words[position] >> 16) as usize;
+        let mut fragment = vec![((ep.len() as u32 + 1) << 16) | 15];
+        fragment.extend(ep);
+        words.splice(position..position, fragment);
+
+        let reflection = reflect::reflect(&words).unwrap();
+        assert_eq!(reflection.entry_points().len(), 2);
+
+        let vertex = reflection
+            .entry_point_for_stage("main", ShaderStage::Vertex)
+            .unwrap();
+        assert_eq!(vertex.ty().stage(), ShaderStage::Vertex);
+        let fragment = reflection
+            .entry_point_for_stage("main", ShaderStage::Fragment)
+            .unwrap();
+        assert_eq!(
+            fragment.ty(),
+            EntryPointType::Graphics(GraphicsShaderType::Fragment)
+        );
+        assert!(reflection
+            .entry_point_for_stage("main", ShaderStage::Compute)
+            .is_none());
+    }
+
+    #[test]
+    fn reflect_big_endian_bytes() {
+        let bytes: Vec<u8> = vertex_shader()
+            .iter()
+            .flat_map(|w| w.to_be_bytes().to_vec())
+            .collect();
+        let reflection = reflect::reflect_bytes(&bytes).unwrap();
+        assert!(reflection.entry_point("main").is_some());
+    }
+
+    #[test]
+    fn wrong_header() {
+        assert_eq!(
+            reflect::reflect(&[0, 0, 0, 0, 0]).unwrap_err(),
+            ReflectError::WrongHeader
+        );
+        assert_eq!(
+            reflect::reflect(&[0x07230203]).unwrap_err(),
+            ReflectError::MissingHeader
+        );
+    }
+
+    #[test]
+    fn truncated_instruction() {
+        let mut words = vertex_shader();
+        words.truncate(words.len() - 1);
+        words.push(5 << 16 | 61);
+        assert_eq!(
+            reflect::reflect(&words).unwrap_err(),
+            ReflectError::IncompleteInstruction
+        );
+    }
+}
@@ -0,0 +1,502 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Minimal parser of SPIR-V modules.
+//!
+//! Only the instructions needed to reflect the interface of a module are decoded. All the other
+//! instructions are kept as their raw opcode and operands.
+
+use std::collections::HashMap;
+
+use crate::pipeline::reflect::ReflectError;
+
+const MAGIC: u32 = 0x07230203;
+
+// Opcodes of the instructions that are decoded.
+pub(crate) const OP_NAME: u16 = 5;
+pub(crate) const OP_MEMBER_NAME: u16 = 6;
+pub(crate) const OP_EXT_INST: u16 = 12;
+pub(crate) const OP_ENTRY_POINT: u16 = 15;
+pub(crate) const OP_EXECUTION_MODE: u16 = 16;
+pub(crate) const OP_CAPABILITY: u16 = 17;
+pub(crate) const OP_TYPE_VOID: u16 = 19;
+pub(crate) const OP_TYPE_BOOL: u16 = 20;
+pub(crate) const OP_TYPE_INT: u16 = 21;
+pub(crate) const OP_TYPE_FLOAT: u16 = 22;
+pub(crate) const OP_TYPE_VECTOR: u16 = 23;
+pub(crate) const OP_TYPE_MATRIX: u16 = 24;
+pub(crate) const OP_TYPE_IMAGE: u16 = 25;
+pub(crate) const OP_TYPE_SAMPLER: u16 = 26;
+pub(crate) const OP_TYPE_SAMPLED_IMAGE: u16 = 27;
+pub(crate) const OP_TYPE_ARRAY: u16 = 28;
+pub(crate) const OP_TYPE_RUNTIME_ARRAY: u16 = 29;
+pub(crate) const OP_TYPE_STRUCT: u16 = 30;
+pub(crate) const OP_TYPE_POINTER: u16 = 32;
+pub(crate) const OP_CONSTANT_TRUE: u16 = 41;
+pub(crate) const OP_CONSTANT_FALSE: u16 = 42;
+pub(crate) const OP_CONSTANT: u16 = 43;
+pub(crate) const OP_SPEC_CONSTANT_TRUE: u16 = 48;
+pub(crate) const OP_SPEC_CONSTANT_FALSE: u16 = 49;
+pub(crate) const OP_SPEC_CONSTANT: u16 = 50;
+pub(crate) const OP_FUNCTION: u16 = 54;
+pub(crate) const OP_FUNCTION_END: u16 = 56;
+pub(crate) const OP_FUNCTION_CALL: u16 = 57;
+pub(crate) const OP_VARIABLE: u16 = 59;
+pub(crate) const OP_IMAGE_TEXEL_POINTER: u16 = 60;
+pub(crate) const OP_LOAD: u16 = 61;
+pub(crate) const OP_STORE: u16 = 62;
+pub(crate) const OP_COPY_MEMORY: u16 = 63;
+pub(crate) const OP_COPY_MEMORY_SIZED: u16 = 64;
+pub(crate) const OP_ACCESS_CHAIN: u16 = 65;
+pub(crate) const OP_IN_BOUNDS_ACCESS_CHAIN: u16 = 66;
+pub(crate) const OP_PTR_ACCESS_CHAIN: u16 = 67;
+pub(crate) const OP_ARRAY_LENGTH: u16 = 68;
+pub(crate) const OP_DECORATE: u16 = 71;
+pub(crate) const OP_MEMBER_DECORATE: u16 = 72;
+pub(crate) const OP_COPY_OBJECT: u16 = 83;
+pub(crate) const OP_ATOMIC_LOAD: u16 = 227;
+pub(crate) const OP_ATOMIC_STORE: u16 = 228;
+pub(crate) const OP_ATOMIC_XOR: u16 = 242;
+pub(crate) const OP_ATOMIC_FLAG_TEST_AND_SET: u16 = 318;
+pub(crate) const OP_ATOMIC_FLAG_CLEAR: u16 = 319;
+
+// Capabilities that are looked up.
+pub(crate) const CAPABILITY_FLOAT16: u32 = 9;
+pub(crate) const CAPABILITY_FLOAT64: u32 = 10;
+pub(crate) const CAPABILITY_INT64: u32 = 11;
+pub(crate) const CAPABILITY_INT16: u32 = 22;
+pub(crate) const CAPABILITY_INT8: u32 = 39;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM: u32 = 61;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM_VOTE: u32 = 62;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM_ARITHMETIC: u32 = 63;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM_BALLOT: u32 = 64;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM_SHUFFLE: u32 = 65;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM_SHUFFLE_RELATIVE: u32 = 66;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM_CLUSTERED: u32 = 67;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM_QUAD: u32 = 68;
+pub(crate) const CAPABILITY_STORAGE_BUFFER_16BIT_ACCESS: u32 = 4433;
+pub(crate) const CAPABILITY_UNIFORM_AND_STORAGE_BUFFER_16BIT_ACCESS: u32 = 4434;
+pub(crate) const CAPABILITY_STORAGE_PUSH_CONSTANT_16: u32 = 4435;
+pub(crate) const CAPABILITY_STORAGE_INPUT_OUTPUT_16: u32 = 4436;
+pub(crate) const CAPABILITY_STORAGE_BUFFER_8BIT_ACCESS: u32 = 4448;
+pub(crate) const CAPABILITY_UNIFORM_AND_STORAGE_BUFFER_8BIT_ACCESS: u32 = 4449;
+pub(crate) const CAPABILITY_STORAGE_PUSH_CONSTANT_8: u32 = 4450;
+pub(crate) const CAPABILITY_GROUP_NON_UNIFORM_PARTITIONED_NV: u32 = 5297;
+
+// Decorations that are looked up.
+pub(crate) const DECORATION_SPEC_ID: u32 = 1;
+pub(crate) const DECORATION_BLOCK: u32 = 2;
+pub(crate) const DECORATION_BUFFER_BLOCK: u32 = 3;
+pub(crate) const DECORATION_ARRAY_STRIDE: u32 = 6;
+pub(crate) const DECORATION_MATRIX_STRIDE: u32 = 7;
+pub(crate) const DECORATION_BUILT_IN: u32 = 11;
+pub(crate) const DECORATION_NON_WRITABLE: u32 = 24;
+pub(crate) const DECORATION_LOCATION: u32 = 30;
+pub(crate) const DECORATION_BINDING: u32 = 33;
+pub(crate) const DECORATION_DESCRIPTOR_SET: u32 = 34;
+pub(crate) const DECORATION_OFFSET: u32 = 35;
+
+// Storage classes that are looked up.
+pub(crate) const STORAGE_CLASS_UNIFORM_CONSTANT: u32 = 0;
+pub(crate) const STORAGE_CLASS_INPUT: u32 = 1;
+pub(crate) const STORAGE_CLASS_UNIFORM: u32 = 2;
+pub(crate) const STORAGE_CLASS_OUTPUT: u32 = 3;
+pub(crate) const STORAGE_CLASS_PUSH_CONSTANT: u32 = 9;
+pub(crate) const STORAGE_CLASS_STORAGE_BUFFER: u32 = 12;
+
+/// A single instruction, with its operands left undecoded.
+#[derive(Debug, Clone)]
+pub(crate) struct Instruction {
+    pub(crate) opcode: u16,
+    pub(crate) operands: Vec<u32>,
+}
+
+/// A type declared in the module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Type {
+    Void,
+    Bool,
+    Int {
+        width: u32,
+        signed: bool,
+    },
+    Float {
+        width: u32,
+    },
+    Vector {
+        component: u32,
+        count: u32,
+    },
+    Matrix {
+        column: u32,
+        count: u32,
+    },
+    Image {
+        dim: u32,
+        arrayed: bool,
+        ms: bool,
+        sampled: u32,
+    },
+    Sampler,
+    SampledImage {
+        image: u32,
+    },
+    Array {
+        element: u32,
+        length: u32,
+    },
+    RuntimeArray {
+        element: u32,
+    },
+    Struct {
+        members: Vec<u32>,
+    },
+    Pointer {
+        storage_class: u32,
+        pointee: u32,
+    },
+}
+
+/// An `OpEntryPoint` instruction.
+#[derive(Debug, Clone)]
+pub(crate) struct EntryPoint {
+    pub(crate) execution_model: u32,
+    pub(crate) function: u32,
+    pub(crate) name: String,
+    pub(crate) interface: Vec<u32>,
+}
+
+// A list of decorations or execution modes, each one with its parameters.
+type Operands = Vec<(u32, Vec<u32>)>;
+
+/// A parsed SPIR-V module, with lookup tables for the instructions that are needed by reflection.
+#[derive(Debug, Clone)]
+pub(crate) struct Spirv {
+    pub(crate) version: (u8, u8),
+    pub(crate) instructions: Vec<Instruction>,
+    pub(crate) entry_points: Vec<EntryPoint>,
+    pub(crate) names: HashMap<u32, String>,
+    pub(crate) member_names: HashMap<(u32, u32), String>,
+    pub(crate) decorations: HashMap<u32, Operands>,
+    pub(crate) member_decorations: HashMap<(u32, u32), Operands>,
+    pub(crate) execution_modes: HashMap<u32, Operands>,
+    pub(crate) types: HashMap<u32, Type>,
+    // The capabilities declared by the module.
+    pub(crate) capabilities: Vec<u32>,
+    // The result type and the value of each scalar constant and specialization constant.
+    pub(crate) constants: HashMap<u32, (u32, Vec<u32>)>,
+    // The ids of the scalar specialization constants, in declaration order.
+    pub(crate) spec_constants: Vec<u32>,
+    // The result type and the storage class of each global variable.
+    pub(crate) variables: HashMap<u32, (u32, u32)>,
+}
+
+impl Spirv {
+    /// Parses a SPIR-V module from its words.
+    pub(crate) fn parse(words: &[u32]) -> Result<Spirv, ReflectError> {
+        if words.len() < 5 {
+            return Err(ReflectError::MissingHeader);
+        }
+
+        if words[0] != MAGIC {
+            return Err(ReflectError::WrongHeader);
+        }
+
+        let version = (
+            ((words[1] & 0x00ff0000) >> 16) as u8,
+            ((words[1] & 0x0000ff00) >> 8) as u8,
+        );
+
+        let mut spirv = Spirv {
+            version,
+            instructions: Vec::new(),
+            entry_points: Vec::new(),
+            names: HashMap::new(),
+            member_names: HashMap::new(),
+            decorations: HashMap::new(),
+            member_decorations: HashMap::new(),
+            execution_modes: HashMap::new(),
+            types: HashMap::new(),
+            capabilities: Vec::new(),
+            constants: HashMap::new(),
+            spec_constants: Vec::new(),
+            variables: HashMap::new(),
+        };
+
+        let mut rest = &words[5..];
+        let mut in_function = false;
+        while !rest.is_empty() {
+            let word_count = (rest[0] >> 16) as usize;
+            let opcode = (rest[0] & 0xffff) as u16;
+            if word_count == 0 || word_count > rest.len() {
+                return Err(ReflectError::IncompleteInstruction);
+            }
+
+            let operands = &rest[1..word_count];
+            rest = &rest[word_count..];
+
+            match opcode {
+                OP_FUNCTION => in_function = true,
+                OP_FUNCTION_END => in_function = false,
+                _ => (),
+            }
+
+            // Variables declared inside functions are not part of the interface of the module.
+            if !in_function {
+                spirv.decode(opcode, operands)?;
+            }
+
+            spirv.instructions.push(Instruction {
+                opcode,
+                operands: operands.to_vec(),
+            });
+        }
+
+        Ok(spirv)
+    }
+
+    /// Parses a SPIR-V module from its bytes, in either endianness.
+    pub(crate) fn parse_bytes(bytes: &[u8]) -> Result<Spirv, ReflectError> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err(ReflectError::IncompleteInstruction);
+        }
+
+        let mut words: Vec<u32> = bytes
+            .chunks(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        if words.first() == Some(&MAGIC.swap_bytes()) {
+            for word in words.iter_mut() {
+                *word = word.swap_bytes();
+            }
+        }
+
+        Spirv::parse(&words)
+    }
+
+    fn decode(&mut self, opcode: u16, operands: &[u32]) -> Result<(), ReflectError> {
+        let operand = |n: usize| -> Result<u32, ReflectError> {
+            operands
+                .get(n)
+                .cloned()
+                .ok_or(ReflectError::IncompleteInstruction)
+        };
+
+        match opcode {
+            OP_NAME => {
+                let (name, _) = parse_string(&operands[1..])?;
+                self.names.insert(operand(0)?, name);
+            }
+            OP_MEMBER_NAME => {
+                let (name, _) = parse_string(&operands[2..])?;
+                self.member_names.insert((operand(0)?, operand(1)?), name);
+            }
+            OP_ENTRY_POINT => {
+                let (name, len) = parse_string(&operands[2..])?;
+                self.entry_points.push(EntryPoint {
+                    execution_model: operand(0)?,
+                    function: operand(1)?,
+                    name,
+                    interface: operands[2 + len..].to_vec(),
+                });
+            }
+            OP_EXECUTION_MODE => {
+                self.execution_modes
+                    .entry(operand(0)?)
+                    .or_default()
+                    .push((operand(1)?, operands[2..].to_vec()));
+            }
+            OP_DECORATE => {
+                self.decorations
+                    .entry(operand(0)?)
+                    .or_default()
+                    .push((operand(1)?, operands[2..].to_vec()));
+            }
+            OP_MEMBER_DECORATE => {
+                self.member_decorations
+                    .entry((operand(0)?, operand(1)?))
+                    .or_default()
+                    .push((operand(2)?, operands[3..].to_vec()));
+            }
+            OP_TYPE_VOID => {
+                self.types.insert(operand(0)?, Type::Void);
+            }
+            OP_TYPE_BOOL => {
+                self.types.insert(operand(0)?, Type::Bool);
+            }
+            OP_TYPE_INT => {
+                self.types.insert(
+                    operand(0)?,
+                    Type::Int {
+                        width: operand(1)?,
+                        signed: operand(2)? != 0,
+                    },
+                );
+            }
+            OP_TYPE_FLOAT => {
+                self.types
+                    .insert(operand(0)?, Type::Float { width: operand(1)? });
+            }
+            OP_TYPE_VECTOR => {
+                self.types.insert(
+                    operand(0)?,
+                    Type::Vector {
+                        component: operand(1)?,
+                        count: operand(2)?,
+                    },
+                );
+            }
+            OP_TYPE_MATRIX => {
+                self.types.insert(
+                    operand(0)?,
+                    Type::Matrix {
+                        column: operand(1)?,
+                        count: operand(2)?,
+                    },
+                );
+            }
+            OP_TYPE_IMAGE => {
+                self.types.insert(
+                    operand(0)?,
+                    Type::Image {
+                        dim: operand(2)?,
+                        arrayed: operand(4)? != 0,
+                        ms: operand(5)? != 0,
+                        sampled: operand(6)?,
+                    },
+                );
+            }
+            OP_TYPE_SAMPLER => {
+                self.types.insert(operand(0)?, Type::Sampler);
+            }
+            OP_TYPE_SAMPLED_IMAGE => {
+                self.types
+                    .insert(operand(0)?, Type::SampledImage { image: operand(1)? });
+            }
+            OP_TYPE_ARRAY => {
+                self.types.insert(
+                    operand(0)?,
+                    Type::Array {
+                        element: operand(1)?,
+                        length: operand(2)?,
+                    },
+                );
+            }
+            OP_TYPE_RUNTIME_ARRAY => {
+                self.types.insert(
+                    operand(0)?,
+                    Type::RuntimeArray {
+                        element: operand(1)?,
+                    },
+                );
+            }
+            OP_TYPE_STRUCT => {
+                self.types.insert(
+                    operand(0)?,
+                    Type::Struct {
+                        members: operands[1..].to_vec(),
+                    },
+                );
+            }
+            OP_TYPE_POINTER => {
+                self.types.insert(
+                    operand(0)?,
+                    Type::Pointer {
+                        storage_class: operand(1)?,
+                        pointee: operand(2)?,
+                    },
+                );
+            }
+            OP_CAPABILITY => {
+                self.capabilities.push(operand(0)?);
+            }
+            OP_CONSTANT_TRUE | OP_SPEC_CONSTANT_TRUE => {
+                self.constants.insert(operand(1)?, (operand(0)?, vec![1]));
+                if opcode == OP_SPEC_CONSTANT_TRUE {
+                    self.spec_constants.push(operand(1)?);
+                }
+            }
+            OP_CONSTANT_FALSE | OP_SPEC_CONSTANT_FALSE => {
+                self.constants.insert(operand(1)?, (operand(0)?, vec![0]));
+                if opcode == OP_SPEC_CONSTANT_FALSE {
+                    self.spec_constants.push(operand(1)?);
+                }
+            }
+            OP_CONSTANT | OP_SPEC_CONSTANT => {
+                self.constants
+                    .insert(operand(1)?, (operand(0)?, operands[2..].to_vec()));
+                if opcode == OP_SPEC_CONSTANT {
+                    self.spec_constants.push(operand(1)?);
+                }
+            }
+            OP_VARIABLE => {
+                self.variables
+                    .insert(operand(1)?, (operand(0)?, operand(2)?));
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the parameters of a decoration of `id`, if it has it.
+    pub(crate) fn decoration(&self, id: u32, decoration: u32) -> Option<&[u32]> {
+        self.decorations.get(&id).and_then(|list| {
+            list.iter()
+                .find(|(d, _)| *d == decoration)
+                .map(|(_, params)| &params[..])
+        })
+    }
+
+    /// Returns the parameters of a decoration of a member of the struct `id`, if it has it.
+    pub(crate) fn member_decoration(
+        &self,
+        id: u32,
+        member: u32,
+        decoration: u32,
+    ) -> Option<&[u32]> {
+        self.member_decorations.get(&(id, member)).and_then(|list| {
+            list.iter()
+                .find(|(d, _)| *d == decoration)
+                .map(|(_, params)| &params[..])
+        })
+    }
+
+    /// Returns the type with the given id.
+    pub(crate) fn ty(&self, id: u32) -> Result<&Type, ReflectError> {
+        self.types.get(&id).ok_or(ReflectError::UnknownId { id })
+    }
+
+    /// Returns the value of an integer constant, or the default value of a specialization
+    /// constant.
+    pub(crate) fn constant_u64(&self, id: u32) -> Result<u64, ReflectError> {
+        let (_, data) = self
+            .constants
+            .get(&id)
+            .ok_or(ReflectError::UnknownId { id })?;
+        Ok(data.iter().rev().fold(0, |a, &b| (a << 32) | b as u64))
+    }
+}
+
+// Parses a nul-terminated UTF-8 string, and returns it with the number of words it occupies.
+fn parse_string(words: &[u32]) -> Result<(String, usize), ReflectError> {
+    let bytes: Vec<u8> = words
+        .iter()
+        .flat_map(|word| word.to_le_bytes().to_vec())
+        .take_while(|&b| b != 0)
+        .collect();
+
+    if bytes.len() >= words.len() * 4 {
+        return Err(ReflectError::IncompleteInstruction);
+    }
+
+    let len = bytes.len() / 4 + 1;
+    let string = String::from_utf8(bytes).map_err(|_| ReflectError::InvalidString)?;
+    Ok((string, len))
+}
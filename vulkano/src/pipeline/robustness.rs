@@ -0,0 +1,170 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Robustness of resource accesses made by the shaders of a pipeline.
+//!
+//! Accessing a buffer or an image out of bounds is normally undefined behavior. The
+//! `robust_buffer_access` feature and the features of the `VK_EXT_robustness2` and
+//! `VK_EXT_image_robustness` extensions make such accesses well-defined, but they apply to all the
+//! pipelines of the device and have a cost.
+//!
+//! With the `VK_EXT_pipeline_robustness` extension, the behavior can instead be chosen for each
+//! pipeline. This makes it possible to only pay the cost of robustness for the pipelines whose
+//! shaders aren't trusted.
+
+use crate::features::Features;
+use crate::vk;
+
+/// Describes the robustness of the resource accesses of a pipeline.
+///
+/// All the members default to `DeviceDefault`, which uses the behavior given by the features
+/// enabled on the device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PipelineRobustness {
+    /// Behavior of accesses to storage buffers.
+    pub storage_buffers: BufferRobustness,
+    /// Behavior of accesses to uniform buffers.
+    pub uniform_buffers: BufferRobustness,
+    /// Behavior of accesses to vertex input attributes.
+    pub vertex_inputs: BufferRobustness,
+    /// Behavior of accesses to images.
+    pub images: ImageRobustness,
+}
+
+impl PipelineRobustness {
+    /// Builds a `PipelineRobustness` that uses the behavior of the device for all accesses.
+    #[inline]
+    pub fn device_default() -> PipelineRobustness {
+        PipelineRobustness::default()
+    }
+
+    /// Returns true if all the members are `DeviceDefault`.
+    #[inline]
+    pub fn is_device_default(&self) -> bool {
+        *self == PipelineRobustness::device_default()
+    }
+
+    /// Returns the name of a device feature that is needed by one of the behaviors but that the
+    /// physical device doesn't support, if any.
+    pub(crate) fn unsupported_feature(&self, supported: &Features) -> Option<&'static str> {
+        let buffers = [
+            self.storage_buffers,
+            self.uniform_buffers,
+            self.vertex_inputs,
+        ];
+
+        if buffers.contains(&BufferRobustness::RobustBufferAccess2)
+            && !supported.robust_buffer_access2
+        {
+            return Some("robust_buffer_access2");
+        }
+
+        match self.images {
+            ImageRobustness::RobustImageAccess if !supported.robust_image_access => {
+                Some("robust_image_access")
+            }
+            ImageRobustness::RobustImageAccess2 if !supported.robust_image_access2 => {
+                Some("robust_image_access2")
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn to_vulkan(self) -> vk::PipelineRobustnessCreateInfoEXT {
+        vk::PipelineRobustnessCreateInfoEXT {
+            sType: vk::STRUCTURE_TYPE_PIPELINE_ROBUSTNESS_CREATE_INFO_EXT,
+            pNext: std::ptr::null(),
+            storageBuffers: self.storage_buffers as u32,
+            uniformBuffers: self.uniform_buffers as u32,
+            vertexInputs: self.vertex_inputs as u32,
+            images: self.images as u32,
+        }
+    }
+}
+
+/// Behavior of out of bounds accesses to a kind of buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum BufferRobustness {
+    /// Uses the behavior given by the features enabled on the device.
+    DeviceDefault = vk::PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_DEVICE_DEFAULT_EXT,
+    /// Out of bounds accesses are undefined behavior, even if robustness features are enabled on
+    /// the device.
+    Disabled = vk::PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_DISABLED_EXT,
+    /// Same behavior as the `robust_buffer_access` feature.
+    RobustBufferAccess = vk::PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_ROBUST_BUFFER_ACCESS_EXT,
+    /// Same behavior as the `robust_buffer_access2` feature, which must be supported by the
+    /// physical device.
+    RobustBufferAccess2 = vk::PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_ROBUST_BUFFER_ACCESS_2_EXT,
+}
+
+impl Default for BufferRobustness {
+    #[inline]
+    fn default() -> BufferRobustness {
+        BufferRobustness::DeviceDefault
+    }
+}
+
+/// Behavior of out of bounds accesses to images.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ImageRobustness {
+    /// Uses the behavior given by the features enabled on the device.
+    DeviceDefault = vk::PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_DEVICE_DEFAULT_EXT,
+    /// Out of bounds accesses are undefined behavior, even if robustness features are enabled on
+    /// the device.
+    Disabled = vk::PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_DISABLED_EXT,
+    /// Same behavior as the `robust_image_access` feature, which must be supported by the
+    /// physical device.
+    RobustImageAccess = vk::PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_ROBUST_IMAGE_ACCESS_EXT,
+    /// Same behavior as the `robust_image_access2` feature, which must be supported by the
+    /// physical device.
+    RobustImageAccess2 = vk::PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_ROBUST_IMAGE_ACCESS_2_EXT,
+}
+
+impl Default for ImageRobustness {
+    #[inline]
+    fn default() -> ImageRobustness {
+        ImageRobustness::DeviceDefault
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::Features;
+    use crate::pipeline::robustness::BufferRobustness;
+    use crate::pipeline::robustness::ImageRobustness;
+    use crate::pipeline::robustness::PipelineRobustness;
+
+    #[test]
+    fn unsupported_feature() {
+        let none = Features::none();
+        assert_eq!(
+            PipelineRobustness::device_default().unsupported_feature(&none),
+            None
+        );
+
+        let robustness = PipelineRobustness {
+            uniform_buffers: BufferRobustness::RobustBufferAccess2,
+            images: ImageRobustness::Disabled,
+            ..PipelineRobustness::device_default()
+        };
+        assert_eq!(
+            robustness.unsupported_feature(&none),
+            Some("robust_buffer_access2")
+        );
+
+        let supported = Features {
+            robust_buffer_access2: true,
+            ..Features::none()
+        };
+        assert_eq!(robustness.unsupported_feature(&supported), None);
+    }
+}
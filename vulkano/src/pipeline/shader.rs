@@ -11,11 +11,23 @@
 //!
 //! In Vulkan, shaders are grouped in *shader modules*. Each shader module is built from SPIR-V
 //! code and can contain one or more entry points. Note that for the moment the official
-//! GLSL-to-SPIR-V compiler does not support multiple entry points.
+//! GLSL-to-SPIR-V compiler does not support multiple entry points, but other languages such as
+//! HLSL and WGSL do. Several entry points may even share the same name if they are of different
+//! stages, in which case the entry points of a reflected module can be picked by name and stage
+//! with `reflected_graphics_entry_point`.
 //!
-//! The vulkano library does not provide any functionality that checks and introspects the SPIR-V
-//! code, therefore the whole shader-related API is unsafe. You are encouraged to use the
-//! `vulkano-shaders` crate that will generate Rust code that wraps around vulkano's shaders API.
+//! The vulkano library does not provide any functionality that checks the SPIR-V code, therefore
+//! the whole shader-related API is unsafe. You are encouraged to use the `vulkano-shaders` crate
+//! that will generate Rust code that wraps around vulkano's shaders API. Shaders that are only
+//! known at runtime can be introspected with the `reflect` module instead.
+//!
+//! # Shader module identifiers
+//!
+//! With the `VK_EXT_shader_module_identifier` extension, a shader module can be given an opaque
+//! identifier with `ShaderModule::identifier`. A pipeline created from a module built with
+//! `ShaderModule::from_identifier` doesn't need the SPIR-V code at all, but can only be created
+//! if the pipeline cache already contains it. Otherwise, the creation fails with a
+//! `PipelineCompileRequired` error, and the pipeline must be created again from the SPIR-V code.
 
 use std::borrow::Cow;
 use std::error;
@@ -32,11 +44,19 @@ use std::sync::Arc;
 
 use crate::descriptor::pipeline_layout::EmptyPipelineDesc;
 use crate::descriptor::pipeline_layout::PipelineLayoutDesc;
+use crate::descriptor::pipeline_layout::RuntimePipelineDesc;
 use crate::format::Format;
 use crate::pipeline::input_assembly::PrimitiveTopology;
+use crate::pipeline::reflect;
+use crate::pipeline::reflect::EntryPointType;
+use crate::pipeline::reflect::ReflectError;
+use crate::pipeline::reflect::ShaderReflection;
+use crate::pipeline::reflect::ShaderStage;
+use crate::pipeline::reflect::SpecializationConstantType;
 
 use crate::check_errors;
 use crate::device::Device;
+use crate::features::Features;
 use crate::vk;
 use crate::OomError;
 use crate::VulkanObject;
@@ -51,6 +71,10 @@ pub struct ShaderModule {
     module: vk::ShaderModule,
     // Pointer to the device.
     device: Arc<Device>,
+    // Description of the module, if it was reflected when it was created.
+    reflection: Option<ShaderReflection>,
+    // The identifier that the module was created from, in which case `module` is null.
+    identifier: Option<ShaderModuleIdentifier>,
 }
 
 impl ShaderModule {
@@ -64,7 +88,7 @@ impl ShaderModule {
     ///
     pub unsafe fn new(device: Arc<Device>, spirv: &[u8]) -> Result<Arc<ShaderModule>, OomError> {
         debug_assert!((spirv.len() % 4) == 0);
-        Self::from_ptr(device, spirv.as_ptr() as *const _, spirv.len())
+        Self::from_ptr(device, spirv.as_ptr() as *const _, spirv.len(), None)
     }
 
     /// Builds a new shader module from SPIR-V 32-bit words.
@@ -79,7 +103,7 @@ impl ShaderModule {
         device: Arc<Device>,
         spirv: &[u32],
     ) -> Result<Arc<ShaderModule>, OomError> {
-        Self::from_ptr(device, spirv.as_ptr(), spirv.len() * mem::size_of::<u32>())
+        Self::from_ptr(device, spirv.as_ptr(), mem::size_of_val(spirv), None)
     }
 
     /// Builds a new shader module from SPIR-V.
@@ -94,6 +118,7 @@ impl ShaderModule {
         device: Arc<Device>,
         spirv: *const u32,
         spirv_len: usize,
+        reflection: Option<ShaderReflection>,
     ) -> Result<Arc<ShaderModule>, OomError> {
         let module = {
             let infos = vk::ShaderModuleCreateInfo {
@@ -109,7 +134,7 @@ impl ShaderModule {
             check_errors(vk.CreateShaderModule(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -118,9 +143,157 @@ impl ShaderModule {
         Ok(Arc::new(ShaderModule {
             module: module,
             device: device,
+            reflection,
+            identifier: None,
+        }))
+    }
+
+    /// Builds a new shader module from SPIR-V 32-bit words, and reflects its code.
+    ///
+    /// The description of the module can then be obtained with `reflection()`.
+    ///
+    /// # Safety
+    ///
+    /// - The SPIR-V code is not validated beyond what is needed to reflect it.
+    /// - The SPIR-V code may require some features that are not enabled. This isn't checked by
+    ///   this function either.
+    ///
+    pub unsafe fn from_words_with_reflection(
+        device: Arc<Device>,
+        spirv: &[u32],
+    ) -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
+        let reflection = reflect::reflect(spirv)?;
+        Ok(Self::from_ptr(
+            device,
+            spirv.as_ptr(),
+            mem::size_of_val(spirv),
+            Some(reflection),
+        )?)
+    }
+
+    /// Returns the description of the module, if it was reflected when it was created.
+    #[inline]
+    pub fn reflection(&self) -> Option<&ShaderReflection> {
+        self.reflection.as_ref()
+    }
+
+    /// Builds a shader module that only contains the identifier of another shader module.
+    ///
+    /// Pipelines that use this module are created with the
+    /// `PIPELINE_CREATE_FAIL_ON_PIPELINE_COMPILE_REQUIRED` flag, and the
+    /// `shader_module_identifier` and `pipeline_creation_cache_control` features must be enabled.
+    ///
+    /// # Safety
+    ///
+    /// - The entry points obtained from this module must match the ones of the module that the
+    ///   identifier was obtained from.
+    ///
+    pub unsafe fn from_identifier(
+        device: Arc<Device>,
+        identifier: ShaderModuleIdentifier,
+    ) -> Result<Arc<ShaderModule>, ShaderModuleIdentifierError> {
+        if !device.enabled_features().shader_module_identifier {
+            return Err(ShaderModuleIdentifierError::FeatureNotEnabled);
+        }
+
+        Ok(Arc::new(ShaderModule {
+            module: vk::NULL_HANDLE,
+            device,
+            reflection: None,
+            identifier: Some(identifier),
         }))
     }
 
+    /// Returns the identifier of the module.
+    ///
+    /// The `shader_module_identifier` feature must be enabled.
+    pub fn identifier(&self) -> Result<ShaderModuleIdentifier, ShaderModuleIdentifierError> {
+        if let Some(identifier) = self.identifier {
+            return Ok(identifier);
+        }
+        if !self.device.enabled_features().shader_module_identifier {
+            return Err(ShaderModuleIdentifierError::FeatureNotEnabled);
+        }
+
+        unsafe {
+            let vk = self.device.pointers();
+            let mut output = ShaderModuleIdentifier::empty_vulkan();
+            vk.GetShaderModuleIdentifierEXT(
+                self.device.internal_object(),
+                self.module,
+                &mut output,
+            );
+            Ok(ShaderModuleIdentifier::from_vulkan(&output))
+        }
+    }
+
+    /// Returns the identifier that a shader module built from `spirv` would have, without
+    /// creating it.
+    ///
+    /// The `shader_module_identifier` feature must be enabled.
+    pub fn identifier_for_spirv(
+        device: &Device,
+        spirv: &[u32],
+    ) -> Result<ShaderModuleIdentifier, ShaderModuleIdentifierError> {
+        if !device.enabled_features().shader_module_identifier {
+            return Err(ShaderModuleIdentifierError::FeatureNotEnabled);
+        }
+
+        unsafe {
+            let infos = vk::ShaderModuleCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SHADER_MODULE_CREATE_INFO,
+                pNext: ptr::null(),
+                flags: 0, // reserved
+                codeSize: mem::size_of_val(spirv),
+                pCode: spirv.as_ptr(),
+            };
+
+            let vk = device.pointers();
+            let mut output = ShaderModuleIdentifier::empty_vulkan();
+            vk.GetShaderModuleCreateInfoIdentifierEXT(
+                device.internal_object(),
+                &infos,
+                &mut output,
+            );
+            Ok(ShaderModuleIdentifier::from_vulkan(&output))
+        }
+    }
+
+    /// Returns true if the module was built with `from_identifier`, and doesn't contain any
+    /// SPIR-V code.
+    #[inline]
+    pub fn is_identifier_only(&self) -> bool {
+        self.identifier.is_some()
+    }
+
+    // Returns the struct to chain to the stages that use this module, if it was built from an
+    // identifier. The struct borrows the identifier of `self`.
+    #[inline]
+    pub(crate) fn stage_identifier_info(
+        &self,
+    ) -> Option<vk::PipelineShaderStageModuleIdentifierCreateInfoEXT> {
+        self.identifier.as_ref().map(|identifier| {
+            vk::PipelineShaderStageModuleIdentifierCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_MODULE_IDENTIFIER_CREATE_INFO_EXT,
+                pNext: ptr::null(),
+                identifierSize: identifier.len as u32,
+                pIdentifier: identifier.data.as_ptr(),
+            }
+        })
+    }
+
+    /// Returns the features that the module requires but that are not in `enabled`.
+    ///
+    /// See `ShaderReflection::required_features` for the features that are checked. Always
+    /// returns `Features::none()` for modules without reflection.
+    #[inline]
+    pub fn missing_features(&self, enabled: &Features) -> Features {
+        match self.reflection {
+            Some(ref reflection) => reflection.required_features().difference(enabled),
+            None => Features::none(),
+        }
+    }
+
     /// Gets access to an entry point contained in this module.
     ///
     /// This is purely a *logical* operation. It returns a struct that *represents* the entry
@@ -176,6 +349,129 @@ impl ShaderModule {
             marker: PhantomData,
         }
     }
+
+    /// Checks the specialization constants against the declarations of the module.
+    ///
+    /// The offset and size of each `SpecializationMapEntry` are checked against the size of `S`,
+    /// and their constant IDs must be unique. If the module was reflected when it was created,
+    /// each constant ID must also exist in the module, each size must match the type of the
+    /// constant, and boolean constants must be `0` or `1`.
+    ///
+    /// This is automatically called when creating a pipeline.
+    pub fn check_specialization_constants<S>(
+        &self,
+        constants: &S,
+    ) -> Result<(), SpecializationConstantsError>
+    where
+        S: SpecializationConstants,
+    {
+        let data_size = mem::size_of_val(constants);
+
+        for (num, entry) in S::descriptors().iter().enumerate() {
+            let constant_id = entry.constant_id;
+
+            if S::descriptors()[..num]
+                .iter()
+                .any(|e| e.constant_id == constant_id)
+            {
+                return Err(SpecializationConstantsError::DuplicateConstantId { constant_id });
+            }
+
+            if entry.offset as usize + entry.size > data_size {
+                return Err(SpecializationConstantsError::OutOfBounds { constant_id });
+            }
+
+            let reflection = match self.reflection {
+                Some(ref reflection) => reflection,
+                None => continue,
+            };
+            let declaration = reflection
+                .specialization_constant(constant_id)
+                .ok_or(SpecializationConstantsError::UnknownConstantId { constant_id })?;
+
+            if entry.size != declaration.ty.size() {
+                return Err(SpecializationConstantsError::SizeMismatch {
+                    constant_id,
+                    expected: declaration.ty.size(),
+                    obtained: entry.size,
+                });
+            }
+
+            if declaration.ty == SpecializationConstantType::Bool {
+                let value = unsafe {
+                    ptr::read_unaligned(
+                        (constants as *const S as *const u8).add(entry.offset as usize)
+                            as *const u32,
+                    )
+                };
+                if value > 1 {
+                    return Err(SpecializationConstantsError::InvalidBool { constant_id, value });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets access to a graphics entry point of a reflected module, using the interface and
+    /// layout found by reflection.
+    ///
+    /// Returns `None` if the module wasn't reflected when it was created, or if it doesn't
+    /// contain an entry point with this name and stage. `stage` must not be
+    /// `ShaderStage::Compute`.
+    ///
+    /// Contrary to `graphics_entry_point`, this function is safe because the specialization
+    /// constants `S` are checked against the module when creating the pipeline.
+    pub fn reflected_graphics_entry_point<'a, S>(
+        &'a self,
+        name: &str,
+        stage: ShaderStage,
+    ) -> Option<
+        GraphicsEntryPoint<
+            'a,
+            S,
+            RuntimeShaderInterfaceDef,
+            RuntimeShaderInterfaceDef,
+            RuntimePipelineDesc,
+        >,
+    > {
+        let entry_point = self.reflection()?.entry_point_for_stage(name, stage)?;
+        let ty = match entry_point.ty() {
+            EntryPointType::Graphics(ty) => ty,
+            EntryPointType::Compute => return None,
+        };
+
+        unsafe {
+            Some(self.graphics_entry_point(
+                entry_point.name(),
+                entry_point.input(),
+                entry_point.output(),
+                entry_point.pipeline_layout_desc(),
+                ty,
+            ))
+        }
+    }
+
+    /// Gets access to a compute entry point of a reflected module, using the layout found by
+    /// reflection.
+    ///
+    /// Returns `None` if the module wasn't reflected when it was created, or if it doesn't
+    /// contain a compute entry point with this name.
+    ///
+    /// Contrary to `compute_entry_point`, this function is safe because the specialization
+    /// constants `S` are checked against the module when creating the pipeline.
+    pub fn reflected_compute_entry_point<'a, S>(
+        &'a self,
+        name: &str,
+    ) -> Option<ComputeEntryPoint<'a, S, RuntimePipelineDesc>> {
+        let entry_point = self
+            .reflection()?
+            .entry_point_for_stage(name, ShaderStage::Compute)?;
+
+        unsafe {
+            Some(self.compute_entry_point(entry_point.name(), entry_point.pipeline_layout_desc()))
+        }
+    }
 }
 
 unsafe impl VulkanObject for ShaderModule {
@@ -192,13 +488,159 @@ unsafe impl VulkanObject for ShaderModule {
 impl Drop for ShaderModule {
     #[inline]
     fn drop(&mut self) {
+        if self.identifier.is_some() {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyShaderModule(self.device.internal_object(), self.module, ptr::null());
+            vk.DestroyShaderModule(
+                self.device.internal_object(),
+                self.module,
+                self.device.alloc_ptr(),
+            );
+        }
+    }
+}
+
+/// Opaque identifier of a shader module, obtained with `ShaderModule::identifier`.
+///
+/// Identifiers can be stored and reused on another run of the program, as long as the device
+/// reports the same `shader_module_identifier_algorithm_uuid`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ShaderModuleIdentifier {
+    data: [u8; vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT as usize],
+    len: u8,
+}
+
+impl ShaderModuleIdentifier {
+    /// Builds an identifier from bytes that were returned by `as_bytes`.
+    ///
+    /// Returns `None` if `bytes` is empty or longer than the maximum size of an identifier.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Option<ShaderModuleIdentifier> {
+        if bytes.is_empty() || bytes.len() > vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT as usize {
+            return None;
+        }
+
+        let mut data = [0; vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT as usize];
+        data[..bytes.len()].copy_from_slice(bytes);
+        Some(ShaderModuleIdentifier {
+            data,
+            len: bytes.len() as u8,
+        })
+    }
+
+    /// Returns the bytes of the identifier.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len as usize]
+    }
+
+    #[inline]
+    fn empty_vulkan() -> vk::ShaderModuleIdentifierEXT {
+        vk::ShaderModuleIdentifierEXT {
+            sType: vk::STRUCTURE_TYPE_SHADER_MODULE_IDENTIFIER_EXT,
+            pNext: ptr::null_mut(),
+            identifierSize: 0,
+            identifier: [0; vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT as usize],
+        }
+    }
+
+    #[inline]
+    fn from_vulkan(identifier: &vk::ShaderModuleIdentifierEXT) -> ShaderModuleIdentifier {
+        ShaderModuleIdentifier {
+            data: identifier.identifier,
+            len: identifier.identifierSize as u8,
+        }
+    }
+}
+
+impl fmt::Debug for ShaderModuleIdentifier {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "ShaderModuleIdentifier(")?;
+        for byte in self.as_bytes() {
+            write!(fmt, "{:02x}", byte)?;
+        }
+        write!(fmt, ")")
+    }
+}
+
+/// Error that can happen when getting or using a shader module identifier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderModuleIdentifierError {
+    /// The `shader_module_identifier` feature is not enabled on the device.
+    FeatureNotEnabled,
+}
+
+impl error::Error for ShaderModuleIdentifierError {}
+
+impl fmt::Display for ShaderModuleIdentifierError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                ShaderModuleIdentifierError::FeatureNotEnabled => {
+                    "the `shader_module_identifier` feature is not enabled"
+                }
+            }
+        )
+    }
+}
+
+/// Error that can happen when creating a shader module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderModuleCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The SPIR-V code couldn't be reflected.
+    ReflectError(ReflectError),
+    /// The source code couldn't be compiled to SPIR-V. Contains the messages of the compiler.
+    CompilationFailed(String),
+}
+
+impl error::Error for ShaderModuleCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ShaderModuleCreationError::OomError(ref err) => Some(err),
+            ShaderModuleCreationError::ReflectError(ref err) => Some(err),
+            _ => None,
         }
     }
 }
 
+impl fmt::Display for ShaderModuleCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ShaderModuleCreationError::OomError(_) => write!(fmt, "not enough memory available"),
+            ShaderModuleCreationError::ReflectError(_) => {
+                write!(fmt, "the SPIR-V code couldn't be reflected")
+            }
+            ShaderModuleCreationError::CompilationFailed(ref messages) => {
+                write!(fmt, "the source code couldn't be compiled: {}", messages)
+            }
+        }
+    }
+}
+
+impl From<OomError> for ShaderModuleCreationError {
+    #[inline]
+    fn from(err: OomError) -> ShaderModuleCreationError {
+        ShaderModuleCreationError::OomError(err)
+    }
+}
+
+impl From<ReflectError> for ShaderModuleCreationError {
+    #[inline]
+    fn from(err: ReflectError) -> ShaderModuleCreationError {
+        ShaderModuleCreationError::ReflectError(err)
+    }
+}
+
 pub unsafe trait GraphicsEntryPointAbstract: EntryPointAbstract {
     type InputDefinition: ShaderInterfaceDef;
     type OutputDefinition: ShaderInterfaceDef;
@@ -469,6 +911,37 @@ unsafe impl ShaderInterfaceDef for EmptyShaderInterfaceDef {
     }
 }
 
+/// Runtime description of a shader interface.
+///
+/// Can be built manually or obtained by reflecting SPIR-V code with the `reflect` module.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeShaderInterfaceDef {
+    elements: Vec<ShaderInterfaceDefEntry>,
+}
+
+impl RuntimeShaderInterfaceDef {
+    /// Builds a new `RuntimeShaderInterfaceDef` from a list of elements.
+    ///
+    /// # Safety
+    ///
+    /// - Must only provide one entry per location.
+    /// - The format of each element must not be larger than 128 bits.
+    ///
+    #[inline]
+    pub unsafe fn new(elements: Vec<ShaderInterfaceDefEntry>) -> RuntimeShaderInterfaceDef {
+        RuntimeShaderInterfaceDef { elements }
+    }
+}
+
+unsafe impl ShaderInterfaceDef for RuntimeShaderInterfaceDef {
+    type Iter = std::vec::IntoIter<ShaderInterfaceDefEntry>;
+
+    #[inline]
+    fn elements(&self) -> Self::Iter {
+        self.elements.clone().into_iter()
+    }
+}
+
 /// Extension trait for `ShaderInterfaceDef` that specifies that the interface is potentially
 /// compatible with another one.
 pub unsafe trait ShaderInterfaceDefMatch<I>: ShaderInterfaceDef
@@ -586,7 +1059,7 @@ impl fmt::Display for ShaderInterfaceMismatchError {
 /// method of this trait indicates how to grab them.
 ///
 /// Boolean specialization constants must be stored as 32bits integers, where `0` means `false` and
-/// any non-zero value means `true`. Integer and floating-point specialization constants are
+/// `1` means `true`. Integer and floating-point specialization constants are
 /// stored as their Rust equivalent.
 ///
 /// This trait is implemented on `()` for shaders that don't have any specialization constant.
@@ -671,3 +1144,91 @@ pub struct SpecializationMapEntry {
     /// Size of the data in bytes. Must match the size of the constant (`4` for booleans).
     pub size: usize,
 }
+
+/// Error that can happen when checking specialization constants against a shader module.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SpecializationConstantsError {
+    /// Two entries have the same constant ID.
+    DuplicateConstantId { constant_id: u32 },
+    /// The data of an entry is outside of the specialization constants struct.
+    OutOfBounds { constant_id: u32 },
+    /// The module doesn't have a specialization constant with this constant ID.
+    UnknownConstantId { constant_id: u32 },
+    /// The size of an entry doesn't match the type of the constant in the module.
+    SizeMismatch {
+        constant_id: u32,
+        expected: usize,
+        obtained: usize,
+    },
+    /// The value of a boolean constant is neither `0` nor `1`.
+    InvalidBool { constant_id: u32, value: u32 },
+}
+
+impl error::Error for SpecializationConstantsError {}
+
+impl fmt::Display for SpecializationConstantsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            SpecializationConstantsError::DuplicateConstantId { constant_id } => write!(
+                fmt,
+                "the constant ID {} is specialized more than once",
+                constant_id
+            ),
+            SpecializationConstantsError::OutOfBounds { constant_id } => write!(
+                fmt,
+                "the data of the constant ID {} is outside of the specialization constants",
+                constant_id
+            ),
+            SpecializationConstantsError::UnknownConstantId { constant_id } => write!(
+                fmt,
+                "the shader module has no specialization constant with the constant ID {}",
+                constant_id
+            ),
+            SpecializationConstantsError::SizeMismatch {
+                constant_id,
+                expected,
+                obtained,
+            } => write!(
+                fmt,
+                "the constant ID {} has a size of {} bytes, but {} bytes were provided",
+                constant_id, expected, obtained
+            ),
+            SpecializationConstantsError::InvalidBool { constant_id, value } => write!(
+                fmt,
+                "the boolean constant ID {} has the value {}, which is neither 0 nor 1",
+                constant_id, value
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::shader::ShaderModuleIdentifier;
+    use crate::vk;
+
+    #[test]
+    fn identifier_bytes() {
+        let identifier = ShaderModuleIdentifier::from_bytes(&[0xde, 0xad, 0xbe, 0xef]).unwrap();
+        assert_eq!(identifier.as_bytes(), &[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            format!("{:?}", identifier),
+            "ShaderModuleIdentifier(deadbeef)"
+        );
+
+        // The unused bytes are not part of the identifier.
+        assert_ne!(
+            identifier,
+            ShaderModuleIdentifier::from_bytes(&[0xde, 0xad]).unwrap()
+        );
+    }
+
+    #[test]
+    fn identifier_bytes_out_of_range() {
+        let max = vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT as usize;
+        assert!(ShaderModuleIdentifier::from_bytes(&[]).is_none());
+        assert!(ShaderModuleIdentifier::from_bytes(&vec![1; max]).is_some());
+        assert!(ShaderModuleIdentifier::from_bytes(&vec![1; max + 1]).is_none());
+    }
+}
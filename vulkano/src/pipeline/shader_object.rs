@@ -0,0 +1,352 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Shaders that can be bound without a pipeline object.
+//!
+//! With the `VK_EXT_shader_object` extension, each shader stage can be compiled on its own into a
+//! `ShaderObject` and bound directly to a command buffer with `bind_shaders`. All the states that
+//! would otherwise be part of a graphics pipeline must then be set dynamically before drawing.
+//!
+//! This avoids creating a pipeline for each combination of shaders and states, which is
+//! convenient for tools and editors where these combinations aren't known in advance.
+
+use smallvec::SmallVec;
+use std::error;
+use std::ffi::CStr;
+use std::fmt;
+use std::mem;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+
+use crate::descriptor::descriptor::ShaderStages;
+use crate::descriptor::pipeline_layout::PipelineLayoutAbstract;
+use crate::descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::pipeline::shader::SpecializationConstants;
+
+use crate::check_errors;
+use crate::vk;
+use crate::Error;
+use crate::OomError;
+use crate::VulkanObject;
+
+/// A single shader stage that can be bound to a command buffer without a pipeline.
+#[derive(Debug)]
+pub struct ShaderObject {
+    // The shader.
+    shader: vk::ShaderEXT,
+    // Pointer to the device.
+    device: Arc<Device>,
+    // The stage of the shader.
+    stage: ShaderObjectStage,
+}
+
+impl ShaderObject {
+    /// Builds a new shader object from the SPIR-V bytes of a module and the name of one of its
+    /// entry points.
+    ///
+    /// `next_stages` are the stages that may be bound after this one when drawing. `layout`
+    /// provides the descriptor set layouts and push constant ranges used by the shader.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `layout` doesn't belong to `device`.
+    ///
+    /// # Safety
+    ///
+    /// - The SPIR-V code is not validated.
+    /// - The SPIR-V code may require some features that are not enabled. This isn't checked by
+    ///   this function either.
+    /// - The user must check that the entry point exists in the module and is of the given stage.
+    /// - `layout` must correctly describe the layout used by the shader.
+    ///
+    pub unsafe fn new<L, Sc>(
+        device: Arc<Device>,
+        stage: ShaderObjectStage,
+        next_stages: ShaderStages,
+        spirv: &[u8],
+        entry_point: &CStr,
+        layout: &L,
+        specialization: &Sc,
+    ) -> Result<Arc<ShaderObject>, ShaderObjectCreationError>
+    where
+        L: PipelineLayoutAbstract,
+        Sc: SpecializationConstants,
+    {
+        debug_assert!(spirv.len().is_multiple_of(4));
+        assert_eq!(device.internal_object(), layout.device().internal_object());
+
+        if !device.loaded_extensions().ext_shader_object {
+            return Err(ShaderObjectCreationError::ExtensionNotEnabled);
+        }
+
+        if !device.enabled_features().shader_object {
+            return Err(ShaderObjectCreationError::FeatureNotEnabled);
+        }
+
+        if stage
+            .supported_next_stages()
+            .is_superset_of(&next_stages)
+            .is_err()
+        {
+            return Err(ShaderObjectCreationError::InvalidNextStages);
+        }
+
+        // `PipelineLayout` creates a descriptor set layout for every set, including empty ones.
+        let set_layouts = (0..layout.num_sets())
+            .map(|num| layout.descriptor_set_layout(num).unwrap().internal_object())
+            .collect::<SmallVec<[_; 16]>>();
+
+        let push_constants = (0..layout.num_push_constants_ranges())
+            .filter_map(|num| layout.push_constants_range(num))
+            .map(
+                |PipelineLayoutDescPcRange {
+                     offset,
+                     size,
+                     stages,
+                 }| vk::PushConstantRange {
+                    stageFlags: stages.into_vulkan_bits(),
+                    offset: offset as u32,
+                    size: size as u32,
+                },
+            )
+            .collect::<SmallVec<[_; 8]>>();
+
+        let spec_descriptors = Sc::descriptors();
+        let specialization = vk::SpecializationInfo {
+            mapEntryCount: spec_descriptors.len() as u32,
+            pMapEntries: spec_descriptors.as_ptr() as *const _,
+            dataSize: mem::size_of_val(specialization),
+            pData: specialization as *const Sc as *const _,
+        };
+
+        let shader = {
+            let infos = vk::ShaderCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_SHADER_CREATE_INFO_EXT,
+                pNext: ptr::null(),
+                flags: 0,
+                stage: stage.into_vulkan_bits(),
+                nextStage: next_stages.into_vulkan_bits(),
+                codeType: vk::SHADER_CODE_TYPE_SPIRV_EXT,
+                codeSize: spirv.len(),
+                pCode: spirv.as_ptr() as *const _,
+                pName: entry_point.as_ptr(),
+                setLayoutCount: set_layouts.len() as u32,
+                pSetLayouts: set_layouts.as_ptr(),
+                pushConstantRangeCount: push_constants.len() as u32,
+                pPushConstantRanges: push_constants.as_ptr(),
+                pSpecializationInfo: if spec_descriptors.is_empty() {
+                    ptr::null()
+                } else {
+                    &specialization
+                },
+            };
+
+            let vk = device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateShadersEXT(
+                device.internal_object(),
+                1,
+                &infos,
+                device.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(ShaderObject {
+            shader,
+            device,
+            stage,
+        }))
+    }
+
+    /// Returns the stage of the shader.
+    #[inline]
+    pub fn stage(&self) -> ShaderObjectStage {
+        self.stage
+    }
+}
+
+unsafe impl DeviceOwned for ShaderObject {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for ShaderObject {
+    type Object = vk::ShaderEXT;
+
+    const TYPE: vk::ObjectType = vk::OBJECT_TYPE_SHADER_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::ShaderEXT {
+        self.shader
+    }
+}
+
+impl Drop for ShaderObject {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroyShaderEXT(
+                self.device.internal_object(),
+                self.shader,
+                self.device.alloc_ptr(),
+            );
+        }
+    }
+}
+
+/// The stage of a `ShaderObject`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ShaderObjectStage {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    Compute,
+}
+
+impl ShaderObjectStage {
+    /// Returns the stages that are allowed to follow this one.
+    #[inline]
+    pub fn supported_next_stages(&self) -> ShaderStages {
+        match *self {
+            ShaderObjectStage::Vertex => ShaderStages {
+                tessellation_control: true,
+                geometry: true,
+                fragment: true,
+                ..ShaderStages::none()
+            },
+            ShaderObjectStage::TessellationControl => ShaderStages {
+                tessellation_evaluation: true,
+                ..ShaderStages::none()
+            },
+            ShaderObjectStage::TessellationEvaluation => ShaderStages {
+                geometry: true,
+                fragment: true,
+                ..ShaderStages::none()
+            },
+            ShaderObjectStage::Geometry => ShaderStages {
+                fragment: true,
+                ..ShaderStages::none()
+            },
+            ShaderObjectStage::Fragment | ShaderObjectStage::Compute => ShaderStages::none(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::ShaderStageFlagBits {
+        match self {
+            ShaderObjectStage::Vertex => vk::SHADER_STAGE_VERTEX_BIT,
+            ShaderObjectStage::TessellationControl => vk::SHADER_STAGE_TESSELLATION_CONTROL_BIT,
+            ShaderObjectStage::TessellationEvaluation => {
+                vk::SHADER_STAGE_TESSELLATION_EVALUATION_BIT
+            }
+            ShaderObjectStage::Geometry => vk::SHADER_STAGE_GEOMETRY_BIT,
+            ShaderObjectStage::Fragment => vk::SHADER_STAGE_FRAGMENT_BIT,
+            ShaderObjectStage::Compute => vk::SHADER_STAGE_COMPUTE_BIT,
+        }
+    }
+}
+
+/// Error that can happen when creating a shader object.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderObjectCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The `VK_EXT_shader_object` extension was not enabled.
+    ExtensionNotEnabled,
+    /// The `shader_object` feature was not enabled.
+    FeatureNotEnabled,
+    /// The next stages contain a stage that can't follow the stage of the shader.
+    InvalidNextStages,
+}
+
+impl error::Error for ShaderObjectCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ShaderObjectCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ShaderObjectCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                ShaderObjectCreationError::OomError(_) => "not enough memory available",
+                ShaderObjectCreationError::ExtensionNotEnabled => {
+                    "the `VK_EXT_shader_object` extension was not enabled"
+                }
+                ShaderObjectCreationError::FeatureNotEnabled => {
+                    "the `shader_object` feature was not enabled"
+                }
+                ShaderObjectCreationError::InvalidNextStages => {
+                    "the next stages contain a stage that can't follow the stage of the shader"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for ShaderObjectCreationError {
+    #[inline]
+    fn from(err: OomError) -> ShaderObjectCreationError {
+        ShaderObjectCreationError::OomError(err)
+    }
+}
+
+impl From<Error> for ShaderObjectCreationError {
+    #[inline]
+    fn from(err: Error) -> ShaderObjectCreationError {
+        match err {
+            err @ Error::OutOfHostMemory => {
+                ShaderObjectCreationError::OomError(OomError::from(err))
+            }
+            err @ Error::OutOfDeviceMemory => {
+                ShaderObjectCreationError::OomError(OomError::from(err))
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::descriptor::descriptor::ShaderStages;
+    use crate::pipeline::shader_object::ShaderObjectStage;
+
+    #[test]
+    fn supported_next_stages() {
+        let vertex = ShaderObjectStage::Vertex.supported_next_stages();
+        assert!(vertex.fragment && vertex.geometry && vertex.tessellation_control);
+        assert!(!vertex.tessellation_evaluation && !vertex.vertex && !vertex.compute);
+
+        assert_eq!(
+            ShaderObjectStage::Compute.supported_next_stages(),
+            ShaderStages::none()
+        );
+        assert_eq!(
+            ShaderObjectStage::Fragment.supported_next_stages(),
+            ShaderStages::none()
+        );
+    }
+}
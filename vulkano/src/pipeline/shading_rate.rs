@@ -0,0 +1,156 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Shading of multiple pixels with a single fragment shader invocation.
+//!
+//! With the `VK_KHR_fragment_shading_rate` extension, a fragment shader invocation can cover a
+//! block of several pixels instead of a single one. This reduces the cost of shading parts of the
+//! framebuffer where a high level of detail isn't needed, for example at the periphery of the
+//! view in foveated rendering.
+//!
+//! The size of the fragments is determined by combining three sources, each of which can be
+//! enabled with its own device feature:
+//!
+//! - The pipeline shading rate, set when building the graphics pipeline or dynamically before
+//!   drawing (`pipeline_fragment_shading_rate`).
+//! - The primitive shading rate, written by the last pre-rasterization shader stage to the
+//!   `PrimitiveShadingRateKHR` built-in (`primitive_fragment_shading_rate`).
+//! - The attachment shading rate, read from a fragment shading rate attachment of the subpass
+//!   (`attachment_fragment_shading_rate`).
+//!
+//! The pipeline shading rate is first combined with the primitive shading rate with the first
+//! combiner operation, then the result is combined with the attachment shading rate with the
+//! second combiner operation.
+
+use crate::vk;
+
+/// Describes the fragment shading rate state of a graphics pipeline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FragmentShadingRateState {
+    /// Each fragment covers a single pixel. This is the default.
+    Disabled,
+
+    /// The shading rate is fixed when the pipeline is built.
+    Fixed(FragmentShadingRate),
+
+    /// The shading rate will need to be specified when you submit a draw command.
+    Dynamic,
+}
+
+impl FragmentShadingRateState {
+    /// Returns true if equal to `FragmentShadingRateState::Dynamic`.
+    #[inline]
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, FragmentShadingRateState::Dynamic)
+    }
+}
+
+impl Default for FragmentShadingRateState {
+    #[inline]
+    fn default() -> FragmentShadingRateState {
+        FragmentShadingRateState::Disabled
+    }
+}
+
+/// A pipeline fragment shading rate, and how it is combined with the other shading rates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FragmentShadingRate {
+    /// The width and height in pixels of each fragment. Each value must be 1, 2 or 4.
+    pub fragment_size: [u32; 2],
+
+    /// The operations used to combine the pipeline shading rate with the primitive shading rate,
+    /// then the result with the attachment shading rate.
+    pub combiner_ops: [FragmentShadingRateCombinerOp; 2],
+}
+
+impl FragmentShadingRate {
+    /// Builds a `FragmentShadingRate` that uses `fragment_size` and ignores the primitive and
+    /// attachment shading rates.
+    #[inline]
+    pub fn new(fragment_size: [u32; 2]) -> FragmentShadingRate {
+        FragmentShadingRate {
+            fragment_size,
+            combiner_ops: [FragmentShadingRateCombinerOp::Keep; 2],
+        }
+    }
+
+    /// Returns true if the fragment size is a valid pipeline fragment size.
+    #[inline]
+    pub(crate) fn is_valid_fragment_size(&self) -> bool {
+        self.fragment_size
+            .iter()
+            .all(|&size| size == 1 || size == 2 || size == 4)
+    }
+}
+
+impl Default for FragmentShadingRate {
+    #[inline]
+    fn default() -> FragmentShadingRate {
+        FragmentShadingRate::new([1, 1])
+    }
+}
+
+/// How two fragment shading rates are combined into one.
+///
+/// In the descriptions below, `A` is the shading rate that results from the previous step and `B`
+/// is the new shading rate that is being combined with it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum FragmentShadingRateCombinerOp {
+    /// The result is `A`.
+    Keep = vk::FRAGMENT_SHADING_RATE_COMBINER_OP_KEEP_KHR,
+    /// The result is `B`.
+    Replace = vk::FRAGMENT_SHADING_RATE_COMBINER_OP_REPLACE_KHR,
+    /// The result is the minimum of `A` and `B` in each dimension.
+    Min = vk::FRAGMENT_SHADING_RATE_COMBINER_OP_MIN_KHR,
+    /// The result is the maximum of `A` and `B` in each dimension.
+    Max = vk::FRAGMENT_SHADING_RATE_COMBINER_OP_MAX_KHR,
+    /// The result is the product of `A` and `B` in each dimension.
+    Mul = vk::FRAGMENT_SHADING_RATE_COMBINER_OP_MUL_KHR,
+}
+
+impl FragmentShadingRateCombinerOp {
+    /// Returns true if the operation is `Keep` or `Replace`.
+    #[inline]
+    pub fn is_trivial(&self) -> bool {
+        matches!(
+            self,
+            FragmentShadingRateCombinerOp::Keep | FragmentShadingRateCombinerOp::Replace
+        )
+    }
+}
+
+impl From<FragmentShadingRateCombinerOp> for vk::FragmentShadingRateCombinerOpKHR {
+    #[inline]
+    fn from(val: FragmentShadingRateCombinerOp) -> Self {
+        val as Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::shading_rate::FragmentShadingRate;
+    use crate::pipeline::shading_rate::FragmentShadingRateCombinerOp;
+
+    #[test]
+    fn fragment_size_validity() {
+        assert!(FragmentShadingRate::new([1, 1]).is_valid_fragment_size());
+        assert!(FragmentShadingRate::new([4, 2]).is_valid_fragment_size());
+        assert!(!FragmentShadingRate::new([0, 1]).is_valid_fragment_size());
+        assert!(!FragmentShadingRate::new([3, 1]).is_valid_fragment_size());
+        assert!(!FragmentShadingRate::new([1, 8]).is_valid_fragment_size());
+    }
+
+    #[test]
+    fn trivial_combiner_ops() {
+        assert!(FragmentShadingRateCombinerOp::Keep.is_trivial());
+        assert!(FragmentShadingRateCombinerOp::Replace.is_trivial());
+        assert!(!FragmentShadingRateCombinerOp::Mul.is_trivial());
+    }
+}
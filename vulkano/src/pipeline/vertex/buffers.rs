@@ -0,0 +1,295 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::mem;
+use std::sync::Arc;
+use std::vec::IntoIter as VecIntoIter;
+
+use crate::buffer::BufferAccess;
+use crate::pipeline::shader::ShaderInterfaceDef;
+use crate::pipeline::vertex::AttributeInfo;
+use crate::pipeline::vertex::IncompatibleVertexDefinitionError;
+use crate::pipeline::vertex::InputRate;
+use crate::pipeline::vertex::Vertex;
+use crate::pipeline::vertex::VertexDefinition;
+use crate::pipeline::vertex::VertexMemberInfo;
+use crate::pipeline::vertex::VertexSource;
+
+/// A vertex definition with any number of bindings, each one reading a buffer of a type that
+/// implements `Vertex`.
+///
+/// The bindings are numbered in the order in which they are added. A shader input is read from
+/// the first binding whose type has a member with the same name.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::pipeline::vertex::BuffersDefinition;
+/// use vulkano::pipeline::vertex::Vertex;
+///
+/// #[repr(C)]
+/// #[derive(Vertex)]
+/// struct Position {
+///     position: [f32; 3],
+/// }
+///
+/// #[repr(C)]
+/// #[derive(Vertex)]
+/// struct Normal {
+///     normal: [f32; 3],
+/// }
+///
+/// #[repr(C)]
+/// #[derive(Vertex)]
+/// struct Instance {
+///     offset: [f32; 3],
+/// }
+///
+/// let definition = BuffersDefinition::new()
+///     .vertex::<Position>()
+///     .vertex::<Normal>()
+///     .instance::<Instance>();
+/// ```
+#[derive(Clone, Default)]
+pub struct BuffersDefinition {
+    bindings: Vec<Binding>,
+}
+
+#[derive(Clone, Copy)]
+struct Binding {
+    member: fn(&str) -> Option<VertexMemberInfo>,
+    stride: usize,
+    input_rate: InputRate,
+}
+
+impl BuffersDefinition {
+    /// Builds a definition without any binding.
+    #[inline]
+    pub fn new() -> BuffersDefinition {
+        BuffersDefinition {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Adds a binding that advances to the next element of its buffer for every vertex.
+    #[inline]
+    pub fn vertex<V: Vertex>(self) -> BuffersDefinition {
+        self.binding::<V>(InputRate::Vertex)
+    }
+
+    /// Adds a binding that advances to the next element of its buffer for every instance.
+    #[inline]
+    pub fn instance<V: Vertex>(self) -> BuffersDefinition {
+        self.instance_with_divisor::<V>(1)
+    }
+
+    /// Adds a binding that advances to the next element of its buffer every `divisor` instances.
+    /// See `SingleInstanceBufferDefinition::with_divisor`.
+    #[inline]
+    pub fn instance_with_divisor<V: Vertex>(self, divisor: u32) -> BuffersDefinition {
+        self.binding::<V>(InputRate::Instance { divisor })
+    }
+
+    #[inline]
+    fn binding<V: Vertex>(mut self, input_rate: InputRate) -> BuffersDefinition {
+        self.bindings.push(Binding {
+            member: V::member,
+            stride: mem::size_of::<V>(),
+            input_rate,
+        });
+        self
+    }
+}
+
+unsafe impl<I> VertexDefinition<I> for BuffersDefinition
+where
+    I: ShaderInterfaceDef,
+{
+    type BuffersIter = VecIntoIter<(u32, usize, InputRate)>;
+    type AttribsIter = VecIntoIter<(u32, u32, AttributeInfo)>;
+
+    fn definition(
+        &self,
+        interface: &I,
+    ) -> Result<(Self::BuffersIter, Self::AttribsIter), IncompatibleVertexDefinitionError> {
+        let mut attribs = Vec::with_capacity(interface.elements().len());
+        for e in interface.elements() {
+            let name = e.name.as_ref().unwrap();
+
+            let (infos, binding) = match self
+                .bindings
+                .iter()
+                .enumerate()
+                .find_map(|(num, binding)| (binding.member)(name).map(|infos| (infos, num)))
+            {
+                Some(v) => v,
+                None => {
+                    return Err(IncompatibleVertexDefinitionError::MissingAttribute {
+                        attribute: name.clone().into_owned(),
+                    })
+                }
+            };
+
+            let format = infos.format.unwrap_or(e.format);
+
+            if !infos
+                .ty
+                .matches(infos.array_size, format, e.location.end - e.location.start)
+            {
+                return Err(IncompatibleVertexDefinitionError::FormatMismatch {
+                    attribute: name.clone().into_owned(),
+                    shader: (e.format, (e.location.end - e.location.start) as usize),
+                    definition: (infos.ty, infos.array_size),
+                });
+            }
+
+            let mut offset = infos.offset;
+            for loc in e.location.clone() {
+                attribs.push((loc, binding as u32, AttributeInfo { offset, format }));
+                offset += format.size().unwrap();
+            }
+        }
+
+        let buffers = self
+            .bindings
+            .iter()
+            .enumerate()
+            .map(|(num, binding)| (num as u32, binding.stride, binding.input_rate))
+            .collect::<Vec<_>>();
+
+        Ok((buffers.into_iter(), attribs.into_iter()))
+    }
+}
+
+unsafe impl VertexSource<Vec<Arc<dyn BufferAccess + Send + Sync>>> for BuffersDefinition {
+    #[inline]
+    fn decode(
+        &self,
+        source: Vec<Arc<dyn BufferAccess + Send + Sync>>,
+    ) -> (Vec<Box<dyn BufferAccess + Send + Sync>>, usize, usize) {
+        // FIXME: safety
+        assert_eq!(source.len(), self.bindings.len());
+
+        let mut vertices = None;
+        let mut instances = None;
+        for (buffer, binding) in source.iter().zip(self.bindings.iter()) {
+            let len = buffer.size() / binding.stride;
+            let (count, limit) = match binding.input_rate {
+                InputRate::Vertex => (&mut vertices, len),
+                // A divisor of 0 uses the same element for all the instances.
+                InputRate::Instance { divisor: 0 } => continue,
+                InputRate::Instance { divisor } => (&mut instances, len * divisor as usize),
+            };
+            *count = Some(count.map_or(limit, |count: usize| count.min(limit)));
+        }
+
+        (
+            source
+                .into_iter()
+                .map(|buffer| Box::new(buffer) as Box<_>)
+                .collect(),
+            vertices.unwrap_or(1),
+            instances.unwrap_or(1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Format;
+    use crate::pipeline::shader::RuntimeShaderInterfaceDef;
+    use crate::pipeline::shader::ShaderInterfaceDefEntry;
+    use crate::pipeline::vertex::BuffersDefinition;
+    use crate::pipeline::vertex::IncompatibleVertexDefinitionError;
+    use crate::pipeline::vertex::InputRate;
+    use crate::pipeline::vertex::Vertex;
+    use crate::pipeline::vertex::VertexDefinition;
+    use std::borrow::Cow;
+
+    #[repr(C)]
+    #[derive(Vertex)]
+    struct Position {
+        position: [f32; 3],
+    }
+
+    #[repr(C)]
+    #[derive(Vertex)]
+    struct Instance {
+        #[format(R8G8B8A8Unorm)]
+        color: [u8; 4],
+        offset: [f32; 2],
+    }
+
+    fn entry(location: u32, format: Format, name: &'static str) -> ShaderInterfaceDefEntry {
+        ShaderInterfaceDefEntry {
+            location: location..location + 1,
+            format,
+            name: Some(Cow::Borrowed(name)),
+        }
+    }
+
+    #[test]
+    fn multiple_bindings() {
+        let definition = BuffersDefinition::new()
+            .vertex::<Position>()
+            .instance_with_divisor::<Instance>(2);
+        let interface = unsafe {
+            RuntimeShaderInterfaceDef::new(vec![
+                entry(0, Format::R32G32B32Sfloat, "position"),
+                entry(1, Format::R32G32B32A32Sfloat, "color"),
+                entry(2, Format::R32G32Sfloat, "offset"),
+            ])
+        };
+
+        let (buffers, attribs) = definition.definition(&interface).unwrap();
+        assert_eq!(
+            buffers.collect::<Vec<_>>(),
+            vec![
+                (0, 12, InputRate::Vertex),
+                (1, 12, InputRate::Instance { divisor: 2 }),
+            ]
+        );
+
+        let attribs = attribs
+            .map(|(loc, binding, info)| (loc, binding, info.offset, info.format))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            attribs,
+            vec![
+                (0, 0, 0, Format::R32G32B32Sfloat),
+                (1, 1, 0, Format::R8G8B8A8Unorm),
+                (2, 1, 4, Format::R32G32Sfloat),
+            ]
+        );
+    }
+
+    #[test]
+    fn mismatched_format() {
+        let definition = BuffersDefinition::new().vertex::<Position>();
+        let interface =
+            unsafe { RuntimeShaderInterfaceDef::new(vec![entry(0, Format::R32G32B32Uint, "position")]) };
+
+        match definition.definition(&interface) {
+            Err(IncompatibleVertexDefinitionError::FormatMismatch { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn missing_attribute() {
+        let definition = BuffersDefinition::new().vertex::<Position>();
+        let interface =
+            unsafe { RuntimeShaderInterfaceDef::new(vec![entry(0, Format::R32G32Sfloat, "offset")]) };
+
+        match definition.definition(&interface) {
+            Err(IncompatibleVertexDefinitionError::MissingAttribute { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -52,13 +52,28 @@ where
 }
 
 /// How the vertex source should be unrolled.
-#[derive(Copy, Clone, Debug)]
-#[repr(u32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum InputRate {
     /// Each element of the source corresponds to a vertex.
-    Vertex = vk::VERTEX_INPUT_RATE_VERTEX,
-    /// Each element of the source corresponds to an instance.
-    Instance = vk::VERTEX_INPUT_RATE_INSTANCE,
+    Vertex,
+
+    /// Each element of the source corresponds to `divisor` consecutive instances.
+    ///
+    /// A divisor different from 1 requires the `ext_vertex_attribute_divisor` device extension
+    /// and the `vertex_attribute_instance_rate_divisor` feature. A divisor of 0 means that all the
+    /// instances use the same element, and requires the
+    /// `vertex_attribute_instance_rate_zero_divisor` feature.
+    Instance { divisor: u32 },
+}
+
+impl From<InputRate> for vk::VertexInputRate {
+    #[inline]
+    fn from(val: InputRate) -> vk::VertexInputRate {
+        match val {
+            InputRate::Vertex => vk::VERTEX_INPUT_RATE_VERTEX,
+            InputRate::Instance { .. } => vk::VERTEX_INPUT_RATE_INSTANCE,
+        }
+    }
 }
 
 /// Information about a single attribute within a vertex.
@@ -8,8 +8,13 @@
 // according to those terms.
 
 use crate::pipeline::vertex::VertexMemberTy;
+use half::f16;
 
 /// Implements the `Vertex` trait on a struct.
+///
+/// `#[derive(Vertex)]` is usually more convenient, as it doesn't require the struct to implement
+/// `Default` and supports custom names and formats. See the documentation of `Vertex`.
+///
 ///# Example
 ///
 ///```
@@ -29,7 +34,6 @@ macro_rules! impl_vertex {
         unsafe impl $crate::pipeline::vertex::Vertex for $out {
             #[inline(always)]
             fn member(name: &str) -> Option<$crate::pipeline::vertex::VertexMemberInfo> {
-                use std::ptr;
                 #[allow(unused_imports)]
                 use $crate::format::Format;
                 use $crate::pipeline::vertex::VertexMemberInfo;
@@ -49,6 +53,7 @@ macro_rules! impl_vertex {
                             offset: member_ptr as usize - dummy_ptr as usize,
                             ty: ty,
                             array_size: array_size,
+                            format: None,
                         });
                     }
                 )*
@@ -107,6 +112,13 @@ unsafe impl VertexMember for u32 {
     }
 }
 
+unsafe impl VertexMember for f16 {
+    #[inline]
+    fn format() -> (VertexMemberTy, usize) {
+        (VertexMemberTy::F16, 1)
+    }
+}
+
 unsafe impl VertexMember for f32 {
     #[inline]
     fn format() -> (VertexMemberTy, usize) {
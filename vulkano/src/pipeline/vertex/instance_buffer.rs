@@ -24,12 +24,41 @@ use crate::pipeline::vertex::VertexDefinition;
 use crate::pipeline::vertex::VertexSource;
 
 /// Same as `SingleBufferDefinition` but advances by instance.
-pub struct SingleInstanceBufferDefinition<T>(pub PhantomData<T>);
+pub struct SingleInstanceBufferDefinition<T> {
+    divisor: u32,
+    marker: PhantomData<T>,
+}
 
 impl<T> SingleInstanceBufferDefinition<T> {
+    /// Builds a definition that advances to the next element of the buffer for every instance.
     #[inline]
     pub fn new() -> SingleInstanceBufferDefinition<T> {
-        SingleInstanceBufferDefinition(PhantomData)
+        SingleInstanceBufferDefinition::with_divisor(1)
+    }
+
+    /// Builds a definition that advances to the next element of the buffer every `divisor`
+    /// instances. A divisor of 0 uses the first element for all the instances.
+    ///
+    /// A divisor other than 1 requires the `ext_vertex_attribute_divisor` device extension and
+    /// the `vertex_attribute_instance_rate_divisor` feature, or the
+    /// `vertex_attribute_instance_rate_zero_divisor` feature for a divisor of 0. It must not
+    /// exceed the `max_vertex_attrib_divisor` limit of the device.
+    #[inline]
+    pub fn with_divisor(divisor: u32) -> SingleInstanceBufferDefinition<T> {
+        SingleInstanceBufferDefinition {
+            divisor,
+            marker: PhantomData,
+        }
+    }
+
+    // Returns the number of instances that can be drawn with `len` elements in the buffer.
+    #[inline]
+    fn num_instances(&self, len: usize) -> usize {
+        match self.divisor {
+            // A divisor of 0 uses the same element for all the instances.
+            0 => 1,
+            divisor => len * divisor as usize,
+        }
     }
 }
 
@@ -59,11 +88,12 @@ where
                     }
                 };
 
-                if !infos.ty.matches(
-                    infos.array_size,
-                    e.format,
-                    e.location.end - e.location.start,
-                ) {
+                let format = infos.format.unwrap_or(e.format);
+
+                if !infos
+                    .ty
+                    .matches(infos.array_size, format, e.location.end - e.location.start)
+                {
                     return Err(IncompatibleVertexDefinitionError::FormatMismatch {
                         attribute: name.clone().into_owned(),
                         shader: (e.format, (e.location.end - e.location.start) as usize),
@@ -73,22 +103,22 @@ where
 
                 let mut offset = infos.offset;
                 for loc in e.location.clone() {
-                    attribs.push((
-                        loc,
-                        0,
-                        AttributeInfo {
-                            offset: offset,
-                            format: e.format,
-                        },
-                    ));
-                    offset += e.format.size().unwrap();
+                    attribs.push((loc, 0, AttributeInfo { offset, format }));
+                    offset += format.size().unwrap();
                 }
             }
             attribs
         }
         .into_iter(); // TODO: meh
 
-        let buffers = Some((0, mem::size_of::<T>(), InputRate::Instance)).into_iter();
+        let buffers = Some((
+            0,
+            mem::size_of::<T>(),
+            InputRate::Instance {
+                divisor: self.divisor,
+            },
+        ))
+        .into_iter();
         Ok((buffers, attrib))
     }
 }
@@ -106,7 +136,7 @@ where
         // FIXME: safety
         assert_eq!(source.len(), 1);
         let len = source[0].size() / mem::size_of::<V>();
-        (vec![Box::new(source.remove(0))], 1, len)
+        (vec![Box::new(source.remove(0))], 1, self.num_instances(len))
     }
 }
 
@@ -118,6 +148,41 @@ where
     #[inline]
     fn decode(&self, source: B) -> (Vec<Box<dyn BufferAccess + Send + Sync>>, usize, usize) {
         let len = source.len();
-        (vec![Box::new(source) as Box<_>], 1, len)
+        (vec![Box::new(source) as Box<_>], 1, self.num_instances(len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use crate::pipeline::vertex::SingleInstanceBufferDefinition;
+    use crate::pipeline::vertex::VertexSource;
+
+    #[derive(Default, Debug, Copy, Clone)]
+    struct Instance {
+        offset: [f32; 2],
+    }
+    crate::impl_vertex!(Instance, offset);
+
+    #[test]
+    fn decode_with_divisor() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            false,
+            [Instance::default(); 3].iter().copied(),
+        )
+        .unwrap();
+
+        let (_, vertices, instances) =
+            SingleInstanceBufferDefinition::<Instance>::with_divisor(2).decode(buffer.clone());
+        assert_eq!((vertices, instances), (1, 6));
+
+        let (_, _, instances) =
+            SingleInstanceBufferDefinition::<Instance>::with_divisor(0).decode(buffer);
+        assert_eq!(instances, 1);
     }
 }
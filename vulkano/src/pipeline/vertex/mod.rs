@@ -65,6 +65,7 @@
 
 pub use self::bufferless::BufferlessDefinition;
 pub use self::bufferless::BufferlessVertices;
+pub use self::buffers::BuffersDefinition;
 pub use self::definition::AttributeInfo;
 pub use self::definition::IncompatibleVertexDefinitionError;
 pub use self::definition::InputRate;
@@ -78,8 +79,10 @@ pub use self::two::TwoBuffersDefinition;
 pub use self::vertex::Vertex;
 pub use self::vertex::VertexMemberInfo;
 pub use self::vertex::VertexMemberTy;
+pub use vulkano_macros::Vertex;
 
 mod bufferless;
+mod buffers;
 mod definition;
 mod impl_vertex;
 mod instance_buffer;
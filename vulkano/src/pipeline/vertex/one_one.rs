@@ -24,12 +24,36 @@ use crate::pipeline::vertex::VertexSource;
 
 /// Unstable.
 // TODO: bad way to do things
-pub struct OneVertexOneInstanceDefinition<T, U>(pub PhantomData<(T, U)>);
+pub struct OneVertexOneInstanceDefinition<T, U> {
+    divisor: u32,
+    marker: PhantomData<(T, U)>,
+}
 
 impl<T, U> OneVertexOneInstanceDefinition<T, U> {
+    /// Builds a definition whose second buffer advances to the next element for every instance.
     #[inline]
     pub fn new() -> OneVertexOneInstanceDefinition<T, U> {
-        OneVertexOneInstanceDefinition(PhantomData)
+        OneVertexOneInstanceDefinition::with_divisor(1)
+    }
+
+    /// Builds a definition whose second buffer advances to the next element every `divisor`
+    /// instances. See `SingleInstanceBufferDefinition::with_divisor`.
+    #[inline]
+    pub fn with_divisor(divisor: u32) -> OneVertexOneInstanceDefinition<T, U> {
+        OneVertexOneInstanceDefinition {
+            divisor,
+            marker: PhantomData,
+        }
+    }
+
+    // Returns the number of instances that can be drawn with `len` elements in the second buffer.
+    #[inline]
+    fn num_instances(&self, len: usize) -> usize {
+        match self.divisor {
+            // A divisor of 0 uses the same element for all the instances.
+            0 => 1,
+            divisor => len * divisor as usize,
+        }
     }
 }
 
@@ -61,11 +85,12 @@ where
                     });
                 };
 
-                if !infos.ty.matches(
-                    infos.array_size,
-                    e.format,
-                    e.location.end - e.location.start,
-                ) {
+                let format = infos.format.unwrap_or(e.format);
+
+                if !infos
+                    .ty
+                    .matches(infos.array_size, format, e.location.end - e.location.start)
+                {
                     return Err(IncompatibleVertexDefinitionError::FormatMismatch {
                         attribute: name.clone().into_owned(),
                         shader: (e.format, (e.location.end - e.location.start) as usize),
@@ -75,15 +100,8 @@ where
 
                 let mut offset = infos.offset;
                 for loc in e.location.clone() {
-                    attribs.push((
-                        loc,
-                        buf_offset,
-                        AttributeInfo {
-                            offset: offset,
-                            format: e.format,
-                        },
-                    ));
-                    offset += e.format.size().unwrap();
+                    attribs.push((loc, buf_offset, AttributeInfo { offset, format }));
+                    offset += format.size().unwrap();
                 }
             }
             attribs
@@ -92,7 +110,13 @@ where
 
         let buffers = vec![
             (0, mem::size_of::<T>(), InputRate::Vertex),
-            (1, mem::size_of::<U>(), InputRate::Instance),
+            (
+                1,
+                mem::size_of::<U>(),
+                InputRate::Instance {
+                    divisor: self.divisor,
+                },
+            ),
         ]
         .into_iter();
 
@@ -120,7 +144,7 @@ where
         (
             vec![Box::new(s0) as Box<_>, Box::new(s1) as Box<_>],
             len,
-            inst,
+            self.num_instances(inst),
         )
     }
 }
@@ -139,7 +163,56 @@ where
         (
             vec![Box::new(source.0) as Box<_>, Box::new(source.1) as Box<_>],
             s1l,
-            s2l,
+            self.num_instances(s2l),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use crate::pipeline::vertex::OneVertexOneInstanceDefinition;
+    use crate::pipeline::vertex::VertexSource;
+
+    #[derive(Default, Debug, Copy, Clone)]
+    struct Position {
+        position: [f32; 2],
+    }
+    crate::impl_vertex!(Position, position);
+
+    #[derive(Default, Debug, Copy, Clone)]
+    struct Instance {
+        offset: [f32; 2],
+    }
+    crate::impl_vertex!(Instance, offset);
+
+    #[test]
+    fn decode_with_divisor() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vertices = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            false,
+            [Position::default(); 4].iter().copied(),
+        )
+        .unwrap();
+        let instances = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            false,
+            [Instance::default(); 3].iter().copied(),
+        )
+        .unwrap();
+
+        let definition = OneVertexOneInstanceDefinition::<Position, Instance>::with_divisor(2);
+        let (_, num_vertices, num_instances) =
+            definition.decode((vertices.clone(), instances.clone()));
+        assert_eq!((num_vertices, num_instances), (4, 6));
+
+        let definition = OneVertexOneInstanceDefinition::<Position, Instance>::with_divisor(0);
+        let (_, _, num_instances) = definition.decode((vertices, instances));
+        assert_eq!(num_instances, 1);
+    }
+}
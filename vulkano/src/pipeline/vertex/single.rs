@@ -59,11 +59,14 @@ where
                     }
                 };
 
-                if !infos.ty.matches(
-                    infos.array_size,
-                    e.format,
-                    e.location.end - e.location.start,
-                ) {
+                // The format of the member in the buffer can differ from the format of the
+                // shader input, for example for normalized integers.
+                let format = infos.format.unwrap_or(e.format);
+
+                if !infos
+                    .ty
+                    .matches(infos.array_size, format, e.location.end - e.location.start)
+                {
                     return Err(IncompatibleVertexDefinitionError::FormatMismatch {
                         attribute: name.clone().into_owned(),
                         shader: (e.format, (e.location.end - e.location.start) as usize),
@@ -73,15 +76,8 @@ where
 
                 let mut offset = infos.offset;
                 for loc in e.location.clone() {
-                    attribs.push((
-                        loc,
-                        0,
-                        AttributeInfo {
-                            offset: offset,
-                            format: e.format,
-                        },
-                    ));
-                    offset += e.format.size().unwrap();
+                    attribs.push((loc, 0, AttributeInfo { offset, format }));
+                    offset += format.size().unwrap();
                 }
             }
             attribs
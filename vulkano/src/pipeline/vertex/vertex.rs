@@ -14,6 +14,40 @@ use crate::format::Format;
 ///
 /// At this stage, the vertex is in a "raw" format. For example a `[f32; 4]` can match both a
 /// `vec4` or a `float[4]`. The way the things are bound depends on the shader.
+///
+/// This trait can be implemented with `#[derive(Vertex)]`, which works on structs with named
+/// fields whose types implement `VertexMember`. Each field is matched with the shader input of
+/// the same name, unless a `#[name(...)]` attribute lists other names to match with. A
+/// `#[format(...)]` attribute sets the format of the member in the vertex buffer when it differs
+/// from the format of the shader input, for example to read normalized integers or half floats
+/// into a `vec4`.
+///
+/// As with any `Vertex`, the derived types can be combined into multiple bindings with
+/// definitions such as `BuffersDefinition`, which accepts any number of vertex and instance
+/// bindings.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::pipeline::vertex::Vertex;
+/// use vulkano::pipeline::vertex::VertexMemberTy;
+///
+/// #[repr(C)]
+/// #[derive(Vertex)]
+/// struct MyVertex {
+///     position: [f32; 3],
+///     #[name("in_normal", "normal")]
+///     normal: [f32; 3],
+///     #[format(R8G8B8A8Unorm)]
+///     color: [u8; 4],
+/// }
+///
+/// let normal = MyVertex::member("normal").unwrap();
+/// assert_eq!(normal.offset, 12);
+/// assert_eq!(normal.ty, VertexMemberTy::F32);
+/// assert!(MyVertex::member("in_normal").is_some());
+/// assert!(MyVertex::member("color").unwrap().format.is_some());
+/// ```
 pub unsafe trait Vertex: 'static + Send + Sync {
     /// Returns the characteristics of a vertex member by its name.
     fn member(name: &str) -> Option<VertexMemberInfo>;
@@ -34,6 +68,9 @@ pub struct VertexMemberInfo {
     pub ty: VertexMemberTy,
     /// Number of consecutive elements of that type.
     pub array_size: usize,
+    /// Format of the member in the vertex buffer. If `None`, the format of the corresponding
+    /// shader input is used.
+    pub format: Option<Format>,
 }
 
 /// Type of a member of a vertex struct.
@@ -46,31 +83,89 @@ pub enum VertexMemberTy {
     U16,
     I32,
     U32,
+    F16,
     F32,
     F64,
 }
 
 impl VertexMemberTy {
     /// Returns true if a combination of `(type, array_size)` matches a format.
+    ///
+    /// The type must be the type of the components of the format as they are stored in memory.
+    /// For example `[u8; 4]` matches `R8G8B8A8Unorm` and `R8G8B8A8Uint`, but not `R8G8B8A8Snorm`
+    /// or `R32Uint`. The components of packed formats are matched by the packed integer type.
     #[inline]
     pub fn matches(&self, array_size: usize, format: Format, num_locs: u32) -> bool {
-        // TODO: implement correctly
-        let my_size = match *self {
-            VertexMemberTy::I8 => 1,
-            VertexMemberTy::U8 => 1,
-            VertexMemberTy::I16 => 2,
-            VertexMemberTy::U16 => 2,
-            VertexMemberTy::I32 => 4,
-            VertexMemberTy::U32 => 4,
-            VertexMemberTy::F32 => 4,
-            VertexMemberTy::F64 => 8,
-        };
-
-        let format_size = match format.size() {
+        let (format_ty, format_array_size) = match format.vertex_member() {
             None => return false,
-            Some(s) => s,
+            Some(v) => v,
         };
 
-        array_size * my_size == format_size * num_locs as usize
+        *self == format_ty && array_size == format_array_size * num_locs as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Format;
+    use crate::pipeline::vertex::Vertex;
+    use crate::pipeline::vertex::VertexMemberTy;
+
+    #[test]
+    fn matches_format() {
+        assert!(VertexMemberTy::F32.matches(4, Format::R32G32B32A32Sfloat, 1));
+        assert!(VertexMemberTy::U8.matches(4, Format::R8G8B8A8Unorm, 1));
+        assert!(VertexMemberTy::U32.matches(1, Format::A2B10G10R10UnormPack32, 1));
+        assert!(VertexMemberTy::F32.matches(16, Format::R32G32B32A32Sfloat, 4));
+    }
+
+    #[test]
+    fn mismatched_format() {
+        // Same size, but not the same components.
+        assert!(!VertexMemberTy::F32.matches(1, Format::R8G8B8A8Unorm, 1));
+        assert!(!VertexMemberTy::U32.matches(1, Format::R32Sfloat, 1));
+        assert!(!VertexMemberTy::I8.matches(4, Format::R8G8B8A8Unorm, 1));
+        assert!(!VertexMemberTy::U16.matches(2, Format::R16G16Sfloat, 1));
+        // Not the same number of components.
+        assert!(!VertexMemberTy::F32.matches(3, Format::R32G32B32A32Sfloat, 1));
+        assert!(!VertexMemberTy::F32.matches(4, Format::R32G32B32A32Sfloat, 4));
+        // Formats that can't be used as vertex members.
+        assert!(!VertexMemberTy::U16.matches(1, Format::D16Unorm, 1));
+    }
+
+    #[test]
+    fn derive() {
+        #[repr(C)]
+        #[derive(Vertex)]
+        struct MyVertex {
+            position: [f32; 3],
+            #[name("in_normal", "normal")]
+            normal: [f32; 3],
+            #[format(R8G8B8A8Unorm)]
+            color: [u8; 4],
+            weight: f32,
+        }
+
+        let position = MyVertex::member("position").unwrap();
+        assert_eq!(position.offset, 0);
+        assert_eq!(position.ty, VertexMemberTy::F32);
+        assert_eq!(position.array_size, 3);
+        assert_eq!(position.format, None);
+
+        let normal = MyVertex::member("in_normal").unwrap();
+        assert_eq!(normal.offset, 12);
+        assert_eq!(MyVertex::member("normal").unwrap().offset, 12);
+
+        let color = MyVertex::member("color").unwrap();
+        assert_eq!(color.offset, 24);
+        assert_eq!(color.ty, VertexMemberTy::U8);
+        assert_eq!(color.array_size, 4);
+        assert_eq!(color.format, Some(Format::R8G8B8A8Unorm));
+
+        let weight = MyVertex::member("weight").unwrap();
+        assert_eq!(weight.offset, 28);
+        assert_eq!(weight.array_size, 1);
+
+        assert!(MyVertex::member("unknown").is_none());
     }
 }
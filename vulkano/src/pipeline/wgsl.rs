@@ -0,0 +1,176 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Compilation of WGSL shaders at runtime.
+//!
+//! This module is only available with the `naga` cargo feature. It translates WGSL source code
+//! to SPIR-V with [naga](https://github.com/gfx-rs/naga), then creates a reflected
+//! `ShaderModule` from it, exactly like `ShaderModule::from_words_with_reflection`.
+//!
+//! Contrary to GLSL, a single WGSL source can contain several entry points of different
+//! stages. All of them are present in the resulting module.
+
+use std::sync::Arc;
+
+use crate::device::Device;
+use crate::pipeline::shader::ShaderModule;
+use crate::pipeline::shader::ShaderModuleCreationError;
+
+/// Options for compiling a WGSL shader.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct WgslCompileOptions {
+    spirv_version: (u8, u8),
+    adjust_coordinate_space: bool,
+    debug_info: bool,
+}
+
+impl WgslCompileOptions {
+    /// Builds the default options.
+    ///
+    /// The output is SPIR-V 1.0, and the coordinate space is adjusted to match wgpu.
+    #[inline]
+    pub fn new() -> WgslCompileOptions {
+        WgslCompileOptions {
+            spirv_version: (1, 0),
+            adjust_coordinate_space: true,
+            debug_info: false,
+        }
+    }
+
+    /// Sets the version of the SPIR-V code. Must be supported by the device.
+    #[inline]
+    pub fn spirv_version(mut self, major: u8, minor: u8) -> Self {
+        self.spirv_version = (major, minor);
+        self
+    }
+
+    /// Whether to negate the Y coordinate of the position output by vertex shaders.
+    ///
+    /// The Y axis of the clip space points upwards in WGSL and downwards in Vulkan. Leaving this
+    /// enabled makes shaders shared with wgpu produce the same image with both APIs.
+    #[inline]
+    pub fn adjust_coordinate_space(mut self, adjust: bool) -> Self {
+        self.adjust_coordinate_space = adjust;
+        self
+    }
+
+    /// Includes debug information, such as the names of variables, in the SPIR-V code.
+    #[inline]
+    pub fn debug_info(mut self, debug_info: bool) -> Self {
+        self.debug_info = debug_info;
+        self
+    }
+}
+
+impl Default for WgslCompileOptions {
+    #[inline]
+    fn default() -> WgslCompileOptions {
+        WgslCompileOptions::new()
+    }
+}
+
+/// Compiles WGSL source code to SPIR-V words.
+pub fn compile_wgsl(
+    source: &str,
+    options: &WgslCompileOptions,
+) -> Result<Vec<u32>, ShaderModuleCreationError> {
+    let module = naga::front::wgsl::parse_str(source)
+        .map_err(|err| ShaderModuleCreationError::CompilationFailed(err.emit_to_string(source)))?;
+
+    let info = naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .map_err(|err| ShaderModuleCreationError::CompilationFailed(err.emit_to_string(source)))?;
+
+    let mut flags = naga::back::spv::WriterFlags::LABEL_VARYINGS
+        | naga::back::spv::WriterFlags::CLAMP_FRAG_DEPTH;
+    if options.adjust_coordinate_space {
+        flags |= naga::back::spv::WriterFlags::ADJUST_COORDINATE_SPACE;
+    }
+    if options.debug_info {
+        flags |= naga::back::spv::WriterFlags::DEBUG;
+    }
+    let spv_options = naga::back::spv::Options {
+        lang_version: options.spirv_version,
+        flags,
+        ..naga::back::spv::Options::default()
+    };
+
+    naga::back::spv::write_vec(&module, &info, &spv_options, None)
+        .map_err(|err| ShaderModuleCreationError::CompilationFailed(err.to_string()))
+}
+
+impl ShaderModule {
+    /// Compiles WGSL source code and builds a new reflected shader module from it.
+    ///
+    /// Only available with the `naga` cargo feature.
+    ///
+    /// # Safety
+    ///
+    /// - The SPIR-V code may require some features that are not enabled. This isn't checked by
+    ///   this function.
+    ///
+    pub unsafe fn from_wgsl(
+        device: Arc<Device>,
+        source: &str,
+        options: &WgslCompileOptions,
+    ) -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
+        let spirv = compile_wgsl(source, options)?;
+        ShaderModule::from_words_with_reflection(device, &spirv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::reflect;
+    use crate::pipeline::reflect::EntryPointType;
+    use crate::pipeline::shader::ShaderModuleCreationError;
+    use crate::pipeline::wgsl::compile_wgsl;
+    use crate::pipeline::wgsl::WgslCompileOptions;
+
+    #[test]
+    fn compile_and_reflect() {
+        let source = "
+            @group(0) @binding(1) var<uniform> color: vec4<f32>;
+
+            @vertex
+            fn vs_main(@location(0) position: vec2<f32>) -> @builtin(position) vec4<f32> {
+                return vec4<f32>(position, 0.0, 1.0);
+            }
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                return color;
+            }
+        ";
+
+        let spirv = compile_wgsl(source, &WgslCompileOptions::new()).unwrap();
+        let reflection = reflect::reflect(&spirv).unwrap();
+
+        let vs = reflection.entry_point("vs_main").unwrap();
+        assert!(match vs.ty() {
+            EntryPointType::Graphics(_) => true,
+            _ => false,
+        });
+        let fs = reflection.entry_point("fs_main").unwrap();
+        assert_eq!(fs.descriptors().len(), 1);
+        assert_eq!(fs.descriptors()[0].set, 0);
+        assert_eq!(fs.descriptors()[0].binding, 1);
+    }
+
+    #[test]
+    fn compilation_error() {
+        match compile_wgsl("fn main( {", &WgslCompileOptions::new()) {
+            Err(ShaderModuleCreationError::CompilationFailed(_)) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -12,9 +12,23 @@
 //! In Vulkan, queries are not created individually. Instead you manipulate **query pools**, which
 //! represent a collection of queries. Whenever you use a query, you have to specify both the query
 //! pool and the slot id within that query pool.
+//!
+//! # Occlusion queries
+//!
+//! An occlusion query counts the number of samples that pass the depth and stencil tests between
+//! the `begin_query` and `end_query` commands of a command buffer. Occlusion queries are
+//! allocated from an `OcclusionQueriesPool`, and the results are read from the host with
+//! `OcclusionQueriesPool::get_results` once the command buffer has been executed.
+//!
+//! Unless the `precise` flag of `QueryControlFlags` is set, which requires the
+//! `occlusion_query_precise` feature, the implementation only guarantees that the result is zero
+//! if no sample passed, and non-zero otherwise.
+//!
+//! A query must be reset with `reset_query_pool` before each use.
 
 use std::error;
 use std::fmt;
+use std::mem;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
@@ -26,11 +40,13 @@ use crate::check_errors;
 use crate::vk;
 use crate::Error;
 use crate::OomError;
+use crate::Success;
 use crate::VulkanObject;
 
 pub struct UnsafeQueryPool {
     pool: vk::QueryPool,
     device: Arc<Device>,
+    ty: QueryType,
     num_slots: u32,
 }
 
@@ -68,7 +84,7 @@ impl UnsafeQueryPool {
             check_errors(vk.CreateQueryPool(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -77,10 +93,17 @@ impl UnsafeQueryPool {
         Ok(UnsafeQueryPool {
             pool: pool,
             device: device,
+            ty,
             num_slots: num_slots,
         })
     }
 
+    /// Returns the type of the queries of that query pool.
+    #[inline]
+    pub fn ty(&self) -> QueryType {
+        self.ty
+    }
+
     /// Returns the number of slots of that query pool.
     #[inline]
     pub fn num_slots(&self) -> u32 {
@@ -104,7 +127,7 @@ impl UnsafeQueryPool {
     pub fn queries_range(&self, first_index: u32, count: u32) -> Option<UnsafeQueriesRange> {
         assert!(count >= 1);
 
-        if first_index + count < self.num_slots() {
+        if matches!(first_index.checked_add(count), Some(end) if end <= self.num_slots()) {
             Some(UnsafeQueriesRange {
                 pool: self,
                 first: first_index,
@@ -172,6 +195,62 @@ impl<'a> UnsafeQueriesRange<'a> {
     pub fn count(&self) -> u32 {
         self.count
     }
+
+    /// Copies the results of the queries to `destination`, starting with the first query of the
+    /// range.
+    ///
+    /// Each query writes one element per result, followed by its availability if
+    /// `with_availability` is set. Returns `Ok(false)` if the results of some of the queries were
+    /// not available and `wait` is not set, in which case the results of these queries are not
+    /// written unless `partial` is set.
+    ///
+    /// If `wait` is set and some of the queries are never going to become available, for
+    /// example because they are not part of a submitted command buffer, this function may never
+    /// return.
+    pub fn get_results<T>(
+        &self,
+        destination: &mut [T],
+        flags: QueryResultFlags,
+    ) -> Result<bool, GetResultsError>
+    where
+        T: QueryResultElement,
+    {
+        if flags.partial {
+            if let QueryType::Timestamp = self.pool.ty() {
+                return Err(GetResultsError::InvalidFlags);
+            }
+        }
+
+        let per_query = self.pool.ty().result_len() + flags.with_availability as usize;
+        let required_len = per_query * self.count as usize;
+        if destination.len() < required_len {
+            return Err(GetResultsError::BufferTooSmall {
+                required_len,
+                actual_len: destination.len(),
+            });
+        }
+
+        let result = unsafe {
+            let device = self.pool.device();
+            let vk = device.pointers();
+            check_errors(vk.GetQueryPoolResults(
+                device.internal_object(),
+                self.pool.internal_object(),
+                self.first,
+                self.count,
+                required_len * mem::size_of::<T>(),
+                destination.as_mut_ptr() as *mut _,
+                (per_query * mem::size_of::<T>()) as vk::DeviceSize,
+                Into::<vk::QueryResultFlags>::into(flags) | T::FLAG,
+            ))?
+        };
+
+        match result {
+            Success::Success => Ok(true),
+            Success::NotReady => Ok(false),
+            s => panic!("unexpected success value: {:?}", s),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -181,11 +260,78 @@ pub enum QueryType {
     Timestamp,
 }
 
+impl QueryType {
+    // Returns the number of elements that a query of this type writes as its result.
+    #[inline]
+    fn result_len(&self) -> usize {
+        match *self {
+            QueryType::Occlusion | QueryType::Timestamp => 1,
+            QueryType::PipelineStatistics(flags) => {
+                Into::<vk::QueryPipelineStatisticFlags>::into(flags).count_ones() as usize
+            }
+        }
+    }
+}
+
+/// Flags that control the execution of a query.
 #[derive(Clone, Copy, Debug, Default)]
 pub struct QueryControlFlags {
+    /// For occlusion queries, counts the exact number of samples that pass the tests. If false,
+    /// the result is only guaranteed to be zero or non-zero, which may be faster.
+    ///
+    /// Requires the `occlusion_query_precise` feature.
     pub precise: bool,
 }
 
+/// Flags that control how the results of queries are retrieved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueryResultFlags {
+    /// Wait for the results of all the queries to be available.
+    pub wait: bool,
+    /// Write the availability of the result after the result of each query. The availability is
+    /// non-zero if the result is available.
+    pub with_availability: bool,
+    /// Write the results of the queries that are not available yet, which are then between zero
+    /// and the final result. Can't be used with timestamp queries.
+    pub partial: bool,
+}
+
+impl From<QueryResultFlags> for vk::QueryResultFlags {
+    #[inline]
+    fn from(flags: QueryResultFlags) -> vk::QueryResultFlags {
+        let mut result = 0;
+        if flags.wait {
+            result |= vk::QUERY_RESULT_WAIT_BIT;
+        }
+        if flags.with_availability {
+            result |= vk::QUERY_RESULT_WITH_AVAILABILITY_BIT;
+        }
+        if flags.partial {
+            result |= vk::QUERY_RESULT_PARTIAL_BIT;
+        }
+        result
+    }
+}
+
+/// Type of the elements that query results are written as.
+///
+/// # Safety
+///
+/// - `FLAG` must select a Vulkan result type with the same size as `Self`.
+///
+pub unsafe trait QueryResultElement: Copy {
+    /// Flag that selects this element type when retrieving the results.
+    const FLAG: vk::QueryResultFlags;
+}
+
+unsafe impl QueryResultElement for u32 {
+    const FLAG: vk::QueryResultFlags = 0;
+}
+
+unsafe impl QueryResultElement for u64 {
+    const FLAG: vk::QueryResultFlags = vk::QUERY_RESULT_64_BIT;
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct QueryPipelineStatisticFlags {
     pub input_assembly_vertices: bool,
@@ -265,7 +411,11 @@ impl Drop for UnsafeQueryPool {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroyQueryPool(self.device.internal_object(), self.pool, ptr::null());
+            vk.DestroyQueryPool(
+                self.device.internal_object(),
+                self.pool,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
@@ -324,6 +474,106 @@ impl From<Error> for QueryPoolCreationError {
     }
 }
 
+/// Error that can happen when retrieving the results of queries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GetResultsError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The connection to the device has been lost.
+    DeviceLost,
+    /// The destination is too small for the results of the queries.
+    BufferTooSmall {
+        /// Required number of elements.
+        required_len: usize,
+        /// Actual number of elements.
+        actual_len: usize,
+    },
+    /// The `partial` flag was set for timestamp queries.
+    InvalidFlags,
+}
+
+impl error::Error for GetResultsError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            GetResultsError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GetResultsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                GetResultsError::OomError(_) => "not enough memory available",
+                GetResultsError::DeviceLost => "the connection to the device has been lost",
+                GetResultsError::BufferTooSmall { .. } => {
+                    "the destination is too small for the results of the queries"
+                }
+                GetResultsError::InvalidFlags => {
+                    "the `partial` flag can't be used with timestamp queries"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for GetResultsError {
+    #[inline]
+    fn from(err: OomError) -> GetResultsError {
+        GetResultsError::OomError(err)
+    }
+}
+
+impl From<Error> for GetResultsError {
+    #[inline]
+    fn from(err: Error) -> GetResultsError {
+        match err {
+            err @ Error::OutOfHostMemory => GetResultsError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => GetResultsError::OomError(OomError::from(err)),
+            Error::DeviceLost => GetResultsError::DeviceLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+/// Pool of occlusion queries.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::command_buffer::AutoCommandBufferBuilder;
+/// use vulkano::query::OcclusionQueriesPool;
+/// use vulkano::query::QueryControlFlags;
+/// use vulkano::query::QueryResultFlags;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+///
+/// let pool = OcclusionQueriesPool::new(device.clone(), 1);
+///
+/// let mut builder = AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+/// unsafe {
+///     builder.reset_query_pool(pool.clone(), 0, 1).unwrap();
+///     builder
+///         .begin_query(pool.clone(), 0, QueryControlFlags { precise: false })
+///         .unwrap();
+/// }
+/// // Draw commands go here.
+/// builder.end_query(pool.clone(), 0).unwrap();
+///
+/// // Once the command buffer has been executed:
+/// let mut results = [0u64; 1];
+/// let flags = QueryResultFlags {
+///     wait: true,
+///     ..QueryResultFlags::default()
+/// };
+/// pool.get_results(0, 1, &mut results, flags).unwrap();
+/// let visible = results[0] != 0;
+/// ```
 pub struct OcclusionQueriesPool {
     inner: UnsafeQueryPool,
 }
@@ -358,6 +608,38 @@ impl OcclusionQueriesPool {
     pub fn num_slots(&self) -> u32 {
         self.inner.num_slots()
     }
+
+    /// Returns the underlying query pool.
+    #[inline]
+    pub fn inner(&self) -> &UnsafeQueryPool {
+        &self.inner
+    }
+
+    /// Copies the results of the `count` queries starting at `first_index` to `destination`.
+    ///
+    /// See `UnsafeQueriesRange::get_results` for the meaning of the flags and of the returned
+    /// value.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `count` is 0, or if the range is out of the pool.
+    ///
+    #[inline]
+    pub fn get_results<T>(
+        &self,
+        first_index: u32,
+        count: u32,
+        destination: &mut [T],
+        flags: QueryResultFlags,
+    ) -> Result<bool, GetResultsError>
+    where
+        T: QueryResultElement,
+    {
+        self.inner
+            .queries_range(first_index, count)
+            .expect("the queries are out of the range of the pool")
+            .get_results(destination, flags)
+    }
 }
 
 unsafe impl DeviceOwned for OcclusionQueriesPool {
@@ -369,9 +651,11 @@ unsafe impl DeviceOwned for OcclusionQueriesPool {
 
 #[cfg(test)]
 mod tests {
+    use crate::query::GetResultsError;
     use crate::query::OcclusionQueriesPool;
     use crate::query::QueryPipelineStatisticFlags;
     use crate::query::QueryPoolCreationError;
+    use crate::query::QueryResultFlags;
     use crate::query::QueryType;
     use crate::query::UnsafeQueryPool;
 
@@ -381,6 +665,36 @@ mod tests {
         let _ = OcclusionQueriesPool::new(device, 256);
     }
 
+    #[test]
+    fn queries_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device, QueryType::Occlusion, 4).unwrap();
+
+        assert!(pool.queries_range(0, 4).is_some());
+        assert!(pool.queries_range(3, 1).is_some());
+        assert!(pool.queries_range(3, 2).is_none());
+        assert!(pool.queries_range(u32::MAX, 2).is_none());
+    }
+
+    #[test]
+    fn get_results_buffer_too_small() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = OcclusionQueriesPool::new(device, 4);
+
+        let flags = QueryResultFlags {
+            with_availability: true,
+            ..QueryResultFlags::default()
+        };
+        let mut results = [0u32; 7];
+        match pool.get_results(0, 4, &mut results, flags) {
+            Err(GetResultsError::BufferTooSmall {
+                required_len: 8,
+                actual_len: 7,
+            }) => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn pipeline_statistics_feature() {
         let (device, _) = gfx_dev_and_queue!();
@@ -353,7 +353,7 @@ impl Sampler {
             check_errors(vk.CreateSampler(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -444,7 +444,7 @@ impl Sampler {
             check_errors(vk.CreateSampler(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -541,7 +541,11 @@ impl Drop for Sampler {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroySampler(self.device.internal_object(), self.sampler, ptr::null());
+            vk.DestroySampler(
+                self.device.internal_object(),
+                self.sampler,
+                self.device.alloc_ptr(),
+            );
         }
     }
 }
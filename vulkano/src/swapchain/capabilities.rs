@@ -9,6 +9,8 @@
 
 use crate::format::Format;
 use crate::image::ImageUsage;
+use crate::pipeline::viewport::Scissor;
+use crate::pipeline::viewport::Viewport;
 use crate::vk;
 
 /// The capabilities of a surface when used by a physical device.
@@ -180,6 +182,19 @@ impl Iterator for SupportedPresentModesIter {
 }
 
 /// A transformation to apply to the image before showing it on the screen.
+///
+/// # Pre-rotation
+///
+/// On some platforms, most notably Android, the surface reports a `current_transform` other than
+/// `Identity` when the device is rotated. If a swapchain is created with a different transform,
+/// the compositor has to rotate every presented image, which usually costs an additional pass on
+/// mobile GPUs.
+///
+/// To avoid this, create the swapchain with the `current_transform` of the surface, swapping the
+/// width and height of the images if the transform rotates by 90 or 270 degrees, and render the
+/// images already transformed. To do so, apply `pre_transform_matrix` after your projection
+/// matrix, and transform your viewports and scissors with `pre_transform_viewport` and
+/// `pre_transform_scissor`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum SurfaceTransform {
@@ -485,6 +500,170 @@ impl Default for SurfaceTransform {
     }
 }
 
+impl SurfaceTransform {
+    /// Returns true if the transform rotates the image by 90 or 270 degrees, in which case the
+    /// width and height of the swapchain images are swapped compared to the surface.
+    #[inline]
+    pub fn swaps_dimensions(&self) -> bool {
+        matches!(
+            *self,
+            SurfaceTransform::Rotate90
+                | SurfaceTransform::Rotate270
+                | SurfaceTransform::HorizontalMirrorRotate90
+                | SurfaceTransform::HorizontalMirrorRotate270
+        )
+    }
+
+    /// Returns the matrix that applies the transform to positions in clip space.
+    ///
+    /// The matrix is in column-major order, like the matrices of GLSL and of crates such as
+    /// `cgmath`, and must be applied after the projection matrix. `Inherit` is treated like
+    /// `Identity`.
+    #[inline]
+    pub fn pre_transform_matrix(&self) -> [[f32; 4]; 4] {
+        let m = self.clip_space_transform();
+        [
+            [m[0][0] as f32, m[1][0] as f32, 0.0, 0.0],
+            [m[0][1] as f32, m[1][1] as f32, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+    }
+
+    /// Applies the transform to a viewport that is relative to the surface, and returns the
+    /// corresponding viewport in swapchain images of dimensions `image_dimensions`.
+    #[inline]
+    pub fn pre_transform_viewport(
+        &self,
+        viewport: &Viewport,
+        image_dimensions: [u32; 2],
+    ) -> Viewport {
+        let (origin, dimensions) = self.pre_transform_rect(
+            [viewport.origin[0] as f64, viewport.origin[1] as f64],
+            [viewport.dimensions[0] as f64, viewport.dimensions[1] as f64],
+            image_dimensions,
+        );
+
+        Viewport {
+            origin: [origin[0] as f32, origin[1] as f32],
+            dimensions: [dimensions[0] as f32, dimensions[1] as f32],
+            depth_range: viewport.depth_range.clone(),
+        }
+    }
+
+    /// Applies the transform to a scissor box that is relative to the surface, and returns the
+    /// corresponding box in swapchain images of dimensions `image_dimensions`.
+    ///
+    /// `Scissor::irrelevant()` is returned unchanged.
+    #[inline]
+    pub fn pre_transform_scissor(&self, scissor: &Scissor, image_dimensions: [u32; 2]) -> Scissor {
+        if *scissor == Scissor::irrelevant() {
+            return *scissor;
+        }
+
+        let (origin, dimensions) = self.pre_transform_rect(
+            [scissor.origin[0] as f64, scissor.origin[1] as f64],
+            [scissor.dimensions[0] as f64, scissor.dimensions[1] as f64],
+            image_dimensions,
+        );
+
+        Scissor {
+            origin: [origin[0] as i32, origin[1] as i32],
+            dimensions: [dimensions[0] as u32, dimensions[1] as u32],
+        }
+    }
+
+    // Returns the 2x2 matrix, in row-major order, that applies the transform to a position in clip
+    // space. The image is mirrored first, then rotated clockwise.
+    fn clip_space_transform(&self) -> [[i32; 2]; 2] {
+        match *self {
+            SurfaceTransform::Identity | SurfaceTransform::Inherit => [[1, 0], [0, 1]],
+            SurfaceTransform::Rotate90 => [[0, -1], [1, 0]],
+            SurfaceTransform::Rotate180 => [[-1, 0], [0, -1]],
+            SurfaceTransform::Rotate270 => [[0, 1], [-1, 0]],
+            SurfaceTransform::HorizontalMirror => [[-1, 0], [0, 1]],
+            SurfaceTransform::HorizontalMirrorRotate90 => [[0, -1], [-1, 0]],
+            SurfaceTransform::HorizontalMirrorRotate180 => [[1, 0], [0, -1]],
+            SurfaceTransform::HorizontalMirrorRotate270 => [[0, 1], [1, 0]],
+        }
+    }
+
+    // Transforms a rectangle in pixels from the surface to the swapchain images.
+    fn pre_transform_rect(
+        &self,
+        origin: [f64; 2],
+        dimensions: [f64; 2],
+        image_dimensions: [u32; 2],
+    ) -> ([f64; 2], [f64; 2]) {
+        let image_dimensions = [image_dimensions[0] as f64, image_dimensions[1] as f64];
+        let surface_dimensions = if self.swaps_dimensions() {
+            [image_dimensions[1], image_dimensions[0]]
+        } else {
+            image_dimensions
+        };
+        let m = self.clip_space_transform();
+
+        // Moves the point so that the center of the surface is the origin, applies the transform,
+        // and moves it back relative to the center of the images.
+        let transform = |point: [f64; 2]| {
+            let centered = [
+                point[0] - surface_dimensions[0] / 2.0,
+                point[1] - surface_dimensions[1] / 2.0,
+            ];
+            [
+                m[0][0] as f64 * centered[0]
+                    + m[0][1] as f64 * centered[1]
+                    + image_dimensions[0] / 2.0,
+                m[1][0] as f64 * centered[0]
+                    + m[1][1] as f64 * centered[1]
+                    + image_dimensions[1] / 2.0,
+            ]
+        };
+
+        let a = transform(origin);
+        let b = transform([origin[0] + dimensions[0], origin[1] + dimensions[1]]);
+        (
+            [a[0].min(b[0]), a[1].min(b[1])],
+            [(a[0] - b[0]).abs(), (a[1] - b[1]).abs()],
+        )
+    }
+}
+
+/// How the presentation engine scales the images of a swapchain whose dimensions don't match
+/// the dimensions of the surface.
+///
+/// Requires the `swapchain_maintenance1` feature.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PresentScaling {
+    /// The image isn't scaled. Pixels of the image map to pixels of the surface one-to-one.
+    OneToOne = vk::PRESENT_SCALING_ONE_TO_ONE_BIT_EXT,
+
+    /// The image is scaled to the largest size that fits in the surface while keeping its aspect
+    /// ratio.
+    AspectRatioStretch = vk::PRESENT_SCALING_ASPECT_RATIO_STRETCH_BIT_EXT,
+
+    /// The image is stretched to the dimensions of the surface.
+    Stretch = vk::PRESENT_SCALING_STRETCH_BIT_EXT,
+}
+
+/// Where the presentation engine places the image on an axis of the surface when the image
+/// doesn't fill the surface.
+///
+/// Requires the `swapchain_maintenance1` feature.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PresentGravity {
+    /// The image is aligned with the left or top edge of the surface.
+    Min = vk::PRESENT_GRAVITY_MIN_BIT_EXT,
+
+    /// The image is aligned with the right or bottom edge of the surface.
+    Max = vk::PRESENT_GRAVITY_MAX_BIT_EXT,
+
+    /// The image is centered in the surface.
+    Centered = vk::PRESENT_GRAVITY_CENTERED_BIT_EXT,
+}
+
 /// How the presentation engine should interpret the data.
 ///
 /// # A quick lesson about color spaces
@@ -572,6 +751,14 @@ impl Default for SurfaceTransform {
 /// Additionally you can try detect whether the implementation supports any additional color space
 /// and perform a manual conversion to that color space from inside your shader.
 ///
+/// # Extended color spaces
+///
+/// All the color spaces other than `SrgbNonLinear` are only reported by surfaces if the
+/// `ext_swapchain_colorspace` extension is enabled on the instance. Outputting HDR10 usually
+/// means using `Hdr10St2084` with a format such as `A2B10G10R10UnormPack32`, while scRGB uses
+/// `ExtendedSrgbLinear` with `R16G16B16A16Sfloat`. The `ext_hdr_metadata` device extension
+/// additionally lets you describe the content with `Swapchain::set_hdr_metadata`.
+///
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum ColorSpace {
@@ -592,8 +779,8 @@ pub enum ColorSpace {
 }
 
 #[inline]
-pub fn color_space_from_num(val: u32) -> ColorSpace {
-    match val {
+pub fn color_space_from_num(val: u32) -> Option<ColorSpace> {
+    Some(match val {
         vk::COLOR_SPACE_SRGB_NONLINEAR_KHR => ColorSpace::SrgbNonLinear,
         vk::COLOR_SPACE_DISPLAY_P3_NONLINEAR_EXT => ColorSpace::DisplayP3NonLinear,
         vk::COLOR_SPACE_EXTENDED_SRGB_LINEAR_EXT => ColorSpace::ExtendedSrgbLinear,
@@ -608,6 +795,62 @@ pub fn color_space_from_num(val: u32) -> ColorSpace {
         vk::COLOR_SPACE_ADOBERGB_LINEAR_EXT => ColorSpace::AdobeRgbLinear,
         vk::COLOR_SPACE_ADOBERGB_NONLINEAR_EXT => ColorSpace::AdobeRgbNonLinear,
         vk::COLOR_SPACE_PASS_THROUGH_EXT => ColorSpace::PassThrough,
-        _ => panic!("Wrong value for color space enum"),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline::viewport::Scissor;
+    use crate::swapchain::capabilities::color_space_from_num;
+    use crate::swapchain::ColorSpace;
+    use crate::swapchain::SurfaceTransform;
+    use crate::vk;
+
+    #[test]
+    fn unknown_color_space() {
+        assert_eq!(
+            color_space_from_num(vk::COLOR_SPACE_HDR10_ST2084_EXT),
+            Some(ColorSpace::Hdr10St2084)
+        );
+        assert_eq!(color_space_from_num(0x7fff_ffff), None);
+    }
+
+    #[test]
+    fn pre_transform_scissor() {
+        // A scissor in the top-left corner of a 300x200 surface.
+        let scissor = Scissor {
+            origin: [10, 20],
+            dimensions: [30, 40],
+        };
+
+        let transformed = SurfaceTransform::Identity.pre_transform_scissor(&scissor, [300, 200]);
+        assert_eq!(transformed, scissor);
+
+        // Rotating clockwise moves the top-left corner to the top-right corner.
+        let transformed = SurfaceTransform::Rotate90.pre_transform_scissor(&scissor, [200, 300]);
+        assert_eq!(transformed.origin, [140, 10]);
+        assert_eq!(transformed.dimensions, [40, 30]);
+
+        let transformed = SurfaceTransform::Rotate180.pre_transform_scissor(&scissor, [300, 200]);
+        assert_eq!(transformed.origin, [260, 140]);
+        assert_eq!(transformed.dimensions, [30, 40]);
+
+        let transformed = SurfaceTransform::Rotate270.pre_transform_scissor(&scissor, [200, 300]);
+        assert_eq!(transformed.origin, [20, 260]);
+        assert_eq!(transformed.dimensions, [40, 30]);
+
+        let transformed =
+            SurfaceTransform::HorizontalMirror.pre_transform_scissor(&scissor, [300, 200]);
+        assert_eq!(transformed.origin, [260, 20]);
+        assert_eq!(transformed.dimensions, [30, 40]);
+    }
+
+    #[test]
+    fn pre_transform_matrix() {
+        // Rotating clockwise moves the right of the clip space to the bottom.
+        let m = SurfaceTransform::Rotate90.pre_transform_matrix();
+        assert_eq!([m[0][0], m[0][1]], [0.0, 1.0]);
+        assert_eq!([m[1][0], m[1][1]], [-1.0, 0.0]);
     }
 }
@@ -9,8 +9,8 @@
 
 //! Allows you to create surfaces that fill a whole display, outside of the windowing system.
 //!
-//! **As far as the author knows, no existing device supports these features. Therefore the code
-//! here is mostly a draft and needs rework in both the API and the implementation.**
+//! This requires the `khr_display` extension to be enabled on the instance. It is mostly useful on
+//! headless machines and embedded systems that don't run a window system.
 //!
 //! The purpose of the objects in this module is to let you create a `Surface` object that
 //! represents a location on the screen. This is done in four steps:
@@ -20,33 +20,62 @@
 //!   `Display::enumerate`.
 //! - Choose a `DisplayMode`, which is the combination of a display, a resolution and a refresh
 //!   rate. You can enumerate the modes available on a display with `Display::display_modes`, or
-//!   attempt to create your own mode with `TODO`.
-//! - Choose a `DisplayPlane`. A display can show multiple planes in a stacking fashion.
+//!   attempt to create your own mode with `DisplayMode::new`.
+//! - Choose a `DisplayPlane`. A display can show multiple planes in a stacking fashion. Use
+//!   `DisplayPlane::supports` to check that a plane can be shown on your display, and
+//!   `DisplayPlane::capabilities` to query what it supports when used with a mode.
 //! - Create a `Surface` object with `Surface::from_display_mode` and pass the chosen `DisplayMode`
-//!   and `DisplayPlane`.
-
-#![allow(dead_code)] // TODO: this module isn't finished
-#![allow(unused_variables)] // TODO: this module isn't finished
+//!   and `DisplayPlane`, or use `Surface::from_display_plane` to customize the surface.
+//!
+//! ```no_run
+//! use vulkano::instance::Instance;
+//! use vulkano::instance::InstanceExtensions;
+//! use vulkano::instance::PhysicalDevice;
+//! use vulkano::swapchain::display::Display;
+//! use vulkano::swapchain::display::DisplayPlane;
+//! use vulkano::swapchain::Surface;
+//!
+//! let extensions = InstanceExtensions {
+//!     khr_surface: true,
+//!     khr_display: true,
+//!     .. InstanceExtensions::none()
+//! };
+//! let instance = Instance::new(None, &extensions, None).unwrap();
+//! let physical = PhysicalDevice::enumerate(&instance).next().unwrap();
+//!
+//! let display = Display::enumerate(physical).next().expect("no display available");
+//! let mode = display.display_modes().next().expect("no display mode available");
+//! let plane = DisplayPlane::enumerate(physical)
+//!     .find(|plane| plane.supports(&display))
+//!     .expect("no plane supports the display");
+//!
+//! let surface = Surface::from_display_mode(&mode, &plane).unwrap();
+//! ```
 
+use std::error;
 use std::ffi::CStr;
+use std::fmt;
+use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
 use std::vec::IntoIter;
 
+use crate::device::Device;
 use crate::instance::Instance;
 use crate::instance::PhysicalDevice;
 use crate::swapchain::capabilities;
 use crate::swapchain::SupportedSurfaceTransforms;
+use crate::sync::Fence;
 
 use crate::check_errors;
 use crate::vk;
+use crate::Error;
 use crate::OomError;
 use crate::VulkanObject;
 
-// TODO: extract this to a `display` module and solve the visibility problems
-
-/// ?
-// TODO: plane capabilities
+/// Represents a plane of a physical device, that is a layer that can be shown on a display.
+///
+/// Multiple planes can be shown on the same display in a stacking fashion.
 // TODO: store properties in the instance?
 pub struct DisplayPlane {
     instance: Arc<Instance>,
@@ -96,8 +125,7 @@ impl DisplayPlane {
                         index as u32,
                         &mut num,
                         ptr::null_mut(),
-                    ))
-                    .unwrap(); // TODO: shouldn't unwrap
+                    ))?;
                     num
                 };
 
@@ -109,21 +137,20 @@ impl DisplayPlane {
                         index as u32,
                         &mut num,
                         displays.as_mut_ptr(),
-                    ))
-                    .unwrap(); // TODO: shouldn't unwrap
+                    ))?;
                     displays.set_len(num as usize);
                     displays
                 };
 
-                DisplayPlane {
+                Ok(DisplayPlane {
                     instance: device.instance().clone(),
                     physical_device: device.index(),
                     index: index as u32,
                     properties: prop,
                     supported_displays: supported_displays,
-                }
+                })
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, OomError>>()?
             .into_iter())
     }
 
@@ -151,6 +178,51 @@ impl DisplayPlane {
         self.index
     }
 
+    /// Returns the index of the plane in the stack of planes of the display it's currently
+    /// associated with.
+    #[inline]
+    pub fn current_stack_index(&self) -> u32 {
+        self.properties.currentStackIndex
+    }
+
+    /// Queries the capabilities of this plane when it is used with the given display mode.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the display mode and the plane don't belong to the same physical device.
+    ///
+    pub fn capabilities(&self, mode: &DisplayMode) -> Result<DisplayPlaneCapabilities, OomError> {
+        assert_eq!(
+            self.physical_device().internal_object(),
+            mode.display().physical_device().internal_object()
+        );
+
+        let vk = self.instance.pointers();
+
+        let caps = unsafe {
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.GetDisplayPlaneCapabilitiesKHR(
+                self.physical_device().internal_object(),
+                mode.internal_object(),
+                self.index,
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(DisplayPlaneCapabilities {
+            supported_alpha: SupportedDisplayPlaneAlpha::from_bits(caps.supportedAlpha),
+            min_src_position: [caps.minSrcPosition.x, caps.minSrcPosition.y],
+            max_src_position: [caps.maxSrcPosition.x, caps.maxSrcPosition.y],
+            min_src_extent: [caps.minSrcExtent.width, caps.minSrcExtent.height],
+            max_src_extent: [caps.maxSrcExtent.width, caps.maxSrcExtent.height],
+            min_dst_position: [caps.minDstPosition.x, caps.minDstPosition.y],
+            max_dst_position: [caps.maxDstPosition.x, caps.maxDstPosition.y],
+            min_dst_extent: [caps.minDstExtent.width, caps.minDstExtent.height],
+            max_dst_extent: [caps.maxDstExtent.width, caps.maxDstExtent.height],
+        })
+    }
+
     /// Returns true if this plane supports the given display.
     #[inline]
     pub fn supports(&self, display: &Display) -> bool {
@@ -166,6 +238,86 @@ impl DisplayPlane {
     }
 }
 
+/// The capabilities of a display plane when used with a specific display mode.
+///
+/// The source values describe the region of the presented images that can be shown, and the
+/// destination values describe the region of the display where they can be shown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DisplayPlaneCapabilities {
+    /// The alpha modes that can be used with the plane.
+    pub supported_alpha: SupportedDisplayPlaneAlpha,
+    pub min_src_position: [i32; 2],
+    pub max_src_position: [i32; 2],
+    pub min_src_extent: [u32; 2],
+    pub max_src_extent: [u32; 2],
+    pub min_dst_position: [i32; 2],
+    pub max_dst_position: [i32; 2],
+    pub min_dst_extent: [u32; 2],
+    pub max_dst_extent: [u32; 2],
+}
+
+/// How the alpha of a display plane is blended with the planes below it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DisplayPlaneAlpha {
+    /// The plane is opaque and the alpha component of the images is ignored.
+    Opaque,
+    /// The given alpha value, between 0.0 and 1.0, is applied to the whole plane and the alpha
+    /// component of the images is ignored.
+    Global(f32),
+    /// The alpha component of the images is used, and the colors aren't premultiplied.
+    PerPixel,
+    /// The alpha component of the images is used, and the colors are premultiplied.
+    PerPixelPremultiplied,
+}
+
+impl DisplayPlaneAlpha {
+    #[inline]
+    pub(crate) fn to_vk(self) -> (vk::DisplayPlaneAlphaFlagsKHR, f32) {
+        match self {
+            DisplayPlaneAlpha::Opaque => (vk::DISPLAY_PLANE_ALPHA_OPAQUE_BIT_KHR, 1.0),
+            DisplayPlaneAlpha::Global(alpha) => (vk::DISPLAY_PLANE_ALPHA_GLOBAL_BIT_KHR, alpha),
+            DisplayPlaneAlpha::PerPixel => (vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_BIT_KHR, 1.0),
+            DisplayPlaneAlpha::PerPixelPremultiplied => {
+                (vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_PREMULTIPLIED_BIT_KHR, 1.0)
+            }
+        }
+    }
+}
+
+/// List of the alpha modes that a display plane supports.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SupportedDisplayPlaneAlpha {
+    pub opaque: bool,
+    pub global: bool,
+    pub per_pixel: bool,
+    pub per_pixel_premultiplied: bool,
+}
+
+impl SupportedDisplayPlaneAlpha {
+    #[inline]
+    fn from_bits(val: vk::DisplayPlaneAlphaFlagsKHR) -> SupportedDisplayPlaneAlpha {
+        SupportedDisplayPlaneAlpha {
+            opaque: (val & vk::DISPLAY_PLANE_ALPHA_OPAQUE_BIT_KHR) != 0,
+            global: (val & vk::DISPLAY_PLANE_ALPHA_GLOBAL_BIT_KHR) != 0,
+            per_pixel: (val & vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_BIT_KHR) != 0,
+            per_pixel_premultiplied: (val
+                & vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_PREMULTIPLIED_BIT_KHR)
+                != 0,
+        }
+    }
+
+    /// Returns true if the given alpha mode is in this list.
+    #[inline]
+    pub fn supports(&self, value: DisplayPlaneAlpha) -> bool {
+        match value {
+            DisplayPlaneAlpha::Opaque => self.opaque,
+            DisplayPlaneAlpha::Global(_) => self.global,
+            DisplayPlaneAlpha::PerPixel => self.per_pixel,
+            DisplayPlaneAlpha::PerPixelPremultiplied => self.per_pixel_premultiplied,
+        }
+    }
+}
+
 /// Represents a monitor connected to a physical device.
 // TODO: store properties in the instance?
 #[derive(Clone)]
@@ -265,18 +417,94 @@ impl Display {
         capabilities::surface_transforms_from_bits(self.properties.supportedTransforms)
     }
 
-    /// Returns true if TODO.
+    /// Returns true if the planes shown on this display can be reordered.
     #[inline]
     pub fn plane_reorder_possible(&self) -> bool {
         self.properties.planeReorderPossible != 0
     }
 
-    /// Returns true if TODO.
+    /// Returns true if the display keeps showing the last presented image on its own, in which
+    /// case surfaces can avoid presenting when nothing changed.
     #[inline]
     pub fn persistent_content(&self) -> bool {
         self.properties.persistentContent != 0
     }
 
+    /// Changes the power state of the display.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_display_control` extension is not enabled on the device.
+    /// - Panics if the device and the display don't belong to the same physical device.
+    ///
+    pub fn set_power_state(
+        &self,
+        device: &Device,
+        power_state: DisplayPowerState,
+    ) -> Result<(), OomError> {
+        assert!(
+            device.loaded_extensions().ext_display_control,
+            "the ext_display_control extension must be enabled"
+        );
+        assert_eq!(
+            device.physical_device().internal_object(),
+            self.physical_device().internal_object()
+        );
+
+        unsafe {
+            let vk = device.pointers();
+            let infos = vk::DisplayPowerInfoEXT {
+                sType: vk::STRUCTURE_TYPE_DISPLAY_POWER_INFO_EXT,
+                pNext: ptr::null(),
+                powerState: power_state as u32,
+            };
+            check_errors(vk.DisplayPowerControlEXT(
+                device.internal_object(),
+                self.properties.display,
+                &infos,
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Returns a fence that is signaled the next time the first pixel of a new frame leaves the
+    /// display engine, in other words at the next vertical blanking period.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_display_control` extension is not enabled on the device.
+    /// - Panics if the device and the display don't belong to the same physical device.
+    ///
+    pub fn register_first_pixel_out_event(&self, device: Arc<Device>) -> Result<Fence, OomError> {
+        assert!(
+            device.loaded_extensions().ext_display_control,
+            "the ext_display_control extension must be enabled"
+        );
+        assert_eq!(
+            device.physical_device().internal_object(),
+            self.physical_device().internal_object()
+        );
+
+        unsafe {
+            let vk = device.pointers();
+            let infos = vk::DisplayEventInfoEXT {
+                sType: vk::STRUCTURE_TYPE_DISPLAY_EVENT_INFO_EXT,
+                pNext: ptr::null(),
+                displayEvent: vk::DISPLAY_EVENT_TYPE_FIRST_PIXEL_OUT_EXT,
+            };
+
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.RegisterDisplayEventEXT(
+                device.internal_object(),
+                self.properties.display,
+                &infos,
+                device.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            Ok(Fence::from_raw(device, output.assume_init()))
+        }
+    }
+
     /// See the docs of display_modes().
     pub fn display_modes_raw(&self) -> Result<IntoIter<DisplayMode>, OomError> {
         let vk = self.instance.pointers();
@@ -329,6 +557,20 @@ impl Display {
     }
 }
 
+/// The power state of a display.
+///
+/// Requires the `ext_display_control` device extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DisplayPowerState {
+    /// The display is powered down.
+    Off = vk::DISPLAY_POWER_STATE_OFF_EXT,
+    /// The display is put in a low power mode, from which it can return faster than from `Off`.
+    Suspend = vk::DISPLAY_POWER_STATE_SUSPEND_EXT,
+    /// The display is powered on.
+    On = vk::DISPLAY_POWER_STATE_ON_EXT,
+}
+
 unsafe impl VulkanObject for Display {
     type Object = vk::DisplayKHR;
 
@@ -348,36 +590,57 @@ pub struct DisplayMode {
 }
 
 impl DisplayMode {
-    /*pub fn new(display: &Display) -> Result<Arc<DisplayMode>, OomError> {
-        let vk = instance.pointers();
-        assert!(device.instance().loaded_extensions().khr_display);     // TODO: return error instead
+    /// Creates a new display mode with the given visible region and refresh rate, in
+    /// millihertz.
+    ///
+    /// The implementation may refuse modes that the display doesn't support, in which case an
+    /// error is returned.
+    pub fn new(
+        display: &Display,
+        visible_region: [u32; 2],
+        refresh_rate: u32,
+    ) -> Result<DisplayMode, DisplayModeCreationError> {
+        if !display.instance.loaded_extensions().khr_display {
+            return Err(DisplayModeCreationError::DisplayExtensionNotEnabled);
+        }
 
-        let parameters = vk::DisplayModeParametersKHR {
-            visibleRegion: vk::Extent2D { width: , height:  },
-            refreshRate: ,
-        };
+        if visible_region[0] == 0 || visible_region[1] == 0 || refresh_rate == 0 {
+            return Err(DisplayModeCreationError::InvalidParameters);
+        }
 
-        let display_mode = {
-            let infos = vk::DisplayModeCreateInfoKHR {
-                sType: vk::STRUCTURE_TYPE_DISPLAY_MODE_CREATE_INFO_KHR,
-                pNext: ptr::null(),
-                flags: 0,   // reserved
-                parameters: parameters,
-            };
+        let vk = display.instance.pointers();
+
+        let infos = vk::DisplayModeCreateInfoKHR {
+            sType: vk::STRUCTURE_TYPE_DISPLAY_MODE_CREATE_INFO_KHR,
+            pNext: ptr::null(),
+            flags: 0, // reserved
+            parameters: vk::DisplayModeParametersKHR {
+                visibleRegion: vk::Extent2D {
+                    width: visible_region[0],
+                    height: visible_region[1],
+                },
+                refreshRate: refresh_rate,
+            },
+        };
 
-            let mut output = mem::uninitialized();
-            check_errors(vk.CreateDisplayModeKHR(display.device.internal_object(),
-                                                      display.display, &infos, ptr::null(),
-                                                      &mut output))?;
-            output
+        let display_mode = unsafe {
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateDisplayModeKHR(
+                display.physical_device().internal_object(),
+                display.internal_object(),
+                &infos,
+                display.physical_device().instance().alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
         };
 
-        Ok(Arc::new(DisplayMode {
-            instance: display.device.instance().clone(),
-            display_mode: display_mode,
-            parameters: ,
-        }))
-    }*/
+        Ok(DisplayMode {
+            display: display.clone(),
+            display_mode,
+            parameters: infos.parameters,
+        })
+    }
 
     /// Returns the display corresponding to this mode.
     #[inline]
@@ -409,3 +672,117 @@ unsafe impl VulkanObject for DisplayMode {
         self.display_mode
     }
 }
+
+/// Error that can happen when creating a display mode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DisplayModeCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The `khr_display` extension must be enabled on the instance in order to create a display
+    /// mode.
+    DisplayExtensionNotEnabled,
+
+    /// The visible region or the refresh rate is zero.
+    InvalidParameters,
+
+    /// The display doesn't support the requested mode.
+    UnsupportedMode,
+}
+
+impl error::Error for DisplayModeCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DisplayModeCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DisplayModeCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DisplayModeCreationError::OomError(_) => "not enough memory available",
+                DisplayModeCreationError::DisplayExtensionNotEnabled => {
+                    "the `khr_display` extension must be enabled on the instance"
+                }
+                DisplayModeCreationError::InvalidParameters => {
+                    "the visible region or the refresh rate is zero"
+                }
+                DisplayModeCreationError::UnsupportedMode => {
+                    "the display doesn't support the requested mode"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for DisplayModeCreationError {
+    #[inline]
+    fn from(err: OomError) -> DisplayModeCreationError {
+        DisplayModeCreationError::OomError(err)
+    }
+}
+
+impl From<Error> for DisplayModeCreationError {
+    #[inline]
+    fn from(err: Error) -> DisplayModeCreationError {
+        match err {
+            err @ Error::OutOfHostMemory => DisplayModeCreationError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => {
+                DisplayModeCreationError::OomError(OomError::from(err))
+            }
+            Error::InitializationFailed => DisplayModeCreationError::UnsupportedMode,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::swapchain::display::DisplayPlaneAlpha;
+    use crate::swapchain::display::DisplayPowerState;
+    use crate::swapchain::display::SupportedDisplayPlaneAlpha;
+    use crate::vk;
+
+    #[test]
+    fn plane_alpha_to_vk() {
+        assert_eq!(
+            DisplayPlaneAlpha::Global(0.5).to_vk(),
+            (vk::DISPLAY_PLANE_ALPHA_GLOBAL_BIT_KHR, 0.5)
+        );
+        assert_eq!(
+            DisplayPlaneAlpha::PerPixelPremultiplied.to_vk(),
+            (vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_PREMULTIPLIED_BIT_KHR, 1.0)
+        );
+    }
+
+    #[test]
+    fn supported_plane_alpha() {
+        let supported = SupportedDisplayPlaneAlpha::from_bits(
+            vk::DISPLAY_PLANE_ALPHA_OPAQUE_BIT_KHR | vk::DISPLAY_PLANE_ALPHA_GLOBAL_BIT_KHR,
+        );
+        assert!(supported.supports(DisplayPlaneAlpha::Opaque));
+        assert!(supported.supports(DisplayPlaneAlpha::Global(0.25)));
+        assert!(!supported.supports(DisplayPlaneAlpha::PerPixel));
+        assert!(!supported.supports(DisplayPlaneAlpha::PerPixelPremultiplied));
+    }
+
+    #[test]
+    fn power_state_to_vk() {
+        assert_eq!(
+            DisplayPowerState::Off as u32,
+            vk::DISPLAY_POWER_STATE_OFF_EXT
+        );
+        assert_eq!(
+            DisplayPowerState::Suspend as u32,
+            vk::DISPLAY_POWER_STATE_SUSPEND_EXT
+        );
+        assert_eq!(DisplayPowerState::On as u32, vk::DISPLAY_POWER_STATE_ON_EXT);
+    }
+}
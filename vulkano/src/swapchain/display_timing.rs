@@ -0,0 +1,177 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::time::Duration;
+
+use crate::vk;
+use crate::Error;
+use crate::OomError;
+
+/// Timing information about an image that has been presented with
+/// `PresentFuture::with_present_time`.
+///
+/// Returned by `Swapchain::past_presentation_timing`. All the times are in nanoseconds, in the
+/// same time domain as `CLOCK_MONOTONIC` on Android and Linux.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PastPresentationTiming {
+    /// The identifier that was passed to `PresentFuture::with_present_time`.
+    pub present_id: u32,
+
+    /// The time at which the image was requested to be displayed.
+    pub desired_present_time: u64,
+
+    /// The time at which the image was actually displayed.
+    pub actual_present_time: u64,
+
+    /// The earliest time at which the image could have been displayed. This is different from
+    /// `actual_present_time` if the image was presented late, or if it could have been displayed
+    /// earlier than `desired_present_time`.
+    pub earliest_present_time: u64,
+
+    /// How early the processing of the present completed compared to the time at which the image
+    /// could have been displayed at the earliest.
+    pub present_margin: Duration,
+}
+
+impl From<vk::PastPresentationTimingGOOGLE> for PastPresentationTiming {
+    #[inline]
+    fn from(val: vk::PastPresentationTimingGOOGLE) -> Self {
+        PastPresentationTiming {
+            present_id: val.presentID,
+            desired_present_time: val.desiredPresentTime,
+            actual_present_time: val.actualPresentTime,
+            earliest_present_time: val.earliestPresentTime,
+            present_margin: Duration::from_nanos(val.presentMargin),
+        }
+    }
+}
+
+/// Error that can happen when querying the display timing or the counters of a swapchain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DisplayTimingError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The surface is no longer accessible and must be recreated.
+    SurfaceLost,
+
+    /// The swapchain has lost or doesn't have fullscreen exclusivity possibly for
+    /// implementation-specific reasons outside of the application’s control.
+    FullscreenExclusiveLost,
+
+    /// The surface has changed in a way that makes the swapchain unusable. You must query the
+    /// surface's new properties and recreate a new swapchain if you want to continue drawing.
+    OutOfDate,
+}
+
+impl error::Error for DisplayTimingError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DisplayTimingError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DisplayTimingError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DisplayTimingError::OomError(_) => "not enough memory",
+                DisplayTimingError::DeviceLost => "the connection to the device has been lost",
+                DisplayTimingError::SurfaceLost => {
+                    "the surface of this swapchain is no longer valid"
+                }
+                DisplayTimingError::OutOfDate => "the swapchain needs to be recreated",
+                DisplayTimingError::FullscreenExclusiveLost => {
+                    "the swapchain no longer has fullscreen exclusivity"
+                }
+            }
+        )
+    }
+}
+
+impl From<Error> for DisplayTimingError {
+    #[inline]
+    fn from(err: Error) -> DisplayTimingError {
+        match err {
+            err @ Error::OutOfHostMemory => DisplayTimingError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => DisplayTimingError::OomError(OomError::from(err)),
+            Error::DeviceLost => DisplayTimingError::DeviceLost,
+            Error::SurfaceLost => DisplayTimingError::SurfaceLost,
+            Error::OutOfDate => DisplayTimingError::OutOfDate,
+            Error::FullscreenExclusiveLost => DisplayTimingError::FullscreenExclusiveLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for DisplayTimingError {
+    #[inline]
+    fn from(err: OomError) -> DisplayTimingError {
+        DisplayTimingError::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::swapchain::DisplayTimingError;
+    use crate::swapchain::PastPresentationTiming;
+    use crate::vk;
+    use crate::Error;
+    use crate::OomError;
+    use std::time::Duration;
+
+    #[test]
+    fn timing_from_vulkan() {
+        let timing = PastPresentationTiming::from(vk::PastPresentationTimingGOOGLE {
+            presentID: 7,
+            desiredPresentTime: 1_000,
+            actualPresentTime: 2_000,
+            earliestPresentTime: 1_500,
+            presentMargin: 250,
+        });
+
+        assert_eq!(
+            timing,
+            PastPresentationTiming {
+                present_id: 7,
+                desired_present_time: 1_000,
+                actual_present_time: 2_000,
+                earliest_present_time: 1_500,
+                present_margin: Duration::from_nanos(250),
+            }
+        );
+    }
+
+    #[test]
+    fn error_from_vulkan() {
+        assert_eq!(
+            DisplayTimingError::from(Error::OutOfHostMemory),
+            DisplayTimingError::OomError(OomError::OutOfHostMemory)
+        );
+        assert_eq!(
+            DisplayTimingError::from(Error::OutOfDate),
+            DisplayTimingError::OutOfDate
+        );
+        assert_eq!(
+            DisplayTimingError::from(Error::FullscreenExclusiveLost),
+            DisplayTimingError::FullscreenExclusiveLost
+        );
+    }
+}
@@ -0,0 +1,657 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::mem;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::command_buffer::pool::TransientCommandPool;
+use crate::command_buffer::submit::SubmitAnyBuilder;
+use crate::command_buffer::submit::SubmitCommandBufferBuilder;
+use crate::command_buffer::submit::SubmitPresentBuilder;
+use crate::command_buffer::submit::SubmitPresentError;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::device::Queue;
+use crate::image::SwapchainImage;
+use crate::instance::QueueFamily;
+use crate::swapchain;
+use crate::swapchain::AcquireError;
+use crate::swapchain::Swapchain;
+use crate::swapchain::SwapchainAcquireFuture;
+use crate::swapchain::SwapchainCreationError;
+use crate::sync::Fence;
+use crate::sync::FlushError;
+use crate::sync::GpuFuture;
+use crate::sync::PipelineStages;
+use crate::sync::Semaphore;
+use crate::OomError;
+use crate::VulkanObject;
+
+/// Manages the frames that are being rendered and presented at the same time.
+///
+/// Each of the frames in flight owns a context made of the semaphore that is used to acquire its
+/// image, the semaphore that its submission signals before presenting, the fence that is signaled
+/// when its submission has been processed by the GPU, and a transient command pool. The context
+/// is reused once the fence has been waited upon, so that none of these objects is created or
+/// destroyed in the steady state. The command buffers of a frame should be allocated from
+/// `Frame::command_pool`.
+///
+/// At each frame, call `begin_frame` to wait for the oldest frame in flight and acquire an image,
+/// then pass the future of your submission to `end_frame` to present it. Suboptimal and
+/// out-of-date swapchains are recreated automatically at the next call to `begin_frame`, and
+/// `Frame::recreated_images` tells you when you need to rebuild what depends on the images. If a
+/// `Frame` is dropped without being turned into a future, for example because recording failed,
+/// the next call to `begin_frame` skips it without presenting its image.
+///
+/// ```
+/// use vulkano::command_buffer::AutoCommandBufferBuilder;
+/// use vulkano::swapchain::FramesInFlight;
+/// use vulkano::sync::GpuFuture;
+///
+/// # let swapchain: ::std::sync::Arc<::vulkano::swapchain::Swapchain<()>> = return;
+/// # let queue: ::std::sync::Arc<::vulkano::device::Queue> = return;
+/// let mut frames = FramesInFlight::new(swapchain, queue.clone(), 2).unwrap();
+///
+/// loop {
+///     let frame = match frames.begin_frame() {
+///         Ok(frame) => frame,
+///         // The window is probably being resized, try again later.
+///         Err(_) => continue,
+///     };
+///
+///     if let Some(images) = frame.recreated_images() {
+///         // Recreate the framebuffers here.
+///     }
+///
+///     // Record the commands that draw on `frame.image_num()` here.
+///     let command_buffer =
+///         AutoCommandBufferBuilder::primary_one_time_submit_from_pool(frame.command_pool())
+///             .unwrap()
+///             .build()
+///             .unwrap();
+///
+///     let future = frame
+///         .into_future()
+///         .then_execute(queue.clone(), command_buffer)
+///         .unwrap();
+///     frames.end_frame(future).unwrap();
+/// #   break;
+/// }
+/// ```
+pub struct FramesInFlight<W> {
+    swapchain: Arc<Swapchain<W>>,
+    queue: Arc<Queue>,
+    // The contexts of the frames in flight, indexed by frame.
+    frames: Vec<FrameContext>,
+    // The frame that will be used by the next call to `begin_frame`.
+    current_frame: usize,
+    // The state of the frame that was returned by `begin_frame`, shared with the `Frame`.
+    state: Arc<Mutex<FrameState>>,
+    // If true, the swapchain must be recreated before acquiring the next image.
+    recreate_swapchain: bool,
+    // The dimensions to use when recreating the swapchain. If `None`, the current extent of the
+    // surface is used.
+    dimensions: Option<[u32; 2]>,
+    // The images of the recreated swapchain, until they are returned by `begin_frame`.
+    recreated_images: Option<Vec<Arc<SwapchainImage<W>>>>,
+}
+
+impl<W> FramesInFlight<W>
+where
+    W: 'static,
+{
+    /// Builds a new `FramesInFlight` that renders at most `num_frames` frames at the same time on
+    /// the given swapchain.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `num_frames` is 0.
+    /// - Panics if the swapchain and the queue don't belong to the same device.
+    ///
+    pub fn new(
+        swapchain: Arc<Swapchain<W>>,
+        queue: Arc<Queue>,
+        num_frames: usize,
+    ) -> Result<FramesInFlight<W>, OomError> {
+        assert!(num_frames >= 1);
+        assert_eq!(
+            swapchain.device().internal_object(),
+            queue.device().internal_object()
+        );
+
+        let frames = (0..num_frames)
+            .map(|_| FrameContext::new(queue.device().clone(), queue.family()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(FramesInFlight {
+            swapchain,
+            queue,
+            frames,
+            current_frame: 0,
+            state: Arc::new(Mutex::new(FrameState::Idle)),
+            recreate_swapchain: false,
+            dimensions: None,
+            recreated_images: None,
+        })
+    }
+
+    /// Returns the current swapchain.
+    #[inline]
+    pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
+        &self.swapchain
+    }
+
+    /// Returns the number of frames that can be in flight at the same time.
+    #[inline]
+    pub fn num_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Requests the swapchain to be recreated at the next call to `begin_frame`, for example
+    /// because the window has been resized.
+    ///
+    /// If `dimensions` is `None`, the current extent of the surface is used.
+    #[inline]
+    pub fn invalidate_swapchain(&mut self, dimensions: Option<[u32; 2]>) {
+        self.recreate_swapchain = true;
+        self.dimensions = dimensions;
+    }
+
+    /// Waits until the oldest frame in flight has been processed by the GPU, recreates the
+    /// swapchain if needed, and acquires the next image of the swapchain.
+    ///
+    /// If an error is returned, no image has been acquired and you can simply call this function
+    /// again later. This happens for example with
+    /// `SwapchainCreationError::UnsupportedDimensions` while a window is being resized.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the previous frame has been turned into a future that hasn't been passed to
+    ///   `end_frame`.
+    ///
+    pub fn begin_frame(&mut self) -> Result<Frame<W>, FrameError> {
+        let state = mem::replace(&mut *self.state.lock().unwrap(), FrameState::Idle);
+        match state {
+            FrameState::Idle => (),
+            FrameState::Acquired(..) => {
+                panic!("end_frame must be called before beginning a new frame")
+            }
+            FrameState::Abandoned(semaphore) => self.skip_frame(semaphore)?,
+        }
+
+        for context in self.frames.iter_mut() {
+            if let Some(submission) = context.submission.as_mut() {
+                submission.cleanup_finished();
+            }
+        }
+
+        self.frames[self.current_frame].wait()?;
+
+        let semaphore = match self.frames[self.current_frame].acquire_semaphore.take() {
+            Some(semaphore) => semaphore,
+            None => Arc::new(
+                Semaphore::alloc(self.swapchain.device().clone()).map_err(AcquireError::from)?,
+            ),
+        };
+
+        // If the swapchain turns out to be out of date, it is recreated and acquiring is tried
+        // again once.
+        for _ in 0..2 {
+            if self.recreate_swapchain {
+                let (swapchain, images) = match self.dimensions {
+                    Some(dimensions) => self.swapchain.recreate_with_dimensions(dimensions)?,
+                    None => self.swapchain.recreate()?,
+                };
+                self.swapchain = swapchain;
+                self.recreated_images = Some(images);
+                self.recreate_swapchain = false;
+                self.dimensions = None;
+            }
+
+            // Safe because the submission that waited on the semaphore, if any, has finished.
+            let result = unsafe {
+                swapchain::acquire_next_image_with_semaphore(
+                    self.swapchain.clone(),
+                    None,
+                    semaphore.clone(),
+                )
+            };
+
+            match result {
+                Ok((image_num, suboptimal, future)) => {
+                    // The image can still be used, but the swapchain should be recreated for the
+                    // next frame.
+                    if suboptimal {
+                        self.recreate_swapchain = true;
+                    }
+
+                    *self.state.lock().unwrap() = FrameState::Acquired(image_num, semaphore);
+                    return Ok(Frame {
+                        image_num,
+                        future: Some(future),
+                        recreated_images: self.recreated_images.take(),
+                        command_pool: self.frames[self.current_frame].command_pool.clone(),
+                        state: self.state.clone(),
+                    });
+                }
+                Err(AcquireError::OutOfDate) => {
+                    self.recreate_swapchain = true;
+                }
+                Err(err) => {
+                    // Nothing has been signaled, so the semaphore can be used again.
+                    self.frames[self.current_frame].acquire_semaphore = Some(semaphore);
+                    return Err(err.into());
+                }
+            }
+        }
+
+        self.frames[self.current_frame].acquire_semaphore = Some(semaphore);
+        Err(FrameError::AcquireError(AcquireError::OutOfDate))
+    }
+
+    /// Submits `future` and presents the image that was acquired by `begin_frame` after it, and
+    /// keeps the submission alive until the GPU has processed it.
+    ///
+    /// `future` is usually built from `Frame::into_future`. Its submission signals the fence and
+    /// the render semaphore of the frame, so it must not have been flushed already. If the
+    /// swapchain is out of date, it is recreated at the next call to `begin_frame` and no error is
+    /// returned. If the submission fails, the image isn't presented and the frame is skipped at
+    /// the next call to `begin_frame`, as if the `Frame` had been dropped.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `begin_frame` hasn't been called before.
+    /// - Panics if `future` already signals a fence.
+    ///
+    pub fn end_frame<F>(&mut self, future: F) -> Result<(), FrameError>
+    where
+        F: GpuFuture + 'static,
+    {
+        let state = mem::replace(&mut *self.state.lock().unwrap(), FrameState::Idle);
+        let (image_num, semaphore) = match state {
+            FrameState::Acquired(image_num, semaphore) => (image_num, semaphore),
+            _ => panic!("begin_frame must be called before ending a frame"),
+        };
+
+        let num_frames = self.frames.len();
+        let context = &mut self.frames[self.current_frame];
+
+        // If the submission fails, the semaphore is still signaled. The frame is abandoned so
+        // that the next `begin_frame` waits upon it with the fence of the same context.
+        if let Err(err) = unsafe { context.submit(&self.queue, future) } {
+            *self.state.lock().unwrap() = FrameState::Abandoned(semaphore);
+            return Err(err.into());
+        }
+        context.acquire_semaphore = Some(semaphore);
+        self.current_frame = (self.current_frame + 1) % num_frames;
+
+        match unsafe { context.present(&self.queue, &self.swapchain, image_num) } {
+            Ok(()) => Ok(()),
+            Err(FlushError::OutOfDate) => {
+                self.recreate_swapchain = true;
+                Ok(())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    // Ends the current frame without presenting its image, after its `Frame` has been dropped.
+    // The semaphore that was signaled by the acquisition is waited upon, so that it can be reused
+    // once the fence of the frame has been waited upon.
+    fn skip_frame(&mut self, semaphore: Arc<Semaphore>) -> Result<(), FrameError> {
+        let context = &mut self.frames[self.current_frame];
+
+        unsafe {
+            let mut builder = SubmitCommandBufferBuilder::new();
+            builder.add_wait_semaphore(
+                &semaphore,
+                PipelineStages {
+                    all_commands: true,
+                    ..PipelineStages::none()
+                },
+            );
+            builder.set_fence_signal(&context.fence);
+            builder.submit(&self.queue).map_err(FlushError::from)?;
+        }
+
+        context.in_flight = true;
+        context.acquire_semaphore = Some(semaphore);
+        self.current_frame = (self.current_frame + 1) % self.frames.len();
+        Ok(())
+    }
+}
+
+impl<W> Drop for FramesInFlight<W> {
+    fn drop(&mut self) {
+        for context in self.frames.iter_mut() {
+            // The submissions must be kept alive until the GPU has processed them.
+            // TODO: handle errors?
+            context.wait().unwrap();
+        }
+    }
+}
+
+// The synchronization objects and the command pool that are owned by one of the frames in flight.
+struct FrameContext {
+    // Signaled when acquiring the image of the frame, and waited upon by its submission. `None`
+    // while the frame is being recorded, or if the semaphore couldn't be reused.
+    acquire_semaphore: Option<Arc<Semaphore>>,
+    // Signaled by the submission of the frame, and waited upon when presenting its image.
+    render_semaphore: Semaphore,
+    // Signaled when the submission of the frame has been processed.
+    fence: Fence,
+    // True if `fence` will be signaled by a submission and must be waited upon.
+    in_flight: bool,
+    // The command buffers of the frame are allocated from this pool, which is reset once the
+    // fence has been waited upon.
+    command_pool: Arc<TransientCommandPool>,
+    // The submission of the frame, kept alive until the fence has been waited upon.
+    submission: Option<Box<dyn GpuFuture>>,
+    // Render semaphores that have been replaced because a presentation that was supposed to wait
+    // on them failed. They stay signaled and are destroyed once the fence has been waited upon.
+    retired_semaphores: Vec<Semaphore>,
+}
+
+impl FrameContext {
+    fn new(device: Arc<Device>, queue_family: QueueFamily) -> Result<FrameContext, OomError> {
+        Ok(FrameContext {
+            acquire_semaphore: None,
+            render_semaphore: Semaphore::alloc(device.clone())?,
+            fence: Fence::alloc(device.clone())?,
+            in_flight: false,
+            command_pool: Arc::new(TransientCommandPool::new(device, queue_family)?),
+            submission: None,
+            retired_semaphores: Vec::new(),
+        })
+    }
+
+    // Waits for the previous submission of the frame, so that the context can be used again.
+    fn wait(&mut self) -> Result<(), FlushError> {
+        if self.in_flight {
+            self.fence.wait(None)?;
+            self.fence.reset().map_err(FlushError::OomError)?;
+            self.in_flight = false;
+        }
+
+        if let Some(submission) = self.submission.take() {
+            // Safe because the fence that is signaled after the submission has been waited upon.
+            unsafe {
+                submission.signal_finished();
+            }
+        }
+
+        self.retired_semaphores.clear();
+        self.command_pool.reset().map_err(FlushError::OomError)?;
+        Ok(())
+    }
+
+    // Submits `future` so that it signals the render semaphore and the fence of the frame.
+    //
+    // Safety: the context must have been waited upon since its last submission.
+    unsafe fn submit<F>(&mut self, queue: &Queue, future: F) -> Result<(), FlushError>
+    where
+        F: GpuFuture + 'static,
+    {
+        debug_assert!(!self.in_flight);
+
+        let mut builder = match future.build_submission()? {
+            SubmitAnyBuilder::Empty => SubmitCommandBufferBuilder::new(),
+            SubmitAnyBuilder::SemaphoresWait(semaphores) => semaphores.into(),
+            SubmitAnyBuilder::CommandBuffer(builder) => builder,
+            SubmitAnyBuilder::BindSparse(_) | SubmitAnyBuilder::QueuePresent(_) => {
+                // These can't signal the fence in the same batch, so they are submitted on their
+                // own and the frame waits for them on the queue.
+                future.flush()?;
+                SubmitCommandBufferBuilder::new()
+            }
+        };
+
+        // Same as `FenceSignalFuture`, this is part of the safety contract of the trait but is
+        // easy to get wrong.
+        assert!(!builder.has_fence());
+        builder.add_signal_semaphore(&self.render_semaphore);
+        builder.set_fence_signal(&self.fence);
+        builder.submit(queue)?;
+
+        self.in_flight = true;
+        self.submission = Some(Box::new(future));
+        Ok(())
+    }
+
+    // Presents the image after the submission of the frame.
+    //
+    // Safety: `submit` must have been called since the last presentation.
+    unsafe fn present<W>(
+        &mut self,
+        queue: &Queue,
+        swapchain: &Swapchain<W>,
+        image_num: usize,
+    ) -> Result<(), FlushError> {
+        let mut builder = SubmitPresentBuilder::new();
+        builder.add_wait_semaphore(&self.render_semaphore);
+        builder.add_swapchain(swapchain, image_num as u32, None, None);
+
+        match builder.submit(queue) {
+            Ok(()) => Ok(()),
+            // The presentation is still enqueued with these errors, and waits on the semaphore.
+            Err(err @ SubmitPresentError::OutOfDate)
+            | Err(err @ SubmitPresentError::SurfaceLost)
+            | Err(err @ SubmitPresentError::FullscreenExclusiveLost) => Err(err.into()),
+            Err(err) => {
+                let semaphore =
+                    Semaphore::alloc(queue.device().clone()).map_err(FlushError::OomError)?;
+                let retired = mem::replace(&mut self.render_semaphore, semaphore);
+                self.retired_semaphores.push(retired);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+// The state of the frame that was returned by `begin_frame`.
+enum FrameState {
+    // No frame is being recorded.
+    Idle,
+    // An image has been acquired with this semaphore and must be presented by `end_frame`.
+    Acquired(usize, Arc<Semaphore>),
+    // The `Frame` has been dropped without being turned into a future. The semaphore has been
+    // signaled by the acquisition and must be waited upon before it can be used again.
+    Abandoned(Arc<Semaphore>),
+}
+
+impl FrameState {
+    // Called when the `Frame` is dropped without being turned into a future.
+    fn abandon(&mut self) {
+        if let FrameState::Acquired(_, semaphore) = self {
+            *self = FrameState::Abandoned(semaphore.clone());
+        }
+    }
+}
+
+/// A frame that has been started with `FramesInFlight::begin_frame`.
+///
+/// If the frame is dropped without calling `into_future`, it is skipped by the next call to
+/// `begin_frame` and its image isn't presented.
+pub struct Frame<W> {
+    image_num: usize,
+    // `None` once the frame has been turned into a future.
+    future: Option<SwapchainAcquireFuture<W>>,
+    recreated_images: Option<Vec<Arc<SwapchainImage<W>>>>,
+    command_pool: Arc<TransientCommandPool>,
+    state: Arc<Mutex<FrameState>>,
+}
+
+impl<W> Frame<W> {
+    /// Returns the index of the acquired image in the list of images of the swapchain.
+    #[inline]
+    pub fn image_num(&self) -> usize {
+        self.image_num
+    }
+
+    /// Returns the images of the new swapchain if it has been recreated when beginning this frame.
+    /// Everything that depends on the images, such as framebuffers, must then be recreated.
+    #[inline]
+    pub fn recreated_images(&self) -> Option<&[Arc<SwapchainImage<W>>]> {
+        self.recreated_images.as_ref().map(|images| &images[..])
+    }
+
+    /// Returns the command pool of this frame, which is reset once the frame has been processed by
+    /// the GPU. The command buffers that are allocated from it should be dropped by then, which
+    /// is the case if they are only used by the submission of the frame.
+    #[inline]
+    pub fn command_pool(&self) -> &Arc<TransientCommandPool> {
+        &self.command_pool
+    }
+
+    /// Returns the future that is signaled when the image has been acquired. Your submission must
+    /// happen after it.
+    #[inline]
+    pub fn into_future(mut self) -> SwapchainAcquireFuture<W> {
+        self.future.take().unwrap()
+    }
+}
+
+impl<W> Drop for Frame<W> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.future.is_some() {
+            self.state.lock().unwrap().abandon();
+        }
+    }
+}
+
+/// Error that can happen when beginning or ending a frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FrameError {
+    /// Error while acquiring an image.
+    AcquireError(AcquireError),
+    /// Error while recreating the swapchain.
+    SwapchainCreationError(SwapchainCreationError),
+    /// Error while submitting or waiting for a frame.
+    FlushError(FlushError),
+}
+
+impl error::Error for FrameError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            FrameError::AcquireError(ref err) => Some(err),
+            FrameError::SwapchainCreationError(ref err) => Some(err),
+            FrameError::FlushError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FrameError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                FrameError::AcquireError(_) => "error while acquiring an image",
+                FrameError::SwapchainCreationError(_) => "error while recreating the swapchain",
+                FrameError::FlushError(_) => "error while submitting or waiting for a frame",
+            }
+        )
+    }
+}
+
+impl From<AcquireError> for FrameError {
+    #[inline]
+    fn from(err: AcquireError) -> FrameError {
+        FrameError::AcquireError(err)
+    }
+}
+
+impl From<SwapchainCreationError> for FrameError {
+    #[inline]
+    fn from(err: SwapchainCreationError) -> FrameError {
+        FrameError::SwapchainCreationError(err)
+    }
+}
+
+impl From<FlushError> for FrameError {
+    #[inline]
+    fn from(err: FlushError) -> FrameError {
+        FrameError::FlushError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_buffer::pool::CommandPool;
+    use crate::sync;
+
+    #[test]
+    fn abandon_acquired_frame() {
+        let (device, _) = gfx_dev_and_queue!();
+        let semaphore = Arc::new(Semaphore::alloc(device).unwrap());
+
+        let mut state = FrameState::Idle;
+        state.abandon();
+        assert!(matches!(state, FrameState::Idle));
+
+        let mut state = FrameState::Acquired(1, semaphore.clone());
+        state.abandon();
+        match state {
+            FrameState::Abandoned(s) => assert!(Arc::ptr_eq(&s, &semaphore)),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn reuse_context() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let mut context = FrameContext::new(device.clone(), queue.family()).unwrap();
+
+        // Waiting on a context that hasn't been submitted doesn't block.
+        context.wait().unwrap();
+        assert!(!context.in_flight);
+
+        let cb = context
+            .command_pool
+            .alloc(false, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+        unsafe {
+            context.submit(&queue, sync::now(device.clone())).unwrap();
+        }
+        assert!(context.in_flight);
+        assert!(context.submission.is_some());
+
+        // The pool isn't reset while one of its command buffers is alive.
+        drop(cb);
+        context.wait().unwrap();
+        assert!(!context.in_flight);
+        assert!(context.submission.is_none());
+        assert_eq!(context.command_pool.num_in_use(), 0);
+
+        // The fence has been reset and can be signaled again.
+        unsafe {
+            context.submit(&queue, sync::now(device)).unwrap();
+        }
+        context.wait().unwrap();
+    }
+
+    #[test]
+    fn frame_error_source() {
+        let err = FrameError::from(AcquireError::OutOfDate);
+        assert_eq!(err, FrameError::AcquireError(AcquireError::OutOfDate));
+        assert!(error::Error::source(&err).is_some());
+
+        let err = FrameError::from(FlushError::OutOfDate);
+        assert_eq!(err, FrameError::FlushError(FlushError::OutOfDate));
+    }
+}
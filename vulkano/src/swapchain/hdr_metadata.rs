@@ -0,0 +1,109 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::vk;
+use std::ptr;
+
+/// Describes the mastering display and the content of a swapchain, so that the presentation
+/// engine can map it to the capabilities of the monitor.
+///
+/// This is mostly useful together with the HDR color spaces, such as `ColorSpace::Hdr10St2084`.
+/// The chromaticity coordinates are given in the CIE 1931 color space, and the luminance values
+/// in nits (candelas per square meter).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HdrMetadata {
+    /// Chromaticity of the red primary of the mastering display.
+    pub display_primary_red: [f32; 2],
+
+    /// Chromaticity of the green primary of the mastering display.
+    pub display_primary_green: [f32; 2],
+
+    /// Chromaticity of the blue primary of the mastering display.
+    pub display_primary_blue: [f32; 2],
+
+    /// Chromaticity of the white point of the mastering display.
+    pub white_point: [f32; 2],
+
+    /// Maximum luminance of the mastering display.
+    pub max_luminance: f32,
+
+    /// Minimum luminance of the mastering display.
+    pub min_luminance: f32,
+
+    /// Luminance of the brightest pixel of the content.
+    pub max_content_light_level: f32,
+
+    /// Maximum average luminance of a frame of the content.
+    pub max_frame_average_light_level: f32,
+}
+
+impl HdrMetadata {
+    /// Returns the metadata of a mastering display with the BT.2020 primaries and a D65 white
+    /// point, as used by HDR10 content.
+    #[inline]
+    pub fn bt2020(max_luminance: f32, min_luminance: f32) -> HdrMetadata {
+        HdrMetadata {
+            display_primary_red: [0.708, 0.292],
+            display_primary_green: [0.170, 0.797],
+            display_primary_blue: [0.131, 0.046],
+            white_point: [0.3127, 0.3290],
+            max_luminance,
+            min_luminance,
+            max_content_light_level: max_luminance,
+            max_frame_average_light_level: max_luminance,
+        }
+    }
+
+    pub(crate) fn to_vk(self) -> vk::HdrMetadataEXT {
+        #[inline]
+        fn xy(val: [f32; 2]) -> vk::XYColorEXT {
+            vk::XYColorEXT {
+                x: val[0],
+                y: val[1],
+            }
+        }
+
+        vk::HdrMetadataEXT {
+            sType: vk::STRUCTURE_TYPE_HDR_METADATA_EXT,
+            pNext: ptr::null(),
+            displayPrimaryRed: xy(self.display_primary_red),
+            displayPrimaryGreen: xy(self.display_primary_green),
+            displayPrimaryBlue: xy(self.display_primary_blue),
+            whitePoint: xy(self.white_point),
+            maxLuminance: self.max_luminance,
+            minLuminance: self.min_luminance,
+            maxContentLightLevel: self.max_content_light_level,
+            maxFrameAverageLightLevel: self.max_frame_average_light_level,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::swapchain::HdrMetadata;
+
+    #[test]
+    fn bt2020_to_vk() {
+        let metadata = HdrMetadata {
+            max_content_light_level: 600.0,
+            ..HdrMetadata::bt2020(1000.0, 0.001)
+        };
+
+        let vk = metadata.to_vk();
+        assert_eq!(
+            [vk.displayPrimaryRed.x, vk.displayPrimaryRed.y],
+            [0.708, 0.292]
+        );
+        assert_eq!([vk.whitePoint.x, vk.whitePoint.y], [0.3127, 0.3290]);
+        assert_eq!(vk.maxLuminance, 1000.0);
+        assert_eq!(vk.minLuminance, 0.001);
+        assert_eq!(vk.maxContentLightLevel, 600.0);
+        assert_eq!(vk.maxFrameAverageLightLevel, 1000.0);
+    }
+}
@@ -165,7 +165,7 @@
 //! # use vulkano::image::ImageUsage;
 //! # use vulkano::sync::SharingMode;
 //! # use vulkano::format::Format;
-//! # use vulkano::swapchain::{Surface, Swapchain, SurfaceTransform, PresentMode, CompositeAlpha, ColorSpace, FullscreenExclusive};
+//! # use vulkano::swapchain::{Surface, Swapchain, SurfaceTransform, PresentMode, CompositeAlpha, ColorSpace, FullscreenExclusive, SwapchainOptions};
 //! # fn create_swapchain(
 //! #     device: Arc<Device>, surface: Arc<Surface<()>>, present_queue: Arc<Queue>,
 //! #     buffers_count: u32, format: Format, dimensions: [u32; 2],
@@ -206,10 +206,14 @@
 //!     present_mode,
 //!     // How to handle fullscreen exclusivity
 //!     fullscreen_exclusive,
+//!     // No Win32 monitor, as we don't control fullscreen exclusivity ourselves.
+//!     None,
 //!     // Clip the parts of the buffer which aren't visible.
 //!     true,
-//!     // No previous swapchain.
-//!     ColorSpace::SrgbNonLinear
+//!     // The color space of the images.
+//!     ColorSpace::SrgbNonLinear,
+//!     // No parameter that requires device features or extensions.
+//!     SwapchainOptions::default(),
 //! )?;
 //!
 //! # Ok(())
@@ -254,6 +258,25 @@
 //! }
 //! ```
 //!
+//! If the `khr_incremental_present` extension is enabled on the device, you can also pass the
+//! rectangles that changed since the previous frame with `then_swapchain_present_incremental`.
+//! The presentation engine may then only compose these areas, which saves power on mobile.
+//!
+//! ```
+//! use vulkano::swapchain::PresentRegion;
+//! use vulkano::sync::GpuFuture;
+//! # use vulkano::swapchain;
+//! # let swapchain: ::std::sync::Arc<::vulkano::swapchain::Swapchain<()>> = return;
+//! # let queue: ::std::sync::Arc<::vulkano::device::Queue> = return;
+//! # let (image_num, _, acquire_future) = swapchain::acquire_next_image(swapchain.clone(), None).unwrap();
+//!
+//! // Only a 200x100 rectangle at the top-left corner of the image was redrawn.
+//! let damage = PresentRegion::from_rectangles(vec![([0, 0], [200, 100])]);
+//! acquire_future
+//!     .then_swapchain_present_incremental(queue.clone(), swapchain.clone(), image_num, damage)
+//!     .then_signal_fence_and_flush().unwrap();
+//! ```
+//!
 //! ## Recreating a swapchain
 //!
 //! In some situations, the swapchain will become invalid by itself. This includes for example when
@@ -309,7 +332,9 @@ use std::sync::atomic::AtomicBool;
 pub use self::capabilities::Capabilities;
 pub use self::capabilities::ColorSpace;
 pub use self::capabilities::CompositeAlpha;
+pub use self::capabilities::PresentGravity;
 pub use self::capabilities::PresentMode;
+pub use self::capabilities::PresentScaling;
 pub use self::capabilities::SupportedCompositeAlpha;
 pub use self::capabilities::SupportedCompositeAlphaIter;
 pub use self::capabilities::SupportedPresentModes;
@@ -317,6 +342,12 @@ pub use self::capabilities::SupportedPresentModesIter;
 pub use self::capabilities::SupportedSurfaceTransforms;
 pub use self::capabilities::SupportedSurfaceTransformsIter;
 pub use self::capabilities::SurfaceTransform;
+pub use self::display_timing::DisplayTimingError;
+pub use self::display_timing::PastPresentationTiming;
+pub use self::frames::Frame;
+pub use self::frames::FrameError;
+pub use self::frames::FramesInFlight;
+pub use self::hdr_metadata::HdrMetadata;
 pub use self::present_region::PresentRegion;
 pub use self::present_region::RectangleLayer;
 pub use self::surface::CapabilitiesError;
@@ -324,19 +355,27 @@ pub use self::surface::Surface;
 pub use self::surface::SurfaceCreationError;
 pub use self::swapchain::acquire_next_image;
 pub use self::swapchain::acquire_next_image_raw;
+pub use self::swapchain::acquire_next_image_with_semaphore;
 pub use self::swapchain::present;
 pub use self::swapchain::present_incremental;
+pub use self::swapchain::present_with_id;
 pub use self::swapchain::AcquireError;
 pub use self::swapchain::AcquiredImage;
 pub use self::swapchain::FullscreenExclusive;
 pub use self::swapchain::FullscreenExclusiveError;
 pub use self::swapchain::PresentFuture;
+pub use self::swapchain::PresentWaitError;
 pub use self::swapchain::Swapchain;
 pub use self::swapchain::SwapchainAcquireFuture;
 pub use self::swapchain::SwapchainCreationError;
+pub use self::swapchain::SwapchainOptions;
+pub use self::swapchain::Win32Monitor;
 
 mod capabilities;
 pub mod display;
+mod display_timing;
+mod frames;
+mod hdr_metadata;
 mod present_region;
 mod surface;
 mod swapchain;
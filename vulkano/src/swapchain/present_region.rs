@@ -12,13 +12,32 @@ use crate::vk;
 
 /// Represents a region on an image.
 ///
-/// A region consists of an arbitrary amount of rectangles.
-#[derive(Debug, Clone)]
+/// A region consists of an arbitrary amount of rectangles, usually the parts of the image that
+/// changed since the previous present. An empty region means that the whole image changed.
+#[derive(Debug, Clone, Default)]
 pub struct PresentRegion {
     pub rectangles: Vec<RectangleLayer>,
 }
 
 impl PresentRegion {
+    /// Builds a present region made of the given damaged rectangles of the first layer.
+    #[inline]
+    pub fn from_rectangles<I>(rectangles: I) -> PresentRegion
+    where
+        I: IntoIterator<Item = ([i32; 2], [u32; 2])>,
+    {
+        PresentRegion {
+            rectangles: rectangles
+                .into_iter()
+                .map(|(offset, extent)| RectangleLayer {
+                    offset,
+                    extent,
+                    layer: 0,
+                })
+                .collect(),
+        }
+    }
+
     /// Returns true if this present region is compatible with swapchain.
     pub fn is_compatible_with<W>(&self, swapchain: &Swapchain<W>) -> bool {
         self.rectangles
@@ -42,13 +61,21 @@ pub struct RectangleLayer {
 
 impl RectangleLayer {
     /// Returns true if this rectangle layer is compatible with swapchain.
+    #[inline]
     pub fn is_compatible_with<W>(&self, swapchain: &Swapchain<W>) -> bool {
+        self.fits(swapchain.dimensions(), swapchain.layers())
+    }
+
+    // Returns true if the rectangle is inside images with the given dimensions and layers.
+    fn fits(&self, dimensions: [u32; 2], layers: u32) -> bool {
         // FIXME negative offset is not disallowed by spec, but semantically should not be possible
-        debug_assert!(self.offset[0] >= 0);
-        debug_assert!(self.offset[1] >= 0);
-        self.offset[0] as u32 + self.extent[0] <= swapchain.dimensions()[0]
-            && self.offset[1] as u32 + self.extent[1] <= swapchain.dimensions()[1]
-            && self.layer < swapchain.layers()
+        if self.offset[0] < 0 || self.offset[1] < 0 {
+            return false;
+        }
+
+        (self.offset[0] as u64 + self.extent[0] as u64) <= dimensions[0] as u64
+            && (self.offset[1] as u64 + self.extent[1] as u64) <= dimensions[1] as u64
+            && self.layer < layers
     }
 
     pub(crate) fn to_vk(&self) -> vk::RectLayerKHR {
@@ -65,3 +92,47 @@ impl RectangleLayer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::swapchain::PresentRegion;
+    use crate::swapchain::RectangleLayer;
+
+    #[test]
+    fn from_rectangles() {
+        let region = PresentRegion::from_rectangles(vec![([0, 0], [200, 100]), ([10, 20], [1, 1])]);
+        assert_eq!(
+            region.rectangles,
+            vec![
+                RectangleLayer {
+                    offset: [0, 0],
+                    extent: [200, 100],
+                    layer: 0,
+                },
+                RectangleLayer {
+                    offset: [10, 20],
+                    extent: [1, 1],
+                    layer: 0,
+                },
+            ]
+        );
+        assert!(PresentRegion::default().rectangles.is_empty());
+    }
+
+    #[test]
+    fn rectangle_fits() {
+        let rect = |offset, extent, layer| RectangleLayer {
+            offset,
+            extent,
+            layer,
+        };
+
+        assert!(rect([0, 0], [800, 600], 0).fits([800, 600], 1));
+        assert!(rect([700, 500], [100, 100], 1).fits([800, 600], 2));
+        assert!(!rect([700, 500], [101, 100], 0).fits([800, 600], 1));
+        assert!(!rect([0, 0], [800, 600], 1).fits([800, 600], 1));
+        assert!(!rect([-1, 0], [10, 10], 0).fits([800, 600], 1));
+        // The sum of the offset and the extent doesn't overflow.
+        assert!(!rect([i32::MAX, 0], [u32::MAX, 1], 0).fits([800, 600], 1));
+    }
+}
@@ -9,6 +9,7 @@
 
 use std::error;
 use std::fmt;
+use std::mem;
 use std::mem::MaybeUninit;
 use std::os::raw::c_ulong;
 use std::ptr;
@@ -23,8 +24,10 @@ use crate::instance::QueueFamily;
 use crate::swapchain::capabilities;
 use crate::swapchain::display::DisplayMode;
 use crate::swapchain::display::DisplayPlane;
+use crate::swapchain::display::DisplayPlaneAlpha;
 use crate::swapchain::Capabilities;
 use crate::swapchain::SurfaceSwapchainLock;
+use crate::swapchain::SurfaceTransform;
 
 use crate::check_errors;
 use crate::vk;
@@ -62,17 +65,54 @@ impl<W> Surface<W> {
             has_swapchain: AtomicBool::new(false),
         }
     }
+}
 
+impl Surface<()> {
     /// Creates a `Surface` that covers a display mode.
     ///
+    /// The surface is opaque, isn't transformed and covers the whole visible region of the mode.
+    /// See `from_display_plane` for more control.
+    ///
     /// # Panic
     ///
     /// - Panics if `display_mode` and `plane` don't belong to the same physical device.
     /// - Panics if `plane` doesn't support the display of `display_mode`.
     ///
+    #[inline]
     pub fn from_display_mode(
         display_mode: &DisplayMode,
         plane: &DisplayPlane,
+    ) -> Result<Arc<Surface<()>>, SurfaceCreationError> {
+        Surface::from_display_plane(
+            display_mode,
+            plane,
+            plane.current_stack_index(),
+            SurfaceTransform::Identity,
+            DisplayPlaneAlpha::Opaque,
+            display_mode.visible_region(),
+        )
+    }
+
+    /// Creates a `Surface` that shows a display plane on a display mode.
+    ///
+    /// - `stack_index` is the position of the plane in the stack of planes of the display.
+    /// - `transform` is the transformation applied to the images before they are shown.
+    /// - `alpha` describes how the plane is blended with the planes below it.
+    /// - `image_extent` is the size of the images that will be presented on the surface.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `display_mode` and `plane` don't belong to the same physical device.
+    /// - Panics if `plane` doesn't support the display of `display_mode`.
+    /// - Panics if `transform` isn't supported by the display, or `alpha` by the plane.
+    ///
+    pub fn from_display_plane(
+        display_mode: &DisplayMode,
+        plane: &DisplayPlane,
+        stack_index: u32,
+        transform: SurfaceTransform,
+        alpha: DisplayPlaneAlpha,
+        image_extent: [u32; 2],
     ) -> Result<Arc<Surface<()>>, SurfaceCreationError> {
         if !display_mode
             .display()
@@ -91,6 +131,16 @@ impl<W> Surface<W> {
             plane.physical_device().internal_object()
         );
         assert!(plane.supports(display_mode.display()));
+        assert!(display_mode
+            .display()
+            .supported_transforms()
+            .supports(transform));
+        assert!(plane
+            .capabilities(display_mode)?
+            .supported_alpha
+            .supports(alpha));
+
+        let (alpha_mode, global_alpha) = alpha.to_vk();
 
         let instance = display_mode.display().physical_device().instance();
         let vk = instance.pointers();
@@ -102,14 +152,13 @@ impl<W> Surface<W> {
                 flags: 0, // reserved
                 displayMode: display_mode.internal_object(),
                 planeIndex: plane.index(),
-                planeStackIndex: 0, // FIXME: plane.properties.currentStackIndex,
-                transform: vk::SURFACE_TRANSFORM_IDENTITY_BIT_KHR, // TODO: let user choose
-                globalAlpha: 0.0,   // TODO: let user choose
-                alphaMode: vk::DISPLAY_PLANE_ALPHA_OPAQUE_BIT_KHR, // TODO: let user choose
+                planeStackIndex: stack_index,
+                transform: transform as u32,
+                globalAlpha: global_alpha,
+                alphaMode: alpha_mode,
                 imageExtent: vk::Extent2D {
-                    // TODO: let user choose
-                    width: display_mode.visible_region()[0],
-                    height: display_mode.visible_region()[1],
+                    width: image_extent[0],
+                    height: image_extent[1],
                 },
             };
 
@@ -117,7 +166,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateDisplayPlaneSurfaceKHR(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -130,7 +179,9 @@ impl<W> Surface<W> {
             has_swapchain: AtomicBool::new(false),
         }))
     }
+}
 
+impl<W> Surface<W> {
     /// Creates a `Surface` from a Win32 window.
     ///
     /// The surface's min, max and current extent will always match the window's dimensions.
@@ -167,7 +218,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateWin32SurfaceKHR(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -216,7 +267,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateXcbSurfaceKHR(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -265,7 +316,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateXlibSurfaceKHR(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -314,7 +365,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateWaylandSurfaceKHR(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -359,7 +410,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateAndroidSurfaceKHR(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -405,7 +456,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateIOSSurfaceMVK(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -451,7 +502,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateMacOSSurfaceMVK(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -496,7 +547,7 @@ impl<W> Surface<W> {
             check_errors(vk.CreateViSurfaceNN(
                 instance.internal_object(),
                 &infos,
-                ptr::null(),
+                instance.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -510,6 +561,48 @@ impl<W> Surface<W> {
         }))
     }
 
+    /// Creates a `Surface` that isn't associated with any window or display.
+    ///
+    /// Presenting to such a surface has no visible effect, but the whole swapchain code path can
+    /// be used, which is useful for testing and offscreen rendering. The current extent of the
+    /// surface is undefined, so the dimensions of the swapchain must be chosen explicitly.
+    pub fn headless(
+        instance: Arc<Instance>,
+        win: W,
+    ) -> Result<Arc<Surface<W>>, SurfaceCreationError> {
+        let vk = instance.pointers();
+
+        if !instance.loaded_extensions().ext_headless_surface {
+            return Err(SurfaceCreationError::MissingExtension {
+                name: "VK_EXT_headless_surface",
+            });
+        }
+
+        let surface = unsafe {
+            let infos = vk::HeadlessSurfaceCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_HEADLESS_SURFACE_CREATE_INFO_EXT,
+                pNext: ptr::null(),
+                flags: 0, // reserved
+            };
+
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateHeadlessSurfaceEXT(
+                instance.internal_object(),
+                &infos,
+                instance.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(Surface {
+            window: win,
+            instance: instance.clone(),
+            surface,
+            has_swapchain: AtomicBool::new(false),
+        }))
+    }
+
     /// Returns true if the given queue family can draw on this surface.
     // FIXME: vulkano doesn't check this for the moment!
     pub fn is_supported(&self, queue: QueueFamily) -> Result<bool, CapabilitiesError> {
@@ -640,8 +733,9 @@ impl<W> Surface<W> {
                     .into_iter()
                     .filter_map(|f| {
                         // TODO: Change the way capabilities not supported in vk-sys are handled
-                        Format::from_vulkan_num(f.format).map(|format| {
-                            (format, capabilities::color_space_from_num(f.colorSpace))
+                        Format::from_vulkan_num(f.format).and_then(|format| {
+                            capabilities::color_space_from_num(f.colorSpace)
+                                .map(|color_space| (format, color_space))
                         })
                     })
                     .collect(),
@@ -650,6 +744,59 @@ impl<W> Surface<W> {
         }
     }
 
+    /// Returns true if the surface can be presented with a protected swapchain by a certain
+    /// device.
+    ///
+    /// Returns false if the `khr_get_surface_capabilities2` and
+    /// `khr_surface_protected_capabilities` extensions aren't loaded on the instance, as the
+    /// support can't be queried without them.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the surface don't belong to the same instance.
+    ///
+    pub fn supports_protected(&self, device: PhysicalDevice) -> Result<bool, CapabilitiesError> {
+        assert_eq!(
+            &*self.instance as *const _,
+            &**device.instance() as *const _,
+            "Instance mismatch in Surface::supports_protected"
+        );
+
+        let extensions = self.instance.loaded_extensions();
+        if !extensions.khr_get_surface_capabilities2
+            || !extensions.khr_surface_protected_capabilities
+        {
+            return Ok(false);
+        }
+
+        unsafe {
+            let vk = self.instance.pointers();
+
+            let info = vk::PhysicalDeviceSurfaceInfo2KHR {
+                sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SURFACE_INFO_2_KHR,
+                pNext: ptr::null(),
+                surface: self.surface,
+            };
+            let mut protected_caps = vk::SurfaceProtectedCapabilitiesKHR {
+                sType: vk::STRUCTURE_TYPE_SURFACE_PROTECTED_CAPABILITIES_KHR,
+                pNext: ptr::null(),
+                supportsProtected: vk::FALSE,
+            };
+            let mut caps = vk::SurfaceCapabilities2KHR {
+                sType: vk::STRUCTURE_TYPE_SURFACE_CAPABILITIES_2_KHR,
+                pNext: &mut protected_caps as *mut _ as *mut _,
+                surfaceCapabilities: mem::zeroed(),
+            };
+            check_errors(vk.GetPhysicalDeviceSurfaceCapabilities2KHR(
+                device.internal_object(),
+                &info,
+                &mut caps,
+            ))?;
+
+            Ok(protected_caps.supportsProtected != vk::FALSE)
+        }
+    }
+
     #[inline]
     pub fn window(&self) -> &W {
         &self.window
@@ -692,7 +839,11 @@ impl<W> Drop for Surface<W> {
     fn drop(&mut self) {
         unsafe {
             let vk = self.instance.pointers();
-            vk.DestroySurfaceKHR(self.instance.internal_object(), self.surface, ptr::null());
+            vk.DestroySurfaceKHR(
+                self.instance.internal_object(),
+                self.surface,
+                self.instance.alloc_ptr(),
+            );
         }
     }
 }
@@ -708,6 +859,9 @@ pub enum SurfaceCreationError {
         /// Name of the missing extension.
         name: &'static str,
     },
+
+    /// The kind of window that was passed isn't supported on this platform.
+    UnsupportedWindowHandle,
 }
 
 impl error::Error for SurfaceCreationError {
@@ -731,6 +885,9 @@ impl fmt::Display for SurfaceCreationError {
                 SurfaceCreationError::MissingExtension { .. } => {
                     "the extension required for this function was not enabled"
                 }
+                SurfaceCreationError::UnsupportedWindowHandle => {
+                    "the kind of window isn't supported on this platform"
+                }
             }
         )
     }
@@ -810,9 +967,9 @@ impl From<Error> for CapabilitiesError {
 
 #[cfg(test)]
 mod tests {
-    use std::ptr;
     use crate::swapchain::Surface;
     use crate::swapchain::SurfaceCreationError;
+    use std::ptr;
 
     #[test]
     fn khr_win32_surface_ext_missing() {
@@ -858,4 +1015,13 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn ext_headless_surface_ext_missing() {
+        let instance = instance!();
+        match Surface::headless(instance, ()) {
+            Err(SurfaceCreationError::MissingExtension { .. }) => (),
+            _ => panic!(),
+        }
+    }
 }
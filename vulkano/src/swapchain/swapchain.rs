@@ -7,12 +7,15 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::cmp;
 use std::error;
 use std::fmt;
 use std::mem;
 use std::mem::MaybeUninit;
+use std::os::raw::c_void;
 use std::ptr;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -21,7 +24,7 @@ use std::time::Duration;
 use crate::buffer::BufferAccess;
 use crate::command_buffer::submit::SubmitAnyBuilder;
 use crate::command_buffer::submit::SubmitPresentBuilder;
-use crate::command_buffer::submit::SubmitPresentError;
+use crate::command_buffer::submit::SubmitScheduler;
 use crate::command_buffer::submit::SubmitSemaphoresWaitBuilder;
 use crate::device::Device;
 use crate::device::DeviceOwned;
@@ -41,8 +44,13 @@ use crate::image::ImageUsage;
 use crate::swapchain::CapabilitiesError;
 use crate::swapchain::ColorSpace;
 use crate::swapchain::CompositeAlpha;
+use crate::swapchain::DisplayTimingError;
+use crate::swapchain::HdrMetadata;
+use crate::swapchain::PastPresentationTiming;
+use crate::swapchain::PresentGravity;
 use crate::swapchain::PresentMode;
 use crate::swapchain::PresentRegion;
+use crate::swapchain::PresentScaling;
 use crate::swapchain::Surface;
 use crate::swapchain::SurfaceSwapchainLock;
 use crate::swapchain::SurfaceTransform;
@@ -83,6 +91,96 @@ pub enum FullscreenExclusive {
     AppControlled,
 }
 
+/// A handle to a Win32 monitor (`HMONITOR`).
+///
+/// On Windows, this must be provided when creating a swapchain with
+/// `FullscreenExclusive::AppControlled`, so that the driver knows on which monitor the
+/// application wants to be fullscreen.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Win32Monitor(*const c_void);
+
+impl Win32Monitor {
+    /// Wraps a Win32 monitor handle, for example as returned by `MonitorFromWindow`.
+    ///
+    /// # Safety
+    ///
+    /// - `hmonitor` must be a valid Win32 monitor handle.
+    #[inline]
+    pub unsafe fn new<T>(hmonitor: *const T) -> Win32Monitor {
+        Win32Monitor(hmonitor as *const c_void)
+    }
+
+    #[inline]
+    fn to_vk(self) -> vk::SurfaceFullScreenExclusiveWin32InfoEXT {
+        vk::SurfaceFullScreenExclusiveWin32InfoEXT {
+            sType: vk::STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_WIN32_INFO_EXT,
+            pNext: ptr::null(),
+            hmonitor: self.0 as *mut _,
+        }
+    }
+}
+
+// Win32 monitor handles can be used from any thread.
+unsafe impl Send for Win32Monitor {}
+unsafe impl Sync for Win32Monitor {}
+
+/// Optional parameters of a swapchain that require device features or extensions.
+///
+/// The default value doesn't use any of them, and can be used without any feature or extension.
+/// The `swapchain_maintenance1` feature itself requires the `ext_surface_maintenance1` instance
+/// extension.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapchainOptions {
+    /// The present modes that can be switched to with `PresentFuture::with_present_mode` without
+    /// recreating the swapchain, in addition to the present mode of the swapchain.
+    ///
+    /// They must be supported by the surface, and must be compatible with the present mode of the
+    /// swapchain according to the surface. Requires the `swapchain_maintenance1` feature.
+    pub present_modes: Vec<PresentMode>,
+
+    /// How the images are scaled when their dimensions don't match the dimensions of the surface.
+    /// If `None`, the behavior is implementation-specific. Requires the `swapchain_maintenance1`
+    /// feature.
+    pub scaling_behavior: Option<PresentScaling>,
+
+    /// Where the images are placed on the horizontal and vertical axes of the surface when they
+    /// don't fill it. If `None`, the behavior is implementation-specific. Requires the
+    /// `swapchain_maintenance1` feature.
+    pub present_gravity: Option<[PresentGravity; 2]>,
+
+    /// If true, the memory of each image is only allocated when the image is acquired for the
+    /// first time, which makes creating the swapchain faster.
+    ///
+    /// You must not create views of an image, and therefore framebuffers, before it has been
+    /// acquired once. Requires the `swapchain_maintenance1` feature.
+    pub deferred_memory_allocation: bool,
+
+    /// If true, the swapchain counts the vertical blanking periods of the display, which can then
+    /// be queried with `Swapchain::vblank_counter`.
+    ///
+    /// Requires the `ext_display_control` extension, and a surface created from a display.
+    pub vblank_counter: bool,
+
+    /// If true, the images of the swapchain are protected images, which can only be accessed by
+    /// protected queues and protected command buffers, and are presented without being readable
+    /// by the host.
+    ///
+    /// Requires the `protected_memory` feature, and a surface that supports protected
+    /// presentation, see `Surface::supports_protected`.
+    pub protected: bool,
+}
+
+impl SwapchainOptions {
+    // Returns true if one of the options that require the `swapchain_maintenance1` feature is
+    // used.
+    fn uses_maintenance1(&self) -> bool {
+        !self.present_modes.is_empty()
+            || self.scaling_behavior.is_some()
+            || self.present_gravity.is_some()
+            || self.deferred_memory_allocation
+    }
+}
+
 impl FullscreenExclusive {
     fn vk_sys_enum(&self) -> u32 {
         match self {
@@ -110,7 +208,32 @@ pub fn acquire_next_image<W>(
     swapchain: Arc<Swapchain<W>>,
     timeout: Option<Duration>,
 ) -> Result<(usize, bool, SwapchainAcquireFuture<W>), AcquireError> {
-    let semaphore = Semaphore::from_pool(swapchain.device.clone())?;
+    let semaphore = Arc::new(Semaphore::from_pool(swapchain.device.clone())?);
+    unsafe { acquire_next_image_with_semaphore(swapchain, timeout, semaphore) }
+}
+
+/// Same as `acquire_next_image`, except that the image is acquired with the given semaphore
+/// instead of a semaphore from the pool of the device.
+///
+/// This makes it possible to cycle between a fixed set of semaphores, for example one per frame
+/// in flight as `FramesInFlight` does. The returned future keeps a reference to the semaphore.
+///
+/// # Safety
+///
+/// - The semaphore must be unsignaled and must not have any pending operation. This is the case
+///   if the future of the previous acquisition with this semaphore has been submitted, and the
+///   submission has finished executing.
+///
+pub unsafe fn acquire_next_image_with_semaphore<W>(
+    swapchain: Arc<Swapchain<W>>,
+    timeout: Option<Duration>,
+    semaphore: Arc<Semaphore>,
+) -> Result<(usize, bool, SwapchainAcquireFuture<W>), AcquireError> {
+    assert_eq!(
+        swapchain.device.internal_object(),
+        semaphore.device().internal_object()
+    );
+
     let fence = Fence::from_pool(swapchain.device.clone())?;
 
     let AcquiredImage { id, suboptimal } = {
@@ -123,7 +246,7 @@ pub fn acquire_next_image<W>(
         }
 
         let acquire_result =
-            unsafe { acquire_next_image_raw(&swapchain, timeout, Some(&semaphore), Some(&fence)) };
+            acquire_next_image_raw(&swapchain, timeout, Some(&semaphore), Some(&fence));
 
         if let &Err(AcquireError::FullscreenExclusiveLost) = &acquire_result {
             swapchain
@@ -178,6 +301,10 @@ where
         swapchain,
         image_id: index,
         present_region: None,
+        present_id: None,
+        present_fence: None,
+        present_mode: None,
+        present_time: None,
         flushed: AtomicBool::new(false),
         finished: AtomicBool::new(false),
     }
@@ -189,6 +316,11 @@ where
 /// This is just an optimization hint, as the Vulkan driver is free to ignore the given present region.
 ///
 /// If `VK_KHR_incremental_present` is not enabled on the device, the parameter will be ignored.
+///
+/// # Panic
+///
+/// - Panics if the present region is not compatible with the swapchain, see
+///   `PresentRegion::is_compatible_with`.
 pub fn present_incremental<F, W>(
     swapchain: Arc<Swapchain<W>>,
     before: F,
@@ -200,6 +332,10 @@ where
     F: GpuFuture,
 {
     assert!(index < swapchain.images.len());
+    assert!(
+        present_region.is_compatible_with(&swapchain),
+        "the present region doesn't fit in the images of the swapchain"
+    );
 
     // TODO: restore this check with a dummy ImageAccess implementation
     /*let swapchain_image = me.images.lock().unwrap().get(index).unwrap().0.upgrade().unwrap();       // TODO: return error instead
@@ -214,11 +350,63 @@ where
         swapchain,
         image_id: index,
         present_region: Some(present_region),
+        present_id: None,
+        present_fence: None,
+        present_mode: None,
+        present_time: None,
+        flushed: AtomicBool::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Same as `swapchain::present`, except that the present is tagged with an identifier.
+///
+/// The identifier can then be passed to `Swapchain::wait_for_present` in order to block until the
+/// image has been presented. Each identifier must be greater than the previous ones used with the
+/// same swapchain.
+///
+/// # Panic
+///
+/// - Panics if the `present_id` feature is not enabled on the device.
+/// - Panics if `present_id` is 0 or isn't greater than the previous identifier.
+pub fn present_with_id<F, W>(
+    swapchain: Arc<Swapchain<W>>,
+    before: F,
+    queue: Arc<Queue>,
+    index: usize,
+    present_id: u64,
+) -> PresentFuture<F, W>
+where
+    F: GpuFuture,
+{
+    assert!(index < swapchain.images.len());
+    assert!(
+        swapchain.device.enabled_features().present_id,
+        "the present_id feature must be enabled"
+    );
+    assert!(
+        claim_present_id(&swapchain.last_present_id, present_id),
+        "present ids must be greater than the previous ones"
+    );
+
+    PresentFuture {
+        previous: before,
+        queue,
+        swapchain,
+        image_id: index,
+        present_region: None,
+        present_id: Some(present_id),
+        present_fence: None,
+        present_mode: None,
+        present_time: None,
         flushed: AtomicBool::new(false),
         finished: AtomicBool::new(false),
     }
 }
 
+// A new swapchain and its images.
+type SwapchainAndImages<W> = (Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>);
+
 /// Contains the swapping system and the images that can be shown on a surface.
 pub struct Swapchain<W> {
     // The Vulkan device this swapchain was created with.
@@ -249,9 +437,15 @@ pub struct Swapchain<W> {
     transform: SurfaceTransform,
     alpha: CompositeAlpha,
     mode: PresentMode,
+    // The present mode that was used by the latest present, see `PresentFuture::with_present_mode`.
+    current_mode: Mutex<PresentMode>,
     fullscreen_exclusive: FullscreenExclusive,
     fullscreen_exclusive_held: AtomicBool,
+    // The greatest identifier that was passed to `present_with_id`.
+    last_present_id: AtomicU64,
+    win32_monitor: Option<Win32Monitor>,
     clipped: bool,
+    options: SwapchainOptions,
 }
 
 struct ImageEntry {
@@ -273,6 +467,28 @@ impl<W> Swapchain<W> {
     /// important to take into account if your fragment shader has side-effects or if you want to
     /// read back the content of the image afterwards.
     ///
+    /// The `usage` parameter is what the images are going to be used for, and must be supported
+    /// by the `supported_usage_flags` of the surface's capabilities. In addition to
+    /// `color_attachment`, you can for example request `storage` to write to the images from a
+    /// compute shader, or `transfer_source` to copy them to a buffer in order to take a
+    /// screenshot. The format must also support these usages.
+    ///
+    /// The `layers` parameter is the number of array layers of each image, up to the
+    /// `max_image_array_layers` of the surface's capabilities. Use more than one layer for stereo
+    /// presentation, and `SwapchainImage::layer_views` to render to each layer separately.
+    ///
+    /// The `transform` parameter is applied by the presentation engine to the images before they
+    /// are shown. Passing the `current_transform` of the surface's capabilities avoids a
+    /// composition pass on platforms such as Android, but you must then render pre-rotated images,
+    /// see the documentation of `SurfaceTransform`.
+    ///
+    /// The `options` parameter contains the parameters that require device features or
+    /// extensions. Pass `SwapchainOptions::default()` if you don't need them.
+    ///
+    /// The `win32_monitor` parameter is only used if the `ext_full_screen_exclusive` extension is
+    /// enabled, and must be provided on Windows when `fullscreen_exclusive` is
+    /// `FullscreenExclusive::AppControlled`.
+    ///
     /// This function returns the swapchain plus a list of the images that belong to the
     /// swapchain. The order in which the images are returned is important for the
     /// `acquire_next_image` and `present` functions.
@@ -297,9 +513,11 @@ impl<W> Swapchain<W> {
         alpha: CompositeAlpha,
         mode: PresentMode,
         fullscreen_exclusive: FullscreenExclusive,
+        win32_monitor: Option<Win32Monitor>,
         clipped: bool,
         color_space: ColorSpace,
-    ) -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError>
+        options: SwapchainOptions,
+    ) -> Result<SwapchainAndImages<W>, SwapchainCreationError>
     where
         F: FormatDesc,
         S: Into<SharingMode>,
@@ -318,7 +536,9 @@ impl<W> Swapchain<W> {
             alpha,
             mode,
             fullscreen_exclusive,
+            win32_monitor,
             clipped,
+            options,
             None,
         )
     }
@@ -338,10 +558,12 @@ impl<W> Swapchain<W> {
         alpha: CompositeAlpha,
         mode: PresentMode,
         fullscreen_exclusive: FullscreenExclusive,
+        win32_monitor: Option<Win32Monitor>,
         clipped: bool,
         color_space: ColorSpace,
+        options: SwapchainOptions,
         old_swapchain: Arc<Swapchain<W>>,
-    ) -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError>
+    ) -> Result<SwapchainAndImages<W>, SwapchainCreationError>
     where
         F: FormatDesc,
         S: Into<SharingMode>,
@@ -351,7 +573,7 @@ impl<W> Swapchain<W> {
             surface,
             num_images,
             format.format(),
-            ColorSpace::SrgbNonLinear,
+            color_space,
             Some(dimensions),
             layers,
             usage,
@@ -360,15 +582,25 @@ impl<W> Swapchain<W> {
             alpha,
             mode,
             fullscreen_exclusive,
+            win32_monitor,
             clipped,
+            options,
             Some(&*old_swapchain),
         )
     }
 
     /// Recreates the swapchain with current dimensions of corresponding surface.
-    pub fn recreate(
-        &self,
-    ) -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError> {
+    ///
+    /// The capabilities of the surface are queried again, and every other parameter (format,
+    /// usage, present mode, ...) is carried over from this swapchain. If the surface doesn't
+    /// report a current extent, the dimensions of this swapchain are kept. Otherwise the width and
+    /// height of the current extent are swapped if the transform of this swapchain rotates by 90
+    /// or 270 degrees. In both cases the dimensions are clamped to the extents supported by the
+    /// surface.
+    ///
+    /// This swapchain is passed as the old swapchain, and the new swapchain and its images are
+    /// returned.
+    pub fn recreate(&self) -> Result<SwapchainAndImages<W>, SwapchainCreationError> {
         Swapchain::new_inner(
             self.device.clone(),
             self.surface.clone(),
@@ -383,7 +615,40 @@ impl<W> Swapchain<W> {
             self.alpha,
             self.mode,
             self.fullscreen_exclusive,
+            self.win32_monitor,
+            self.clipped,
+            self.options.clone(),
+            Some(self),
+        )
+    }
+
+    /// Recreates the swapchain with a new transform and the current dimensions of the surface,
+    /// swapped if the transform rotates by 90 or 270 degrees.
+    ///
+    /// This is usually called with the `current_transform` of the surface's capabilities when the
+    /// swapchain becomes suboptimal because the device has been rotated. See the documentation
+    /// of `SurfaceTransform` about pre-rotation.
+    pub fn recreate_with_transform(
+        &self,
+        transform: SurfaceTransform,
+    ) -> Result<SwapchainAndImages<W>, SwapchainCreationError> {
+        Swapchain::new_inner(
+            self.device.clone(),
+            self.surface.clone(),
+            self.num_images,
+            self.format,
+            self.color_space,
+            None,
+            self.layers,
+            self.usage,
+            self.sharing.clone(),
+            transform,
+            self.alpha,
+            self.mode,
+            self.fullscreen_exclusive,
+            self.win32_monitor,
             self.clipped,
+            self.options.clone(),
             Some(self),
         )
     }
@@ -392,7 +657,7 @@ impl<W> Swapchain<W> {
     pub fn recreate_with_dimensions(
         &self,
         dimensions: [u32; 2],
-    ) -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError> {
+    ) -> Result<SwapchainAndImages<W>, SwapchainCreationError> {
         Swapchain::new_inner(
             self.device.clone(),
             self.surface.clone(),
@@ -407,7 +672,9 @@ impl<W> Swapchain<W> {
             self.alpha,
             self.mode,
             self.fullscreen_exclusive,
+            self.win32_monitor,
             self.clipped,
+            self.options.clone(),
             Some(self),
         )
     }
@@ -426,9 +693,11 @@ impl<W> Swapchain<W> {
         alpha: CompositeAlpha,
         mode: PresentMode,
         fullscreen_exclusive: FullscreenExclusive,
+        win32_monitor: Option<Win32Monitor>,
         clipped: bool,
+        options: SwapchainOptions,
         old_swapchain: Option<&Swapchain<W>>,
-    ) -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError> {
+    ) -> Result<SwapchainAndImages<W>, SwapchainCreationError> {
         assert_eq!(
             device.instance().internal_object(),
             surface.instance().internal_object()
@@ -466,7 +735,27 @@ impl<W> Swapchain<W> {
             }
             dimensions
         } else {
-            capabilities.current_extent.unwrap()
+            // The current extent is in the orientation of the surface, while the images of a
+            // pre-rotated swapchain must be in the orientation of the transform. If the surface
+            // lets the swapchain choose its extent, keep the dimensions of the old swapchain. In
+            // both cases, clamp the dimensions to what the surface now supports.
+            let dimensions = capabilities
+                .current_extent
+                .map(|extent| {
+                    if transform.swaps_dimensions() {
+                        [extent[1], extent[0]]
+                    } else {
+                        extent
+                    }
+                })
+                .or_else(|| old_swapchain.map(|old| old.dimensions))
+                .expect("the surface doesn't have a current extent");
+            clamp_extent(
+                dimensions,
+                capabilities.min_image_extent,
+                capabilities.max_image_extent,
+            )
+            .ok_or(SwapchainCreationError::UnsupportedDimensions)?
         };
         if layers < 1 || layers > capabilities.max_image_array_layers {
             return Err(SwapchainCreationError::UnsupportedArrayLayers);
@@ -485,8 +774,32 @@ impl<W> Swapchain<W> {
         if !capabilities.present_modes.supports(mode) {
             return Err(SwapchainCreationError::UnsupportedPresentMode);
         }
+        if options.uses_maintenance1() && !device.enabled_features().swapchain_maintenance1 {
+            return Err(SwapchainCreationError::MissingFeatureSwapchainMaintenance1);
+        }
+        if options.vblank_counter && !device.loaded_extensions().ext_display_control {
+            return Err(SwapchainCreationError::MissingExtensionExtDisplayControl);
+        }
+        if options.protected {
+            if !device.enabled_features().protected_memory {
+                return Err(SwapchainCreationError::MissingFeatureProtectedMemory);
+            }
+            if !surface.supports_protected(device.physical_device())? {
+                return Err(SwapchainCreationError::UnsupportedProtected);
+            }
+        }
+        if !options
+            .present_modes
+            .iter()
+            .all(|&mode| capabilities.present_modes.supports(mode))
+        {
+            return Err(SwapchainCreationError::UnsupportedPresentMode);
+        }
 
-        let flags = ImageCreateFlags::none();
+        let flags = ImageCreateFlags {
+            protected: options.protected,
+            ..ImageCreateFlags::none()
+        };
 
         // check that the physical device supports the swapchain image configuration
         match device.image_format_properties(
@@ -523,6 +836,7 @@ impl<W> Swapchain<W> {
         }
 
         let mut surface_full_screen_exclusive_info = None;
+        let surface_full_screen_exclusive_win32_info = win32_monitor.map(Win32Monitor::to_vk);
 
         if device.loaded_extensions().ext_full_screen_exclusive
             && surface
@@ -536,16 +850,72 @@ impl<W> Swapchain<W> {
         {
             surface_full_screen_exclusive_info = Some(vk::SurfaceFullScreenExclusiveInfoEXT {
                 sType: vk::STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_INFO_EXT,
-                pNext: ptr::null(),
+                pNext: match surface_full_screen_exclusive_win32_info.as_ref() {
+                    Some(info) => info as *const _ as *const _,
+                    None => ptr::null(),
+                },
                 fullScreenExclusive: fullscreen_exclusive.vk_sys_enum(),
             });
         }
 
-        let p_next = match surface_full_screen_exclusive_info.as_ref() {
+        let mut p_next: *const c_void = match surface_full_screen_exclusive_info.as_ref() {
             Some(some) => unsafe { mem::transmute(some as *const _) },
             None => ptr::null(),
         };
 
+        let present_modes = present_modes_list(mode, &options.present_modes);
+        let present_modes_info = if !present_modes.is_empty() {
+            Some(vk::SwapchainPresentModesCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_SWAPCHAIN_PRESENT_MODES_CREATE_INFO_EXT,
+                pNext: p_next,
+                presentModeCount: present_modes.len() as u32,
+                pPresentModes: present_modes.as_ptr(),
+            })
+        } else {
+            None
+        };
+        if let Some(info) = present_modes_info.as_ref() {
+            p_next = info as *const _ as *const _;
+        }
+
+        let present_scaling_info =
+            if options.scaling_behavior.is_some() || options.present_gravity.is_some() {
+                Some(vk::SwapchainPresentScalingCreateInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_SWAPCHAIN_PRESENT_SCALING_CREATE_INFO_EXT,
+                    pNext: p_next,
+                    scalingBehavior: options
+                        .scaling_behavior
+                        .map(|scaling| scaling as u32)
+                        .unwrap_or(0),
+                    presentGravityX: options
+                        .present_gravity
+                        .map(|gravity| gravity[0] as u32)
+                        .unwrap_or(0),
+                    presentGravityY: options
+                        .present_gravity
+                        .map(|gravity| gravity[1] as u32)
+                        .unwrap_or(0),
+                })
+            } else {
+                None
+            };
+        if let Some(info) = present_scaling_info.as_ref() {
+            p_next = info as *const _ as *const _;
+        }
+
+        let swapchain_counter_info = if options.vblank_counter {
+            Some(vk::SwapchainCounterCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_SWAPCHAIN_COUNTER_CREATE_INFO_EXT,
+                pNext: p_next,
+                surfaceCounters: vk::SURFACE_COUNTER_VBLANK_BIT_EXT,
+            })
+        } else {
+            None
+        };
+        if let Some(info) = swapchain_counter_info.as_ref() {
+            p_next = info as *const _ as *const _;
+        }
+
         // Required by the specs.
         assert_ne!(usage, ImageUsage::none());
 
@@ -580,7 +950,15 @@ impl<W> Swapchain<W> {
             let infos = vk::SwapchainCreateInfoKHR {
                 sType: vk::STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR,
                 pNext: p_next,
-                flags: 0, // reserved
+                flags: if options.deferred_memory_allocation {
+                    vk::SWAPCHAIN_CREATE_DEFERRED_MEMORY_ALLOCATION_BIT_EXT
+                } else {
+                    0
+                } | if options.protected {
+                    vk::SWAPCHAIN_CREATE_PROTECTED_BIT_KHR
+                } else {
+                    0
+                },
                 surface: surface.internal_object(),
                 minImageCount: num_images,
                 imageFormat: format as u32,
@@ -609,7 +987,7 @@ impl<W> Swapchain<W> {
             check_errors(vk.CreateSwapchainKHR(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -683,9 +1061,13 @@ impl<W> Swapchain<W> {
             transform,
             alpha,
             mode,
+            current_mode: Mutex::new(mode),
             fullscreen_exclusive,
             fullscreen_exclusive_held: AtomicBool::new(fullscreen_exclusive_held),
+            last_present_id: AtomicU64::new(0),
+            win32_monitor,
             clipped,
+            options,
         });
 
         let swapchain_images = unsafe {
@@ -748,6 +1130,14 @@ impl<W> Swapchain<W> {
         self.layers
     }
 
+    /// Returns the usage of the images of the swapchain.
+    ///
+    /// See the documentation of `Swapchain::new`.
+    #[inline]
+    pub fn usage(&self) -> ImageUsage {
+        self.usage
+    }
+
     /// Returns the transform that was passed when creating the swapchain.
     ///
     /// See the documentation of `Swapchain::new`.
@@ -772,6 +1162,21 @@ impl<W> Swapchain<W> {
         self.mode
     }
 
+    /// Returns the present mode that is currently used by the swapchain. This is different from
+    /// `present_mode` if another mode has been chosen with `PresentFuture::with_present_mode`.
+    #[inline]
+    pub fn current_present_mode(&self) -> PresentMode {
+        *self.current_mode.lock().unwrap()
+    }
+
+    /// Returns the options that were passed when creating the swapchain.
+    ///
+    /// See the documentation of `SwapchainOptions`.
+    #[inline]
+    pub fn options(&self) -> &SwapchainOptions {
+        &self.options
+    }
+
     /// Returns the value of `clipped` that was passed when creating the swapchain.
     ///
     /// See the documentation of `Swapchain::new`.
@@ -788,6 +1193,145 @@ impl<W> Swapchain<W> {
         self.fullscreen_exclusive
     }
 
+    /// Blocks until the present that was tagged with `present_id` has been displayed, or until
+    /// the timeout is reached. Passing `None` as the timeout waits forever.
+    ///
+    /// See `swapchain::present_with_id`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `present_wait` feature is not enabled on the device.
+    pub fn wait_for_present(
+        &self,
+        present_id: u64,
+        timeout: Option<Duration>,
+    ) -> Result<(), PresentWaitError> {
+        assert!(
+            self.device.enabled_features().present_wait,
+            "the present_wait feature must be enabled"
+        );
+
+        let timeout_ns = if let Some(timeout) = timeout {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        } else {
+            u64::MAX
+        };
+
+        let r = unsafe {
+            check_errors(self.device.pointers().WaitForPresentKHR(
+                self.device.internal_object(),
+                self.swapchain,
+                present_id,
+                timeout_ns,
+            ))?
+        };
+
+        match r {
+            Success::Success | Success::Suboptimal => Ok(()),
+            Success::Timeout => Err(PresentWaitError::Timeout),
+            s => panic!("unexpected success value: {:?}", s),
+        }
+    }
+
+    /// Returns the duration of a refresh cycle of the display, in other words the time between two
+    /// vertical blanking periods.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `google_display_timing` extension is not enabled on the device.
+    pub fn refresh_cycle_duration(&self) -> Result<Duration, DisplayTimingError> {
+        assert!(
+            self.device.loaded_extensions().google_display_timing,
+            "the google_display_timing extension must be enabled"
+        );
+
+        unsafe {
+            let vk = self.device.pointers();
+            let mut properties = MaybeUninit::uninit();
+            check_errors(vk.GetRefreshCycleDurationGOOGLE(
+                self.device.internal_object(),
+                self.swapchain,
+                properties.as_mut_ptr(),
+            ))?;
+            let properties: vk::RefreshCycleDurationGOOGLE = properties.assume_init();
+            Ok(Duration::from_nanos(properties.refreshDuration))
+        }
+    }
+
+    /// Returns the timing information of the images that have been presented with
+    /// `PresentFuture::with_present_time` and that have been displayed since the previous call.
+    ///
+    /// The information about each present is only returned once.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `google_display_timing` extension is not enabled on the device.
+    pub fn past_presentation_timing(
+        &self,
+    ) -> Result<Vec<PastPresentationTiming>, DisplayTimingError> {
+        assert!(
+            self.device.loaded_extensions().google_display_timing,
+            "the google_display_timing extension must be enabled"
+        );
+
+        unsafe {
+            let vk = self.device.pointers();
+
+            let mut num = 0;
+            check_errors(vk.GetPastPresentationTimingGOOGLE(
+                self.device.internal_object(),
+                self.swapchain,
+                &mut num,
+                ptr::null_mut(),
+            ))?;
+
+            let mut timings = Vec::with_capacity(num as usize);
+            check_errors(vk.GetPastPresentationTimingGOOGLE(
+                self.device.internal_object(),
+                self.swapchain,
+                &mut num,
+                timings.as_mut_ptr(),
+            ))?;
+            timings.set_len(num as usize);
+
+            Ok(timings.into_iter().map(Into::into).collect())
+        }
+    }
+
+    /// Returns the number of vertical blanking periods that have happened since the swapchain was
+    /// created.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the swapchain wasn't created with `SwapchainOptions::vblank_counter`.
+    pub fn vblank_counter(&self) -> Result<u64, DisplayTimingError> {
+        assert!(
+            self.options.vblank_counter,
+            "the swapchain wasn't created with a vblank counter"
+        );
+
+        unsafe {
+            let vk = self.device.pointers();
+            let mut value = 0;
+            check_errors(vk.GetSwapchainCounterEXT(
+                self.device.internal_object(),
+                self.swapchain,
+                vk::SURFACE_COUNTER_VBLANK_BIT_EXT,
+                &mut value,
+            ))?;
+            Ok(value)
+        }
+    }
+
+    /// Returns the Win32 monitor that was passed when creating the swapchain.
+    #[inline]
+    pub fn win32_monitor(&self) -> Option<Win32Monitor> {
+        self.win32_monitor
+    }
+
     /// `FullscreenExclusive::AppControlled` must be the active fullscreen exclusivity mode.
     /// Acquire fullscreen exclusivity until either the `release_fullscreen_exclusive` is
     /// called, or if any of the the other `Swapchain` functions return `FullscreenExclusiveLost`.
@@ -836,6 +1380,30 @@ impl<W> Swapchain<W> {
         Ok(())
     }
 
+    /// Sets the HDR metadata of the swapchain, which describes how its content was mastered.
+    ///
+    /// The metadata applies to the images presented after this call.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_hdr_metadata` extension is not enabled on the device.
+    pub fn set_hdr_metadata(&self, metadata: &HdrMetadata) {
+        assert!(
+            self.device.loaded_extensions().ext_hdr_metadata,
+            "the ext_hdr_metadata extension must be enabled"
+        );
+
+        let metadata = metadata.to_vk();
+        unsafe {
+            self.device.pointers().SetHdrMetadataEXT(
+                self.device.internal_object(),
+                1,
+                &self.swapchain,
+                &metadata,
+            );
+        }
+    }
+
     /// `FullscreenExclusive::AppControlled` is not the active fullscreen exclusivity mode,
     /// then this function will always return false. If true is returned the swapchain
     /// is in `FullscreenExclusive::AppControlled` fullscreen exclusivity mode and exclusivity
@@ -898,12 +1466,52 @@ impl<W> Drop for Swapchain<W> {
     fn drop(&mut self) {
         unsafe {
             let vk = self.device.pointers();
-            vk.DestroySwapchainKHR(self.device.internal_object(), self.swapchain, ptr::null());
+            vk.DestroySwapchainKHR(
+                self.device.internal_object(),
+                self.swapchain,
+                self.device.alloc_ptr(),
+            );
             self.surface.flag().store(false, Ordering::Release);
         }
     }
 }
 
+// Records `present_id` as the greatest identifier used with a swapchain. Returns false if it isn't
+// greater than the previous ones, which also rejects 0.
+fn claim_present_id(last_present_id: &AtomicU64, present_id: u64) -> bool {
+    last_present_id.fetch_max(present_id, Ordering::SeqCst) < present_id
+}
+
+// Builds the list of present modes that the swapchain can switch between, which must start with
+// the present mode of the swapchain. Returns an empty list if there is no other mode to switch to.
+fn present_modes_list(mode: PresentMode, others: &[PresentMode]) -> Vec<vk::PresentModeKHR> {
+    if others.is_empty() {
+        return Vec::new();
+    }
+
+    let mut present_modes = vec![mode as vk::PresentModeKHR];
+    for &other in others {
+        if !present_modes.contains(&(other as vk::PresentModeKHR)) {
+            present_modes.push(other as vk::PresentModeKHR);
+        }
+    }
+    present_modes
+}
+
+// Clamps the dimensions of a recreated swapchain to the extents supported by the surface.
+// Returns `None` if the result is empty, which happens when a minimized window reports a maximum
+// extent of zero.
+fn clamp_extent(dimensions: [u32; 2], min: [u32; 2], max: [u32; 2]) -> Option<[u32; 2]> {
+    let dimensions = [
+        cmp::min(cmp::max(dimensions[0], min[0]), max[0]),
+        cmp::min(cmp::max(dimensions[1], min[1]), max[1]),
+    ];
+    if dimensions[0] == 0 || dimensions[1] == 0 {
+        return None;
+    }
+    Some(dimensions)
+}
+
 /// Error that can happen when creation a swapchain.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SwapchainCreationError {
@@ -945,6 +1553,16 @@ pub enum SwapchainCreationError {
     UnsupportedPresentMode,
     /// The image configuration is not supported by the physical device.
     UnsupportedImageConfiguration,
+    /// The `swapchain_maintenance1` feature was not enabled.
+    MissingFeatureSwapchainMaintenance1,
+    /// The `VK_EXT_display_control` extension was not enabled.
+    MissingExtensionExtDisplayControl,
+    /// A protected swapchain was requested but the `protected_memory` feature was not enabled.
+    MissingFeatureProtectedMemory,
+    /// A protected swapchain was requested but the surface doesn't support protected
+    /// presentation, or the `khr_surface_protected_capabilities` instance extension was not
+    /// enabled.
+    UnsupportedProtected,
 }
 
 impl error::Error for SwapchainCreationError {
@@ -1015,6 +1633,20 @@ impl fmt::Display for SwapchainCreationError {
                 SwapchainCreationError::UnsupportedImageConfiguration => {
                     "the requested image configuration is not supported by the physical device"
                 }
+                SwapchainCreationError::MissingFeatureSwapchainMaintenance1 => {
+                    "the `swapchain_maintenance1` feature was not enabled"
+                }
+                SwapchainCreationError::MissingExtensionExtDisplayControl => {
+                    "the `VK_EXT_display_control` extension was not enabled"
+                }
+                SwapchainCreationError::MissingFeatureProtectedMemory => {
+                    "a protected swapchain was requested but the `protected_memory` feature was \
+                     not enabled"
+                }
+                SwapchainCreationError::UnsupportedProtected => {
+                    "a protected swapchain was requested but the surface doesn't support protected \
+                     presentation"
+                }
             }
         )
     }
@@ -1058,7 +1690,7 @@ pub struct SwapchainAcquireFuture<W> {
     image_id: usize,
     // Semaphore that is signalled when the acquire is complete. Empty if the acquire has already
     // happened.
-    semaphore: Option<Semaphore>,
+    semaphore: Option<Arc<Semaphore>>,
     // Fence that is signalled when the acquire is complete. Empty if the acquire has already
     // happened.
     fence: Option<Fence>,
@@ -1171,6 +1803,14 @@ impl<W> Drop for SwapchainAcquireFuture<W> {
     fn drop(&mut self) {
         if let Some(ref fence) = self.fence {
             fence.wait(None).unwrap(); // TODO: handle error?
+
+            // If the future has never been submitted, the semaphore stays signaled and can't be
+            // used for another acquisition.
+            if !*self.finished.get_mut() {
+                if let Some(semaphore) = self.semaphore.as_mut().and_then(Arc::get_mut) {
+                    semaphore.remove_from_pool();
+                }
+            }
             self.semaphore = None;
         }
 
@@ -1336,6 +1976,75 @@ impl From<Error> for AcquireError {
     }
 }
 
+/// Error that can happen when calling `Swapchain::wait_for_present`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PresentWaitError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The timeout of the function has been reached before the present was displayed.
+    Timeout,
+
+    /// The surface is no longer accessible and must be recreated.
+    SurfaceLost,
+
+    /// The swapchain has lost or doesn't have fullscreen exclusivity possibly for
+    /// implementation-specific reasons outside of the application’s control.
+    FullscreenExclusiveLost,
+
+    /// The surface has changed in a way that makes the swapchain unusable. You must query the
+    /// surface's new properties and recreate a new swapchain if you want to continue drawing.
+    OutOfDate,
+}
+
+impl error::Error for PresentWaitError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            PresentWaitError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PresentWaitError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                PresentWaitError::OomError(_) => "not enough memory",
+                PresentWaitError::DeviceLost => "the connection to the device has been lost",
+                PresentWaitError::Timeout => "the present hasn't been displayed yet",
+                PresentWaitError::SurfaceLost => "the surface of this swapchain is no longer valid",
+                PresentWaitError::OutOfDate => "the swapchain needs to be recreated",
+                PresentWaitError::FullscreenExclusiveLost => {
+                    "the swapchain no longer has fullscreen exclusivity"
+                }
+            }
+        )
+    }
+}
+
+impl From<Error> for PresentWaitError {
+    #[inline]
+    fn from(err: Error) -> PresentWaitError {
+        match err {
+            err @ Error::OutOfHostMemory => PresentWaitError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => PresentWaitError::OomError(OomError::from(err)),
+            Error::DeviceLost => PresentWaitError::DeviceLost,
+            Error::SurfaceLost => PresentWaitError::SurfaceLost,
+            Error::OutOfDate => PresentWaitError::OutOfDate,
+            Error::FullscreenExclusiveLost => PresentWaitError::FullscreenExclusiveLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
 /// Represents a swapchain image being presented on the screen.
 #[must_use = "Dropping this object will immediately block the thread until the GPU has finished processing the submission"]
 pub struct PresentFuture<P, W>
@@ -1347,6 +2056,10 @@ where
     swapchain: Arc<Swapchain<W>>,
     image_id: usize,
     present_region: Option<PresentRegion>,
+    present_id: Option<u64>,
+    present_fence: Option<Arc<Fence>>,
+    present_mode: Option<PresentMode>,
+    present_time: Option<(u32, u64)>,
     // True if `flush()` has been called on the future, which means that the present command has
     // been submitted.
     flushed: AtomicBool,
@@ -1370,6 +2083,117 @@ where
     pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
         &self.swapchain
     }
+
+    /// Signals `fence` once the present no longer uses its resources, such as the semaphores that
+    /// it waits upon.
+    ///
+    /// Contrary to a fence signaled after the present with `then_signal_fence`, this fence tells
+    /// you when the resources of the frame can be reused.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `swapchain_maintenance1` feature is not enabled on the device.
+    /// - Panics if the fence doesn't belong to the same device as the swapchain.
+    /// - Panics if the fence is already signaled.
+    pub fn with_present_fence(mut self, fence: Arc<Fence>) -> PresentFuture<P, W> {
+        assert!(
+            self.swapchain
+                .device
+                .enabled_features()
+                .swapchain_maintenance1,
+            "the swapchain_maintenance1 feature must be enabled"
+        );
+        assert_eq!(
+            fence.device().internal_object(),
+            self.swapchain.device.internal_object()
+        );
+        assert!(
+            !fence.ready().unwrap_or(true),
+            "the present fence must be unsignaled"
+        );
+
+        self.present_fence = Some(fence);
+        self
+    }
+
+    /// Switches the swapchain to `mode`, starting with this present. The following presents keep
+    /// using this mode until another one is chosen.
+    ///
+    /// This can for example be used to toggle vertical synchronization without recreating the
+    /// swapchain.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `swapchain_maintenance1` feature is not enabled on the device.
+    /// - Panics if `mode` is neither the present mode of the swapchain nor one of the
+    ///   `SwapchainOptions::present_modes` that it was created with.
+    pub fn with_present_mode(mut self, mode: PresentMode) -> PresentFuture<P, W> {
+        assert!(
+            self.swapchain
+                .device
+                .enabled_features()
+                .swapchain_maintenance1,
+            "the swapchain_maintenance1 feature must be enabled"
+        );
+        assert!(
+            mode == self.swapchain.mode || self.swapchain.options.present_modes.contains(&mode),
+            "the present mode wasn't passed when creating the swapchain"
+        );
+
+        self.present_mode = Some(mode);
+        self
+    }
+
+    /// Asks for the image to be displayed at `desired_present_time` at the earliest, and tags the
+    /// present with `present_id` in the results of `Swapchain::past_presentation_timing`.
+    ///
+    /// The time is in nanoseconds, in the same time domain as `CLOCK_MONOTONIC` on Android and
+    /// Linux. A time of 0 lets the image be displayed as soon as possible. You can compute the
+    /// time of a future vertical blanking period from the results of
+    /// `Swapchain::past_presentation_timing` and from `Swapchain::refresh_cycle_duration`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `google_display_timing` extension is not enabled on the device.
+    pub fn with_present_time(
+        mut self,
+        present_id: u32,
+        desired_present_time: u64,
+    ) -> PresentFuture<P, W> {
+        assert!(
+            self.swapchain
+                .device
+                .loaded_extensions()
+                .google_display_timing,
+            "the google_display_timing extension must be enabled"
+        );
+
+        self.present_time = Some((present_id, desired_present_time));
+        self
+    }
+
+    // Adds the present of this future to `builder`.
+    unsafe fn add_to_builder<'a>(&'a self, builder: &mut SubmitPresentBuilder<'a>) {
+        builder.add_swapchain(
+            &self.swapchain,
+            self.image_id as u32,
+            self.present_region.as_ref(),
+            self.present_id,
+        );
+
+        if let Some(ref fence) = self.present_fence {
+            builder.set_present_fence(fence);
+        }
+
+        if let Some((present_id, desired_present_time)) = self.present_time {
+            builder.set_present_time(present_id, desired_present_time);
+        }
+
+        if let Some(mode) = self.present_mode {
+            builder.set_present_mode(mode);
+            *self.swapchain.current_mode.lock().unwrap() = mode;
+        }
+    }
 }
 
 unsafe impl<P, W> GpuFuture for PresentFuture<P, W>
@@ -1395,20 +2219,12 @@ where
         Ok(match self.previous.build_submission()? {
             SubmitAnyBuilder::Empty => {
                 let mut builder = SubmitPresentBuilder::new();
-                builder.add_swapchain(
-                    &self.swapchain,
-                    self.image_id as u32,
-                    self.present_region.as_ref(),
-                );
+                self.add_to_builder(&mut builder);
                 SubmitAnyBuilder::QueuePresent(builder)
             }
             SubmitAnyBuilder::SemaphoresWait(sem) => {
                 let mut builder: SubmitPresentBuilder = sem.into();
-                builder.add_swapchain(
-                    &self.swapchain,
-                    self.image_id as u32,
-                    self.present_region.as_ref(),
-                );
+                self.add_to_builder(&mut builder);
                 SubmitAnyBuilder::QueuePresent(builder)
             }
             SubmitAnyBuilder::CommandBuffer(cb) => {
@@ -1417,11 +2233,7 @@ where
                 self.previous.flush()?;
 
                 let mut builder = SubmitPresentBuilder::new();
-                builder.add_swapchain(
-                    &self.swapchain,
-                    self.image_id as u32,
-                    self.present_region.as_ref(),
-                );
+                self.add_to_builder(&mut builder);
                 SubmitAnyBuilder::QueuePresent(builder)
             }
             SubmitAnyBuilder::BindSparse(cb) => {
@@ -1430,11 +2242,7 @@ where
                 self.previous.flush()?;
 
                 let mut builder = SubmitPresentBuilder::new();
-                builder.add_swapchain(
-                    &self.swapchain,
-                    self.image_id as u32,
-                    self.present_region.as_ref(),
-                );
+                self.add_to_builder(&mut builder);
                 SubmitAnyBuilder::QueuePresent(builder)
             }
             SubmitAnyBuilder::QueuePresent(present) => {
@@ -1460,22 +2268,18 @@ where
                     .store(false, Ordering::SeqCst);
             }
 
-            match build_submission_result? {
-                SubmitAnyBuilder::Empty => {}
-                SubmitAnyBuilder::QueuePresent(present) => {
-                    let present_result = present.submit(&self.queue);
-
-                    if let &Err(SubmitPresentError::FullscreenExclusiveLost) = &present_result {
-                        self.swapchain
-                            .fullscreen_exclusive_held
-                            .store(false, Ordering::SeqCst);
-                    }
+            let mut scheduler = SubmitScheduler::new();
+            scheduler.add(build_submission_result?);
+            let present_result = scheduler.flush(&self.queue);
 
-                    present_result?;
-                }
-                _ => unreachable!(),
+            if let &Err(FlushError::FullscreenExclusiveLost) = &present_result {
+                self.swapchain
+                    .fullscreen_exclusive_held
+                    .store(false, Ordering::SeqCst);
             }
 
+            present_result?;
+
             self.flushed.store(true, Ordering::SeqCst);
             Ok(())
         }
@@ -1618,3 +2422,319 @@ pub unsafe fn acquire_next_image_raw<W>(
 
     Ok(AcquiredImage { id, suboptimal })
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::device::Device;
+    use crate::device::DeviceExtensions;
+    use crate::device::Queue;
+    use crate::features::Features;
+    use crate::image::ImageUsage;
+    use crate::instance::Instance;
+    use crate::instance::InstanceExtensions;
+    use crate::instance::PhysicalDevice;
+    use crate::swapchain::acquire_next_image_with_semaphore;
+    use crate::swapchain::swapchain::claim_present_id;
+    use crate::swapchain::swapchain::clamp_extent;
+    use crate::swapchain::swapchain::present_modes_list;
+    use crate::swapchain::Capabilities;
+    use crate::swapchain::FullscreenExclusive;
+    use crate::swapchain::PresentMode;
+    use crate::swapchain::PresentWaitError;
+    use crate::swapchain::Surface;
+    use crate::swapchain::Swapchain;
+    use crate::swapchain::SwapchainCreationError;
+    use crate::swapchain::SwapchainOptions;
+    use crate::swapchain::Win32Monitor;
+    use crate::sync::GpuFuture;
+    use crate::sync::Semaphore;
+    use crate::sync::SharingMode;
+    use crate::vk;
+    use crate::Error;
+    use crate::OomError;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::Arc;
+
+    // Device, queue, surface and surface capabilities returned by `headless`.
+    type Headless = (Arc<Device>, Arc<Queue>, Arc<Surface<()>>, Capabilities);
+
+    // Creates a device with the `khr_swapchain` extension and a headless surface, or returns `None`
+    // if the implementation doesn't support them.
+    fn headless() -> Option<Headless> {
+        let extensions = InstanceExtensions {
+            khr_surface: true,
+            ext_headless_surface: true,
+            ..InstanceExtensions::none()
+        };
+        let instance = Instance::new(None, &extensions, None).ok()?;
+        let physical = PhysicalDevice::enumerate(&instance).next()?;
+        let queue_family = physical.queue_families().find(|q| q.supports_graphics())?;
+        let device_extensions = DeviceExtensions {
+            khr_swapchain: true,
+            ..DeviceExtensions::none()
+        };
+        let (device, mut queues) = Device::new(
+            physical,
+            &Features::none(),
+            &device_extensions,
+            [(queue_family, 0.5)].iter().cloned(),
+        )
+        .ok()?;
+
+        let surface = Surface::headless(instance.clone(), ()).ok()?;
+        let caps = surface.capabilities(physical).ok()?;
+        Some((device, queues.next()?, surface, caps))
+    }
+
+    // Creates a swapchain with the default parameters supported by the surface.
+    fn swapchain(
+        device: Arc<Device>,
+        surface: Arc<Surface<()>>,
+        caps: &Capabilities,
+        usage: ImageUsage,
+        options: SwapchainOptions,
+    ) -> Result<Arc<Swapchain<()>>, SwapchainCreationError> {
+        let (format, color_space) = caps.supported_formats[0];
+
+        Swapchain::new(
+            device,
+            surface,
+            caps.min_image_count,
+            format,
+            caps.current_extent.unwrap_or(caps.min_image_extent),
+            1,
+            usage,
+            SharingMode::Exclusive,
+            caps.current_transform,
+            caps.supported_composite_alpha.iter().next().unwrap(),
+            PresentMode::Fifo,
+            FullscreenExclusive::Default,
+            None,
+            true,
+            color_space,
+            options,
+        )
+        .map(|(swapchain, _)| swapchain)
+    }
+
+    #[test]
+    fn clamp_recreated_extent() {
+        assert_eq!(
+            clamp_extent([800, 600], [1, 1], [4096, 4096]),
+            Some([800, 600])
+        );
+        assert_eq!(
+            clamp_extent([800, 600], [1024, 1], [4096, 512]),
+            Some([1024, 512])
+        );
+        assert_eq!(clamp_extent([800, 600], [0, 0], [0, 0]), None);
+    }
+
+    #[test]
+    fn win32_monitor_info() {
+        let hmonitor = 0x1234 as *const u8;
+        let monitor = unsafe { Win32Monitor::new(hmonitor) };
+        assert_eq!(monitor, unsafe { Win32Monitor::new(hmonitor) });
+
+        let info = monitor.to_vk();
+        assert_eq!(
+            info.sType,
+            vk::STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_WIN32_INFO_EXT
+        );
+        assert!(info.pNext.is_null());
+        assert_eq!(info.hmonitor as usize, 0x1234);
+    }
+
+    #[test]
+    fn present_ids_increase() {
+        let last_present_id = AtomicU64::new(0);
+        assert!(!claim_present_id(&last_present_id, 0));
+        assert!(claim_present_id(&last_present_id, 1));
+        assert!(claim_present_id(&last_present_id, 5));
+        assert!(!claim_present_id(&last_present_id, 5));
+        assert!(!claim_present_id(&last_present_id, 3));
+        assert!(claim_present_id(&last_present_id, 6));
+    }
+
+    #[test]
+    fn present_wait_error_from_vulkan() {
+        assert_eq!(
+            PresentWaitError::from(Error::OutOfDeviceMemory),
+            PresentWaitError::OomError(OomError::OutOfDeviceMemory)
+        );
+        assert_eq!(
+            PresentWaitError::from(Error::OutOfDate),
+            PresentWaitError::OutOfDate
+        );
+        assert_eq!(
+            PresentWaitError::from(Error::SurfaceLost),
+            PresentWaitError::SurfaceLost
+        );
+    }
+
+    #[test]
+    fn present_modes_start_with_swapchain_mode() {
+        assert!(present_modes_list(PresentMode::Fifo, &[]).is_empty());
+        assert_eq!(
+            present_modes_list(
+                PresentMode::Fifo,
+                &[
+                    PresentMode::Immediate,
+                    PresentMode::Fifo,
+                    PresentMode::Immediate
+                ]
+            ),
+            vec![
+                PresentMode::Fifo as vk::PresentModeKHR,
+                PresentMode::Immediate as vk::PresentModeKHR,
+            ]
+        );
+    }
+
+    #[test]
+    fn options_require_maintenance1() {
+        let (device, _, surface, caps) = match headless() {
+            Some(r) => r,
+            None => return,
+        };
+
+        let options = SwapchainOptions {
+            deferred_memory_allocation: true,
+            ..SwapchainOptions::default()
+        };
+        match swapchain(
+            device.clone(),
+            surface.clone(),
+            &caps,
+            ImageUsage::color_attachment(),
+            options,
+        ) {
+            Err(SwapchainCreationError::MissingFeatureSwapchainMaintenance1) => (),
+            _ => panic!(),
+        }
+
+        let swapchain = swapchain(
+            device,
+            surface,
+            &caps,
+            ImageUsage::color_attachment(),
+            SwapchainOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(swapchain.current_present_mode(), PresentMode::Fifo);
+        assert_eq!(swapchain.options(), &SwapchainOptions::default());
+    }
+
+    #[test]
+    fn protected_requires_feature() {
+        let (device, _, surface, caps) = match headless() {
+            Some(r) => r,
+            None => return,
+        };
+
+        // The instance doesn't load `khr_surface_protected_capabilities`.
+        assert!(!surface
+            .supports_protected(device.physical_device())
+            .unwrap());
+
+        let options = SwapchainOptions {
+            protected: true,
+            ..SwapchainOptions::default()
+        };
+        match swapchain(
+            device,
+            surface,
+            &caps,
+            ImageUsage::color_attachment(),
+            options,
+        ) {
+            Err(SwapchainCreationError::MissingFeatureProtectedMemory) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn display_timing_requires_extension() {
+        let (device, _, surface, caps) = match headless() {
+            Some(r) => r,
+            None => return,
+        };
+
+        let swapchain = swapchain(
+            device,
+            surface,
+            &caps,
+            ImageUsage::color_attachment(),
+            SwapchainOptions::default(),
+        )
+        .unwrap();
+        assert_should_panic!("google_display_timing", {
+            let _ = swapchain.refresh_cycle_duration();
+        });
+        assert_should_panic!("google_display_timing", {
+            let _ = swapchain.past_presentation_timing();
+        });
+    }
+
+    #[test]
+    fn image_usage() {
+        let (device, _, surface, caps) = match headless() {
+            Some(r) => r,
+            None => return,
+        };
+
+        if !caps.supported_usage_flags.depth_stencil_attachment {
+            match swapchain(
+                device.clone(),
+                surface.clone(),
+                &caps,
+                ImageUsage::depth_stencil_attachment(),
+                SwapchainOptions::default(),
+            ) {
+                Err(SwapchainCreationError::UnsupportedUsageFlags) => (),
+                _ => panic!(),
+            }
+        }
+
+        let usage = ImageUsage {
+            transfer_source: caps.supported_usage_flags.transfer_source,
+            ..ImageUsage::color_attachment()
+        };
+        let swapchain =
+            swapchain(device, surface, &caps, usage, SwapchainOptions::default()).unwrap();
+        assert_eq!(swapchain.usage(), usage);
+    }
+
+    #[test]
+    fn reuse_acquire_semaphore() {
+        let (device, queue, surface, caps) = match headless() {
+            Some(r) => r,
+            None => return,
+        };
+
+        let swapchain = swapchain(
+            device.clone(),
+            surface,
+            &caps,
+            ImageUsage::color_attachment(),
+            SwapchainOptions::default(),
+        )
+        .unwrap();
+        let semaphore = Arc::new(Semaphore::alloc(device).unwrap());
+
+        // The semaphore can be used again once the submission that waited on it has finished.
+        for _ in 0..2 {
+            let (image_num, _, future) = unsafe {
+                acquire_next_image_with_semaphore(swapchain.clone(), None, semaphore.clone())
+            }
+            .unwrap();
+            future
+                .then_swapchain_present(queue.clone(), swapchain.clone(), image_num)
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+            assert_eq!(Arc::strong_count(&semaphore), 1);
+        }
+    }
+}
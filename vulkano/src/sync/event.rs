@@ -8,8 +8,8 @@
 // according to those terms.
 
 use std::mem::MaybeUninit;
-use std::ptr;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use crate::check_errors;
 use crate::device::Device;
@@ -25,6 +25,23 @@ use crate::VulkanObject;
 /// event to be signaled, in order to avoid interfering with progress of other clients of the GPU.
 /// If the event isn't signaled within these limits, results are undefined and may include
 /// device loss.
+///
+/// The host can set, reset and query the event through a shared reference, so that an
+/// `Arc<Event>` can be used by a command buffer and by the CPU at the same time. A command buffer
+/// can wait for the CPU to set the event, and the CPU can poll `signaled()` to know when the GPU
+/// has reached a `set_event` command.
+///
+/// ```
+/// use std::sync::Arc;
+/// use vulkano::sync::Event;
+/// # let device: Arc<vulkano::device::Device> = return;
+///
+/// let event = Arc::new(Event::alloc(device.clone()).unwrap());
+///
+/// // Record and submit a command buffer that waits for `event.clone()` here, then unblock it
+/// // once the CPU is done with its work.
+/// event.set();
+/// ```
 #[derive(Debug)]
 pub struct Event {
     // The event.
@@ -32,6 +49,8 @@ pub struct Event {
     // The device.
     device: Arc<Device>,
     must_put_in_pool: bool,
+    // Vulkan requires the host accesses to the event to be externally synchronized.
+    host_access: Mutex<()>,
 }
 
 impl Event {
@@ -41,8 +60,14 @@ impl Event {
     ///
     /// For most applications, using the event pool should be preferred,
     /// in order to avoid creating new events every frame.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device enables `khr_portability_subset` and the `events` feature isn't
+    ///   enabled.
+    ///
     pub fn from_pool(device: Arc<Device>) -> Result<Event, OomError> {
-        let maybe_raw_event = device.event_pool().lock().unwrap().pop();
+        let maybe_raw_event = device.event_pool().pop();
         match maybe_raw_event {
             Some(raw_event) => {
                 unsafe {
@@ -54,6 +79,7 @@ impl Event {
                     event: raw_event,
                     device: device,
                     must_put_in_pool: true,
+                    host_access: Mutex::new(()),
                 })
             }
             None => {
@@ -64,12 +90,23 @@ impl Event {
     }
 
     /// Builds a new event.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device enables `khr_portability_subset` and the `events` feature isn't
+    ///   enabled.
+    ///
     #[inline]
     pub fn alloc(device: Arc<Device>) -> Result<Event, OomError> {
         Event::alloc_impl(device, false)
     }
 
     fn alloc_impl(device: Arc<Device>, must_put_in_pool: bool) -> Result<Event, OomError> {
+        assert!(
+            !device.loaded_extensions().khr_portability_subset || device.enabled_features().events,
+            "the `events` feature must be enabled on a portability subset device"
+        );
+
         let event = unsafe {
             // since the creation is constant, we use a `static` instead of a struct on the stack
             static mut INFOS: vk::EventCreateInfo = vk::EventCreateInfo {
@@ -83,7 +120,7 @@ impl Event {
             check_errors(vk.CreateEvent(
                 device.internal_object(),
                 &INFOS,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -93,6 +130,7 @@ impl Event {
             device: device,
             event: event,
             must_put_in_pool: must_put_in_pool,
+            host_access: Mutex::new(()),
         })
     }
 
@@ -113,7 +151,8 @@ impl Event {
 
     /// See the docs of set().
     #[inline]
-    pub fn set_raw(&mut self) -> Result<(), OomError> {
+    pub fn set_raw(&self) -> Result<(), OomError> {
+        let _guard = self.host_access.lock().unwrap();
         unsafe {
             let vk = self.device.pointers();
             check_errors(vk.SetEvent(self.device.internal_object(), self.event))?;
@@ -130,13 +169,14 @@ impl Event {
     /// - Panics if the device or host ran out of memory.
     ///
     #[inline]
-    pub fn set(&mut self) {
+    pub fn set(&self) {
         self.set_raw().unwrap();
     }
 
     /// See the docs of reset().
     #[inline]
-    pub fn reset_raw(&mut self) -> Result<(), OomError> {
+    pub fn reset_raw(&self) -> Result<(), OomError> {
+        let _guard = self.host_access.lock().unwrap();
         unsafe {
             let vk = self.device.pointers();
             check_errors(vk.ResetEvent(self.device.internal_object(), self.event))?;
@@ -151,7 +191,7 @@ impl Event {
     /// - Panics if the device or host ran out of memory.
     ///
     #[inline]
-    pub fn reset(&mut self) {
+    pub fn reset(&self) {
         self.reset_raw().unwrap();
     }
 }
@@ -180,10 +220,14 @@ impl Drop for Event {
         unsafe {
             if self.must_put_in_pool {
                 let raw_event = self.event;
-                self.device.event_pool().lock().unwrap().push(raw_event);
+                self.device.event_pool().push(raw_event);
             } else {
                 let vk = self.device.pointers();
-                vk.DestroyEvent(self.device.internal_object(), self.event, ptr::null());
+                vk.DestroyEvent(
+                    self.device.internal_object(),
+                    self.event,
+                    self.device.alloc_ptr(),
+                );
             }
         }
     }
@@ -193,6 +237,7 @@ impl Drop for Event {
 mod tests {
     use crate::sync::Event;
     use crate::VulkanObject;
+    use std::sync::Arc;
 
     #[test]
     fn event_create() {
@@ -201,10 +246,19 @@ mod tests {
         assert!(!event.signaled().unwrap());
     }
 
+    #[test]
+    fn event_create_portability_subset() {
+        let (device, _) = gfx_dev_and_queue!(events; khr_portability_subset);
+        assert!(device.loaded_extensions().khr_portability_subset);
+
+        let event = Event::alloc(device).unwrap();
+        assert!(!event.signaled().unwrap());
+    }
+
     #[test]
     fn event_set() {
         let (device, _) = gfx_dev_and_queue!();
-        let mut event = Event::alloc(device).unwrap();
+        let event = Event::alloc(device).unwrap();
         assert!(!event.signaled().unwrap());
 
         event.set();
@@ -215,7 +269,7 @@ mod tests {
     fn event_reset() {
         let (device, _) = gfx_dev_and_queue!();
 
-        let mut event = Event::alloc(device).unwrap();
+        let event = Event::alloc(device).unwrap();
         event.set();
         assert!(event.signaled().unwrap());
 
@@ -223,20 +277,30 @@ mod tests {
         assert!(!event.signaled().unwrap());
     }
 
+    #[test]
+    fn event_set_shared() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let event = Arc::new(Event::alloc(device).unwrap());
+        let other = event.clone();
+        other.set();
+        assert!(event.signaled().unwrap());
+    }
+
     #[test]
     fn event_pool() {
         let (device, _) = gfx_dev_and_queue!();
 
-        assert_eq!(device.event_pool().lock().unwrap().len(), 0);
+        assert_eq!(device.event_pool().len(), 0);
         let event1_internal_obj = {
             let event = Event::from_pool(device.clone()).unwrap();
-            assert_eq!(device.event_pool().lock().unwrap().len(), 0);
+            assert_eq!(device.event_pool().len(), 0);
             event.internal_object()
         };
 
-        assert_eq!(device.event_pool().lock().unwrap().len(), 1);
+        assert_eq!(device.event_pool().len(), 1);
         let event2 = Event::from_pool(device.clone()).unwrap();
-        assert_eq!(device.event_pool().lock().unwrap().len(), 0);
+        assert_eq!(device.event_pool().len(), 0);
         assert_eq!(event2.internal_object(), event1_internal_obj);
     }
 }
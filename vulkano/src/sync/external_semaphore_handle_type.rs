@@ -0,0 +1,104 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::vk;
+use std::ops::BitOr;
+
+/// Describes the handle type used for Vulkan external semaphore apis. Check out
+/// vkExternalSemaphoreHandleTypeFlagBits in the Vulkan spec.
+///
+/// The `opaque_fd` and `sync_fd` handle types are only available on Unix-like systems, while the
+/// other handle types are only available on Windows.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ExternalSemaphoreHandleType {
+    pub opaque_fd: bool,
+    pub opaque_win32: bool,
+    pub opaque_win32_kmt: bool,
+    pub d3d12_fence: bool,
+    pub sync_fd: bool,
+}
+
+impl ExternalSemaphoreHandleType {
+    /// Builds a `ExternalSemaphoreHandleType` with all values set to false. Useful as a default
+    /// value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use vulkano::sync::ExternalSemaphoreHandleType;
+    ///
+    /// let _handle_type = ExternalSemaphoreHandleType {
+    ///     opaque_fd: true,
+    ///     .. ExternalSemaphoreHandleType::none()
+    /// };
+    /// ```
+    #[inline]
+    pub fn none() -> ExternalSemaphoreHandleType {
+        ExternalSemaphoreHandleType {
+            opaque_fd: false,
+            opaque_win32: false,
+            opaque_win32_kmt: false,
+            d3d12_fence: false,
+            sync_fd: false,
+        }
+    }
+
+    /// Builds an `ExternalSemaphoreHandleType` for a posix file descriptor.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use vulkano::sync::ExternalSemaphoreHandleType;
+    ///
+    /// let _handle_type = ExternalSemaphoreHandleType::posix();
+    /// ```
+    #[inline]
+    pub fn posix() -> ExternalSemaphoreHandleType {
+        ExternalSemaphoreHandleType {
+            opaque_fd: true,
+            ..ExternalSemaphoreHandleType::none()
+        }
+    }
+
+    #[inline]
+    pub(crate) fn to_bits(self) -> vk::ExternalSemaphoreHandleTypeFlagBits {
+        let mut result = 0;
+        if self.opaque_fd {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT;
+        }
+        if self.opaque_win32 {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT;
+        }
+        if self.opaque_win32_kmt {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT;
+        }
+        if self.d3d12_fence {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT;
+        }
+        if self.sync_fd {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT;
+        }
+        result
+    }
+}
+
+impl BitOr for ExternalSemaphoreHandleType {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        ExternalSemaphoreHandleType {
+            opaque_fd: self.opaque_fd || rhs.opaque_fd,
+            opaque_win32: self.opaque_win32 || rhs.opaque_win32,
+            opaque_win32_kmt: self.opaque_win32_kmt || rhs.opaque_win32_kmt,
+            d3d12_fence: self.d3d12_fence || rhs.d3d12_fence,
+            sync_fd: self.sync_fd || rhs.sync_fd,
+        }
+    }
+}
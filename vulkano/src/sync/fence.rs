@@ -10,16 +10,29 @@
 use smallvec::SmallVec;
 use std::error;
 use std::fmt;
+#[cfg(target_os = "linux")]
+use std::fs::File;
+use std::mem;
 use std::mem::MaybeUninit;
+#[cfg(windows)]
+use std::os::raw::c_void;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::FromRawFd;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::IntoRawFd;
 use std::ptr;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Waker;
+use std::thread;
 use std::time::Duration;
 
 use crate::check_errors;
 use crate::device::Device;
 use crate::device::DeviceOwned;
+use crate::sync::ExternalFenceHandleType;
 use crate::vk;
 use crate::Error;
 use crate::OomError;
@@ -49,6 +62,9 @@ where
     // Indicates whether this fence was taken from the fence pool.
     // If true, will be put back into fence pool on drop.
     must_put_in_pool: bool,
+
+    // The handle types that the payload of the fence can be exported to.
+    export_handle_types: ExternalFenceHandleType,
 }
 
 impl<D> Fence<D>
@@ -61,22 +77,19 @@ where
     ///
     /// For most applications, using the fence pool should be preferred,
     /// in order to avoid creating new fences every frame.
+    ///
+    /// The fences that are put back into the pool are reset lazily, all at once, the next time
+    /// the pool runs out of fences that are ready to be handed out.
     pub fn from_pool(device: D) -> Result<Fence<D>, OomError> {
-        let maybe_raw_fence = device.fence_pool().lock().unwrap().pop();
+        let maybe_raw_fence = device.fence_pool().lock().unwrap().pop(&device)?;
         match maybe_raw_fence {
-            Some(raw_fence) => {
-                unsafe {
-                    // Make sure the fence isn't signaled
-                    let vk = device.pointers();
-                    check_errors(vk.ResetFences(device.internal_object(), 1, &raw_fence))?;
-                }
-                Ok(Fence {
-                    fence: raw_fence,
-                    device: device,
-                    signaled: AtomicBool::new(false),
-                    must_put_in_pool: true,
-                })
-            }
+            Some(raw_fence) => Ok(Fence {
+                fence: raw_fence,
+                device,
+                signaled: AtomicBool::new(false),
+                must_put_in_pool: true,
+                export_handle_types: ExternalFenceHandleType::none(),
+            }),
             None => {
                 // Pool is empty, alloc new fence
                 Fence::alloc_impl(device, false, true)
@@ -113,7 +126,7 @@ where
             check_errors(vk.CreateFence(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -124,9 +137,315 @@ where
             device: device,
             signaled: AtomicBool::new(signaled),
             must_put_in_pool: must_put_in_pool,
+            export_handle_types: ExternalFenceHandleType::none(),
         })
     }
 
+    /// Wraps a fence that has been created by the implementation, for example by
+    /// `vkRegisterDisplayEventEXT`. The fence is destroyed on drop.
+    #[inline]
+    pub(crate) unsafe fn from_raw(device: D, fence: vk::Fence) -> Fence<D> {
+        Fence {
+            fence,
+            device,
+            signaled: AtomicBool::new(false),
+            must_put_in_pool: false,
+            export_handle_types: ExternalFenceHandleType::none(),
+        }
+    }
+
+    /// Builds a new unsignaled fence whose payload can be exported with the given handle types,
+    /// for example so that another process can wait for a submission.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_fence` extension isn't enabled on the device.
+    ///
+    pub fn alloc_with_exportable(
+        device: D,
+        handle_types: ExternalFenceHandleType,
+    ) -> Result<Fence<D>, OomError> {
+        assert!(
+            device.loaded_extensions_or_core().khr_external_fence,
+            "the khr_external_fence extension must be enabled"
+        );
+
+        let fence = unsafe {
+            let export_info = vk::ExportFenceCreateInfo {
+                sType: vk::STRUCTURE_TYPE_EXPORT_FENCE_CREATE_INFO,
+                pNext: ptr::null(),
+                handleTypes: handle_types.to_bits(),
+            };
+
+            let infos = vk::FenceCreateInfo {
+                sType: vk::STRUCTURE_TYPE_FENCE_CREATE_INFO,
+                pNext: &export_info as *const _ as *const _,
+                flags: 0,
+            };
+
+            let vk = device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateFence(
+                device.internal_object(),
+                &infos,
+                device.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(Fence {
+            fence,
+            device,
+            signaled: AtomicBool::new(false),
+            must_put_in_pool: false,
+            export_handle_types: handle_types,
+        })
+    }
+
+    /// Returns the handle types that the fence can be exported to.
+    #[inline]
+    pub fn export_handle_types(&self) -> ExternalFenceHandleType {
+        self.export_handle_types
+    }
+
+    /// Exports the payload of the fence into a Unix file descriptor. The caller retains
+    /// ownership of the file, as per the Vulkan spec.
+    ///
+    /// `handle_type` must be either `opaque_fd` or `sync_fd`. Exporting a `sync_fd` handle
+    /// resets the fence, and can return `None` if the fence is already signaled.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_fence_fd` extension isn't enabled on the device.
+    /// - Panics if `handle_type` isn't a single POSIX file descriptor handle type, or wasn't
+    ///   passed to `alloc_with_exportable`.
+    ///
+    /// # Safety
+    ///
+    /// - If `handle_type` is `sync_fd`, the fence must be signaled, or have a signal operation
+    ///   pending in a queue.
+    ///
+    // This function takes a `&mut self` because exporting a `sync_fd` resets the fence, which
+    // requires the fence to be externally synchronized.
+    #[cfg(target_os = "linux")]
+    pub unsafe fn export_fd(
+        &mut self,
+        handle_type: ExternalFenceHandleType,
+    ) -> Result<Option<File>, ExternalFenceError> {
+        assert!(
+            self.device.loaded_extensions().khr_external_fence_fd,
+            "the khr_external_fence_fd extension must be enabled"
+        );
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_FD_BIT
+                || bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_SYNC_FD_BIT,
+            "the handle type must be either opaque_fd or sync_fd"
+        );
+        assert!(
+            bits & self.export_handle_types.to_bits() != 0,
+            "the fence wasn't created as exportable to this handle type"
+        );
+
+        let info = vk::FenceGetFdInfoKHR {
+            sType: vk::STRUCTURE_TYPE_FENCE_GET_FD_INFO_KHR,
+            pNext: ptr::null(),
+            fence: self.fence,
+            handleType: bits,
+        };
+
+        let vk = self.device.pointers();
+        let mut output = MaybeUninit::uninit();
+        check_errors(vk.GetFenceFdKHR(self.device.internal_object(), &info, output.as_mut_ptr()))?;
+
+        if bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_SYNC_FD_BIT {
+            self.signaled.store(false, Ordering::Relaxed);
+        }
+
+        match output.assume_init() {
+            -1 => Ok(None),
+            fd => Ok(Some(File::from_raw_fd(fd))),
+        }
+    }
+
+    /// Imports a payload into the fence from a Unix file descriptor. On success, the
+    /// implementation takes ownership of the file.
+    ///
+    /// If `temporary` is true, the payload is only used until the fence is reset, after which
+    /// the fence goes back to its own payload. `sync_fd` payloads can only be imported
+    /// temporarily.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_fence_fd` extension isn't enabled on the device.
+    /// - Panics if `handle_type` isn't a single POSIX file descriptor handle type.
+    /// - Panics if `handle_type` is `sync_fd` and `temporary` is false.
+    ///
+    /// # Safety
+    ///
+    /// - The file must hold a payload of the given handle type, exported from a fence that is
+    ///   compatible with this device.
+    /// - The fence must not be in use by the GPU.
+    ///
+    #[cfg(target_os = "linux")]
+    pub unsafe fn import_fd(
+        &mut self,
+        handle_type: ExternalFenceHandleType,
+        file: File,
+        temporary: bool,
+    ) -> Result<(), ExternalFenceError> {
+        assert!(
+            self.device.loaded_extensions().khr_external_fence_fd,
+            "the khr_external_fence_fd extension must be enabled"
+        );
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_FD_BIT
+                || bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_SYNC_FD_BIT,
+            "the handle type must be either opaque_fd or sync_fd"
+        );
+        assert!(
+            temporary || bits != vk::EXTERNAL_FENCE_HANDLE_TYPE_SYNC_FD_BIT,
+            "sync_fd payloads can only be imported temporarily"
+        );
+
+        let fd = file.into_raw_fd();
+        let info = vk::ImportFenceFdInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_FENCE_FD_INFO_KHR,
+            pNext: ptr::null(),
+            fence: self.fence,
+            flags: if temporary {
+                vk::FENCE_IMPORT_TEMPORARY_BIT
+            } else {
+                0
+            },
+            handleType: bits,
+            fd,
+        };
+
+        let vk = self.device.pointers();
+        if let Err(err) = check_errors(vk.ImportFenceFdKHR(self.device.internal_object(), &info)) {
+            // The implementation only takes ownership of the file on success.
+            drop(File::from_raw_fd(fd));
+            return Err(err.into());
+        }
+
+        // The signaled state of the fence is now the one of the imported payload.
+        self.signaled.store(false, Ordering::Relaxed);
+        self.must_put_in_pool = false;
+        Ok(())
+    }
+
+    /// Exports the payload of the fence into a Windows handle.
+    ///
+    /// `handle_type` must be either `opaque_win32` or `opaque_win32_kmt`. The caller owns the
+    /// returned handle and must close it with `CloseHandle`, except for `opaque_win32_kmt`
+    /// handles.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_fence_win32` extension isn't enabled on the device.
+    /// - Panics if `handle_type` isn't a single Windows handle type, or wasn't passed to
+    ///   `alloc_with_exportable`.
+    ///
+    #[cfg(windows)]
+    pub fn export_win32_handle(
+        &self,
+        handle_type: ExternalFenceHandleType,
+    ) -> Result<*mut c_void, ExternalFenceError> {
+        assert!(
+            self.device.loaded_extensions().khr_external_fence_win32,
+            "the khr_external_fence_win32 extension must be enabled"
+        );
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_BIT
+                || bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT,
+            "the handle type must be either opaque_win32 or opaque_win32_kmt"
+        );
+        assert!(
+            bits & self.export_handle_types.to_bits() != 0,
+            "the fence wasn't created as exportable to this handle type"
+        );
+
+        unsafe {
+            let info = vk::FenceGetWin32HandleInfoKHR {
+                sType: vk::STRUCTURE_TYPE_FENCE_GET_WIN32_HANDLE_INFO_KHR,
+                pNext: ptr::null(),
+                fence: self.fence,
+                handleType: bits,
+            };
+
+            let vk = self.device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.GetFenceWin32HandleKHR(
+                self.device.internal_object(),
+                &info,
+                output.as_mut_ptr(),
+            ))?;
+            Ok(output.assume_init())
+        }
+    }
+
+    /// Imports a payload into the fence from a Windows handle. The caller keeps the ownership
+    /// of the handle.
+    ///
+    /// If `temporary` is true, the payload is only used until the fence is reset, after which
+    /// the fence goes back to its own payload.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_fence_win32` extension isn't enabled on the device.
+    /// - Panics if `handle_type` isn't a single Windows handle type.
+    ///
+    /// # Safety
+    ///
+    /// - The handle must hold a payload of the given handle type, exported from a fence that is
+    ///   compatible with this device.
+    /// - The fence must not be in use by the GPU.
+    ///
+    #[cfg(windows)]
+    pub unsafe fn import_win32_handle(
+        &mut self,
+        handle_type: ExternalFenceHandleType,
+        handle: *mut c_void,
+        temporary: bool,
+    ) -> Result<(), ExternalFenceError> {
+        assert!(
+            self.device.loaded_extensions().khr_external_fence_win32,
+            "the khr_external_fence_win32 extension must be enabled"
+        );
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_BIT
+                || bits == vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT,
+            "the handle type must be either opaque_win32 or opaque_win32_kmt"
+        );
+
+        let info = vk::ImportFenceWin32HandleInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_FENCE_WIN32_HANDLE_INFO_KHR,
+            pNext: ptr::null(),
+            fence: self.fence,
+            flags: if temporary {
+                vk::FENCE_IMPORT_TEMPORARY_BIT
+            } else {
+                0
+            },
+            handleType: bits,
+            handle,
+            name: ptr::null(),
+        };
+
+        let vk = self.device.pointers();
+        check_errors(vk.ImportFenceWin32HandleKHR(self.device.internal_object(), &info))?;
+
+        // The signaled state of the fence is now the one of the imported payload.
+        self.signaled.store(false, Ordering::Relaxed);
+        self.must_put_in_pool = false;
+        Ok(())
+    }
+
     /// Returns true if the fence is signaled.
     #[inline]
     pub fn ready(&self) -> Result<bool, OomError> {
@@ -195,11 +514,37 @@ where
     ///
     /// Panics if not all fences belong to the same device.
     pub fn multi_wait<'a, I>(iter: I, timeout: Option<Duration>) -> Result<(), FenceWaitError>
+    where
+        I: IntoIterator<Item = &'a Fence<D>>,
+        D: 'a,
+    {
+        Fence::multi_wait_impl(iter, true, timeout)
+    }
+
+    /// Same as `multi_wait`, but returns as soon as one of the fences is signaled.
+    #[inline]
+    pub(crate) fn multi_wait_any<'a, I>(
+        iter: I,
+        timeout: Option<Duration>,
+    ) -> Result<(), FenceWaitError>
+    where
+        I: IntoIterator<Item = &'a Fence<D>>,
+        D: 'a,
+    {
+        Fence::multi_wait_impl(iter, false, timeout)
+    }
+
+    fn multi_wait_impl<'a, I>(
+        iter: I,
+        wait_all: bool,
+        timeout: Option<Duration>,
+    ) -> Result<(), FenceWaitError>
     where
         I: IntoIterator<Item = &'a Fence<D>>,
         D: 'a,
     {
         let mut device: Option<&Device> = None;
+        let mut signaled = false;
 
         let fences: SmallVec<[vk::Fence; 8]> = iter
             .into_iter()
@@ -215,6 +560,7 @@ where
                 };
 
                 if fence.signaled.load(Ordering::Relaxed) {
+                    signaled = true;
                     None
                 } else {
                     Some(fence.fence)
@@ -222,6 +568,11 @@ where
             })
             .collect();
 
+        // `vkWaitForFences` would wait for one of the other fences.
+        if signaled && !wait_all {
+            return Ok(());
+        }
+
         let timeout_ns = if let Some(timeout) = timeout {
             timeout
                 .as_secs()
@@ -238,7 +589,7 @@ where
                     device.internal_object(),
                     fences.len() as u32,
                     fences.as_ptr(),
-                    vk::TRUE,
+                    if wait_all { vk::TRUE } else { vk::FALSE },
                     timeout_ns,
                 ))?
             }
@@ -344,12 +695,129 @@ where
                 self.device.fence_pool().lock().unwrap().push(raw_fence);
             } else {
                 let vk = self.device.pointers();
-                vk.DestroyFence(self.device.internal_object(), self.fence, ptr::null());
+                vk.DestroyFence(
+                    self.device.internal_object(),
+                    self.fence,
+                    self.device.alloc_ptr(),
+                );
             }
         }
     }
 }
 
+/// The fences of a device that are waiting to be handed out again by `Fence::from_pool`.
+#[derive(Debug, Default)]
+pub(crate) struct FencePool {
+    // Fences that have been reset and can be handed out immediately.
+    reset: Vec<vk::Fence>,
+    // Fences that have been put back into the pool and that may still be signaled.
+    returned: Vec<vk::Fence>,
+}
+
+impl FencePool {
+    /// Returns the total number of fences in the pool.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.reset.len() + self.returned.len()
+    }
+
+    /// Returns all the fences in the pool.
+    #[inline]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &vk::Fence> {
+        self.reset.iter().chain(self.returned.iter())
+    }
+
+    /// Puts back a fence into the pool. The fence must not be in use by the GPU.
+    #[inline]
+    pub(crate) fn push(&mut self, fence: vk::Fence) {
+        self.returned.push(fence);
+    }
+
+    /// Takes an unsignaled fence from the pool, or returns `None` if the pool is empty.
+    ///
+    /// If no fence has been reset yet, all the fences that have been put back are reset with a
+    /// single call.
+    pub(crate) fn pop(&mut self, device: &Device) -> Result<Option<vk::Fence>, OomError> {
+        if self.reset.is_empty() && !self.returned.is_empty() {
+            unsafe {
+                let vk = device.pointers();
+                check_errors(vk.ResetFences(
+                    device.internal_object(),
+                    self.returned.len() as u32,
+                    self.returned.as_ptr(),
+                ))?;
+            }
+            mem::swap(&mut self.reset, &mut self.returned);
+        }
+
+        Ok(self.reset.pop())
+    }
+}
+
+/// The fences of a device whose signal has to wake up a task.
+///
+/// The fences are waited for by a single thread, which is started when the first fence is added
+/// and which stops once all the fences have been signaled.
+#[derive(Debug, Default)]
+pub(crate) struct FenceWaiter {
+    // The fences that are not known to be signaled, and the waker to call once they are.
+    fences: Vec<(Arc<Fence>, Arc<Mutex<Waker>>)>,
+    // True if the waiting thread is running.
+    running: bool,
+}
+
+// How long the waiting thread waits before taking into account the fences that have been added
+// in the meantime.
+const FENCE_WAITER_TIMEOUT: Duration = Duration::from_millis(1);
+
+impl FenceWaiter {
+    /// Wakes up `waker` once `fence` is signaled, or once waiting for it fails.
+    pub(crate) fn wake_when_signaled(fence: Arc<Fence>, waker: Arc<Mutex<Waker>>) {
+        let device = fence.device.clone();
+        let mut waiter = device.fence_waiter().lock().unwrap();
+        waiter.fences.push((fence, waker));
+
+        if !waiter.running {
+            waiter.running = true;
+            let device = device.clone();
+            thread::spawn(move || FenceWaiter::run(device));
+        }
+    }
+
+    fn run(device: Arc<Device>) {
+        loop {
+            let fences: SmallVec<[Arc<Fence>; 8]> = {
+                let mut waiter = device.fence_waiter().lock().unwrap();
+
+                // Errors are returned by the next poll of the task.
+                waiter.fences.retain(|(fence, waker)| match fence.ready() {
+                    Ok(false) => true,
+                    _ => {
+                        waker.lock().unwrap().wake_by_ref();
+                        false
+                    }
+                });
+
+                if waiter.fences.is_empty() {
+                    waiter.running = false;
+                    return;
+                }
+
+                waiter
+                    .fences
+                    .iter()
+                    .map(|(fence, _)| fence.clone())
+                    .collect()
+            };
+
+            let _ = Fence::multi_wait_any(
+                fences.iter().map(|fence| &**fence),
+                Some(FENCE_WAITER_TIMEOUT),
+            );
+        }
+    }
+}
+
 /// Error that can be returned when waiting on a fence.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FenceWaitError {
@@ -400,11 +868,72 @@ impl From<Error> for FenceWaitError {
     }
 }
 
+/// Error that can happen when exporting or importing the payload of a fence.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExternalFenceError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// Too many handles have been exported.
+    TooManyObjects,
+
+    /// The handle to import isn't valid for the given handle type.
+    InvalidExternalHandle,
+}
+
+impl error::Error for ExternalFenceError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ExternalFenceError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ExternalFenceError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                ExternalFenceError::OomError(_) => "not enough memory",
+                ExternalFenceError::TooManyObjects => "too many handles have been exported",
+                ExternalFenceError::InvalidExternalHandle => {
+                    "the handle isn't valid for the given handle type"
+                }
+            }
+        )
+    }
+}
+
+impl From<Error> for ExternalFenceError {
+    #[inline]
+    fn from(err: Error) -> ExternalFenceError {
+        match err {
+            err @ Error::OutOfHostMemory => ExternalFenceError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => ExternalFenceError::OomError(OomError::from(err)),
+            Error::TooManyObjects => ExternalFenceError::TooManyObjects,
+            Error::InvalidExternalHandle => ExternalFenceError::InvalidExternalHandle,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for ExternalFenceError {
+    #[inline]
+    fn from(err: OomError) -> ExternalFenceError {
+        ExternalFenceError::OomError(err)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use crate::sync::ExternalFenceHandleType;
     use crate::sync::Fence;
     use crate::VulkanObject;
+    use std::time::Duration;
 
     #[test]
     fn fence_create() {
@@ -494,4 +1023,32 @@ mod tests {
         assert_eq!(device.fence_pool().lock().unwrap().len(), 0);
         assert_eq!(fence2.internal_object(), fence1_internal_obj);
     }
+
+    #[test]
+    fn fence_pool_resets_returned_fences() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        {
+            let fence1 = Fence::from_pool(device.clone()).unwrap();
+            let fence2 = Fence::alloc_impl(device.clone(), true, true).unwrap();
+            assert!(!fence1.ready().unwrap());
+            assert!(fence2.ready().unwrap());
+        }
+
+        assert_eq!(device.fence_pool().lock().unwrap().len(), 2);
+        let fence1 = Fence::from_pool(device.clone()).unwrap();
+        let fence2 = Fence::from_pool(device.clone()).unwrap();
+        assert_eq!(device.fence_pool().lock().unwrap().len(), 0);
+        assert!(!fence1.ready().unwrap());
+        assert!(!fence2.ready().unwrap());
+    }
+
+    #[test]
+    fn exportable_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!("the khr_external_fence extension must be enabled", {
+            let _ = Fence::alloc_with_exportable(device.clone(), ExternalFenceHandleType::posix());
+        });
+    }
 }
@@ -7,15 +7,21 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::future::Future;
 use std::mem;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
 use std::time::Duration;
 
 use crate::buffer::BufferAccess;
 use crate::command_buffer::submit::SubmitAnyBuilder;
 use crate::command_buffer::submit::SubmitCommandBufferBuilder;
+use crate::command_buffer::submit::SubmitScheduler;
 use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::device::Queue;
@@ -24,6 +30,8 @@ use crate::image::ImageLayout;
 use crate::sync::AccessCheckError;
 use crate::sync::AccessFlagBits;
 use crate::sync::Fence;
+use crate::sync::FenceWaitError;
+use crate::sync::FenceWaiter;
 use crate::sync::FlushError;
 use crate::sync::GpuFuture;
 use crate::sync::PipelineStages;
@@ -38,11 +46,12 @@ where
 
     assert!(future.queue().is_some()); // TODO: document
 
-    let fence = Fence::from_pool(device.clone()).unwrap();
+    let fence = Arc::new(Fence::from_pool(device.clone()).unwrap());
     FenceSignalFuture {
         device: device,
         state: Mutex::new(FenceSignalFutureState::Pending(future, fence)),
         behavior: behavior,
+        waker: Mutex::new(None),
     }
 }
 
@@ -88,6 +97,26 @@ pub enum FenceSignalFutureBehavior {
 /// // Later you can wait until you reach the point of `fence_signal`:
 /// fence_signal.wait(None).unwrap();
 /// ```
+///
+/// # Async
+///
+/// `FenceSignalFuture` also implements the standard `Future` trait, so that async code can await
+/// the GPU instead of blocking a thread with `wait()`. Awaiting the future performs a flush if
+/// necessary, and resolves once the fence is signaled.
+///
+/// The first time the future is polled while the GPU is still busy, its fence is handed to a
+/// thread of the device, which waits for the fences of all the futures being awaited at once and
+/// wakes up each task once its fence is signaled.
+///
+/// ```
+/// use vulkano::sync::FenceSignalFuture;
+/// use vulkano::sync::GpuFuture;
+///
+/// async fn render(future: FenceSignalFuture<Box<dyn GpuFuture>>) {
+///     // The thread is free to run other tasks while the GPU is working.
+///     future.await.unwrap();
+/// }
+/// ```
 #[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
               processing the submission"]
 pub struct FenceSignalFuture<F>
@@ -99,6 +128,10 @@ where
     // The device of the future.
     device: Arc<Device>,
     behavior: FenceSignalFutureBehavior,
+    // The waker of the task that awaits this future, shared with the thread of the device that
+    // waits for the fences. `None` if the future has never been polled while the fence was
+    // unsignaled.
+    waker: Mutex<Option<Arc<Mutex<Waker>>>>,
 }
 
 // This future can be in three different states: pending (ie. newly-created), submitted (ie. the
@@ -106,17 +139,17 @@ where
 // been dropped).
 enum FenceSignalFutureState<F> {
     // Newly-created. Not submitted yet.
-    Pending(F, Fence),
+    Pending(F, Arc<Fence>),
 
     // Partially submitted to the queue. Only happens in situations where submitting requires two
     // steps, and when the first step succeeded while the second step failed.
     //
     // Note that if there's ever a submit operation that needs three steps we will need to rework
     // this code, as it was designed for two-step operations only.
-    PartiallyFlushed(F, Fence),
+    PartiallyFlushed(F, Arc<Fence>),
 
     // Submitted to the queue.
-    Flushed(F, Fence),
+    Flushed(F, Arc<Fence>),
 
     // The submission is finished. The previous future and the fence have been cleaned.
     Cleaned,
@@ -224,56 +257,62 @@ where
                 Partial(E),
                 Full(E),
             }
-            let result = match previous.build_submission()? {
-                SubmitAnyBuilder::Empty => {
-                    debug_assert!(!partially_flushed);
-                    let mut b = SubmitCommandBufferBuilder::new();
-                    b.set_fence_signal(&fence);
-                    b.submit(&queue).map_err(|err| OutcomeErr::Full(err.into()))
-                }
-                SubmitAnyBuilder::SemaphoresWait(sem) => {
-                    debug_assert!(!partially_flushed);
-                    let b: SubmitCommandBufferBuilder = sem.into();
-                    debug_assert!(!b.has_fence());
-                    b.submit(&queue).map_err(|err| OutcomeErr::Full(err.into()))
-                }
-                SubmitAnyBuilder::CommandBuffer(mut cb_builder) => {
-                    debug_assert!(!partially_flushed);
-                    // The assert below could technically be a debug assertion as it is part of the
-                    // safety contract of the trait. However it is easy to get this wrong if you
-                    // write a custom implementation, and if so the consequences would be
-                    // disastrous and hard to debug. Therefore we prefer to just use a regular
-                    // assertion.
-                    assert!(!cb_builder.has_fence());
-                    cb_builder.set_fence_signal(&fence);
-                    cb_builder
-                        .submit(&queue)
-                        .map_err(|err| OutcomeErr::Full(err.into()))
-                }
-                SubmitAnyBuilder::BindSparse(mut sparse) => {
-                    debug_assert!(!partially_flushed);
-                    // Same remark as `CommandBuffer`.
-                    assert!(!sparse.has_fence());
-                    sparse.set_fence_signal(&fence);
-                    sparse
-                        .submit(&queue)
-                        .map_err(|err| OutcomeErr::Full(err.into()))
-                }
-                SubmitAnyBuilder::QueuePresent(present) => {
-                    let intermediary_result = if partially_flushed {
-                        Ok(())
-                    } else {
-                        present.submit(&queue)
-                    };
-                    match intermediary_result {
-                        Ok(()) => {
-                            let mut b = SubmitCommandBufferBuilder::new();
-                            b.set_fence_signal(&fence);
-                            b.submit(&queue)
-                                .map_err(|err| OutcomeErr::Partial(err.into()))
+            let result = {
+                let mut scheduler = SubmitScheduler::new();
+                let ends_with_fence_submit = match previous.build_submission()? {
+                    SubmitAnyBuilder::Empty => {
+                        debug_assert!(!partially_flushed);
+                        let mut b = SubmitCommandBufferBuilder::new();
+                        b.set_fence_signal(&fence);
+                        scheduler.add_command_buffers(b);
+                        false
+                    }
+                    SubmitAnyBuilder::SemaphoresWait(sem) => {
+                        debug_assert!(!partially_flushed);
+                        let b: SubmitCommandBufferBuilder = sem.into();
+                        debug_assert!(!b.has_fence());
+                        scheduler.add_command_buffers(b);
+                        false
+                    }
+                    SubmitAnyBuilder::CommandBuffer(mut cb_builder) => {
+                        debug_assert!(!partially_flushed);
+                        // The assert below could technically be a debug assertion as it is part
+                        // of the safety contract of the trait. However it is easy to get this
+                        // wrong if you write a custom implementation, and if so the consequences
+                        // would be disastrous and hard to debug. Therefore we prefer to just use a
+                        // regular assertion.
+                        assert!(!cb_builder.has_fence());
+                        cb_builder.set_fence_signal(&fence);
+                        scheduler.add_command_buffers(cb_builder);
+                        false
+                    }
+                    SubmitAnyBuilder::BindSparse(mut sparse) => {
+                        debug_assert!(!partially_flushed);
+                        // Same remark as `CommandBuffer`.
+                        assert!(!sparse.has_fence());
+                        sparse.set_fence_signal(&fence);
+                        scheduler.add_bind_sparse(sparse);
+                        false
+                    }
+                    SubmitAnyBuilder::QueuePresent(present) => {
+                        if !partially_flushed {
+                            scheduler.add_present(present);
                         }
-                        Err(err) => Err(OutcomeErr::Full(err.into())),
+                        let mut b = SubmitCommandBufferBuilder::new();
+                        b.set_fence_signal(&fence);
+                        scheduler.add_command_buffers(b);
+                        true
                     }
+                };
+
+                match scheduler.flush(&queue) {
+                    Ok(()) => Ok(()),
+                    // Only the submission that signals the fence failed, the present operation
+                    // has already been sent.
+                    Err(err) if ends_with_fence_submit && scheduler.is_empty() => {
+                        Err(OutcomeErr::Partial(err))
+                    }
+                    Err(err) => Err(OutcomeErr::Full(err)),
                 }
             };
 
@@ -296,6 +335,63 @@ where
     }
 }
 
+impl<F> Future for FenceSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    type Output = Result<(), FlushError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Err(err) = self.flush_impl(&mut state) {
+            return Poll::Ready(Err(err));
+        }
+
+        let fence = match *state {
+            FenceSignalFutureState::Flushed(_, ref fence) => fence.clone(),
+            FenceSignalFutureState::Cleaned | FenceSignalFutureState::Poisoned => {
+                return Poll::Ready(Ok(()))
+            }
+            FenceSignalFutureState::Pending(_, _) => unreachable!(),
+            FenceSignalFutureState::PartiallyFlushed(_, _) => unreachable!(),
+        };
+
+        // The waker must be registered before checking the fence, otherwise the waiting thread
+        // could wake up the previous waker between the check and the registration.
+        {
+            let mut waker = self.waker.lock().unwrap();
+            match *waker {
+                Some(ref waker) => {
+                    let mut waker = waker.lock().unwrap();
+                    if !waker.will_wake(cx.waker()) {
+                        *waker = cx.waker().clone();
+                    }
+                }
+                None => {
+                    let shared = Arc::new(Mutex::new(cx.waker().clone()));
+                    FenceWaiter::wake_when_signaled(fence.clone(), shared.clone());
+                    *waker = Some(shared);
+                }
+            }
+        }
+
+        match fence.wait(Some(Duration::from_secs(0))) {
+            Ok(()) => {
+                match mem::replace(&mut *state, FenceSignalFutureState::Cleaned) {
+                    FenceSignalFutureState::Flushed(previous, _) => unsafe {
+                        previous.signal_finished();
+                    },
+                    _ => unreachable!(),
+                }
+                Poll::Ready(Ok(()))
+            }
+            Err(FenceWaitError::Timeout) => Poll::Pending,
+            Err(err) => Poll::Ready(Err(err.into())),
+        }
+    }
+}
+
 impl<F> FenceSignalFutureState<F> {
     #[inline]
     fn get_prev(&self) -> Option<&F> {
@@ -514,3 +610,63 @@ where
         (**self).check_image_access(image, layout, exclusive, queue)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::command_buffer::AutoCommandBufferBuilder;
+    use crate::command_buffer::PrimaryCommandBuffer;
+    use crate::sync::GpuFuture;
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::Context;
+    use std::task::Poll;
+    use std::task::Wake;
+    use std::task::Waker;
+    use std::thread;
+    use std::thread::Thread;
+    use std::time::Duration;
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    #[test]
+    fn poll_multiple_futures() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut futures = (0..4)
+            .map(|_| {
+                let cb = AutoCommandBufferBuilder::primary_one_time_submit(
+                    device.clone(),
+                    queue.family(),
+                )
+                .unwrap()
+                .build()
+                .unwrap();
+                Box::pin(cb.execute(queue.clone()).unwrap().then_signal_fence())
+            })
+            .collect::<Vec<_>>();
+
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        while !futures.is_empty() {
+            let mut pending = Vec::new();
+            for mut future in futures.drain(..) {
+                match future.as_mut().poll(&mut cx) {
+                    Poll::Ready(result) => result.unwrap(),
+                    Poll::Pending => pending.push(future),
+                }
+            }
+            futures = pending;
+
+            if !futures.is_empty() {
+                thread::park_timeout(Duration::from_secs(1));
+            }
+        }
+    }
+}
@@ -267,7 +267,7 @@ pub unsafe trait GpuFuture: DeviceOwned {
 
     /// Same as `then_swapchain_present`, except it allows specifying a present region.
     ///
-    /// > **Note**: This is just a shortcut for the `Swapchain::present_incremental()` function.
+    /// > **Note**: This is just a shortcut for the `swapchain::present_incremental()` function.
     #[inline]
     fn then_swapchain_present_incremental<W>(
         self,
@@ -282,6 +282,24 @@ pub unsafe trait GpuFuture: DeviceOwned {
         swapchain::present_incremental(swapchain, self, queue, image_index, present_region)
     }
 
+    /// Same as `then_swapchain_present`, except that the present is tagged with an identifier
+    /// that can be waited upon with `Swapchain::wait_for_present`.
+    ///
+    /// > **Note**: This is just a shortcut for the `swapchain::present_with_id()` function.
+    #[inline]
+    fn then_swapchain_present_with_id<W>(
+        self,
+        queue: Arc<Queue>,
+        swapchain: Arc<Swapchain<W>>,
+        image_index: usize,
+        present_id: u64,
+    ) -> PresentFuture<Self, W>
+    where
+        Self: Sized,
+    {
+        swapchain::present_with_id(swapchain, self, queue, image_index, present_id)
+    }
+
     /// Turn the current future into a `Box<dyn GpuFuture>`.
     ///
     /// This is a helper function that calls `Box::new(yourFuture) as Box<dyn GpuFuture>`.
@@ -466,6 +484,10 @@ pub enum FlushError {
 
     /// The flush operation needed to block, but the timeout has elapsed.
     Timeout,
+
+    /// More than one of the command buffer submissions of a single queue submission has a
+    /// fence.
+    MultipleFences,
 }
 
 impl error::Error for FlushError {
@@ -498,6 +520,10 @@ impl fmt::Display for FlushError {
                     "the flush operation needed to block, but the timeout has \
                                     elapsed"
                 }
+                FlushError::MultipleFences => {
+                    "more than one of the command buffer submissions of a queue submission has a \
+                     fence"
+                }
             }
         )
     }
@@ -529,6 +555,7 @@ impl From<SubmitCommandBufferError> for FlushError {
         match err {
             SubmitCommandBufferError::OomError(err) => FlushError::OomError(err),
             SubmitCommandBufferError::DeviceLost => FlushError::DeviceLost,
+            SubmitCommandBufferError::MultipleFences => FlushError::MultipleFences,
         }
     }
 }
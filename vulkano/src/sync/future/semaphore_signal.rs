@@ -15,6 +15,7 @@ use std::sync::Mutex;
 use crate::buffer::BufferAccess;
 use crate::command_buffer::submit::SubmitAnyBuilder;
 use crate::command_buffer::submit::SubmitCommandBufferBuilder;
+use crate::command_buffer::submit::SubmitScheduler;
 use crate::command_buffer::submit::SubmitSemaphoresWaitBuilder;
 use crate::device::Device;
 use crate::device::DeviceOwned;
@@ -91,21 +92,22 @@ where
 
             let queue = self.previous.queue().unwrap().clone();
 
+            let mut scheduler = SubmitScheduler::new();
             match self.previous.build_submission()? {
                 SubmitAnyBuilder::Empty => {
                     let mut builder = SubmitCommandBufferBuilder::new();
                     builder.add_signal_semaphore(&self.semaphore);
-                    builder.submit(&queue)?;
+                    scheduler.add_command_buffers(builder);
                 }
                 SubmitAnyBuilder::SemaphoresWait(sem) => {
                     let mut builder: SubmitCommandBufferBuilder = sem.into();
                     builder.add_signal_semaphore(&self.semaphore);
-                    builder.submit(&queue)?;
+                    scheduler.add_command_buffers(builder);
                 }
                 SubmitAnyBuilder::CommandBuffer(mut builder) => {
                     debug_assert_eq!(builder.num_signal_semaphores(), 0);
                     builder.add_signal_semaphore(&self.semaphore);
-                    builder.submit(&queue)?;
+                    scheduler.add_command_buffers(builder);
                 }
                 SubmitAnyBuilder::BindSparse(_) => {
                     unimplemented!() // TODO: how to do that?
@@ -114,12 +116,13 @@ where
                                      builder.submit(&queue)?;*/
                 }
                 SubmitAnyBuilder::QueuePresent(present) => {
-                    present.submit(&queue)?;
+                    scheduler.add_present(present);
                     let mut builder = SubmitCommandBufferBuilder::new();
                     builder.add_signal_semaphore(&self.semaphore);
-                    builder.submit(&queue)?; // FIXME: problematic because if we return an error and flush() is called again, then we'll submit the present twice
+                    scheduler.add_command_buffers(builder); // FIXME: problematic because if we return an error and flush() is called again, then we'll submit the present twice
                 }
             };
+            scheduler.flush(&queue)?;
 
             // Only write `true` here in order to try again next time if an error occurs.
             *wait_submitted = true;
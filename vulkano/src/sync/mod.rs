@@ -107,7 +107,12 @@ use crate::device::Queue;
 use std::sync::Arc;
 
 pub use self::event::Event;
+pub use self::external_fence_handle_type::ExternalFenceHandleType;
+pub use self::external_semaphore_handle_type::ExternalSemaphoreHandleType;
+pub use self::fence::ExternalFenceError;
 pub use self::fence::Fence;
+pub(crate) use self::fence::FencePool;
+pub(crate) use self::fence::FenceWaiter;
 pub use self::fence::FenceWaitError;
 pub use self::future::now;
 pub use self::future::AccessCheckError;
@@ -121,13 +126,19 @@ pub use self::future::SemaphoreSignalFuture;
 pub use self::pipeline::AccessFlagBits;
 pub use self::pipeline::PipelineMemoryAccess;
 pub use self::pipeline::PipelineStages;
+pub use self::semaphore::ExternalSemaphoreError;
 pub use self::semaphore::Semaphore;
+pub use self::timeline_semaphore::TimelineSemaphore;
+pub use self::timeline_semaphore::TimelineSemaphoreError;
 
 mod event;
+mod external_fence_handle_type;
+mod external_semaphore_handle_type;
 mod fence;
 mod future;
 mod pipeline;
 mod semaphore;
+mod timeline_semaphore;
 
 /// Declares in which queue(s) a resource can be used.
 ///
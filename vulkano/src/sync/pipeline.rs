@@ -38,6 +38,15 @@ macro_rules! pipeline_stages {
                 )+
                 result
             }
+
+            // Returns the names of the fields that are set, for logging purposes.
+            pub(crate) fn names(&self) -> Vec<&'static str> {
+                let mut result = Vec::new();
+                $(
+                    if self.$elem { result.push(stringify!($elem)) }
+                )+
+                result
+            }
         }
 
         impl ops::BitOr for PipelineStages {
@@ -121,6 +130,15 @@ macro_rules! access_flags {
                 )+
                 result
             }
+
+            // Returns the names of the fields that are set, for logging purposes.
+            pub(crate) fn names(&self) -> Vec<&'static str> {
+                let mut result = Vec::new();
+                $(
+                    if self.$elem { result.push(stringify!($elem)) }
+                )+
+                result
+            }
         }
 
         impl ops::BitOr for AccessFlagBits {
@@ -7,14 +7,26 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::error;
+use std::fmt;
+#[cfg(target_os = "linux")]
+use std::fs::File;
 use std::mem::MaybeUninit;
+#[cfg(windows)]
+use std::os::raw::c_void;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::FromRawFd;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::IntoRawFd;
 use std::ptr;
 use std::sync::Arc;
 
 use crate::check_errors;
 use crate::device::Device;
 use crate::device::DeviceOwned;
+use crate::sync::ExternalSemaphoreHandleType;
 use crate::vk;
+use crate::Error;
 use crate::OomError;
 use crate::SafeDeref;
 use crate::VulkanObject;
@@ -31,6 +43,7 @@ where
     semaphore: vk::Semaphore,
     device: D,
     must_put_in_pool: bool,
+    export_handle_types: ExternalSemaphoreHandleType,
 }
 
 impl<D> Semaphore<D>
@@ -44,12 +57,13 @@ where
     /// For most applications, using the pool should be preferred,
     /// in order to avoid creating new semaphores every frame.
     pub fn from_pool(device: D) -> Result<Semaphore<D>, OomError> {
-        let maybe_raw_sem = device.semaphore_pool().lock().unwrap().pop();
+        let maybe_raw_sem = device.semaphore_pool().pop();
         match maybe_raw_sem {
             Some(raw_sem) => Ok(Semaphore {
                 device: device,
                 semaphore: raw_sem,
                 must_put_in_pool: true,
+                export_handle_types: ExternalSemaphoreHandleType::none(),
             }),
             None => {
                 // Pool is empty, alloc new semaphore
@@ -64,6 +78,305 @@ where
         Semaphore::alloc_impl(device, false)
     }
 
+    /// Builds a new semaphore whose payload can be exported with the given handle types, for
+    /// example to share it with another API or another process.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_semaphore` extension isn't enabled on the device.
+    ///
+    pub fn alloc_with_exportable(
+        device: D,
+        handle_types: ExternalSemaphoreHandleType,
+    ) -> Result<Semaphore<D>, OomError> {
+        assert!(
+            device.loaded_extensions_or_core().khr_external_semaphore,
+            "the khr_external_semaphore extension must be enabled"
+        );
+
+        let semaphore = unsafe {
+            let export_info = vk::ExportSemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_EXPORT_SEMAPHORE_CREATE_INFO,
+                pNext: ptr::null(),
+                handleTypes: handle_types.to_bits(),
+            };
+
+            let infos = vk::SemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO,
+                pNext: &export_info as *const _ as *const _,
+                flags: 0, // reserved
+            };
+
+            let vk = device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateSemaphore(
+                device.internal_object(),
+                &infos,
+                device.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(Semaphore {
+            device,
+            semaphore,
+            must_put_in_pool: false,
+            export_handle_types: handle_types,
+        })
+    }
+
+    /// Makes the semaphore destroyed instead of put back into the pool when it is dropped, for
+    /// example because it is left in the signaled state.
+    #[inline]
+    pub(crate) fn remove_from_pool(&mut self) {
+        self.must_put_in_pool = false;
+    }
+
+    /// Returns the handle types that the semaphore can be exported to.
+    #[inline]
+    pub fn export_handle_types(&self) -> ExternalSemaphoreHandleType {
+        self.export_handle_types
+    }
+
+    /// Exports the payload of the semaphore into a Unix file descriptor. The caller retains
+    /// ownership of the file, as per the Vulkan spec.
+    ///
+    /// `handle_type` must be either `opaque_fd` or `sync_fd`. Exporting a `sync_fd` handle
+    /// consumes the payload of the semaphore, as if a wait operation had been performed on it,
+    /// and can return `None` if the semaphore is already signaled.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_semaphore_fd` extension isn't enabled on the device.
+    /// - Panics if `handle_type` isn't a single POSIX file descriptor handle type, or wasn't
+    ///   passed to `alloc_with_exportable`.
+    ///
+    /// # Safety
+    ///
+    /// - If `handle_type` is `sync_fd`, the semaphore must be signaled, or have a signal
+    ///   operation pending in a queue.
+    ///
+    #[cfg(target_os = "linux")]
+    pub unsafe fn export_fd(
+        &self,
+        handle_type: ExternalSemaphoreHandleType,
+    ) -> Result<Option<File>, ExternalSemaphoreError> {
+        assert!(
+            self.device.loaded_extensions().khr_external_semaphore_fd,
+            "the khr_external_semaphore_fd extension must be enabled"
+        );
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT
+                || bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT,
+            "the handle type must be either opaque_fd or sync_fd"
+        );
+        assert!(
+            bits & self.export_handle_types.to_bits() != 0,
+            "the semaphore wasn't created as exportable to this handle type"
+        );
+
+        let info = vk::SemaphoreGetFdInfoKHR {
+            sType: vk::STRUCTURE_TYPE_SEMAPHORE_GET_FD_INFO_KHR,
+            pNext: ptr::null(),
+            semaphore: self.semaphore,
+            handleType: bits,
+        };
+
+        let vk = self.device.pointers();
+        let mut output = MaybeUninit::uninit();
+        check_errors(vk.GetSemaphoreFdKHR(
+            self.device.internal_object(),
+            &info,
+            output.as_mut_ptr(),
+        ))?;
+
+        match output.assume_init() {
+            -1 => Ok(None),
+            fd => Ok(Some(File::from_raw_fd(fd))),
+        }
+    }
+
+    /// Imports a payload into the semaphore from a Unix file descriptor. On success, the
+    /// implementation takes ownership of the file.
+    ///
+    /// If `temporary` is true, the payload is only used until the next wait operation on the
+    /// semaphore, after which the semaphore goes back to its own payload. `sync_fd` payloads
+    /// can only be imported temporarily.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_semaphore_fd` extension isn't enabled on the device.
+    /// - Panics if `handle_type` isn't a single POSIX file descriptor handle type.
+    /// - Panics if `handle_type` is `sync_fd` and `temporary` is false.
+    ///
+    /// # Safety
+    ///
+    /// - The file must hold a payload of the given handle type, exported from a semaphore that
+    ///   is compatible with this device.
+    /// - The semaphore must not be in use by the GPU.
+    ///
+    #[cfg(target_os = "linux")]
+    pub unsafe fn import_fd(
+        &mut self,
+        handle_type: ExternalSemaphoreHandleType,
+        file: File,
+        temporary: bool,
+    ) -> Result<(), ExternalSemaphoreError> {
+        assert!(
+            self.device.loaded_extensions().khr_external_semaphore_fd,
+            "the khr_external_semaphore_fd extension must be enabled"
+        );
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT
+                || bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT,
+            "the handle type must be either opaque_fd or sync_fd"
+        );
+        assert!(
+            temporary || bits != vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT,
+            "sync_fd payloads can only be imported temporarily"
+        );
+
+        let fd = file.into_raw_fd();
+        let info = vk::ImportSemaphoreFdInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_SEMAPHORE_FD_INFO_KHR,
+            pNext: ptr::null(),
+            semaphore: self.semaphore,
+            flags: if temporary {
+                vk::SEMAPHORE_IMPORT_TEMPORARY_BIT
+            } else {
+                0
+            },
+            handleType: bits,
+            fd,
+        };
+
+        let vk = self.device.pointers();
+        if let Err(err) =
+            check_errors(vk.ImportSemaphoreFdKHR(self.device.internal_object(), &info))
+        {
+            // The implementation only takes ownership of the file on success.
+            drop(File::from_raw_fd(fd));
+            return Err(err.into());
+        }
+
+        // The semaphore no longer has the payload of a freshly-created semaphore.
+        self.must_put_in_pool = false;
+        Ok(())
+    }
+
+    /// Exports the payload of the semaphore into a Windows handle.
+    ///
+    /// `handle_type` must be one of `opaque_win32`, `opaque_win32_kmt` or `d3d12_fence`. The
+    /// caller owns the returned handle and must close it with `CloseHandle`, except for
+    /// `opaque_win32_kmt` handles.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_semaphore_win32` extension isn't enabled on the device.
+    /// - Panics if `handle_type` isn't a single Windows handle type, or wasn't passed to
+    ///   `alloc_with_exportable`.
+    ///
+    #[cfg(windows)]
+    pub fn export_win32_handle(
+        &self,
+        handle_type: ExternalSemaphoreHandleType,
+    ) -> Result<*mut c_void, ExternalSemaphoreError> {
+        assert!(
+            self.device.loaded_extensions().khr_external_semaphore_win32,
+            "the khr_external_semaphore_win32 extension must be enabled"
+        );
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT
+                || bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT
+                || bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT,
+            "the handle type must be one of opaque_win32, opaque_win32_kmt or d3d12_fence"
+        );
+        assert!(
+            bits & self.export_handle_types.to_bits() != 0,
+            "the semaphore wasn't created as exportable to this handle type"
+        );
+
+        unsafe {
+            let info = vk::SemaphoreGetWin32HandleInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_GET_WIN32_HANDLE_INFO_KHR,
+                pNext: ptr::null(),
+                semaphore: self.semaphore,
+                handleType: bits,
+            };
+
+            let vk = self.device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.GetSemaphoreWin32HandleKHR(
+                self.device.internal_object(),
+                &info,
+                output.as_mut_ptr(),
+            ))?;
+            Ok(output.assume_init())
+        }
+    }
+
+    /// Imports a payload into the semaphore from a Windows handle. The caller keeps the
+    /// ownership of the handle.
+    ///
+    /// If `temporary` is true, the payload is only used until the next wait operation on the
+    /// semaphore, after which the semaphore goes back to its own payload.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_external_semaphore_win32` extension isn't enabled on the device.
+    /// - Panics if `handle_type` isn't a single Windows handle type.
+    ///
+    /// # Safety
+    ///
+    /// - The handle must hold a payload of the given handle type, exported from a semaphore that
+    ///   is compatible with this device.
+    /// - The semaphore must not be in use by the GPU.
+    ///
+    #[cfg(windows)]
+    pub unsafe fn import_win32_handle(
+        &mut self,
+        handle_type: ExternalSemaphoreHandleType,
+        handle: *mut c_void,
+        temporary: bool,
+    ) -> Result<(), ExternalSemaphoreError> {
+        assert!(
+            self.device.loaded_extensions().khr_external_semaphore_win32,
+            "the khr_external_semaphore_win32 extension must be enabled"
+        );
+        let bits = handle_type.to_bits();
+        assert!(
+            bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT
+                || bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT
+                || bits == vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT,
+            "the handle type must be one of opaque_win32, opaque_win32_kmt or d3d12_fence"
+        );
+
+        let info = vk::ImportSemaphoreWin32HandleInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_SEMAPHORE_WIN32_HANDLE_INFO_KHR,
+            pNext: ptr::null(),
+            semaphore: self.semaphore,
+            flags: if temporary {
+                vk::SEMAPHORE_IMPORT_TEMPORARY_BIT
+            } else {
+                0
+            },
+            handleType: bits,
+            handle,
+            name: ptr::null(),
+        };
+
+        let vk = self.device.pointers();
+        check_errors(vk.ImportSemaphoreWin32HandleKHR(self.device.internal_object(), &info))?;
+
+        // The semaphore no longer has the payload of a freshly-created semaphore.
+        self.must_put_in_pool = false;
+        Ok(())
+    }
+
     fn alloc_impl(device: D, must_put_in_pool: bool) -> Result<Semaphore<D>, OomError> {
         let semaphore = unsafe {
             // since the creation is constant, we use a `static` instead of a struct on the stack
@@ -78,7 +391,7 @@ where
             check_errors(vk.CreateSemaphore(
                 device.internal_object(),
                 &INFOS,
-                ptr::null(),
+                device.alloc_ptr(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -88,6 +401,7 @@ where
             device: device,
             semaphore: semaphore,
             must_put_in_pool: must_put_in_pool,
+            export_handle_types: ExternalSemaphoreHandleType::none(),
         })
     }
 }
@@ -122,17 +436,82 @@ where
         unsafe {
             if self.must_put_in_pool {
                 let raw_sem = self.semaphore;
-                self.device.semaphore_pool().lock().unwrap().push(raw_sem);
+                self.device.semaphore_pool().push(raw_sem);
             } else {
                 let vk = self.device.pointers();
-                vk.DestroySemaphore(self.device.internal_object(), self.semaphore, ptr::null());
+                vk.DestroySemaphore(
+                    self.device.internal_object(),
+                    self.semaphore,
+                    self.device.alloc_ptr(),
+                );
+            }
+        }
+    }
+}
+
+/// Error that can happen when exporting or importing the payload of a semaphore.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExternalSemaphoreError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// Too many handles have been exported.
+    TooManyObjects,
+
+    /// The handle to import isn't valid for the given handle type.
+    InvalidExternalHandle,
+}
+
+impl error::Error for ExternalSemaphoreError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ExternalSemaphoreError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ExternalSemaphoreError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                ExternalSemaphoreError::OomError(_) => "not enough memory",
+                ExternalSemaphoreError::TooManyObjects => "too many handles have been exported",
+                ExternalSemaphoreError::InvalidExternalHandle => {
+                    "the handle isn't valid for the given handle type"
+                }
             }
+        )
+    }
+}
+
+impl From<Error> for ExternalSemaphoreError {
+    #[inline]
+    fn from(err: Error) -> ExternalSemaphoreError {
+        match err {
+            err @ Error::OutOfHostMemory => ExternalSemaphoreError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => ExternalSemaphoreError::OomError(OomError::from(err)),
+            Error::TooManyObjects => ExternalSemaphoreError::TooManyObjects,
+            Error::InvalidExternalHandle => ExternalSemaphoreError::InvalidExternalHandle,
+            _ => panic!("unexpected error: {:?}", err),
         }
     }
 }
 
+impl From<OomError> for ExternalSemaphoreError {
+    #[inline]
+    fn from(err: OomError) -> ExternalSemaphoreError {
+        ExternalSemaphoreError::OomError(err)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::sync::ExternalSemaphoreHandleType;
     use crate::sync::Semaphore;
     use crate::VulkanObject;
 
@@ -146,16 +525,38 @@ mod tests {
     fn semaphore_pool() {
         let (device, _) = gfx_dev_and_queue!();
 
-        assert_eq!(device.semaphore_pool().lock().unwrap().len(), 0);
+        assert_eq!(device.semaphore_pool().len(), 0);
         let sem1_internal_obj = {
             let sem = Semaphore::from_pool(device.clone()).unwrap();
-            assert_eq!(device.semaphore_pool().lock().unwrap().len(), 0);
+            assert_eq!(device.semaphore_pool().len(), 0);
             sem.internal_object()
         };
 
-        assert_eq!(device.semaphore_pool().lock().unwrap().len(), 1);
+        assert_eq!(device.semaphore_pool().len(), 1);
         let sem2 = Semaphore::from_pool(device.clone()).unwrap();
-        assert_eq!(device.semaphore_pool().lock().unwrap().len(), 0);
+        assert_eq!(device.semaphore_pool().len(), 0);
         assert_eq!(sem2.internal_object(), sem1_internal_obj);
     }
+
+    #[test]
+    fn removed_from_pool() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut sem = Semaphore::from_pool(device.clone()).unwrap();
+        sem.remove_from_pool();
+        drop(sem);
+        assert_eq!(device.semaphore_pool().len(), 0);
+    }
+
+    #[test]
+    fn exportable_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!("the khr_external_semaphore extension must be enabled", {
+            let _ = Semaphore::alloc_with_exportable(
+                device.clone(),
+                ExternalSemaphoreHandleType::posix(),
+            );
+        });
+    }
 }
@@ -0,0 +1,341 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::error;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::check_errors;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::vk;
+use crate::Error;
+use crate::OomError;
+use crate::SafeDeref;
+use crate::Success;
+use crate::VulkanObject;
+
+/// A semaphore that holds a 64-bits counter instead of a signaled state.
+///
+/// Each submission can wait for the counter to reach a value and set the counter to a new value
+/// when it ends, and the CPU can query, signal and wait for the counter as well. The value of the
+/// counter can only ever increase.
+///
+/// Contrary to binary semaphores, any number of submissions can wait for the same value, and
+/// waiting doesn't reset the semaphore. This makes it possible to synchronize multiple queues with
+/// a single semaphore per queue instead of a chain of semaphores and fences.
+///
+/// The `khr_timeline_semaphore` extension and the `timeline_semaphore` feature must be enabled on
+/// the device.
+#[derive(Debug)]
+pub struct TimelineSemaphore<D = Arc<Device>>
+where
+    D: SafeDeref<Target = Device>,
+{
+    semaphore: vk::Semaphore,
+    device: D,
+}
+
+impl<D> TimelineSemaphore<D>
+where
+    D: SafeDeref<Target = Device>,
+{
+    /// Builds a new timeline semaphore whose counter starts at `initial_value`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `khr_timeline_semaphore` extension or the `timeline_semaphore` feature
+    ///   isn't enabled on the device.
+    ///
+    pub fn new(device: D, initial_value: u64) -> Result<TimelineSemaphore<D>, OomError> {
+        assert!(
+            device.loaded_extensions_or_core().khr_timeline_semaphore
+                && device.enabled_features().timeline_semaphore,
+            "the `khr_timeline_semaphore` extension and the `timeline_semaphore` feature must be \
+             enabled"
+        );
+
+        let semaphore = unsafe {
+            let type_info = vk::SemaphoreTypeCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_TYPE_CREATE_INFO,
+                pNext: ptr::null(),
+                semaphoreType: vk::SEMAPHORE_TYPE_TIMELINE,
+                initialValue: initial_value,
+            };
+
+            let infos = vk::SemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO,
+                pNext: &type_info as *const _ as *const _,
+                flags: 0, // reserved
+            };
+
+            let vk = device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateSemaphore(
+                device.internal_object(),
+                &infos,
+                device.alloc_ptr(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(TimelineSemaphore { semaphore, device })
+    }
+
+    /// Returns the current value of the counter.
+    pub fn value(&self) -> Result<u64, TimelineSemaphoreError> {
+        unsafe {
+            let vk = self.device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.GetSemaphoreCounterValueKHR(
+                self.device.internal_object(),
+                self.semaphore,
+                output.as_mut_ptr(),
+            ))?;
+            Ok(output.assume_init())
+        }
+    }
+
+    /// Sets the counter to `value` from the CPU.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `value` is not strictly greater than the current value of the counter.
+    ///
+    /// # Safety
+    ///
+    /// - `value` must be smaller than the values of all the signal operations that are pending on
+    ///   the GPU for this semaphore.
+    ///
+    pub unsafe fn signal(&self, value: u64) -> Result<(), TimelineSemaphoreError> {
+        assert!(
+            value > self.value()?,
+            "the value of a timeline semaphore can only increase"
+        );
+
+        let infos = vk::SemaphoreSignalInfo {
+            sType: vk::STRUCTURE_TYPE_SEMAPHORE_SIGNAL_INFO,
+            pNext: ptr::null(),
+            semaphore: self.semaphore,
+            value,
+        };
+
+        let vk = self.device.pointers();
+        check_errors(vk.SignalSemaphoreKHR(self.device.internal_object(), &infos))?;
+        Ok(())
+    }
+
+    /// Waits until the counter is greater than or equal to `value`, or at least until the timeout
+    /// duration has elapsed.
+    ///
+    /// Returns `Ok` if the counter has reached the value. Returns `Err` if the timeout was reached
+    /// instead.
+    ///
+    /// If you pass a duration of 0, then the function will return without blocking.
+    #[inline]
+    pub fn wait(
+        &self,
+        value: u64,
+        timeout: Option<Duration>,
+    ) -> Result<(), TimelineSemaphoreError> {
+        TimelineSemaphore::multi_wait(Some((self, value)), false, timeout)
+    }
+
+    /// Waits for multiple semaphores at once.
+    ///
+    /// If `wait_any` is true, returns as soon as one of the semaphores has reached its value.
+    /// Otherwise, waits for all of them.
+    ///
+    /// # Panic
+    ///
+    /// Panics if not all semaphores belong to the same device.
+    pub fn multi_wait<'a, I>(
+        iter: I,
+        wait_any: bool,
+        timeout: Option<Duration>,
+    ) -> Result<(), TimelineSemaphoreError>
+    where
+        I: IntoIterator<Item = (&'a TimelineSemaphore<D>, u64)>,
+        D: 'a,
+    {
+        let mut device: Option<&Device> = None;
+
+        let (semaphores, values): (SmallVec<[vk::Semaphore; 8]>, SmallVec<[u64; 8]>) = iter
+            .into_iter()
+            .map(|(semaphore, value)| {
+                match &mut device {
+                    dev @ &mut None => *dev = Some(&*semaphore.device),
+                    &mut Some(dev) if ptr::eq(dev, &*semaphore.device) => {}
+                    _ => panic!(
+                        "Tried to wait for multiple semaphores that didn't belong to the same \
+                         device"
+                    ),
+                };
+
+                (semaphore.semaphore, value)
+            })
+            .unzip();
+
+        let device = match device {
+            Some(device) => device,
+            None => return Ok(()),
+        };
+
+        let timeout_ns = if let Some(timeout) = timeout {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        } else {
+            u64::MAX
+        };
+
+        let infos = vk::SemaphoreWaitInfo {
+            sType: vk::STRUCTURE_TYPE_SEMAPHORE_WAIT_INFO,
+            pNext: ptr::null(),
+            flags: if wait_any {
+                vk::SEMAPHORE_WAIT_ANY_BIT
+            } else {
+                0
+            },
+            semaphoreCount: semaphores.len() as u32,
+            pSemaphores: semaphores.as_ptr(),
+            pValues: values.as_ptr(),
+        };
+
+        let r = unsafe {
+            let vk = device.pointers();
+            check_errors(vk.WaitSemaphoresKHR(device.internal_object(), &infos, timeout_ns))?
+        };
+
+        match r {
+            Success::Success => Ok(()),
+            Success::Timeout => Err(TimelineSemaphoreError::Timeout),
+            _ => unreachable!(),
+        }
+    }
+}
+
+unsafe impl DeviceOwned for TimelineSemaphore {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl<D> VulkanObject for TimelineSemaphore<D>
+where
+    D: SafeDeref<Target = Device>,
+{
+    type Object = vk::Semaphore;
+
+    const TYPE: vk::ObjectType = vk::OBJECT_TYPE_SEMAPHORE;
+
+    #[inline]
+    fn internal_object(&self) -> vk::Semaphore {
+        self.semaphore
+    }
+}
+
+impl<D> Drop for TimelineSemaphore<D>
+where
+    D: SafeDeref<Target = Device>,
+{
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroySemaphore(
+                self.device.internal_object(),
+                self.semaphore,
+                self.device.alloc_ptr(),
+            );
+        }
+    }
+}
+
+/// Error that can be returned when querying, signaling or waiting on a timeline semaphore.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimelineSemaphoreError {
+    /// Not enough memory to complete the operation.
+    OomError(OomError),
+
+    /// The specified timeout wasn't long enough.
+    Timeout,
+
+    /// The device has been lost.
+    DeviceLost,
+}
+
+impl error::Error for TimelineSemaphoreError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            TimelineSemaphoreError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimelineSemaphoreError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                TimelineSemaphoreError::OomError(_) => "no memory available",
+                TimelineSemaphoreError::Timeout => "the timeout has been reached",
+                TimelineSemaphoreError::DeviceLost => "the device was lost",
+            }
+        )
+    }
+}
+
+impl From<Error> for TimelineSemaphoreError {
+    #[inline]
+    fn from(err: Error) -> TimelineSemaphoreError {
+        match err {
+            err @ Error::OutOfHostMemory => TimelineSemaphoreError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => TimelineSemaphoreError::OomError(OomError::from(err)),
+            Error::DeviceLost => TimelineSemaphoreError::DeviceLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for TimelineSemaphoreError {
+    #[inline]
+    fn from(err: OomError) -> TimelineSemaphoreError {
+        TimelineSemaphoreError::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sync::TimelineSemaphore;
+
+    #[test]
+    fn missing_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!(
+            "the `khr_timeline_semaphore` extension and the `timeline_semaphore` feature must be \
+             enabled",
+            {
+                let _ = TimelineSemaphore::new(device.clone(), 0);
+            }
+        );
+    }
+}
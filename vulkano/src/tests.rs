@@ -22,8 +22,12 @@ macro_rules! instance {
 }
 
 /// Creates a device and a queue for graphics operations.
+///
+/// The features to enable can be followed by `;` and a list of device extensions to enable.
+/// Returns if the device doesn't support them.
 macro_rules! gfx_dev_and_queue {
-    ($($feature:ident),*) => ({
+    ($($feature:ident),*) => (gfx_dev_and_queue!($($feature),*;));
+    ($($feature:ident),*; $($extension:ident),*) => ({
         use crate::instance;
         use crate::device::Device;
         use crate::device::DeviceExtensions;
@@ -41,7 +45,12 @@ macro_rules! gfx_dev_and_queue {
             None => return
         };
 
-        let extensions = DeviceExtensions::none();
+        let extensions = DeviceExtensions {
+            $(
+                $extension: true,
+            )*
+            .. DeviceExtensions::none()
+        };
 
         let features = Features {
             $(